@@ -0,0 +1,153 @@
+//! Benchmarks for the hot paths: age encryption, dotenv parsing,
+//! and environment resolution.
+//!
+//! Run with `cargo bench`. Compare against a saved baseline with
+//! `cargo bench -- --save-baseline main` / `--baseline main`.
+
+use std::collections::HashMap;
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+
+use vaultic::adapters::cipher::age_backend::AgeBackend;
+use vaultic::adapters::parsers::dotenv_parser::DotenvParser;
+use vaultic::config::app_config::{AppConfig, EnvEntry, VaulticSection};
+use vaultic::core::models::key_identity::KeyIdentity;
+use vaultic::core::models::secret_file::SecretFile;
+use vaultic::core::services::env_resolver::EnvResolver;
+use vaultic::core::traits::cipher::CipherBackend;
+use vaultic::core::traits::parser::ConfigParser;
+
+/// Build a dotenv document with `lines` variables (plus a comment every 10 lines).
+fn dotenv_content(lines: usize) -> String {
+    let mut out = String::new();
+    for i in 0..lines {
+        if i % 10 == 0 {
+            out.push_str(&format!("# Section {i}\n"));
+        }
+        out.push_str(&format!(
+            "KEY_{i}=\"value-{i}-abcdefghijklmnopqrstuvwxyz\"\n"
+        ));
+    }
+    out
+}
+
+fn bench_age(c: &mut Criterion) {
+    let dir = tempfile::tempdir().unwrap();
+    let key_path = dir.path().join("keys.txt");
+    let public_key = AgeBackend::generate_identity(&key_path).unwrap();
+    let backend = AgeBackend::new(key_path);
+    let recipients = [KeyIdentity {
+        public_key,
+        label: None,
+        added_at: None,
+    }];
+
+    let mut group = c.benchmark_group("age");
+    for size in [1024usize, 64 * 1024, 1024 * 1024] {
+        let plaintext = vec![b'x'; size];
+        let ciphertext = backend.encrypt(&plaintext, &recipients).unwrap();
+        group.throughput(Throughput::Bytes(size as u64));
+
+        group.bench_with_input(BenchmarkId::new("encrypt", size), &plaintext, |b, p| {
+            b.iter(|| backend.encrypt(black_box(p), &recipients).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("decrypt", size), &ciphertext, |b, ct| {
+            b.iter(|| backend.decrypt(black_box(ct)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_dotenv(c: &mut Criterion) {
+    let parser = DotenvParser;
+
+    let mut group = c.benchmark_group("dotenv");
+    for lines in [100usize, 1_000, 10_000] {
+        let content = dotenv_content(lines);
+        let parsed = parser.parse(&content).unwrap();
+        group.throughput(Throughput::Bytes(content.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("parse", lines), &content, |b, c| {
+            b.iter(|| parser.parse(black_box(c)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("serialize", lines), &parsed, |b, f| {
+            b.iter(|| parser.serialize(black_box(f)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+/// Config with a linear chain `env_0 <- env_1 <- ... <- env_{depth-1}`.
+fn chain_config(depth: usize) -> AppConfig {
+    let mut environments = HashMap::new();
+    for i in 0..depth {
+        environments.insert(
+            format!("env_{i}"),
+            EnvEntry {
+                file: None,
+                inherits: (i > 0).then(|| format!("env_{}", i - 1)),
+                template: None,
+                format: None,
+                mirror: None,
+                review: false,
+                threshold: None,
+            },
+        );
+    }
+    AppConfig {
+        vaultic: VaulticSection {
+            version: "0.1.0".to_string(),
+            format_version: 1,
+            default_cipher: "age".to_string(),
+            default_env: Some("env_0".to_string()),
+            template: None,
+            identity: None,
+            rotation_days: None,
+            storage: Default::default(),
+            extends_parent: false,
+        },
+        environments,
+        audit: None,
+        validation: None,
+        owners: None,
+        test_env: None,
+        recipients: None,
+        aliases: None,
+        outputs: None,
+        history: None,
+        ui: None,
+        references: None,
+        leases: None,
+        gpg: None,
+        hooks: None,
+        policy: None,
+    }
+}
+
+fn bench_resolver(c: &mut Criterion) {
+    let parser = DotenvParser;
+    let resolver = EnvResolver;
+
+    let mut group = c.benchmark_group("env_resolver");
+    for depth in [2usize, 8, 32] {
+        let config = chain_config(depth);
+        let files: HashMap<String, SecretFile> = (0..depth)
+            .map(|i| {
+                (
+                    format!("env_{i}"),
+                    parser.parse(&dotenv_content(200)).unwrap(),
+                )
+            })
+            .collect();
+        let leaf = format!("env_{}", depth - 1);
+
+        group.bench_with_input(BenchmarkId::new("resolve", depth), &leaf, |b, leaf| {
+            b.iter(|| resolver.resolve(black_box(leaf), &config, &files).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_age, bench_dotenv, bench_resolver);
+criterion_main!(benches);
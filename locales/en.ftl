@@ -0,0 +1,46 @@
+# Vaultic user-facing messages — English (reference catalog).
+#
+# Error messages are not listed here: their English text lives on
+# `VaulticError` itself. Other catalogs translate them under
+# `error-<variant>` ids; see src/cli/i18n.rs.
+
+## Output
+
+error-label = Error
+confirm-hint-default-yes = [Y/n]
+confirm-hint-default-no = [y/N]
+# Comma-separated answers accepted as "yes" by confirmation prompts.
+confirm-yes-answers = y, yes
+input-required =
+    Input required: '{ $prompt }'
+
+      This step cannot be answered automatically. Run it without --yes / --no-input.
+input-no-terminal =
+    No terminal to answer '{ $prompt }'
+
+      Pass --yes to accept, or --no-input to take the defaults.
+update-available = New version available: v{ $version }. Run 'vaultic update' to upgrade.
+json-unsupported = --json is only supported by status, check, diff, log and keys list
+
+## First run
+
+not-initialized-config =
+    Vaultic not initialized: { $path } does not exist.
+
+      Check the --config path, or run 'vaultic init' in the project root.
+not-initialized = Vaultic not initialized in this directory.
+not-initialized-parent =
+    Found a Vaultic project in a parent directory: { $parent }
+
+      Solutions:
+        → Run the command from there: cd { $parent }
+        → Or point at it: vaultic --config { $config } <command>
+        → Or start a separate project here: vaultic init
+not-initialized-found-here = Found here: { $files }
+not-initialized-git-repository = git repository
+not-initialized-solutions =
+    Solutions:
+      → Start a project here: vaultic init
+      → { $next }
+not-initialized-next-existing = Then encrypt your existing .env: vaultic encrypt --env dev
+not-initialized-next-new = Then put your secrets in .env and run: vaultic encrypt --env dev
@@ -0,0 +1,237 @@
+# Mensajes de Vaultic — español.
+
+## Output
+
+error-label = Error
+confirm-hint-default-yes = [S/n]
+confirm-hint-default-no = [s/N]
+confirm-yes-answers = s, si, sí, y, yes
+input-required =
+    Se requiere una respuesta: '{ $prompt }'
+
+      Este paso no se puede responder automáticamente. Ejecútalo sin --yes / --no-input.
+input-no-terminal =
+    No hay una terminal para responder '{ $prompt }'
+
+      Usa --yes para aceptar, o --no-input para tomar los valores por defecto.
+update-available = Nueva versión disponible: v{ $version }. Ejecuta 'vaultic update' para actualizar.
+json-unsupported = --json solo está disponible en status, check, diff, log y keys list
+
+## First run
+
+not-initialized-config =
+    Vaultic no está inicializado: { $path } no existe.
+
+      Revisa la ruta de --config, o ejecuta 'vaultic init' en la raíz del proyecto.
+not-initialized = Vaultic no está inicializado en este directorio.
+not-initialized-parent =
+    Hay un proyecto de Vaultic en un directorio superior: { $parent }
+
+      Soluciones:
+        → Ejecuta el comando desde allí: cd { $parent }
+        → O apunta a él: vaultic --config { $config } <comando>
+        → O crea un proyecto independiente aquí: vaultic init
+not-initialized-found-here = Encontrado aquí: { $files }
+not-initialized-git-repository = repositorio git
+not-initialized-solutions =
+    Soluciones:
+      → Crea un proyecto aquí: vaultic init
+      → { $next }
+not-initialized-next-existing = Después cifra tu .env existente: vaultic encrypt --env dev
+not-initialized-next-new = Después guarda tus secretos en .env y ejecuta: vaultic encrypt --env dev
+
+## Errors (translations of VaulticError)
+
+error-file-not-found =
+    Archivo no encontrado: { $path }
+
+      Comprueba que la ruta es correcta y que el archivo existe.
+      Ejecuta 'vaultic status' para ver los entornos y archivos disponibles.
+error-encryption-failed = Error al cifrar: { $reason }
+error-decryption-no-key =
+    Error al descifrar: no se encontró ninguna clave válida
+
+      Tu clave privada no está en la lista de destinatarios de este archivo.
+
+      Soluciones:
+        → Pide a un administrador que añada tu clave pública: vaultic keys add <tu-clave>
+        → Después vuelve a cifrar: vaultic encrypt --all
+        → Comprueba tu estado como destinatario: vaultic status
+error-armor-corrupted =
+    El archivo cifrado está dañado en la línea { $line }: { $reason }
+
+      Soluciones:
+        → Deshaz los cambios de saltos de línea y espacios: vaultic repair-armor
+        → O restaura el archivo desde git: git checkout -- .vaultic/
+error-shares-required =
+    Descifrar { $env } requiere { $need } partes, pero solo hay { $have } disponibles
+
+      { $env } está cifrado con umbral: ningún destinatario puede descifrarlo solo.
+
+      Soluciones:
+        → Pide a otros titulares que ejecuten: vaultic keys share release --env { $env } --to <tu-clave>
+        → Después descifra con sus archivos: vaultic decrypt --env { $env } --share <archivo>
+        → Consulta quién tiene partes: vaultic keys share status
+error-parse-error =
+    Error de formato en { $file }: { $detail }
+
+      Formato esperado: CLAVE=valor (uno por línea).
+      Se permiten comentarios (#) y líneas en blanco.
+error-environment-not-found =
+    No se encontró el entorno '{ $name }'
+
+      Entornos disponibles: { $available }
+      Revisa las definiciones de entornos en .vaultic/config.toml.
+error-circular-inheritance =
+    Herencia circular detectada: { $chain }
+
+      Dos o más entornos heredan entre sí, formando un ciclo.
+
+      Solución: edita .vaultic/config.toml para que la herencia forme un árbol:
+        → Válido:   base → dev, base → staging, base → prod
+        → Inválido: dev → staging → dev (ciclo)
+error-recipients-signature-invalid =
+    Falló la verificación de la firma de destinatarios: { $reason }
+
+      {"["}recipients] admin_keys en .vaultic/config.toml exige que recipients.txt (y cualquier recipients.<env>.txt) esté firmado por un administrador, para que los cambios de destinatarios sin revisar no tengan efecto.
+
+      Soluciones:
+        → Revisa los cambios recientes: git log -p .vaultic/recipients.txt
+        → Pide a un administrador que lo firme: minisign -Sm .vaultic/recipients.txt -x .vaultic/recipients.txt.sig
+error-key-not-found = La clave '{ $identity }' no está entre los destinatarios
+error-key-already-exists = La clave '{ $identity }' ya está entre los destinatarios
+error-invalid-config = Configuración no válida: { $detail }
+error-audit-error = Error en el registro de auditoría: { $detail }
+error-hook-error = Error en el hook de git: { $detail }
+error-update-check-failed =
+    No se pudo comprobar si hay actualizaciones: { $reason }
+
+      No es grave: tu versión actual sigue funcionando.
+      Inténtalo más tarde o consulta https://github.com/SoftDryzz/vaultic/releases
+error-update-verification-failed =
+    Falló la verificación de la actualización: { $reason }
+
+      No se pudo verificar el binario descargado y NO se ha instalado.
+      Tu instalación actual no ha cambiado.
+
+      Soluciones:
+        → Vuelve a intentarlo: vaultic update
+        → Descarga manual: https://github.com/SoftDryzz/vaultic/releases/latest
+        → Informa del problema: https://github.com/SoftDryzz/vaultic/issues
+error-update-failed =
+    Falló la actualización: { $reason }
+
+      No se pudo reemplazar el binario. Es posible que tu instalación actual esté intacta.
+
+      Soluciones:
+        → Vuelve a intentarlo: vaultic update
+        → Instalación manual: cargo install vaultic --force
+error-binary-integrity-failed =
+    Falló la verificación del binario: { $reason }
+
+      Soluciones:
+        → Reinstala el binario oficial: vaultic update, o descárgalo de
+          https://github.com/SoftDryzz/vaultic/releases
+        → Los binarios compilados desde el código (cargo install) nunca coinciden con el hash oficial
+error-unsupported-platform =
+    Plataforma no soportada por la actualización automática: { $platform }
+
+      No hay binarios precompilados para tu plataforma.
+
+      Soluciones:
+        → Instala desde el código: cargo install vaultic
+        → Compila manualmente: cargo build --release
+error-template-not-found =
+    No se encontró ninguna plantilla
+
+      Vaultic buscó:
+        { $searched }
+
+      Soluciones:
+        → Crea una plantilla: cp .env .env.template (y elimina los valores secretos)
+        → Indícala en .vaultic/config.toml:
+          {"["}vaultic]
+          template = "ruta/a/tu/plantilla"
+error-format-version-too-new =
+    Este proyecto usa la versión de formato { $project_version }, pero tu Vaultic solo admite hasta la versión { $supported_version }.
+
+      Soluciones:
+        → Actualiza Vaultic: vaultic update
+        → O instala la última versión: cargo install vaultic --force
+error-validation-failed = Validación fallida: { $count } regla(s) incumplida(s)
+error-secret-leaks-found =
+    Se encontraron { $count } valor(es) secreto(s) en archivos en texto plano
+
+      Soluciones:
+        → Elimina los valores de los archivos listados arriba
+        → Rota cada secreto filtrado: una vez commiteado, queda en el historial de git
+error-policy-violations =
+    Se encontraron { $count } valor(es) secreto(s) que incumplen las reglas de [policy]
+
+      Soluciones:
+        → Sustituye los valores cortos o de ejemplo y vuelve a cifrar
+        → Rota los entornos que superan max_age_days: vaultic encrypt --env <entorno>
+error-status-problems-found = Se encontraron { $count } problema(s) en el estado del proyecto
+error-verify-failed =
+    { $count } entorno(s) no pueden ser leídos exactamente por sus destinatarios
+
+      Soluciones:
+        → Vuelve a cifrar para los destinatarios actuales: vaultic encrypt --all
+        → Revisa la lista de destinatarios: vaultic keys list
+error-unrecorded-changes =
+    { $count } archivo(s) de .vaultic/ se modificaron fuera de Vaultic
+
+      Soluciones:
+        → Revisa el cambio: git diff .vaultic/
+        → Deshazlo: git checkout -- .vaultic/
+        → Consérvalo tras revisarlo: vaultic verify --accept-changes
+error-state-chain-broken =
+    { $env } se modificó fuera de Vaultic { $count } vez/veces
+
+      Su archivo cifrado no sigue los estados que Vaultic registró en .vaultic/manifest.toml.
+
+      Soluciones:
+        → Revisa el cambio: git log -p .vaultic/
+        → Deshazlo: git checkout -- .vaultic/
+        → Consérvalo tras revisarlo: vaultic verify --accept-changes
+error-template-check-failed = { $count } entorno(s) no coinciden con su plantilla
+error-reference-resolution-failed =
+    No se pudo resolver { $key } = { $reference }: { $reason }
+
+      Soluciones:
+        → Revisa el proveedor en [references] de .vaultic/config.toml
+        → Asegúrate de que su CLI está instalada y con la sesión iniciada
+        → O deja las referencias sin resolver: --keep-refs
+error-read-only-mirror =
+    El entorno '{ $name }' es un espejo de solo lectura de { $origin }
+
+      Cambia sus secretos en el repositorio al que pertenecen y actualiza el espejo
+      (p. ej. git submodule update --remote).
+error-invalid-pattern =
+    Expresión regular no válida '{ $pattern }' para la clave '{ $key }': { $reason }
+
+      Corrige el patrón en .vaultic/config.toml, en [validation].
+error-ci-export-failed =
+    Formato de CI no válido: '{ $format }'
+
+      Formatos admitidos: github, gitlab, generic
+
+      Ejemplos:
+        → vaultic ci export --env dev --format github
+        → vaultic ci export --env dev --format gitlab
+        → vaultic ci export --env dev --format generic
+error-gpg-agent-unavailable =
+    Agente GPG no disponible: { $detail }
+
+      gpg necesita gpg-agent para usar tus claves secretas y no pudo contactarlo.
+
+      Soluciones:
+        → Inícialo: gpgconf --launch gpg-agent
+        → Con un homedir propio, usa una ruta corta: el socket del agente vive en él
+        → En contenedores, instala gpg-agent (p. ej. el paquete gnupg2)
+error-clipboard-unavailable =
+    Portapapeles no disponible: { $detail }
+
+      Soluciones:
+        → Ejecuta Vaultic dentro de una sesión de escritorio (X11, o Wayland con XWayland)
+        → Por SSH o en CI, quita --copy y usa la salida impresa
@@ -0,0 +1,164 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::adapters::archive::tar_archive::{self, ArchiveEntry};
+use crate::core::errors::{Result, VaulticError};
+
+/// Version of the bundle layout written by `vaultic bundle`.
+pub const ENV_BUNDLE_VERSION: u32 = 1;
+
+const METADATA_FILE: &str = "metadata.json";
+const CHECKSUMS_FILE: &str = "checksums.txt";
+
+/// What a deploy bundle holds, besides the secrets themselves.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleMetadata {
+    pub version: u32,
+    pub env: String,
+    /// Inheritance chain the secrets were resolved from, base first.
+    pub layers: Vec<String>,
+    pub variables: usize,
+    pub created_at: DateTime<Utc>,
+    /// Version of Vaultic that wrote the bundle.
+    pub vaultic_version: String,
+}
+
+/// One resolved environment packed for a host without the repository:
+/// a tarball of `<env>.env`, `metadata.json` and a `checksums.txt` in
+/// `sha256sum` format, encrypted by the caller.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvBundle {
+    pub metadata: BundleMetadata,
+    /// The resolved environment as dotenv.
+    pub content: Vec<u8>,
+}
+
+impl EnvBundle {
+    /// Name of the secrets file inside the bundle, e.g. `prod.env`.
+    pub fn env_file(&self) -> String {
+        format!("{}.env", self.metadata.env)
+    }
+
+    /// Pack into an in-memory gzip tarball (still plaintext).
+    pub fn pack(&self) -> Result<Vec<u8>> {
+        let metadata =
+            serde_json::to_vec_pretty(&self.metadata).map_err(|e| VaulticError::InvalidConfig {
+                detail: format!("Failed to serialize {METADATA_FILE}: {e}"),
+            })?;
+        let mut entries = vec![
+            ArchiveEntry {
+                name: METADATA_FILE.into(),
+                data: metadata,
+            },
+            ArchiveEntry {
+                name: self.env_file(),
+                data: self.content.clone(),
+            },
+        ];
+        let checksums: String = entries
+            .iter()
+            .map(|e| format!("{}  {}\n", sha256(&e.data), e.name))
+            .collect();
+        entries.push(ArchiveEntry {
+            name: CHECKSUMS_FILE.into(),
+            data: checksums.into_bytes(),
+        });
+        tar_archive::pack(&entries)
+    }
+
+    /// Unpack a tarball made by [`EnvBundle::pack`], checking every file
+    /// against `checksums.txt`.
+    pub fn unpack(packed: &[u8]) -> Result<Self> {
+        let invalid = |detail: String| VaulticError::InvalidConfig {
+            detail: format!("Invalid bundle: {detail}"),
+        };
+        let entries = tar_archive::unpack(packed)?;
+        let find = |name: &str| {
+            entries
+                .iter()
+                .find(|e| e.name == name)
+                .ok_or_else(|| invalid(format!("missing {name}")))
+        };
+
+        let checksums = String::from_utf8_lossy(&find(CHECKSUMS_FILE)?.data).into_owned();
+        for entry in entries.iter().filter(|e| e.name != CHECKSUMS_FILE) {
+            let recorded = checksums.lines().find_map(|line| {
+                let (hash, name) = line.split_once("  ")?;
+                (name == entry.name).then_some(hash)
+            });
+            match recorded {
+                Some(hash) if hash == sha256(&entry.data) => {}
+                Some(_) => {
+                    return Err(invalid(format!(
+                        "{} does not match its checksum",
+                        entry.name
+                    )));
+                }
+                None => return Err(invalid(format!("{} has no checksum", entry.name))),
+            }
+        }
+
+        let metadata: BundleMetadata = serde_json::from_slice(&find(METADATA_FILE)?.data)
+            .map_err(|e| invalid(format!("{METADATA_FILE}: {e}")))?;
+        if metadata.version > ENV_BUNDLE_VERSION {
+            return Err(VaulticError::InvalidConfig {
+                detail: format!(
+                    "Bundle version {} is newer than this Vaultic supports \
+                     ({ENV_BUNDLE_VERSION}). Update Vaultic on this host to extract it.",
+                    metadata.version
+                ),
+            });
+        }
+        let content = find(&format!("{}.env", metadata.env))?.data.clone();
+        Ok(Self { metadata, content })
+    }
+}
+
+fn sha256(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundle() -> EnvBundle {
+        EnvBundle {
+            metadata: BundleMetadata {
+                version: ENV_BUNDLE_VERSION,
+                env: "prod".into(),
+                layers: vec!["base".into(), "prod".into()],
+                variables: 2,
+                created_at: Utc::now(),
+                vaultic_version: "1.0.0".into(),
+            },
+            content: b"A=1\nB=2\n".to_vec(),
+        }
+    }
+
+    #[test]
+    fn bundle_round_trips_with_checksums() {
+        let bundle = bundle();
+        let packed = bundle.pack().unwrap();
+        let entries = tar_archive::unpack(&packed).unwrap();
+        assert_eq!(
+            entries.iter().map(|e| e.name.as_str()).collect::<Vec<_>>(),
+            [METADATA_FILE, "prod.env", CHECKSUMS_FILE]
+        );
+        assert_eq!(EnvBundle::unpack(&packed).unwrap(), bundle);
+    }
+
+    #[test]
+    fn tampered_files_are_rejected() {
+        let mut entries = tar_archive::unpack(&bundle().pack().unwrap()).unwrap();
+        entries[1].data = b"A=evil\n".to_vec();
+        let err = EnvBundle::unpack(&tar_archive::pack(&entries).unwrap())
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("prod.env does not match its checksum"),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,2 @@
+pub mod env_bundle;
+pub mod tar_archive;
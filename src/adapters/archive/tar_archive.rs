@@ -0,0 +1,123 @@
+use std::io::Read;
+use std::path::Component;
+
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+
+use crate::core::errors::{Result, VaulticError};
+
+/// A single file stored in a project archive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchiveEntry {
+    /// Bare file name (no directories), e.g. `dev.env.enc`.
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+/// Pack entries into an in-memory gzip-compressed tarball.
+///
+/// The result is still plaintext; callers encrypt it before it
+/// touches disk.
+pub fn pack(entries: &[ArchiveEntry]) -> Result<Vec<u8>> {
+    let encoder = GzEncoder::new(Vec::new(), Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+
+    for entry in entries {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(entry.data.len() as u64);
+        header.set_mode(0o600);
+        header.set_cksum();
+        builder.append_data(&mut header, &entry.name, entry.data.as_slice())?;
+    }
+
+    Ok(builder.into_inner()?.finish()?)
+}
+
+/// Unpack a gzip-compressed tarball produced by [`pack`].
+///
+/// Every entry must be a regular file with a bare file name; anything
+/// else (directories, links, `..`, absolute paths) is rejected so a
+/// crafted archive cannot write outside the target directory.
+pub fn unpack(data: &[u8]) -> Result<Vec<ArchiveEntry>> {
+    let mut archive = tar::Archive::new(GzDecoder::new(data));
+    let mut entries = Vec::new();
+
+    let invalid = |detail: String| VaulticError::InvalidConfig {
+        detail: format!("Invalid archive: {detail}"),
+    };
+
+    for entry in archive
+        .entries()
+        .map_err(|e| invalid(format!("not a Vaultic archive ({e})")))?
+    {
+        let mut entry = entry.map_err(|e| invalid(e.to_string()))?;
+        if !entry.header().entry_type().is_file() {
+            return Err(invalid("contains a non-file entry".into()));
+        }
+
+        let path = entry.path().map_err(|e| invalid(e.to_string()))?;
+        let mut components = path.components();
+        let name = match (components.next(), components.next()) {
+            (Some(Component::Normal(name)), None) => name.to_string_lossy().into_owned(),
+            _ => {
+                return Err(invalid(format!(
+                    "unexpected path '{}' (only bare file names are allowed)",
+                    path.display()
+                )));
+            }
+        };
+
+        let mut buf = Vec::new();
+        entry.read_to_end(&mut buf)?;
+        entries.push(ArchiveEntry { name, data: buf });
+    }
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, data: &str) -> ArchiveEntry {
+        ArchiveEntry {
+            name: name.into(),
+            data: data.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn pack_unpack_round_trip() {
+        let entries = vec![
+            entry("config.toml", "[vaultic]\n"),
+            entry("recipients.txt", "age1abc\n"),
+            entry("dev.env.enc", "ciphertext"),
+        ];
+
+        let packed = pack(&entries).unwrap();
+        assert_eq!(unpack(&packed).unwrap(), entries);
+    }
+
+    #[test]
+    fn unpack_rejects_garbage() {
+        assert!(unpack(b"definitely not a tarball").is_err());
+    }
+
+    #[test]
+    fn unpack_rejects_nested_paths() {
+        let encoder = GzEncoder::new(Vec::new(), Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        let mut header = tar::Header::new_gnu();
+        header.set_size(1);
+        header.set_mode(0o600);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, "sub/evil.txt", &b"x"[..])
+            .unwrap();
+        let packed = builder.into_inner().unwrap().finish().unwrap();
+
+        let err = unpack(&packed).unwrap_err().to_string();
+        assert!(err.contains("only bare file names"));
+    }
+}
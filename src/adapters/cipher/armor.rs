@@ -0,0 +1,412 @@
+use std::borrow::Cow;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+
+use crate::core::errors::{Result, VaulticError};
+
+const BEGIN_MARKER: &str = "-----BEGIN AGE ENCRYPTED FILE-----";
+const END_MARKER: &str = "-----END AGE ENCRYPTED FILE-----";
+/// Line width age writes, and the only one its reader accepts.
+const COLUMNS: usize = 64;
+const BOM: char = '\u{feff}';
+const TRUNCATED: &str = "the END marker is missing, the file is cut short";
+const UNDECODABLE: &str = "the base64 does not decode, the file is cut short or edited";
+
+/// What [`repair`] changed to make a file readable again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Repaired {
+    pub content: Vec<u8>,
+    /// One line per kind of mangling removed, e.g. "CRLF line endings".
+    /// Empty when the file was already canonical.
+    pub fixes: Vec<String>,
+}
+
+/// Whether `content` is age armor, even with mangling [`repair`] undoes
+/// in front of the BEGIN marker.
+pub fn is_armored(content: &[u8]) -> bool {
+    let start = content
+        .strip_prefix("\u{feff}".as_bytes())
+        .unwrap_or(content);
+    let start = start.trim_ascii_start();
+    start.starts_with(BEGIN_MARKER.as_bytes())
+}
+
+/// Armored `content` in the form age reads: lines wrapped at another
+/// width, as some git hosts and editors reflow them, are wrapped at 64
+/// again. Binary files and canonical armor are returned as they are.
+///
+/// Anything else wrong with the armor fails with the line at fault.
+pub fn normalize(content: &[u8]) -> Result<Cow<'_, [u8]>> {
+    if !is_armored(content) {
+        return Ok(Cow::Borrowed(content));
+    }
+    let text = text(content)?;
+    let body = parse(text).map_err(|(line, reason)| corrupted(line, reason))?;
+    if body.is_canonical() {
+        Ok(Cow::Borrowed(content))
+    } else {
+        Ok(Cow::Owned(wrap(&body.lines.concat())))
+    }
+}
+
+/// Undo common mangling of armored `content`: CRLF line endings, stray
+/// carriage returns, leading and trailing spaces, blank lines, a byte
+/// order mark or text around the markers, and lines wrapped at another
+/// width. Fails with the line at fault when the base64 itself is damaged
+/// or cut short, which no repair can undo.
+pub fn repair(content: &[u8]) -> Result<Repaired> {
+    if !is_armored(content) {
+        return Err(VaulticError::EncryptionFailed {
+            reason: "Not an age armored file: nothing to repair".into(),
+        });
+    }
+    let text = text(content)?;
+    let mut fixes = Vec::new();
+    let mut fix = |what: &str| {
+        if !fixes.iter().any(|f| f == what) {
+            fixes.push(what.to_string());
+        }
+    };
+
+    let mut lines = text.split('\n').enumerate().map(|(i, line)| (i + 1, line));
+    let mut before = String::new();
+    for (_, line) in lines.by_ref() {
+        if line.trim_matches(|c: char| c.is_whitespace() || c == BOM) == BEGIN_MARKER {
+            break;
+        }
+        before.push_str(line);
+    }
+    if text.starts_with(BOM) {
+        fix("byte order mark");
+    }
+    if !before
+        .trim_matches(|c: char| c.is_whitespace() || c == BOM)
+        .is_empty()
+    {
+        fix("text before the BEGIN marker");
+    }
+
+    let mut body = String::new();
+    let mut widths = Vec::new();
+    let mut last_line = 1;
+    let mut found_end = false;
+    for (number, line) in lines.by_ref() {
+        last_line = number;
+        if line.contains('\r') {
+            fix("CRLF line endings");
+        }
+        let trimmed = line.trim();
+        if trimmed == END_MARKER {
+            found_end = true;
+            break;
+        }
+        if trimmed.is_empty() {
+            fix("blank lines");
+            continue;
+        }
+        if trimmed != line.trim_end_matches('\r') {
+            fix("leading or trailing spaces");
+        }
+        for (column, c) in trimmed.chars().enumerate() {
+            if c.is_whitespace() {
+                fix("spaces inside lines");
+            } else if is_base64(c) {
+                body.push(c);
+            } else {
+                return Err(not_base64(number, column + 1, c));
+            }
+        }
+        widths.push(trimmed.chars().filter(|c| !c.is_whitespace()).count());
+    }
+    if !found_end {
+        return Err(corrupted(last_line, TRUNCATED.into()));
+    }
+    if lines.any(|(_, line)| !line.trim().is_empty()) {
+        fix("text after the END marker");
+    }
+    if let Some((last, full)) = widths.split_last()
+        && (full.iter().any(|&w| w != COLUMNS) || *last > COLUMNS)
+    {
+        fix("lines rewrapped at 64 characters");
+    }
+
+    if !decodes(&body) {
+        return Err(corrupted(last_line, UNDECODABLE.into()));
+    }
+    let content = wrap(&body);
+    let fixes = if content == text.as_bytes() {
+        Vec::new()
+    } else {
+        fixes
+    };
+    Ok(Repaired { content, fixes })
+}
+
+/// The base64 lines between the markers.
+struct Body<'a> {
+    lines: Vec<&'a str>,
+}
+
+impl Body<'_> {
+    /// Every line but the last is 64 characters, and the last no more.
+    fn is_canonical(&self) -> bool {
+        match self.lines.split_last() {
+            Some((last, full)) => full.iter().all(|l| l.len() == COLUMNS) && last.len() <= COLUMNS,
+            None => true,
+        }
+    }
+}
+
+/// Check armor line by line, accepting any consistent line width and
+/// CRLF line endings (age reads both). Errors are a 1-based line number
+/// and the reason.
+fn parse(text: &str) -> std::result::Result<Body<'_>, (usize, String)> {
+    let mut lines = text
+        .split('\n')
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.strip_suffix('\r').unwrap_or(line)));
+
+    match lines.next() {
+        Some((_, BEGIN_MARKER)) => {}
+        Some((_, line)) if line.trim() == BEGIN_MARKER => {
+            return Err((1, "leading or trailing spaces".into()));
+        }
+        _ => return Err((1, "text before the BEGIN marker".into())),
+    }
+
+    let mut body = Vec::new();
+    let mut last_line = 1;
+    let mut end_line = None;
+    for (number, line) in lines.by_ref() {
+        last_line = number;
+        if line == END_MARKER {
+            end_line = Some(number);
+            break;
+        }
+        if line.contains('\r') {
+            return Err((number, "stray carriage return".into()));
+        }
+        if line.is_empty() {
+            return Err((number, "blank line inside the armor".into()));
+        }
+        if line.trim() != line {
+            return Err((number, "leading or trailing spaces".into()));
+        }
+        if let Some((column, c)) = line.chars().enumerate().find(|&(_, c)| !is_base64(c)) {
+            return Err((number, not_base64_reason(column + 1, c)));
+        }
+        body.push((number, line));
+    }
+    let Some(end_line) = end_line else {
+        return Err((last_line, TRUNCATED.into()));
+    };
+    if let Some((number, _)) = lines.find(|(_, line)| !line.trim().is_empty()) {
+        return Err((number, "text after the END marker".into()));
+    }
+
+    if let Some((&(_, first), rest)) = body.split_first() {
+        let width = first.len();
+        for (i, &(number, line)) in rest.iter().enumerate() {
+            let is_last = i + 1 == rest.len();
+            if (is_last && line.len() > width) || (!is_last && line.len() != width) {
+                return Err((
+                    number,
+                    format!(
+                        "{} characters where the lines above have {width}, \
+                         the armor was reflowed unevenly",
+                        line.len()
+                    ),
+                ));
+            }
+        }
+    }
+    let joined: String = body.iter().map(|(_, line)| *line).collect();
+    if !decodes(&joined) {
+        let number = body.last().map_or(end_line, |(number, _)| *number);
+        return Err((number, UNDECODABLE.into()));
+    }
+    Ok(Body {
+        lines: body.into_iter().map(|(_, line)| line).collect(),
+    })
+}
+
+fn decodes(body: &str) -> bool {
+    STANDARD.decode(body).is_ok()
+}
+
+/// Canonical armor around base64 `body`.
+fn wrap(body: &str) -> Vec<u8> {
+    let mut out = String::with_capacity(body.len() + body.len() / COLUMNS + 80);
+    out.push_str(BEGIN_MARKER);
+    out.push('\n');
+    for chunk in body.as_bytes().chunks(COLUMNS) {
+        out.push_str(std::str::from_utf8(chunk).unwrap_or_default());
+        out.push('\n');
+    }
+    out.push_str(END_MARKER);
+    out.push('\n');
+    out.into_bytes()
+}
+
+fn text(content: &[u8]) -> Result<&str> {
+    std::str::from_utf8(content).map_err(|e| {
+        let line = content[..e.valid_up_to()]
+            .iter()
+            .filter(|&&b| b == b'\n')
+            .count();
+        corrupted(line + 1, "bytes that are not text".into())
+    })
+}
+
+fn is_base64(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '=')
+}
+
+fn not_base64_reason(column: usize, c: char) -> String {
+    format!("{c:?} at column {column} is not base64")
+}
+
+fn not_base64(line: usize, column: usize, c: char) -> VaulticError {
+    corrupted(line, not_base64_reason(column, c))
+}
+
+fn corrupted(line: usize, reason: String) -> VaulticError {
+    VaulticError::ArmorCorrupted { line, reason }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+
+    fn armored(len: usize) -> (Vec<u8>, String) {
+        let data: Vec<u8> = (0..len).map(|i| (i * 7 % 251) as u8).collect();
+        let mut out = Vec::new();
+        let mut writer =
+            age::armor::ArmoredWriter::wrap_output(&mut out, age::armor::Format::AsciiArmor)
+                .unwrap();
+        writer.write_all(&data).unwrap();
+        writer.finish().unwrap();
+        (data, String::from_utf8(out).unwrap())
+    }
+
+    fn read_with_age(content: &[u8]) -> Vec<u8> {
+        let mut data = Vec::new();
+        age::armor::ArmoredReader::new(content)
+            .read_to_end(&mut data)
+            .unwrap();
+        data
+    }
+
+    fn reflow(text: &str, width: usize) -> String {
+        let body: String = text.lines().filter(|l| !l.starts_with("-----")).collect();
+        let mut out = format!("{BEGIN_MARKER}\n");
+        for chunk in body.as_bytes().chunks(width) {
+            out.push_str(std::str::from_utf8(chunk).unwrap());
+            out.push('\n');
+        }
+        out + END_MARKER + "\n"
+    }
+
+    fn error_line(result: Result<impl std::fmt::Debug>) -> (usize, String) {
+        match result.unwrap_err() {
+            VaulticError::ArmorCorrupted { line, reason } => (line, reason),
+            other => panic!("unexpected error: {other}"),
+        }
+    }
+
+    #[test]
+    fn canonical_armor_and_binary_are_left_alone() {
+        let (_, text) = armored(300);
+        assert!(matches!(
+            normalize(text.as_bytes()).unwrap(),
+            Cow::Borrowed(_)
+        ));
+        let crlf = text.replace('\n', "\r\n");
+        assert!(matches!(
+            normalize(crlf.as_bytes()).unwrap(),
+            Cow::Borrowed(_)
+        ));
+        let binary = b"age-encryption.org/v1\n-> X25519 abc\n";
+        assert!(matches!(normalize(binary).unwrap(), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn other_line_widths_are_rewrapped_for_age() {
+        let (data, text) = armored(300);
+        for width in [76, 72, 70, 400] {
+            let reflowed = reflow(&text, width);
+            let normalized = normalize(reflowed.as_bytes()).unwrap();
+            assert_eq!(normalized.as_ref(), text.as_bytes(), "width {width}");
+            assert_eq!(read_with_age(&normalized), data);
+        }
+    }
+
+    #[test]
+    fn mangled_lines_are_reported_precisely() {
+        let (_, text) = armored(300);
+        let mut lines: Vec<String> = text.lines().map(String::from).collect();
+
+        let mut spaced = lines.clone();
+        spaced[3].push(' ');
+        assert_eq!(
+            error_line(normalize(spaced.join("\n").as_bytes())),
+            (4, "leading or trailing spaces".into())
+        );
+
+        let mut edited = lines.clone();
+        edited[2].replace_range(5..6, "*");
+        assert_eq!(
+            error_line(normalize(edited.join("\n").as_bytes())),
+            (3, "'*' at column 6 is not base64".into())
+        );
+
+        let mut reflowed = lines.clone();
+        reflowed[2].truncate(40);
+        let (line, reason) = error_line(normalize(reflowed.join("\n").as_bytes()));
+        assert_eq!(line, 3);
+        assert!(reason.contains("40 characters"), "{reason}");
+
+        lines.pop();
+        let (line, reason) = error_line(normalize(lines.join("\n").as_bytes()));
+        assert_eq!(line, lines.len());
+        assert!(reason.contains("END marker is missing"), "{reason}");
+    }
+
+    #[test]
+    fn repair_strips_common_mangling() {
+        let (data, text) = armored(300);
+        let mangled = format!(
+            "\u{feff}{}\r\n\r\n",
+            reflow(&text, 76)
+                .lines()
+                .map(|l| format!("{l}  "))
+                .collect::<Vec<_>>()
+                .join("\r\n")
+        );
+        assert!(normalize(mangled.as_bytes()).is_err());
+
+        let repaired = repair(mangled.as_bytes()).unwrap();
+        assert_eq!(repaired.content, text.as_bytes());
+        assert_eq!(read_with_age(&repaired.content), data);
+        assert_eq!(
+            repaired.fixes,
+            [
+                "byte order mark",
+                "CRLF line endings",
+                "leading or trailing spaces",
+                "lines rewrapped at 64 characters"
+            ]
+        );
+        assert!(repair(text.as_bytes()).unwrap().fixes.is_empty());
+    }
+
+    #[test]
+    fn repair_refuses_damaged_base64() {
+        let (_, text) = armored(300);
+        let cut: String = text.lines().take(3).collect::<Vec<_>>().join("\n");
+        assert!(error_line(repair(cut.as_bytes())).1.contains("END marker"));
+        assert!(repair(b"age-encryption.org/v1\n").is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 use std::process::Command;
 
+use crate::config::app_config::GpgProfile;
 use crate::core::errors::{Result, VaulticError};
 use crate::core::models::key_identity::KeyIdentity;
 use crate::core::traits::cipher::CipherBackend;
@@ -12,20 +13,53 @@ use crate::core::traits::cipher::CipherBackend;
 pub struct GpgBackend {
     /// Path to the gpg binary (defaults to "gpg").
     gpg_path: PathBuf,
+    /// Keyring directory passed as `--homedir`; gpg's default when `None`.
+    homedir: Option<PathBuf>,
+    profile: GpgProfile,
 }
 
 impl GpgBackend {
     /// Create a new backend using the default `gpg` binary.
     pub fn new() -> Self {
-        Self {
-            gpg_path: PathBuf::from("gpg"),
-        }
+        Self::with_path(PathBuf::from("gpg"))
     }
 
     /// Create a new backend with a custom gpg binary path.
-    #[allow(dead_code)]
     pub fn with_path(gpg_path: PathBuf) -> Self {
-        Self { gpg_path }
+        Self {
+            gpg_path,
+            homedir: None,
+            profile: GpgProfile::default(),
+        }
+    }
+
+    /// Use the keyring in `homedir` instead of gpg's default.
+    pub fn with_homedir(mut self, homedir: Option<PathBuf>) -> Self {
+        self.homedir = homedir;
+        self
+    }
+
+    /// Invoke gpg with `profile`.
+    pub fn with_profile(mut self, profile: GpgProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// A gpg command with the homedir and profile options applied.
+    ///
+    /// Every profile runs in batch mode. The `ci` profile also drops the
+    /// tty and sets loopback pinentry, so gpg-agent can never open a
+    /// passphrase dialog: a protected key fails instead of waiting.
+    fn command(&self) -> Command {
+        let mut cmd = Command::new(&self.gpg_path);
+        if let Some(homedir) = &self.homedir {
+            cmd.arg("--homedir").arg(homedir);
+        }
+        cmd.arg("--batch");
+        if self.profile == GpgProfile::Ci {
+            cmd.args(["--no-tty", "--pinentry-mode", "loopback"]);
+        }
+        cmd
     }
 
     /// Check if GPG is available on the system.
@@ -36,9 +70,25 @@ impl GpgBackend {
             .is_ok_and(|o| o.status.success())
     }
 
+    /// Secret keys in the keyring, from `gpg --list-secret-keys`.
+    pub fn list_secret_keys(&self) -> Result<Vec<GpgSecretKey>> {
+        let output = self.run_gpg(
+            &["--list-secret-keys", "--with-colons", "--fixed-list-mode"],
+            None,
+        )?;
+        Ok(parse_secret_keys(&String::from_utf8_lossy(&output)))
+    }
+
+    /// Whether the keyring holds a public key for `query`, a fingerprint,
+    /// key ID or email, from `gpg --list-keys`.
+    pub fn has_public_key(&self, query: &str) -> bool {
+        self.run_gpg(&["--list-keys", "--with-colons", "--", query], None)
+            .is_ok()
+    }
+
     /// Run a gpg command and return stdout on success.
     fn run_gpg(&self, args: &[&str], stdin_data: Option<&[u8]>) -> Result<Vec<u8>> {
-        let mut cmd = Command::new(&self.gpg_path);
+        let mut cmd = self.command();
         cmd.args(args);
 
         if let Some(data) = stdin_data {
@@ -67,10 +117,7 @@ impl GpgBackend {
                 })?;
 
             if !output.status.success() {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                return Err(VaulticError::EncryptionFailed {
-                    reason: format!("gpg exited with error: {stderr}"),
-                });
+                return Err(failure(&String::from_utf8_lossy(&output.stderr)));
             }
 
             Ok(output.stdout)
@@ -80,10 +127,7 @@ impl GpgBackend {
             })?;
 
             if !output.status.success() {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                return Err(VaulticError::EncryptionFailed {
-                    reason: format!("gpg exited with error: {stderr}"),
-                });
+                return Err(failure(&String::from_utf8_lossy(&output.stderr)));
             }
 
             Ok(output.stdout)
@@ -91,6 +135,119 @@ impl GpgBackend {
     }
 }
 
+/// Why a gpg invocation failed, read from its stderr.
+#[derive(Debug, PartialEq, Eq)]
+enum Failure {
+    /// gpg-agent could not be started or reached.
+    AgentUnavailable,
+    /// The key needs a passphrase and gpg was not allowed to ask.
+    PassphraseUnavailable,
+    /// None of the secret keys is a recipient of the message.
+    NoSecretKey,
+    Other,
+}
+
+/// Classify gpg's stderr. An agent or passphrase problem is checked
+/// first: gpg reports those as "No secret key" too.
+fn classify(stderr: &str) -> Failure {
+    let has = |needles: &[&str]| needles.iter().any(|n| stderr.contains(n));
+    if has(&[
+        "can't connect to the agent",
+        "no gpg-agent running",
+        "No agent running",
+        "problem with the agent",
+        "error running '",
+        "socket name",
+    ]) {
+        Failure::AgentUnavailable
+    } else if has(&[
+        "No passphrase given",
+        "Bad passphrase",
+        "can't get input",
+        "No pinentry",
+        "Inappropriate ioctl for device",
+        "cannot open '/dev/tty'",
+    ]) {
+        Failure::PassphraseUnavailable
+    } else if has(&["No secret key"]) {
+        Failure::NoSecretKey
+    } else {
+        Failure::Other
+    }
+}
+
+/// The error for a failed gpg run.
+fn failure(stderr: &str) -> VaulticError {
+    // The last line is usually the outcome, the first the cause
+    let cause = stderr
+        .lines()
+        .map(|l| l.trim().trim_start_matches("gpg: "))
+        .find(|l| !l.is_empty())
+        .unwrap_or("gpg failed")
+        .to_string();
+    match classify(stderr) {
+        Failure::AgentUnavailable => VaulticError::GpgAgentUnavailable { detail: cause },
+        Failure::PassphraseUnavailable => VaulticError::EncryptionFailed {
+            reason: format!(
+                "gpg needs the key's passphrase but cannot ask for it ({cause})\n\n  \
+                 Solutions:\n    \
+                 → In a terminal, use the interactive profile: VAULTIC_GPG_PROFILE=interactive\n    \
+                 → In CI, use a key without a passphrase, or cache it first with \
+                 gpg-preset-passphrase"
+            ),
+        },
+        Failure::NoSecretKey => VaulticError::DecryptionNoKey,
+        Failure::Other => VaulticError::EncryptionFailed {
+            reason: format!("gpg exited with error: {stderr}"),
+        },
+    }
+}
+
+/// A secret key in the GPG keyring.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GpgSecretKey {
+    pub fingerprint: String,
+    /// User IDs, e.g. `Alice <alice@example.com>`.
+    pub user_ids: Vec<String>,
+}
+
+/// Parse `gpg --with-colons` output: each `sec` record starts a key, the
+/// `fpr` record right after it is the primary fingerprint and `uid`
+/// records hold its user IDs. Subkeys are skipped.
+fn parse_secret_keys(colons: &str) -> Vec<GpgSecretKey> {
+    let mut keys: Vec<GpgSecretKey> = Vec::new();
+    let mut in_primary = false;
+    for line in colons.lines() {
+        let fields: Vec<&str> = line.split(':').collect();
+        match fields[0] {
+            "sec" => {
+                in_primary = true;
+                keys.push(GpgSecretKey {
+                    fingerprint: String::new(),
+                    user_ids: Vec::new(),
+                });
+            }
+            "ssb" => in_primary = false,
+            "fpr" if in_primary => {
+                if let (Some(key), Some(fpr)) = (keys.last_mut(), fields.get(9))
+                    && key.fingerprint.is_empty()
+                {
+                    key.fingerprint = fpr.to_string();
+                }
+            }
+            "uid" => {
+                if let (Some(key), Some(uid)) = (keys.last_mut(), fields.get(9)) {
+                    // Colons inside user IDs are escaped as \x3a
+                    key.user_ids.push(uid.replace("\\x3a", ":"));
+                }
+            }
+            _ => {}
+        }
+    }
+    keys.retain(|k| !k.fingerprint.is_empty());
+    keys
+}
+
 impl Default for GpgBackend {
     fn default() -> Self {
         Self::new()
@@ -105,14 +262,7 @@ impl CipherBackend for GpgBackend {
             });
         }
 
-        let mut args = vec![
-            "--encrypt",
-            "--armor",
-            "--batch",
-            "--yes",
-            "--trust-model",
-            "always",
-        ];
+        let mut args = vec!["--encrypt", "--armor", "--yes", "--trust-model", "always"];
 
         // Collect recipient flags
         let recipient_args: Vec<String> = recipients
@@ -126,10 +276,7 @@ impl CipherBackend for GpgBackend {
     }
 
     fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>> {
-        let args = ["--decrypt", "--batch", "--yes"];
-
-        self.run_gpg(&args, Some(ciphertext))
-            .map_err(|_| VaulticError::DecryptionNoKey)
+        self.run_gpg(&["--decrypt", "--yes"], Some(ciphertext))
     }
 
     fn name(&self) -> &str {
@@ -154,5 +301,85 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn parse_secret_keys_reads_fingerprints_and_user_ids() {
+        let colons = "\
+sec:u:255:22:89ABCDEF01234567:1700000000:::u:::scESC:::+:::ed25519:::0:
+fpr:::::::::0123456789ABCDEF0123456789ABCDEF01234567:
+grp:::::::::AAAA:
+uid:u::::1700000000::HASH::Alice Ops <alice@example.com>::::::::::0:
+uid:u::::1700000000::HASH::Alice \\x3a work <alice@corp.example>::::::::::0:
+ssb:u:255:18:1111222233334444:1700000000::::::e:::+:::cv25519::
+fpr:::::::::FFFFEEEEDDDDCCCCBBBBAAAA1111222233334444:
+sec:u:3072:1:5555666677778888:1700000000:::u:::scESC:::+:::::0:
+fpr:::::::::99990000111122223333444455556666777788889:
+uid:u::::1700000000::HASH::bob@example.com::::::::::0:
+";
+        let keys = parse_secret_keys(colons);
+        assert_eq!(keys.len(), 2);
+        assert_eq!(
+            keys[0].fingerprint,
+            "0123456789ABCDEF0123456789ABCDEF01234567"
+        );
+        assert_eq!(
+            keys[0].user_ids,
+            vec![
+                "Alice Ops <alice@example.com>",
+                "Alice : work <alice@corp.example>"
+            ]
+        );
+        assert_eq!(keys[1].user_ids, vec!["bob@example.com"]);
+    }
+
+    #[test]
+    fn classify_separates_agent_passphrase_and_missing_key() {
+        assert_eq!(
+            classify(
+                "gpg: can't connect to the agent: IPC connect call failed\n\
+                 gpg: decryption failed: No secret key\n"
+            ),
+            Failure::AgentUnavailable
+        );
+        assert_eq!(
+            classify(
+                "gpg: public key decryption failed: No passphrase given\n\
+                 gpg: decryption failed: No secret key\n"
+            ),
+            Failure::PassphraseUnavailable
+        );
+        assert_eq!(
+            classify("gpg: decryption failed: No secret key\n"),
+            Failure::NoSecretKey
+        );
+        assert_eq!(
+            classify("gpg: no valid OpenPGP data found.\n"),
+            Failure::Other
+        );
+        assert!(matches!(
+            failure("gpg: can't connect to the agent: IPC connect call failed\n"),
+            VaulticError::GpgAgentUnavailable { detail } if detail.starts_with("can't connect")
+        ));
+    }
+
+    #[test]
+    fn ci_profile_never_prompts() {
+        let backend = GpgBackend::new()
+            .with_homedir(Some(PathBuf::from("/ci/gnupg")))
+            .with_profile(GpgProfile::Ci);
+        let cmd = backend.command();
+        let args: Vec<_> = cmd.get_args().map(|a| a.to_string_lossy()).collect();
+        assert_eq!(
+            args,
+            [
+                "--homedir",
+                "/ci/gnupg",
+                "--batch",
+                "--no-tty",
+                "--pinentry-mode",
+                "loopback"
+            ]
+        );
+    }
+
     // Integration tests that require GPG installed are in tests/integration/
 }
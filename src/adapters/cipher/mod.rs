@@ -1,2 +1,5 @@
 pub mod age_backend;
+pub mod armor;
 pub mod gpg_backend;
+pub mod plugin_backend;
+pub mod threshold_backend;
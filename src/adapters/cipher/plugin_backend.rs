@@ -0,0 +1,310 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde::{Deserialize, Serialize};
+
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::key_identity::KeyIdentity;
+use crate::core::traits::cipher::CipherBackend;
+
+/// Executables named `vaultic-cipher-<name>` on `PATH` provide the
+/// `<name>` cipher.
+pub const PLUGIN_PREFIX: &str = "vaultic-cipher-";
+
+/// Version of the request format, sent with every request.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Cipher backend run as an external `vaultic-cipher-<name>` executable,
+/// so an HSM or a proprietary KMS can be used without forking Vaultic.
+///
+/// Each operation runs the plugin once with a JSON request on stdin:
+///
+/// ```json
+/// {"version": 1, "op": "encrypt", "plaintext": "<base64>", "recipients": ["..."]}
+/// {"version": 1, "op": "decrypt", "ciphertext": "<base64>"}
+/// ```
+///
+/// and reads a JSON response from stdout: `{"data": "<base64>"}` on
+/// success, or `{"error": "...", "code": "no_key"}` on failure, where
+/// `code` is optional and `no_key` means no local key can decrypt.
+pub struct PluginBackend {
+    name: String,
+    program: PathBuf,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum Request<'a> {
+    Encrypt {
+        version: u32,
+        plaintext: String,
+        recipients: Vec<&'a str>,
+    },
+    Decrypt {
+        version: u32,
+        ciphertext: String,
+    },
+}
+
+#[derive(Deserialize)]
+struct Response {
+    data: Option<String>,
+    error: Option<String>,
+    code: Option<String>,
+}
+
+impl PluginBackend {
+    /// Run `program` as the `name` cipher.
+    pub fn new(name: &str, program: PathBuf) -> Self {
+        Self {
+            name: name.to_string(),
+            program,
+        }
+    }
+
+    /// The `vaultic-cipher-<name>` executable on `PATH`, if any.
+    pub fn find(name: &str) -> Option<Self> {
+        if name.is_empty() {
+            return None;
+        }
+        find_executable(&format!("{PLUGIN_PREFIX}{name}")).map(|program| Self::new(name, program))
+    }
+
+    /// Every plugin on `PATH`, sorted by name. When two directories hold
+    /// the same plugin, the first one wins, as it would when run.
+    pub fn installed() -> Vec<Self> {
+        let mut plugins: Vec<Self> = Vec::new();
+        for dir in search_path() {
+            let Ok(entries) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+            for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+                let Some(name) = path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .and_then(|n| n.strip_prefix(PLUGIN_PREFIX))
+                    .map(|n| n.strip_suffix(std::env::consts::EXE_SUFFIX).unwrap_or(n))
+                else {
+                    continue;
+                };
+                if is_executable(&path) && !plugins.iter().any(|p| p.name == name) {
+                    plugins.push(Self::new(name, path.clone()));
+                }
+            }
+        }
+        plugins.sort_by(|a, b| a.name.cmp(&b.name));
+        plugins
+    }
+
+    /// Path of the plugin executable.
+    pub fn program(&self) -> &Path {
+        &self.program
+    }
+
+    /// Send `request` to the plugin and decode the data it returns.
+    fn call(&self, request: &Request) -> Result<Vec<u8>> {
+        let failed = |reason: String| VaulticError::EncryptionFailed {
+            reason: format!("{} plugin: {reason}", self.name),
+        };
+        let input = serde_json::to_vec(request)
+            .map_err(|e| failed(format!("failed to serialize request: {e}")))?;
+
+        let mut child = Command::new(&self.program)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| failed(format!("failed to run {}: {e}", self.program.display())))?;
+        // Write from another thread while stdout and stderr are drained: a
+        // plugin that answers before it has read everything would otherwise
+        // block on a full pipe while Vaultic blocks writing to it
+        let stdin = child.stdin.take();
+        let (written, output) = std::thread::scope(|scope| {
+            let writer = scope.spawn(move || match stdin {
+                Some(mut stdin) => stdin.write_all(&input),
+                None => Ok(()),
+            });
+            let output = child.wait_with_output();
+            let written = writer
+                .join()
+                .unwrap_or_else(|_| Err(std::io::Error::other("writer thread panicked")));
+            (written, output)
+        });
+        let output = output.map_err(|e| failed(format!("process failed: {e}")))?;
+
+        let response: Response = match serde_json::from_slice(&output.stdout) {
+            Ok(response) => response,
+            Err(_) if !output.status.success() => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(failed(format!(
+                    "exited with {}: {}",
+                    output.status,
+                    stderr.trim()
+                )));
+            }
+            Err(_) if written.is_err() => {
+                let e = written.unwrap_err();
+                return Err(failed(format!("failed to write request: {e}")));
+            }
+            Err(e) => return Err(failed(format!("invalid response: {e}"))),
+        };
+        match (response.data, response.error) {
+            (_, Some(_)) if response.code.as_deref() == Some("no_key") => {
+                Err(VaulticError::DecryptionNoKey)
+            }
+            (_, Some(error)) => Err(failed(error)),
+            (Some(data), None) if output.status.success() => STANDARD
+                .decode(data)
+                .map_err(|e| failed(format!("invalid base64 in response: {e}"))),
+            _ => Err(failed(format!(
+                "exited with {} without data or an error",
+                output.status
+            ))),
+        }
+    }
+}
+
+impl CipherBackend for PluginBackend {
+    fn encrypt(&self, plaintext: &[u8], recipients: &[KeyIdentity]) -> Result<Vec<u8>> {
+        self.call(&Request::Encrypt {
+            version: PROTOCOL_VERSION,
+            plaintext: STANDARD.encode(plaintext),
+            recipients: recipients.iter().map(|r| r.public_key.as_str()).collect(),
+        })
+    }
+
+    fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>> {
+        self.call(&Request::Decrypt {
+            version: PROTOCOL_VERSION,
+            ciphertext: STANDARD.encode(ciphertext),
+        })
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// The first executable named `name` (plus `.exe` on Windows) in a
+/// `PATH` directory, as a shell would run it. Names with anything but
+/// letters, digits, `-` and `_` are never looked up, so they cannot
+/// reach outside `PATH`.
+pub fn find_executable(name: &str) -> Option<PathBuf> {
+    let plain = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !plain {
+        return None;
+    }
+    let file_name = format!("{name}{}", std::env::consts::EXE_SUFFIX);
+    search_path()
+        .into_iter()
+        .map(|dir| dir.join(&file_name))
+        .find(|p| is_executable(p))
+}
+
+fn search_path() -> Vec<PathBuf> {
+    std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    /// A plugin that stores data as is, and has no key for `locked`.
+    const PLAIN_PLUGIN: &str = r#"#!/bin/sh
+req=$(cat)
+case "$req" in
+  *'"op":"encrypt"'*) field=plaintext ;;
+  *) field=ciphertext ;;
+esac
+data=$(printf '%s' "$req" | sed "s/.*\"$field\":\"\([^\"]*\)\".*/\1/")
+if [ "$data" = "bG9ja2Vk" ]; then
+  echo '{"error":"not a recipient","code":"no_key"}'
+  exit 1
+fi
+printf '{"data":"%s"}' "$data"
+"#;
+
+    /// A plugin that fills its stdout pipe before reading the request.
+    const EAGER_PLUGIN: &str = r#"#!/bin/sh
+head -c 200000 /dev/zero | tr '\0' ' '
+cat > /dev/null
+printf '{"data":"b2s="}'
+"#;
+
+    fn install(dir: &Path, name: &str, script: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+        let path = dir.join(format!("{PLUGIN_PREFIX}{name}"));
+        std::fs::write(&path, script).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[test]
+    fn plugin_round_trips_over_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = PluginBackend::new("plain", install(dir.path(), "plain", PLAIN_PLUGIN));
+        let recipients = [KeyIdentity {
+            public_key: "kms:alias/prod".into(),
+            label: None,
+            added_at: None,
+        }];
+
+        let ciphertext = backend.encrypt(b"SECRET=1\n", &recipients).unwrap();
+        assert_eq!(ciphertext, b"SECRET=1\n");
+        assert_eq!(backend.decrypt(&ciphertext).unwrap(), b"SECRET=1\n");
+        assert_eq!(backend.name(), "plain");
+    }
+
+    #[test]
+    fn plugin_errors_are_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = PluginBackend::new("plain", install(dir.path(), "plain", PLAIN_PLUGIN));
+        assert!(matches!(
+            backend.decrypt(b"locked"),
+            Err(VaulticError::DecryptionNoKey)
+        ));
+
+        let broken = PluginBackend::new(
+            "broken",
+            install(dir.path(), "broken", "#!/bin/sh\necho boom >&2\nexit 3\n"),
+        );
+        let err = broken.decrypt(b"x").unwrap_err().to_string();
+        assert!(err.contains("broken plugin"), "{err}");
+        assert!(err.contains("boom"), "{err}");
+    }
+
+    #[test]
+    fn large_requests_do_not_deadlock_a_plugin_that_answers_early() {
+        let dir = tempfile::tempdir().unwrap();
+        let eager = install(dir.path(), "eager", EAGER_PLUGIN);
+        let backend = PluginBackend::new("eager", eager);
+        let plaintext = vec![b'A'; 1 << 20];
+        assert_eq!(backend.decrypt(&plaintext).unwrap(), b"ok");
+    }
+
+    #[test]
+    fn find_rejects_names_that_are_not_plain() {
+        assert!(PluginBackend::find("../age").is_none());
+        assert!(PluginBackend::find("").is_none());
+    }
+}
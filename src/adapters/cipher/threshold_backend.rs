@@ -0,0 +1,489 @@
+use std::collections::HashSet;
+
+use age::secrecy::ExposeSecret;
+use age::secrecy::zeroize::Zeroizing;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde::{Deserialize, Serialize};
+
+use crate::adapters::cipher::age_backend::AgeBackend;
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::key_identity::KeyIdentity;
+use crate::core::services::shamir::{self, Share};
+use crate::core::traits::cipher::CipherBackend;
+
+/// `format` of a threshold-encrypted file.
+pub const ENVELOPE_FORMAT: &str = "vaultic-threshold";
+
+/// Version of the envelope written by this build.
+pub const ENVELOPE_VERSION: u32 = 1;
+
+/// A threshold-encrypted environment, stored as JSON in its `.enc` file:
+///
+/// ```json
+/// {
+///   "format": "vaultic-threshold",
+///   "version": 1,
+///   "env": "prod",
+///   "threshold": 2,
+///   "payload_key": "age1…",
+///   "shares": [{ "recipient": "age1…", "index": 1, "share": "-----BEGIN AGE…" }],
+///   "payload": "-----BEGIN AGE…"
+/// }
+/// ```
+///
+/// The payload is encrypted to a one-off age key whose secret is split
+/// into one Shamir share per recipient, each encrypted to that recipient
+/// alone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Envelope {
+    pub format: String,
+    pub version: u32,
+    pub env: String,
+    pub threshold: u8,
+    /// Public half of the one-off key the payload is encrypted to.
+    pub payload_key: String,
+    pub shares: Vec<EnvelopeShare>,
+    pub payload: String,
+}
+
+/// A recipient's share, armored age ciphertext of [`Share::to_bytes`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvelopeShare {
+    pub recipient: String,
+    pub index: u8,
+    pub share: String,
+}
+
+/// A share released by one shareholder to another with
+/// `vaultic keys share release`, encrypted to the receiver.
+#[derive(Debug, Serialize, Deserialize)]
+struct ReleasedShare {
+    env: String,
+    payload_key: String,
+    /// Base64 of [`Share::to_bytes`].
+    share: String,
+}
+
+impl Envelope {
+    /// Parse `content` when it is a threshold envelope, `None` when it is
+    /// ordinary ciphertext.
+    pub fn parse(content: &[u8]) -> Option<Result<Self>> {
+        if !content.trim_ascii_start().starts_with(b"{") {
+            return None;
+        }
+        let envelope: Self = match serde_json::from_slice(content) {
+            Ok(envelope) => envelope,
+            Err(e) => {
+                return Some(Err(VaulticError::EncryptionFailed {
+                    reason: format!("Invalid threshold envelope: {e}"),
+                }));
+            }
+        };
+        if envelope.format != ENVELOPE_FORMAT {
+            return None;
+        }
+        if envelope.version > ENVELOPE_VERSION {
+            return Some(Err(VaulticError::EncryptionFailed {
+                reason: format!(
+                    "Threshold envelope version {} is newer than this Vaultic supports \
+                     ({ENVELOPE_VERSION}). Update Vaultic to decrypt it.",
+                    envelope.version
+                ),
+            }));
+        }
+        Some(Ok(envelope))
+    }
+}
+
+/// Age encryption that splits the key of threshold environments into
+/// Shamir shares (see [`Envelope`]). Without a threshold it encrypts like
+/// the wrapped [`AgeBackend`], and it decrypts ordinary age files too.
+pub struct ThresholdBackend {
+    inner: AgeBackend,
+    threshold: Option<(String, u8)>,
+    released: Vec<Vec<u8>>,
+}
+
+impl ThresholdBackend {
+    /// Wrap the age backend holding this machine's identities.
+    pub fn new(inner: AgeBackend) -> Self {
+        Self {
+            inner,
+            threshold: None,
+            released: Vec::new(),
+        }
+    }
+
+    /// Encrypt `env` so that `threshold` recipients are needed to decrypt.
+    pub fn with_threshold(mut self, env: &str, threshold: u8) -> Self {
+        self.threshold = Some((env.to_string(), threshold));
+        self
+    }
+
+    /// Shares other shareholders released to this machine, as the
+    /// contents of the files `vaultic keys share release` wrote.
+    pub fn with_released(mut self, released: Vec<Vec<u8>>) -> Self {
+        self.released = released;
+        self
+    }
+
+    /// Whether `content` is a threshold envelope rather than age ciphertext.
+    pub fn is_envelope(content: &[u8]) -> bool {
+        Envelope::parse(content).is_some()
+    }
+
+    /// Re-encrypt this machine's share of `content` to `to`, returning the
+    /// share index and the file to hand over.
+    pub fn release(&self, content: &[u8], to: &KeyIdentity) -> Result<(u8, Vec<u8>)> {
+        let envelope = parse_envelope(content)?;
+        let share = self
+            .own_share(&envelope)?
+            .ok_or(VaulticError::DecryptionNoKey)?;
+        let released = ReleasedShare {
+            env: envelope.env.clone(),
+            payload_key: envelope.payload_key.clone(),
+            share: STANDARD.encode(share.to_bytes()),
+        };
+        let json = serde_json::to_vec(&released).map_err(|e| VaulticError::EncryptionFailed {
+            reason: format!("Failed to serialize share: {e}"),
+        })?;
+        Ok((
+            share.x,
+            self.inner.encrypt(&json, std::slice::from_ref(to))?,
+        ))
+    }
+
+    /// The share in `envelope` this machine's identities decrypt.
+    ///
+    /// A threshold counts people, so identities that unlock more than one
+    /// share (someone listed under both an age and an SSH key, say) are
+    /// refused rather than counted twice.
+    fn own_share(&self, envelope: &Envelope) -> Result<Option<Share>> {
+        let mut shares: Vec<Share> = envelope
+            .shares
+            .iter()
+            .filter_map(|s| self.inner.decrypt(s.share.as_bytes()).ok())
+            .filter_map(|bytes| Share::from_bytes(&bytes).ok())
+            .collect();
+        if shares.len() > 1 {
+            return Err(VaulticError::EncryptionFailed {
+                reason: format!(
+                    "Your identities unlock {} shares of '{}', but each shareholder must \
+                     hold exactly one.\n\n  \
+                     Keep one of your keys in recipients.txt and re-encrypt '{}'.",
+                    shares.len(),
+                    envelope.env,
+                    envelope.env
+                ),
+            });
+        }
+        Ok(shares.pop())
+    }
+
+    /// The released shares that belong to `envelope`.
+    fn released_shares(&self, envelope: &Envelope) -> Result<Vec<Share>> {
+        let mut shares = Vec::new();
+        for file in &self.released {
+            let json = self.inner.decrypt(file)?;
+            let released: ReleasedShare =
+                serde_json::from_slice(&json).map_err(|e| VaulticError::EncryptionFailed {
+                    reason: format!("Invalid share file: {e}"),
+                })?;
+            if released.payload_key != envelope.payload_key {
+                return Err(VaulticError::EncryptionFailed {
+                    reason: format!(
+                        "A share file is for another encryption of '{}'. Ask for a share \
+                         of the current file: secrets were re-encrypted since it was released.",
+                        released.env
+                    ),
+                });
+            }
+            let bytes =
+                STANDARD
+                    .decode(&released.share)
+                    .map_err(|e| VaulticError::EncryptionFailed {
+                        reason: format!("Invalid share file: {e}"),
+                    })?;
+            shares.push(Share::from_bytes(&bytes)?);
+        }
+        Ok(shares)
+    }
+
+    fn encrypt_split(
+        &self,
+        plaintext: &[u8],
+        recipients: &[KeyIdentity],
+        env: &str,
+        threshold: u8,
+    ) -> Result<Vec<u8>> {
+        // One share per key: a key listed twice must not count twice
+        let mut seen = HashSet::new();
+        let recipients: Vec<&KeyIdentity> = recipients
+            .iter()
+            .filter(|r| seen.insert(r.public_key.as_str()))
+            .collect();
+        let count = u8::try_from(recipients.len()).map_err(|_| VaulticError::EncryptionFailed {
+            reason: "Threshold encryption supports at most 255 recipients".into(),
+        })?;
+        if threshold > count {
+            return Err(VaulticError::InvalidConfig {
+                detail: format!(
+                    "environments.{env}.threshold is {threshold}, but only {count} \
+                     recipient(s) would get a share"
+                ),
+            });
+        }
+
+        let payload_identity = age::x25519::Identity::generate();
+        let payload_key = payload_identity.to_public().to_string();
+        let payload = self.inner.encrypt(
+            plaintext,
+            &[KeyIdentity {
+                public_key: payload_key.clone(),
+                label: None,
+                added_at: None,
+            }],
+        )?;
+
+        let secret = payload_identity.to_string();
+        let secret = secret.expose_secret().as_bytes();
+        let mut pool = vec![0u8; secret.len() * (threshold as usize - 1)];
+        getrandom::fill(&mut pool).map_err(|e| VaulticError::EncryptionFailed {
+            reason: format!("No secure randomness available: {e}"),
+        })?;
+        let mut unused = pool.as_slice();
+        let shares = shamir::split(secret, threshold, count, |buf| {
+            let (taken, rest) = unused.split_at(buf.len());
+            buf.copy_from_slice(taken);
+            unused = rest;
+        })?;
+        pool.fill(0);
+
+        let shares = recipients
+            .iter()
+            .zip(shares)
+            .map(|(recipient, share)| {
+                let ciphertext = self
+                    .inner
+                    .encrypt(&share.to_bytes(), std::slice::from_ref(*recipient))?;
+                Ok(EnvelopeShare {
+                    recipient: recipient.public_key.clone(),
+                    index: share.x,
+                    share: String::from_utf8_lossy(&ciphertext).into_owned(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let envelope = Envelope {
+            format: ENVELOPE_FORMAT.into(),
+            version: ENVELOPE_VERSION,
+            env: env.to_string(),
+            threshold,
+            payload_key,
+            shares,
+            payload: String::from_utf8_lossy(&payload).into_owned(),
+        };
+        let mut json =
+            serde_json::to_vec_pretty(&envelope).map_err(|e| VaulticError::EncryptionFailed {
+                reason: format!("Failed to serialize threshold envelope: {e}"),
+            })?;
+        json.push(b'\n');
+        Ok(json)
+    }
+}
+
+impl CipherBackend for ThresholdBackend {
+    fn encrypt(&self, plaintext: &[u8], recipients: &[KeyIdentity]) -> Result<Vec<u8>> {
+        match &self.threshold {
+            Some((env, threshold)) => self.encrypt_split(plaintext, recipients, env, *threshold),
+            None => self.inner.encrypt(plaintext, recipients),
+        }
+    }
+
+    fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>> {
+        let Some(envelope) = Envelope::parse(ciphertext) else {
+            return self.inner.decrypt(ciphertext);
+        };
+        let envelope = envelope?;
+
+        let mut shares: Vec<Share> = self.own_share(&envelope)?.into_iter().collect();
+        for share in self.released_shares(&envelope)? {
+            if !shares.iter().any(|s| s.x == share.x) {
+                shares.push(share);
+            }
+        }
+        let need = envelope.threshold as usize;
+        if shares.len() < need {
+            return Err(VaulticError::SharesRequired {
+                env: envelope.env,
+                have: shares.len(),
+                need,
+            });
+        }
+
+        // Wiped on drop, like the identity parsed from it
+        let secret = Zeroizing::new(shamir::combine(&shares[..need])?);
+        let identity = std::str::from_utf8(&secret)
+            .ok()
+            .and_then(|s| s.parse::<age::x25519::Identity>().ok())
+            .filter(|identity| identity.to_public().to_string() == envelope.payload_key)
+            .ok_or_else(|| VaulticError::EncryptionFailed {
+                reason: format!(
+                    "The shares of '{}' do not rebuild its key. The file may have been \
+                     tampered with.",
+                    envelope.env
+                ),
+            })?;
+        AgeBackend::decrypt_with(
+            std::iter::once(&identity as &dyn age::Identity),
+            envelope.payload.as_bytes(),
+        )
+    }
+
+    fn name(&self) -> &str {
+        "age"
+    }
+}
+
+/// Parse `content`, failing when it is not a threshold envelope.
+fn parse_envelope(content: &[u8]) -> Result<Envelope> {
+    Envelope::parse(content).unwrap_or_else(|| {
+        Err(VaulticError::EncryptionFailed {
+            reason: "Not a threshold-encrypted file".into(),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Holder {
+        key: KeyIdentity,
+        secret: String,
+    }
+
+    impl Holder {
+        fn new() -> Self {
+            let identity = age::x25519::Identity::generate();
+            Self {
+                key: KeyIdentity {
+                    public_key: identity.to_public().to_string(),
+                    label: None,
+                    added_at: None,
+                },
+                secret: identity.to_string().expose_secret().to_string(),
+            }
+        }
+
+        fn backend(&self) -> ThresholdBackend {
+            ThresholdBackend::new(AgeBackend::from_key_data(self.secret.clone()))
+        }
+    }
+
+    fn encrypt_for(holders: &[Holder], plaintext: &[u8]) -> Vec<u8> {
+        let recipients: Vec<KeyIdentity> = holders.iter().map(|h| h.key.clone()).collect();
+        ThresholdBackend::new(AgeBackend::from_key_data(String::new()))
+            .with_threshold("prod", 2)
+            .encrypt(plaintext, &recipients)
+            .unwrap()
+    }
+
+    #[test]
+    fn two_of_three_need_a_released_share() {
+        let holders = [Holder::new(), Holder::new(), Holder::new()];
+        let ciphertext = encrypt_for(&holders, b"DB_PASSWORD=s3cret\n");
+        assert!(ThresholdBackend::is_envelope(&ciphertext));
+
+        match holders[0].backend().decrypt(&ciphertext) {
+            Err(VaulticError::SharesRequired { env, have, need }) => {
+                assert_eq!((env.as_str(), have, need), ("prod", 1, 2));
+            }
+            other => panic!("expected SharesRequired, got {other:?}"),
+        }
+
+        let (index, released) = holders[2]
+            .backend()
+            .release(&ciphertext, &holders[0].key)
+            .unwrap();
+        assert_eq!(index, 3);
+        let coordinator = holders[0].backend().with_released(vec![released]);
+        assert_eq!(
+            coordinator.decrypt(&ciphertext).unwrap(),
+            b"DB_PASSWORD=s3cret\n"
+        );
+    }
+
+    #[test]
+    fn shares_of_another_encryption_are_rejected() {
+        let holders = [Holder::new(), Holder::new()];
+        let old = encrypt_for(&holders, b"A=1\n");
+        let new = encrypt_for(&holders, b"A=1\n");
+
+        let (_, released) = holders[1].backend().release(&old, &holders[0].key).unwrap();
+        let coordinator = holders[0].backend().with_released(vec![released]);
+        assert!(matches!(
+            coordinator.decrypt(&new),
+            Err(VaulticError::EncryptionFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn ordinary_age_files_pass_through() {
+        let holder = Holder::new();
+        let backend = holder.backend();
+        let ciphertext = backend
+            .encrypt(b"A=1\n", std::slice::from_ref(&holder.key))
+            .unwrap();
+        assert!(!ThresholdBackend::is_envelope(&ciphertext));
+        assert_eq!(backend.decrypt(&ciphertext).unwrap(), b"A=1\n");
+    }
+
+    #[test]
+    fn duplicate_recipients_get_one_share() {
+        let holders = [Holder::new(), Holder::new()];
+        let recipients = [
+            holders[0].key.clone(),
+            holders[0].key.clone(),
+            holders[1].key.clone(),
+        ];
+        let ciphertext = ThresholdBackend::new(AgeBackend::from_key_data(String::new()))
+            .with_threshold("prod", 2)
+            .encrypt(b"A=1\n", &recipients)
+            .unwrap();
+
+        let envelope = parse_envelope(&ciphertext).unwrap();
+        assert_eq!(envelope.shares.len(), 2);
+        assert!(matches!(
+            holders[0].backend().decrypt(&ciphertext),
+            Err(VaulticError::SharesRequired {
+                have: 1,
+                need: 2,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn identities_unlocking_several_shares_are_refused() {
+        let holders = [Holder::new(), Holder::new(), Holder::new()];
+        let ciphertext = encrypt_for(&holders, b"A=1\n");
+
+        let both = format!("{}\n{}\n", holders[0].secret, holders[1].secret);
+        let backend = ThresholdBackend::new(AgeBackend::from_key_data(both));
+        let err = backend.decrypt(&ciphertext).unwrap_err().to_string();
+        assert!(err.contains("unlock 2 shares of 'prod'"), "{err}");
+        assert!(backend.release(&ciphertext, &holders[2].key).is_err());
+    }
+
+    #[test]
+    fn threshold_above_recipient_count_is_rejected() {
+        let holder = Holder::new();
+        let result = holder
+            .backend()
+            .with_threshold("prod", 2)
+            .encrypt(b"A=1\n", std::slice::from_ref(&holder.key));
+        assert!(matches!(result, Err(VaulticError::InvalidConfig { .. })));
+    }
+}
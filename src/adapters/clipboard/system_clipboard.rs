@@ -0,0 +1,83 @@
+use std::time::Instant;
+
+use crate::core::errors::{Result, VaulticError};
+
+/// The system clipboard, through arboard.
+///
+/// On Linux the clipboard belongs to the process that set it: text copied
+/// here lasts after Vaultic exits only when a clipboard manager takes it
+/// over, which desktop sessions usually run.
+pub struct SystemClipboard {
+    inner: arboard::Clipboard,
+}
+
+impl SystemClipboard {
+    /// Connect to the clipboard. Fails without a desktop session, e.g.
+    /// over SSH or in CI.
+    pub fn open() -> Result<Self> {
+        arboard::Clipboard::new()
+            .map(|inner| Self { inner })
+            .map_err(unavailable)
+    }
+
+    /// Put `text` on the clipboard.
+    pub fn copy(&mut self, text: &str) -> Result<()> {
+        self.inner.set_text(text).map_err(unavailable)
+    }
+
+    /// Put `text` on the clipboard, kept out of clipboard history where
+    /// the platform supports it, and block until `deadline`. It is then
+    /// cleared, unless something else was copied in the meantime.
+    pub fn copy_until(&mut self, text: &str, deadline: Instant) -> Result<()> {
+        #[cfg(all(
+            unix,
+            not(any(target_os = "macos", target_os = "android", target_os = "emscripten"))
+        ))]
+        {
+            use arboard::SetExtLinux;
+            // Serves the selection until the deadline or until another
+            // application takes it over
+            self.inner
+                .set()
+                .exclude_from_history()
+                .wait_until(deadline)
+                .text(text)
+                .map_err(unavailable)?;
+        }
+        #[cfg(not(all(
+            unix,
+            not(any(target_os = "macos", target_os = "android", target_os = "emscripten"))
+        )))]
+        {
+            #[cfg(target_os = "macos")]
+            use arboard::SetExtApple;
+            #[cfg(windows)]
+            use arboard::SetExtWindows;
+            self.inner
+                .set()
+                .exclude_from_history()
+                .text(text)
+                .map_err(unavailable)?;
+            std::thread::sleep(deadline.saturating_duration_since(Instant::now()));
+        }
+
+        if self.inner.get_text().is_ok_and(|current| current == text) {
+            self.inner.clear().map_err(unavailable)?;
+        }
+        Ok(())
+    }
+}
+
+/// Clear the clipboard, e.g. when interrupted while holding a secret.
+/// Errors are ignored.
+pub fn clear() {
+    if let Ok(mut clipboard) = arboard::Clipboard::new() {
+        let _ = clipboard.clear();
+    }
+}
+
+fn unavailable(e: arboard::Error) -> VaulticError {
+    VaulticError::ClipboardUnavailable {
+        detail: e.to_string(),
+    }
+}
@@ -0,0 +1 @@
+pub mod system_clock;
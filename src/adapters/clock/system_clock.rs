@@ -0,0 +1,60 @@
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::core::traits::clock::Clock;
+
+/// Clock backed by the system time.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Clock that always returns the same instant.
+///
+/// Used under `VAULTIC_TEST_MODE` so snapshot tests of audit logs
+/// and generated files are stable across runs.
+pub struct FixedClock {
+    instant: DateTime<Utc>,
+}
+
+impl FixedClock {
+    /// Create a clock frozen at `instant`.
+    pub fn new(instant: DateTime<Utc>) -> Self {
+        Self { instant }
+    }
+
+    /// The instant used by test mode: 2026-01-01T00:00:00Z.
+    pub fn test_default() -> Self {
+        Self::new(
+            Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0)
+                .single()
+                .expect("valid fixed timestamp"),
+        )
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.instant
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_clock_never_moves() {
+        let clock = FixedClock::test_default();
+        assert_eq!(clock.now(), clock.now());
+        assert_eq!(clock.now().to_rfc3339(), "2026-01-01T00:00:00+00:00");
+    }
+
+    #[test]
+    fn system_clock_is_close_to_now() {
+        let delta = Utc::now() - SystemClock.now();
+        assert!(delta.num_seconds().abs() < 5);
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+use std::process::Command;
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::lease::Lease;
+use crate::core::traits::credential_provider::CredentialProvider;
+
+/// Temporary credentials from `aws sts assume-role`.
+///
+/// Talks to AWS through the `aws` CLI, so the credentials used to assume
+/// the role are whatever the CLI is configured with.
+pub struct AwsStsProvider {
+    pub name: String,
+    pub role_arn: String,
+    pub duration_seconds: u64,
+    pub region: Option<String>,
+}
+
+impl CredentialProvider for AwsStsProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn lease(&self, now: DateTime<Utc>) -> Result<Lease> {
+        let mut cmd = Command::new("aws");
+        cmd.args([
+            "sts",
+            "assume-role",
+            "--role-arn",
+            &self.role_arn,
+            "--role-session-name",
+            &format!("vaultic-{}", self.name),
+            "--duration-seconds",
+            &self.duration_seconds.to_string(),
+            "--output",
+            "json",
+        ]);
+        if let Some(region) = &self.region {
+            cmd.args(["--region", region]);
+        }
+        let out = cmd.output().map_err(|e| VaulticError::InvalidConfig {
+            detail: format!(
+                "Failed to run the AWS CLI: {e}\n\n  \
+                 Solutions:\n    \
+                 → Install AWS CLI v2: https://aws.amazon.com/cli/\n    \
+                 → Configure credentials: aws configure (or set AWS_PROFILE)"
+            ),
+        })?;
+        if !out.status.success() {
+            return Err(VaulticError::InvalidConfig {
+                detail: format!(
+                    "AWS CLI could not assume {}: {}",
+                    self.role_arn,
+                    String::from_utf8_lossy(&out.stderr).trim()
+                ),
+            });
+        }
+
+        let response: Value = serde_json::from_slice(&out.stdout).unwrap_or(Value::Null);
+        credentials(&response, now).ok_or_else(|| VaulticError::InvalidConfig {
+            detail: "Unexpected response from aws sts assume-role: no Credentials".into(),
+        })
+    }
+}
+
+/// The lease in an `assume-role` response.
+fn credentials(response: &Value, now: DateTime<Utc>) -> Option<Lease> {
+    let creds = &response["Credentials"];
+    let field = |name: &str| creds[name].as_str().map(String::from);
+    let vars = BTreeMap::from([
+        ("AWS_ACCESS_KEY_ID".to_string(), field("AccessKeyId")?),
+        (
+            "AWS_SECRET_ACCESS_KEY".to_string(),
+            field("SecretAccessKey")?,
+        ),
+        ("AWS_SESSION_TOKEN".to_string(), field("SessionToken")?),
+    ]);
+    let expires_at = field("Expiration")
+        .and_then(|e| DateTime::parse_from_rfc3339(&e).ok())
+        .map(|e| e.with_timezone(&Utc));
+    Some(Lease {
+        vars,
+        issued_at: now,
+        expires_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reads_assume_role_response() {
+        let now = Utc::now();
+        let response = json!({
+            "Credentials": {
+                "AccessKeyId": "ASIAEXAMPLE",
+                "SecretAccessKey": "secret",
+                "SessionToken": "token",
+                "Expiration": "2026-03-01T12:00:00+00:00"
+            },
+            "AssumedRoleUser": { "Arn": "arn:aws:sts::123:assumed-role/deploy/vaultic-aws" }
+        });
+
+        let lease = credentials(&response, now).unwrap();
+        assert_eq!(lease.vars["AWS_ACCESS_KEY_ID"], "ASIAEXAMPLE");
+        assert_eq!(lease.vars["AWS_SESSION_TOKEN"], "token");
+        assert_eq!(
+            lease.expires_at.unwrap().to_rfc3339(),
+            "2026-03-01T12:00:00+00:00"
+        );
+        assert!(credentials(&json!({}), now).is_none());
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+use std::process::Command;
+
+use chrono::{DateTime, Duration, Utc};
+use serde_json::Value;
+
+use crate::adapters::parsers::dotenv_parser::DotenvParser;
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::lease::Lease;
+use crate::core::traits::credential_provider::CredentialProvider;
+use crate::core::traits::parser::ConfigParser;
+
+/// Credentials printed by any command: `KEY=value` lines or a JSON
+/// object of variables, optionally with an RFC 3339 `expires_at`.
+pub struct CommandProvider {
+    pub name: String,
+    pub command: Vec<String>,
+    pub ttl_seconds: Option<u64>,
+}
+
+impl CredentialProvider for CommandProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn lease(&self, now: DateTime<Utc>) -> Result<Lease> {
+        let Some((program, args)) = self.command.split_first() else {
+            return Err(VaulticError::InvalidConfig {
+                detail: format!("[leases.{}] has an empty command", self.name),
+            });
+        };
+        let out =
+            Command::new(program)
+                .args(args)
+                .output()
+                .map_err(|e| VaulticError::InvalidConfig {
+                    detail: format!("Failed to run '{program}' for lease '{}': {e}", self.name),
+                })?;
+        if !out.status.success() {
+            return Err(VaulticError::InvalidConfig {
+                detail: format!(
+                    "'{program}' failed for lease '{}': {}",
+                    self.name,
+                    String::from_utf8_lossy(&out.stderr).trim()
+                ),
+            });
+        }
+        let ttl = self
+            .ttl_seconds
+            .map(|s| Duration::seconds(i64::try_from(s).unwrap_or(i64::MAX)));
+        parse_output(&String::from_utf8_lossy(&out.stdout), now, ttl)
+    }
+}
+
+/// The lease in a command's output.
+fn parse_output(stdout: &str, now: DateTime<Utc>, ttl: Option<Duration>) -> Result<Lease> {
+    if !stdout.trim_start().starts_with('{') {
+        let file = DotenvParser.parse(stdout)?;
+        let vars = file
+            .entries()
+            .map(|e| (e.key.clone(), e.value.clone()))
+            .collect();
+        return Ok(Lease::for_ttl(vars, now, ttl));
+    }
+
+    let Ok(Value::Object(object)) = serde_json::from_str::<Value>(stdout) else {
+        return Err(VaulticError::InvalidConfig {
+            detail: "Lease command output is not a JSON object of variables".into(),
+        });
+    };
+    let mut expires_at = None;
+    let mut vars = BTreeMap::new();
+    for (key, value) in object {
+        let value = match value {
+            Value::String(s) => s,
+            other => other.to_string(),
+        };
+        if key == "expires_at" {
+            expires_at = DateTime::parse_from_rfc3339(&value)
+                .ok()
+                .map(|e| e.with_timezone(&Utc));
+        } else {
+            vars.insert(key, value);
+        }
+    }
+    let mut lease = Lease::for_ttl(vars, now, ttl);
+    if expires_at.is_some() {
+        lease.expires_at = expires_at;
+    }
+    Ok(lease)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_dotenv_output_with_ttl() {
+        let now = Utc::now();
+        let lease = parse_output(
+            "DB_USER=v-app-1\nDB_PASS=x\n",
+            now,
+            Some(Duration::seconds(60)),
+        )
+        .unwrap();
+        assert_eq!(lease.vars["DB_USER"], "v-app-1");
+        assert_eq!(lease.expires_at, Some(now + Duration::seconds(60)));
+    }
+
+    #[test]
+    fn json_expires_at_wins_over_ttl() {
+        let now = Utc::now();
+        let lease = parse_output(
+            r#"{"TOKEN": "abc", "PORT": 5432, "expires_at": "2026-03-01T12:00:00Z"}"#,
+            now,
+            Some(Duration::seconds(60)),
+        )
+        .unwrap();
+        assert_eq!(lease.vars.len(), 2);
+        assert_eq!(lease.vars["PORT"], "5432");
+        assert_eq!(
+            lease.expires_at.unwrap().to_rfc3339(),
+            "2026-03-01T12:00:00+00:00"
+        );
+    }
+}
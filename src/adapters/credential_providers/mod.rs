@@ -0,0 +1,2 @@
+pub mod aws_sts;
+pub mod command;
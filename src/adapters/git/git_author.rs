@@ -0,0 +1,61 @@
+use std::process::Command;
+
+use crate::core::traits::author::AuthorProvider;
+
+/// Reads the author from `git config user.name` / `user.email`.
+pub struct GitAuthorProvider;
+
+impl GitAuthorProvider {
+    fn git_config(key: &str) -> Option<String> {
+        Command::new("git")
+            .args(["config", key])
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+    }
+}
+
+impl AuthorProvider for GitAuthorProvider {
+    /// Returns `("unknown", None)` if git is not available.
+    fn author(&self) -> (String, Option<String>) {
+        let name = Self::git_config("user.name").unwrap_or_else(|| "unknown".to_string());
+        let email = Self::git_config("user.email").filter(|e| !e.is_empty());
+        (name, email)
+    }
+}
+
+/// Author provider that always returns the same identity.
+pub struct FixedAuthorProvider {
+    pub name: String,
+    pub email: Option<String>,
+}
+
+impl FixedAuthorProvider {
+    /// The identity used by test mode.
+    pub fn test_default() -> Self {
+        Self {
+            name: "vaultic-test".to_string(),
+            email: Some("test@vaultic.invalid".to_string()),
+        }
+    }
+}
+
+impl AuthorProvider for FixedAuthorProvider {
+    fn author(&self) -> (String, Option<String>) {
+        (self.name.clone(), self.email.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_author_is_stable() {
+        let provider = FixedAuthorProvider::test_default();
+        let (name, email) = provider.author();
+        assert_eq!(name, "vaultic-test");
+        assert_eq!(email.as_deref(), Some("test@vaultic.invalid"));
+    }
+}
@@ -0,0 +1,183 @@
+use std::path::Path;
+use std::process::Command;
+
+use crate::core::errors::{Result, VaulticError};
+
+/// Read `path` as it was at `rev` (e.g. `origin/main`).
+///
+/// `path` is relative to the current directory (absolute paths under it
+/// are accepted). Returns `Ok(None)` when the file did not exist at that
+/// revision.
+pub fn show_file(rev: &str, path: &Path) -> Result<Option<Vec<u8>>> {
+    verify_rev(rev)?;
+
+    let cwd = std::env::current_dir()?;
+    let path = path.strip_prefix(&cwd).unwrap_or(path);
+
+    let spec = format!("{rev}:./{}", path.to_string_lossy().replace('\\', "/"));
+    let output = Command::new("git")
+        .args(["show", &spec])
+        .output()
+        .map_err(|e| VaulticError::InvalidConfig {
+            detail: format!("Failed to run git: {e}"),
+        })?;
+
+    Ok(output.status.success().then_some(output.stdout))
+}
+
+/// Files changed by the commit `rev`, relative to the repository root.
+/// Works for the root commit too.
+pub fn commit_files(rev: &str) -> Result<Vec<String>> {
+    verify_rev(rev)?;
+    let output = Command::new("git")
+        .args([
+            "diff-tree",
+            "--no-commit-id",
+            "--name-only",
+            "-r",
+            "--root",
+            rev,
+        ])
+        .output()
+        .map_err(|e| VaulticError::InvalidConfig {
+            detail: format!("Failed to run git: {e}"),
+        })?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(String::from)
+        .collect())
+}
+
+/// Files tracked by git under the current directory, relative to it.
+pub fn tracked_files() -> Result<Vec<String>> {
+    let output = Command::new("git")
+        .args(["ls-files", "-z"])
+        .output()
+        .map_err(|e| VaulticError::InvalidConfig {
+            detail: format!("Failed to run git: {e}"),
+        })?;
+    if !output.status.success() {
+        return Err(not_a_repository());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+/// Every commit reachable from any ref, oldest first.
+pub fn all_commits() -> Result<Vec<String>> {
+    let output = Command::new("git")
+        .args(["rev-list", "--all", "--reverse"])
+        .output()
+        .map_err(|e| VaulticError::InvalidConfig {
+            detail: format!("Failed to run git: {e}"),
+        })?;
+    if !output.status.success() {
+        return Err(not_a_repository());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(String::from)
+        .collect())
+}
+
+/// Read `path` (relative to the repository root, as listed by
+/// [`commit_files`]) as it was at the commit `rev`. Returns `Ok(None)`
+/// when the commit has no such file.
+pub fn show_blob(rev: &str, path: &str) -> Result<Option<Vec<u8>>> {
+    let output = Command::new("git")
+        .args(["show", &format!("{rev}:{path}")])
+        .output()
+        .map_err(|e| VaulticError::InvalidConfig {
+            detail: format!("Failed to run git: {e}"),
+        })?;
+    Ok(output.status.success().then_some(output.stdout))
+}
+
+/// Files that differ between the commits `from` and `to`, relative to
+/// the repository root.
+pub fn changed_files(from: &str, to: &str) -> Result<Vec<String>> {
+    verify_rev(from)?;
+    verify_rev(to)?;
+    let output = Command::new("git")
+        .args(["diff", "--name-only", from, to])
+        .output()
+        .map_err(|e| VaulticError::InvalidConfig {
+            detail: format!("Failed to run git: {e}"),
+        })?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(String::from)
+        .collect())
+}
+
+/// The last commit reachable from `rev` that changed `path`, or `None`
+/// when no commit did.
+pub fn last_commit(rev: &str, path: &Path) -> Result<Option<String>> {
+    let output = Command::new("git")
+        .args(["rev-list", "-1", rev, "--"])
+        .arg(path)
+        .output()
+        .map_err(|e| VaulticError::InvalidConfig {
+            detail: format!("Failed to run git: {e}"),
+        })?;
+    let commit = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Ok((output.status.success() && !commit.is_empty()).then_some(commit))
+}
+
+/// Whether `ancestor` is reachable from `descendant`.
+pub fn is_ancestor(ancestor: &str, descendant: &str) -> bool {
+    Command::new("git")
+        .args(["merge-base", "--is-ancestor", ancestor, descendant])
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+/// Abbreviated hash of `rev`, e.g. `3f2a9c1`.
+pub fn short_hash(rev: &str) -> Result<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", rev])
+        .output()
+        .map_err(|e| VaulticError::InvalidConfig {
+            detail: format!("Failed to run git: {e}"),
+        })?;
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Fail with a clear message if `rev` does not name a commit.
+fn verify_rev(rev: &str) -> Result<()> {
+    let ok = Command::new("git")
+        .args([
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{rev}^{{commit}}"),
+        ])
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false);
+
+    if ok {
+        Ok(())
+    } else {
+        Err(VaulticError::InvalidConfig {
+            detail: format!(
+                "Unknown git revision: '{rev}'\n\n  \
+                 Solutions:\n    \
+                 → Run inside a git repository\n    \
+                 → Fetch the base branch first (e.g. git fetch origin main)"
+            ),
+        })
+    }
+}
+
+fn not_a_repository() -> VaulticError {
+    VaulticError::InvalidConfig {
+        detail: "Not a git repository.\n\n  \
+                 Run the command inside the repository to scan."
+            .into(),
+    }
+}
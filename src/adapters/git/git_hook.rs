@@ -52,24 +52,129 @@ if [ -n "$blocked" ]; then
 fi
 "#;
 
-/// Install the Vaultic pre-commit hook.
+/// The post-commit hook script that records commits made with
+/// `--no-verify`, which skips pre-commit but not post-commit hooks.
+const POST_COMMIT_SCRIPT: &str = r#"#!/bin/sh
+# vaultic-managed-hook
+# Vaultic post-commit hook — records commits that skipped the pre-commit hook.
+# Installed by: vaultic hook install --post-commit
+# Remove with:  vaultic hook uninstall
+
+if command -v vaultic >/dev/null 2>&1; then
+    vaultic hook post-commit || true
+fi
+"#;
+
+/// The pre-push hook script that blocks pushing environments that were
+/// not re-encrypted after their recipients list changed.
+const PRE_PUSH_SCRIPT: &str = r#"#!/bin/sh
+# vaultic-managed-hook
+# Vaultic pre-push hook — blocks pushing environments encrypted for old recipients.
+# Installed by: vaultic hook install --hook pre-push
+# Remove with:  vaultic hook uninstall
+
+if ! command -v vaultic >/dev/null 2>&1; then
+    echo "  vaultic not found in PATH — skipping the Vaultic pre-push check" >&2
+    exit 0
+fi
+vaultic hook pre-push
+"#;
+
+/// The post-merge hook script that points out encrypted environments
+/// changed by a pull.
+const POST_MERGE_SCRIPT: &str = r#"#!/bin/sh
+# vaultic-managed-hook
+# Vaultic post-merge hook — warns when encrypted environments changed upstream.
+# Installed by: vaultic hook install --hook post-merge
+# Remove with:  vaultic hook uninstall
+
+if command -v vaultic >/dev/null 2>&1; then
+    vaultic hook post-merge || true
+fi
+"#;
+
+/// The post-checkout hook script: the same warning after switching
+/// branches.
+const POST_CHECKOUT_SCRIPT: &str = r#"#!/bin/sh
+# vaultic-managed-hook
+# Vaultic post-checkout hook — warns when encrypted environments differ on the new branch.
+# Installed by: vaultic hook install --hook post-checkout
+# Remove with:  vaultic hook uninstall
+
+if command -v vaultic >/dev/null 2>&1; then
+    vaultic hook post-checkout "$1" "$2" "$3" || true
+fi
+"#;
+
+/// File in the git directory that holds an unreported hook bypass.
+const BYPASS_FILE: &str = "vaultic-hook-bypass";
+
+/// The git hooks Vaultic can manage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookKind {
+    PreCommit,
+    PostCommit,
+    PrePush,
+    PostMerge,
+    PostCheckout,
+}
+
+impl HookKind {
+    /// Every managed hook, in the order they are reported.
+    pub const ALL: [HookKind; 5] = [
+        HookKind::PreCommit,
+        HookKind::PostCommit,
+        HookKind::PrePush,
+        HookKind::PostMerge,
+        HookKind::PostCheckout,
+    ];
+
+    /// Git's name for the hook, e.g. `pre-push`.
+    pub fn name(self) -> &'static str {
+        match self {
+            HookKind::PreCommit => "pre-commit",
+            HookKind::PostCommit => "post-commit",
+            HookKind::PrePush => "pre-push",
+            HookKind::PostMerge => "post-merge",
+            HookKind::PostCheckout => "post-checkout",
+        }
+    }
+
+    /// Parse a hook name as accepted by `vaultic hook install --hook`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+
+    fn script(self) -> &'static str {
+        match self {
+            HookKind::PreCommit => PRE_COMMIT_SCRIPT,
+            HookKind::PostCommit => POST_COMMIT_SCRIPT,
+            HookKind::PrePush => PRE_PUSH_SCRIPT,
+            HookKind::PostMerge => POST_MERGE_SCRIPT,
+            HookKind::PostCheckout => POST_CHECKOUT_SCRIPT,
+        }
+    }
+}
+
+/// Install the Vaultic hook of the given kind.
 ///
-/// If a pre-commit hook already exists and is not managed by Vaultic,
+/// If a hook of that kind already exists and is not managed by Vaultic,
 /// returns an error to avoid overwriting user hooks.
-pub fn install(git_dir: &Path) -> Result<()> {
+pub fn install(git_dir: &Path, kind: HookKind) -> Result<()> {
+    let name = kind.name();
     let hooks_dir = git_dir.join("hooks");
     if !hooks_dir.exists() {
         fs::create_dir_all(&hooks_dir)?;
     }
 
-    let hook_path = hooks_dir.join("pre-commit");
+    let hook_path = hooks_dir.join(name);
 
     if hook_path.exists() {
         let content = fs::read_to_string(&hook_path)?;
         if !content.contains(HOOK_MARKER) {
             return Err(VaulticError::HookError {
                 detail: format!(
-                    "A pre-commit hook already exists at {}\n\n  \
+                    "A {name} hook already exists at {}\n\n  \
                      It was not installed by Vaultic and will not be overwritten.\n  \
                      To replace it, remove the existing hook first:\n  \
                      rm {}",
@@ -80,7 +185,7 @@ pub fn install(git_dir: &Path) -> Result<()> {
         }
     }
 
-    fs::write(&hook_path, PRE_COMMIT_SCRIPT)?;
+    fs::write(&hook_path, kind.script())?;
 
     // Make executable on Unix
     #[cfg(unix)]
@@ -93,29 +198,69 @@ pub fn install(git_dir: &Path) -> Result<()> {
     Ok(())
 }
 
-/// Uninstall the Vaultic pre-commit hook.
+/// Uninstall every hook Vaultic installed and return their kinds.
 ///
-/// Only removes the hook if it was installed by Vaultic (contains the marker).
-pub fn uninstall(git_dir: &Path) -> Result<()> {
-    let hook_path = git_dir.join("hooks").join("pre-commit");
-
-    if !hook_path.exists() {
-        return Err(VaulticError::HookError {
-            detail: "No pre-commit hook found. Nothing to uninstall.".into(),
-        });
+/// Only removes hooks containing the marker. Fails when there is none,
+/// explaining when a pre-commit hook exists but is not Vaultic's.
+pub fn uninstall(git_dir: &Path) -> Result<Vec<HookKind>> {
+    let mut removed = Vec::new();
+    for kind in HookKind::ALL {
+        let hook_path = git_dir.join("hooks").join(kind.name());
+        let managed = fs::read_to_string(&hook_path)
+            .map(|c| c.contains(HOOK_MARKER))
+            .unwrap_or(false);
+        if managed {
+            fs::remove_file(&hook_path)?;
+            removed.push(kind);
+        }
     }
 
-    let content = fs::read_to_string(&hook_path)?;
-    if !content.contains(HOOK_MARKER) {
+    if removed.is_empty() {
+        let detail = if git_dir.join("hooks/pre-commit").exists() {
+            "The pre-commit hook was not installed by Vaultic. Not removing it."
+        } else {
+            "No Vaultic hook found. Nothing to uninstall."
+        };
         return Err(VaulticError::HookError {
-            detail: "The pre-commit hook was not installed by Vaultic. Not removing it.".into(),
+            detail: detail.into(),
         });
     }
+    Ok(removed)
+}
+
+/// Whether the pre-commit hook would block committing `path`.
+///
+/// Mirrors the patterns in `PRE_COMMIT_SCRIPT`: `.env` and `.env.*` at
+/// the repository root, except templates, examples and `.enc` files.
+pub fn is_blocked(path: &str) -> bool {
+    (path == ".env" || path.starts_with(".env."))
+        && !path.ends_with(".template")
+        && !path.ends_with(".example")
+        && !path.ends_with(".enc")
+}
 
-    fs::remove_file(&hook_path)?;
+/// Remember a hook bypass so the next Vaultic command can report it.
+/// Appends to any bypass not yet reported.
+pub fn record_bypass(git_dir: &Path, message: &str) -> Result<()> {
+    use std::io::Write;
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(git_dir.join(BYPASS_FILE))?;
+    writeln!(file, "{message}")?;
     Ok(())
 }
 
+/// Take the unreported hook bypasses, if any, so each is shown once.
+pub fn take_bypasses(git_dir: &Path) -> Vec<String> {
+    let path = git_dir.join(BYPASS_FILE);
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+    let _ = fs::remove_file(&path);
+    content.lines().map(String::from).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -130,7 +275,7 @@ mod tests {
     #[test]
     fn install_creates_hook() {
         let git_dir = setup_git_dir();
-        install(git_dir.path()).unwrap();
+        install(git_dir.path(), HookKind::PreCommit).unwrap();
 
         let hook = git_dir.path().join("hooks/pre-commit");
         assert!(hook.exists());
@@ -143,10 +288,10 @@ mod tests {
     #[test]
     fn install_overwrites_vaultic_hook() {
         let git_dir = setup_git_dir();
-        install(git_dir.path()).unwrap();
+        install(git_dir.path(), HookKind::PreCommit).unwrap();
 
         // Install again — should succeed (same marker)
-        install(git_dir.path()).unwrap();
+        install(git_dir.path(), HookKind::PreCommit).unwrap();
     }
 
     #[test]
@@ -155,14 +300,14 @@ mod tests {
         let hook_path = git_dir.path().join("hooks/pre-commit");
         fs::write(&hook_path, "#!/bin/sh\necho custom hook\n").unwrap();
 
-        let result = install(git_dir.path());
+        let result = install(git_dir.path(), HookKind::PreCommit);
         assert!(result.is_err());
     }
 
     #[test]
     fn uninstall_removes_vaultic_hook() {
         let git_dir = setup_git_dir();
-        install(git_dir.path()).unwrap();
+        install(git_dir.path(), HookKind::PreCommit).unwrap();
         uninstall(git_dir.path()).unwrap();
 
         assert!(!git_dir.path().join("hooks/pre-commit").exists());
@@ -185,11 +330,60 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn uninstall_also_removes_post_commit_hook() {
+        let git_dir = setup_git_dir();
+        install(git_dir.path(), HookKind::PreCommit).unwrap();
+        install(git_dir.path(), HookKind::PostCommit).unwrap();
+        uninstall(git_dir.path()).unwrap();
+
+        assert!(!git_dir.path().join("hooks/post-commit").exists());
+    }
+
+    #[test]
+    fn uninstall_keeps_foreign_hooks_of_other_kinds() {
+        let git_dir = setup_git_dir();
+        install(git_dir.path(), HookKind::PrePush).unwrap();
+        fs::write(git_dir.path().join("hooks/post-merge"), "#!/bin/sh\n").unwrap();
+
+        assert_eq!(uninstall(git_dir.path()).unwrap(), vec![HookKind::PrePush]);
+        assert!(!git_dir.path().join("hooks/pre-push").exists());
+        assert!(git_dir.path().join("hooks/post-merge").exists());
+    }
+
+    #[test]
+    fn hook_names_round_trip() {
+        for kind in HookKind::ALL {
+            assert_eq!(HookKind::from_name(kind.name()), Some(kind));
+        }
+        assert_eq!(HookKind::from_name("pre-rebase"), None);
+    }
+
+    #[test]
+    fn is_blocked_mirrors_pre_commit_patterns() {
+        assert!(is_blocked(".env"));
+        assert!(is_blocked(".env.local"));
+        assert!(!is_blocked(".env.template"));
+        assert!(!is_blocked(".env.example"));
+        assert!(!is_blocked(".vaultic/dev.env.enc"));
+        assert!(!is_blocked("src/main.rs"));
+    }
+
+    #[test]
+    fn bypasses_are_reported_once() {
+        let git_dir = setup_git_dir();
+        record_bypass(git_dir.path(), "commit abc1234: .env").unwrap();
+        record_bypass(git_dir.path(), "commit def5678: .env.local").unwrap();
+
+        assert_eq!(take_bypasses(git_dir.path()).len(), 2);
+        assert!(take_bypasses(git_dir.path()).is_empty());
+    }
+
     #[test]
     fn install_creates_hooks_dir_if_missing() {
         let tmp = TempDir::new().unwrap();
         // No hooks dir exists
-        install(tmp.path()).unwrap();
+        install(tmp.path(), HookKind::PreCommit).unwrap();
 
         assert!(tmp.path().join("hooks/pre-commit").exists());
     }
@@ -1 +1,3 @@
+pub mod git_author;
+pub mod git_history;
 pub mod git_hook;
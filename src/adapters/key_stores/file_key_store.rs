@@ -1,4 +1,5 @@
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
 use crate::core::errors::{Result, VaulticError};
 use crate::core::models::key_identity::KeyIdentity;
@@ -15,15 +16,45 @@ use crate::core::traits::key_store::KeyStore;
 /// age1ql3z7hjy54pw3hyww5ayyfg7zqgvc7w3j2elw8zmrj2kg5sfn9aqmcac8p
 /// age1x9ynm5k7wz6v3mj8d4qr5tl2hj9nc0kp6w3f7s2y8x4u1v0n3m5q7f2p # dev2
 /// ```
+///
+/// The file is read once: `list` returns a cached copy afterwards, and
+/// `add`/`remove` update it along with the file. Clones share the cache,
+/// so re-encrypting several environments in one command reads
+/// `recipients.txt` a single time.
+///
+/// An environment can restrict who decrypts it with its own
+/// `recipients.<env>.txt` next to `recipients.txt`; see [`Self::for_env`].
 #[derive(Clone)]
 pub struct FileKeyStore {
     path: PathBuf,
+    cache: Arc<Mutex<Option<Vec<KeyIdentity>>>>,
 }
 
 impl FileKeyStore {
     /// Create a key store backed by the given file path.
     pub fn new(path: PathBuf) -> Self {
-        Self { path }
+        Self {
+            path,
+            cache: Arc::default(),
+        }
+    }
+
+    /// Path of the recipient list for `env_name` alone
+    /// (`recipients.<env>.txt`), whether or not it exists.
+    pub fn env_list_path(vaultic_dir: &Path, env_name: &str) -> PathBuf {
+        vaultic_dir.join(format!("recipients.{env_name}.txt"))
+    }
+
+    /// The store to encrypt `env_name` for: its own `recipients.<env>.txt`
+    /// when that file exists next to this store's file, otherwise `self`.
+    pub fn for_env(&self, env_name: &str) -> Self {
+        let dir = self.path.parent().unwrap_or(Path::new("."));
+        let path = Self::env_list_path(dir, env_name);
+        if path.exists() {
+            Self::new(path)
+        } else {
+            self.clone()
+        }
     }
 
     /// Return the file path this store reads from.
@@ -57,6 +88,33 @@ impl FileKeyStore {
         })
     }
 
+    /// Read and parse the file, bypassing the cache.
+    fn read(&self) -> Result<Vec<KeyIdentity>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content =
+            std::fs::read_to_string(&self.path).map_err(|_| VaulticError::FileNotFound {
+                path: self.path.clone(),
+            })?;
+
+        Ok(content.lines().filter_map(Self::parse_line).collect())
+    }
+
+    /// Write `identities` to the file and the cache.
+    fn write(&self, identities: Vec<KeyIdentity>) -> Result<()> {
+        std::fs::write(&self.path, Self::serialize(&identities))?;
+        *self.lock() = Some(identities);
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<Vec<KeyIdentity>>> {
+        // The cache only ever holds a complete list, so a panic while
+        // holding the lock cannot leave it half-written.
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
     /// Serialize all identities back to the file format.
     fn serialize(identities: &[KeyIdentity]) -> String {
         identities
@@ -86,21 +144,17 @@ impl KeyStore for FileKeyStore {
         }
 
         existing.push(identity.clone());
-        std::fs::write(&self.path, Self::serialize(&existing))?;
-        Ok(())
+        self.write(existing)
     }
 
     fn list(&self) -> Result<Vec<KeyIdentity>> {
-        if !self.path.exists() {
-            return Ok(Vec::new());
+        let mut cache = self.lock();
+        if let Some(identities) = cache.as_ref() {
+            return Ok(identities.clone());
         }
-
-        let content =
-            std::fs::read_to_string(&self.path).map_err(|_| VaulticError::FileNotFound {
-                path: self.path.clone(),
-            })?;
-
-        Ok(content.lines().filter_map(Self::parse_line).collect())
+        let identities = self.read()?;
+        *cache = Some(identities.clone());
+        Ok(identities)
     }
 
     fn remove(&self, public_key: &str) -> Result<()> {
@@ -117,8 +171,7 @@ impl KeyStore for FileKeyStore {
             .filter(|ki| ki.public_key != public_key)
             .collect();
 
-        std::fs::write(&self.path, Self::serialize(&filtered))?;
-        Ok(())
+        self.write(filtered)
     }
 }
 
@@ -141,6 +194,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn list_reads_the_file_once_and_clones_share_it() {
+        let (_dir, store) = temp_store();
+        std::fs::write(store.path(), "age1testkeyone\n").unwrap();
+        let clone = store.clone();
+        assert_eq!(store.list().unwrap().len(), 1);
+
+        // An edit made behind the store's back is not seen again...
+        std::fs::write(store.path(), "").unwrap();
+        assert_eq!(clone.list().unwrap().len(), 1);
+
+        // ...but the store's own writes are
+        clone.add(&sample_key("two")).unwrap();
+        assert_eq!(store.list().unwrap().len(), 2);
+        assert_eq!(
+            FileKeyStore::new(store.path().to_path_buf())
+                .list()
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn for_env_prefers_the_environment_list() {
+        let (dir, store) = temp_store();
+        std::fs::write(store.path(), "age1testkeyall\n").unwrap();
+        std::fs::write(dir.path().join("recipients.prod.txt"), "age1testkeyops\n").unwrap();
+
+        let prod = store.for_env("prod");
+        assert_eq!(prod.path(), dir.path().join("recipients.prod.txt"));
+        assert_eq!(prod.list().unwrap()[0].public_key, "age1testkeyops");
+        assert_eq!(store.for_env("dev").path(), store.path());
+    }
+
     #[test]
     fn list_empty_file_returns_empty() {
         let (_dir, store) = temp_store();
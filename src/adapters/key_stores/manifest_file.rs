@@ -0,0 +1,251 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::manifest::{ChainBreak, Manifest, StateLink, UnrecordedChange};
+
+/// Name of the manifest inside `.vaultic/`.
+pub const MANIFEST_FILE: &str = "manifest.toml";
+
+/// The checksums of `config.toml`, `recipients.txt` and every
+/// `recipients.<env>.txt`, stored as TOML next to them:
+///
+/// ```toml
+/// updated_at = "2026-10-16T09:30:00Z"
+///
+/// [files]
+/// "config.toml" = "3f1c…"
+/// "recipients.txt" = "9a0b…"
+///
+/// [[chains.prod]]
+/// at = "2026-10-16T09:30:00Z"
+/// prev_hash = "51d2…"
+/// new_hash = "c47e…"
+/// ```
+pub struct ManifestFile {
+    vaultic_dir: PathBuf,
+}
+
+impl ManifestFile {
+    /// The manifest of the project at `vaultic_dir`.
+    pub fn new(vaultic_dir: &Path) -> Self {
+        Self {
+            vaultic_dir: vaultic_dir.to_path_buf(),
+        }
+    }
+
+    /// Return the file path.
+    pub fn path(&self) -> PathBuf {
+        self.vaultic_dir.join(MANIFEST_FILE)
+    }
+
+    /// Read the manifest, or `None` for projects that have none yet.
+    pub fn load(&self) -> Result<Option<Manifest>> {
+        let path = self.path();
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path)?;
+        toml::from_str(&content)
+            .map(Some)
+            .map_err(|e| VaulticError::ParseError {
+                file: path,
+                detail: e.to_string(),
+            })
+    }
+
+    /// Checksums of the protected files as they are on disk now.
+    pub fn current(&self) -> Result<BTreeMap<String, String>> {
+        let mut files = BTreeMap::new();
+        for entry in std::fs::read_dir(&self.vaultic_dir)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if is_protected(&name) && entry.path().is_file() {
+                let hash = Sha256::digest(std::fs::read(entry.path())?);
+                files.insert(name, format!("{hash:x}"));
+            }
+        }
+        Ok(files)
+    }
+
+    /// Record the protected files as they are now, keeping the
+    /// environment chains.
+    pub fn record(&self, now: DateTime<Utc>) -> Result<()> {
+        let chains = self.load()?.map(|m| m.chains).unwrap_or_default();
+        self.save(&Manifest {
+            updated_at: now,
+            files: self.current()?,
+            chains,
+        })
+    }
+
+    /// Append a write of `env`'s encrypted file to its chain. Creates the
+    /// manifest, recording the protected files, when there is none.
+    pub fn record_state(
+        &self,
+        env: &str,
+        prev_hash: Option<String>,
+        new_hash: String,
+        now: DateTime<Utc>,
+    ) -> Result<()> {
+        let mut manifest = match self.load()? {
+            Some(manifest) => manifest,
+            None => Manifest {
+                updated_at: now,
+                files: self.current()?,
+                chains: BTreeMap::new(),
+            },
+        };
+        manifest.push_state(
+            env,
+            StateLink {
+                at: now,
+                prev_hash,
+                new_hash,
+            },
+        );
+        self.save(&manifest)
+    }
+
+    /// Start `env`'s chain again from `hash`, after a change made outside
+    /// Vaultic was reviewed. A `None` hash (the file is gone) drops it.
+    pub fn restart_chain(&self, env: &str, hash: Option<String>, now: DateTime<Utc>) -> Result<()> {
+        let Some(mut manifest) = self.load()? else {
+            return Ok(());
+        };
+        manifest.chains.remove(env);
+        if let Some(new_hash) = hash {
+            manifest.push_state(
+                env,
+                StateLink {
+                    at: now,
+                    prev_hash: None,
+                    new_hash,
+                },
+            );
+        }
+        self.save(&manifest)
+    }
+
+    /// Where `env`'s chain breaks, given the checksum of its encrypted
+    /// file now, or `None` when there is no manifest.
+    pub fn chain_breaks(
+        &self,
+        env: &str,
+        current: Option<&str>,
+    ) -> Result<Option<Vec<ChainBreak>>> {
+        Ok(self.load()?.map(|m| m.chain_breaks(env, current)))
+    }
+
+    /// Protected files that changed since the manifest was recorded, or
+    /// `None` when there is no manifest to compare with.
+    pub fn changes(&self) -> Result<Option<Vec<UnrecordedChange>>> {
+        let Some(manifest) = self.load()? else {
+            return Ok(None);
+        };
+        Ok(Some(manifest.changes(&self.current()?)))
+    }
+
+    fn save(&self, manifest: &Manifest) -> Result<()> {
+        let content = toml::to_string(manifest).map_err(|e| VaulticError::InvalidConfig {
+            detail: format!("Failed to serialize {MANIFEST_FILE}: {e}"),
+        })?;
+        std::fs::write(self.path(), content)?;
+        Ok(())
+    }
+}
+
+/// `config.toml`, `recipients.txt` or a `recipients.<env>.txt`.
+fn is_protected(name: &str) -> bool {
+    name == "config.toml"
+        || name == "recipients.txt"
+        || name
+            .strip_prefix("recipients.")
+            .and_then(|rest| rest.strip_suffix(".txt"))
+            .is_some_and(|env| !env.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::manifest::FileChange;
+
+    #[test]
+    fn detects_edits_made_after_recording() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("config.toml"), "[vaultic]\n").unwrap();
+        std::fs::write(dir.path().join("recipients.txt"), "age1alice\n").unwrap();
+        std::fs::write(dir.path().join("dev.env.enc"), "ciphertext").unwrap();
+        let file = ManifestFile::new(dir.path());
+        assert_eq!(file.changes().unwrap(), None);
+
+        file.record(Utc::now()).unwrap();
+        assert_eq!(file.changes().unwrap(), Some(Vec::new()));
+        assert_eq!(
+            file.load()
+                .unwrap()
+                .unwrap()
+                .files
+                .keys()
+                .collect::<Vec<_>>(),
+            ["config.toml", "recipients.txt"]
+        );
+
+        // Ciphertext is not protected; recipient lists are
+        std::fs::write(dir.path().join("dev.env.enc"), "other").unwrap();
+        std::fs::write(dir.path().join("recipients.txt"), "age1mallory\n").unwrap();
+        let changes = file.changes().unwrap().unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].file, "recipients.txt");
+        assert_eq!(changes[0].change, FileChange::Modified);
+    }
+
+    #[test]
+    fn recording_files_keeps_environment_chains() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("config.toml"), "[vaultic]\n").unwrap();
+        let file = ManifestFile::new(dir.path());
+
+        file.record_state("prod", None, "aaa".into(), Utc::now())
+            .unwrap();
+        file.record_state("prod", Some("aaa".into()), "bbb".into(), Utc::now())
+            .unwrap();
+        std::fs::write(dir.path().join("config.toml"), "[vaultic]\n# edited\n").unwrap();
+        file.record(Utc::now()).unwrap();
+
+        let manifest = file.load().unwrap().unwrap();
+        assert_eq!(manifest.chains["prod"].len(), 2);
+        assert_eq!(file.changes().unwrap(), Some(Vec::new()));
+        assert_eq!(
+            file.chain_breaks("prod", Some("bbb")).unwrap(),
+            Some(Vec::new())
+        );
+        assert_eq!(
+            file.chain_breaks("prod", Some("ccc"))
+                .unwrap()
+                .unwrap()
+                .len(),
+            1
+        );
+
+        file.restart_chain("prod", Some("ccc".into()), Utc::now())
+            .unwrap();
+        assert_eq!(
+            file.chain_breaks("prod", Some("ccc")).unwrap(),
+            Some(Vec::new())
+        );
+    }
+
+    #[test]
+    fn protects_config_and_recipient_lists_only() {
+        assert!(is_protected("config.toml"));
+        assert!(is_protected("recipients.txt"));
+        assert!(is_protected("recipients.prod.txt"));
+        assert!(!is_protected("recipients.txt.sig"));
+        assert!(!is_protected("members.toml"));
+        assert!(!is_protected("recipients..txt"));
+    }
+}
@@ -0,0 +1,61 @@
+use std::path::{Path, PathBuf};
+
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::member::Members;
+
+/// Name of the member file inside `.vaultic/`.
+pub const MEMBERS_FILE: &str = "members.toml";
+
+/// The names, emails and roles behind recipient keys, stored as TOML
+/// next to `recipients.txt`:
+///
+/// ```toml
+/// [[member]]
+/// key = "age1ql3z7hjy54pw3hyww5ayyfg7zqgvc7w3j2elw8zmrj2kg5sfn9aqmcac8p"
+/// name = "Alice"
+/// email = "alice@example.com"
+/// role = "admin"
+/// ```
+pub struct MembersFile {
+    path: PathBuf,
+}
+
+impl MembersFile {
+    /// The member file of the project at `vaultic_dir`.
+    pub fn new(vaultic_dir: &Path) -> Self {
+        Self {
+            path: vaultic_dir.join(MEMBERS_FILE),
+        }
+    }
+
+    /// Return the file path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether the file exists yet.
+    pub fn exists(&self) -> bool {
+        self.path.exists()
+    }
+
+    /// Read the members, or none when the file does not exist.
+    pub fn load(&self) -> Result<Members> {
+        if !self.path.exists() {
+            return Ok(Members::default());
+        }
+        let content = std::fs::read_to_string(&self.path)?;
+        toml::from_str(&content).map_err(|e| VaulticError::ParseError {
+            file: self.path.clone(),
+            detail: e.to_string(),
+        })
+    }
+
+    /// Write `members`, replacing the file.
+    pub fn save(&self, members: &Members) -> Result<()> {
+        let content = toml::to_string(members).map_err(|e| VaulticError::InvalidConfig {
+            detail: format!("Failed to serialize {MEMBERS_FILE}: {e}"),
+        })?;
+        std::fs::write(&self.path, content)?;
+        Ok(())
+    }
+}
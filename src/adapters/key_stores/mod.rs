@@ -1 +1,4 @@
 pub mod file_key_store;
+pub mod manifest_file;
+pub mod members_file;
+pub mod recipients_signature;
@@ -0,0 +1,143 @@
+use crate::core::errors::{Result, VaulticError};
+
+/// Verify a minisign signature of a recipient list against pinned admin
+/// keys. `name` is the list's file name, used in error messages.
+///
+/// `admin_keys` are minisign public keys, either the bare base64 line
+/// (`RWQ...`) or the full two-line `.pub` file content. The signature is
+/// accepted when any one of them verifies it.
+pub fn verify(name: &str, content: &[u8], signature: &[u8], admin_keys: &[String]) -> Result<()> {
+    let keys = admin_keys
+        .iter()
+        .map(|k| parse_public_key(k))
+        .collect::<Result<Vec<_>>>()?;
+
+    let signature = String::from_utf8_lossy(signature);
+    let signature = minisign_verify::Signature::decode(&signature).map_err(|e| {
+        VaulticError::RecipientsSignatureInvalid {
+            reason: format!("{name}.sig is not a minisign signature ({e})"),
+        }
+    })?;
+
+    if keys
+        .iter()
+        .any(|pk| pk.verify(content, &signature, false).is_ok())
+    {
+        Ok(())
+    } else {
+        Err(VaulticError::RecipientsSignatureInvalid {
+            reason: format!(
+                "{name} was changed after it was signed, or was not signed by a pinned admin key"
+            ),
+        })
+    }
+}
+
+/// Parse one pinned admin key from config.
+fn parse_public_key(key: &str) -> Result<minisign_verify::PublicKey> {
+    let line = key
+        .lines()
+        .map(str::trim)
+        .rfind(|l| !l.is_empty() && !l.starts_with("untrusted comment:"))
+        .unwrap_or_default();
+    minisign_verify::PublicKey::from_base64(line).map_err(|e| VaulticError::InvalidConfig {
+        detail: format!(
+            "Invalid admin key in [recipients] admin_keys: '{line}' ({e})\n\n  \
+             Use the minisign public key, e.g. the second line of minisign.pub."
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD;
+    use blake2::Digest;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    const KEYNUM: [u8; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
+
+    fn public_key(sk: &SigningKey) -> String {
+        let mut bytes = b"Ed".to_vec();
+        bytes.extend_from_slice(&KEYNUM);
+        bytes.extend_from_slice(sk.verifying_key().as_bytes());
+        STANDARD.encode(bytes)
+    }
+
+    /// Produce a prehashed minisign signature, as `minisign -S` does.
+    fn sign(sk: &SigningKey, message: &[u8]) -> String {
+        let sig = sk.sign(&blake2::Blake2b512::digest(message));
+        let mut bytes = b"ED".to_vec();
+        bytes.extend_from_slice(&KEYNUM);
+        bytes.extend_from_slice(&sig.to_bytes());
+
+        let trusted = "timestamp:1700000000\tfile:recipients.txt";
+        let mut global = sig.to_bytes().to_vec();
+        global.extend_from_slice(trusted.as_bytes());
+        let global_sig = sk.sign(&global);
+
+        format!(
+            "untrusted comment: signature from minisign secret key\n{}\ntrusted comment: {trusted}\n{}\n",
+            STANDARD.encode(bytes),
+            STANDARD.encode(global_sig.to_bytes())
+        )
+    }
+
+    #[test]
+    fn accepts_signature_from_pinned_key() {
+        let admin = SigningKey::from_bytes(&[7; 32]);
+        let other = SigningKey::from_bytes(&[9; 32]);
+        let content = b"age1alice\nage1bob # bob\n";
+        let sig = sign(&admin, content);
+
+        let keys = vec![
+            public_key(&other),
+            format!(
+                "untrusted comment: minisign public key\n{}",
+                public_key(&admin)
+            ),
+        ];
+        assert!(verify("recipients.txt", content, sig.as_bytes(), &keys).is_ok());
+    }
+
+    #[test]
+    fn rejects_modified_content() {
+        let admin = SigningKey::from_bytes(&[7; 32]);
+        let sig = sign(&admin, b"age1alice\n");
+
+        let err = verify(
+            "recipients.txt",
+            b"age1alice\nage1mallory\n",
+            sig.as_bytes(),
+            &[public_key(&admin)],
+        );
+        assert!(matches!(
+            err,
+            Err(VaulticError::RecipientsSignatureInvalid { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_signature_from_unpinned_key() {
+        let admin = SigningKey::from_bytes(&[7; 32]);
+        let intruder = SigningKey::from_bytes(&[9; 32]);
+        let sig = sign(&intruder, b"age1alice\n");
+
+        assert!(
+            verify(
+                "recipients.txt",
+                b"age1alice\n",
+                sig.as_bytes(),
+                &[public_key(&admin)]
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn invalid_admin_key_is_a_config_error() {
+        let err = verify("recipients.txt", b"", b"", &["not-a-key".to_string()]);
+        assert!(matches!(err, Err(VaulticError::InvalidConfig { .. })));
+    }
+}
@@ -1,6 +1,14 @@
+pub mod archive;
 pub mod audit;
 pub mod cipher;
+#[cfg(feature = "cli")]
+pub mod clipboard;
+pub mod clock;
+pub mod credential_providers;
 pub mod git;
 pub mod key_stores;
 pub mod parsers;
+pub mod remote_stores;
+pub mod secret_providers;
+#[cfg(feature = "cli")]
 pub mod updater;
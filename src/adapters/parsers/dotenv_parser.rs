@@ -63,6 +63,62 @@ impl DotenvParser {
     }
 }
 
+impl DotenvParser {
+    /// Set `key` to `value` in raw `.env` content.
+    ///
+    /// Replaces the first line defining `key` (keeping an `export ` prefix)
+    /// or appends a new line. Every other line, including comments and
+    /// quoting, is kept byte for byte. Returns the new content and whether
+    /// the key already existed.
+    pub fn set_value(&self, content: &str, key: &str, value: &str) -> Result<(String, bool)> {
+        if value.contains(['\n', '\r']) {
+            return Err(VaulticError::ParseError {
+                file: PathBuf::from(".env"),
+                detail: format!("value for {key} must be a single line"),
+            });
+        }
+        let file = self.parse(content)?;
+        let new_line = format!("{key}={}", quote_value(value));
+
+        let mut lines: Vec<String> = content.lines().map(String::from).collect();
+        let existing = file.entries().find(|e| e.key == key).map(|e| e.line_number);
+        match existing {
+            Some(line_number) => {
+                let old = &lines[line_number - 1];
+                let export = old.trim_start().starts_with("export ");
+                lines[line_number - 1] = if export {
+                    format!("export {new_line}")
+                } else {
+                    new_line
+                };
+            }
+            None => lines.push(new_line),
+        }
+
+        let mut output = lines.join("\n");
+        if content.ends_with('\n') || existing.is_none() {
+            output.push('\n');
+        }
+        Ok((output, existing.is_some()))
+    }
+}
+
+/// Quote a value when the parser would otherwise trim or misread it.
+fn quote_value(value: &str) -> String {
+    let needs_quotes = value.contains(char::is_whitespace)
+        || value.contains('#')
+        || (value.len() >= 2
+            && ((value.starts_with('"') && value.ends_with('"'))
+                || (value.starts_with('\'') && value.ends_with('\''))));
+    if !needs_quotes {
+        value.to_string()
+    } else if value.contains('"') {
+        format!("'{value}'")
+    } else {
+        format!("\"{value}\"")
+    }
+}
+
 /// Remove matching surrounding quotes (single or double) from a value.
 fn strip_quotes(s: &str) -> String {
     let bytes = s.as_bytes();
@@ -312,4 +368,37 @@ mod tests {
 
         assert_eq!(file.keys(), vec!["DB_HOST", "API_KEY", "PORT"]);
     }
+
+    #[test]
+    fn set_value_replaces_in_place_and_keeps_other_lines() {
+        let parser = DotenvParser;
+        let content = "# Database\nexport DB_HOST=\"localhost\"\nDB_PORT=5432\n";
+        let (updated, existed) = parser.set_value(content, "DB_HOST", "db.internal").unwrap();
+
+        assert!(existed);
+        assert_eq!(
+            updated,
+            "# Database\nexport DB_HOST=db.internal\nDB_PORT=5432\n"
+        );
+    }
+
+    #[test]
+    fn set_value_appends_new_key() {
+        let parser = DotenvParser;
+        let (updated, existed) = parser.set_value("A=1", "B", "two words").unwrap();
+
+        assert!(!existed);
+        assert_eq!(updated, "A=1\nB=\"two words\"\n");
+        assert_eq!(parser.parse(&updated).unwrap().get("B"), Some("two words"));
+    }
+
+    #[test]
+    fn set_value_round_trips_awkward_values() {
+        let parser = DotenvParser;
+        for value in ["", " padded ", "a#b", "\"quoted\"", "it's", "x=y"] {
+            let (updated, _) = parser.set_value("", "K", value).unwrap();
+            assert_eq!(parser.parse(&updated).unwrap().get("K"), Some(value));
+        }
+        assert!(parser.set_value("", "K", "two\nlines").is_err());
+    }
 }
@@ -0,0 +1,170 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use serde_json::{Map, Value};
+
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::secret_file::{Line, SecretEntry, SecretFile};
+use crate::core::traits::parser::ConfigParser;
+
+/// Parses and serializes JSON secret files.
+///
+/// The top level must be an object. Nested objects are flattened into
+/// environment-style keys (`{"db": {"host": …}}` → `DB_HOST`); numbers
+/// and booleans become strings and `null` an empty value. Arrays are
+/// rejected because they have no single-variable form. Key order is
+/// preserved.
+pub struct JsonParser;
+
+impl ConfigParser for JsonParser {
+    fn parse(&self, content: &str) -> Result<SecretFile> {
+        if content.trim().is_empty() {
+            return flatten(Value::Null, "secrets.json");
+        }
+        let value: Value = serde_json::from_str(content).map_err(|e| VaulticError::ParseError {
+            file: PathBuf::from("secrets.json"),
+            detail: e.to_string(),
+        })?;
+        flatten(value, "secrets.json")
+    }
+
+    fn serialize(&self, secrets: &SecretFile) -> Result<String> {
+        let map: Map<String, Value> = secrets
+            .entries()
+            .map(|e| (e.key.clone(), Value::String(e.value.clone())))
+            .collect();
+        serde_json::to_string_pretty(&map).map_err(|e| VaulticError::ParseError {
+            file: PathBuf::from("secrets.json"),
+            detail: e.to_string(),
+        })
+    }
+
+    fn supported_extensions(&self) -> &[&str] {
+        &[".json"]
+    }
+}
+
+/// Flatten a parsed JSON or YAML document into a `SecretFile`.
+///
+/// `file` names the format in error messages.
+pub(super) fn flatten(value: Value, file: &str) -> Result<SecretFile> {
+    let error = |detail: String| VaulticError::ParseError {
+        file: PathBuf::from(file),
+        detail,
+    };
+    let root = match value {
+        Value::Object(map) => map,
+        Value::Null => Map::new(),
+        _ => {
+            return Err(error(
+                "top level must be a mapping of keys to values".into(),
+            ));
+        }
+    };
+
+    let mut pairs = Vec::new();
+    flatten_into(&root, "", &mut pairs).map_err(error)?;
+
+    let mut seen = HashSet::new();
+    let mut lines = Vec::with_capacity(pairs.len());
+    for (idx, (key, value)) in pairs.into_iter().enumerate() {
+        if !seen.insert(key.clone()) {
+            return Err(error(format!("'{key}' is defined more than once")));
+        }
+        lines.push(Line::Entry(SecretEntry {
+            key,
+            value,
+            comment: None,
+            line_number: idx + 1,
+        }));
+    }
+
+    Ok(SecretFile {
+        lines,
+        source_path: None,
+    })
+}
+
+fn flatten_into(
+    map: &Map<String, Value>,
+    prefix: &str,
+    out: &mut Vec<(String, String)>,
+) -> std::result::Result<(), String> {
+    for (name, value) in map {
+        let key = env_key(prefix, name);
+        match value {
+            Value::Object(nested) => flatten_into(nested, &key, out)?,
+            Value::Array(_) => {
+                return Err(format!(
+                    "'{key}' is a list; only scalar values are supported"
+                ));
+            }
+            Value::String(s) => out.push((key, s.clone())),
+            Value::Null => out.push((key, String::new())),
+            other => out.push((key, other.to_string())),
+        }
+    }
+    Ok(())
+}
+
+/// Environment-style key for `name` under `prefix`: upper case, with
+/// every character other than letters, digits and `_` replaced by `_`.
+fn env_key(prefix: &str, name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if prefix.is_empty() {
+        name
+    } else {
+        format!("{prefix}_{name}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flattens_nested_objects_in_order() {
+        let file = JsonParser
+            .parse(r#"{"db": {"host": "localhost", "port": 5432}, "debug": true, "api-key": null}"#)
+            .unwrap();
+        assert_eq!(file.keys(), vec!["DB_HOST", "DB_PORT", "DEBUG", "API_KEY"]);
+        assert_eq!(file.get("DB_PORT"), Some("5432"));
+        assert_eq!(file.get("DEBUG"), Some("true"));
+        assert_eq!(file.get("API_KEY"), Some(""));
+    }
+
+    #[test]
+    fn dotted_keys_match_nested_keys() {
+        let file = JsonParser.parse(r#"{"db.host": "x"}"#).unwrap();
+        assert_eq!(file.keys(), vec!["DB_HOST"]);
+
+        let err = JsonParser
+            .parse(r#"{"db.host": "x", "db": {"host": "y"}}"#)
+            .unwrap_err();
+        assert!(err.to_string().contains("more than once"));
+    }
+
+    #[test]
+    fn rejects_lists_and_non_objects() {
+        assert!(JsonParser.parse(r#"{"hosts": ["a", "b"]}"#).is_err());
+        assert!(JsonParser.parse(r#"["a"]"#).is_err());
+        assert!(JsonParser.parse("{not json").is_err());
+    }
+
+    #[test]
+    fn serialize_round_trips() {
+        let file = JsonParser.parse(r#"{"b": "2", "a": "1"}"#).unwrap();
+        let out = JsonParser.serialize(&file).unwrap();
+        assert_eq!(out, "{\n  \"B\": \"2\",\n  \"A\": \"1\"\n}");
+        assert_eq!(JsonParser.parse(&out).unwrap(), file);
+    }
+}
@@ -1 +1,15 @@
 pub mod dotenv_parser;
+pub mod json_parser;
+pub mod yaml_parser;
+
+use crate::config::app_config::SecretFormat;
+use crate::core::traits::parser::ConfigParser;
+
+/// The parser for secrets stored in `format`.
+pub fn for_format(format: SecretFormat) -> Box<dyn ConfigParser> {
+    match format {
+        SecretFormat::Dotenv => Box::new(dotenv_parser::DotenvParser),
+        SecretFormat::Yaml => Box::new(yaml_parser::YamlParser),
+        SecretFormat::Json => Box::new(json_parser::JsonParser),
+    }
+}
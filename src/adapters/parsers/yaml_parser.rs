@@ -0,0 +1,78 @@
+use std::path::PathBuf;
+
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::secret_file::SecretFile;
+use crate::core::traits::parser::ConfigParser;
+
+use super::json_parser::flatten;
+
+/// Parses and serializes YAML secret files.
+///
+/// Follows the same rules as `JsonParser`: the document must be a
+/// mapping, nested mappings are flattened (`db: {host: …}` → `DB_HOST`),
+/// scalars become strings and lists are rejected.
+pub struct YamlParser;
+
+impl ConfigParser for YamlParser {
+    fn parse(&self, content: &str) -> Result<SecretFile> {
+        let value: serde_json::Value =
+            serde_yaml::from_str(content).map_err(|e| VaulticError::ParseError {
+                file: PathBuf::from("secrets.yaml"),
+                detail: e.to_string(),
+            })?;
+        flatten(value, "secrets.yaml")
+    }
+
+    fn serialize(&self, secrets: &SecretFile) -> Result<String> {
+        let mut map = serde_yaml::Mapping::new();
+        for entry in secrets.entries() {
+            map.insert(entry.key.clone().into(), entry.value.clone().into());
+        }
+        serde_yaml::to_string(&map).map_err(|e| VaulticError::ParseError {
+            file: PathBuf::from("secrets.yaml"),
+            detail: e.to_string(),
+        })
+    }
+
+    fn supported_extensions(&self) -> &[&str] {
+        &[".yaml", ".yml"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flattens_nested_mappings() {
+        let file = YamlParser
+            .parse("db:\n  host: localhost\n  port: 5432\nfeature_x: yes\nempty:\n")
+            .unwrap();
+        assert_eq!(
+            file.keys(),
+            vec!["DB_HOST", "DB_PORT", "FEATURE_X", "EMPTY"]
+        );
+        assert_eq!(file.get("DB_HOST"), Some("localhost"));
+        assert_eq!(file.get("DB_PORT"), Some("5432"));
+        assert_eq!(file.get("FEATURE_X"), Some("yes"));
+        assert_eq!(file.get("EMPTY"), Some(""));
+    }
+
+    #[test]
+    fn empty_document_has_no_entries() {
+        assert!(YamlParser.parse("").unwrap().keys().is_empty());
+    }
+
+    #[test]
+    fn rejects_lists() {
+        let err = YamlParser.parse("hosts:\n  - a\n  - b\n").unwrap_err();
+        assert!(err.to_string().contains("HOSTS"));
+    }
+
+    #[test]
+    fn serialize_round_trips() {
+        let file = YamlParser.parse("B: '2'\nA: x y\n").unwrap();
+        let out = YamlParser.serialize(&file).unwrap();
+        assert_eq!(YamlParser.parse(&out).unwrap(), file);
+    }
+}
@@ -0,0 +1,268 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use serde_json::{Value, json};
+
+use crate::core::errors::{Result, VaulticError};
+use crate::core::traits::remote_store::RemoteStore;
+
+/// A single JSON secret in AWS Secrets Manager holding every key.
+///
+/// Talks to AWS through the `aws` CLI, so credentials, profiles and
+/// regions are whatever the CLI is configured with.
+pub struct SecretsManagerStore {
+    pub secret_id: String,
+    pub region: Option<String>,
+}
+
+/// One `SecureString` parameter per key under a path prefix in SSM
+/// Parameter Store, e.g. `/myapp/prod/DATABASE_URL`.
+pub struct ParameterStore {
+    pub prefix: String,
+    pub region: Option<String>,
+}
+
+impl RemoteStore for SecretsManagerStore {
+    fn location(&self) -> String {
+        format!("secretsmanager:{}", self.secret_id)
+    }
+
+    fn fetch(&self) -> Result<BTreeMap<String, String>> {
+        let out = run(
+            &[
+                "secretsmanager",
+                "get-secret-value",
+                "--secret-id",
+                &self.secret_id,
+            ],
+            self.region.as_deref(),
+            None,
+        )?;
+        if !out.status.success() {
+            if stderr(&out).contains("ResourceNotFoundException") {
+                return Ok(BTreeMap::new());
+            }
+            return Err(failed("read the secret", &out));
+        }
+        let response = parse_json(&out)?;
+        let secret = response["SecretString"].as_str().unwrap_or("{}");
+        let Ok(Value::Object(map)) = serde_json::from_str::<Value>(secret) else {
+            return Err(VaulticError::InvalidConfig {
+                detail: format!(
+                    "Secret '{}' is not a JSON object of keys and values; \
+                     refusing to overwrite it.",
+                    self.secret_id
+                ),
+            });
+        };
+        Ok(map
+            .into_iter()
+            .map(|(k, v)| match v {
+                Value::String(s) => (k, s),
+                other => (k, other.to_string()),
+            })
+            .collect())
+    }
+
+    fn push(&self, desired: &BTreeMap<String, String>, _removed: &[String]) -> Result<()> {
+        // The whole secret is replaced, which also drops removed keys.
+        let secret = serde_json::to_string(desired).map_err(json_error)?;
+        let input = json!({ "SecretId": self.secret_id, "SecretString": secret });
+        let out = run(
+            &["secretsmanager", "put-secret-value"],
+            self.region.as_deref(),
+            Some(&input),
+        )?;
+        if out.status.success() {
+            return Ok(());
+        }
+        if !stderr(&out).contains("ResourceNotFoundException") {
+            return Err(failed("update the secret", &out));
+        }
+
+        let input = json!({ "Name": self.secret_id, "SecretString": secret });
+        let out = run(
+            &["secretsmanager", "create-secret"],
+            self.region.as_deref(),
+            Some(&input),
+        )?;
+        if out.status.success() {
+            Ok(())
+        } else {
+            Err(failed("create the secret", &out))
+        }
+    }
+}
+
+impl ParameterStore {
+    fn name(&self, key: &str) -> String {
+        format!("{}/{key}", self.prefix.trim_end_matches('/'))
+    }
+}
+
+impl RemoteStore for ParameterStore {
+    fn location(&self) -> String {
+        format!("ssm:{}", self.prefix)
+    }
+
+    fn fetch(&self) -> Result<BTreeMap<String, String>> {
+        let out = run(
+            &[
+                "ssm",
+                "get-parameters-by-path",
+                "--path",
+                &self.prefix,
+                "--with-decryption",
+            ],
+            self.region.as_deref(),
+            None,
+        )?;
+        if !out.status.success() {
+            return Err(failed("list the parameters", &out));
+        }
+        let response = parse_json(&out)?;
+        let prefix = format!("{}/", self.prefix.trim_end_matches('/'));
+        Ok(response["Parameters"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|p| {
+                let key = p["Name"].as_str()?.strip_prefix(&prefix)?;
+                Some((key.to_string(), p["Value"].as_str()?.to_string()))
+            })
+            .collect())
+    }
+
+    fn push(&self, desired: &BTreeMap<String, String>, removed: &[String]) -> Result<()> {
+        let current = self.fetch()?;
+        for (key, value) in desired {
+            if current.get(key) == Some(value) {
+                continue;
+            }
+            let input = json!({
+                "Name": self.name(key),
+                "Value": value,
+                "Type": "SecureString",
+                "Overwrite": true,
+            });
+            let out = run(
+                &["ssm", "put-parameter"],
+                self.region.as_deref(),
+                Some(&input),
+            )?;
+            if !out.status.success() {
+                return Err(failed(&format!("write {}", self.name(key)), &out));
+            }
+        }
+
+        // delete-parameters accepts at most 10 names per call
+        for chunk in removed.chunks(10) {
+            let mut args = vec![
+                "ssm".to_string(),
+                "delete-parameters".into(),
+                "--names".into(),
+            ];
+            args.extend(chunk.iter().map(|k| self.name(k)));
+            let args: Vec<&str> = args.iter().map(String::as_str).collect();
+            let out = run(&args, self.region.as_deref(), None)?;
+            if !out.status.success() {
+                return Err(failed("delete parameters", &out));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Run `aws <args> --output json`. Secret values are passed in `input`
+/// through `--cli-input-json file://…` rather than on the command line,
+/// where other local users could read them from the process list.
+fn run(args: &[&str], region: Option<&str>, input: Option<&Value>) -> Result<Output> {
+    let mut cmd = Command::new("aws");
+    cmd.args(args).args(["--output", "json"]);
+    if let Some(region) = region {
+        cmd.args(["--region", region]);
+    }
+
+    // Kept alive until the command has finished; deleted on drop.
+    let input_file = match input {
+        Some(input) => {
+            let mut file = private_temp_file()?;
+            file.write_all(input.to_string().as_bytes())?;
+            file.flush()?;
+            cmd.arg("--cli-input-json")
+                .arg(format!("file://{}", file.path().display()));
+            Some(file)
+        }
+        None => None,
+    };
+
+    let out = cmd.output().map_err(|e| VaulticError::InvalidConfig {
+        detail: format!(
+            "Failed to run the AWS CLI: {e}\n\n  \
+             Solutions:\n    \
+             → Install AWS CLI v2: https://aws.amazon.com/cli/\n    \
+             → Configure credentials: aws configure (or set AWS_PROFILE)"
+        ),
+    })?;
+    drop(input_file);
+    Ok(out)
+}
+
+/// A temp file readable only by the current user, on memory-backed
+/// `/dev/shm` when available.
+fn private_temp_file() -> Result<tempfile::NamedTempFile> {
+    let shm = Path::new("/dev/shm");
+    let file = if shm.is_dir() {
+        tempfile::Builder::new()
+            .prefix("vaultic-sync-")
+            .tempfile_in(shm)?
+    } else {
+        tempfile::Builder::new()
+            .prefix("vaultic-sync-")
+            .tempfile()?
+    };
+    Ok(file)
+}
+
+fn stderr(out: &Output) -> String {
+    String::from_utf8_lossy(&out.stderr).into_owned()
+}
+
+fn parse_json(out: &Output) -> Result<Value> {
+    serde_json::from_slice(&out.stdout).map_err(json_error)
+}
+
+fn json_error(e: serde_json::Error) -> VaulticError {
+    VaulticError::InvalidConfig {
+        detail: format!("Unexpected response from the AWS CLI: {e}"),
+    }
+}
+
+fn failed(action: &str, out: &Output) -> VaulticError {
+    VaulticError::InvalidConfig {
+        detail: format!(
+            "AWS CLI could not {action}: {}\n\n  \
+             Solutions:\n    \
+             → Check your credentials: aws sts get-caller-identity\n    \
+             → Check the region (--region) and IAM permissions",
+            stderr(out).trim()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parameter_names_join_prefix_and_key() {
+        let store = ParameterStore {
+            prefix: "/myapp/prod/".into(),
+            region: None,
+        };
+        assert_eq!(store.name("DB_URL"), "/myapp/prod/DB_URL");
+        assert_eq!(store.location(), "ssm:/myapp/prod/");
+    }
+}
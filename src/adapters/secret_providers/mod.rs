@@ -0,0 +1,2 @@
+pub mod onepassword;
+pub mod vault;
@@ -0,0 +1,43 @@
+use std::process::Command;
+
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::secret_ref::SecretRef;
+use crate::core::traits::secret_provider::SecretProvider;
+
+/// 1Password, for `op://<vault>/<item>/<field>` references.
+///
+/// Reads through the `op` CLI, which understands these URIs natively, so
+/// sign-in, biometric unlock and service-account tokens all work as they
+/// do for the CLI.
+pub struct OnePasswordProvider {
+    pub account: Option<String>,
+}
+
+impl SecretProvider for OnePasswordProvider {
+    fn scheme(&self) -> &'static str {
+        "op"
+    }
+
+    fn fetch(&self, reference: &SecretRef) -> Result<String> {
+        let mut cmd = Command::new("op");
+        cmd.args(["read", "--no-newline"]);
+        if let Some(account) = &self.account {
+            cmd.args(["--account", account]);
+        }
+        cmd.arg(reference.to_string());
+        let out = cmd.output().map_err(|e| VaulticError::InvalidConfig {
+            detail: format!("failed to run the 1Password CLI: {e}"),
+        })?;
+        if !out.status.success() {
+            return Err(VaulticError::InvalidConfig {
+                detail: format!(
+                    "op read failed: {}",
+                    String::from_utf8_lossy(&out.stderr).trim()
+                ),
+            });
+        }
+        String::from_utf8(out.stdout).map_err(|_| VaulticError::InvalidConfig {
+            detail: "the 1Password CLI returned a value that is not UTF-8".into(),
+        })
+    }
+}
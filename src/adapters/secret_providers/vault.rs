@@ -0,0 +1,93 @@
+use std::process::Command;
+
+use serde_json::Value;
+
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::secret_ref::SecretRef;
+use crate::core::traits::secret_provider::SecretProvider;
+
+/// HashiCorp Vault, for `vault://<path>#<field>` references.
+///
+/// Reads through the `vault` CLI, so authentication is whatever the CLI
+/// is logged in with. Works with KV v1 and v2 paths
+/// (`secret/data/app` for v2).
+pub struct VaultProvider {
+    pub address: Option<String>,
+    pub namespace: Option<String>,
+}
+
+impl SecretProvider for VaultProvider {
+    fn scheme(&self) -> &'static str {
+        "vault"
+    }
+
+    fn fetch(&self, reference: &SecretRef) -> Result<String> {
+        let field = reference
+            .field
+            .as_deref()
+            .ok_or_else(|| VaulticError::InvalidConfig {
+                detail: "name the field after '#', e.g. vault://secret/data/app#api_key".into(),
+            })?;
+
+        let mut cmd = Command::new("vault");
+        cmd.args(["read", "-format=json", "--", &reference.path]);
+        if let Some(address) = &self.address {
+            cmd.env("VAULT_ADDR", address);
+        }
+        if let Some(namespace) = &self.namespace {
+            cmd.env("VAULT_NAMESPACE", namespace);
+        }
+        let out = cmd.output().map_err(|e| VaulticError::InvalidConfig {
+            detail: format!("failed to run the Vault CLI: {e}"),
+        })?;
+        if !out.status.success() {
+            return Err(VaulticError::InvalidConfig {
+                detail: format!(
+                    "vault read failed: {}",
+                    String::from_utf8_lossy(&out.stderr).trim()
+                ),
+            });
+        }
+
+        let response: Value =
+            serde_json::from_slice(&out.stdout).map_err(|e| VaulticError::InvalidConfig {
+                detail: format!("unexpected response from the Vault CLI: {e}"),
+            })?;
+        field_value(&response, field).ok_or_else(|| VaulticError::InvalidConfig {
+            detail: format!("{} has no field '{field}'", reference.path),
+        })
+    }
+}
+
+/// `field` from a `vault read` response: under `data.data` for KV v2,
+/// under `data` for KV v1 and other engines.
+fn field_value(response: &Value, field: &str) -> Option<String> {
+    let data = &response["data"];
+    let value = data["data"]
+        .get(field)
+        .filter(|_| data["metadata"].is_object())
+        .or_else(|| data.get(field))?;
+    Some(match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reads_kv_v1_and_v2_responses() {
+        let v2 = json!({
+            "data": { "data": { "api_key": "abc" }, "metadata": { "version": 3 } }
+        });
+        assert_eq!(field_value(&v2, "api_key").as_deref(), Some("abc"));
+
+        let v1 = json!({ "data": { "api_key": "abc", "port": 5432 } });
+        assert_eq!(field_value(&v1, "api_key").as_deref(), Some("abc"));
+        assert_eq!(field_value(&v1, "port").as_deref(), Some("5432"));
+        assert_eq!(field_value(&v1, "missing"), None);
+    }
+}
@@ -1,22 +1,40 @@
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use crate::core::errors::{Result, VaulticError};
 use crate::core::models::update_info::{
-    GitHubRelease, UpdateCheckCache, UpdateInfo, current_platform_asset, current_version,
+    AvailableUpdate, GitHubRelease, ReleaseCache, UpdateCheckCache, UpdateInfo,
+    current_platform_asset, current_version, summarize_notes,
 };
 
 const GITHUB_API_URL: &str = "https://api.github.com/repos/SoftDryzz/vaultic/releases/latest";
 
+/// Base URL of release assets, followed by `/v<version>/<file>`.
+const RELEASE_DOWNLOAD_URL: &str = "https://github.com/SoftDryzz/vaultic/releases/download";
+
 /// Timeout for the passive version check (startup banner).
 const CHECK_TIMEOUT: Duration = Duration::from_secs(3);
 
 /// Timeout for the explicit download during `vaultic update`.
 const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(120);
 
+/// Lines of release notes shown with an update notice.
+pub const NOTES_SUMMARY_LINES: usize = 8;
+
 /// How long to cache the update check result (24 hours).
 const CACHE_TTL_SECS: i64 = 86400;
 
+/// Attempts for the explicit `vaultic update` request (1 = no retry).
+/// The passive startup check never retries so it cannot slow commands down.
+const UPDATE_ATTEMPTS: u32 = 4;
+
+/// Base delay for exponential backoff on rate limiting.
+const BACKOFF_BASE: Duration = Duration::from_secs(1);
+
+/// Longest single wait we accept; beyond this we fail with a hint instead.
+const MAX_RETRY_WAIT: Duration = Duration::from_secs(30);
+
 /// Build a reqwest client with the given timeout.
 fn build_client(timeout: Duration) -> Result<reqwest::Client> {
     reqwest::Client::builder()
@@ -36,6 +54,143 @@ fn cache_path() -> Result<PathBuf> {
     Ok(config_dir.join("vaultic").join("last_update_check.json"))
 }
 
+/// Path to the cached latest-release response (for ETag revalidation).
+fn release_cache_path() -> Result<PathBuf> {
+    Ok(cache_path()?.with_file_name("latest_release.json"))
+}
+
+fn load_release_cache() -> Option<ReleaseCache> {
+    let content = std::fs::read_to_string(release_cache_path().ok()?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn save_release_cache(cache: &ReleaseCache) {
+    let Ok(path) = release_cache_path() else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let _ = serde_json::to_string(cache).map(|json| std::fs::write(&path, json));
+}
+
+/// Whether a response status means the GitHub API rate limit was hit.
+fn is_rate_limited(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::FORBIDDEN || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+}
+
+/// How long to wait before retry number `attempt` (0-based).
+///
+/// Honors `Retry-After` and the `x-ratelimit-reset` epoch when GitHub
+/// sends them, otherwise backs off exponentially. Returns `None` when
+/// the required wait exceeds [`MAX_RETRY_WAIT`].
+fn retry_delay(attempt: u32, retry_after: Option<u64>, reset_in: Option<u64>) -> Option<Duration> {
+    let delay = match (retry_after, reset_in) {
+        (Some(secs), _) | (None, Some(secs)) => Duration::from_secs(secs),
+        (None, None) => BACKOFF_BASE * 2u32.saturating_pow(attempt),
+    };
+    (delay <= MAX_RETRY_WAIT).then_some(delay)
+}
+
+/// Read an integer response header.
+fn header_u64(resp: &reqwest::Response, name: &str) -> Option<u64> {
+    resp.headers().get(name)?.to_str().ok()?.trim().parse().ok()
+}
+
+/// Fetch the latest release, revalidating the cached copy with
+/// `If-None-Match` and retrying with backoff when rate limited.
+///
+/// Sends `GITHUB_TOKEN` as a bearer token when set, which lifts the
+/// anonymous per-IP limit shared by CI runners.
+async fn fetch_latest_release(client: &reqwest::Client, attempts: u32) -> Result<GitHubRelease> {
+    let cached = load_release_cache();
+    let token = std::env::var("GITHUB_TOKEN")
+        .ok()
+        .filter(|t| !t.trim().is_empty());
+
+    let mut attempt = 0;
+    loop {
+        let mut req = client
+            .get(GITHUB_API_URL)
+            .header("Accept", "application/vnd.github+json");
+        if let Some(cache) = &cached {
+            req = req.header("If-None-Match", &cache.etag);
+        }
+        if let Some(token) = &token {
+            req = req.bearer_auth(token.trim());
+        }
+
+        let resp = req
+            .send()
+            .await
+            .map_err(|e| VaulticError::UpdateCheckFailed {
+                reason: format!("GitHub API request failed: {e}"),
+            })?;
+        let status = resp.status();
+
+        if status == reqwest::StatusCode::NOT_MODIFIED
+            && let Some(cache) = &cached
+        {
+            return parse_release(&cache.body);
+        }
+
+        if is_rate_limited(status) {
+            attempt += 1;
+            let reset_in = (header_u64(&resp, "x-ratelimit-remaining") == Some(0))
+                .then(|| header_u64(&resp, "x-ratelimit-reset"))
+                .flatten()
+                .map(|reset| reset.saturating_sub(chrono::Utc::now().timestamp().max(0) as u64));
+            let delay = retry_delay(attempt - 1, header_u64(&resp, "retry-after"), reset_in);
+
+            match delay {
+                Some(delay) if attempt < attempts => {
+                    tokio::time::sleep(delay).await;
+                    continue;
+                }
+                _ => {
+                    return Err(VaulticError::UpdateCheckFailed {
+                        reason: format!(
+                            "GitHub API rate limit reached (status {status})\n\n  \
+                             Solutions:\n    \
+                             → Set GITHUB_TOKEN to raise the limit\n    \
+                             → Try again later"
+                        ),
+                    });
+                }
+            }
+        }
+
+        if !status.is_success() {
+            return Err(VaulticError::UpdateCheckFailed {
+                reason: format!("GitHub API returned status {status}"),
+            });
+        }
+
+        let etag = resp
+            .headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|v| v.to_str().ok())
+            .map(String::from);
+        let body = resp
+            .text()
+            .await
+            .map_err(|e| VaulticError::UpdateCheckFailed {
+                reason: format!("Failed to read GitHub response: {e}"),
+            })?;
+        let release = parse_release(&body)?;
+        if let Some(etag) = etag {
+            save_release_cache(&ReleaseCache { etag, body });
+        }
+        return Ok(release);
+    }
+}
+
+fn parse_release(body: &str) -> Result<GitHubRelease> {
+    serde_json::from_str(body).map_err(|e| VaulticError::UpdateCheckFailed {
+        reason: format!("Failed to parse GitHub response: {e}"),
+    })
+}
+
 /// Check if the cached update check is still fresh (< 24 hours old).
 pub fn is_cache_fresh() -> bool {
     let Ok(path) = cache_path() else {
@@ -55,7 +210,7 @@ pub fn is_cache_fresh() -> bool {
 }
 
 /// Save the update check result to cache.
-fn save_cache(latest_version: Option<&str>) {
+fn save_cache(latest_version: Option<&str>, notes: Option<&str>) {
     let Ok(path) = cache_path() else { return };
     if let Some(parent) = path.parent() {
         let _ = std::fs::create_dir_all(parent);
@@ -63,15 +218,16 @@ fn save_cache(latest_version: Option<&str>) {
     let cache = UpdateCheckCache {
         checked_at: chrono::Utc::now().to_rfc3339(),
         latest_version: latest_version.map(|s| s.to_string()),
+        notes: notes.map(|s| s.to_string()),
     };
     let _ = serde_json::to_string(&cache).map(|json| std::fs::write(&path, json));
 }
 
 /// Fetch the latest release info from GitHub (quick check, 3s timeout).
 ///
-/// Returns `Some(version_string)` if a newer version is available, `None` otherwise.
-/// Never errors — returns `None` on any failure (network, parse, etc.).
-pub fn check_latest_version() -> Option<String> {
+/// Returns the newer version and a summary of its release notes, or `None`
+/// when up to date. Never errors — returns `None` on any failure.
+pub fn check_latest_version() -> Option<AvailableUpdate> {
     if is_cache_fresh() {
         let path = cache_path().ok()?;
         let content = std::fs::read_to_string(path).ok()?;
@@ -79,7 +235,10 @@ pub fn check_latest_version() -> Option<String> {
         let latest_str = cache.latest_version?;
         let latest: semver::Version = latest_str.parse().ok()?;
         if latest > current_version() {
-            return Some(latest_str);
+            return Some(AvailableUpdate {
+                version: latest_str,
+                notes: cache.notes,
+            });
         }
         return None;
     }
@@ -91,23 +250,24 @@ pub fn check_latest_version() -> Option<String> {
 
     rt.block_on(async {
         let client = build_client(CHECK_TIMEOUT).ok()?;
-        let resp = client
-            .get(GITHUB_API_URL)
-            .header("Accept", "application/vnd.github+json")
-            .send()
-            .await
-            .ok()?;
-        let release: GitHubRelease = resp.json().await.ok()?;
+        let release = fetch_latest_release(&client, 1).await.ok()?;
         let version_str = release
             .tag_name
             .strip_prefix('v')
             .unwrap_or(&release.tag_name);
         let latest: semver::Version = version_str.parse().ok()?;
+        let notes = release
+            .body
+            .as_deref()
+            .and_then(|b| summarize_notes(b, NOTES_SUMMARY_LINES));
 
-        save_cache(Some(version_str));
+        save_cache(Some(version_str), notes.as_deref());
 
         if latest > current_version() {
-            Some(version_str.to_string())
+            Some(AvailableUpdate {
+                version: version_str.to_string(),
+                notes,
+            })
         } else {
             None
         }
@@ -129,27 +289,7 @@ pub fn fetch_update_info() -> Result<Option<UpdateInfo>> {
 
     rt.block_on(async {
         let client = build_client(DOWNLOAD_TIMEOUT)?;
-        let resp = client
-            .get(GITHUB_API_URL)
-            .header("Accept", "application/vnd.github+json")
-            .send()
-            .await
-            .map_err(|e| VaulticError::UpdateCheckFailed {
-                reason: format!("GitHub API request failed: {e}"),
-            })?;
-
-        if !resp.status().is_success() {
-            return Err(VaulticError::UpdateCheckFailed {
-                reason: format!("GitHub API returned status {}", resp.status()),
-            });
-        }
-
-        let release: GitHubRelease =
-            resp.json()
-                .await
-                .map_err(|e| VaulticError::UpdateCheckFailed {
-                    reason: format!("Failed to parse GitHub response: {e}"),
-                })?;
+        let release = fetch_latest_release(&client, UPDATE_ATTEMPTS).await?;
 
         let version_str = release
             .tag_name
@@ -199,10 +339,130 @@ pub fn fetch_update_info() -> Result<Option<UpdateInfo>> {
             checksums_url: checksums.browser_download_url.clone(),
             signature_url: signature.browser_download_url.clone(),
             release_url: release.html_url,
+            notes: release.body,
         }))
     })
 }
 
+/// Download URL of `file` in the release tagged `v<version>`.
+pub fn release_file_url(version: &semver::Version, file: &str) -> String {
+    format!("{RELEASE_DOWNLOAD_URL}/v{version}/{file}")
+}
+
+/// Where a partially downloaded release asset is kept between runs.
+///
+/// The version is part of the name so a leftover from an older release is
+/// never resumed into a newer one.
+pub fn partial_download_path(asset_name: &str, version: &semver::Version) -> Result<PathBuf> {
+    let cache = cache_path().map_err(|e| VaulticError::UpdateFailed {
+        reason: e.to_string(),
+    })?;
+    Ok(cache.with_file_name(format!("{asset_name}-{version}.part")))
+}
+
+/// Download `url` into `part_path`, resuming a previous partial download.
+///
+/// When `part_path` already holds bytes, a `Range` request asks for the
+/// rest; a server that ignores it (200) or rejects it (416) restarts the
+/// download from scratch. `on_progress` receives the bytes downloaded so
+/// far and the total size when the server reports one. Returns the
+/// complete content; the caller removes `part_path` once it is verified.
+pub fn download_resumable(
+    url: &str,
+    part_path: &Path,
+    mut on_progress: impl FnMut(u64, Option<u64>),
+) -> Result<Vec<u8>> {
+    let error = |reason: String| VaulticError::UpdateFailed { reason };
+    if let Some(parent) = part_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| error(format!("Failed to create {}: {e}", parent.display())))?;
+    }
+
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| error(format!("Failed to create async runtime: {e}")))?;
+
+    rt.block_on(async {
+        let client = build_client(DOWNLOAD_TIMEOUT)?;
+        let mut offset = std::fs::metadata(part_path).map(|m| m.len()).unwrap_or(0);
+
+        let mut request = client.get(url);
+        if offset > 0 {
+            request = request.header(reqwest::header::RANGE, format!("bytes={offset}-"));
+        }
+        let mut resp = request
+            .send()
+            .await
+            .map_err(|e| error(format!("Download failed: {e}")))?;
+
+        if resp.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+            // The partial file is stale or already complete; start over.
+            let _ = std::fs::remove_file(part_path);
+            offset = 0;
+            resp = client
+                .get(url)
+                .send()
+                .await
+                .map_err(|e| error(format!("Download failed: {e}")))?;
+        }
+        if !resp.status().is_success() {
+            return Err(error(format!("Download returned status {}", resp.status())));
+        }
+
+        let resumed = resp.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+        if !resumed {
+            offset = 0;
+        }
+        let total = if resumed {
+            range_total(
+                resp.headers()
+                    .get(reqwest::header::CONTENT_RANGE)
+                    .and_then(|v| v.to_str().ok()),
+            )
+            .or_else(|| resp.content_length().map(|len| offset + len))
+        } else {
+            resp.content_length()
+        };
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(resumed)
+            .truncate(!resumed)
+            .open(part_path)
+            .map_err(|e| error(format!("Failed to open {}: {e}", part_path.display())))?;
+
+        let mut downloaded = offset;
+        on_progress(downloaded, total);
+        while let Some(chunk) = resp
+            .chunk()
+            .await
+            .map_err(|e| error(format!("Failed to read download: {e}")))?
+        {
+            file.write_all(&chunk)
+                .map_err(|e| error(format!("Failed to write {}: {e}", part_path.display())))?;
+            downloaded += chunk.len() as u64;
+            on_progress(downloaded, total);
+        }
+        file.flush()
+            .map_err(|e| error(format!("Failed to write {}: {e}", part_path.display())))?;
+
+        std::fs::read(part_path)
+            .map_err(|e| error(format!("Failed to read {}: {e}", part_path.display())))
+    })
+}
+
+/// Full size from a `Content-Range: bytes <start>-<end>/<total>` header.
+fn range_total(content_range: Option<&str>) -> Option<u64> {
+    content_range?
+        .strip_prefix("bytes ")?
+        .rsplit_once('/')?
+        .1
+        .parse()
+        .ok()
+}
+
 /// Download bytes from a URL.
 pub fn download_bytes(url: &str) -> Result<Vec<u8>> {
     let rt = tokio::runtime::Builder::new_current_thread()
@@ -236,3 +496,52 @@ pub fn download_bytes(url: &str) -> Result<Vec<u8>> {
             })
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_per_attempt() {
+        assert_eq!(retry_delay(0, None, None), Some(Duration::from_secs(1)));
+        assert_eq!(retry_delay(1, None, None), Some(Duration::from_secs(2)));
+        assert_eq!(retry_delay(3, None, None), Some(Duration::from_secs(8)));
+    }
+
+    #[test]
+    fn retry_after_takes_precedence() {
+        assert_eq!(
+            retry_delay(3, Some(2), Some(20)),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(retry_delay(0, None, Some(5)), Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn long_waits_give_up() {
+        assert_eq!(retry_delay(0, None, Some(3600)), None);
+        assert_eq!(retry_delay(10, None, None), None);
+    }
+
+    #[test]
+    fn rate_limit_statuses() {
+        assert!(is_rate_limited(reqwest::StatusCode::FORBIDDEN));
+        assert!(is_rate_limited(reqwest::StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_rate_limited(reqwest::StatusCode::NOT_FOUND));
+    }
+
+    #[test]
+    fn content_range_total() {
+        assert_eq!(range_total(Some("bytes 100-999/1000")), Some(1000));
+        assert_eq!(range_total(Some("bytes 100-999/*")), None);
+        assert_eq!(range_total(Some("items 1-2/3")), None);
+        assert_eq!(range_total(None), None);
+    }
+
+    #[test]
+    fn parse_release_reads_cached_body() {
+        let body = r#"{"tag_name":"v9.9.9","html_url":"https://example.com","assets":[]}"#;
+        assert_eq!(parse_release(body).unwrap().tag_name, "v9.9.9");
+        assert!(parse_release("not json").is_err());
+    }
+}
@@ -23,24 +23,14 @@ pub fn sha256_hex(data: &[u8]) -> String {
 pub fn verify_sha256(binary_data: &[u8], asset_name: &str, checksums_content: &str) -> Result<()> {
     let computed = sha256_hex(binary_data);
 
-    let expected = checksums_content
-        .lines()
-        .find_map(|line| {
-            let mut parts = line.splitn(2, "  ");
-            let hash = parts.next()?.trim();
-            let name = parts.next()?.trim();
-            if name == asset_name {
-                Some(hash.to_string())
-            } else {
-                None
-            }
-        })
-        .ok_or_else(|| VaulticError::UpdateVerificationFailed {
+    let expected = expected_sha256(asset_name, checksums_content).ok_or_else(|| {
+        VaulticError::UpdateVerificationFailed {
             reason: format!(
                 "Asset '{asset_name}' not found in SHA256SUMS.txt\n\n  \
                  This release may not include a binary for your platform."
             ),
-        })?;
+        }
+    })?;
 
     if computed != expected {
         return Err(VaulticError::UpdateVerificationFailed {
@@ -56,6 +46,21 @@ pub fn verify_sha256(binary_data: &[u8], asset_name: &str, checksums_content: &s
     Ok(())
 }
 
+/// Hash listed for `asset_name` in `checksums_content` (SHA256SUMS.txt
+/// format), if any.
+pub fn expected_sha256(asset_name: &str, checksums_content: &str) -> Option<String> {
+    checksums_content.lines().find_map(|line| {
+        let mut parts = line.splitn(2, "  ");
+        let hash = parts.next()?.trim();
+        let name = parts.next()?.trim();
+        if name == asset_name {
+            Some(hash.to_string())
+        } else {
+            None
+        }
+    })
+}
+
 /// Verify the minisign signature of SHA256SUMS.txt.
 pub fn verify_signature(checksums_content: &[u8], signature_content: &[u8]) -> Result<()> {
     let pk_line = MINISIGN_PUBLIC_KEY
@@ -120,6 +125,19 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn expected_sha256_finds_the_asset_line() {
+        let checksums = "abc123  vaultic-linux-amd64\ndef456  vaultic-darwin-arm64\n";
+        assert_eq!(
+            expected_sha256("vaultic-darwin-arm64", checksums).as_deref(),
+            Some("def456")
+        );
+        assert_eq!(
+            expected_sha256("vaultic-windows-amd64.exe", checksums),
+            None
+        );
+    }
+
     #[test]
     fn verify_sha256_fails_when_asset_missing() {
         let data = b"binary content";
@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::Path;
+
+use clap::CommandFactory;
+
+use crate::cli::Cli;
+use crate::config::app_config::AppConfig;
+use crate::core::errors::{Result, VaulticError};
+
+/// Global flags that take a value, so the value is not mistaken for the
+/// subcommand when looking for an alias.
+const VALUE_FLAGS: &[&str] = &["--cipher", "--env", "--config"];
+
+/// Expand a `[aliases]` entry from config.toml in the raw command line.
+///
+/// The first positional argument is looked up among the aliases unless it
+/// is a built-in command, which always wins. The alias value is split into
+/// words and spliced in its place, so global flags before the alias and
+/// extra arguments after it still apply. Aliases are expanded once and
+/// cannot refer to other aliases.
+pub fn expand(args: Vec<OsString>) -> Result<Vec<OsString>> {
+    let Some(pos) = subcommand_position(&args) else {
+        return Ok(args);
+    };
+    let Some(name) = args[pos].to_str() else {
+        return Ok(args);
+    };
+    if is_builtin(name) {
+        return Ok(args);
+    }
+
+    let vaultic_dir = config_dir(&args[..pos]);
+    let Some(aliases) = AppConfig::load(Path::new(&vaultic_dir))
+        .ok()
+        .and_then(|c| c.aliases)
+    else {
+        return Ok(args);
+    };
+
+    expand_with(args, pos, &aliases)
+}
+
+/// Splice the alias at `pos`, if one is defined.
+fn expand_with(
+    mut args: Vec<OsString>,
+    pos: usize,
+    aliases: &HashMap<String, String>,
+) -> Result<Vec<OsString>> {
+    let name = args[pos].to_string_lossy().into_owned();
+    let Some(value) = aliases.get(&name) else {
+        return Ok(args);
+    };
+    let words = split_words(value).map_err(|detail| VaulticError::InvalidConfig {
+        detail: format!("Invalid alias '{name}' in config.toml: {detail}"),
+    })?;
+    if words.is_empty() {
+        return Err(VaulticError::InvalidConfig {
+            detail: format!("Alias '{name}' in config.toml is empty"),
+        });
+    }
+    args.splice(pos..=pos, words.into_iter().map(OsString::from));
+    Ok(args)
+}
+
+/// Index of the first argument that is not a global flag or its value.
+fn subcommand_position(args: &[OsString]) -> Option<usize> {
+    let mut i = 1;
+    while i < args.len() {
+        let arg = args[i].to_string_lossy();
+        if VALUE_FLAGS.contains(&arg.as_ref()) {
+            i += 2;
+        } else if arg.starts_with('-') {
+            i += 1;
+        } else {
+            return Some(i);
+        }
+    }
+    None
+}
+
+/// Whether `name` is a built-in subcommand (or one of its aliases).
+fn is_builtin(name: &str) -> bool {
+    name == "help"
+        || Cli::command()
+            .get_subcommands()
+            .any(|c| c.get_name() == name || c.get_all_aliases().any(|a| a == name))
+}
+
+/// The `.vaultic` directory selected by a `--config` flag, falling back
+/// to `VAULTIC_CONFIG` the same way clap does for `context::init`.
+fn config_dir(global_args: &[OsString]) -> OsString {
+    let mut dir = std::env::var_os("VAULTIC_CONFIG")
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| OsString::from(".vaultic"));
+    let mut iter = global_args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--config" {
+            if let Some(value) = iter.next() {
+                dir = value.clone();
+            }
+        } else if let Some(value) = arg.to_str().and_then(|a| a.strip_prefix("--config=")) {
+            dir = value.into();
+        }
+    }
+    dir
+}
+
+/// Split an alias or hook command into words, honoring single and double
+/// quotes.
+pub fn split_words(value: &str) -> std::result::Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+
+    for c in value.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_word = true;
+            }
+            None if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            None => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if let Some(q) = quote {
+        return Err(format!("unterminated {q} quote"));
+    }
+    if in_word {
+        words.push(current);
+    }
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<OsString> {
+        line.split_whitespace().map(OsString::from).collect()
+    }
+
+    fn aliases() -> HashMap<String, String> {
+        HashMap::from([(
+            "deploy-prep".to_string(),
+            "resolve --env prod -o deploy/.env".to_string(),
+        )])
+    }
+
+    #[test]
+    fn expands_alias_keeping_surrounding_args() {
+        let input = args("vaultic --quiet deploy-prep --verbose");
+        let pos = subcommand_position(&input).unwrap();
+        let expanded = expand_with(input, pos, &aliases()).unwrap();
+        assert_eq!(
+            expanded,
+            args("vaultic --quiet resolve --env prod -o deploy/.env --verbose")
+        );
+    }
+
+    #[test]
+    fn skips_values_of_global_flags() {
+        let input = args("vaultic --env dev --config other deploy-prep");
+        assert_eq!(subcommand_position(&input), Some(5));
+        assert_eq!(config_dir(&input[..5]), "other");
+    }
+
+    #[test]
+    fn builtins_are_never_aliases() {
+        assert!(is_builtin("resolve"));
+        assert!(is_builtin("help"));
+        assert!(!is_builtin("deploy-prep"));
+    }
+
+    #[test]
+    fn unknown_names_pass_through() {
+        let input = args("vaultic nope");
+        assert_eq!(expand_with(input.clone(), 1, &aliases()).unwrap(), input);
+    }
+
+    #[test]
+    fn split_words_honors_quotes() {
+        assert_eq!(
+            split_words(r#"encrypt --note "weekly rotation" --env 'prod'"#).unwrap(),
+            vec!["encrypt", "--note", "weekly rotation", "--env", "prod"]
+        );
+        assert_eq!(
+            split_words(r#"run -- sh -c """#).unwrap().last().unwrap(),
+            ""
+        );
+        assert!(split_words("encrypt --note 'open").is_err());
+    }
+}
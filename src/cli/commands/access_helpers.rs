@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::adapters::cipher::age_backend::AgeBackend;
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::audit_entry::{AuditAction, AuditDetail};
+
+/// Consecutive failed decrypts of one file before the remediation notice
+/// is shown and the attempt is recorded in the audit log.
+const FAILURE_LIMIT: u32 = 3;
+
+/// Failed decrypts of one file since its last successful decrypt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FailureStreak {
+    count: u32,
+    since: DateTime<Utc>,
+}
+
+/// Run `decrypt` for `enc_path` and track failures caused by a key that
+/// is not a recipient.
+///
+/// The streak is kept per user and per file outside the repository, so a
+/// misconfigured key and someone probing a shared host both stand out
+/// after [`FAILURE_LIMIT`] attempts. A successful decrypt clears it.
+pub fn tracked<T>(enc_path: &Path, decrypt: impl FnOnce() -> Result<T>) -> Result<T> {
+    let result = decrypt();
+    match &result {
+        Ok(_) => update(enc_path, |streaks, key| streaks.remove(key).is_some()),
+        Err(VaulticError::DecryptionNoKey) => record_failure(enc_path),
+        Err(_) => {}
+    }
+    result
+}
+
+fn record_failure(enc_path: &Path) {
+    let now = crate::cli::context::clock().now();
+    let mut streak = None;
+    update(enc_path, |streaks, key| {
+        let entry = streaks.entry(key.to_string()).or_insert(FailureStreak {
+            count: 0,
+            since: now,
+        });
+        entry.count += 1;
+        streak = Some(entry.clone());
+        true
+    });
+    let Some(streak) = streak.filter(|s| s.count >= FAILURE_LIMIT) else {
+        return;
+    };
+
+    print_notice(enc_path, &streak);
+    if streak.count == FAILURE_LIMIT {
+        // The default identity, unless VAULTIC_AGE_KEY supplied another one
+        let key = std::env::var_os("VAULTIC_AGE_KEY")
+            .is_none()
+            .then(crate::cli::context::identity_path)
+            .and_then(|p| p.ok())
+            .and_then(|p| AgeBackend::read_public_key(&p).ok());
+        super::audit_helpers::log_audit(
+            AuditAction::DecryptFailed,
+            vec![file_name(enc_path)],
+            Some(AuditDetail::DecryptFailed {
+                attempts: streak.count,
+                key,
+                since: streak.since,
+            }),
+        );
+    }
+}
+
+/// Print the remediation notice. Always shown, on stderr, since it may
+/// be the only sign of someone else using the account.
+fn print_notice(enc_path: &Path, streak: &FailureStreak) {
+    eprintln!(
+        "\n  {} {}",
+        "✗".red(),
+        format!(
+            "{} consecutive failed decrypts of {} since {}",
+            streak.count,
+            enc_path.display(),
+            streak.since.format("%Y-%m-%d %H:%M")
+        )
+        .red()
+        .bold()
+    );
+    eprintln!(
+        "    Your key cannot open this file. If you did not expect that:\n    \
+         → Check which key you are using: vaultic keys show\n    \
+         → Compare it with the recipients: vaultic status\n    \
+         → Ask an admin to add it and re-encrypt: vaultic keys add <key>, vaultic encrypt --all\n    \
+         The failed access was recorded in the audit log.\n"
+    );
+}
+
+/// Apply `change` to the stored streaks, keyed by the file's absolute
+/// path, and save them if it reports a change. Tracking is best effort:
+/// errors are ignored.
+fn update(
+    enc_path: &Path,
+    change: impl FnOnce(&mut BTreeMap<String, FailureStreak>, &str) -> bool,
+) {
+    let Some(path) = state_path() else {
+        return;
+    };
+    let mut streaks: BTreeMap<String, FailureStreak> = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    let key = std::fs::canonicalize(enc_path)
+        .unwrap_or_else(|_| enc_path.to_path_buf())
+        .to_string_lossy()
+        .into_owned();
+
+    if !change(&mut streaks, &key) {
+        return;
+    }
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let _ = serde_json::to_string_pretty(&streaks).map(|json| std::fs::write(&path, json));
+}
+
+/// Local state file, next to the update check cache.
+fn state_path() -> Option<PathBuf> {
+    Some(
+        dirs::config_dir()?
+            .join("vaultic")
+            .join("failed_decrypts.json"),
+    )
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
@@ -0,0 +1,275 @@
+use std::path::{Path, PathBuf};
+
+use crate::adapters::archive::tar_archive::{self, ArchiveEntry};
+use crate::adapters::key_stores::file_key_store::FileKeyStore;
+use crate::adapters::key_stores::members_file::MEMBERS_FILE;
+use crate::cli::ArchiveAction;
+use crate::cli::output;
+use crate::config::app_config::AppConfig;
+use crate::config::value_schema::SCHEMA_FILE;
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::audit_entry::{AuditAction, AuditDetail};
+use crate::core::services::encryption_service::EncryptionService;
+use crate::core::traits::cipher::CipherBackend;
+
+const CONFIG_FILE: &str = "config.toml";
+const RECIPIENTS_FILE: &str = "recipients.txt";
+
+/// Execute `vaultic archive <action>`.
+pub fn execute(action: &ArchiveAction, cipher: &str) -> Result<()> {
+    match action {
+        ArchiveAction::Create { output } => execute_create(Path::new(output), cipher),
+        ArchiveAction::Restore { input, force } => {
+            execute_restore(Path::new(input), cipher, *force)
+        }
+    }
+}
+
+/// Bundle config, recipients (including per-environment lists and
+/// `members.toml`), and every `.enc` file into one encrypted archive.
+///
+/// The tarball is built and encrypted in memory; only ciphertext is written.
+fn execute_create(dest: &Path, cipher: &str) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+
+    let entries = collect_entries(vaultic_dir)?;
+    let names: Vec<String> = entries.iter().map(|e| e.name.clone()).collect();
+    for name in &names {
+        output::detail(&format!("Adding {name}"));
+    }
+
+    let packed = tar_archive::pack(&entries)?;
+    let key_store = FileKeyStore::new(vaultic_dir.join(RECIPIENTS_FILE));
+
+    let sp = output::spinner(&format!("Encrypting archive with {cipher}..."));
+    let backend = super::crypto_helpers::encryptor(cipher)?;
+    encrypt_archive(backend, key_store, &packed, dest)?;
+    output::finish_spinner(sp, &format!("Archived {} file(s)", names.len()));
+    output::success(&format!("Saved to {}", dest.display()));
+
+    super::audit_helpers::log_audit(
+        AuditAction::ArchiveCreate,
+        names,
+        Some(AuditDetail::ArchiveCreate {
+            dest: dest.display().to_string(),
+        }),
+    );
+
+    Ok(())
+}
+
+/// Collect the files that make up a project, sorted by name.
+fn collect_entries(vaultic_dir: &Path) -> Result<Vec<ArchiveEntry>> {
+    let mut paths: Vec<PathBuf> = vec![
+        vaultic_dir.join(CONFIG_FILE),
+        vaultic_dir.join(RECIPIENTS_FILE),
+    ];
+    for path in &paths {
+        if !path.exists() {
+            return Err(VaulticError::FileNotFound { path: path.clone() });
+        }
+    }
+
+    let mut encrypted: Vec<PathBuf> = std::fs::read_dir(vaultic_dir)?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.is_file()
+                && (p.extension().is_some_and(|ext| ext == "enc")
+                    || p.file_name().is_some_and(|n| {
+                        let name = n.to_string_lossy();
+                        is_env_recipients(&name) || name == MEMBERS_FILE || name == SCHEMA_FILE
+                    }))
+        })
+        .collect();
+    encrypted.sort();
+    paths.extend(encrypted);
+
+    paths
+        .iter()
+        .map(|path| {
+            Ok(ArchiveEntry {
+                name: path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                data: std::fs::read(path)?,
+            })
+        })
+        .collect()
+}
+
+fn encrypt_archive<C: CipherBackend>(
+    cipher: C,
+    key_store: FileKeyStore,
+    packed: &[u8],
+    dest: &Path,
+) -> Result<()> {
+    let service = EncryptionService { cipher, key_store };
+    service.encrypt_bytes(packed, dest)
+}
+
+/// Decrypt an archive, validate its contents, and write them into `.vaultic/`.
+///
+/// Nothing is written until the whole archive has been validated.
+fn execute_restore(source: &Path, cipher: &str, force: bool) -> Result<()> {
+    if !source.exists() {
+        return Err(VaulticError::FileNotFound {
+            path: source.to_path_buf(),
+        });
+    }
+
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if vaultic_dir.join(CONFIG_FILE).exists() && !force {
+        return Err(VaulticError::InvalidConfig {
+            detail: format!(
+                "{} is already initialized\n\n  \
+                 Solutions:\n    \
+                 → Restore into a fresh checkout\n    \
+                 → Use --force to overwrite the existing files",
+                vaultic_dir.display()
+            ),
+        });
+    }
+
+    let sp = output::spinner("Decrypting archive...");
+    let packed = super::crypto_helpers::decrypt_in_memory(source, vaultic_dir, cipher)?;
+    let entries = tar_archive::unpack(&packed)?;
+    output::finish_spinner(sp, &format!("Decrypted {} file(s)", entries.len()));
+
+    validate_entries(&entries)?;
+
+    std::fs::create_dir_all(vaultic_dir)?;
+    let config = entries
+        .iter()
+        .find(|e| e.name == CONFIG_FILE)
+        .and_then(|e| std::str::from_utf8(&e.data).ok())
+        .and_then(|content| AppConfig::parse(content).ok());
+    for entry in &entries {
+        output::detail(&format!("Restoring {}", entry.name));
+        let path = vaultic_dir.join(&entry.name);
+        let previous = super::audit_helpers::compute_file_hash(&path);
+        std::fs::write(&path, &entry.data)?;
+        let env = config
+            .as_ref()
+            .and_then(|c| super::audit_helpers::env_of_file(c, &entry.name));
+        if let Some(env) = env {
+            super::audit_helpers::record_state(vaultic_dir, &env, previous, &path);
+        }
+    }
+    super::audit_helpers::record_manifest(vaultic_dir);
+
+    output::success(&format!(
+        "Restored {} file(s) into {}",
+        entries.len(),
+        vaultic_dir.display()
+    ));
+    println!("\n  Run 'vaultic status' to review the restored project.");
+
+    super::audit_helpers::log_audit(
+        AuditAction::ArchiveRestore,
+        entries.iter().map(|e| e.name.clone()).collect(),
+        Some(AuditDetail::ArchiveRestore {
+            source: source.display().to_string(),
+        }),
+    );
+
+    Ok(())
+}
+
+/// Check that an unpacked archive looks like a Vaultic project.
+fn validate_entries(entries: &[ArchiveEntry]) -> Result<()> {
+    let find = |name: &str| entries.iter().find(|e| e.name == name);
+    let invalid = |detail: String| VaulticError::InvalidConfig {
+        detail: format!("Invalid archive: {detail}"),
+    };
+
+    let config = find(CONFIG_FILE).ok_or_else(|| invalid(format!("missing {CONFIG_FILE}")))?;
+    let config = std::str::from_utf8(&config.data)
+        .map_err(|_| invalid(format!("{CONFIG_FILE} is not valid UTF-8")))?;
+    AppConfig::parse(config)?;
+
+    find(RECIPIENTS_FILE).ok_or_else(|| invalid(format!("missing {RECIPIENTS_FILE}")))?;
+    for list in entries
+        .iter()
+        .filter(|e| e.name == RECIPIENTS_FILE || is_env_recipients(&e.name))
+    {
+        let recipients = std::str::from_utf8(&list.data)
+            .map_err(|_| invalid(format!("{} is not valid UTF-8", list.name)))?;
+        for line in recipients.lines() {
+            let key = line.split('#').next().unwrap_or_default().trim();
+            if !key.is_empty() {
+                super::keys::validate_recipient_key(key)?;
+            }
+        }
+    }
+
+    for entry in entries {
+        if entry.name != CONFIG_FILE
+            && entry.name != RECIPIENTS_FILE
+            && entry.name != MEMBERS_FILE
+            && entry.name != SCHEMA_FILE
+            && !is_env_recipients(&entry.name)
+            && !entry.name.ends_with(".enc")
+        {
+            return Err(invalid(format!("unexpected file '{}'", entry.name)));
+        }
+        crate::config::names::validate_simple_filename(&entry.name, "archive entry")?;
+    }
+
+    Ok(())
+}
+
+/// Whether `name` is a per-environment recipient list (`recipients.<env>.txt`).
+fn is_env_recipients(name: &str) -> bool {
+    name.strip_prefix("recipients.")
+        .and_then(|rest| rest.strip_suffix(".txt"))
+        .is_some_and(|env| !env.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str =
+        "[vaultic]\nversion = \"0.1.0\"\ndefault_cipher = \"age\"\n\n[environments]\ndev = {}\n";
+
+    fn entry(name: &str, data: &str) -> ArchiveEntry {
+        ArchiveEntry {
+            name: name.into(),
+            data: data.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn validate_accepts_project_files() {
+        let entries = vec![
+            entry(CONFIG_FILE, CONFIG),
+            entry(RECIPIENTS_FILE, "user@example.com # ci\n"),
+            entry("recipients.prod.txt", "ops@example.com\n"),
+            entry("dev.env.enc", "ciphertext"),
+        ];
+        assert!(validate_entries(&entries).is_ok());
+        assert!(!is_env_recipients("recipients.txt"));
+    }
+
+    #[test]
+    fn validate_requires_config() {
+        let entries = vec![entry(RECIPIENTS_FILE, "")];
+        let err = validate_entries(&entries).unwrap_err().to_string();
+        assert!(err.contains("missing config.toml"));
+    }
+
+    #[test]
+    fn validate_rejects_unexpected_files() {
+        let entries = vec![
+            entry(CONFIG_FILE, CONFIG),
+            entry(RECIPIENTS_FILE, ""),
+            entry(".env", "SECRET=plaintext"),
+        ];
+        let err = validate_entries(&entries).unwrap_err().to_string();
+        assert!(err.contains("unexpected file '.env'"));
+    }
+}
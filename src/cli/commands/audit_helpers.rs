@@ -1,47 +1,15 @@
 use std::path::Path;
-use std::process::Command;
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use sha2::{Digest, Sha256};
 
 use crate::adapters::audit::json_audit_logger::JsonAuditLogger;
+use crate::adapters::key_stores::manifest_file::{MANIFEST_FILE, ManifestFile};
 use crate::cli::output;
 use crate::config::app_config::AppConfig;
-use crate::core::models::audit_entry::{AuditAction, AuditEntry};
+use crate::core::models::audit_entry::{AuditAction, AuditDetail, AuditEntry};
 use crate::core::traits::audit::AuditLogger;
 
-/// Read the git user name and email from the local/global config.
-/// Returns `("unknown", None)` if git is not available.
-pub fn git_author() -> (String, Option<String>) {
-    let name = Command::new("git")
-        .args(["config", "user.name"])
-        .output()
-        .ok()
-        .and_then(|o| {
-            if o.status.success() {
-                Some(String::from_utf8_lossy(&o.stdout).trim().to_string())
-            } else {
-                None
-            }
-        })
-        .unwrap_or_else(|| "unknown".to_string());
-
-    let email = Command::new("git")
-        .args(["config", "user.email"])
-        .output()
-        .ok()
-        .and_then(|o| {
-            if o.status.success() {
-                let val = String::from_utf8_lossy(&o.stdout).trim().to_string();
-                if val.is_empty() { None } else { Some(val) }
-            } else {
-                None
-            }
-        });
-
-    (name, email)
-}
-
 /// Compute the SHA-256 hash of a file, returning the hex string.
 /// Returns `None` if the file cannot be read.
 pub fn compute_file_hash(path: &Path) -> Option<String> {
@@ -52,7 +20,7 @@ pub fn compute_file_hash(path: &Path) -> Option<String> {
 
 /// Record an audit event. Warns on failure instead of propagating
 /// the error, since audit should not block the main operation.
-pub fn log_audit(action: AuditAction, files: Vec<String>, detail: Option<String>) {
+pub fn log_audit(action: AuditAction, files: Vec<String>, detail: Option<AuditDetail>) {
     log_audit_with_hash(action, files, detail, None);
 }
 
@@ -60,24 +28,48 @@ pub fn log_audit(action: AuditAction, files: Vec<String>, detail: Option<String>
 pub fn log_audit_with_hash(
     action: AuditAction,
     files: Vec<String>,
-    detail: Option<String>,
+    detail: Option<AuditDetail>,
     state_hash: Option<String>,
 ) {
-    let vaultic_dir = crate::cli::context::vaultic_dir();
+    log_audit_in(
+        crate::cli::context::vaultic_dir(),
+        action,
+        files,
+        detail,
+        state_hash,
+    );
+}
 
-    let config = AppConfig::load(vaultic_dir).ok();
+/// Record an audit event in the project at `vaultic_dir` rather than the
+/// current one.
+pub fn log_audit_in(
+    vaultic_dir: &Path,
+    action: AuditAction,
+    files: Vec<String>,
+    detail: Option<AuditDetail>,
+    state_hash: Option<String>,
+) {
+    // Another project (e.g. the destination of `vaultic move`) is read
+    // fresh; the current one reuses the cached config.
+    let loaded;
+    let config = if vaultic_dir == crate::cli::context::vaultic_dir() {
+        crate::cli::context::config().ok()
+    } else {
+        loaded = AppConfig::load(vaultic_dir).ok();
+        loaded.as_ref()
+    };
 
-    let audit_section = config.as_ref().and_then(|c| c.audit.as_ref());
+    let audit_section = config.and_then(|c| c.audit.as_ref());
 
     if !JsonAuditLogger::is_enabled(audit_section) {
         return;
     }
 
     let logger = JsonAuditLogger::from_config(vaultic_dir, audit_section);
-    let (author, email) = git_author();
+    let (author, email) = crate::cli::context::author_provider().author();
 
     let entry = AuditEntry {
-        timestamp: Utc::now(),
+        timestamp: crate::cli::context::clock().now(),
         author,
         email,
         action,
@@ -91,20 +83,104 @@ pub fn log_audit_with_hash(
     }
 }
 
+/// Record the checksums of `config.toml` and the recipient lists in
+/// `.vaultic/manifest.toml` after a command changed them, so `status` and
+/// `verify` can tell Vaultic's edits from ones made behind its back.
+/// Warns on failure, like [`log_audit`].
+pub fn record_manifest(vaultic_dir: &Path) {
+    let now = crate::cli::context::clock().now();
+    if let Err(e) = ManifestFile::new(vaultic_dir).record(now) {
+        output::warning(&format!("Could not update {MANIFEST_FILE}: {e}"));
+    }
+}
+
+/// Append the write of `env`'s encrypted file at `enc_path` to its chain
+/// in `.vaultic/manifest.toml`, so `vaultic history <env> --verify` can
+/// tell it from a file replaced outside Vaultic. `prev_hash` is the
+/// file's checksum before the write. Warns on failure, like [`log_audit`].
+pub fn record_state(vaultic_dir: &Path, env: &str, prev_hash: Option<String>, enc_path: &Path) {
+    let Some(new_hash) = compute_file_hash(enc_path) else {
+        return;
+    };
+    let now = crate::cli::context::clock().now();
+    if let Err(e) = ManifestFile::new(vaultic_dir).record_state(env, prev_hash, new_hash, now) {
+        output::warning(&format!("Could not update {MANIFEST_FILE}: {e}"));
+    }
+}
+
+/// The name of `env`'s encrypted file inside `vaultic_dir`.
+///
+/// `{name}.env.enc`, which `encrypt --env` and `rollback` write, unless
+/// only `{file}.enc` exists for an environment with a custom `file`.
+pub fn env_enc_file(vaultic_dir: &Path, config: &AppConfig, env: &str) -> String {
+    let written = format!("{env}.env.enc");
+    let custom = format!("{}.enc", config.env_file_name(env));
+    if !vaultic_dir.join(&written).exists() && vaultic_dir.join(&custom).exists() {
+        custom
+    } else {
+        written
+    }
+}
+
+/// The environment whose encrypted file inside `.vaultic/` is `file_name`.
+///
+/// Like `prune`, recognizes both `{name}.env.enc` and `{file}.enc` for an
+/// environment with a custom `file`. `{name}.env.enc` wins when the two
+/// overlap, as in [`env_enc_file`].
+pub fn env_of_file(config: &AppConfig, file_name: &str) -> Option<String> {
+    let envs = || config.environments.keys();
+    envs()
+        .find(|env| format!("{env}.env.enc") == file_name)
+        .or_else(|| envs().find(|env| format!("{}.enc", config.env_file_name(env)) == file_name))
+        .cloned()
+}
+
+/// When `keys add` or `keys rotate` last recorded `public_key`, according
+/// to `entries`. `recipients.txt` stores no dates, so this is the only
+/// source for them.
+pub fn key_added_at(entries: &[AuditEntry], public_key: &str) -> Option<DateTime<Utc>> {
+    // Entries from before details were structured hold the summary text
+    let added_detail = format!("added {public_key}");
+    let rotated_suffix = format!(" to {public_key}");
+    entries
+        .iter()
+        .filter(|e| match (&e.action, &e.detail) {
+            (AuditAction::KeyAdd, Some(AuditDetail::KeyAdd { key, .. })) => key == public_key,
+            (AuditAction::KeyRotate, Some(AuditDetail::KeyRotate { to, .. })) => to == public_key,
+            (AuditAction::KeyAdd, Some(AuditDetail::Text(text))) => *text == added_detail,
+            (AuditAction::KeyRotate, Some(AuditDetail::Text(text))) => {
+                text.ends_with(&rotated_suffix)
+            }
+            _ => false,
+        })
+        .map(|e| e.timestamp)
+        .max()
+}
+
+/// When `file` (e.g. `dev.env.enc`) was last written by `vaultic encrypt`,
+/// according to `entries`.
+pub fn last_encrypted_at(entries: &[AuditEntry], file: &str) -> Option<DateTime<Utc>> {
+    entries
+        .iter()
+        .filter(|e| e.action == AuditAction::Encrypt && e.files.iter().any(|f| f == file))
+        .map(|e| e.timestamp)
+        .max()
+}
+
 /// Record an audit event right after `vaultic init`, before config
 /// exists. Uses default values for the logger path.
 pub fn log_audit_init() {
     let vaultic_dir = crate::cli::context::vaultic_dir();
     let logger = JsonAuditLogger::new(vaultic_dir, "audit.log");
-    let (author, email) = git_author();
+    let (author, email) = crate::cli::context::author_provider().author();
 
     let entry = AuditEntry {
-        timestamp: Utc::now(),
+        timestamp: crate::cli::context::clock().now(),
         author,
         email,
         action: AuditAction::Init,
         files: vec![],
-        detail: Some("project initialized".to_string()),
+        detail: Some(AuditDetail::Init),
         state_hash: None,
     };
 
@@ -131,6 +207,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn env_of_file_knows_both_file_names() {
+        let config = AppConfig::parse(
+            r#"
+[vaultic]
+version = "1.0.0"
+default_cipher = "age"
+
+[environments]
+dev = {}
+prod = { file = "production.env" }
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(env_of_file(&config, "dev.env.enc").as_deref(), Some("dev"));
+        assert_eq!(
+            env_of_file(&config, "production.env.enc").as_deref(),
+            Some("prod")
+        );
+        assert_eq!(
+            env_of_file(&config, "prod.env.enc").as_deref(),
+            Some("prod")
+        );
+        assert_eq!(env_of_file(&config, "qa.env.enc"), None);
+    }
+
+    #[test]
+    fn env_enc_file_prefers_the_file_encrypt_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = AppConfig::parse(
+            r#"
+[vaultic]
+version = "1.0.0"
+default_cipher = "age"
+
+[environments]
+prod = { file = "production.env" }
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(env_enc_file(dir.path(), &config, "prod"), "prod.env.enc");
+        std::fs::write(dir.path().join("production.env.enc"), "x").unwrap();
+        assert_eq!(
+            env_enc_file(dir.path(), &config, "prod"),
+            "production.env.enc"
+        );
+        std::fs::write(dir.path().join("prod.env.enc"), "x").unwrap();
+        assert_eq!(env_enc_file(dir.path(), &config, "prod"), "prod.env.enc");
+    }
+
     #[test]
     fn compute_file_hash_nonexistent_returns_none() {
         let result = compute_file_hash(Path::new("/nonexistent/file.txt"));
@@ -0,0 +1,150 @@
+use std::path::{Path, PathBuf};
+
+use crate::adapters::archive::env_bundle::{BundleMetadata, ENV_BUNDLE_VERSION, EnvBundle};
+use crate::adapters::cipher::age_backend::AgeBackend;
+use crate::adapters::parsers::dotenv_parser::DotenvParser;
+use crate::cli::BundleAction;
+use crate::cli::commands::{crypto_helpers, reference_helpers};
+use crate::cli::output;
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::audit_entry::{AuditAction, AuditDetail};
+use crate::core::models::key_identity::KeyIdentity;
+use crate::core::traits::cipher::CipherBackend;
+use crate::core::traits::parser::ConfigParser;
+
+/// Execute `vaultic bundle`, or `vaultic bundle extract`.
+pub fn execute(
+    action: Option<&BundleAction>,
+    env: Option<&str>,
+    recipients: &[String],
+    output_path: Option<&str>,
+    cipher: &str,
+) -> Result<()> {
+    match action {
+        Some(BundleAction::Extract {
+            bundle,
+            output,
+            key,
+        }) => execute_extract(Path::new(bundle), Path::new(output), key.as_deref(), cipher),
+        None => execute_create(env, recipients, output_path, cipher),
+    }
+}
+
+/// Resolve `env` and encrypt it, with metadata and checksums, to
+/// `recipients` only.
+///
+/// References are always fetched: the host that extracts the bundle has
+/// no `[references]` configuration to fetch them with.
+fn execute_create(
+    env: Option<&str>,
+    recipients: &[String],
+    output_path: Option<&str>,
+    cipher: &str,
+) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+    if recipients.is_empty() {
+        return Err(VaulticError::InvalidConfig {
+            detail: "A bundle is encrypted to the deployment host's key only. \
+                     Pass it with --recipient <key>."
+                .into(),
+        });
+    }
+    for key in recipients {
+        super::keys::validate_recipient_key(key)?;
+    }
+
+    let config = crate::cli::context::config()?;
+    let env_name = &crate::cli::context::select_env(env, Some(config))?;
+    config.require_env(env_name)?;
+    crypto_helpers::check_recipients_signature(vaultic_dir, Some(config))?;
+
+    output::header(&format!("Bundling environment: {env_name}"));
+    let environment = crypto_helpers::resolve_env(env_name, vaultic_dir, cipher, config, true)?;
+    let resolved = reference_helpers::apply(environment.resolved, Some(true), config)?;
+    let bundle = EnvBundle {
+        metadata: BundleMetadata {
+            version: ENV_BUNDLE_VERSION,
+            env: env_name.clone(),
+            layers: environment.layers,
+            variables: resolved.keys().len(),
+            created_at: crate::cli::context::clock().now(),
+            vaultic_version: env!("CARGO_PKG_VERSION").to_string(),
+        },
+        content: DotenvParser.serialize(&resolved)?.into_bytes(),
+    };
+
+    let keys: Vec<KeyIdentity> = recipients
+        .iter()
+        .map(|key| KeyIdentity {
+            public_key: key.clone(),
+            label: None,
+            added_at: None,
+        })
+        .collect();
+    let ciphertext = crypto_helpers::encryptor(cipher)?.encrypt(&bundle.pack()?, &keys)?;
+    let dest = output_path
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(format!("{env_name}.vaultic-bundle")));
+    std::fs::write(&dest, ciphertext)?;
+
+    super::audit_helpers::log_audit(
+        AuditAction::BundleCreate,
+        vec![format!("{}.enc", config.env_file_name(env_name))],
+        Some(AuditDetail::BundleCreate {
+            recipients: recipients.to_vec(),
+            dest: dest.display().to_string(),
+        }),
+    );
+
+    output::success(&format!(
+        "Bundled {} variables from {} layer(s) into {}",
+        bundle.metadata.variables,
+        bundle.metadata.layers.len(),
+        dest.display()
+    ));
+    println!("\n  Copy it to the host and run:");
+    println!("    vaultic bundle extract {} -o .env", dest.display());
+    Ok(())
+}
+
+/// Decrypt a bundle with this host's key, verify it, and write the
+/// secrets to `dest`. Works outside any Vaultic project.
+fn execute_extract(source: &Path, dest: &Path, key_path: Option<&str>, cipher: &str) -> Result<()> {
+    if !source.exists() {
+        return Err(VaulticError::FileNotFound {
+            path: source.to_path_buf(),
+        });
+    }
+    let backend: Box<dyn CipherBackend> = match key_path {
+        Some(p) => {
+            let path = PathBuf::from(p);
+            if !path.exists() {
+                return Err(VaulticError::FileNotFound { path });
+            }
+            Box::new(AgeBackend::new(path))
+        }
+        None => crypto_helpers::decryptor(cipher)?,
+    };
+
+    let packed = backend.decrypt(&std::fs::read(source)?)?;
+    let bundle = EnvBundle::unpack(&packed)?;
+    std::fs::write(dest, &bundle.content)?;
+
+    let metadata = &bundle.metadata;
+    output::success(&format!(
+        "Extracted {} variables of {} to {}",
+        metadata.variables,
+        metadata.env,
+        dest.display()
+    ));
+    output::detail(&format!(
+        "Bundled {} by Vaultic {} from {}",
+        metadata.created_at.format("%Y-%m-%d %H:%M UTC"),
+        metadata.vaultic_version,
+        metadata.layers.join(" -> ")
+    ));
+    Ok(())
+}
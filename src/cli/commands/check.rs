@@ -1,22 +1,46 @@
+use std::collections::HashSet;
 use std::path::Path;
 
+use colored::Color;
+use serde::Serialize;
+
+use crate::adapters::audit::json_audit_logger::JsonAuditLogger;
+use crate::adapters::git::git_history;
 use crate::adapters::parsers::dotenv_parser::DotenvParser;
+use crate::cli::commands::crypto_helpers;
+use crate::cli::commands::junit_helpers::{self, TestCase, TestSuite};
 use crate::cli::output;
-use crate::config::app_config::AppConfig;
+use crate::cli::table::{Cell, Column, Table};
+use crate::config::app_config::{AppConfig, OutputEntry, SecretFormat};
+use crate::config::value_schema::{self, SCHEMA_FILE};
 use crate::core::errors::{Result, VaulticError};
-use crate::core::services::check_service::CheckService;
+use crate::core::models::audit_entry::{AuditAction, AuditDetail};
+use crate::core::models::secret_file::SecretFile;
+use crate::core::services::check_service::{CheckResult, CheckService};
+use crate::core::services::policy_service::{PolicyService, PolicyViolation};
+use crate::core::services::scan_service::{KnownSecret, ScanService, SecretLeak};
 use crate::core::services::template_resolver::TemplateResolver;
+use crate::core::services::validation_service::ValidationService;
+use crate::core::traits::audit::AuditLogger;
 use crate::core::traits::parser::ConfigParser;
 
 /// Execute the `vaultic check` command.
 ///
 /// Compares the local `.env` against the template file and reports
-/// missing, extra, and empty-value variables.
+/// missing, extra, and empty-value variables, or prints the same report
+/// as JSON with `--json`.
 ///
 /// The template is resolved using a priority chain:
 /// 1. `template` in config.toml (if configured)
 /// 2. Auto-discovery: `.env.template`, `.env.example`, `.env.sample`, `env.template`
-pub fn execute() -> Result<()> {
+///
+/// With `all_outputs`, checks every `[outputs]` entry instead. `report`
+/// (`json` or `junit`) is emitted on stdout or into `output_path`.
+pub fn execute(all_outputs: bool, report: Option<&str>, output_path: Option<&str>) -> Result<()> {
+    if all_outputs {
+        return check_outputs(report, output_path);
+    }
+
     let env_path = Path::new(".env");
 
     if !env_path.exists() {
@@ -27,14 +51,13 @@ pub fn execute() -> Result<()> {
 
     // Load config if available (non-fatal — check works without .vaultic/)
     let project_root = Path::new(".");
-    let vaultic_dir = Path::new(".vaultic");
-    let config = if vaultic_dir.exists() {
-        AppConfig::load(vaultic_dir).ok()
+    let config = if crate::cli::context::vaultic_dir().exists() {
+        crate::cli::context::config().ok()
     } else {
         None
     };
 
-    let template_path = TemplateResolver::resolve_global(config.as_ref(), project_root)?;
+    let template_path = TemplateResolver::resolve_global(config, project_root)?;
 
     let parser = DotenvParser;
     let env_content = std::fs::read_to_string(env_path)?;
@@ -43,14 +66,174 @@ pub fn execute() -> Result<()> {
     let env_file = parser.parse(&env_content)?;
     let template_file = parser.parse(&template_content)?;
 
-    let svc = CheckService;
-    let result = svc.check(&env_file, &template_file)?;
+    report_against_template(
+        &TemplateCheck {
+            name: ".env",
+            layers: None,
+            file: &env_file,
+            template_path: &template_path,
+            template: &template_file,
+        },
+        report,
+        output_path,
+    )?;
+    Ok(())
+}
+
+/// Execute `vaultic check --env <env>`.
+///
+/// Resolves `env_name` through its inheritance chain in memory and
+/// checks it against its own template (see
+/// [`TemplateResolver::resolve_for_env`]), so CI can assert an
+/// environment has every variable the app needs without decrypting to
+/// disk. Unlike the `.env` check, any issue fails the command.
+pub fn resolved(
+    env_name: &str,
+    cipher: &str,
+    report: Option<&str>,
+    output_path: Option<&str>,
+) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+    let config = crate::cli::context::config()?;
+    config.require_env(env_name)?;
+
+    let template_path =
+        TemplateResolver::resolve_for_env(env_name, config, vaultic_dir, Path::new("."))?;
+    let environment = crypto_helpers::resolve_env(env_name, vaultic_dir, cipher, config, false)?;
+    let template_file = parse_file(&template_path)?;
+
+    let result = report_against_template(
+        &TemplateCheck {
+            name: env_name,
+            layers: Some(&environment.layers),
+            file: &environment.resolved,
+            template_path: &template_path,
+            template: &template_file,
+        },
+        report,
+        output_path,
+    )?;
+    if !result.is_ok() {
+        return Err(VaulticError::TemplateCheckFailed { count: 1 });
+    }
+    Ok(())
+}
+
+/// A secrets file, or resolved environment, and the template it is
+/// checked against.
+struct TemplateCheck<'a> {
+    /// `.env`, or the environment's name.
+    name: &'a str,
+    /// Inheritance chain of a resolved environment, base first.
+    layers: Option<&'a [String]>,
+    file: &'a SecretFile,
+    template_path: &'a Path,
+    template: &'a SecretFile,
+}
+
+/// Compare `check.file` with its template, emit the report in the
+/// requested format and record the check in the audit log.
+fn report_against_template(
+    check: &TemplateCheck,
+    report: Option<&str>,
+    output_path: Option<&str>,
+) -> Result<CheckResult> {
+    let result = CheckService.check(check.file, check.template)?;
 
-    let total_template = template_file.keys().len();
+    let total_template = check.template.keys().len();
     let present = total_template - result.missing.len();
 
+    let json = CheckJson {
+        env: check.layers.map(|_| check.name),
+        template: check.template_path.display().to_string(),
+        total: total_template,
+        present,
+        missing: &result.missing,
+        extra: &result.extra,
+        empty_values: &result.empty_values,
+        ok: result.is_ok(),
+    };
+    if let Some(format) = report {
+        let template_keys: Vec<String> = check
+            .template
+            .keys()
+            .into_iter()
+            .map(String::from)
+            .collect();
+        junit_helpers::emit(
+            format,
+            "vaultic check",
+            || {
+                vec![junit_helpers::template_suite(
+                    check.name,
+                    &template_keys,
+                    &result.missing,
+                    &result.extra,
+                    &result.empty_values,
+                    None,
+                )]
+            },
+            &json,
+            output_path,
+        )?;
+    }
+    if junit_helpers::stdout_free(report, output_path) {
+        if output::is_json_mode() {
+            output::json(&json)?;
+        } else {
+            print_report(&result, check, present, total_template);
+        }
+    }
+
+    // Audit
+    let detail = AuditDetail::CheckTemplate {
+        present,
+        total: total_template,
+        missing: result.missing.len(),
+    };
+    let file = match check.layers {
+        Some(_) => format!("{}.env.enc", check.name),
+        None => check.name.to_string(),
+    };
+    super::audit_helpers::log_audit(AuditAction::Check, vec![file], Some(detail));
+
+    Ok(result)
+}
+
+/// `vaultic check --json` output.
+#[derive(Serialize)]
+struct CheckJson<'a> {
+    /// Set when a resolved environment was checked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    env: Option<&'a str>,
+    template: String,
+    total: usize,
+    present: usize,
+    missing: &'a [String],
+    extra: &'a [String],
+    empty_values: &'a [String],
+    ok: bool,
+}
+
+/// Print the missing, extra and empty variables with a summary line.
+fn print_report(
+    result: &CheckResult,
+    check: &TemplateCheck,
+    present: usize,
+    total_template: usize,
+) {
     output::header("🔍 vaultic check");
-    output::detail(&format!("Template: {}", template_path.display()));
+    if let Some(layers) = check.layers {
+        output::detail(&format!(
+            "Environment: {} ({})",
+            check.name,
+            layers.join(" → ")
+        ));
+    }
+    output::detail(&format!("Template: {}", check.template_path.display()));
 
     if !result.missing.is_empty() {
         output::warning(&format!("Missing variables ({}):", result.missing.len()));
@@ -90,21 +273,870 @@ pub fn execute() -> Result<()> {
             result.issue_count()
         ));
     }
+}
 
-    // Audit
-    let detail = if result.is_ok() {
-        format!("{present}/{total_template} present")
+/// Files larger than this are not scanned; they are almost never
+/// hand-written source.
+const MAX_SCAN_BYTES: usize = 5 * 1024 * 1024;
+
+/// Execute `vaultic check --scan`.
+///
+/// Decrypts `envs` (every environment when empty) in memory and searches
+/// the files tracked by git, and with `deep` every commit in the history,
+/// for their current values. Environments this key cannot decrypt are
+/// skipped with a warning. Fails with the number of leaks found so CI
+/// can gate on it. `report` is emitted as in [`execute`].
+pub fn scan(
+    envs: &[String],
+    deep: bool,
+    cipher: &str,
+    report: Option<&str>,
+    output_path: Option<&str>,
+) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+    let config = crate::cli::context::config()?;
+    for env in envs {
+        config.require_env(env)?;
+    }
+    let mut names: Vec<&String> = if envs.is_empty() {
+        config.environments.keys().collect()
     } else {
-        format!(
-            "{present}/{total_template} present, {} missing",
-            result.missing.len()
-        )
+        envs.iter().collect()
+    };
+    names.sort();
+    names.dedup();
+
+    let mut secrets = Vec::new();
+    let mut scanned_envs = Vec::new();
+    for name in names {
+        let enc_path = vaultic_dir.join(format!("{name}.env.enc"));
+        if !enc_path.exists() {
+            continue;
+        }
+        let plaintext = match crypto_helpers::decrypt_in_memory(&enc_path, vaultic_dir, cipher) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                output::warning(&format!("Skipping {name}: {e}"));
+                continue;
+            }
+        };
+        let file = crate::adapters::parsers::for_format(config.env_format(name))
+            .parse(&String::from_utf8_lossy(&plaintext))?;
+        secrets.extend(ScanService::secrets(name, &file));
+        scanned_envs.push(name.clone());
+    }
+
+    let sp = output::spinner("Scanning tracked files...");
+    let tracked = git_history::tracked_files()?;
+    let mut leaks = Vec::new();
+    let mut files_scanned = 0;
+    for path in &tracked {
+        if skip_path(path) {
+            continue;
+        }
+        let Ok(bytes) = std::fs::read(path) else {
+            continue;
+        };
+        if let Some(content) = scannable(&bytes) {
+            files_scanned += 1;
+            leaks.extend(ScanService.scan(&secrets, path, &content, None));
+        }
+    }
+
+    let mut history_leaks = Vec::new();
+    if deep {
+        if let Some(pb) = &sp {
+            pb.set_message("Scanning git history...");
+        }
+        history_leaks = scan_history(&secrets)?;
+    }
+    output::finish_spinner(
+        sp,
+        &format!(
+            "Scanned {files_scanned} tracked file(s){}",
+            if deep { " and the git history" } else { "" }
+        ),
+    );
+
+    let total = leaks.len() + history_leaks.len();
+    let json = ScanJson {
+        environments: &scanned_envs,
+        files_scanned,
+        leaks: &leaks,
+        history_leaks: deep.then_some(history_leaks.as_slice()),
+        ok: total == 0,
     };
+    if let Some(format) = report {
+        junit_helpers::emit(
+            format,
+            "vaultic check --scan",
+            || scan_suites(&scanned_envs, &leaks, &history_leaks),
+            &json,
+            output_path,
+        )?;
+    }
+    if junit_helpers::stdout_free(report, output_path) {
+        if output::is_json_mode() {
+            output::json(&json)?;
+        } else {
+            print_scan(&scanned_envs, &leaks, &history_leaks, deep);
+        }
+    }
+
     super::audit_helpers::log_audit(
-        crate::core::models::audit_entry::AuditAction::Check,
-        vec![".env".to_string()],
-        Some(detail),
+        AuditAction::Check,
+        scanned_envs
+            .iter()
+            .map(|name| format!("{name}.env.enc"))
+            .collect(),
+        Some(AuditDetail::CheckScan { leaks: total }),
     );
 
+    if total > 0 {
+        return Err(VaulticError::SecretLeaksFound { count: total });
+    }
     Ok(())
 }
+
+/// One JUnit suite per scanned environment, with a failed test case per
+/// leaked variable and location, or a passed one when nothing leaked.
+fn scan_suites(
+    envs: &[String],
+    leaks: &[SecretLeak],
+    history_leaks: &[SecretLeak],
+) -> Vec<TestSuite> {
+    envs.iter()
+        .map(|env| {
+            let mut cases: Vec<TestCase> = leaks
+                .iter()
+                .chain(history_leaks)
+                .filter(|leak| &leak.env == env)
+                .map(|leak| {
+                    let location = match &leak.commit {
+                        Some(commit) => format!("{}:{} in {commit}", leak.path, leak.line),
+                        None => format!("{}:{}", leak.path, leak.line),
+                    };
+                    TestCase::failed(
+                        leak.key.as_str(),
+                        "leak",
+                        format!("value of {} found in {location}", leak.key),
+                    )
+                })
+                .collect();
+            if cases.is_empty() {
+                cases.push(TestCase::passed("no leaked values"));
+            }
+            TestSuite {
+                name: env.clone(),
+                cases,
+            }
+        })
+        .collect()
+}
+
+/// Leaks in every commit, each reported once at the commit that first
+/// added it to a file.
+fn scan_history(secrets: &[KnownSecret]) -> Result<Vec<SecretLeak>> {
+    let mut seen = HashSet::new();
+    let mut leaks = Vec::new();
+    for commit in git_history::all_commits()? {
+        let short = git_history::short_hash(&commit)?;
+        for path in git_history::commit_files(&commit)? {
+            if skip_path(&path) {
+                continue;
+            }
+            let Some(bytes) = git_history::show_blob(&commit, &path)? else {
+                continue;
+            };
+            let Some(content) = scannable(&bytes) else {
+                continue;
+            };
+            for leak in ScanService.scan(secrets, &path, &content, Some(&short)) {
+                if seen.insert((leak.path.clone(), leak.env.clone(), leak.key.clone())) {
+                    leaks.push(leak);
+                }
+            }
+        }
+    }
+    Ok(leaks)
+}
+
+/// Vaultic's own files hold only ciphertext.
+fn skip_path(path: &str) -> bool {
+    path.split('/').any(|part| part == ".vaultic")
+}
+
+/// File content as text, or `None` for binary and oversized files.
+fn scannable(bytes: &[u8]) -> Option<String> {
+    if bytes.len() > MAX_SCAN_BYTES || bytes.contains(&0) {
+        return None;
+    }
+    Some(String::from_utf8_lossy(bytes).into_owned())
+}
+
+/// `vaultic check --scan --json` output.
+#[derive(Serialize)]
+struct ScanJson<'a> {
+    environments: &'a [String],
+    files_scanned: usize,
+    leaks: &'a [SecretLeak],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    history_leaks: Option<&'a [SecretLeak]>,
+    ok: bool,
+}
+
+/// Print the leaks found, by file. Values are never shown.
+fn print_scan(envs: &[String], leaks: &[SecretLeak], history_leaks: &[SecretLeak], deep: bool) {
+    output::header("🔍 vaultic check --scan");
+    output::detail(&format!("Environments: {}", envs.join(", ")));
+
+    if envs.is_empty() {
+        output::warning("No environment could be decrypted — nothing to look for.");
+        return;
+    }
+
+    if !leaks.is_empty() {
+        output::warning(&format!(
+            "Secret values in tracked files ({}):",
+            leaks.len()
+        ));
+        let mut table = Table::new(vec![
+            Column::new("File").shrink(12),
+            Column::new("Line"),
+            Column::new("Variable"),
+            Column::new("Environment"),
+        ]);
+        for leak in leaks {
+            table.row(vec![
+                Cell::new(leak.path.as_str()).color(Color::Red),
+                Cell::new(leak.line.to_string()),
+                Cell::new(leak.key.as_str()),
+                Cell::new(leak.env.as_str()).dimmed(),
+            ]);
+        }
+        table.print();
+    }
+
+    if !history_leaks.is_empty() {
+        output::warning(&format!(
+            "Secret values in git history ({}):",
+            history_leaks.len()
+        ));
+        let mut table = Table::new(vec![
+            Column::new("Commit"),
+            Column::new("File").shrink(12),
+            Column::new("Line"),
+            Column::new("Variable"),
+            Column::new("Environment"),
+        ]);
+        for leak in history_leaks {
+            table.row(vec![
+                Cell::new(leak.commit.as_deref().unwrap_or_default()).color(Color::Cyan),
+                Cell::new(leak.path.as_str()).color(Color::Red),
+                Cell::new(leak.line.to_string()),
+                Cell::new(leak.key.as_str()),
+                Cell::new(leak.env.as_str()).dimmed(),
+            ]);
+        }
+        table.print();
+    }
+
+    if leaks.is_empty() && history_leaks.is_empty() {
+        output::success(&format!(
+            "No secret values of {} found in {}",
+            envs.join(", "),
+            if deep {
+                "tracked files or git history"
+            } else {
+                "tracked files"
+            }
+        ));
+    } else {
+        println!();
+    }
+}
+
+/// Execute `vaultic check --policy`.
+///
+/// Decrypts each environment (or only `envs`) in memory and holds its
+/// values to the `[policy]` rules, plus the rotation age recorded in the
+/// audit log. Fails with the number of violations, so CI stops on them.
+pub fn policy(
+    envs: &[String],
+    cipher: &str,
+    report: Option<&str>,
+    output_path: Option<&str>,
+) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+    let config = crate::cli::context::config()?;
+    for env in envs {
+        config.require_env(env)?;
+    }
+    let mut names: Vec<&String> = if envs.is_empty() {
+        config.environments.keys().collect()
+    } else {
+        envs.iter().collect()
+    };
+    names.sort();
+    names.dedup();
+
+    let rules = config.policy();
+    let entries = JsonAuditLogger::from_config(vaultic_dir, config.audit.as_ref())
+        .query(None, None)
+        .unwrap_or_default();
+    let now = crate::cli::context::clock().now();
+
+    let mut violations = Vec::new();
+    let mut checked_envs = Vec::new();
+    for name in names {
+        let enc_file = format!("{}.enc", config.env_file_name(name));
+        let enc_path = vaultic_dir.join(&enc_file);
+        if !enc_path.exists() {
+            continue;
+        }
+        let plaintext = match crypto_helpers::decrypt_in_memory(&enc_path, vaultic_dir, cipher) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                output::warning(&format!("Skipping {name}: {e}"));
+                continue;
+            }
+        };
+        let file = crate::adapters::parsers::for_format(config.env_format(name))
+            .parse(&String::from_utf8_lossy(&plaintext))?;
+        violations.extend(PolicyService::check_values(name, &file, &rules));
+        let last_encrypted = super::audit_helpers::last_encrypted_at(&entries, &enc_file);
+        violations.extend(PolicyService::check_age(name, last_encrypted, &rules, now));
+        checked_envs.push(name.clone());
+    }
+
+    let json = PolicyJson {
+        environments: &checked_envs,
+        violations: &violations,
+        ok: violations.is_empty(),
+    };
+    if let Some(format) = report {
+        junit_helpers::emit(
+            format,
+            "vaultic check --policy",
+            || policy_suites(&checked_envs, &violations),
+            &json,
+            output_path,
+        )?;
+    }
+    if junit_helpers::stdout_free(report, output_path) {
+        if output::is_json_mode() {
+            output::json(&json)?;
+        } else {
+            print_policy(&checked_envs, &violations);
+        }
+    }
+
+    super::audit_helpers::log_audit(
+        AuditAction::Check,
+        checked_envs
+            .iter()
+            .map(|name| format!("{}.enc", config.env_file_name(name)))
+            .collect(),
+        Some(AuditDetail::CheckPolicy {
+            violations: violations.len(),
+        }),
+    );
+
+    if !violations.is_empty() {
+        return Err(VaulticError::PolicyViolations {
+            count: violations.len(),
+        });
+    }
+    Ok(())
+}
+
+/// One JUnit suite per checked environment, with a failed test case per
+/// violation, or a passed one when the environment follows the policy.
+fn policy_suites(envs: &[String], violations: &[PolicyViolation]) -> Vec<TestSuite> {
+    envs.iter()
+        .map(|env| {
+            let mut cases: Vec<TestCase> = violations
+                .iter()
+                .filter(|v| &v.env == env)
+                .map(|v| {
+                    TestCase::failed(
+                        v.key.as_deref().unwrap_or(env.as_str()),
+                        v.rule.label(),
+                        v.detail.clone(),
+                    )
+                })
+                .collect();
+            if cases.is_empty() {
+                cases.push(TestCase::passed("policy"));
+            }
+            TestSuite {
+                name: env.clone(),
+                cases,
+            }
+        })
+        .collect()
+}
+
+/// `vaultic check --policy --json` output.
+#[derive(Serialize)]
+struct PolicyJson<'a> {
+    environments: &'a [String],
+    violations: &'a [PolicyViolation],
+    ok: bool,
+}
+
+/// Print the policy violations, by environment. Values are never shown.
+fn print_policy(envs: &[String], violations: &[PolicyViolation]) {
+    output::header("🔍 vaultic check --policy");
+    output::detail(&format!("Environments: {}", envs.join(", ")));
+
+    if envs.is_empty() {
+        output::warning("No environment could be decrypted — nothing to check.");
+        return;
+    }
+    if violations.is_empty() {
+        output::success(&format!("{} follow the policy", envs.join(", ")));
+        return;
+    }
+
+    output::warning(&format!("Policy violations ({}):", violations.len()));
+    let mut table = Table::new(vec![
+        Column::new("Environment"),
+        Column::new("Variable"),
+        Column::new("Rule"),
+        Column::new("Detail").shrink(12),
+    ]);
+    for v in violations {
+        table.row(vec![
+            Cell::new(v.env.as_str()),
+            Cell::new(v.key.as_deref().unwrap_or("-")),
+            Cell::new(v.rule.label()).color(Color::Red),
+            Cell::new(v.detail.as_str()).dimmed(),
+        ]);
+    }
+    table.print();
+    println!();
+}
+
+/// Execute `vaultic check --schema`.
+///
+/// Resolves `envs`, or every environment that no other one inherits
+/// from, in memory and validates their values against the constraints
+/// in `.vaultic/schema.toml`. Values are never printed. Fails when any
+/// key breaks its rule.
+pub fn schema(
+    envs: &[String],
+    cipher: &str,
+    report: Option<&str>,
+    output_path: Option<&str>,
+) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+    let config = crate::cli::context::config()?;
+    for env in envs {
+        config.require_env(env)?;
+    }
+    let rules = value_schema::load(vaultic_dir)?.ok_or_else(|| VaulticError::InvalidConfig {
+        detail: format!(
+            "No .vaultic/{SCHEMA_FILE} found.\n\n  \
+             Solutions:\n    \
+             → Declare the constraints of each key, e.g.\n      \
+             DATABASE_URL = {{ type = \"url\", required = true }}\n      \
+             PORT = {{ type = \"int\", min = 1024, max = 65535 }}\n      \
+             LOG_LEVEL = {{ type = \"enum\", values = [\"info\", \"debug\"] }}"
+        ),
+    })?;
+    let mut names: Vec<&String> = if envs.is_empty() {
+        deployed_envs(config)
+    } else {
+        envs.iter().collect()
+    };
+    names.sort();
+    names.dedup();
+
+    let mut results = Vec::new();
+    let mut checked_envs = Vec::new();
+    for name in names {
+        if !vaultic_dir
+            .join(format!("{}.enc", config.env_file_name(name)))
+            .exists()
+        {
+            continue;
+        }
+        let environment =
+            match crypto_helpers::resolve_env(name, vaultic_dir, cipher, config, false) {
+                Ok(environment) => environment,
+                Err(e) => {
+                    output::warning(&format!("Skipping {name}: {e}"));
+                    continue;
+                }
+            };
+        let values = environment
+            .resolved
+            .entries()
+            .map(|e| (e.key.clone(), e.value.clone()))
+            .collect();
+        let mut keys = ValidationService::validate(&values, &rules)?.results;
+        keys.sort_by(|a, b| a.key.cmp(&b.key));
+        results.extend(keys.into_iter().map(|k| SchemaResult {
+            env: name.clone(),
+            key: k.key,
+            failures: k.failures,
+        }));
+        checked_envs.push(name.clone());
+    }
+    let failed = results.iter().filter(|r| !r.failures.is_empty()).count();
+
+    let json = SchemaJson {
+        environments: &checked_envs,
+        results: &results,
+        ok: failed == 0,
+    };
+    if let Some(format) = report {
+        junit_helpers::emit(
+            format,
+            "vaultic check --schema",
+            || schema_suites(&checked_envs, &results),
+            &json,
+            output_path,
+        )?;
+    }
+    if junit_helpers::stdout_free(report, output_path) {
+        if output::is_json_mode() {
+            output::json(&json)?;
+        } else {
+            print_schema(&checked_envs, &results, failed);
+        }
+    }
+
+    super::audit_helpers::log_audit(
+        AuditAction::Check,
+        checked_envs
+            .iter()
+            .map(|name| format!("{}.enc", config.env_file_name(name)))
+            .collect(),
+        Some(AuditDetail::CheckSchema { failed }),
+    );
+
+    if failed > 0 {
+        return Err(VaulticError::ValidationFailed { count: failed });
+    }
+    Ok(())
+}
+
+/// Environments that no other environment inherits from: the ones that
+/// get deployed, where every layer is in place.
+fn deployed_envs(config: &AppConfig) -> Vec<&String> {
+    let parents: HashSet<&str> = config
+        .environments
+        .values()
+        .filter_map(|e| e.inherits.as_deref())
+        .collect();
+    config
+        .environments
+        .keys()
+        .filter(|name| !parents.contains(name.as_str()))
+        .collect()
+}
+
+/// One schema rule checked against one environment.
+#[derive(Serialize)]
+struct SchemaResult {
+    env: String,
+    key: String,
+    /// Why the value breaks its rule; empty when it passes.
+    failures: Vec<String>,
+}
+
+/// `vaultic check --schema --json` output.
+#[derive(Serialize)]
+struct SchemaJson<'a> {
+    environments: &'a [String],
+    results: &'a [SchemaResult],
+    ok: bool,
+}
+
+/// One JUnit suite per checked environment, with a test case per rule.
+fn schema_suites(envs: &[String], results: &[SchemaResult]) -> Vec<TestSuite> {
+    envs.iter()
+        .map(|env| TestSuite {
+            name: env.clone(),
+            cases: results
+                .iter()
+                .filter(|r| &r.env == env)
+                .map(|r| {
+                    if r.failures.is_empty() {
+                        TestCase::passed(r.key.as_str())
+                    } else {
+                        TestCase::failed(r.key.as_str(), "schema", r.failures.join("; "))
+                    }
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Print the keys that break the schema, by environment. Values are
+/// never shown.
+fn print_schema(envs: &[String], results: &[SchemaResult], failed: usize) {
+    output::header("🔍 vaultic check --schema");
+    output::detail(&format!("Environments: {}", envs.join(", ")));
+
+    if envs.is_empty() {
+        output::warning("No environment could be decrypted — nothing to check.");
+        return;
+    }
+    if failed == 0 {
+        output::success(&format!("{} match {SCHEMA_FILE}", envs.join(", ")));
+        return;
+    }
+
+    output::warning(&format!("Schema failures ({failed}):"));
+    let mut table = Table::new(vec![
+        Column::new("Environment"),
+        Column::new("Variable"),
+        Column::new("Problem").shrink(12),
+    ]);
+    for r in results.iter().filter(|r| !r.failures.is_empty()) {
+        table.row(vec![
+            Cell::new(r.env.as_str()),
+            Cell::new(r.key.as_str()).color(Color::Red),
+            Cell::new(r.failures.join("; ")).dimmed(),
+        ]);
+    }
+    table.print();
+    println!();
+}
+
+/// Check every `[outputs]` entry against its template and print one
+/// summary row per output.
+fn check_outputs(report: Option<&str>, output_path: Option<&str>) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+    let config = crate::cli::context::config()?;
+    let outputs = config
+        .outputs
+        .as_ref()
+        .filter(|o| !o.is_empty())
+        .ok_or_else(|| VaulticError::InvalidConfig {
+            detail: "No outputs defined in .vaultic/config.toml.\n\n  \
+                     Solutions:\n    \
+                     → List the files to check, e.g.\n      \
+                     [outputs]\n      \
+                     backend = { path = \"backend/.env\" }\n      \
+                     web = { path = \"apps/web/.env.local\", template = \"apps/web/.env.example\" }"
+                .into(),
+        })?;
+
+    let mut names: Vec<&String> = outputs.keys().collect();
+    names.sort();
+    let project_root = Path::new(".");
+    let reports: Vec<OutputReport> = names
+        .into_iter()
+        .map(|name| check_output(name, &outputs[name], project_root))
+        .collect();
+    let passed = reports.iter().filter(|r| r.ok).count();
+
+    let json = OutputsJson {
+        outputs: &reports,
+        ok: passed == reports.len(),
+    };
+    if let Some(format) = report {
+        junit_helpers::emit(
+            format,
+            "vaultic check --all-outputs",
+            || {
+                reports
+                    .iter()
+                    .map(|r| {
+                        junit_helpers::template_suite(
+                            &r.name,
+                            &r.template_keys,
+                            &r.missing,
+                            &r.extra,
+                            &r.empty_values,
+                            r.error.as_deref(),
+                        )
+                    })
+                    .collect()
+            },
+            &json,
+            output_path,
+        )?;
+    }
+    if junit_helpers::stdout_free(report, output_path) {
+        if output::is_json_mode() {
+            output::json(&json)?;
+        } else {
+            print_outputs(&reports, passed);
+        }
+    }
+
+    super::audit_helpers::log_audit(
+        AuditAction::Check,
+        reports.iter().map(|r| r.path.clone()).collect(),
+        Some(AuditDetail::CheckOutputs {
+            passed,
+            total: reports.len(),
+        }),
+    );
+
+    Ok(())
+}
+
+/// Result of checking one `[outputs]` entry.
+#[derive(Serialize)]
+struct OutputReport {
+    name: String,
+    path: String,
+    template: Option<String>,
+    total: usize,
+    present: usize,
+    missing: Vec<String>,
+    extra: Vec<String>,
+    empty_values: Vec<String>,
+    /// Why the output could not be checked (missing file, no template...).
+    error: Option<String>,
+    ok: bool,
+    /// Template variables, in template order, for the JUnit report.
+    #[serde(skip)]
+    template_keys: Vec<String>,
+}
+
+/// `vaultic check --all-outputs --json` output.
+#[derive(Serialize)]
+struct OutputsJson<'a> {
+    outputs: &'a [OutputReport],
+    ok: bool,
+}
+
+fn check_output(name: &str, entry: &OutputEntry, project_root: &Path) -> OutputReport {
+    let mut report = OutputReport {
+        name: name.to_string(),
+        path: entry.path.clone(),
+        template: None,
+        total: 0,
+        present: 0,
+        missing: Vec::new(),
+        extra: Vec::new(),
+        empty_values: Vec::new(),
+        error: None,
+        ok: false,
+        template_keys: Vec::new(),
+    };
+    if let Err(e) = compare_output(entry, project_root, &mut report) {
+        report.error = Some(match e {
+            VaulticError::FileNotFound { .. } => "file not found".into(),
+            VaulticError::TemplateNotFound { .. } => "template not found".into(),
+            other => other.to_string(),
+        });
+    }
+    report
+}
+
+/// Fill `report` with the comparison of one output against its template.
+fn compare_output(
+    entry: &OutputEntry,
+    project_root: &Path,
+    report: &mut OutputReport,
+) -> Result<()> {
+    let path = crate::config::path_expand::expand_path(&entry.path, project_root)?;
+    if !path.exists() {
+        return Err(VaulticError::FileNotFound { path });
+    }
+    let template_path = TemplateResolver::resolve_for_output(entry, project_root)?;
+    report.template = Some(template_path.display().to_string());
+
+    let env_file = parse_file(&path)?;
+    let template_file = parse_file(&template_path)?;
+    let result = CheckService.check(&env_file, &template_file)?;
+
+    report.template_keys = template_file.keys().into_iter().map(String::from).collect();
+    report.total = report.template_keys.len();
+    report.present = report.total - result.missing.len();
+    report.ok = result.is_ok();
+    report.missing = result.missing;
+    report.extra = result.extra;
+    report.empty_values = result.empty_values;
+    Ok(())
+}
+
+/// Parse a file with the parser its extension implies.
+pub fn parse_file(path: &Path) -> Result<crate::core::models::secret_file::SecretFile> {
+    let content = std::fs::read_to_string(path)?;
+    crate::adapters::parsers::for_format(SecretFormat::from_path(path))
+        .parse(&content)
+        .map_err(|e| match e {
+            VaulticError::ParseError { detail, .. } => VaulticError::ParseError {
+                file: path.to_path_buf(),
+                detail,
+            },
+            other => other,
+        })
+}
+
+/// Print the `--all-outputs` summary table.
+fn print_outputs(reports: &[OutputReport], passed: usize) {
+    output::header("🔍 vaultic check --all-outputs");
+    let mut table = Table::new(vec![
+        Column::new("Output"),
+        Column::new("Path").shrink(12),
+        Column::new("Present"),
+        Column::new("Missing"),
+        Column::new("Extra"),
+        Column::new("Empty"),
+        Column::new("Status"),
+    ]);
+    for r in reports {
+        let status = match (&r.error, r.ok) {
+            (Some(e), _) => Cell::new(format!("✗ {e}")).color(Color::Red),
+            (None, true) => Cell::new("✓ ok").color(Color::Green),
+            (None, false) => Cell::new("⚠ issues").color(Color::Yellow),
+        };
+        let counts = |n: usize| {
+            if r.error.is_some() {
+                Cell::new("-").dimmed()
+            } else {
+                Cell::new(n.to_string())
+            }
+        };
+        table.row(vec![
+            Cell::new(r.name.as_str()),
+            Cell::new(r.path.as_str()),
+            if r.error.is_some() {
+                Cell::new("-").dimmed()
+            } else {
+                Cell::new(format!("{}/{}", r.present, r.total))
+            },
+            counts(r.missing.len()),
+            counts(r.extra.len()),
+            counts(r.empty_values.len()),
+            status,
+        ]);
+    }
+    table.print();
+
+    println!();
+    if passed == reports.len() {
+        output::success(&format!(
+            "{passed}/{} outputs match their templates",
+            reports.len()
+        ));
+    } else {
+        output::warning(&format!(
+            "{} of {} output(s) need attention. Use --json to list the affected variables.",
+            reports.len() - passed,
+            reports.len()
+        ));
+    }
+}
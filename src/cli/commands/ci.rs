@@ -1,9 +1,6 @@
-use crate::adapters::parsers::dotenv_parser::DotenvParser;
 use crate::cli::commands::crypto_helpers;
-use crate::config::app_config::AppConfig;
 use crate::core::errors::{Result, VaulticError};
-use crate::core::models::audit_entry::AuditAction;
-use crate::core::services::env_resolver::EnvResolver;
+use crate::core::models::audit_entry::{AuditAction, AuditDetail};
 
 /// Execute `vaultic ci export`.
 ///
@@ -12,9 +9,7 @@ use crate::core::services::env_resolver::EnvResolver;
 pub fn execute_export(env: Option<&str>, cipher: &str, format: &str, mask: bool) -> Result<()> {
     let vaultic_dir = crate::cli::context::vaultic_dir();
     if !vaultic_dir.exists() {
-        return Err(VaulticError::InvalidConfig {
-            detail: "Vaultic not initialized. Run 'vaultic init' first.".into(),
-        });
+        return Err(crate::cli::first_run::not_initialized());
     }
 
     // Validate format
@@ -31,15 +26,10 @@ pub fn execute_export(env: Option<&str>, cipher: &str, format: &str, mask: bool)
         });
     }
 
-    let config = AppConfig::load(vaultic_dir)?;
-    let env_name = env.unwrap_or(&config.vaultic.default_env);
-    let parser = DotenvParser;
-    let resolver = EnvResolver;
+    let config = crate::cli::context::config()?;
+    let env_name = &crate::cli::context::select_env(env, Some(config))?;
 
-    // Build inheritance chain and decrypt layers
-    let chain = resolver.build_chain(env_name, &config)?;
-    let files = crypto_helpers::load_env_files(&chain, vaultic_dir, cipher, &parser, false)?;
-    let environment = resolver.resolve(env_name, &config, &files)?;
+    let environment = crypto_helpers::resolve_env(env_name, vaultic_dir, cipher, config, true)?;
 
     // Extract key-value pairs from resolved environment.
     let entries: Vec<(&str, &str)> = environment
@@ -71,7 +61,10 @@ pub fn execute_export(env: Option<&str>, cipher: &str, format: &str, mask: bool)
     super::audit_helpers::log_audit(
         AuditAction::CiExport,
         vec![env_name.to_string()],
-        Some(format!("{} variables exported as {format}", entries.len())),
+        Some(AuditDetail::CiExport {
+            variables: entries.len(),
+            format: format.to_string(),
+        }),
     );
 
     Ok(())
@@ -0,0 +1,30 @@
+use std::time::{Duration, Instant};
+
+use crate::adapters::clipboard::system_clipboard::SystemClipboard;
+use crate::cli::{output, signals};
+use crate::core::errors::Result;
+
+/// How long a secret copied with `--copy` stays on the clipboard.
+pub const CLEAR_AFTER: Duration = Duration::from_secs(45);
+
+/// Copy a public key. It is not secret, so it stays on the clipboard.
+pub fn copy_public_key(public_key: &str) -> Result<()> {
+    SystemClipboard::open()?.copy(public_key)?;
+    output::success("Public key copied to the clipboard");
+    Ok(())
+}
+
+/// Copy a secret and wait until it is cleared, after [`CLEAR_AFTER`] or
+/// on Ctrl-C. `what` names it in the messages.
+pub fn copy_secret(mut clipboard: SystemClipboard, what: &str, secret: &str) -> Result<()> {
+    output::success(&format!(
+        "{what} copied to the clipboard; clearing it in {}s (Ctrl-C clears it now)",
+        CLEAR_AFTER.as_secs()
+    ));
+    signals::set_clipboard_secret(true);
+    let result = clipboard.copy_until(secret, Instant::now() + CLEAR_AFTER);
+    signals::set_clipboard_secret(false);
+    result?;
+    output::success("Clipboard cleared");
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use crate::cli::ConfigAction;
+use crate::cli::output;
+use crate::config::app_config::AppConfig;
+use crate::core::errors::{Result, VaulticError};
+
+/// Execute `vaultic config` subcommands.
+pub fn execute(action: &ConfigAction) -> Result<()> {
+    match action {
+        ConfigAction::Schema {
+            output: output_path,
+        } => schema(output_path.as_deref()),
+    }
+}
+
+/// Implement `vaultic config schema`.
+///
+/// Serializes the JSON Schema of `AppConfig` to stdout or to a file.
+fn schema(output_path: Option<&str>) -> Result<()> {
+    let schema = AppConfig::json_schema();
+    let json = serde_json::to_string_pretty(&schema).map_err(|e| VaulticError::InvalidConfig {
+        detail: format!("Failed to serialize config schema: {e}"),
+    })?;
+
+    match output_path {
+        Some(path) => {
+            std::fs::write(path, format!("{json}\n"))?;
+            output::success(&format!("Config schema written to {path}"));
+        }
+        None => println!("{json}"),
+    }
+
+    Ok(())
+}
@@ -1,33 +1,41 @@
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::adapters::cipher::age_backend::AgeBackend;
 use crate::adapters::cipher::gpg_backend::GpgBackend;
+use crate::adapters::cipher::plugin_backend::{PLUGIN_PREFIX, PluginBackend};
+use crate::adapters::cipher::threshold_backend::ThresholdBackend;
 use crate::adapters::key_stores::file_key_store::FileKeyStore;
-use crate::adapters::parsers::dotenv_parser::DotenvParser;
+use crate::adapters::key_stores::recipients_signature;
+use crate::adapters::parsers;
+use crate::config::app_config::{AppConfig, GpgProfile, Storage};
+use crate::config::path_expand::expand_path;
 use crate::core::errors::{Result, VaulticError};
+use crate::core::models::environment::Environment;
 use crate::core::models::secret_file::SecretFile;
-use crate::core::services::encryption_service::EncryptionService;
-use crate::core::traits::parser::ConfigParser;
+use crate::core::services::encryption_service::{self, EncryptionService};
+use crate::core::services::env_resolver::EnvResolver;
+use crate::core::traits::cipher::CipherBackend;
 
 /// Load and decrypt env files for each layer in the chain.
 ///
 /// For each environment name, tries to decrypt the corresponding
-/// `.env.enc` file from `.vaultic/`. If the encrypted file doesn't
-/// exist, the layer is skipped (it may have no overrides).
+/// `.env.enc` file from `.vaultic/`, or from upstream for a mirror. If
+/// the encrypted file doesn't exist, the layer is skipped (it may have no
+/// overrides).
 ///
 /// When `warn_missing` is true, prints a warning for missing files.
 pub fn load_env_files(
     chain: &[String],
     vaultic_dir: &Path,
     cipher: &str,
-    parser: &DotenvParser,
+    config: &AppConfig,
     warn_missing: bool,
 ) -> Result<HashMap<String, SecretFile>> {
     let mut files = HashMap::new();
 
     for name in chain {
-        let enc_path = vaultic_dir.join(format!("{name}.env.enc"));
+        let enc_path = super::mirror_helpers::enc_path(vaultic_dir, config, name)?;
 
         if !enc_path.exists() {
             if warn_missing {
@@ -46,6 +54,7 @@ pub fn load_env_files(
                 detail: "Decrypted content is not valid UTF-8".into(),
             })?;
 
+        let parser = parsers::for_format(config.env_format(name));
         let secret_file = parser.parse(&plaintext)?;
         files.insert(name.clone(), secret_file);
     }
@@ -53,57 +62,288 @@ pub fn load_env_files(
     Ok(files)
 }
 
+/// Decrypt the inheritance chain of `env_name` and resolve it.
+///
+/// With `extends_parent = true`, the result is layered over the same
+/// environment of the nearest parent project (see [`parent_project`]),
+/// which may extend its own parent in turn. A parent that does not
+/// define `env_name` adds nothing. Parent layers are named
+/// `<dir>:<env>` and decrypted with the parent's `default_cipher`.
+pub fn resolve_env(
+    env_name: &str,
+    vaultic_dir: &Path,
+    cipher: &str,
+    config: &AppConfig,
+    warn_missing: bool,
+) -> Result<Environment> {
+    let resolver = EnvResolver;
+    let chain = resolver.build_chain(env_name, config)?;
+    let files = load_env_files(&chain, vaultic_dir, cipher, config, warn_missing)?;
+    let environment = resolver.resolve(env_name, config, &files)?;
+    if !config.vaultic.extends_parent {
+        return Ok(environment);
+    }
+
+    let parent_dir = parent_project(vaultic_dir).ok_or_else(|| VaulticError::InvalidConfig {
+        detail: format!(
+            "extends_parent is set in {}, but no parent directory has a .vaultic/config.toml",
+            vaultic_dir.join("config.toml").display()
+        ),
+    })?;
+    let parent_config = AppConfig::load(&parent_dir)?;
+    if !parent_config.environments.contains_key(env_name) {
+        return Ok(environment);
+    }
+    check_recipients_signature(&parent_dir, Some(&parent_config))?;
+    let mut parent = resolve_env(
+        env_name,
+        &parent_dir,
+        &parent_config.vaultic.default_cipher,
+        &parent_config,
+        warn_missing,
+    )?;
+    // Environment names cannot contain ':', so unlabeled layers are the
+    // parent's own
+    for layer in parent.layers.iter_mut().filter(|l| !l.contains(':')) {
+        *layer = format!("{}:{layer}", parent_dir.display());
+    }
+    Ok(resolver.extend(parent, environment))
+}
+
+/// The nearest `.vaultic/` holding a config.toml above the project that
+/// owns `vaultic_dir`, as a path relative to it, e.g. `../../.vaultic`.
+pub fn parent_project(vaultic_dir: &Path) -> Option<PathBuf> {
+    let root = std::fs::canonicalize(vaultic_dir).ok()?;
+    let mut up = vaultic_dir.parent().unwrap_or(Path::new("")).to_path_buf();
+    for ancestor in root.ancestors().skip(2) {
+        up.push("..");
+        if ancestor.join(".vaultic").join("config.toml").is_file() {
+            return Some(up.join(".vaultic"));
+        }
+    }
+    None
+}
+
+/// Verify `recipients.txt.sig` when `[recipients] admin_keys` is configured,
+/// and the `.sig` of every per-environment `recipients.<env>.txt`.
+///
+/// Does nothing when no admin keys are pinned. With `enforce = false` a
+/// failed check is only a warning.
+pub fn check_recipients_signature(vaultic_dir: &Path, config: Option<&AppConfig>) -> Result<()> {
+    let Some(config) = config else {
+        return Ok(());
+    };
+    let Some(section) = config.recipients.as_ref() else {
+        return Ok(());
+    };
+    if section.admin_keys.is_empty() {
+        return Ok(());
+    }
+
+    let mut lists = vec![vaultic_dir.join("recipients.txt")];
+    lists.extend(
+        env_recipient_lists(vaultic_dir, config)
+            .into_iter()
+            .map(|(_, p)| p),
+    );
+
+    for recipients_path in lists {
+        let file_name = recipients_path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let content = std::fs::read(&recipients_path).unwrap_or_default();
+        let result = match std::fs::read(vaultic_dir.join(format!("{file_name}.sig"))) {
+            Ok(signature) => {
+                recipients_signature::verify(&file_name, &content, &signature, &section.admin_keys)
+            }
+            Err(_) => Err(VaulticError::RecipientsSignatureInvalid {
+                reason: format!("{file_name}.sig not found"),
+            }),
+        };
+
+        match result {
+            Err(VaulticError::RecipientsSignatureInvalid { reason }) if !section.enforce => {
+                crate::cli::output::warning(&format!(
+                    "Recipients signature check failed: {reason}"
+                ));
+            }
+            other => other?,
+        }
+    }
+    Ok(())
+}
+
+/// Environments with their own recipient list, with the list's path,
+/// sorted by environment name.
+pub fn env_recipient_lists(vaultic_dir: &Path, config: &AppConfig) -> Vec<(String, PathBuf)> {
+    let mut lists: Vec<(String, PathBuf)> = config
+        .environments
+        .keys()
+        .map(|env| (env.clone(), FileKeyStore::env_list_path(vaultic_dir, env)))
+        .filter(|(_, path)| path.exists())
+        .collect();
+    lists.sort();
+    lists
+}
+
 /// Decrypt a single encrypted file in memory using the configured cipher.
 pub fn decrypt_in_memory(enc_path: &Path, vaultic_dir: &Path, cipher: &str) -> Result<Vec<u8>> {
     let key_store = FileKeyStore::new(vaultic_dir.join("recipients.txt"));
 
+    super::access_helpers::tracked(enc_path, || {
+        let service = EncryptionService {
+            cipher: decryptor(cipher)?,
+            key_store,
+        };
+        service.decrypt_to_bytes(enc_path)
+    })
+}
+
+/// Decrypt raw ciphertext in memory using the configured cipher.
+pub fn decrypt_bytes(ciphertext: &[u8], cipher: &str) -> Result<Vec<u8>> {
+    encryption_service::decrypt_content(&decryptor(cipher)?, ciphertext)
+}
+
+/// The backend that encrypts for `cipher`: `age`, `gpg`, or a
+/// `vaultic-cipher-<name>` plugin on PATH.
+pub fn encryptor(cipher: &str) -> Result<Box<dyn CipherBackend>> {
     match cipher {
-        "age" => {
-            let backend = if let Ok(key_data) = std::env::var("VAULTIC_AGE_KEY") {
-                let key_data = key_data.trim();
-                if key_data.is_empty() {
-                    return Err(VaulticError::EncryptionFailed {
-                        reason: "VAULTIC_AGE_KEY is set but empty. Provide the full age identity content.".into(),
-                    });
-                }
-                AgeBackend::from_key_data(key_data.to_string())
-            } else {
-                let identity_path = AgeBackend::default_identity_path()?;
-                if !identity_path.exists() {
-                    return Err(VaulticError::EncryptionFailed {
-                        reason: format!(
-                            "No private key found at {}\n\n  \
-                             Solutions:\n    \
-                             → Run 'vaultic keys setup' to generate a key\n    \
-                             → Set VAULTIC_AGE_KEY environment variable with your private key\n    \
-                             → Use --key <path> to specify the key file location",
-                            identity_path.display()
-                        ),
-                    });
-                }
-                AgeBackend::new(identity_path)
-            };
-            let service = EncryptionService {
-                cipher: backend,
-                key_store,
-            };
-            service.decrypt_to_bytes(enc_path)
-        }
+        "age" => Ok(Box::new(AgeBackend::new(
+            crate::cli::context::identity_path()?,
+        ))),
+        _ => decryptor(cipher),
+    }
+}
+
+/// The backend that encrypts environment `env_name`: [`encryptor`], or for
+/// an environment with a `threshold`, age split into Shamir shares.
+pub fn env_encryptor(cipher: &str, env_name: &str) -> Result<Box<dyn CipherBackend>> {
+    let config = crate::cli::context::config().ok();
+    let Some(threshold) = config.and_then(|c| c.threshold(env_name)) else {
+        return encryptor(cipher);
+    };
+    if cipher != "age" {
+        return Err(VaulticError::InvalidConfig {
+            detail: format!(
+                "environments.{env_name}.threshold needs the age cipher, not '{cipher}'"
+            ),
+        });
+    }
+    if config.is_some_and(|c| c.vaultic.storage == Storage::PerValue) {
+        return Err(VaulticError::InvalidConfig {
+            detail: format!(
+                "environments.{env_name}.threshold cannot be combined with \
+                 storage = \"per-value\""
+            ),
+        });
+    }
+    Ok(Box::new(
+        ThresholdBackend::new(AgeBackend::new(crate::cli::context::identity_path()?))
+            .with_threshold(env_name, threshold),
+    ))
+}
+
+/// The backend that decrypts `cipher` with the keys on this machine: every
+/// age identity, the GPG keyring, or a `vaultic-cipher-<name>` plugin.
+///
+/// Age files encrypted with a threshold decrypt only when this machine
+/// holds enough shares itself; `vaultic decrypt --share` adds released ones.
+pub fn decryptor(cipher: &str) -> Result<Box<dyn CipherBackend>> {
+    match cipher {
+        "age" => Ok(Box::new(ThresholdBackend::new(age_backend()?))),
         "gpg" => {
-            let backend = GpgBackend::new();
+            let backend = gpg_backend()?;
             if !backend.is_available() {
                 return Err(VaulticError::EncryptionFailed {
                     reason: "GPG is not installed or not found in PATH".into(),
                 });
             }
-            let service = EncryptionService {
-                cipher: backend,
-                key_store,
-            };
-            service.decrypt_to_bytes(enc_path)
+            Ok(Box::new(backend))
         }
-        other => Err(VaulticError::InvalidConfig {
-            detail: format!("Unknown cipher backend: '{other}'. Use 'age' or 'gpg'."),
-        }),
+        other => PluginBackend::find(other)
+            .map(|plugin| Box::new(plugin) as Box<dyn CipherBackend>)
+            .ok_or_else(|| unknown_cipher(other)),
     }
 }
+
+/// Whether `cipher` is built in or provided by a plugin on PATH.
+pub fn is_known_cipher(cipher: &str) -> bool {
+    matches!(cipher, "age" | "gpg") || PluginBackend::find(cipher).is_some()
+}
+
+/// The error for a cipher that is neither built in nor a plugin.
+pub fn unknown_cipher(cipher: &str) -> VaulticError {
+    VaulticError::InvalidConfig {
+        detail: format!(
+            "Unknown cipher backend: '{cipher}'. Use 'age' or 'gpg', or install a \
+             {PLUGIN_PREFIX}{cipher} plugin on PATH."
+        ),
+    }
+}
+
+/// Build the GPG backend from `[gpg]` in config.toml, overridden by
+/// `VAULTIC_GPG_HOMEDIR` and `VAULTIC_GPG_PROFILE`.
+pub fn gpg_backend() -> Result<GpgBackend> {
+    let section = crate::cli::context::config()
+        .ok()
+        .and_then(|c| c.gpg.clone())
+        .unwrap_or_default();
+
+    let profile = match std::env::var("VAULTIC_GPG_PROFILE") {
+        Ok(name) if !name.is_empty() => {
+            GpgProfile::from_name(&name).ok_or_else(|| VaulticError::InvalidConfig {
+                detail: format!("Unknown VAULTIC_GPG_PROFILE '{name}'. Use 'interactive' or 'ci'."),
+            })?
+        }
+        _ => section.profile,
+    };
+    let homedir = match std::env::var("VAULTIC_GPG_HOMEDIR") {
+        Ok(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+        _ => section
+            .homedir
+            .as_deref()
+            .map(|raw| expand_path(raw, Path::new(".")))
+            .transpose()?,
+    };
+    if let Some(dir) = homedir.as_ref().filter(|d| !d.is_dir()) {
+        return Err(VaulticError::InvalidConfig {
+            detail: format!("GPG homedir {} does not exist", dir.display()),
+        });
+    }
+
+    Ok(GpgBackend::new()
+        .with_homedir(homedir)
+        .with_profile(profile))
+}
+
+/// Build the age backend from `VAULTIC_AGE_KEY`, or from every identity
+/// file on this machine (see [`crate::cli::context::identity_paths`]).
+pub fn age_backend() -> Result<AgeBackend> {
+    let backend = if let Ok(key_data) = std::env::var("VAULTIC_AGE_KEY") {
+        let key_data = key_data.trim();
+        if key_data.is_empty() {
+            return Err(VaulticError::EncryptionFailed {
+                reason: "VAULTIC_AGE_KEY is set but empty. Provide the full age identity content."
+                    .into(),
+            });
+        }
+        AgeBackend::from_key_data(key_data.to_string())
+    } else {
+        let identity_paths = crate::cli::context::identity_paths()?;
+        if identity_paths.is_empty() {
+            return Err(VaulticError::EncryptionFailed {
+                reason: format!(
+                    "No private key found at {}\n\n  \
+                     Solutions:\n    \
+                     → Run 'vaultic keys setup' to generate a key\n    \
+                     → Set VAULTIC_AGE_KEY environment variable with your private key\n    \
+                     → Use --key <path> to specify the key file location",
+                    crate::cli::context::identity_path()?.display()
+                ),
+            });
+        }
+        AgeBackend::from_files(identity_paths)
+    };
+    Ok(backend)
+}
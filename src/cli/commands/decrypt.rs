@@ -1,38 +1,61 @@
 use std::path::{Path, PathBuf};
 
+use crate::adapters::audit::json_audit_logger::JsonAuditLogger;
 use crate::adapters::cipher::age_backend::AgeBackend;
-use crate::adapters::cipher::gpg_backend::GpgBackend;
+use crate::adapters::cipher::threshold_backend::ThresholdBackend;
 use crate::adapters::key_stores::file_key_store::FileKeyStore;
+use crate::cli::commands::crypto_helpers;
+use crate::cli::commands::lifecycle_helpers::{self, Hook};
 use crate::cli::output;
+use crate::config::app_config::AppConfig;
 use crate::core::errors::{Result, VaulticError};
+use crate::core::models::audit_entry::{AuditAction, AuditDetail};
+use crate::core::models::key_identity::KeyType;
 use crate::core::services::encryption_service::EncryptionService;
+use crate::core::traits::audit::AuditLogger;
 use crate::core::traits::cipher::CipherBackend;
+use crate::core::traits::key_store::KeyStore;
 
 /// Execute the `vaultic decrypt` command.
 ///
 /// Decrypts an encrypted file from `.vaultic/` and writes
 /// the plaintext to the working directory (or to `output_path` if provided).
 /// When `key_path` is provided, uses that file as the private key
-/// instead of the default location.
+/// instead of the default location. `shares` are files released with
+/// `vaultic keys share release` for a threshold environment. A
+/// `post_decrypt` hook runs once the plaintext is written.
 pub fn execute(
     file: Option<&str>,
     env: Option<&str>,
     cipher: &str,
     key_path: Option<&str>,
+    shares: &[String],
     output_path: Option<&str>,
     to_stdout: bool,
 ) -> Result<()> {
     let vaultic_dir = crate::cli::context::vaultic_dir();
     if !vaultic_dir.exists() {
-        return Err(VaulticError::InvalidConfig {
-            detail: "Vaultic not initialized. Run 'vaultic init' first.".into(),
-        });
+        return Err(crate::cli::first_run::not_initialized());
+    }
+
+    let config = crate::cli::context::config().ok();
+    let env_name = &crate::cli::context::select_env(env, config)?;
+
+    // Catch typos early: list the configured environments instead of
+    // reporting a missing .enc file
+    if file.is_none()
+        && let Some(config) = &config
+    {
+        config.require_env(env_name)?;
     }
+    crypto_helpers::check_recipients_signature(vaultic_dir, config)?;
 
-    let env_name = env.unwrap_or("dev");
     let source = match file {
         Some(f) => PathBuf::from(f),
-        None => vaultic_dir.join(format!("{env_name}.env.enc")),
+        None => match config {
+            Some(config) => super::mirror_helpers::enc_path(vaultic_dir, config, env_name)?,
+            None => vaultic_dir.join(format!("{env_name}.env.enc")),
+        },
     };
 
     if !source.exists() {
@@ -45,59 +68,145 @@ pub fn execute(
         Some(p) => PathBuf::from(p),
         None => PathBuf::from(".env"),
     };
-    let key_store = FileKeyStore::new(vaultic_dir.join("recipients.txt"));
-
-    match cipher {
-        "age" => {
-            let backend = match key_path {
-                Some(p) => {
-                    let path = PathBuf::from(p);
-                    if !path.exists() {
-                        return Err(VaulticError::FileNotFound { path });
-                    }
-                    AgeBackend::new(path)
+    let key_store = FileKeyStore::new(vaultic_dir.join("recipients.txt")).for_env(env_name);
+    warn_on_stale_recipients(&source, &key_store, vaultic_dir, config, cipher);
+
+    let backend: Box<dyn CipherBackend> = match cipher {
+        "age" => Box::new(ThresholdBackend::new(match key_path {
+            Some(p) => {
+                let path = PathBuf::from(p);
+                if !path.exists() {
+                    return Err(VaulticError::FileNotFound { path });
                 }
-                None => {
-                    if let Ok(key_data) = std::env::var("VAULTIC_AGE_KEY") {
-                        let key_data = key_data.trim();
-                        if key_data.is_empty() {
-                            return Err(VaulticError::EncryptionFailed {
+                AgeBackend::new(path)
+            }
+            None => {
+                if let Ok(key_data) = std::env::var("VAULTIC_AGE_KEY") {
+                    let key_data = key_data.trim();
+                    if key_data.is_empty() {
+                        return Err(VaulticError::EncryptionFailed {
                                 reason: "VAULTIC_AGE_KEY is set but empty. Provide the full age identity content.".into(),
                             });
-                        }
-                        AgeBackend::from_key_data(key_data.to_string())
-                    } else {
-                        let path = AgeBackend::default_identity_path()?;
-                        if !path.exists() {
-                            return Err(VaulticError::EncryptionFailed {
-                                reason: format!(
-                                    "No private key found at {}\n\n  Solutions:\n    \
+                    }
+                    AgeBackend::from_key_data(key_data.to_string())
+                } else {
+                    let paths = crate::cli::context::identity_paths()?;
+                    if paths.is_empty() {
+                        let path = crate::cli::context::identity_path()?;
+                        return Err(VaulticError::EncryptionFailed {
+                            reason: format!(
+                                "No private key found at {}\n\n  Solutions:\n    \
                                      → New here? Run 'vaultic keys setup' to generate a key\n    \
                                      → Set VAULTIC_AGE_KEY environment variable with your private key\n    \
                                      → Have a key? Use --key <path> to specify the location\n    \
                                      → Lost your key? Ask an admin to re-add you as a recipient",
-                                    path.display()
-                                ),
-                            });
-                        }
-                        AgeBackend::new(path)
+                                path.display()
+                            ),
+                        });
                     }
+                    AgeBackend::from_files(paths)
                 }
-            };
-            decrypt_with(backend, key_store, &source, &dest, env_name, to_stdout)
-        }
-        "gpg" => {
-            let backend = GpgBackend::new();
-            if !backend.is_available() {
-                return Err(VaulticError::EncryptionFailed {
-                    reason: "GPG is not installed or not found in PATH".into(),
-                });
             }
-            decrypt_with(backend, key_store, &source, &dest, env_name, to_stdout)
+        }).with_released(read_shares(shares)?)),
+        _ if !shares.is_empty() => {
+            return Err(VaulticError::InvalidConfig {
+                detail: "--share only applies to the age cipher".into(),
+            });
+        }
+        other => super::crypto_helpers::decryptor(other)?,
+    };
+    super::access_helpers::tracked(&source, || {
+        decrypt_with(backend, key_store, &source, &dest, env_name, to_stdout)
+    })?;
+    if !to_stdout {
+        lifecycle_helpers::run(Hook::PostDecrypt, env_name, &dest)?;
+    }
+    Ok(())
+}
+
+/// Read the share files passed with `--share`.
+fn read_shares(paths: &[String]) -> Result<Vec<Vec<u8>>> {
+    paths
+        .iter()
+        .map(|p| {
+            std::fs::read(p).map_err(|_| VaulticError::FileNotFound {
+                path: PathBuf::from(p),
+            })
+        })
+        .collect()
+}
+
+/// Warn when `source` was not encrypted for the current recipients list.
+///
+/// For age files, the number of recipient stanzas in the header must match
+/// the age keys in `key_store` (`recipients.txt` or the environment's own list). For any cipher, the audit log must not
+/// show a recipient change after the file was last encrypted. Either case
+/// means the file is stale, or still readable by a removed key.
+fn warn_on_stale_recipients(
+    source: &Path,
+    key_store: &FileKeyStore,
+    vaultic_dir: &Path,
+    config: Option<&AppConfig>,
+    cipher: &str,
+) {
+    let Ok(keys) = key_store.list() else {
+        return;
+    };
+    let file_name = source
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    if cipher == "age"
+        && let Ok(ciphertext) = std::fs::read(source)
+        && let Ok(encrypted_for) = AgeBackend::recipient_count(&ciphertext)
+    {
+        let listed = keys
+            .iter()
+            .filter(|k| matches!(k.key_type(), KeyType::Age | KeyType::Ssh))
+            .count();
+        if encrypted_for != listed {
+            output::warning(&format!(
+                "{file_name} is encrypted for {encrypted_for} recipient(s), but {} \
+                 lists {listed} age or SSH key(s). Re-encrypt with 'vaultic encrypt --all'.",
+                key_store
+                    .path()
+                    .file_name()
+                    .map(|n| n.to_string_lossy())
+                    .unwrap_or_default()
+            ));
+            return;
         }
-        other => Err(VaulticError::InvalidConfig {
-            detail: format!("Unknown cipher backend: '{other}'. Use 'age' or 'gpg'."),
-        }),
+    }
+
+    let logger = JsonAuditLogger::from_config(vaultic_dir, config.and_then(|c| c.audit.as_ref()));
+    let Ok(entries) = logger.query(None, None) else {
+        return;
+    };
+    let last_encrypt = entries
+        .iter()
+        .filter(|e| e.action == AuditAction::Encrypt && e.files.contains(&file_name))
+        .map(|e| e.timestamp)
+        .max();
+    let last_key_change = entries
+        .iter()
+        .filter(|e| {
+            matches!(
+                e.action,
+                AuditAction::KeyAdd | AuditAction::KeyRemove | AuditAction::KeyRotate
+            )
+        })
+        .map(|e| e.timestamp)
+        .max();
+    if let (Some(encrypted), Some(changed)) = (last_encrypt, last_key_change)
+        && changed > encrypted
+    {
+        output::warning(&format!(
+            "Recipients changed on {} after {file_name} was last encrypted ({}). \
+             Re-encrypt with 'vaultic encrypt --all'.",
+            changed.format("%Y-%m-%d"),
+            encrypted.format("%Y-%m-%d")
+        ));
     }
 }
 
@@ -152,10 +261,10 @@ fn decrypt_with<C: CipherBackend>(
     super::audit_helpers::log_audit_with_hash(
         crate::core::models::audit_entry::AuditAction::Decrypt,
         vec![format!("{env_name}.env.enc")],
-        Some(format!(
-            "{var_count} variables decrypted to {}",
-            dest.display()
-        )),
+        Some(AuditDetail::Decrypt {
+            variables: var_count,
+            dest: dest.display().to_string(),
+        }),
         state_hash,
     );
 
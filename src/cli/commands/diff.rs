@@ -1,86 +1,252 @@
+use std::collections::HashMap;
 use std::path::Path;
 
-use colored::Colorize;
+use colored::{Color, Colorize};
+use serde::Serialize;
 
-use crate::adapters::parsers::dotenv_parser::DotenvParser;
+use crate::adapters::git::git_history;
+use crate::adapters::parsers;
 use crate::cli::commands::crypto_helpers;
 use crate::cli::output;
-use crate::config::app_config::AppConfig;
+use crate::cli::table::{Cell, Column, Table, truncate};
+use crate::config::app_config::SecretFormat;
 use crate::core::errors::{Result, VaulticError};
-use crate::core::models::diff_result::{DiffKind, DiffResult};
+use crate::core::models::audit_entry::AuditDetail;
+use crate::core::models::diff_result::{Charset, DiffKind, DiffResult};
+use crate::core::models::secret_file::SecretFile;
 use crate::core::services::diff_service::DiffService;
-use crate::core::services::env_resolver::EnvResolver;
-use crate::core::traits::parser::ConfigParser;
+
+/// How the differences are rendered.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DiffView {
+    /// Two columns (left file, right file) instead of the summary table.
+    pub side_by_side: bool,
+    /// Show raw values instead of their shape.
+    pub show_values: bool,
+    /// Only list the changed key names, grouped by kind of change.
+    pub only_keys: bool,
+}
 
 /// Execute the `vaultic diff` command.
 ///
-/// Two modes:
-/// - File mode:  `vaultic diff file1 file2`
-/// - Env mode:   `vaultic diff --env dev --env prod`
+/// Three modes:
+/// - File mode:      `vaultic diff file1 file2`
+/// - Env mode:       `vaultic diff --env dev --env prod`
+/// - File vs env:    `vaultic diff .env --env prod`
+/// - Revision mode:  `vaultic diff --env prod --rev HEAD~3`
+///
+/// With `--json`, the differences are printed as a JSON document.
 pub fn execute(
     file1: Option<&str>,
     file2: Option<&str>,
     envs: &[String],
+    rev: Option<&str>,
     cipher: &str,
+    view: DiffView,
 ) -> Result<()> {
-    if envs.len() >= 2 {
-        execute_env_diff(&envs[0], &envs[1], cipher)
-    } else {
-        execute_file_diff(file1, file2)
+    if view.show_values
+        && let Ok(config) = crate::cli::context::config()
+        && config.ui.as_ref().is_some_and(|ui| ui.redact_values)
+    {
+        return Err(VaulticError::InvalidConfig {
+            detail: "--show-values is disabled for this project (redact_values = true in [ui] \
+                     of .vaultic/config.toml)"
+                .to_string(),
+        });
+    }
+    if let Some(rev) = rev {
+        return match (envs, file1, file2) {
+            ([env], None, None) => execute_rev_diff(env, rev, cipher, view),
+            _ => Err(VaulticError::InvalidConfig {
+                detail: "--rev compares one environment with its history: \
+                         vaultic diff --env <name> --rev <revision>"
+                    .to_string(),
+            }),
+        };
+    }
+    match envs {
+        [left, right, ..] => execute_env_diff(left, right, cipher, view),
+        [env] if file2.is_none() => {
+            execute_file_env_diff(file1.unwrap_or(".env"), env, cipher, view)
+        }
+        [_] => Err(VaulticError::InvalidConfig {
+            detail: "Compare one file with an environment: vaultic diff <file> --env <name>"
+                .to_string(),
+        }),
+        [] => execute_file_diff(file1, file2, view),
     }
 }
 
 /// Compare two resolved environments.
-fn execute_env_diff(left_env: &str, right_env: &str, cipher: &str) -> Result<()> {
+fn execute_env_diff(left_env: &str, right_env: &str, cipher: &str, view: DiffView) -> Result<()> {
     let vaultic_dir = crate::cli::context::vaultic_dir();
     if !vaultic_dir.exists() {
-        return Err(VaulticError::InvalidConfig {
-            detail: "Vaultic not initialized. Run 'vaultic init' first.".into(),
-        });
+        return Err(crate::cli::first_run::not_initialized());
     }
 
-    let config = AppConfig::load(vaultic_dir)?;
-    let resolver = EnvResolver;
-    let parser = DotenvParser;
+    let config = crate::cli::context::config()?;
 
-    output::header(&format!(
-        "Comparing environments: {left_env} vs {right_env}"
-    ));
-
-    // Resolve left environment
-    let left_chain = resolver.build_chain(left_env, &config)?;
-    let left_files =
-        crypto_helpers::load_env_files(&left_chain, vaultic_dir, cipher, &parser, false)?;
-    let left = resolver.resolve(left_env, &config, &left_files)?;
+    if !view.only_keys {
+        output::header(&format!(
+            "Comparing environments: {left_env} vs {right_env}"
+        ));
+    }
 
-    // Resolve right environment
-    let right_chain = resolver.build_chain(right_env, &config)?;
-    let right_files =
-        crypto_helpers::load_env_files(&right_chain, vaultic_dir, cipher, &parser, false)?;
-    let right = resolver.resolve(right_env, &config, &right_files)?;
+    let left = crypto_helpers::resolve_env(left_env, vaultic_dir, cipher, config, false)?;
+    let right = crypto_helpers::resolve_env(right_env, vaultic_dir, cipher, config, false)?;
 
     let svc = DiffService;
     let result = svc.diff(&left.resolved, &right.resolved, left_env, right_env)?;
 
-    if result.is_empty() {
-        output::success("No differences found between environments");
-    } else {
-        print_diff_table(&result);
-        print_diff_summary(&result);
-    }
+    report(
+        &left.resolved,
+        &right.resolved,
+        &result,
+        view,
+        "No differences found between environments",
+    )?;
 
     // Audit
     super::audit_helpers::log_audit(
         crate::core::models::audit_entry::AuditAction::Diff,
         vec![left_env.to_string(), right_env.to_string()],
-        Some(format!("{} difference(s)", result.entries.len())),
+        Some(AuditDetail::Diff {
+            differences: result.entries.len(),
+        }),
+    );
+
+    Ok(())
+}
+
+/// Compare a local file with what is encrypted for `env_name`: what
+/// would change if the file were encrypted into that environment.
+///
+/// The environment's own layer is decrypted in memory, without
+/// inheritance, since that is the part `encrypt` replaces.
+fn execute_file_env_diff(path: &str, env_name: &str, cipher: &str, view: DiffView) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+    let config = crate::cli::context::config()?;
+    config.require_env(env_name)?;
+
+    let file_path = Path::new(path);
+    if !file_path.exists() {
+        return Err(VaulticError::FileNotFound {
+            path: file_path.to_path_buf(),
+        });
+    }
+    let content = std::fs::read_to_string(file_path)?;
+    let local = parsers::for_format(SecretFormat::from_path(file_path)).parse(&content)?;
+
+    let mut layers =
+        crypto_helpers::load_env_files(&[env_name.to_string()], vaultic_dir, cipher, config, true)?;
+    let encrypted = layers.remove(env_name).unwrap_or(SecretFile {
+        lines: Vec::new(),
+        source_path: None,
+    });
+
+    if !view.only_keys {
+        output::header(&format!("Comparing {env_name} with {path}"));
+    }
+
+    let result = DiffService.diff(&encrypted, &local, env_name, path)?;
+    report(
+        &encrypted,
+        &local,
+        &result,
+        view,
+        &format!("{path} matches what is encrypted for {env_name}"),
+    )?;
+
+    super::audit_helpers::log_audit(
+        crate::core::models::audit_entry::AuditAction::Diff,
+        vec![env_name.to_string(), path.to_string()],
+        Some(AuditDetail::Diff {
+            differences: result.entries.len(),
+        }),
+    );
+
+    Ok(())
+}
+
+/// Compare an environment as it was at the git revision `rev` with its
+/// current encrypted file. Both versions are decrypted in memory.
+///
+/// Only the environment's own file is compared, without inheritance,
+/// since that is the blob git has a history for.
+fn execute_rev_diff(env_name: &str, rev: &str, cipher: &str, view: DiffView) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+    let config = crate::cli::context::config()?;
+    config.require_env(env_name)?;
+
+    let enc_path = vaultic_dir.join(format!("{env_name}.env.enc"));
+    let parser = parsers::for_format(config.env_format(env_name));
+    let parse = |bytes: Vec<u8>| -> Result<SecretFile> {
+        let text = String::from_utf8(bytes).map_err(|_| VaulticError::ParseError {
+            file: enc_path.clone(),
+            detail: "Decrypted content is not valid UTF-8".into(),
+        })?;
+        parser.parse(&text)
+    };
+    let empty = || SecretFile {
+        lines: Vec::new(),
+        source_path: None,
+    };
+
+    let old = match git_history::show_file(rev, &enc_path)? {
+        Some(ciphertext) => parse(crypto_helpers::decrypt_bytes(&ciphertext, cipher)?)?,
+        None => {
+            output::warning(&format!(
+                "{} did not exist at {rev} — comparing against an empty file",
+                enc_path.display()
+            ));
+            empty()
+        }
+    };
+    let current = if enc_path.exists() {
+        parse(crypto_helpers::decrypt_in_memory(
+            &enc_path,
+            vaultic_dir,
+            cipher,
+        )?)?
+    } else {
+        empty()
+    };
+
+    let left_name = format!("{env_name}@{rev}");
+    if !view.only_keys {
+        output::header(&format!(
+            "Comparing {env_name} at {rev} with the working tree"
+        ));
+    }
+
+    let result = DiffService.diff(&old, &current, &left_name, env_name)?;
+    report(
+        &old,
+        &current,
+        &result,
+        view,
+        &format!("{env_name} has not changed since {rev}"),
+    )?;
+
+    super::audit_helpers::log_audit(
+        crate::core::models::audit_entry::AuditAction::Diff,
+        vec![left_name, env_name.to_string()],
+        Some(AuditDetail::Diff {
+            differences: result.entries.len(),
+        }),
     );
 
     Ok(())
 }
 
 /// Compare two plain files.
-fn execute_file_diff(file1: Option<&str>, file2: Option<&str>) -> Result<()> {
+fn execute_file_diff(file1: Option<&str>, file2: Option<&str>, view: DiffView) -> Result<()> {
     let left_path = file1.unwrap_or(".env");
     let right_path = file2.ok_or_else(|| VaulticError::InvalidConfig {
         detail: "diff requires two files. Usage: vaultic diff <file1> <file2>".to_string(),
@@ -100,89 +266,281 @@ fn execute_file_diff(file1: Option<&str>, file2: Option<&str>) -> Result<()> {
         });
     }
 
-    let parser = DotenvParser;
+    // Each side is parsed by its extension, so `.env` can be compared
+    // with `secrets.yaml`
     let left_content = std::fs::read_to_string(left)?;
     let right_content = std::fs::read_to_string(right)?;
 
-    let left_file = parser.parse(&left_content)?;
-    let right_file = parser.parse(&right_content)?;
+    let left_file = parsers::for_format(SecretFormat::from_path(left)).parse(&left_content)?;
+    let right_file = parsers::for_format(SecretFormat::from_path(right)).parse(&right_content)?;
 
     let svc = DiffService;
     let result = svc.diff(&left_file, &right_file, left_path, right_path)?;
 
-    output::header("vaultic diff");
-
-    if result.is_empty() {
-        output::success("No differences found");
-    } else {
-        print_diff_table(&result);
-        print_diff_summary(&result);
+    if !view.only_keys {
+        output::header("vaultic diff");
     }
 
+    report(
+        &left_file,
+        &right_file,
+        &result,
+        view,
+        "No differences found",
+    )?;
+
     // Audit
     super::audit_helpers::log_audit(
         crate::core::models::audit_entry::AuditAction::Diff,
         vec![left_path.to_string(), right_path.to_string()],
-        Some(format!("{} difference(s)", result.entries.len())),
+        Some(AuditDetail::Diff {
+            differences: result.entries.len(),
+        }),
     );
 
     Ok(())
 }
 
-/// Print the diff results as a formatted table.
-fn print_diff_table(result: &DiffResult) {
-    let key_width = result
+/// Print `result` as JSON, as `no_changes` when empty, or as `view`
+/// followed by a summary line.
+pub(super) fn report(
+    left: &SecretFile,
+    right: &SecretFile,
+    result: &DiffResult,
+    view: DiffView,
+    no_changes: &str,
+) -> Result<()> {
+    if output::is_json_mode() {
+        print_diff_json(left, right, result)?;
+    } else if view.only_keys {
+        print_key_summary(result);
+    } else if result.is_empty() {
+        output::success(no_changes);
+    } else {
+        print_diff(left, right, result, view);
+        print_diff_summary(result);
+    }
+    Ok(())
+}
+
+/// Print the differences in the layout selected by `view`.
+fn print_diff(left: &SecretFile, right: &SecretFile, result: &DiffResult, view: DiffView) {
+    if view.side_by_side {
+        print_side_by_side(left, right, result, view.show_values);
+    } else {
+        print_diff_table(left, right, result, view.show_values);
+    }
+}
+
+/// Print both files in two columns, one row per key.
+///
+/// Keys keep the left file's order, followed by keys only on the right.
+/// Unchanged rows are dimmed; values are masked unless `show_values`.
+fn print_side_by_side(
+    left: &SecretFile,
+    right: &SecretFile,
+    result: &DiffResult,
+    show_values: bool,
+) {
+    // "  ~ " before the left column and " │ " between the two.
+    let column = (output::terminal_width().saturating_sub(7) / 2).max(20);
+    let kinds: HashMap<&str, &DiffKind> = result
         .entries
         .iter()
-        .map(|e| e.key.len())
-        .max()
-        .unwrap_or(8)
-        .max(8);
-
-    let header = format!(
-        "  {:<width$}   {:<12}   {}",
-        "Variable",
-        &result.left_name,
-        &result.right_name,
-        width = key_width
+        .map(|e| (e.key.as_str(), &e.kind))
+        .collect();
+
+    let mut keys = left.keys();
+    for key in right.keys() {
+        if left.get(key).is_none() {
+            keys.push(key);
+        }
+    }
+
+    println!(
+        "    {} {} {}",
+        fit(&result.left_name, column).bold(),
+        "│".dimmed(),
+        truncate(&result.right_name, column).bold()
     );
-    println!("{}", header.bold());
-    println!("  {}", "─".repeat(header.len()));
+    println!("  {}", "─".repeat(column * 2 + 5).dimmed());
+
+    for key in keys {
+        let l = match left.get(key) {
+            Some(v) => fit(&side_cell(key, v, show_values), column),
+            None => " ".repeat(column),
+        };
+        let r = right
+            .get(key)
+            .map(|v| truncate(&side_cell(key, v, show_values), column))
+            .unwrap_or_default();
+        let sep = "│".dimmed();
+        match kinds.get(key) {
+            Some(DiffKind::Added) => println!("  {} {l} {sep} {}", "+".green(), r.green()),
+            Some(DiffKind::Removed) => println!("  {} {} {sep} {r}", "-".red(), l.red()),
+            Some(DiffKind::Modified { .. }) => {
+                println!("  {} {} {sep} {}", "~".yellow(), l.yellow(), r.yellow())
+            }
+            None => println!("    {} {sep} {}", l.dimmed(), r.dimmed()),
+        }
+    }
+}
+
+/// One side-by-side cell: `KEY=value`, or `KEY=[32 hex]` when masked.
+fn side_cell(key: &str, value: &str, show_values: bool) -> String {
+    if show_values {
+        format!("{key}={value}")
+    } else {
+        format!("{key}=[{}]", value_shape(value))
+    }
+}
+
+/// Truncate or pad `s` to exactly `width` characters.
+fn fit(s: &str, width: usize) -> String {
+    let cut = truncate(s, width);
+    let pad = width.saturating_sub(cut.chars().count());
+    format!("{cut}{}", " ".repeat(pad))
+}
+
+/// Print the diff results as a formatted table.
+///
+/// Values are shown by shape (length and charset) plus a summary of what
+/// changed, and by their raw content only with `show_values`.
+fn print_diff_table(left: &SecretFile, right: &SecretFile, result: &DiffResult, show_values: bool) {
+    let cell = |value: Option<&str>| match value {
+        Some(v) if show_values => v.to_string(),
+        Some(v) => value_shape(v),
+        None => String::new(),
+    };
+    let mut table = Table::new(vec![
+        Column::new("Variable").shrink(8),
+        Column::new(&result.left_name).shrink(8),
+        Column::new(&result.right_name).shrink(8),
+        Column::new("Change").shrink(6),
+    ]);
 
     for entry in &result.entries {
+        let key = Cell::new(entry.key.as_str());
         match &entry.kind {
-            DiffKind::Added => {
-                println!(
-                    "  {:<width$}   {:<12}   {}",
-                    entry.key.green(),
-                    "—".dimmed(),
-                    "(added)".green(),
-                    width = key_width
-                );
-            }
-            DiffKind::Removed => {
-                println!(
-                    "  {:<width$}   {:<12}   {}",
-                    entry.key.red(),
-                    "(removed)".red(),
-                    "—".dimmed(),
-                    width = key_width
-                );
-            }
+            DiffKind::Added => table.row(vec![
+                key.color(Color::Green),
+                Cell::new("—").dimmed(),
+                Cell::new(if show_values {
+                    cell(right.get(&entry.key))
+                } else {
+                    "(added)".to_string()
+                })
+                .color(Color::Green),
+            ]),
+            DiffKind::Removed => table.row(vec![
+                key.color(Color::Red),
+                Cell::new(if show_values {
+                    cell(left.get(&entry.key))
+                } else {
+                    "(removed)".to_string()
+                })
+                .color(Color::Red),
+                Cell::new("—").dimmed(),
+            ]),
             DiffKind::Modified {
                 old_value,
                 new_value,
-            } => {
-                let old_display = truncate(old_value, 12);
-                let new_display = truncate(new_value, 12);
-                println!(
-                    "  {:<width$}   {:<12}   {}",
-                    entry.key.yellow(),
-                    old_display,
-                    new_display.yellow(),
-                    width = key_width
-                );
+                ..
+            } => table.row(vec![
+                key.color(Color::Yellow),
+                Cell::new(cell(Some(old_value))),
+                Cell::new(cell(Some(new_value))).color(Color::Yellow),
+                Cell::new(entry.kind.change_summary().unwrap_or_default()).dimmed(),
+            ]),
+        }
+    }
+    table.print();
+}
+
+/// `vaultic diff --json` output.
+#[derive(Serialize)]
+struct DiffJson<'a> {
+    left: &'a str,
+    right: &'a str,
+    entries: Vec<DiffEntryJson<'a>>,
+}
+
+/// One changed variable. Values are described by shape only, so the
+/// output is safe to keep in CI logs.
+#[derive(Serialize)]
+struct DiffEntryJson<'a> {
+    key: &'a str,
+    change: &'static str,
+    left_shape: Option<String>,
+    right_shape: Option<String>,
+    summary: Option<String>,
+}
+
+/// Print the differences as a JSON document on stdout.
+fn print_diff_json(left: &SecretFile, right: &SecretFile, result: &DiffResult) -> Result<()> {
+    let entries = result
+        .entries
+        .iter()
+        .map(|entry| {
+            let l = left.get(&entry.key);
+            let r = right.get(&entry.key);
+            DiffEntryJson {
+                key: &entry.key,
+                change: match entry.kind {
+                    DiffKind::Added => "added",
+                    DiffKind::Removed => "removed",
+                    DiffKind::Modified { .. } => "modified",
+                },
+                left_shape: l.map(value_shape),
+                right_shape: r.map(value_shape),
+                summary: entry.kind.change_summary(),
             }
+        })
+        .collect();
+    output::json(&DiffJson {
+        left: &result.left_name,
+        right: &result.right_name,
+        entries,
+    })
+}
+
+/// Print only the changed keys, grouped by kind of change, in plain text
+/// short enough to paste into a pull request or chat:
+///
+/// ```text
+/// dev → prod: 1 added, 2 modified
+/// Added (1): NEW_FLAG
+/// Modified (2): DB_HOST, DB_PASS
+/// ```
+fn print_key_summary(result: &DiffResult) {
+    let keys = |kind: fn(&DiffKind) -> bool| -> Vec<&str> {
+        result
+            .entries
+            .iter()
+            .filter(|e| kind(&e.kind))
+            .map(|e| e.key.as_str())
+            .collect()
+    };
+    let groups = [
+        ("Added", keys(|k| matches!(k, DiffKind::Added))),
+        ("Removed", keys(|k| matches!(k, DiffKind::Removed))),
+        ("Modified", keys(|k| matches!(k, DiffKind::Modified { .. }))),
+    ];
+
+    let counts: Vec<String> = groups
+        .iter()
+        .filter(|(_, keys)| !keys.is_empty())
+        .map(|(label, keys)| format!("{} {}", keys.len(), label.to_lowercase()))
+        .collect();
+    let counts = if counts.is_empty() {
+        "no differences".to_string()
+    } else {
+        counts.join(", ")
+    };
+    println!("{} → {}: {counts}", result.left_name, result.right_name);
+    for (label, keys) in &groups {
+        if !keys.is_empty() {
+            println!("{label} ({}): {}", keys.len(), keys.join(", "));
         }
     }
 }
@@ -220,17 +578,9 @@ fn print_diff_summary(result: &DiffResult) {
     output::success(&parts.join(", "));
 }
 
-/// Truncate a string to `max_len` characters, appending "..." if needed.
-/// Uses char boundaries to avoid panic on multibyte UTF-8 sequences.
-fn truncate(s: &str, max_len: usize) -> String {
-    let char_count = s.chars().count();
-    if char_count <= max_len {
-        s.to_string()
-    } else {
-        let limit = max_len.saturating_sub(3);
-        let truncated: String = s.chars().take(limit).collect();
-        format!("{truncated}...")
-    }
+/// Describe a value without revealing it, e.g. `32 hex`.
+fn value_shape(value: &str) -> String {
+    format!("{} {}", value.chars().count(), Charset::of(value).label())
 }
 
 #[cfg(test)]
@@ -238,36 +588,15 @@ mod tests {
     use super::*;
 
     #[test]
-    fn truncate_short_string_unchanged() {
-        assert_eq!(truncate("hello", 10), "hello");
-    }
-
-    #[test]
-    fn truncate_exact_length_unchanged() {
-        assert_eq!(truncate("hello", 5), "hello");
-    }
-
-    #[test]
-    fn truncate_long_string() {
-        assert_eq!(truncate("hello world!", 8), "hello...");
-    }
-
-    #[test]
-    fn truncate_unicode_safe() {
-        // "contraseña" has 10 chars but 11 bytes (ñ = 2 bytes)
-        let result = truncate("contraseña", 8);
-        assert_eq!(result, "contr...");
-        // Should not panic
-        let _ = truncate("日本語テスト", 5);
-    }
-
-    #[test]
-    fn truncate_empty_string() {
-        assert_eq!(truncate("", 5), "");
+    fn fit_pads_and_truncates_to_width() {
+        assert_eq!(fit("abc", 5), "abc  ");
+        assert_eq!(fit("abcdefgh", 6), "abc...");
+        assert_eq!(fit("ñandú", 5), "ñandú");
     }
 
     #[test]
-    fn truncate_max_len_zero() {
-        assert_eq!(truncate("hello", 0), "...");
+    fn side_cell_masks_unless_asked() {
+        assert_eq!(side_cell("PORT", "5432", false), "PORT=[4 numeric]");
+        assert_eq!(side_cell("PORT", "5432", true), "PORT=5432");
     }
 }
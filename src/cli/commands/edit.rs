@@ -0,0 +1,160 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::adapters::key_stores::file_key_store::FileKeyStore;
+use crate::adapters::parsers;
+use crate::cli::commands::crypto_helpers;
+use crate::cli::output;
+use crate::cli::signals::SensitiveFile;
+use crate::config::app_config::SecretFormat;
+use crate::core::errors::{Result, VaulticError};
+
+/// Execute the `vaultic edit` command.
+///
+/// Decrypts the environment into a private temp file (on tmpfs when
+/// available), opens it in the user's editor, validates the result and
+/// re-encrypts it. The temp file is removed on every exit path, including
+/// Ctrl-C.
+pub fn execute(env: Option<&str>, cipher: &str, note: Option<&str>, force: bool) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+
+    let config = crate::cli::context::config()?;
+    let env_name = &crate::cli::context::select_env(env, Some(config))?;
+    config.require_env(env_name)?;
+    config.require_writable(env_name)?;
+    crypto_helpers::check_recipients_signature(vaultic_dir, Some(config))?;
+
+    let enc_path = vaultic_dir.join(format!("{env_name}.env.enc"));
+    let original = if enc_path.exists() {
+        crypto_helpers::decrypt_in_memory(&enc_path, vaultic_dir, cipher)?
+    } else {
+        output::warning(&format!(
+            "{} does not exist yet — starting from an empty file",
+            enc_path.display()
+        ));
+        Vec::new()
+    };
+
+    let format = config.env_format(env_name);
+    let temp = SensitiveFile::new(temp_path(env_name, format)?);
+    write_private(temp.path(), &original)?;
+
+    let parser = parsers::for_format(format);
+    let edited = loop {
+        open_editor(temp.path())?;
+        let content = std::fs::read(temp.path())?;
+        let text = String::from_utf8(content.clone()).map_err(|_| VaulticError::ParseError {
+            file: enc_path.clone(),
+            detail: "Edited content is not valid UTF-8".into(),
+        });
+        match text.and_then(|t| parser.parse(&t)) {
+            Ok(_) => break content,
+            // `--yes` / `--no-input` would re-open the editor forever
+            Err(e) if !output::is_interactive() => return Err(e),
+            Err(e) => {
+                output::error(&format!("{e}"));
+                if !output::confirm("Re-open the editor to fix it?", true)? {
+                    return Err(VaulticError::InvalidConfig {
+                        detail: format!(
+                            "Edit aborted: {env_name} was not changed because the result \
+                             is not a valid env file."
+                        ),
+                    });
+                }
+            }
+        }
+    };
+    drop(temp);
+
+    if edited == original {
+        output::success(&format!("No changes to {env_name}"));
+        return Ok(());
+    }
+
+    let key_store = FileKeyStore::new(vaultic_dir.join("recipients.txt")).for_env(env_name);
+    super::encrypt::encrypt_bytes_to(
+        &edited, &enc_path, env_name, cipher, &key_store, note, force,
+    )?;
+    output::success(&format!("Saved to {}", enc_path.display()));
+    println!("\n  Commit {} to the repo.", enc_path.display());
+
+    Ok(())
+}
+
+/// Temp file location for the plaintext, preferring memory-backed
+/// `/dev/shm` so the secrets never reach a disk. The extension matches
+/// the environment's format so editors pick the right highlighting.
+fn temp_path(env_name: &str, format: SecretFormat) -> Result<PathBuf> {
+    let shm = Path::new("/dev/shm");
+    let dir = if shm.is_dir() {
+        shm.to_path_buf()
+    } else {
+        std::env::temp_dir()
+    };
+    let path = tempfile::Builder::new()
+        .prefix(&format!("vaultic-{env_name}-"))
+        .suffix(match format {
+            SecretFormat::Dotenv => ".env",
+            SecretFormat::Yaml => ".yaml",
+            SecretFormat::Json => ".json",
+        })
+        .tempfile_in(dir)?
+        .into_temp_path()
+        .keep()
+        .map_err(|e| VaulticError::Io(e.error))?;
+    Ok(path)
+}
+
+/// Write `content` to `path`, readable by the current user only.
+fn write_private(path: &Path, content: &[u8]) -> Result<()> {
+    std::fs::write(path, content)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    }
+    Ok(())
+}
+
+/// Open `path` in the user config `editor`, `$VISUAL`, `$EDITOR`, or the
+/// platform default, and wait.
+fn open_editor(path: &Path) -> Result<()> {
+    let editor = crate::cli::context::user_config()
+        .editor
+        .clone()
+        .or_else(|| std::env::var("VISUAL").ok())
+        .or_else(|| std::env::var("EDITOR").ok())
+        .filter(|e| !e.trim().is_empty())
+        .unwrap_or_else(|| {
+            if cfg!(windows) {
+                "notepad".to_string()
+            } else {
+                "vi".to_string()
+            }
+        });
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+
+    // Not a child that owns the interrupt: if Vaultic is stopped while the
+    // editor is open, the plaintext copy must go with it
+    let status = Command::new(program).args(parts).arg(path).status();
+
+    let status = status.map_err(|e| VaulticError::InvalidConfig {
+        detail: format!(
+            "Failed to start editor '{editor}': {e}\n\n  \
+             Solutions:\n    \
+             → Set EDITOR to an installed editor, e.g. EDITOR=nano\n    \
+             → Or set editor in ~/.config/vaultic/config.toml\n    \
+             → For GUI editors, make them wait: EDITOR=\"code --wait\""
+        ),
+    })?;
+    if !status.success() {
+        return Err(VaulticError::InvalidConfig {
+            detail: format!("Editor '{editor}' exited with {status}; nothing was saved."),
+        });
+    }
+    Ok(())
+}
@@ -1,30 +1,44 @@
 use std::path::{Path, PathBuf};
 
-use crate::adapters::cipher::age_backend::AgeBackend;
-use crate::adapters::cipher::gpg_backend::GpgBackend;
 use crate::adapters::key_stores::file_key_store::FileKeyStore;
+use crate::adapters::key_stores::members_file::MembersFile;
+use crate::cli::commands::crypto_helpers;
+use crate::cli::commands::lifecycle_helpers::{self, Hook};
 use crate::cli::output;
-use crate::config::app_config::AppConfig;
+use crate::config::app_config::{AppConfig, DEFAULT_MAX_SHRINK_PERCENT, SecretFormat, Storage};
 use crate::core::errors::{Result, VaulticError};
+use crate::core::models::audit_entry::AuditDetail;
+use crate::core::models::key_identity::{KeyIdentity, KeyType};
+use crate::core::services::diff_service::DiffService;
 use crate::core::services::encryption_service::EncryptionService;
 use crate::core::traits::cipher::CipherBackend;
 use crate::core::traits::key_store::KeyStore;
 
 /// Execute the `vaultic encrypt` command.
 ///
-/// Encrypts a source file for all authorized recipients
-/// and stores the ciphertext in `.vaultic/`.
+/// Encrypts a source file for the environment's recipients
+/// (`recipients.<env>.txt`, or `recipients.txt` when the environment has
+/// no list of its own) and stores the ciphertext in `.vaultic/`.
 /// When `all` is true, re-encrypts every environment defined in config.
-pub fn execute(file: Option<&str>, env: Option<&str>, cipher: &str, all: bool) -> Result<()> {
+/// With `review`, or `review = true` on the environment, the changes to
+/// an existing ciphertext are shown and confirmed first. The `pre_encrypt`
+/// and `post_encrypt` hooks run around a single file, not `all`.
+pub fn execute(
+    file: Option<&str>,
+    env: Option<&str>,
+    cipher: &str,
+    all: bool,
+    note: Option<&str>,
+    force: bool,
+    review: bool,
+) -> Result<()> {
     let vaultic_dir = crate::cli::context::vaultic_dir();
     if !vaultic_dir.exists() {
-        return Err(VaulticError::InvalidConfig {
-            detail: "Vaultic not initialized. Run 'vaultic init' first.".into(),
-        });
+        return Err(crate::cli::first_run::not_initialized());
     }
 
     if all {
-        return encrypt_all(vaultic_dir, cipher);
+        return encrypt_all(vaultic_dir, cipher, note, force);
     }
 
     let source = PathBuf::from(file.unwrap_or(".env"));
@@ -34,19 +48,184 @@ pub fn execute(file: Option<&str>, env: Option<&str>, cipher: &str, all: bool) -
         });
     }
 
-    let env_name = env.unwrap_or("dev");
+    let config = crate::cli::context::config().ok();
+    let env_name = &crate::cli::context::select_env(env, config)?;
+    if let Some(config) = &config {
+        config.require_env(env_name)?;
+        config.require_writable(env_name)?;
+        warn_on_format_mismatch(&source, env_name, config);
+    }
+    crypto_helpers::check_recipients_signature(vaultic_dir, config)?;
+
     let dest = vaultic_dir.join(format!("{env_name}.env.enc"));
-    let key_store = FileKeyStore::new(vaultic_dir.join("recipients.txt"));
+    check_shrink(&source, vaultic_dir, env_name, force)?;
+    let review = review
+        || config
+            .and_then(|c| c.environments.get(env_name))
+            .is_some_and(|e| e.review);
+    if review && dest.exists() && !confirm_changes(&source, &dest, env_name, cipher)? {
+        output::warning("Nothing encrypted.");
+        return Ok(());
+    }
+    let key_store = FileKeyStore::new(vaultic_dir.join("recipients.txt")).for_env(env_name);
+    check_expired(vaultic_dir, &key_store, env_name, force)?;
+    lifecycle_helpers::run(Hook::PreEncrypt, env_name, &source)?;
 
-    encrypt_single(&source, &dest, env_name, cipher, &key_store)
+    encrypt_single(&source, &dest, env_name, cipher, &key_store, note)?;
+    lifecycle_helpers::run(Hook::PostEncrypt, env_name, &dest)
 }
 
-/// Re-encrypt all environments defined in config.toml.
-///
-/// For each environment, decrypts the existing `.enc` file and
-/// re-encrypts it with the current recipients list.
-fn encrypt_all(vaultic_dir: &Path, cipher: &str) -> Result<()> {
-    let config = AppConfig::load(vaultic_dir)?;
+/// Warn when the source file's extension suggests a format other than the
+/// one `env_name` is read as, since resolve/diff would fail to parse it.
+fn warn_on_format_mismatch(source: &Path, env_name: &str, config: &AppConfig) {
+    let detected = SecretFormat::from_path(source);
+    let configured = config.env_format(env_name);
+    if detected != SecretFormat::Dotenv && detected != configured {
+        output::warning(&format!(
+            "{} looks like {} but {env_name} is read as {}. \
+             Set format = \"{}\" for {env_name} in config.toml.",
+            source.display(),
+            detected.label(),
+            configured.label(),
+            detected.label().to_lowercase()
+        ));
+    }
+}
+
+/// Refuse, unless `force`, to encrypt a `source` with no variables, or
+/// with more than `[history] max_shrink_percent` fewer than the last
+/// kept version of `env_name`: a script that truncated `.env` would
+/// otherwise wipe the environment for the whole team.
+fn check_shrink(source: &Path, vaultic_dir: &Path, env_name: &str, force: bool) -> Result<()> {
+    let Some(count) = std::fs::read(source)
+        .ok()
+        .and_then(|plaintext| variable_count(&plaintext, env_name))
+    else {
+        return Ok(());
+    };
+    let problem = if count == 0 {
+        format!("{} has no variables", source.display())
+    } else {
+        let max_shrink = crate::cli::context::config()
+            .map(|c| c.max_shrink_percent())
+            .unwrap_or(DEFAULT_MAX_SHRINK_PERCENT);
+        match super::rollback::last_version(vaultic_dir, env_name).and_then(|v| v.variables) {
+            Some(last) if count < last && (last - count) * 100 > last * max_shrink as usize => {
+                format!(
+                    "{} has {count} variable(s), down from {last} in the last version of \
+                     {env_name} ({}% fewer; the limit is {max_shrink}%)",
+                    source.display(),
+                    (last - count) * 100 / last
+                )
+            }
+            _ => return Ok(()),
+        }
+    };
+    if force {
+        output::warning(&format!("{problem}; encrypting anyway (--force)"));
+        return Ok(());
+    }
+    Err(VaulticError::InvalidConfig {
+        detail: format!(
+            "{problem}.\n\n  \
+             Encrypting it would replace {env_name} for everyone.\n  \
+             Solutions:\n    \
+             → Check that {} is the file you meant to encrypt\n    \
+             → Use --force if the variables were removed on purpose",
+            source.display()
+        ),
+    })
+}
+
+/// Refuse, unless `force`, to encrypt `env_name` for a recipient of
+/// `key_store` whose `expires` date in `members.toml` has passed: a
+/// contractor's key would otherwise keep receiving every new secret.
+fn check_expired(
+    vaultic_dir: &Path,
+    key_store: &FileKeyStore,
+    env_name: &str,
+    force: bool,
+) -> Result<()> {
+    let members = MembersFile::new(vaultic_dir).load()?;
+    let today = crate::cli::context::clock().now().date_naive();
+    let expired: Vec<String> = key_store
+        .list()?
+        .iter()
+        .filter_map(|ki| {
+            let member = members.get(&ki.public_key)?;
+            let note = member
+                .expiry_note(today)
+                .filter(|_| member.is_expired(today))?;
+            Some(match members.describe(ki) {
+                Some(who) => format!("{} ({who}, {note})", ki.public_key),
+                None => format!("{} ({note})", ki.public_key),
+            })
+        })
+        .collect();
+    if expired.is_empty() {
+        return Ok(());
+    }
+    let problem = format!(
+        "{env_name} would be encrypted for {} expired recipient(s):\n    {}",
+        expired.len(),
+        expired.join("\n    ")
+    );
+    if force {
+        output::warning(&format!("{problem}\n  Encrypting anyway (--force)"));
+        return Ok(());
+    }
+    Err(VaulticError::InvalidConfig {
+        detail: format!(
+            "{problem}\n\n  \
+             Solutions:\n    \
+             → Remove them with 'vaultic keys remove <key>'\n    \
+             → Move their expires date in .vaultic/members.toml if access was extended\n    \
+             → Use --force to encrypt for them anyway"
+        ),
+    })
+}
+
+/// Decrypt `dest` in memory, show how `source` changes it key by key,
+/// values masked, and ask whether to go ahead.
+fn confirm_changes(source: &Path, dest: &Path, env_name: &str, cipher: &str) -> Result<bool> {
+    let vaultic_dir = dest.parent().unwrap_or(Path::new("."));
+    let current = crypto_helpers::decrypt_in_memory(dest, vaultic_dir, cipher)?;
+    let format = crate::cli::context::config()
+        .map(|c| c.env_format(env_name))
+        .unwrap_or(SecretFormat::Dotenv);
+    let parser = crate::adapters::parsers::for_format(format);
+    let current = parser.parse(&String::from_utf8_lossy(&current))?;
+    let new = parser.parse(&std::fs::read_to_string(source)?)?;
+
+    let source_name = source.display().to_string();
+    output::header(&format!("Reviewing {source_name} → {env_name}"));
+    let result = DiffService.diff(&current, &new, env_name, &source_name)?;
+    super::diff::report(
+        &current,
+        &new,
+        &result,
+        super::diff::DiffView::default(),
+        &format!("{source_name} matches {env_name}"),
+    )?;
+    output::confirm(&format!("Encrypt {source_name} as {env_name}?"), false)
+}
+
+/// Number of variables in `plaintext`, read in `env_name`'s format, or
+/// `None` when it does not parse.
+fn variable_count(plaintext: &[u8], env_name: &str) -> Option<usize> {
+    let format = crate::cli::context::config()
+        .map(|c| c.env_format(env_name))
+        .unwrap_or(SecretFormat::Dotenv);
+    crate::adapters::parsers::for_format(format)
+        .parse(&String::from_utf8_lossy(plaintext))
+        .ok()
+        .map(|file| file.entries().count())
+}
+
+/// Re-encrypt all environments defined in config.toml (see [`reencrypt`]).
+fn encrypt_all(vaultic_dir: &Path, cipher: &str, note: Option<&str>, force: bool) -> Result<()> {
+    let config = crate::cli::context::config()?;
+    crypto_helpers::check_recipients_signature(vaultic_dir, Some(config))?;
     let key_store = FileKeyStore::new(vaultic_dir.join("recipients.txt"));
 
     let mut envs: Vec<_> = config.environments.keys().collect();
@@ -56,20 +235,33 @@ fn encrypt_all(vaultic_dir: &Path, cipher: &str) -> Result<()> {
     let mut skip_count = 0;
 
     for env_name in &envs {
-        let file_name = config.env_file_name(env_name);
-        let enc_path = vaultic_dir.join(format!("{file_name}.enc"));
+        if let Some(origin) = config.mirror(env_name) {
+            output::detail(&format!(
+                "Skipping {env_name}: read-only mirror of {origin}"
+            ));
+            skip_count += 1;
+            continue;
+        }
+        let file_name = super::audit_helpers::env_enc_file(vaultic_dir, config, env_name);
+        let enc_path = vaultic_dir.join(&file_name);
 
         if !enc_path.exists() {
-            output::warning(&format!("Skipping {env_name}: {file_name}.enc not found"));
+            output::warning(&format!("Skipping {env_name}: {file_name} not found"));
             skip_count += 1;
             continue;
         }
 
-        // Decrypt in memory and re-encrypt directly — no plaintext on disk
-        let ciphertext = std::fs::read(&enc_path)?;
-        let plaintext = decrypt_bytes(&ciphertext, cipher)?;
-
-        encrypt_bytes_to(&plaintext, &enc_path, env_name, cipher, &key_store)?;
+        match reencrypt(&enc_path, env_name, cipher, &key_store, note, force) {
+            Err(VaulticError::SharesRequired { need, .. }) => {
+                output::warning(&format!(
+                    "Skipping {env_name}: it needs {need} shares to decrypt. Collect them and run \
+                     'vaultic decrypt --env {env_name} --share <file>', then 'vaultic encrypt --env {env_name}'."
+                ));
+                skip_count += 1;
+                continue;
+            }
+            other => other?,
+        }
 
         success_count += 1;
     }
@@ -81,32 +273,62 @@ fn encrypt_all(vaultic_dir: &Path, cipher: &str) -> Result<()> {
     Ok(())
 }
 
-/// Decrypt raw bytes using the specified cipher backend.
-fn decrypt_bytes(ciphertext: &[u8], cipher: &str) -> Result<Vec<u8>> {
-    match cipher {
-        "age" => {
-            let backend = if let Ok(key_data) = std::env::var("VAULTIC_AGE_KEY") {
-                let key_data = key_data.trim();
-                if key_data.is_empty() {
-                    return Err(VaulticError::EncryptionFailed {
-                        reason: "VAULTIC_AGE_KEY is set but empty. Provide the full age identity content.".into(),
-                    });
-                }
-                AgeBackend::from_key_data(key_data.to_string())
-            } else {
-                let identity_path = AgeBackend::default_identity_path()?;
-                AgeBackend::new(identity_path)
-            };
-            backend.decrypt(ciphertext)
-        }
-        "gpg" => {
-            let backend = GpgBackend::new();
-            backend.decrypt(ciphertext)
-        }
-        other => Err(VaulticError::InvalidConfig {
-            detail: format!("Unknown cipher backend: '{other}'. Use 'age' or 'gpg'."),
-        }),
+/// Re-encrypt one environment's existing ciphertext at `enc_path` for
+/// its current recipients — its own `recipients.<env>.txt` when it has
+/// one. Decrypts in memory, so no plaintext touches the disk.
+fn reencrypt(
+    enc_path: &Path,
+    env_name: &str,
+    cipher: &str,
+    key_store: &FileKeyStore,
+    note: Option<&str>,
+    force: bool,
+) -> Result<()> {
+    let env_store = key_store.for_env(env_name);
+    let ciphertext = std::fs::read(enc_path)?;
+    let plaintext = super::crypto_helpers::decrypt_bytes(&ciphertext, cipher)?;
+
+    if env_store.path() != key_store.path() {
+        output::detail(&format!("{env_name} uses {}", env_store.path().display()));
     }
+    encrypt_bytes_to(
+        &plaintext, enc_path, env_name, cipher, &env_store, note, force,
+    )
+}
+
+/// Execute `vaultic encrypt --reencrypt`.
+///
+/// Re-encrypts one environment's current ciphertext in place for its
+/// recipients, after they changed, without the plaintext source file.
+/// Like `all`, runs no hooks.
+pub fn execute_reencrypt(
+    env: Option<&str>,
+    cipher: &str,
+    note: Option<&str>,
+    force: bool,
+) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+    let config = crate::cli::context::config()?;
+    let env_name = &crate::cli::context::select_env(env, Some(config))?;
+    config.require_env(env_name)?;
+    config.require_writable(env_name)?;
+    crypto_helpers::check_recipients_signature(vaultic_dir, Some(config))?;
+
+    let enc_path = vaultic_dir.join(super::audit_helpers::env_enc_file(
+        vaultic_dir,
+        config,
+        env_name,
+    ));
+    if !enc_path.exists() {
+        return Err(VaulticError::FileNotFound { path: enc_path });
+    }
+    let key_store = FileKeyStore::new(vaultic_dir.join("recipients.txt"));
+    reencrypt(&enc_path, env_name, cipher, &key_store, note, force)?;
+    println!("\n  Commit {} to the repo.", enc_path.display());
+    Ok(())
 }
 
 /// Encrypt a single file for one environment.
@@ -116,26 +338,18 @@ fn encrypt_single(
     env_name: &str,
     cipher: &str,
     key_store: &FileKeyStore,
+    note: Option<&str>,
 ) -> Result<()> {
-    match cipher {
-        "age" => {
-            let identity_path = AgeBackend::default_identity_path()?;
-            let backend = AgeBackend::new(identity_path);
-            encrypt_with(backend, key_store, source, dest, env_name)
-        }
-        "gpg" => {
-            let backend = GpgBackend::new();
-            if !backend.is_available() {
-                return Err(VaulticError::EncryptionFailed {
-                    reason: "GPG is not installed or not found in PATH".into(),
-                });
-            }
-            encrypt_with(backend, key_store, source, dest, env_name)
-        }
-        other => Err(VaulticError::InvalidConfig {
-            detail: format!("Unknown cipher backend: '{other}'. Use 'age' or 'gpg'."),
-        }),
-    }
+    let plaintext = std::fs::read(source).ok();
+    let history = plaintext
+        .as_ref()
+        .and_then(|plaintext| super::history::changes(dest, env_name, plaintext, cipher));
+    let variables = plaintext.and_then(|plaintext| variable_count(&plaintext, env_name));
+    let backend = super::crypto_helpers::env_encryptor(cipher, env_name)?;
+    encrypt_with(backend, key_store, source, dest, env_name, note)?;
+    super::history::append(dest, env_name, history, cipher, key_store);
+    super::rollback::record_version(dest, env_name, note, variables);
+    Ok(())
 }
 
 /// Encrypt with a given backend (reads plaintext from file).
@@ -145,6 +359,7 @@ fn encrypt_with<C: CipherBackend>(
     source: &Path,
     dest: &Path,
     env_name: &str,
+    note: Option<&str>,
 ) -> Result<()> {
     let recipients = key_store.list()?;
     let cipher_name = cipher.name().to_string();
@@ -159,52 +374,64 @@ fn encrypt_with<C: CipherBackend>(
         output::detail(&format!("Recipient: {}", r.public_key));
     }
 
+    let count = recipient_summary(&recipients);
     let sp = output::spinner(&format!(
-        "Encrypting {env_name} with {cipher_name} for {} recipient(s)...",
-        recipients.len()
+        "Encrypting {env_name} with {cipher_name} for {count}..."
     ));
-    service.encrypt_file(source, dest)?;
-    output::finish_spinner(
-        sp,
-        &format!(
-            "Encrypted with {cipher_name} for {} recipient(s)",
-            recipients.len()
-        ),
-    );
+    let previous = super::audit_helpers::compute_file_hash(dest);
+    if per_value(env_name)? {
+        let plaintext = std::fs::read(source).map_err(|_| VaulticError::FileNotFound {
+            path: source.to_path_buf(),
+        })?;
+        service.encrypt_values_to(&plaintext, dest)?;
+    } else {
+        service.encrypt_file(source, dest)?;
+    }
+    output::finish_spinner(sp, &format!("Encrypted with {cipher_name} for {count}"));
 
     output::success(&format!("Saved to {}", dest.display()));
     println!("\n  Commit {} to the repo.", dest.display());
 
-    log_encrypt_audit(env_name, &cipher_name, recipients.len(), dest);
+    log_encrypt_audit(
+        env_name,
+        &cipher_name,
+        recipients.len(),
+        dest,
+        previous,
+        note,
+    );
 
     Ok(())
 }
 
 /// Encrypt from in-memory bytes (no plaintext written to disk).
 ///
-/// Used by `encrypt --all` to re-encrypt already-decrypted content
-/// without ever writing plaintext to a temp file.
-fn encrypt_bytes_to(
+/// Used by `encrypt --all` and `edit` to encrypt already-decrypted
+/// content without ever writing plaintext to a temp file. Like
+/// `encrypt`, refuses expired recipients unless `force`, records the
+/// changed variables in the value history and keeps the new ciphertext
+/// as a version for `vaultic rollback`.
+pub(super) fn encrypt_bytes_to(
     plaintext: &[u8],
     dest: &Path,
     env_name: &str,
     cipher: &str,
     key_store: &FileKeyStore,
+    note: Option<&str>,
+    force: bool,
 ) -> Result<()> {
-    match cipher {
-        "age" => {
-            let identity_path = AgeBackend::default_identity_path()?;
-            let backend = AgeBackend::new(identity_path);
-            encrypt_bytes_with(backend, key_store, plaintext, dest, env_name)
-        }
-        "gpg" => {
-            let backend = GpgBackend::new();
-            encrypt_bytes_with(backend, key_store, plaintext, dest, env_name)
-        }
-        other => Err(VaulticError::InvalidConfig {
-            detail: format!("Unknown cipher backend: '{other}'. Use 'age' or 'gpg'."),
-        }),
-    }
+    check_expired(
+        dest.parent().unwrap_or(Path::new(".")),
+        key_store,
+        env_name,
+        force,
+    )?;
+    let history = super::history::changes(dest, env_name, plaintext, cipher);
+    let backend = super::crypto_helpers::env_encryptor(cipher, env_name)?;
+    encrypt_bytes_with(backend, key_store, plaintext, dest, env_name, note)?;
+    super::history::append(dest, env_name, history, cipher, key_store);
+    super::rollback::record_version(dest, env_name, note, variable_count(plaintext, env_name));
+    Ok(())
 }
 
 /// Encrypt bytes with a given backend (no file I/O for plaintext).
@@ -214,6 +441,7 @@ fn encrypt_bytes_with<C: CipherBackend>(
     plaintext: &[u8],
     dest: &Path,
     env_name: &str,
+    note: Option<&str>,
 ) -> Result<()> {
     let recipients = key_store.list()?;
     let cipher_name = cipher.name().to_string();
@@ -223,33 +451,126 @@ fn encrypt_bytes_with<C: CipherBackend>(
         key_store: key_store.clone(),
     };
 
+    let count = recipient_summary(&recipients);
     let sp = output::spinner(&format!(
-        "Re-encrypting {env_name} with {cipher_name} for {} recipient(s)...",
-        recipients.len()
+        "Re-encrypting {env_name} with {cipher_name} for {count}..."
     ));
-    service.encrypt_bytes(plaintext, dest)?;
+    let previous = super::audit_helpers::compute_file_hash(dest);
+    if per_value(env_name)? {
+        service.encrypt_values_to(plaintext, dest)?;
+    } else {
+        service.encrypt_bytes(plaintext, dest)?;
+    }
     output::finish_spinner(
         sp,
-        &format!(
-            "Re-encrypted {env_name} with {cipher_name} for {} recipient(s)",
-            recipients.len()
-        ),
+        &format!("Re-encrypted {env_name} with {cipher_name} for {count}"),
     );
 
-    log_encrypt_audit(env_name, &cipher_name, recipients.len(), dest);
+    log_encrypt_audit(
+        env_name,
+        &cipher_name,
+        recipients.len(),
+        dest,
+        previous,
+        note,
+    );
 
     Ok(())
 }
 
-/// Log an encrypt audit entry.
-fn log_encrypt_audit(env_name: &str, cipher_name: &str, recipient_count: usize, dest: &Path) {
+/// Whether `env_name` is stored with `storage = "per-value"`. Only
+/// dotenv environments can be, since values are encrypted line by line.
+fn per_value(env_name: &str) -> Result<bool> {
+    let config = crate::cli::context::config()?;
+    if config.vaultic.storage != Storage::PerValue {
+        return Ok(false);
+    }
+    if config.env_format(env_name) != SecretFormat::Dotenv {
+        return Err(VaulticError::InvalidConfig {
+            detail: format!(
+                "storage = \"per-value\" only works with dotenv environments, \
+                 and '{env_name}' is not one"
+            ),
+        });
+    }
+    Ok(true)
+}
+
+/// `3 recipient(s)`, broken down by key type when the list mixes them,
+/// e.g. `3 recipient(s) (2 age, 1 ssh)`.
+fn recipient_summary(recipients: &[KeyIdentity]) -> String {
+    let count = |kind: KeyType| recipients.iter().filter(|r| r.key_type() == kind).count();
+    let parts: Vec<String> = [
+        (KeyType::Age, "age"),
+        (KeyType::Ssh, "ssh"),
+        (KeyType::Gpg, "gpg"),
+    ]
+    .into_iter()
+    .map(|(kind, name)| (count(kind), name))
+    .filter(|(n, _)| *n > 0)
+    .map(|(n, name)| format!("{n} {name}"))
+    .collect();
+
+    if parts.len() > 1 {
+        format!("{} recipient(s) ({})", recipients.len(), parts.join(", "))
+    } else {
+        format!("{} recipient(s)", recipients.len())
+    }
+}
+
+/// Log an encrypt audit entry and extend the environment's chain in the
+/// manifest from `previous`, the checksum `dest` had before.
+///
+/// A `--note` is kept in the detail so reviewers (and
+/// `vaultic report owners --check`) can see why the change was made.
+fn log_encrypt_audit(
+    env_name: &str,
+    cipher_name: &str,
+    recipient_count: usize,
+    dest: &Path,
+    previous: Option<String>,
+    note: Option<&str>,
+) {
+    if let Some(vaultic_dir) = dest.parent() {
+        super::audit_helpers::record_state(vaultic_dir, env_name, previous, dest);
+    }
     let state_hash = super::audit_helpers::compute_file_hash(dest);
+    let detail = AuditDetail::Encrypt {
+        cipher: cipher_name.to_string(),
+        recipients: recipient_count,
+        note: note.map(String::from),
+    };
     super::audit_helpers::log_audit_with_hash(
         crate::core::models::audit_entry::AuditAction::Encrypt,
         vec![format!("{env_name}.env.enc")],
-        Some(format!(
-            "encrypted with {cipher_name} for {recipient_count} recipient(s)",
-        )),
+        Some(detail),
         state_hash,
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(public_key: &str) -> KeyIdentity {
+        KeyIdentity {
+            public_key: public_key.into(),
+            label: None,
+            added_at: None,
+        }
+    }
+
+    #[test]
+    fn recipient_summary_breaks_down_mixed_lists() {
+        let age = key("age1abc");
+        let ssh = key("ssh-ed25519 AAAAC3Nz");
+        assert_eq!(
+            recipient_summary(&[age.clone(), age.clone()]),
+            "2 recipient(s)"
+        );
+        assert_eq!(
+            recipient_summary(&[age.clone(), ssh.clone(), age]),
+            "3 recipient(s) (2 age, 1 ssh)"
+        );
+    }
+}
@@ -0,0 +1,259 @@
+use std::path::Path;
+
+use colored::Color;
+use serde::Serialize;
+
+use crate::cli::commands::junit_helpers::{self, TestSuite};
+use crate::cli::commands::{check, crypto_helpers};
+use crate::cli::output;
+use crate::cli::table::{Cell, Column, Table};
+use crate::config::app_config::AppConfig;
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::audit_entry::{AuditAction, AuditDetail};
+use crate::core::services::check_service::CheckService;
+use crate::core::services::template_resolver::TemplateResolver;
+
+/// Execute `vaultic env template-check`.
+///
+/// Resolves every encrypted environment (or those in `envs`) in memory
+/// and checks it against its own template. The report is printed as a
+/// table, or as JSON or JUnit XML with `report`, on stdout or into
+/// `output_path`. Fails when any environment does not match, so CI can
+/// stop before a deploy.
+pub fn execute_template_check(
+    envs: &[String],
+    cipher: &str,
+    report: Option<&str>,
+    output_path: Option<&str>,
+) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+    let config = crate::cli::context::config()?;
+    for env in envs {
+        config.require_env(env)?;
+    }
+    let mut names: Vec<&String> = if envs.is_empty() {
+        config.environments.keys().collect()
+    } else {
+        envs.iter().collect()
+    };
+    names.sort();
+    names.dedup();
+
+    let reports: Vec<EnvTemplateReport> = names
+        .into_iter()
+        .filter(|name| is_encrypted(vaultic_dir, config, name))
+        .map(|name| check_env(name, config, vaultic_dir, cipher))
+        .collect();
+    let passed = reports.iter().filter(|r| r.ok).count();
+
+    let json = TemplateCheckJson {
+        environments: &reports,
+        ok: passed == reports.len(),
+    };
+    if let Some(format) = report {
+        junit_helpers::emit(
+            format,
+            "vaultic env template-check",
+            || to_suites(&reports),
+            &json,
+            output_path,
+        )?;
+    }
+    if junit_helpers::stdout_free(report, output_path) {
+        if output::is_json_mode() {
+            output::json(&json)?;
+        } else {
+            print_reports(&reports, passed);
+        }
+    }
+
+    super::audit_helpers::log_audit(
+        AuditAction::Check,
+        reports
+            .iter()
+            .map(|r| format!("{}.env.enc", r.env))
+            .collect(),
+        Some(AuditDetail::CheckEnvTemplates {
+            passed,
+            total: reports.len(),
+        }),
+    );
+
+    let failed = reports.len() - passed;
+    if failed > 0 {
+        return Err(VaulticError::TemplateCheckFailed { count: failed });
+    }
+    Ok(())
+}
+
+/// Whether `name` has an encrypted file to resolve, here or upstream.
+fn is_encrypted(vaultic_dir: &Path, config: &AppConfig, name: &str) -> bool {
+    match config.mirror(name) {
+        Some(origin) => super::mirror_helpers::local_copy(vaultic_dir, name, origin)
+            .is_ok_and(|p| p.exists() || origin.starts_with("https://")),
+        None => vaultic_dir.join(format!("{name}.env.enc")).exists(),
+    }
+}
+
+/// Result of checking one resolved environment against its template.
+#[derive(Serialize)]
+struct EnvTemplateReport {
+    env: String,
+    template: Option<String>,
+    total: usize,
+    present: usize,
+    missing: Vec<String>,
+    extra: Vec<String>,
+    empty_values: Vec<String>,
+    /// Why the environment could not be checked (no key, no template...).
+    error: Option<String>,
+    ok: bool,
+    /// Template variables, in template order, for the JUnit report.
+    #[serde(skip)]
+    template_keys: Vec<String>,
+}
+
+/// `vaultic env template-check --report json` output.
+#[derive(Serialize)]
+struct TemplateCheckJson<'a> {
+    environments: &'a [EnvTemplateReport],
+    ok: bool,
+}
+
+fn check_env(
+    name: &str,
+    config: &AppConfig,
+    vaultic_dir: &Path,
+    cipher: &str,
+) -> EnvTemplateReport {
+    let mut report = EnvTemplateReport {
+        env: name.to_string(),
+        template: None,
+        total: 0,
+        present: 0,
+        missing: Vec::new(),
+        extra: Vec::new(),
+        empty_values: Vec::new(),
+        error: None,
+        ok: false,
+        template_keys: Vec::new(),
+    };
+    if let Err(e) = compare_env(name, config, vaultic_dir, cipher, &mut report) {
+        report.error = Some(match e {
+            VaulticError::DecryptionNoKey => "cannot decrypt with your key".into(),
+            VaulticError::TemplateNotFound { .. } => "template not found".into(),
+            other => other.to_string(),
+        });
+    }
+    report
+}
+
+/// Fill `report` with the comparison of the resolved environment against
+/// the template `TemplateResolver::resolve_for_env` picks for it.
+fn compare_env(
+    name: &str,
+    config: &AppConfig,
+    vaultic_dir: &Path,
+    cipher: &str,
+    report: &mut EnvTemplateReport,
+) -> Result<()> {
+    let template_path =
+        TemplateResolver::resolve_for_env(name, config, vaultic_dir, Path::new("."))?;
+    report.template = Some(template_path.display().to_string());
+
+    let environment = crypto_helpers::resolve_env(name, vaultic_dir, cipher, config, false)?;
+
+    let template_file = check::parse_file(&template_path)?;
+    let result = CheckService.check(&environment.resolved, &template_file)?;
+
+    report.template_keys = template_file.keys().into_iter().map(String::from).collect();
+    report.total = report.template_keys.len();
+    report.present = report.total - result.missing.len();
+    report.ok = result.is_ok();
+    report.missing = result.missing;
+    report.extra = result.extra;
+    report.empty_values = result.empty_values;
+    Ok(())
+}
+
+/// One JUnit suite per environment.
+fn to_suites(reports: &[EnvTemplateReport]) -> Vec<TestSuite> {
+    reports
+        .iter()
+        .map(|r| {
+            junit_helpers::template_suite(
+                &r.env,
+                &r.template_keys,
+                &r.missing,
+                &r.extra,
+                &r.empty_values,
+                r.error.as_deref(),
+            )
+        })
+        .collect()
+}
+
+/// Print the summary table.
+fn print_reports(reports: &[EnvTemplateReport], passed: usize) {
+    output::header("🔍 vaultic env template-check");
+    if reports.is_empty() {
+        output::warning("No encrypted environments to check");
+        return;
+    }
+    let mut table = Table::new(vec![
+        Column::new("Env"),
+        Column::new("Template").shrink(12),
+        Column::new("Present"),
+        Column::new("Missing"),
+        Column::new("Extra"),
+        Column::new("Empty"),
+        Column::new("Status"),
+    ]);
+    for r in reports {
+        let status = match (&r.error, r.ok) {
+            (Some(e), _) => Cell::new(format!("✗ {e}")).color(Color::Red),
+            (None, true) => Cell::new("✓ ok").color(Color::Green),
+            (None, false) => Cell::new("⚠ issues").color(Color::Yellow),
+        };
+        let counts = |n: usize| {
+            if r.error.is_some() {
+                Cell::new("-").dimmed()
+            } else {
+                Cell::new(n.to_string())
+            }
+        };
+        table.row(vec![
+            Cell::new(r.env.as_str()),
+            Cell::new(r.template.as_deref().unwrap_or("-")),
+            if r.error.is_some() {
+                Cell::new("-").dimmed()
+            } else {
+                Cell::new(format!("{}/{}", r.present, r.total))
+            },
+            counts(r.missing.len()),
+            counts(r.extra.len()),
+            counts(r.empty_values.len()),
+            status,
+        ]);
+    }
+    table.print();
+
+    for r in reports.iter().filter(|r| !r.missing.is_empty()) {
+        output::warning(&format!("{} is missing: {}", r.env, r.missing.join(", ")));
+    }
+    println!();
+    if passed == reports.len() {
+        output::success(&format!(
+            "{passed}/{} environments match their template",
+            reports.len()
+        ));
+    } else {
+        output::warning(&format!(
+            "{passed}/{} environments match their template",
+            reports.len()
+        ));
+    }
+}
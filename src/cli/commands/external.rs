@@ -0,0 +1,58 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::adapters::cipher::plugin_backend::find_executable;
+use crate::cli::signals;
+use crate::core::errors::{Result, VaulticError};
+
+/// Executables named `vaultic-<name>` on `PATH` add `vaultic <name>`.
+pub const COMMAND_PREFIX: &str = "vaultic-";
+
+/// The `vaultic-<name>` executable on `PATH`, if any.
+pub fn find(name: &str) -> Option<PathBuf> {
+    find_executable(&format!("{COMMAND_PREFIX}{name}"))
+}
+
+/// Execute a `vaultic-<name>` plugin for a command Vaultic does not know.
+///
+/// The plugin gets the remaining arguments as is, the terminal, and the
+/// project in its environment:
+///
+/// - `VAULTIC_DIR` and `VAULTIC_CONFIG`: absolute path of the `.vaultic`
+///   directory, when there is one, so `vaultic` run by the plugin targets
+///   the same project.
+/// - `VAULTIC_ENV`: the `--env` given or the project's `default_env`,
+///   when either is set.
+/// - `VAULTIC_BIN`: the running `vaultic` executable.
+///
+/// Returns the plugin's exit code.
+pub fn execute(program: &Path, args: &[String], env: Option<&str>) -> Result<i32> {
+    let mut cmd = Command::new(program);
+    cmd.args(args);
+
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if let Ok(dir) = std::fs::canonicalize(vaultic_dir) {
+        cmd.env("VAULTIC_DIR", &dir).env("VAULTIC_CONFIG", &dir);
+    }
+    let env = env.map(str::to_string).or_else(|| {
+        crate::cli::context::config()
+            .ok()
+            .and_then(|c| c.vaultic.default_env.clone())
+    });
+    if let Some(env) = env {
+        cmd.env("VAULTIC_ENV", env);
+    }
+    if let Ok(exe) = std::env::current_exe() {
+        cmd.env("VAULTIC_BIN", exe);
+    }
+    crate::cli::output::detail(&format!("Running plugin {}", program.display()));
+
+    signals::set_child_running(true);
+    let status = cmd.status();
+    signals::set_child_running(false);
+
+    let status = status.map_err(|e| VaulticError::InvalidConfig {
+        detail: format!("Failed to run plugin {}: {e}", program.display()),
+    })?;
+    Ok(super::run::exit_code(status))
+}
@@ -0,0 +1,48 @@
+use crate::adapters::clipboard::system_clipboard::SystemClipboard;
+use crate::cli::commands::crypto_helpers;
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::audit_entry::{AuditAction, AuditDetail};
+
+/// Execute the `vaultic get <KEY>` command.
+///
+/// Resolves the environment in memory (with inheritance, like `resolve`)
+/// and prints the value of a single variable on stdout, or with `copy`
+/// puts it on the clipboard until it is cleared.
+pub fn execute(key: &str, env: Option<&str>, cipher: &str, copy: bool) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+
+    let config = crate::cli::context::config()?;
+    let env_name = &crate::cli::context::select_env(env, Some(config))?;
+
+    let environment = crypto_helpers::resolve_env(env_name, vaultic_dir, cipher, config, true)?;
+
+    let Some(value) = environment.resolved.get(key) else {
+        return Err(VaulticError::InvalidConfig {
+            detail: format!(
+                "Variable '{key}' is not set in '{env_name}'\n\n  \
+                 Set it with: vaultic set {key}=<value> --env {env_name}"
+            ),
+        });
+    };
+    // Without a clipboard, fail before the read is logged
+    let clipboard = copy.then(SystemClipboard::open).transpose()?;
+
+    super::audit_helpers::log_audit(
+        AuditAction::Resolve,
+        vec![env_name.to_string()],
+        Some(AuditDetail::Get {
+            key: key.to_string(),
+        }),
+    );
+
+    match clipboard {
+        Some(clipboard) => super::clipboard_helpers::copy_secret(clipboard, key, value),
+        None => {
+            println!("{value}");
+            Ok(())
+        }
+    }
+}
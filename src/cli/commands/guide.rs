@@ -0,0 +1,178 @@
+use colored::Colorize;
+
+use crate::cli::output;
+use crate::core::errors::{Result, VaulticError};
+
+/// A task-oriented guide embedded in the binary, so it works offline.
+struct Guide {
+    topic: &'static str,
+    summary: &'static str,
+    text: &'static str,
+}
+
+const GUIDES: &[Guide] = &[
+    Guide {
+        topic: "onboarding",
+        summary: "Get access to a project's secrets as a new teammate",
+        text: include_str!("../../../guides/onboarding.md"),
+    },
+    Guide {
+        topic: "rotation",
+        summary: "Offboard someone and rotate keys and secrets",
+        text: include_str!("../../../guides/rotation.md"),
+    },
+    Guide {
+        topic: "ci",
+        summary: "Give a pipeline its own key and export secrets in CI",
+        text: include_str!("../../../guides/ci.md"),
+    },
+];
+
+/// Execute the `vaultic guide` command.
+///
+/// Without `topic`, lists the guides. With one, prints it with the
+/// current project's paths and environment names filled in, or generic
+/// ones outside a project.
+pub fn execute(topic: Option<&str>) -> Result<()> {
+    let Some(topic) = topic else {
+        output::header("Guides");
+        for guide in GUIDES {
+            println!("  {:<12} {}", guide.topic.cyan(), guide.summary);
+        }
+        println!("\n  Read one with: vaultic guide <topic>");
+        return Ok(());
+    };
+
+    let guide = GUIDES
+        .iter()
+        .find(|g| g.topic.eq_ignore_ascii_case(topic))
+        .ok_or_else(|| VaulticError::InvalidConfig {
+            detail: format!(
+                "Unknown guide '{topic}'. Available: {}",
+                GUIDES
+                    .iter()
+                    .map(|g| g.topic)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        })?;
+    render(&fill(guide.text, &placeholders()));
+    Ok(())
+}
+
+/// Values for the `{{name}}` placeholders in the guides.
+fn placeholders() -> Vec<(&'static str, String)> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    let config = crate::cli::context::config().ok();
+    let mut envs: Vec<&str> = config
+        .map(|c| c.environments.keys().map(String::as_str).collect())
+        .unwrap_or_default();
+    envs.sort();
+    let named = |candidates: &[&str], fallback: &str| {
+        candidates
+            .iter()
+            .find(|c| envs.contains(c))
+            .unwrap_or(&fallback)
+            .to_string()
+    };
+    let dev = config
+        .and_then(|c| c.vaultic.default_env.clone())
+        .unwrap_or_else(|| named(&["dev", "development", "local"], "dev"));
+    let prod = named(&["prod", "production", "live"], "prod");
+    let identity = crate::cli::context::identity_path()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|_| "~/.config/age/keys.txt".to_string());
+
+    vec![
+        ("vaultic_dir", vaultic_dir.display().to_string()),
+        (
+            "recipients",
+            vaultic_dir.join("recipients.txt").display().to_string(),
+        ),
+        (
+            "envs",
+            if envs.is_empty() {
+                "dev, staging, prod".to_string()
+            } else {
+                envs.join(", ")
+            },
+        ),
+        ("dev", dev),
+        ("prod", prod),
+        ("identity", identity),
+    ]
+}
+
+/// Replace every `{{name}}` in `text` with its value.
+fn fill(text: &str, values: &[(&str, String)]) -> String {
+    values.iter().fold(text.to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{{{name}}}}}"), value)
+    })
+}
+
+/// Print a guide's Markdown for the terminal: headings in bold, code
+/// blocks and `inline code` in cyan.
+fn render(markdown: &str) {
+    let mut in_code = false;
+    for line in markdown.lines() {
+        if line.starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            println!("    {}", line.cyan());
+        } else if let Some(title) = line.strip_prefix("# ") {
+            output::header(title);
+        } else if let Some(title) = line.strip_prefix("## ") {
+            println!("  {}", title.bold());
+        } else if line.is_empty() {
+            println!();
+        } else {
+            println!("  {}", inline_code(line));
+        }
+    }
+}
+
+/// `line` with the spans between backticks in cyan, backticks dropped.
+fn inline_code(line: &str) -> String {
+    line.split('`')
+        .enumerate()
+        .map(|(i, part)| {
+            if i % 2 == 1 {
+                part.cyan().to_string()
+            } else {
+                part.to_string()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fill_replaces_every_placeholder() {
+        let values = [
+            ("prod", "production".to_string()),
+            ("dev", "local".to_string()),
+        ];
+        assert_eq!(
+            fill("{{dev}} then {{prod}}, {{prod}} again", &values),
+            "local then production, production again"
+        );
+    }
+
+    #[test]
+    fn guides_use_only_known_placeholders() {
+        let known = placeholders();
+        for guide in GUIDES {
+            let filled = fill(guide.text, &known);
+            assert!(
+                !filled.contains("{{"),
+                "{} has an unknown placeholder",
+                guide.topic
+            );
+        }
+    }
+}
@@ -0,0 +1,334 @@
+use std::path::{Path, PathBuf};
+
+use colored::Color;
+use serde::Serialize;
+
+use crate::adapters::key_stores::file_key_store::FileKeyStore;
+use crate::adapters::key_stores::manifest_file::ManifestFile;
+use crate::cli::commands::crypto_helpers;
+use crate::cli::output;
+use crate::cli::table::{Cell, Column, Table};
+use crate::config::app_config::SecretFormat;
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::manifest::{ChainBreak, StateLink};
+use crate::core::models::value_history::{ValueChangeKind, ValueHistoryEntry};
+use crate::core::services::encryption_service::EncryptionService;
+use crate::core::services::history_service::HistoryService;
+
+/// Characters of a value hash shown in the history table.
+const SHORT_HASH: usize = 8;
+
+/// Execute the `vaultic history` command.
+///
+/// Decrypts the environment's value history in memory and lists every
+/// recorded change to `key`, oldest first, with the author and the
+/// hashes of the value before and after.
+pub fn execute(key: &str, env: Option<&str>, cipher: &str) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+    let config = crate::cli::context::config()?;
+    let env_name = &crate::cli::context::select_env(env, Some(config))?;
+    config.require_env(env_name)?;
+
+    let path = history_path(vaultic_dir, env_name);
+    let entries: Vec<ValueHistoryEntry> = if path.exists() {
+        read(&path, vaultic_dir, cipher)?
+            .into_iter()
+            .filter(|e| e.key == key)
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    if output::is_json_mode() {
+        return output::json(&entries);
+    }
+
+    if entries.is_empty() {
+        output::success(&format!("No recorded changes to {key} in {env_name}"));
+        if !path.exists() {
+            println!(
+                "\n  History is recorded each time {env_name} is encrypted; \
+                 nothing has been recorded yet."
+            );
+        }
+        return Ok(());
+    }
+
+    output::header(&format!("📜 History of {key} in {env_name}"));
+    let mut table = Table::new(vec![
+        Column::new("When"),
+        Column::new("Change"),
+        Column::new("Author").shrink(10),
+        Column::new("Value (SHA-256)"),
+    ]);
+    for entry in &entries {
+        let (label, color) = match entry.change {
+            ValueChangeKind::Added => ("added", Color::Green),
+            ValueChangeKind::Modified => ("modified", Color::Yellow),
+            ValueChangeKind::Removed => ("removed", Color::Red),
+        };
+        table.row(vec![
+            Cell::new(entry.timestamp.format("%Y-%m-%d %H:%M").to_string()).dimmed(),
+            Cell::new(label).color(color),
+            Cell::new(entry.author.as_str()),
+            Cell::new(format!(
+                "{} → {}",
+                short(entry.old_hash.as_deref()),
+                short(entry.new_hash.as_deref())
+            ))
+            .dimmed(),
+        ]);
+    }
+    table.print();
+
+    Ok(())
+}
+
+/// Execute `vaultic history <env> --verify`.
+///
+/// Checks that every write of the environment's encrypted file recorded
+/// in `manifest.toml` follows on from the one before, and that the file
+/// on disk is the last one Vaultic wrote. Needs no private key.
+pub fn verify(env: Option<&str>) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+    let config = crate::cli::context::config()?;
+    let env_name = &crate::cli::context::select_env(env, Some(config))?;
+    config.require_env(env_name)?;
+
+    let states = ManifestFile::new(vaultic_dir)
+        .load()?
+        .and_then(|mut m| m.chains.remove(env_name.as_str()))
+        .unwrap_or_default();
+    let (_, breaks) = chain_breaks(vaultic_dir, env_name)?;
+    let breaks = breaks.unwrap_or_default();
+
+    if output::is_json_mode() {
+        output::json(&ChainJson {
+            env: env_name,
+            ok: breaks.is_empty(),
+            states: &states,
+            breaks: &breaks,
+        })?;
+    } else if states.is_empty() {
+        output::success(&format!("No recorded states of {env_name} yet"));
+        println!(
+            "\n  States are recorded in .vaultic/manifest.toml each time \
+             {env_name} is encrypted."
+        );
+    } else {
+        output::header(&format!("🔗 States of {env_name}"));
+        let mut table = Table::new(vec![
+            Column::new("When"),
+            Column::new("Ciphertext (SHA-256)"),
+        ]);
+        for state in &states {
+            table.row(vec![
+                Cell::new(state.at.format("%Y-%m-%d %H:%M").to_string()).dimmed(),
+                Cell::new(format!(
+                    "{} → {}",
+                    short(state.prev_hash.as_deref()),
+                    short(Some(&state.new_hash))
+                ))
+                .dimmed(),
+            ]);
+        }
+        table.print();
+        println!();
+        for chain_break in &breaks {
+            output::warning(&chain_break.describe(env_name));
+        }
+        if breaks.is_empty() {
+            output::success(&format!(
+                "{env_name} matches the last of {} recorded state(s)",
+                states.len()
+            ));
+        }
+    }
+
+    if !breaks.is_empty() {
+        return Err(VaulticError::StateChainBroken {
+            env: env_name.clone(),
+            count: breaks.len(),
+        });
+    }
+    Ok(())
+}
+
+/// `vaultic history --verify --json` output.
+#[derive(Serialize)]
+struct ChainJson<'a> {
+    env: &'a str,
+    ok: bool,
+    states: &'a [StateLink],
+    breaks: &'a [ChainBreak],
+}
+
+/// The checksum of `env`'s encrypted file now and where its chain in the
+/// manifest breaks, or `None` breaks when there is no manifest.
+pub(super) fn chain_breaks(
+    vaultic_dir: &Path,
+    env: &str,
+) -> Result<(Option<String>, Option<Vec<ChainBreak>>)> {
+    let config = crate::cli::context::config()?;
+    let enc_path = vaultic_dir.join(super::audit_helpers::env_enc_file(vaultic_dir, config, env));
+    let current = super::audit_helpers::compute_file_hash(&enc_path);
+    let breaks = ManifestFile::new(vaultic_dir).chain_breaks(env, current.as_deref())?;
+    Ok((current, breaks))
+}
+
+/// Variables that `plaintext` adds, changes or removes compared with the
+/// environment currently encrypted at `enc_path`.
+///
+/// Returns `None` (and records nothing) when the current version cannot
+/// be decrypted or either version cannot be parsed, since a partial
+/// history would be misleading. A missing `enc_path` counts as empty.
+///
+/// Threshold environments keep no history: it is encrypted to every
+/// recipient, so one shareholder could test guesses against its value
+/// hashes.
+pub(super) fn changes(
+    enc_path: &Path,
+    env_name: &str,
+    plaintext: &[u8],
+    cipher: &str,
+) -> Option<Vec<ValueHistoryEntry>> {
+    let vaultic_dir = enc_path.parent()?;
+    if crate::cli::context::config()
+        .ok()
+        .and_then(|c| c.threshold(env_name))
+        .is_some()
+    {
+        output::detail(&format!(
+            "Value history not recorded for {env_name}: it has a threshold"
+        ));
+        return None;
+    }
+    let old = if enc_path.exists() {
+        match crypto_helpers::decrypt_in_memory(enc_path, vaultic_dir, cipher) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                output::detail(&format!("Value history not recorded for {env_name}: {e}"));
+                return None;
+            }
+        }
+    } else {
+        Vec::new()
+    };
+
+    let format = crate::cli::context::config()
+        .map(|c| c.env_format(env_name))
+        .unwrap_or(SecretFormat::Dotenv);
+    let parser = crate::adapters::parsers::for_format(format);
+    let old = parser.parse(&String::from_utf8_lossy(&old)).ok()?;
+    let new = parser.parse(&String::from_utf8_lossy(plaintext)).ok()?;
+
+    let (author, email) = crate::cli::context::author_provider().author();
+    HistoryService
+        .changes(
+            &old,
+            &new,
+            crate::cli::context::clock().now(),
+            &author,
+            email.as_deref(),
+        )
+        .ok()
+}
+
+/// Append `entries` to the history of the environment encrypted at
+/// `enc_path` and re-encrypt it for `key_store`'s recipients.
+///
+/// Runs on every encryption, even without new entries, so the history
+/// follows recipient changes. Like the audit log, failures only warn:
+/// a history problem never blocks an encryption that already happened.
+pub(super) fn append(
+    enc_path: &Path,
+    env_name: &str,
+    entries: Option<Vec<ValueHistoryEntry>>,
+    cipher: &str,
+    key_store: &FileKeyStore,
+) {
+    let Some(vaultic_dir) = enc_path.parent() else {
+        return;
+    };
+    // The current version could not be read, so neither can the history.
+    let Some(entries) = entries else {
+        return;
+    };
+    let path = history_path(vaultic_dir, env_name);
+    if entries.is_empty() && !path.exists() {
+        return;
+    }
+    if let Err(e) = try_append(&path, vaultic_dir, entries, cipher, key_store) {
+        output::warning(&format!(
+            "Could not update value history for {env_name}: {e}"
+        ));
+    }
+}
+
+fn try_append(
+    path: &Path,
+    vaultic_dir: &Path,
+    entries: Vec<ValueHistoryEntry>,
+    cipher: &str,
+    key_store: &FileKeyStore,
+) -> Result<()> {
+    let mut history = if path.exists() {
+        read(path, vaultic_dir, cipher)?
+    } else {
+        Vec::new()
+    };
+    history.extend(entries);
+
+    let mut content = String::new();
+    for entry in &history {
+        let line = serde_json::to_string(entry).map_err(|e| VaulticError::InvalidConfig {
+            detail: format!("Failed to serialize history entry: {e}"),
+        })?;
+        content.push_str(&line);
+        content.push('\n');
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    EncryptionService {
+        cipher: super::crypto_helpers::encryptor(cipher)?,
+        key_store: key_store.clone(),
+    }
+    .encrypt_bytes(content.as_bytes(), path)
+}
+
+/// Decrypt and parse a history file.
+fn read(path: &Path, vaultic_dir: &Path, cipher: &str) -> Result<Vec<ValueHistoryEntry>> {
+    let bytes = crypto_helpers::decrypt_in_memory(path, vaultic_dir, cipher)?;
+    String::from_utf8_lossy(&bytes)
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line).map_err(|e| VaulticError::ParseError {
+                file: path.to_path_buf(),
+                detail: e.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// `.vaultic/history/<env>.history.enc`
+fn history_path(vaultic_dir: &Path, env_name: &str) -> PathBuf {
+    vaultic_dir
+        .join("history")
+        .join(format!("{env_name}.history.enc"))
+}
+
+fn short(hash: Option<&str>) -> &str {
+    match hash {
+        Some(hash) => &hash[..SHORT_HASH.min(hash.len())],
+        None => "∅",
+    }
+}
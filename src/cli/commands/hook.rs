@@ -1,21 +1,38 @@
+use std::io::IsTerminal;
 use std::path::Path;
 
-use crate::adapters::git::git_hook;
+use crate::adapters::git::git_history;
+use crate::adapters::git::git_hook::{self, HookKind};
+use crate::adapters::key_stores::file_key_store::FileKeyStore;
 use crate::cli::HookAction;
 use crate::cli::output;
 use crate::core::errors::{Result, VaulticError};
-use crate::core::models::audit_entry::AuditAction;
+use crate::core::models::audit_entry::{AuditAction, AuditDetail};
 
 /// Execute the `vaultic hook` command.
 pub fn execute(action: &HookAction) -> Result<()> {
     match action {
-        HookAction::Install => execute_install(),
+        HookAction::Install { hooks, post_commit } => execute_install(hooks, *post_commit),
         HookAction::Uninstall => execute_uninstall(),
+        HookAction::PostCommit => execute_post_commit(),
+        HookAction::PrePush => execute_pre_push(),
+        HookAction::PostMerge => report_changed_envs("ORIG_HEAD", "HEAD", "merge"),
+        HookAction::PostCheckout {
+            previous,
+            new,
+            branch_flag,
+        } => match (previous, new, branch_flag.as_deref()) {
+            (Some(previous), Some(new), Some("1")) if previous != new => {
+                report_changed_envs(previous, new, "checkout")
+            }
+            _ => Ok(()),
+        },
     }
 }
 
-/// Install the git pre-commit hook.
-fn execute_install() -> Result<()> {
+/// Install the named git hooks: the pre-commit hook when none are
+/// named, plus the post-commit hook with `post_commit`.
+fn execute_install(hooks: &[String], post_commit: bool) -> Result<()> {
     let git_dir = Path::new(".git");
     if !git_dir.exists() {
         return Err(VaulticError::HookError {
@@ -23,20 +40,62 @@ fn execute_install() -> Result<()> {
         });
     }
 
-    output::header("Installing git pre-commit hook");
+    let mut kinds: Vec<HookKind> = hooks
+        .iter()
+        .filter_map(|name| HookKind::from_name(name))
+        .collect();
+    if kinds.is_empty() {
+        kinds.push(HookKind::PreCommit);
+    }
+    if post_commit && !kinds.contains(&HookKind::PostCommit) {
+        kinds.push(HookKind::PostCommit);
+    }
+    kinds.dedup();
 
-    git_hook::install(git_dir)?;
+    let names: Vec<&str> = kinds.iter().map(|k| k.name()).collect();
+    output::header(&format!(
+        "Installing git {} hook{}",
+        names.join(", "),
+        if kinds.len() == 1 { "" } else { "s" }
+    ));
 
-    output::success("Pre-commit hook installed at .git/hooks/pre-commit");
-    println!("\n  The hook will block commits that include plaintext .env files.");
-    println!("  To remove it later: vaultic hook uninstall");
+    for kind in &kinds {
+        git_hook::install(git_dir, *kind)?;
+        output::success(&format!(
+            "{} hook installed at .git/hooks/{}",
+            capitalize(kind.name()),
+            kind.name()
+        ));
+    }
+    println!();
+    for kind in &kinds {
+        println!(
+            "  {}",
+            match kind {
+                HookKind::PreCommit =>
+                    "The pre-commit hook will block commits that include plaintext .env files.",
+                HookKind::PostCommit =>
+                    "Commits that skip it with --no-verify are recorded in the audit log.",
+                HookKind::PrePush =>
+                    "The pre-push hook will block pushing environments encrypted for old recipients.",
+                HookKind::PostMerge =>
+                    "After a pull, you will be told which encrypted environments changed.",
+                HookKind::PostCheckout =>
+                    "After switching branches, you will be told which encrypted environments differ.",
+            }
+        );
+    }
+    println!("  To remove them later: vaultic hook uninstall");
 
-    super::audit_helpers::log_audit(AuditAction::HookInstall, vec![], None);
+    let detail = (kinds != [HookKind::PreCommit]).then(|| AuditDetail::HookInstall {
+        hooks: names.iter().map(|n| n.to_string()).collect(),
+    });
+    super::audit_helpers::log_audit(AuditAction::HookInstall, vec![], detail);
 
     Ok(())
 }
 
-/// Uninstall the git pre-commit hook.
+/// Uninstall every Vaultic-managed git hook.
 fn execute_uninstall() -> Result<()> {
     let git_dir = Path::new(".git");
     if !git_dir.exists() {
@@ -45,13 +104,176 @@ fn execute_uninstall() -> Result<()> {
         });
     }
 
-    output::header("Uninstalling git pre-commit hook");
-
-    git_hook::uninstall(git_dir)?;
+    output::header("Uninstalling git hooks");
 
-    output::success("Pre-commit hook removed");
+    for kind in git_hook::uninstall(git_dir)? {
+        output::success(&format!("{} hook removed", capitalize(kind.name())));
+    }
 
     super::audit_helpers::log_audit(AuditAction::HookUninstall, vec![], None);
 
     Ok(())
 }
+
+/// Run by the pre-push hook: fail when, in a pushed commit, an
+/// environment was last encrypted before the last change to its
+/// recipients list, so removed recipients could still read it.
+///
+/// The pushed commits come from the hook's stdin (`<local ref> <local
+/// sha> <remote ref> <remote sha>` per line); run by hand, `HEAD` is
+/// checked.
+fn execute_pre_push() -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Ok(());
+    }
+    let config = crate::cli::context::config()?;
+    let mut env_names: Vec<&String> = config.environments.keys().collect();
+    env_names.sort();
+
+    let mut revs = pushed_commits();
+    if revs.is_empty() {
+        revs.push("HEAD".into());
+    }
+
+    let shared = FileKeyStore::new(vaultic_dir.join("recipients.txt"));
+    let mut stale = Vec::new();
+    for env_name in env_names {
+        let enc_path = vaultic_dir.join(format!("{env_name}.env.enc"));
+        let list = shared.for_env(env_name);
+        for rev in &revs {
+            let (Some(encrypted), Some(listed)) = (
+                git_history::last_commit(rev, &enc_path)?,
+                git_history::last_commit(rev, list.path())?,
+            ) else {
+                continue;
+            };
+            if encrypted != listed && git_history::is_ancestor(&encrypted, &listed) {
+                let list_name = list
+                    .path()
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                stale.push(format!("{env_name}.env.enc (after {list_name} changed)"));
+                break;
+            }
+        }
+    }
+
+    if stale.is_empty() {
+        return Ok(());
+    }
+    Err(VaulticError::HookError {
+        detail: format!(
+            "Push blocked: environments not re-encrypted since their recipients changed:\n    \
+             {}\n\n  \
+             Removed recipients can still decrypt them.\n\n  \
+             Solutions:\n    \
+             → Re-encrypt and commit: vaultic encrypt --all && git commit -am \"Re-encrypt\"\n    \
+             → Skip check: git push --no-verify (NOT recommended)",
+            stale.join("\n    ")
+        ),
+    })
+}
+
+/// Local commits named on the pre-push hook's stdin, skipping deleted
+/// refs.
+fn pushed_commits() -> Vec<String> {
+    if std::io::stdin().is_terminal() {
+        return Vec::new();
+    }
+    std::io::stdin()
+        .lines()
+        .map_while(|line| line.ok())
+        .filter_map(|line| line.split_whitespace().nth(1).map(String::from))
+        .filter(|sha| !sha.chars().all(|c| c == '0'))
+        .collect()
+}
+
+/// Run by the post-merge and post-checkout hooks: list the encrypted
+/// environments that differ between `from` and `to` and suggest
+/// decrypting them. Never fails; git ignores these hooks' exit codes.
+fn report_changed_envs(from: &str, to: &str, operation: &str) -> Result<()> {
+    let Ok(files) = git_history::changed_files(from, to) else {
+        return Ok(());
+    };
+    let envs: Vec<&str> = files
+        .iter()
+        .filter(|f| f.split('/').any(|part| part == ".vaultic"))
+        .filter_map(|f| f.rsplit('/').next()?.strip_suffix(".env.enc"))
+        .collect();
+    if envs.is_empty() {
+        return Ok(());
+    }
+
+    output::warning(&format!(
+        "The {operation} changed encrypted environment(s): {}",
+        envs.join(", ")
+    ));
+    for env in &envs {
+        println!("    → vaultic decrypt --env {env}");
+    }
+    Ok(())
+}
+
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Run by the post-commit hook: record `HEAD` in the audit log if it
+/// contains files the pre-commit hook would have blocked.
+///
+/// Never fails the commit; git ignores the post-commit exit code anyway.
+fn execute_post_commit() -> Result<()> {
+    let git_dir = Path::new(".git");
+    let Ok(files) = git_history::commit_files("HEAD") else {
+        return Ok(());
+    };
+    let blocked: Vec<String> = files
+        .into_iter()
+        .filter(|f| git_hook::is_blocked(f))
+        .collect();
+    if blocked.is_empty() {
+        return Ok(());
+    }
+
+    let commit = git_history::short_hash("HEAD").unwrap_or_else(|_| "HEAD".into());
+    output::warning(&format!(
+        "Commit {commit} skipped the Vaultic pre-commit hook and contains: {}",
+        blocked.join(", ")
+    ));
+    super::audit_helpers::log_audit(
+        AuditAction::HookBypass,
+        blocked.clone(),
+        Some(AuditDetail::HookBypass {
+            commit: commit.to_string(),
+        }),
+    );
+    if git_dir.is_dir() {
+        git_hook::record_bypass(git_dir, &format!("{commit}: {}", blocked.join(", ")))?;
+    }
+    Ok(())
+}
+
+/// Warn about commits that bypassed the pre-commit hook since the last
+/// Vaultic command, once each.
+pub fn report_bypasses() {
+    let bypasses = git_hook::take_bypasses(Path::new(".git"));
+    if bypasses.is_empty() {
+        return;
+    }
+    output::warning(&format!(
+        "{} commit(s) skipped the pre-commit hook and include plaintext secret files:",
+        bypasses.len()
+    ));
+    for bypass in &bypasses {
+        output::warning(&format!("  {bypass}"));
+    }
+    output::warning(
+        "Remove the files from history and rotate the exposed secrets. See 'vaultic log'.",
+    );
+}
@@ -0,0 +1,307 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use age::secrecy::ExposeSecret;
+use colored::Colorize;
+
+use crate::adapters::cipher::age_backend::AgeBackend;
+use crate::adapters::key_stores::file_key_store::FileKeyStore;
+use crate::cli::output;
+use crate::cli::signals::SensitiveFile;
+use crate::config::app_config::AppConfig;
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::key_identity::KeyType;
+use crate::core::models::local_identity::LocalIdentity;
+use crate::core::services::key_service::KeyService;
+
+/// Where GPG identities come from, shown instead of a file path.
+pub const GPG_KEYRING: &str = "GPG keyring";
+
+/// Every private key on this machine: the age identity files, then the
+/// secret keys in the GPG keyring when `gpg` is installed.
+///
+/// Each one lists the encrypted environments it is a recipient of, and
+/// is marked preferred when `vaultic keys use` picked it for the project.
+pub fn discover(config: &AppConfig, vaultic_dir: &Path) -> Vec<LocalIdentity> {
+    let mut identities: Vec<LocalIdentity> = Vec::new();
+    // SSH keys count when their .pub file is next to them
+    for (path, public_key) in crate::cli::context::identity_paths()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|p| AgeBackend::read_public_key(&p).ok().map(|pk| (p, pk)))
+    {
+        if identities.iter().any(|i| i.id == public_key) {
+            continue;
+        }
+        identities.push(LocalIdentity {
+            key_type: if public_key.starts_with("ssh-") {
+                KeyType::Ssh
+            } else {
+                KeyType::Age
+            },
+            id: public_key,
+            source: path.display().to_string(),
+            user_ids: Vec::new(),
+            preferred: false,
+            envs: Vec::new(),
+        });
+    }
+    let gpg = super::crypto_helpers::gpg_backend().unwrap_or_default();
+    if gpg.is_available() {
+        for key in gpg.list_secret_keys().unwrap_or_default() {
+            identities.push(LocalIdentity {
+                key_type: KeyType::Gpg,
+                id: key.fingerprint,
+                source: GPG_KEYRING.to_string(),
+                user_ids: key.user_ids,
+                preferred: false,
+                envs: Vec::new(),
+            });
+        }
+    }
+
+    let preferred = preferred(vaultic_dir);
+    let readable = readable_by(config, vaultic_dir);
+    for identity in &mut identities {
+        identity.preferred = preferred.as_deref() == Some(identity.id.as_str());
+        identity.envs = readable
+            .iter()
+            .filter(|(_, cipher, recipients)| {
+                cipher.is_none_or(|c| (c == KeyType::Gpg) == (identity.key_type == KeyType::Gpg))
+                    && recipients.iter().any(|r| identity.matches(r))
+            })
+            .map(|(env, _, _)| env.clone())
+            .collect();
+    }
+    identities
+}
+
+/// The identity Vaultic reports as yours: the preferred one, else the age
+/// or SSH key, else a GPG key that can read something, else any GPG key.
+pub fn active(identities: &[LocalIdentity]) -> Option<&LocalIdentity> {
+    identities
+        .iter()
+        .find(|i| i.preferred)
+        .or_else(|| identities.iter().find(|i| i.key_type != KeyType::Gpg))
+        .or_else(|| identities.iter().find(|i| !i.envs.is_empty()))
+        .or_else(|| identities.first())
+}
+
+/// Encrypted environments with the cipher their file was written with,
+/// when the header tells, and their recipients. Mirrors are skipped:
+/// their recipients live upstream.
+fn readable_by(
+    config: &AppConfig,
+    vaultic_dir: &Path,
+) -> Vec<(String, Option<KeyType>, Vec<String>)> {
+    let shared = FileKeyStore::new(vaultic_dir.join("recipients.txt"));
+    let mut names: Vec<&String> = config.environments.keys().collect();
+    names.sort();
+    names
+        .into_iter()
+        .filter(|name| config.mirror(name).is_none())
+        .filter_map(|name| {
+            let path = vaultic_dir.join(format!("{}.enc", config.env_file_name(name)));
+            let head = std::fs::read(path).ok()?;
+            let cipher = if head.starts_with(b"-----BEGIN PGP") {
+                Some(KeyType::Gpg)
+            } else if head.starts_with(b"age-encryption.org/")
+                || head.starts_with(b"-----BEGIN AGE")
+            {
+                Some(KeyType::Age)
+            } else {
+                None
+            };
+            let recipients = KeyService {
+                store: shared.for_env(name),
+            }
+            .list_keys()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|ki| ki.public_key)
+            .collect();
+            Some((name.clone(), cipher, recipients))
+        })
+        .collect()
+}
+
+/// Find the identity `query` names: `age` for the age key, an age public
+/// key, or a GPG fingerprint, key ID or email.
+pub fn find<'a>(identities: &'a [LocalIdentity], query: &str) -> Result<&'a LocalIdentity> {
+    identities
+        .iter()
+        .find(|i| {
+            if query.eq_ignore_ascii_case("age") {
+                i.key_type != KeyType::Gpg
+            } else {
+                i.matches(query)
+            }
+        })
+        .ok_or_else(|| {
+            let available: Vec<String> = identities
+                .iter()
+                .map(|i| format!("→ {}", i.describe()))
+                .collect();
+            VaulticError::InvalidConfig {
+                detail: if available.is_empty() {
+                    format!(
+                        "No private key matches '{query}': none found on this machine\n\n  \
+                         Run 'vaultic keys setup' to generate or import one."
+                    )
+                } else {
+                    format!(
+                        "No private key matches '{query}'\n\n  Keys on this machine:\n  {}",
+                        available.join("\n  ")
+                    )
+                },
+            }
+        })
+}
+
+/// Print every identity with the environments it can read.
+pub fn print_identities(identities: &[LocalIdentity]) {
+    for identity in identities {
+        let kind = match identity.key_type {
+            KeyType::Gpg => "gpg",
+            KeyType::Age => "age",
+            KeyType::Ssh => "ssh",
+        };
+        let envs = if identity.envs.is_empty() {
+            "no environments".to_string()
+        } else {
+            identity.envs.join(", ")
+        };
+        let preferred = if identity.preferred {
+            " (preferred)".green().to_string()
+        } else {
+            String::new()
+        };
+        println!(
+            "    • {} {}{preferred} {}",
+            kind.cyan(),
+            identity.describe(),
+            format!("— decrypts: {envs}").dimmed()
+        );
+    }
+}
+
+/// Identity picked with `vaultic keys use` for the project at `vaultic_dir`.
+pub fn preferred(vaultic_dir: &Path) -> Option<String> {
+    load().remove(&project_key(vaultic_dir))
+}
+
+/// Remember `id` as the preferred identity for the project at
+/// `vaultic_dir`.
+pub fn set_preferred(vaultic_dir: &Path, id: &str) -> Result<()> {
+    let path = state_path().ok_or_else(|| VaulticError::InvalidConfig {
+        detail: "Could not determine the config directory".into(),
+    })?;
+    let mut preferences = load();
+    preferences.insert(project_key(vaultic_dir), id.to_string());
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json =
+        serde_json::to_string_pretty(&preferences).map_err(|e| VaulticError::InvalidConfig {
+            detail: format!("Failed to save identity preference: {e}"),
+        })?;
+    std::fs::write(&path, json)?;
+    output::detail(&format!("Saved to {}", path.display()));
+    Ok(())
+}
+
+/// Preferences by project, kept per user outside the repository since
+/// each teammate has their own keys.
+fn load() -> BTreeMap<String, String> {
+    state_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn project_key(vaultic_dir: &Path) -> String {
+    std::fs::canonicalize(vaultic_dir)
+        .unwrap_or_else(|_| vaultic_dir.to_path_buf())
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Local state file, next to the update check cache.
+fn state_path() -> Option<PathBuf> {
+    Some(dirs::config_dir()?.join("vaultic").join("identities.json"))
+}
+/// Generate a new age identity in a temp file next to `identity_path`
+/// and return its public key with the guard of that file. The caller
+/// persists it to `identity_path`.
+///
+/// The temp file is registered with [`SensitiveFile`] before the key is
+/// written and is readable by the current user only, so Ctrl-C, which
+/// exits without running destructors, never leaves a private key behind.
+pub fn stage_age_identity(identity_path: &Path) -> Result<(String, SensitiveFile)> {
+    if let Some(parent) = identity_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file_name = identity_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let staged = SensitiveFile::new(identity_path.with_file_name(format!("{file_name}.new")));
+    let (public_key, contents) = AgeBackend::new_identity(crate::cli::context::clock().now());
+    write_private(staged.path(), contents.expose_secret().as_bytes())?;
+    Ok((public_key, staged))
+}
+
+/// Generate a new age identity at `identity_path` (see
+/// [`stage_age_identity`]) and return its public key.
+pub fn generate_age_identity(identity_path: &Path) -> Result<String> {
+    let (public_key, staged) = stage_age_identity(identity_path)?;
+    staged.persist(identity_path)?;
+    Ok(public_key)
+}
+
+/// Create `path` with `content`, readable by the current user only from
+/// the start.
+fn write_private(path: &Path, content: &[u8]) -> Result<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)?.write_all(content)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn staged_identity_is_registered_until_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        let identity_path = dir.path().join("age/keys.txt");
+
+        let (public_key, staged) = stage_age_identity(&identity_path).unwrap();
+        let tmp = staged.path().to_path_buf();
+        assert!(crate::cli::signals::is_registered(&tmp));
+        let content = std::fs::read_to_string(&tmp).unwrap();
+        assert!(content.contains("AGE-SECRET-KEY-"));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&tmp).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        staged.persist(&identity_path).unwrap();
+        assert!(!tmp.exists());
+        assert!(!crate::cli::signals::is_registered(&tmp));
+        assert_eq!(
+            AgeBackend::read_public_key(&identity_path).unwrap(),
+            public_key
+        );
+    }
+}
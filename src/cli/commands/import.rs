@@ -0,0 +1,300 @@
+use std::io::{IsTerminal, Read};
+use std::path::Path;
+
+use crate::adapters::key_stores::file_key_store::FileKeyStore;
+use crate::adapters::parsers::dotenv_parser::DotenvParser;
+use crate::cli::commands::crypto_helpers;
+use crate::cli::output;
+use crate::config::app_config::SecretFormat;
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::secret_file::{Line, SecretEntry, SecretFile};
+
+/// Execute the `vaultic import` command.
+///
+/// Reads secrets from a dotenv, JSON or YAML file (or stdin), decrypts
+/// the target environment in memory, merges the imported variables into
+/// it and re-encrypts. Variables already in the environment but absent
+/// from the source are kept. With `keep_existing`, only new variables are
+/// added and existing values win.
+pub fn execute(
+    source: Option<&str>,
+    format: Option<&str>,
+    keep_existing: bool,
+    env: Option<&str>,
+    cipher: &str,
+    note: Option<&str>,
+    force: bool,
+) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+
+    let (label, content) = read_source(source)?;
+    let source_format = match format {
+        Some(name) => parse_format(name)?,
+        None => detect_format(source, &content),
+    };
+    let imported = crate::adapters::parsers::for_format(source_format)
+        .parse(&content)
+        .map_err(|e| match e {
+            VaulticError::ParseError { detail, .. } => VaulticError::ParseError {
+                file: label.clone().into(),
+                detail,
+            },
+            other => other,
+        })?;
+    if imported.keys().is_empty() {
+        output::warning(&format!(
+            "{label} contains no variables — nothing to import"
+        ));
+        return Ok(());
+    }
+
+    let config = crate::cli::context::config()?;
+    let env_name = &crate::cli::context::select_env(env, Some(config))?;
+    config.require_env(env_name)?;
+    config.require_writable(env_name)?;
+    crypto_helpers::check_recipients_signature(vaultic_dir, Some(config))?;
+
+    let enc_path = vaultic_dir.join(format!("{env_name}.env.enc"));
+    let original = if enc_path.exists() {
+        crypto_helpers::decrypt_in_memory(&enc_path, vaultic_dir, cipher)?
+    } else {
+        output::warning(&format!(
+            "{} does not exist yet — creating it",
+            enc_path.display()
+        ));
+        Vec::new()
+    };
+    let current = String::from_utf8(original).map_err(|_| VaulticError::ParseError {
+        file: enc_path.clone(),
+        detail: "Decrypted content is not valid UTF-8".into(),
+    })?;
+
+    let target_format = config.env_format(env_name);
+    let (updated, summary) = merge(&current, &imported, target_format, keep_existing)?;
+
+    if summary.added.is_empty() && summary.modified.is_empty() {
+        output::success(&format!(
+            "{env_name} already has every variable in {label} — nothing to import"
+        ));
+        return Ok(());
+    }
+
+    let default_note = format!(
+        "import {} variable(s) from {label}",
+        summary.added.len() + summary.modified.len()
+    );
+    let key_store = FileKeyStore::new(vaultic_dir.join("recipients.txt")).for_env(env_name);
+    super::encrypt::encrypt_bytes_to(
+        updated.as_bytes(),
+        &enc_path,
+        env_name,
+        cipher,
+        &key_store,
+        Some(note.unwrap_or(&default_note)),
+        force,
+    )?;
+
+    print_summary(&summary, env_name);
+    println!("\n  Commit {} to the repo.", enc_path.display());
+
+    Ok(())
+}
+
+/// Keys affected by an import, in source order.
+#[derive(Debug, Default, PartialEq)]
+pub(super) struct ImportSummary {
+    pub added: Vec<String>,
+    pub modified: Vec<String>,
+    pub unchanged: usize,
+    /// Keys whose different existing value was kept (`--keep-existing`).
+    pub kept: Vec<String>,
+}
+
+/// Read the source file, or stdin when `source` is `None` or `-`.
+/// Returns a label for messages along with the content.
+fn read_source(source: Option<&str>) -> Result<(String, String)> {
+    match source {
+        Some(path) if path != "-" => {
+            let path = Path::new(path);
+            if !path.exists() {
+                return Err(VaulticError::FileNotFound {
+                    path: path.to_path_buf(),
+                });
+            }
+            Ok((path.display().to_string(), std::fs::read_to_string(path)?))
+        }
+        _ => {
+            if std::io::stdin().is_terminal() {
+                return Err(VaulticError::InvalidConfig {
+                    detail: "No source given.\n\n  \
+                             Usage: vaultic import <file> --env <name>\n         \
+                             cat secrets.json | vaultic import --env <name>"
+                        .into(),
+                });
+            }
+            let mut content = String::new();
+            std::io::stdin().read_to_string(&mut content)?;
+            Ok(("stdin".to_string(), content))
+        }
+    }
+}
+
+fn parse_format(name: &str) -> Result<SecretFormat> {
+    match name.to_ascii_lowercase().as_str() {
+        "dotenv" | "env" => Ok(SecretFormat::Dotenv),
+        "yaml" | "yml" => Ok(SecretFormat::Yaml),
+        "json" => Ok(SecretFormat::Json),
+        _ => Err(VaulticError::InvalidConfig {
+            detail: format!("Unknown format '{name}'. Use dotenv, json or yaml."),
+        }),
+    }
+}
+
+/// Format of the source: from the file extension, or for stdin, JSON when
+/// the content looks like an object and dotenv otherwise.
+fn detect_format(source: Option<&str>, content: &str) -> SecretFormat {
+    match source {
+        Some(path) if path != "-" => SecretFormat::from_path(Path::new(path)),
+        _ if content.trim_start().starts_with('{') => SecretFormat::Json,
+        _ => SecretFormat::Dotenv,
+    }
+}
+
+/// Merge `imported` into `current`, which is stored as `format`.
+///
+/// Dotenv content is edited line by line so comments and ordering
+/// survive; YAML and JSON are re-serialized.
+pub(super) fn merge(
+    current: &str,
+    imported: &SecretFile,
+    format: SecretFormat,
+    keep_existing: bool,
+) -> Result<(String, ImportSummary)> {
+    let parser = crate::adapters::parsers::for_format(format);
+    let mut existing = parser.parse(current)?;
+    let mut summary = ImportSummary::default();
+    let mut updated = current.to_string();
+
+    for entry in imported.entries() {
+        match existing.get(&entry.key) {
+            Some(value) if value == entry.value => {
+                summary.unchanged += 1;
+                continue;
+            }
+            Some(_) if keep_existing => {
+                summary.kept.push(entry.key.clone());
+                continue;
+            }
+            Some(_) => summary.modified.push(entry.key.clone()),
+            None => summary.added.push(entry.key.clone()),
+        }
+
+        if format == SecretFormat::Dotenv {
+            updated = DotenvParser
+                .set_value(&updated, &entry.key, &entry.value)?
+                .0;
+        } else {
+            set_entry(&mut existing, &entry.key, &entry.value);
+        }
+    }
+
+    if format != SecretFormat::Dotenv {
+        updated = parser.serialize(&existing)?;
+    }
+    Ok((updated, summary))
+}
+
+/// Update `key` in `file`, or append it.
+fn set_entry(file: &mut SecretFile, key: &str, value: &str) {
+    for line in &mut file.lines {
+        if let Line::Entry(entry) = line
+            && entry.key == key
+        {
+            entry.value = value.to_string();
+            return;
+        }
+    }
+    let line_number = file.lines.len() + 1;
+    file.lines.push(Line::Entry(SecretEntry {
+        key: key.to_string(),
+        value: value.to_string(),
+        comment: None,
+        line_number,
+    }));
+}
+
+fn print_summary(summary: &ImportSummary, env_name: &str) {
+    output::success(&format!(
+        "Imported into {env_name}: {} added, {} modified, {} unchanged",
+        summary.added.len(),
+        summary.modified.len(),
+        summary.unchanged
+    ));
+    for key in &summary.added {
+        println!("    + {key}");
+    }
+    for key in &summary.modified {
+        println!("    ~ {key}");
+    }
+    if !summary.kept.is_empty() {
+        output::warning(&format!(
+            "Kept the existing value of {} variable(s) (--keep-existing):",
+            summary.kept.len()
+        ));
+        for key in &summary.kept {
+            println!("    • {key}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::traits::parser::ConfigParser;
+
+    fn dotenv(content: &str) -> SecretFile {
+        DotenvParser.parse(content).unwrap()
+    }
+
+    #[test]
+    fn merge_dotenv_keeps_comments_and_reports_changes() {
+        let current = "# db\nDB_HOST=old\nPORT=5432\n";
+        let imported = dotenv("DB_HOST=new\nPORT=5432\nAPI_KEY=abc\n");
+
+        let (updated, summary) = merge(current, &imported, SecretFormat::Dotenv, false).unwrap();
+        assert_eq!(updated, "# db\nDB_HOST=new\nPORT=5432\nAPI_KEY=abc\n");
+        assert_eq!(summary.added, vec!["API_KEY"]);
+        assert_eq!(summary.modified, vec!["DB_HOST"]);
+        assert_eq!(summary.unchanged, 1);
+    }
+
+    #[test]
+    fn merge_keep_existing_only_adds() {
+        let imported = dotenv("DB_HOST=new\nAPI_KEY=abc\n");
+        let (updated, summary) =
+            merge("DB_HOST=old\n", &imported, SecretFormat::Dotenv, true).unwrap();
+        assert_eq!(updated, "DB_HOST=old\nAPI_KEY=abc\n");
+        assert_eq!(summary.kept, vec!["DB_HOST"]);
+        assert!(summary.modified.is_empty());
+    }
+
+    #[test]
+    fn merge_into_json_environment() {
+        let imported = dotenv("B=2\nA=1\n");
+        let (updated, summary) =
+            merge(r#"{"A": "0"}"#, &imported, SecretFormat::Json, false).unwrap();
+        assert_eq!(updated, "{\n  \"A\": \"1\",\n  \"B\": \"2\"\n}");
+        assert_eq!(summary.added, vec!["B"]);
+        assert_eq!(summary.modified, vec!["A"]);
+    }
+
+    #[test]
+    fn detect_format_sniffs_stdin() {
+        assert_eq!(detect_format(None, " {\"A\": 1}"), SecretFormat::Json);
+        assert_eq!(detect_format(Some("-"), "A=1"), SecretFormat::Dotenv);
+        assert_eq!(detect_format(Some("vars.yml"), "A: 1"), SecretFormat::Yaml);
+    }
+}
@@ -1,4 +1,4 @@
-use std::io::{self, BufRead, Write};
+use std::io::Write;
 use std::path::Path;
 
 use crate::adapters::cipher::age_backend::AgeBackend;
@@ -6,11 +6,71 @@ use crate::adapters::cipher::gpg_backend::GpgBackend;
 use crate::cli::output;
 use crate::core::errors::{Result, VaulticError};
 
+/// An `[environments]` tree: each environment and the one it inherits.
+type Layout = &'static [(&'static str, Option<&'static str>)];
+
+/// Environments created when neither `--template` nor `--environments`
+/// is given.
+const DEFAULT_LAYOUT: Layout = &[
+    ("base", None),
+    ("dev", Some("base")),
+    ("staging", Some("base")),
+    ("prod", Some("base")),
+];
+
+/// Layouts selectable with `--template <preset>`.
+const PRESETS: &[(&str, Layout)] = &[
+    ("default", DEFAULT_LAYOUT),
+    // Two independent environments, no shared layer
+    ("minimal", &[("dev", None), ("prod", None)]),
+    // Shared layers per stage, then one environment per app on top
+    (
+        "monorepo",
+        &[
+            ("base", None),
+            ("dev", Some("base")),
+            ("prod", Some("base")),
+            ("api-dev", Some("dev")),
+            ("api-prod", Some("prod")),
+            ("web-dev", Some("dev")),
+            ("web-prod", Some("prod")),
+        ],
+    ),
+    // Short-lived previews built from dev; main ships straight to prod
+    (
+        "trunk-based",
+        &[
+            ("base", None),
+            ("dev", Some("base")),
+            ("preview", Some("dev")),
+            ("prod", Some("base")),
+        ],
+    ),
+];
+
+/// Choices for `vaultic init` that would otherwise be defaults or prompts.
+#[derive(Debug)]
+pub struct InitOptions<'a> {
+    /// Default cipher written to config.toml (`age` or `gpg`).
+    pub cipher: &'a str,
+    /// Never generate a key; only use one that already exists.
+    pub no_keygen: bool,
+    /// `default_env` in config.toml (default: `dev`, or the first environment).
+    pub default_env: Option<&'a str>,
+    /// Environments to create, each inheriting `base`.
+    pub environments: &'a [String],
+    /// A preset name from [`PRESETS`], or the path of a starter
+    /// config.toml to copy as is.
+    pub template: Option<&'a str>,
+}
+
 /// Execute the `vaultic init` command.
 ///
 /// Creates the `.vaultic/` directory structure, generates config defaults,
-/// and optionally sets up encryption keys via interactive prompts.
-pub fn execute() -> Result<()> {
+/// and optionally sets up encryption keys via interactive prompts. Every
+/// prompt can be answered up front through `options` and `--yes`, so init
+/// can run from provisioning scripts.
+pub fn execute(options: &InitOptions) -> Result<()> {
     let vaultic_dir = crate::cli::context::vaultic_dir();
 
     if vaultic_dir.exists() {
@@ -18,6 +78,7 @@ pub fn execute() -> Result<()> {
             detail: "Vaultic is already initialized in this project (.vaultic/ exists)".into(),
         });
     }
+    let config_content = config_toml(options)?;
 
     output::header("Vaultic — Initializing project");
 
@@ -26,22 +87,6 @@ pub fn execute() -> Result<()> {
     output::success("Created .vaultic/");
 
     // Generate config.toml
-    let config_content = r#"[vaultic]
-version = "0.1.0"
-format_version = 1
-default_cipher = "age"
-default_env = "dev"
-
-[environments]
-base = { file = "base.env" }
-dev = { file = "dev.env", inherits = "base" }
-staging = { file = "staging.env", inherits = "base" }
-prod = { file = "prod.env", inherits = "base" }
-
-[audit]
-enabled = true
-log_file = "audit.log"
-"#;
     std::fs::write(vaultic_dir.join("config.toml"), config_content)?;
     output::success("Generated config.toml with defaults");
 
@@ -55,13 +100,13 @@ log_file = "audit.log"
     }
 
     // Add .env to .gitignore
-    add_to_gitignore(".env")?;
+    add_to_gitignore(Path::new("."), ".env")?;
 
     // Key setup
     output::header("Key configuration");
     println!("  Searching for existing keys...\n");
 
-    let identity_path = AgeBackend::default_identity_path()?;
+    let identity_path = crate::cli::context::identity_path()?;
 
     if identity_path.exists() {
         // Scenario A: Existing age key found
@@ -70,11 +115,17 @@ log_file = "audit.log"
         output::success(&format!("Public key: {public_key}"));
 
         add_self_to_recipients(vaultic_dir, &public_key)?;
+    } else if options.no_keygen {
+        output::warning("No age key found — skipped key generation (--no-keygen)");
+        println!("  Run 'vaultic keys setup' later to configure your key.\n");
     } else {
         let gpg = GpgBackend::new();
         let gpg_available = gpg.is_available();
 
-        if gpg_available {
+        if gpg_available && options.cipher == "gpg" {
+            output::success("Using GPG for encryption");
+            println!("  Run 'vaultic keys setup' to configure your GPG identity.\n");
+        } else if gpg_available {
             // Scenario C: Has GPG but not age
             output::warning("No age key found");
             output::success("GPG keyring detected\n");
@@ -82,12 +133,7 @@ log_file = "audit.log"
             println!("  What do you prefer?");
             println!("  1. Generate a new age key (recommended, simpler)");
             println!("  2. Use your existing GPG key\n");
-            print!("  Selection [1]: ");
-            io::stdout().flush()?;
-
-            let mut input = String::new();
-            io::stdin().lock().read_line(&mut input)?;
-            let choice = input.trim();
+            let choice = output::input("Selection", "1")?;
 
             if choice == "2" {
                 output::success("Using GPG for encryption");
@@ -99,14 +145,7 @@ log_file = "audit.log"
         } else {
             // Scenario B: No keys at all
             output::warning("No age or GPG key found\n");
-            print!("  Generate a new age key now? [Y/n]: ");
-            io::stdout().flush()?;
-
-            let mut input = String::new();
-            io::stdin().lock().read_line(&mut input)?;
-            let answer = input.trim().to_lowercase();
-
-            if answer.is_empty() || answer == "y" || answer == "yes" {
+            if output::confirm("Generate a new age key now?", true)? {
                 generate_age_key(&identity_path, vaultic_dir)?;
             } else {
                 output::warning("Skipped key generation");
@@ -115,6 +154,7 @@ log_file = "audit.log"
         }
     }
 
+    super::audit_helpers::record_manifest(vaultic_dir);
     output::success("Project ready.\n");
     print_next_steps();
 
@@ -124,10 +164,112 @@ log_file = "audit.log"
     Ok(())
 }
 
+/// The config.toml to write: a starter file given with `--template`, or
+/// one generated from a preset or the `--environments` list.
+fn config_toml(options: &InitOptions) -> Result<String> {
+    if !super::crypto_helpers::is_known_cipher(options.cipher) {
+        return Err(super::crypto_helpers::unknown_cipher(options.cipher));
+    }
+
+    let layout: Vec<(&str, Option<&str>)> = match options.template {
+        Some(template) => match PRESETS.iter().find(|(name, _)| *name == template) {
+            Some((_, layout)) => layout.to_vec(),
+            None if options.default_env.is_some() => {
+                return Err(VaulticError::InvalidConfig {
+                    detail: "--default-env only applies to presets; set default_env in the \
+                             starter config instead"
+                        .into(),
+                });
+            }
+            None => return starter_config(template),
+        },
+        None if options.environments.is_empty() => DEFAULT_LAYOUT.to_vec(),
+        None => {
+            let mut layout = vec![("base", None)];
+            for env in options.environments.iter().map(|e| e.trim()) {
+                crate::config::names::validate_env_name(env)?;
+                if !layout.iter().any(|(name, _)| *name == env) {
+                    layout.push((env, Some("base")));
+                }
+            }
+            layout
+        }
+    };
+    let names: Vec<&str> = layout.iter().map(|(name, _)| *name).collect();
+
+    let default_env = match options.default_env {
+        Some(env) if names.contains(&env) => env,
+        Some(env) => {
+            return Err(VaulticError::InvalidConfig {
+                detail: format!(
+                    "--default-env '{env}' is not one of the environments: {}",
+                    names.join(", ")
+                ),
+            });
+        }
+        None if names.contains(&"dev") => "dev",
+        None => names
+            .iter()
+            .find(|name| **name != "base")
+            .copied()
+            .unwrap_or("base"),
+    };
+
+    let mut config = format!(
+        "[vaultic]\n\
+         version = \"0.1.0\"\n\
+         format_version = 1\n\
+         default_cipher = \"{}\"\n\
+         default_env = \"{default_env}\"\n\
+         \n\
+         [environments]\n",
+        options.cipher
+    );
+    for (env, parent) in &layout {
+        match parent {
+            Some(parent) => config.push_str(&format!(
+                "{env} = {{ file = \"{env}.env\", inherits = \"{parent}\" }}\n"
+            )),
+            None => config.push_str(&format!("{env} = {{ file = \"{env}.env\" }}\n")),
+        }
+    }
+    config.push_str("\n[audit]\nenabled = true\nlog_file = \"audit.log\"\n");
+    Ok(config)
+}
+
+/// Read a starter config.toml, checking that it is a valid config.
+fn starter_config(path: &str) -> Result<String> {
+    let content = std::fs::read_to_string(path).map_err(|_| VaulticError::InvalidConfig {
+        detail: format!(
+            "Unknown template '{path}': not a preset or a readable file\n\n  \
+             Presets: {}",
+            PRESETS
+                .iter()
+                .map(|(name, _)| *name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    })?;
+    let config = crate::config::app_config::AppConfig::parse(&content).map_err(|e| {
+        let reason = match e {
+            VaulticError::InvalidConfig { detail } => detail,
+            other => other.to_string(),
+        };
+        VaulticError::InvalidConfig {
+            detail: format!("Starter config {path} is not valid: {reason}"),
+        }
+    })?;
+    // The resolver would reject a broken tree on first use; fail now instead
+    for env in config.environments.keys() {
+        crate::core::services::env_resolver::EnvResolver.build_chain(env, &config)?;
+    }
+    Ok(content)
+}
+
 /// Generate a new age key, print the warning, and add to recipients.
 fn generate_age_key(identity_path: &Path, vaultic_dir: &Path) -> Result<()> {
     println!();
-    let public_key = AgeBackend::generate_identity(identity_path)?;
+    let public_key = super::identity_helpers::generate_age_identity(identity_path)?;
     output::success(&format!(
         "Private key saved to: {}",
         identity_path.display()
@@ -139,21 +281,21 @@ fn generate_age_key(identity_path: &Path, vaultic_dir: &Path) -> Result<()> {
     Ok(())
 }
 
-/// Add an entry to .gitignore if not already present.
-fn add_to_gitignore(entry: &str) -> Result<()> {
-    let gitignore = Path::new(".gitignore");
+/// Add an entry to `root/.gitignore` if not already present.
+pub(super) fn add_to_gitignore(root: &Path, entry: &str) -> Result<()> {
+    let gitignore = root.join(".gitignore");
 
     if gitignore.exists() {
-        let content = std::fs::read_to_string(gitignore)?;
+        let content = std::fs::read_to_string(&gitignore)?;
         if content.lines().any(|l| l.trim() == entry) {
             output::success(&format!("{entry} already in .gitignore"));
             return Ok(());
         }
-        let mut file = std::fs::OpenOptions::new().append(true).open(gitignore)?;
+        let mut file = std::fs::OpenOptions::new().append(true).open(&gitignore)?;
         writeln!(file, "\n# Vaultic: never commit plaintext secrets\n{entry}")?;
     } else {
         std::fs::write(
-            gitignore,
+            &gitignore,
             format!("# Vaultic: never commit plaintext secrets\n{entry}\n"),
         )?;
     }
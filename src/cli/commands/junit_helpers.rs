@@ -0,0 +1,240 @@
+use std::fmt::Write as _;
+
+use serde::Serialize;
+
+use crate::cli::output;
+use crate::core::errors::Result;
+
+/// A `<testsuite>`: one environment, output or file being checked.
+pub struct TestSuite {
+    pub name: String,
+    pub cases: Vec<TestCase>,
+}
+
+/// A `<testcase>`, usually one variable.
+pub struct TestCase {
+    pub name: String,
+    pub outcome: Outcome,
+}
+
+/// How a test case ended.
+pub enum Outcome {
+    Passed,
+    /// A finding, e.g. a missing variable: `<failure type="…">`.
+    Failed {
+        kind: String,
+        message: String,
+    },
+    /// The check itself could not run: `<error>`.
+    Error {
+        message: String,
+    },
+}
+
+impl TestCase {
+    pub fn passed(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            outcome: Outcome::Passed,
+        }
+    }
+
+    pub fn failed(name: impl Into<String>, kind: &str, message: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            outcome: Outcome::Failed {
+                kind: kind.to_string(),
+                message: message.into(),
+            },
+        }
+    }
+
+    pub fn error(name: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            outcome: Outcome::Error {
+                message: message.into(),
+            },
+        }
+    }
+}
+
+/// Render `suites` as a JUnit XML document named `name`, the format
+/// GitLab and Jenkins show in their test summaries.
+pub fn to_xml(name: &str, suites: &[TestSuite]) -> String {
+    let count = |suite: &TestSuite, f: fn(&Outcome) -> bool| {
+        suite.cases.iter().filter(|c| f(&c.outcome)).count()
+    };
+    let is_failure = |o: &Outcome| matches!(o, Outcome::Failed { .. });
+    let is_error = |o: &Outcome| matches!(o, Outcome::Error { .. });
+
+    let tests: usize = suites.iter().map(|s| s.cases.len()).sum();
+    let failures: usize = suites.iter().map(|s| count(s, is_failure)).sum();
+    let errors: usize = suites.iter().map(|s| count(s, is_error)).sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"{}\" tests=\"{tests}\" failures=\"{failures}\" errors=\"{errors}\">",
+        escape(name)
+    );
+    for suite in suites {
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\">",
+            escape(&suite.name),
+            suite.cases.len(),
+            count(suite, is_failure),
+            count(suite, is_error)
+        );
+        for case in &suite.cases {
+            let open = format!(
+                "    <testcase classname=\"{}\" name=\"{}\"",
+                escape(&suite.name),
+                escape(&case.name)
+            );
+            match &case.outcome {
+                Outcome::Passed => {
+                    let _ = writeln!(xml, "{open}/>");
+                }
+                Outcome::Failed { kind, message } => {
+                    let _ = writeln!(
+                        xml,
+                        "{open}>\n      <failure type=\"{}\" message=\"{}\"/>\n    </testcase>",
+                        escape(kind),
+                        escape(message)
+                    );
+                }
+                Outcome::Error { message } => {
+                    let _ = writeln!(
+                        xml,
+                        "{open}>\n      <error message=\"{}\"/>\n    </testcase>",
+                        escape(message)
+                    );
+                }
+            }
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Emit the `--report` of a command: JUnit XML built from `suites`
+/// for `junit`, or `json` serialized for `json`. Goes to `path`, or to
+/// stdout when it is `None`.
+pub fn emit<T: Serialize + ?Sized>(
+    format: &str,
+    name: &str,
+    suites: impl FnOnce() -> Vec<TestSuite>,
+    json: &T,
+    path: Option<&str>,
+) -> Result<()> {
+    let content = match format {
+        "junit" => to_xml(name, &suites()),
+        _ => format!("{}\n", output::to_json(json)?),
+    };
+    write_report(&content, path)
+}
+
+/// The suite for a file checked against its template: a test case per
+/// template variable, failed when it is missing or empty, and a failed
+/// one per extra variable. An `error` that kept the check from running
+/// is its only case.
+pub fn template_suite(
+    name: &str,
+    template_keys: &[String],
+    missing: &[String],
+    extra: &[String],
+    empty_values: &[String],
+    error: Option<&str>,
+) -> TestSuite {
+    let mut cases = Vec::new();
+    if let Some(error) = error {
+        cases.push(TestCase::error("check", error));
+    }
+    for key in template_keys {
+        cases.push(if missing.contains(key) {
+            TestCase::failed(
+                key.as_str(),
+                "missing",
+                format!("{key} is in the template but not in {name}"),
+            )
+        } else if empty_values.contains(key) {
+            TestCase::failed(key.as_str(), "empty", format!("{key} is empty in {name}"))
+        } else {
+            TestCase::passed(key.as_str())
+        });
+    }
+    for key in extra {
+        cases.push(TestCase::failed(
+            key.as_str(),
+            "extra",
+            format!("{key} is in {name} but not in the template"),
+        ));
+    }
+    TestSuite {
+        name: name.to_string(),
+        cases,
+    }
+}
+
+/// Whether stdout is left for the command's usual table or `--json`
+/// output: there is no report, or it goes to a file.
+pub fn stdout_free(report: Option<&str>, path: Option<&str>) -> bool {
+    report.is_none() || path.is_some()
+}
+
+/// Write a report to `path`, or to stdout when it is `None`.
+pub fn write_report(content: &str, path: Option<&str>) -> Result<()> {
+    match path {
+        Some(path) => {
+            std::fs::write(path, content)?;
+            output::success(&format!("Report written to {path}"));
+        }
+        None => print!("{content}"),
+    }
+    Ok(())
+}
+
+/// Escape text for an XML attribute.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\n' => escaped.push_str("&#10;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_counts_and_escapes_attributes() {
+        let suites = vec![TestSuite {
+            name: "prod".into(),
+            cases: vec![
+                TestCase::passed("DATABASE_URL"),
+                TestCase::failed("API_KEY", "missing", "not in \"prod\" & <base>"),
+                TestCase::error("resolve", "no key"),
+            ],
+        }];
+        let xml = to_xml("vaultic", &suites);
+        assert!(
+            xml.contains("<testsuites name=\"vaultic\" tests=\"3\" failures=\"1\" errors=\"1\">")
+        );
+        assert!(xml.contains("<testcase classname=\"prod\" name=\"DATABASE_URL\"/>"));
+        assert!(xml.contains(
+            "<failure type=\"missing\" message=\"not in &quot;prod&quot; &amp; &lt;base&gt;\"/>"
+        ));
+        assert!(xml.contains("<error message=\"no key\"/>"));
+    }
+}
@@ -1,30 +1,90 @@
-use std::io::{self, BufRead, Write};
 use std::path::{Path, PathBuf};
 
-use crate::adapters::cipher::age_backend::AgeBackend;
+use chrono::{DateTime, NaiveDate, Utc};
+use colored::Color;
+use serde::Serialize;
+
+use crate::adapters::audit::json_audit_logger::JsonAuditLogger;
+use crate::adapters::cipher::age_backend::{self, AgeBackend};
 use crate::adapters::cipher::gpg_backend::GpgBackend;
+use crate::adapters::cipher::threshold_backend::{Envelope, ThresholdBackend};
 use crate::adapters::key_stores::file_key_store::FileKeyStore;
-use crate::cli::KeysAction;
+use crate::adapters::key_stores::members_file::MembersFile;
+use crate::adapters::key_stores::recipients_signature;
 use crate::cli::output;
+use crate::cli::signals::SensitiveFile;
+use crate::cli::table::{Cell, Column, Table};
+use crate::cli::{KeysAction, ShareAction};
 use crate::core::errors::{Result, VaulticError};
-use crate::core::models::key_identity::KeyIdentity;
-use crate::core::services::key_service::KeyService;
+use crate::core::models::audit_entry::{AuditAction, AuditDetail};
+use crate::core::models::key_identity::{KeyIdentity, KeyType};
+use crate::core::models::member::{EXPIRY_NOTICE_DAYS, Member, Members};
+use crate::core::models::recipient_bundle::RecipientBundle;
+use crate::core::services::encryption_service;
+use crate::core::services::key_service::{self, KeyService};
+use crate::core::traits::audit::AuditLogger;
 
-/// Execute the `vaultic keys` command.
-pub fn execute(action: &KeysAction) -> Result<()> {
+/// Execute the `vaultic keys` command. `env` is the global `--env`,
+/// used by `keys share release`.
+pub fn execute(action: &KeysAction, env: Option<&str>) -> Result<()> {
     match action {
-        KeysAction::Setup => execute_setup(),
-        KeysAction::Add { identity } => execute_add(identity),
-        KeysAction::List => execute_list(),
+        KeysAction::Setup { from_ssh, copy } => execute_setup(from_ssh.as_deref(), *copy),
+        KeysAction::Add {
+            identity,
+            name,
+            email,
+            role,
+            expires,
+        } => execute_add(
+            identity,
+            Member {
+                key: String::new(),
+                name: name.clone(),
+                email: email.clone(),
+                role: role.clone(),
+                expires: expires.as_deref().map(parse_expires).transpose()?,
+            },
+        ),
+        KeysAction::List { check_valid } => execute_list(*check_valid),
+        KeysAction::Show { qr, copy } => execute_show(*qr, *copy),
+        KeysAction::Use { identity } => execute_use(identity),
         KeysAction::Remove { identity } => execute_remove(identity),
+        KeysAction::Rotate { note } => execute_rotate(note.as_deref()),
+        KeysAction::Audit => execute_audit(),
+        KeysAction::Export { output } => execute_export(output.as_deref()),
+        KeysAction::Import {
+            bundle,
+            signer,
+            dry_run,
+        } => execute_import(bundle, signer, *dry_run),
+        KeysAction::Share { action } => match action {
+            ShareAction::Status => execute_share_status(),
+            ShareAction::Release { to, output } => {
+                execute_share_release(env, to, output.as_deref())
+            }
+        },
+        KeysAction::VerifyRoster { roster } => execute_verify_roster(roster),
     }
 }
 
-/// Interactive key setup for new users.
-fn execute_setup() -> Result<()> {
+/// Interactive key setup for new users, or straight to using an SSH key
+/// with `--from-ssh`. With `copy`, the public key to share is put on the
+/// clipboard.
+fn execute_setup(from_ssh: Option<&str>, copy: bool) -> Result<()> {
     output::header("Key configuration for Vaultic");
+    let public_key = match from_ssh {
+        Some(raw) => Some(setup_use_ssh(raw)?),
+        None => setup_interactive()?,
+    };
+    if copy && let Some(public_key) = public_key {
+        super::clipboard_helpers::copy_public_key(&public_key)?;
+    }
+    Ok(())
+}
 
-    let identity_path = AgeBackend::default_identity_path()?;
+/// The setup menu. Returns the public key to share, if any.
+fn setup_interactive() -> Result<Option<String>> {
+    let identity_path = crate::cli::context::identity_path()?;
 
     if identity_path.exists() {
         let public_key = AgeBackend::read_public_key(&identity_path)?;
@@ -35,8 +95,9 @@ fn execute_setup() -> Result<()> {
         output::success(&format!("Public key: {public_key}"));
 
         println!("\n  Share this PUBLIC key with the project admin.");
-        println!("  The admin will run: vaultic keys add {public_key}");
-        return Ok(());
+        println!("  The admin will run: {}", keys_add_command(&public_key));
+        print_project_keys();
+        return Ok(Some(public_key));
     }
 
     // Detect GPG availability
@@ -49,48 +110,45 @@ fn execute_setup() -> Result<()> {
     if gpg_available {
         println!("  3. Use an existing GPG key from your keyring");
     }
+    let ssh_choice = dirs::home_dir()
+        .is_some_and(|home| home.join(".ssh").join("id_ed25519").exists())
+        .then_some(if gpg_available { "4" } else { "3" });
+    if let Some(n) = ssh_choice {
+        println!("  {n}. Use your SSH key (~/.ssh/id_ed25519)");
+    }
     println!();
-    print!("  Selection [1]: ");
-    io::stdout().flush()?;
-
-    let mut input = String::new();
-    io::stdin().lock().read_line(&mut input)?;
-    let choice = input.trim();
+    let choice = output::input("Selection", "1")?;
 
-    match choice {
-        "" | "1" => setup_generate_age(&identity_path)?,
-        "2" => setup_import_age(&identity_path)?,
+    Ok(match choice.as_str() {
+        "1" => Some(setup_generate_age(&identity_path)?),
+        "2" => Some(setup_import_age(&identity_path)?),
         "3" if gpg_available => setup_use_gpg()?,
+        n if ssh_choice == Some(n) => Some(setup_use_ssh("~/.ssh/id_ed25519")?),
         _ => {
             println!(
                 "\n  When you have your key ready, share the public key with the project admin."
             );
+            None
         }
-    }
-
-    Ok(())
+    })
 }
 
 /// Option 1: Generate a new age key.
-fn setup_generate_age(identity_path: &Path) -> Result<()> {
+fn setup_generate_age(identity_path: &Path) -> Result<String> {
     println!();
-    let public_key = AgeBackend::generate_identity(identity_path)?;
+    let public_key = super::identity_helpers::generate_age_identity(identity_path)?;
     output::success(&format!("Private key: {}", identity_path.display()));
     output::success(&format!("Public key: {public_key}"));
 
     print_next_step(&public_key);
     try_auto_add_recipient(&public_key);
-    Ok(())
+    Ok(public_key)
 }
 
 /// Option 2: Import an existing age key from a file.
-fn setup_import_age(identity_path: &Path) -> Result<()> {
-    print!("\n  Path to your age identity file: ");
-    io::stdout().flush()?;
-
-    let mut input = String::new();
-    io::stdin().lock().read_line(&mut input)?;
-    let source = PathBuf::from(input.trim());
+fn setup_import_age(identity_path: &Path) -> Result<String> {
+    println!();
+    let source = PathBuf::from(output::input_required("Path to your age identity file")?);
 
     if !source.exists() {
         return Err(VaulticError::FileNotFound { path: source });
@@ -106,59 +164,154 @@ fn setup_import_age(identity_path: &Path) -> Result<()> {
             ),
         })?;
 
-    // Copy the identity file to the default location
+    // Copy the identity file to the default location via a guarded temp
+    // file, so Ctrl-C mid-copy never leaves a half-written identity
     if let Some(parent) = identity_path.parent() {
         std::fs::create_dir_all(parent)?;
     }
-    std::fs::copy(&source, identity_path)?;
+    let staged = SensitiveFile::new(identity_path.with_extension("txt.tmp"));
+    std::fs::copy(&source, staged.path())?;
+    staged.persist(identity_path)?;
 
     output::success(&format!("Key imported to {}", identity_path.display()));
     output::success(&format!("Public key: {public_key}"));
 
     print_next_step(&public_key);
     try_auto_add_recipient(&public_key);
-    Ok(())
+    Ok(public_key)
 }
 
 /// Option 3: Use an existing GPG key from the system keyring.
-fn setup_use_gpg() -> Result<()> {
-    // List available GPG secret keys
-    let list_output = std::process::Command::new("gpg")
-        .args(["--list-secret-keys", "--keyid-format", "long"])
-        .output()
-        .map_err(|e| VaulticError::EncryptionFailed {
-            reason: format!("Failed to list GPG keys: {e}"),
-        })?;
-
-    if !list_output.status.success() {
-        return Err(VaulticError::EncryptionFailed {
-            reason: "Failed to list GPG secret keys".into(),
-        });
+fn setup_use_gpg() -> Result<Option<String>> {
+    let keys = super::crypto_helpers::gpg_backend()?.list_secret_keys()?;
+    if keys.is_empty() {
+        output::warning("No GPG secret keys found. Create one with: gpg --full-generate-key");
+        return Ok(None);
     }
 
-    let key_list = String::from_utf8_lossy(&list_output.stdout);
     println!("\n  Available GPG keys:\n");
-    for line in key_list.lines() {
-        println!("  {line}");
+    for (i, key) in keys.iter().enumerate() {
+        let uid = key.user_ids.first().map(String::as_str).unwrap_or("");
+        println!("  {}. {} {uid}", i + 1, key.fingerprint);
     }
 
-    print!("\n  Enter the GPG key ID or email to use: ");
-    io::stdout().flush()?;
-
-    let mut input = String::new();
-    io::stdin().lock().read_line(&mut input)?;
-    let gpg_id = input.trim().to_string();
+    println!();
+    let choice = output::input_required("Enter the number, key ID or email to use")?;
 
-    if gpg_id.is_empty() {
+    if choice.is_empty() {
         output::warning("No key selected, setup skipped.");
-        return Ok(());
+        return Ok(None);
     }
+    let gpg_id = match choice.parse::<usize>() {
+        Ok(n) if (1..=keys.len()).contains(&n) => keys[n - 1].fingerprint.clone(),
+        _ => choice,
+    };
 
     output::success(&format!("GPG key selected: {gpg_id}"));
     println!("\n  Use --cipher gpg when encrypting/decrypting.");
 
+    // Prefer it in this project when it is a key from the list
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if vaultic_dir.exists()
+        && let Ok(config) = crate::cli::context::config()
+    {
+        let identities = super::identity_helpers::discover(config, vaultic_dir);
+        if let Ok(identity) = super::identity_helpers::find(&identities, &gpg_id)
+            && identity.key_type == KeyType::Gpg
+        {
+            super::identity_helpers::set_preferred(vaultic_dir, &identity.id)?;
+            output::success("Preferred key for this project");
+        }
+    }
+
     print_next_step(&gpg_id);
     try_auto_add_recipient(&gpg_id);
+    Ok(Some(gpg_id))
+}
+
+/// Option 4: Use an SSH private key as the age identity. age decrypts
+/// with it directly, so nothing is generated or copied: the user config
+/// `identity` points at it and the public key comes from its `.pub` file.
+fn setup_use_ssh(raw: &str) -> Result<String> {
+    let path = crate::config::path_expand::expand_input(raw, Path::new("."))?;
+    if !path.exists() {
+        return Err(VaulticError::FileNotFound { path });
+    }
+    let public_key = AgeBackend::read_public_key(&path)?;
+    println!();
+    output::success(&format!("SSH key: {}", path.display()));
+    output::success(&format!("Public key: {public_key}"));
+
+    // A relative path would not resolve from other projects
+    let written = if Path::new(raw).is_relative() && !raw.starts_with(['~', '$']) {
+        std::fs::canonicalize(&path)?.display().to_string()
+    } else {
+        raw.to_string()
+    };
+    let user_config = crate::config::user_config::UserConfig::path().unwrap_or_default();
+    if crate::config::user_config::UserConfig::write_identity(&written)? {
+        output::success(&format!(
+            "Set as your identity in {}",
+            user_config.display()
+        ));
+    } else {
+        output::warning(&format!(
+            "{} already sets identity; add {written} to it to use this key everywhere",
+            user_config.display()
+        ));
+    }
+
+    print_next_step(&public_key);
+    try_auto_add_recipient(&public_key);
+    Ok(public_key)
+}
+
+/// In a project, list every age and GPG key on this machine with the
+/// environments it can decrypt, when there is more than the age key.
+fn print_project_keys() {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    let Some(config) = vaultic_dir
+        .exists()
+        .then(crate::cli::context::config)
+        .and_then(|c| c.ok())
+    else {
+        return;
+    };
+    let identities = super::identity_helpers::discover(config, vaultic_dir);
+    if identities.len() > 1 {
+        println!("\n  Keys on this machine:");
+        super::identity_helpers::print_identities(&identities);
+        if !identities.iter().any(|i| i.preferred) {
+            println!("\n  Pick one for this project with: vaultic keys use <key>");
+        }
+    }
+}
+
+/// Remember which of your keys this project uses.
+fn execute_use(query: &str) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+    let config = crate::cli::context::config()?;
+    let identities = super::identity_helpers::discover(config, vaultic_dir);
+    let identity = super::identity_helpers::find(&identities, query)?;
+
+    super::identity_helpers::set_preferred(vaultic_dir, &identity.id)?;
+    output::success(&format!(
+        "Using {} in this project ({})",
+        identity.describe(),
+        identity.source
+    ));
+    if identity.envs.is_empty() {
+        output::warning("It is not a recipient of any encrypted environment yet");
+        println!("  Ask an admin to run: vaultic keys add {}", identity.id);
+    } else {
+        output::success(&format!("Decrypts: {}", identity.envs.join(", ")));
+    }
+    if identity.key_type == KeyType::Gpg {
+        println!("\n  Use --cipher gpg when encrypting/decrypting.");
+    }
     Ok(())
 }
 
@@ -170,11 +323,20 @@ fn print_next_step(public_key: &str) {
     println!("  {public_key}");
     println!();
     println!("  The admin will run:");
-    println!("  vaultic keys add {public_key}");
+    println!("  {}", keys_add_command(public_key));
     println!();
     println!("  After that you can decrypt with: vaultic decrypt --env dev");
 }
 
+/// `vaultic keys add <key>`, quoted for SSH keys.
+fn keys_add_command(public_key: &str) -> String {
+    if public_key.contains(' ') {
+        format!("vaultic keys add \"{public_key}\"")
+    } else {
+        format!("vaultic keys add {public_key}")
+    }
+}
+
 /// Try to auto-add the public key to recipients if .vaultic exists.
 fn try_auto_add_recipient(public_key: &str) {
     let vaultic_dir = crate::cli::context::vaultic_dir();
@@ -185,9 +347,10 @@ fn try_auto_add_recipient(public_key: &str) {
         let ki = KeyIdentity {
             public_key: public_key.to_string(),
             label: None,
-            added_at: Some(chrono::Utc::now()),
+            added_at: Some(crate::cli::context::clock().now()),
         };
         if service.add_key(&ki).is_ok() {
+            super::audit_helpers::record_manifest(vaultic_dir);
             output::success("Public key added to .vaultic/recipients.txt");
         }
     }
@@ -196,8 +359,9 @@ fn try_auto_add_recipient(public_key: &str) {
 /// Validate that a string is a plausible recipient key.
 ///
 /// For age keys: must parse as `age::x25519::Recipient`.
+/// For SSH keys: must parse as an `ssh-ed25519` or `ssh-rsa` age recipient.
 /// For GPG keys: must be a hex fingerprint (16+ hex chars) or an email address.
-fn validate_recipient_key(identity: &str) -> Result<()> {
+pub(super) fn validate_recipient_key(identity: &str) -> Result<()> {
     if identity.starts_with("age1") {
         identity
             .parse::<age::x25519::Recipient>()
@@ -208,6 +372,17 @@ fn validate_recipient_key(identity: &str) -> Result<()> {
                      Example: age1ql3z7hjy54pw3hyww5ayyfg7zqgvc7w3j2elw8zmrj2kg5sfn9aqmcac8p"
                 ),
             })?;
+    } else if identity.starts_with("ssh-") {
+        identity
+            .parse::<age::ssh::Recipient>()
+            .map_err(|e| VaulticError::InvalidConfig {
+                detail: format!(
+                    "Invalid SSH public key: {}\n\n  \
+                     Pass the whole public key line, quoted, e.g.:\n  \
+                     vaultic keys add \"$(cat ~/.ssh/id_ed25519.pub)\"",
+                    age_backend::ssh_recipient_error(e)
+                ),
+            })?;
     } else if identity.contains('@') {
         // GPG email identifier — accept as-is
     } else if identity.len() >= 16 && identity.chars().all(|c| c.is_ascii_hexdigit()) {
@@ -218,6 +393,7 @@ fn validate_recipient_key(identity: &str) -> Result<()> {
                 "Unrecognized key format: '{identity}'\n\n  \
                  Expected one of:\n  \
                  → age public key (starts with 'age1')\n  \
+                 → SSH public key (ssh-ed25519 or ssh-rsa)\n  \
                  → GPG fingerprint (hex, 16+ characters)\n  \
                  → GPG email identifier (contains '@')"
             ),
@@ -226,53 +402,111 @@ fn validate_recipient_key(identity: &str) -> Result<()> {
     Ok(())
 }
 
-/// Add a recipient public key.
-fn execute_add(identity: &str) -> Result<()> {
+/// Split an SSH public key line into the key and its trailing comment.
+///
+/// `ssh-ed25519 AAAA… alice@laptop` is stored as the key with the comment
+/// as its label, so the comment never becomes part of the key. Other key
+/// formats are returned unchanged.
+fn split_key_comment(identity: &str) -> (String, Option<String>) {
+    if !identity.starts_with("ssh-") {
+        return (identity.to_string(), None);
+    }
+    let mut parts = identity.split_whitespace();
+    let key = parts.by_ref().take(2).collect::<Vec<_>>().join(" ");
+    let comment = parts.collect::<Vec<_>>().join(" ");
+    (key, (!comment.is_empty()).then_some(comment))
+}
+
+/// Add a recipient public key, recording the member behind it in
+/// `members.toml` when any of its name, email, role or expiry date is
+/// given. The member's `key` is filled in here.
+fn execute_add(identity: &str, member: Member) -> Result<()> {
     let vaultic_dir = crate::cli::context::vaultic_dir();
     if !vaultic_dir.exists() {
-        return Err(VaulticError::InvalidConfig {
-            detail: "Vaultic not initialized. Run 'vaultic init' first.".into(),
-        });
+        return Err(crate::cli::first_run::not_initialized());
     }
 
     validate_recipient_key(identity)?;
+    if let Some(email) = member.email.as_deref().filter(|e| !e.contains('@')) {
+        return Err(VaulticError::InvalidConfig {
+            detail: format!("Invalid email: '{email}'"),
+        });
+    }
+    let (key, comment) = split_key_comment(identity);
+    let identity = key.as_str();
 
     let store = FileKeyStore::new(vaultic_dir.join("recipients.txt"));
     let service = KeyService { store };
 
+    // The name doubles as the label, so recipients.txt stays readable
     let ki = KeyIdentity {
         public_key: identity.to_string(),
-        label: None,
-        added_at: Some(chrono::Utc::now()),
+        label: member.name.clone().or(comment),
+        added_at: Some(crate::cli::context::clock().now()),
     };
 
+    let existing = service.list_keys()?;
     service.add_key(&ki)?;
-    output::success(&format!("Added recipient: {identity}"));
+    super::audit_helpers::record_manifest(vaultic_dir);
+    let member = Member {
+        key: identity.to_string(),
+        ..member
+    };
+    let described = member.describe();
+    if described.is_some() || member.expires.is_some() {
+        let file = MembersFile::new(vaultic_dir);
+        // Projects that only had labels start from them
+        let mut members = if file.exists() {
+            file.load()?
+        } else {
+            Members::from_labels(&existing)
+        };
+        members.upsert(member.clone());
+        file.save(&members)?;
+    }
+
+    match &described {
+        Some(who) => output::success(&format!("Added recipient: {identity} ({who})")),
+        None => output::success(&format!("Added recipient: {identity}")),
+    }
+    if let Some(expires) = member.expires {
+        println!("  Access expires after {expires}.");
+    }
     println!("\n  Re-encrypt with 'vaultic encrypt' so this recipient can decrypt.");
+    print_resign_hint();
 
     // Audit
     super::audit_helpers::log_audit(
         crate::core::models::audit_entry::AuditAction::KeyAdd,
         vec![],
-        Some(format!("added {identity}")),
+        Some(AuditDetail::KeyAdd {
+            key: identity.to_string(),
+            label: ki.label.clone(),
+            email: member.email,
+            role: member.role,
+        }),
     );
 
     Ok(())
 }
 
-/// List all authorized recipients.
-fn execute_list() -> Result<()> {
+/// List all authorized recipients. With `check_valid`, each one is
+/// validated too, and the command fails when any is invalid.
+fn execute_list(check_valid: bool) -> Result<()> {
     let vaultic_dir = crate::cli::context::vaultic_dir();
     if !vaultic_dir.exists() {
-        return Err(VaulticError::InvalidConfig {
-            detail: "Vaultic not initialized. Run 'vaultic init' first.".into(),
-        });
+        return Err(crate::cli::first_run::not_initialized());
+    }
+
+    if output::is_json_mode() {
+        return print_recipients_json(vaultic_dir, check_valid);
     }
 
     let store = FileKeyStore::new(vaultic_dir.join("recipients.txt"));
     output::detail(&format!("Recipients file: {}", store.path().display()));
     let service = KeyService { store };
     let keys = service.list_keys()?;
+    let members = MembersFile::new(vaultic_dir).load()?;
 
     if keys.is_empty() {
         output::warning("No recipients configured.");
@@ -280,43 +514,980 @@ fn execute_list() -> Result<()> {
         return Ok(());
     }
 
+    let problems = if check_valid {
+        recipient_problems(&keys)
+    } else {
+        vec![None; keys.len()]
+    };
+
     output::header(&format!("Authorized recipients ({})", keys.len()));
-    for ki in &keys {
-        match &ki.label {
-            Some(label) => println!("  • {}  # {label}", ki.public_key),
-            None => println!("  • {}", ki.public_key),
+    // Keys are never truncated so they stay copyable; labels give way.
+    let mut columns = vec![Column::new("Key"), Column::new("Label").shrink(10)];
+    if check_valid {
+        columns.push(Column::new("Status").shrink(10));
+    }
+    let mut table = Table::new(columns).separator("  ").headerless();
+    for (ki, problem) in keys.iter().zip(&problems) {
+        let label = members
+            .describe(ki)
+            .map(|who| format!("# {who}"))
+            .unwrap_or_default();
+        let mut row = vec![Cell::new(format!("• {}", ki.public_key)), Cell::new(label)];
+        if check_valid {
+            row.push(match problem {
+                Some(problem) => Cell::new(format!("✗ {problem}")).color(Color::Red),
+                None => Cell::new("✓ valid").color(Color::Green),
+            });
+        }
+        table.row(row);
+    }
+    table.print();
+
+    let invalid = problems.iter().flatten().count();
+    if invalid > 0 {
+        println!();
+        output::warning(&format!("{invalid} recipient(s) cannot be encrypted to"));
+        println!(
+            "  Fix them in {}, or remove them with 'vaultic keys remove <key>'.",
+            service.store.path().display()
+        );
+        return Err(VaulticError::ValidationFailed { count: invalid });
+    }
+    if check_valid {
+        println!();
+        output::success("Every recipient is valid");
+    }
+
+    Ok(())
+}
+
+/// Why each of `keys` cannot be encrypted to, in order, or `None` for
+/// the valid ones. GPG recipients are looked up in the keyring when gpg
+/// is installed, and only checked for format otherwise.
+fn recipient_problems(keys: &[KeyIdentity]) -> Vec<Option<String>> {
+    let gpg = super::crypto_helpers::gpg_backend()
+        .ok()
+        .filter(|gpg| gpg.is_available());
+    if gpg.is_none()
+        && !output::is_json_mode()
+        && keys.iter().any(|ki| ki.key_type() == KeyType::Gpg)
+    {
+        output::warning("gpg is not installed: GPG recipients are only checked for format");
+    }
+    keys.iter()
+        .map(|ki| recipient_problem(&ki.public_key, gpg.as_ref()))
+        .collect()
+}
+
+/// Why `key`, an entry of a recipients list, cannot be encrypted to.
+///
+/// age keys are decoded, which verifies their bech32 checksum, and SSH
+/// keys parsed. A GPG fingerprint must have 16, 40 or 64 hex digits, and
+/// with `keyring` a fingerprint or email must name a public key in it.
+fn recipient_problem(key: &str, keyring: Option<&GpgBackend>) -> Option<String> {
+    if key.starts_with("age1") {
+        return key
+            .parse::<age::x25519::Recipient>()
+            .err()
+            .map(|e: &str| format!("invalid age key: {e}"));
+    }
+    if key.starts_with("ssh-") {
+        return key
+            .parse::<age::ssh::Recipient>()
+            .err()
+            .map(|e| format!("invalid SSH key: {}", age_backend::ssh_recipient_error(e)));
+    }
+    let hex = key.trim_start_matches("0x");
+    if !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        if ![16, 40, 64].contains(&hex.len()) {
+            return Some(format!(
+                "truncated GPG fingerprint ({} hex digits)",
+                hex.len()
+            ));
         }
+    } else if !key.contains('@') || key.contains(char::is_whitespace) {
+        return Some("unrecognized key format".into());
+    }
+    match keyring {
+        Some(gpg) if !gpg.has_public_key(key) => Some("no public key in the GPG keyring".into()),
+        _ => None,
+    }
+}
+
+/// One recipient in `keys list --json`.
+#[derive(Debug, Serialize)]
+struct RecipientJson {
+    key: String,
+    label: Option<String>,
+    /// Member behind the key, from `members.toml`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    role: Option<String>,
+    /// Last day the key should be a recipient, from `members.toml`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expires: Option<NaiveDate>,
+    /// When `keys add` or `keys rotate` recorded the key in the audit log.
+    added_at: Option<DateTime<Utc>>,
+    #[serde(rename = "type")]
+    key_type: KeyType,
+    /// Encrypted environments that were written with this key as a recipient.
+    envs: Vec<String>,
+    /// With `--check-valid`, whether the key can be encrypted to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    valid: Option<bool>,
+    /// With `--check-valid`, why the key cannot be encrypted to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    problem: Option<String>,
+}
+
+/// Print recipients as a JSON array on stdout.
+///
+/// `recipients.txt` stores no dates, so `added_at` comes from the audit
+/// log. An environment counts as accessible when its `.enc` file was last
+/// encrypted after the key was added, or always when that date is unknown.
+fn print_recipients_json(vaultic_dir: &Path, check_valid: bool) -> Result<()> {
+    let config = crate::cli::context::config()?;
+    let store = FileKeyStore::new(vaultic_dir.join("recipients.txt"));
+    let keys = KeyService { store }.list_keys()?;
+    let members = MembersFile::new(vaultic_dir).load()?;
+    let problems = if check_valid {
+        recipient_problems(&keys)
+    } else {
+        vec![None; keys.len()]
+    };
+    let entries = JsonAuditLogger::from_config(vaultic_dir, config.audit.as_ref())
+        .query(None, None)
+        .unwrap_or_default();
+
+    let mut env_names: Vec<&String> = config.environments.keys().collect();
+    env_names.sort();
+    let encrypted: Vec<(&String, Option<DateTime<Utc>>)> = env_names
+        .into_iter()
+        .filter_map(|env| {
+            let file = format!("{}.enc", config.env_file_name(env));
+            if !vaultic_dir.join(&file).exists() {
+                return None;
+            }
+            let written = super::audit_helpers::last_encrypted_at(&entries, &file);
+            Some((env, written))
+        })
+        .collect();
+
+    let recipients: Vec<RecipientJson> = keys
+        .into_iter()
+        .zip(problems)
+        .map(|(ki, problem)| {
+            let added_at = super::audit_helpers::key_added_at(&entries, &ki.public_key);
+            let envs = encrypted
+                .iter()
+                .filter(|(_, written)| match (added_at, written) {
+                    (Some(added), Some(written)) => *written >= added,
+                    (Some(_), None) => false,
+                    (None, _) => true,
+                })
+                .map(|(env, _)| env.to_string())
+                .collect();
+            let member = members.get(&ki.public_key).cloned();
+            RecipientJson {
+                key_type: ki.key_type(),
+                name: member.as_ref().and_then(|m| m.name.clone()),
+                email: member.as_ref().and_then(|m| m.email.clone()),
+                role: member.as_ref().and_then(|m| m.role.clone()),
+                expires: member.and_then(|m| m.expires),
+                key: ki.public_key,
+                label: ki.label,
+                added_at,
+                envs,
+                valid: check_valid.then_some(problem.is_none()),
+                problem,
+            }
+        })
+        .collect();
+
+    output::json(&recipients)?;
+    match recipients.iter().filter(|r| r.valid == Some(false)).count() {
+        0 => Ok(()),
+        count => Err(VaulticError::ValidationFailed { count }),
+    }
+}
+
+/// Print the local age public key, optionally as a terminal QR code and
+/// copied to the clipboard.
+fn execute_show(qr: bool, copy: bool) -> Result<()> {
+    let identity_path = crate::cli::context::identity_path()?;
+    if !identity_path.exists() {
+        return Err(VaulticError::InvalidConfig {
+            detail: format!(
+                "No age key found at {}\n\n  \
+                 Solutions:\n    \
+                 → Run 'vaultic keys setup' to generate or import one",
+                identity_path.display()
+            ),
+        });
     }
 
+    let public_key = AgeBackend::read_public_key(&identity_path)?;
+    output::detail(&format!("Identity file: {}", identity_path.display()));
+    // Before the key, which stays on the last line for scripts
+    if copy {
+        super::clipboard_helpers::copy_public_key(&public_key)?;
+    }
+
+    if qr {
+        println!("{}", render_qr(&public_key)?);
+    }
+    println!("{public_key}");
+
     Ok(())
 }
 
+/// Render `data` as a QR code using Unicode half blocks (two rows per line).
+fn render_qr(data: &str) -> Result<String> {
+    let code = qrcode::QrCode::new(data.as_bytes()).map_err(|e| VaulticError::InvalidConfig {
+        detail: format!("Failed to build QR code: {e}"),
+    })?;
+    // Colors are inverted so the code scans on dark terminal backgrounds:
+    // light modules are drawn as filled blocks, dark modules as blanks.
+    Ok(code
+        .render::<qrcode::render::unicode::Dense1x2>()
+        .dark_color(qrcode::render::unicode::Dense1x2::Light)
+        .light_color(qrcode::render::unicode::Dense1x2::Dark)
+        .quiet_zone(true)
+        .build())
+}
+
 /// Remove a recipient by public key.
 fn execute_remove(identity: &str) -> Result<()> {
     let vaultic_dir = crate::cli::context::vaultic_dir();
     if !vaultic_dir.exists() {
-        return Err(VaulticError::InvalidConfig {
-            detail: "Vaultic not initialized. Run 'vaultic init' first.".into(),
-        });
+        return Err(crate::cli::first_run::not_initialized());
     }
 
-    let store = FileKeyStore::new(vaultic_dir.join("recipients.txt"));
-    let service = KeyService { store };
+    let (key, _) = split_key_comment(identity);
+    let identity = key.as_str();
 
-    service.remove_key(identity)?;
-    output::success(&format!("Removed recipient: {identity}"));
+    // Offboarding must also revoke access to environments with their own list
+    let mut removed_from = Vec::new();
+    for store in all_recipient_lists(vaultic_dir) {
+        let name = list_name(&store);
+        let service = KeyService { store };
+        if service
+            .list_keys()?
+            .iter()
+            .any(|k| k.public_key == identity)
+        {
+            service.remove_key(identity)?;
+            removed_from.push(name);
+        }
+    }
+    if removed_from.is_empty() {
+        return Err(VaulticError::KeyNotFound {
+            identity: identity.to_string(),
+        });
+    }
+    let members_file = MembersFile::new(vaultic_dir);
+    let mut members = members_file.load()?;
+    let member = members.remove(identity);
+    if member.is_some() {
+        members_file.save(&members)?;
+    }
+    let name = member.as_ref().and_then(|m| m.name.clone());
+    output::success(&format!(
+        "Removed recipient: {identity}{} (from {})",
+        name.as_ref().map(|n| format!(" ({n})")).unwrap_or_default(),
+        removed_from.join(", ")
+    ));
     println!("\n  Re-encrypt with 'vaultic encrypt --all' to revoke this recipient's access.");
+    print_resign_hint();
 
     // Audit
     super::audit_helpers::log_audit(
         crate::core::models::audit_entry::AuditAction::KeyRemove,
         vec![],
-        Some(format!("removed {identity}")),
+        Some(AuditDetail::KeyRemove {
+            key: identity.to_string(),
+            name,
+        }),
+    );
+    super::audit_helpers::record_manifest(vaultic_dir);
+
+    Ok(())
+}
+
+/// Rotate the local age identity and re-encrypt every environment.
+///
+/// Every environment is decrypted before anything changes, so a file the
+/// old key cannot read aborts the rotation untouched. The new identity is
+/// installed before recipients change and the old one is kept as
+/// `<identity>.old`, so an interrupted run never locks the user out.
+fn execute_rotate(note: Option<&str>) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+
+    let config = crate::cli::context::config()?;
+    crate::cli::commands::crypto_helpers::check_recipients_signature(vaultic_dir, Some(config))?;
+
+    let identity_path = crate::cli::context::identity_path()?;
+    if !identity_path.exists() {
+        return Err(VaulticError::FileNotFound {
+            path: identity_path,
+        });
+    }
+    let old_key = AgeBackend::read_public_key(&identity_path)?;
+
+    let store = FileKeyStore::new(vaultic_dir.join("recipients.txt"));
+    let service = KeyService {
+        store: store.clone(),
+    };
+    let old_identity = service
+        .list_keys()?
+        .into_iter()
+        .find(|k| k.public_key == old_key);
+    if old_identity.is_none() {
+        output::warning(&format!(
+            "{old_key} is not in recipients.txt; it will not be removed"
+        ));
+    }
+
+    // Decrypt everything up front with the current key
+    let backend = AgeBackend::new(identity_path.clone());
+    let mut env_names: Vec<&String> = config.environments.keys().collect();
+    env_names.sort();
+    let mut plaintexts = Vec::new();
+    for env_name in env_names {
+        let enc_path = vaultic_dir.join(format!("{}.enc", config.env_file_name(env_name)));
+        if !enc_path.exists() {
+            continue;
+        }
+        let ciphertext = std::fs::read(&enc_path)?;
+        if ThresholdBackend::is_envelope(&ciphertext) {
+            output::warning(&format!(
+                "Skipping {env_name}: it needs several shareholders to decrypt. Once \
+                 the new key is in place, collect shares and re-encrypt it."
+            ));
+            continue;
+        }
+        let plaintext = encryption_service::decrypt_content(&backend, &ciphertext)?;
+        plaintexts.push((env_name.as_str(), enc_path, plaintext));
+    }
+
+    output::header("Key rotation");
+    println!("  Current key: {old_key}");
+    println!("  Environments to re-encrypt: {}", plaintexts.len());
+    println!();
+    if !output::confirm("Generate a new key and re-encrypt all environments?", false)? {
+        output::warning("Rotation cancelled, nothing changed.");
+        return Ok(());
+    }
+
+    // Install the new identity, keeping the old one as a backup
+    let file_name = identity_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let backup_path = identity_path.with_file_name(format!("{file_name}.old"));
+    let (new_key, staged) = super::identity_helpers::stage_age_identity(&identity_path)?;
+    std::fs::copy(&identity_path, &backup_path)?;
+    staged.persist(&identity_path)?;
+    output::success(&format!("New key: {new_key}"));
+
+    service.add_key(&KeyIdentity {
+        public_key: new_key.clone(),
+        label: old_identity.and_then(|k| k.label),
+        added_at: Some(crate::cli::context::clock().now()),
+    })?;
+    if service.list_keys()?.iter().any(|k| k.public_key == old_key) {
+        service.remove_key(&old_key)?;
+    }
+    let members_file = MembersFile::new(vaultic_dir);
+    let mut members = members_file.load()?;
+    if let Some(member) = members.remove(&old_key) {
+        members.upsert(Member {
+            key: new_key.clone(),
+            ..member
+        });
+        members_file.save(&members)?;
+    }
+    // Logged before re-encrypting so the new ciphertexts postdate the
+    // recipients change
+    super::audit_helpers::log_audit(
+        AuditAction::KeyRotate,
+        plaintexts
+            .iter()
+            .filter_map(|(_, path, _)| path.file_name())
+            .map(|n| n.to_string_lossy().into_owned())
+            .collect(),
+        Some(AuditDetail::KeyRotate {
+            from: old_key.clone(),
+            to: new_key.clone(),
+        }),
+    );
+
+    // Environments with their own list keep the same members, with the
+    // rotated key swapped in
+    for store in all_recipient_lists(vaultic_dir).into_iter().skip(1) {
+        let env_service = KeyService { store };
+        let keys = env_service.list_keys()?;
+        if let Some(old) = keys.iter().find(|k| k.public_key == old_key) {
+            env_service.add_key(&KeyIdentity {
+                public_key: new_key.clone(),
+                label: old.label.clone(),
+                added_at: Some(crate::cli::context::clock().now()),
+            })?;
+            env_service.remove_key(&old_key)?;
+        }
+    }
+    super::audit_helpers::record_manifest(vaultic_dir);
+
+    for (env_name, enc_path, plaintext) in &plaintexts {
+        let env_store = store.for_env(env_name);
+        // The identity is already replaced: warn about expired recipients
+        // rather than stop with some environments left on the old key
+        super::encrypt::encrypt_bytes_to(
+            plaintext, enc_path, env_name, "age", &env_store, note, true,
+        )?;
+    }
+
+    output::success(&format!(
+        "Rotated key and re-encrypted {} environment(s)",
+        plaintexts.len()
+    ));
+    println!("\n  Old key backed up to {}", backup_path.display());
+    println!("  Delete it once the new ciphertexts are committed and pushed.");
+    println!("  Commit .vaultic/ to the repo.");
+    print_resign_hint();
+
+    Ok(())
+}
+
+/// `recipients.txt` followed by every per-environment `recipients.<env>.txt`.
+fn all_recipient_lists(vaultic_dir: &Path) -> Vec<FileKeyStore> {
+    let mut lists = vec![FileKeyStore::new(vaultic_dir.join("recipients.txt"))];
+    if let Ok(config) = crate::cli::context::config() {
+        lists.extend(
+            super::crypto_helpers::env_recipient_lists(vaultic_dir, config)
+                .into_iter()
+                .map(|(_, path)| FileKeyStore::new(path)),
+        );
+    }
+    lists
+}
+
+/// File name of a recipient list, e.g. `recipients.prod.txt`.
+fn list_name(store: &FileKeyStore) -> String {
+    store
+        .path()
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Remind the user that a signed recipients list must be re-signed.
+fn print_resign_hint() {
+    let signed = crate::cli::context::config()
+        .ok()
+        .and_then(|c| c.recipients.as_ref())
+        .is_some_and(|r| !r.admin_keys.is_empty());
+    if signed {
+        output::warning(
+            "recipients.txt is signed: an admin must re-sign it before encrypt/decrypt \
+             accept the change:\n    \
+             minisign -Sm .vaultic/recipients.txt -x .vaultic/recipients.txt.sig",
+        );
+    }
+}
+
+/// Review every recipient list against the `expires` dates in
+/// `members.toml`.
+///
+/// Fails with a validation error when any recipient has expired, so the
+/// review can gate a CI job; keys expiring soon are only flagged.
+fn execute_audit() -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+
+    // Each key once, with every list it is on
+    let mut keys: Vec<(KeyIdentity, Vec<String>)> = Vec::new();
+    for store in all_recipient_lists(vaultic_dir) {
+        let name = list_name(&store);
+        for key in (KeyService { store }).list_keys()? {
+            match keys
+                .iter_mut()
+                .find(|(k, _)| k.public_key == key.public_key)
+            {
+                Some((_, lists)) => lists.push(name.clone()),
+                None => keys.push((key, vec![name.clone()])),
+            }
+        }
+    }
+    if keys.is_empty() {
+        output::warning("No recipients configured.");
+        return Ok(());
+    }
+    let members = MembersFile::new(vaultic_dir).load()?;
+    let today = crate::cli::context::clock().now().date_naive();
+
+    output::header(&format!("Access review ({} recipient(s))", keys.len()));
+    let mut table = Table::new(vec![
+        Column::new("Key").shrink(20).middle(),
+        Column::new("Member").shrink(10),
+        Column::new("Lists").shrink(10),
+        Column::new("Expiry").shrink(10),
+    ]);
+    let mut expired: Vec<&str> = Vec::new();
+    let mut expiring = 0;
+    for (ki, lists) in &keys {
+        let member = members.get(&ki.public_key);
+        let expiry = match member.and_then(|m| Some((m.days_left(today)?, m.expiry_note(today)?))) {
+            Some((days, note)) if days < 0 => {
+                expired.push(&ki.public_key);
+                Cell::new(format!("✗ {note}")).color(Color::Red)
+            }
+            Some((days, note)) if days <= EXPIRY_NOTICE_DAYS => {
+                expiring += 1;
+                Cell::new(format!("⚠ {note}")).color(Color::Yellow)
+            }
+            Some((_, note)) => Cell::new(format!("✓ {note}")).color(Color::Green),
+            None => Cell::new("no expiry").dimmed(),
+        };
+        table.row(vec![
+            Cell::new(ki.public_key.as_str()),
+            Cell::new(members.describe(ki).unwrap_or_else(|| "—".into())),
+            Cell::new(lists.join(", ")),
+            expiry,
+        ]);
+    }
+    table.print();
+    println!();
+
+    if expiring > 0 {
+        output::warning(&format!(
+            "{expiring} recipient(s) expire within {EXPIRY_NOTICE_DAYS} days"
+        ));
+    }
+    if expired.is_empty() {
+        output::success("No recipient has expired");
+        return Ok(());
+    }
+    output::warning(&format!("{} recipient(s) have expired:", expired.len()));
+    for key in &expired {
+        println!("    vaultic keys remove {key}");
+    }
+    println!("\n  Then re-encrypt with 'vaultic encrypt --all' to revoke their access.");
+    Err(VaulticError::ValidationFailed {
+        count: expired.len(),
+    })
+}
+
+/// List the environments with a `threshold` and the shareholders of
+/// each, marking the shares this machine holds.
+fn execute_share_status() -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+    let config = crate::cli::context::config()?;
+    let envs: Vec<(&str, u8)> = config
+        .environment_names()
+        .into_iter()
+        .filter_map(|env| config.threshold(env).map(|t| (env, t)))
+        .collect();
+    if envs.is_empty() {
+        output::success("No environment has a threshold; any recipient can decrypt");
+        println!("\n  Set threshold = 2 under [environments.<env>] in config.toml to require two.");
+        return Ok(());
+    }
+
+    let own_keys: Vec<String> = crate::cli::context::identity_paths()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|p| AgeBackend::read_public_key(p).ok())
+        .collect();
+    let members = MembersFile::new(vaultic_dir).load()?;
+
+    for (env_name, threshold) in envs {
+        let enc_path = super::mirror_helpers::enc_path(vaultic_dir, config, env_name)?;
+        let file_name = config.env_file_name(env_name);
+        let envelope = match std::fs::read(&enc_path).ok().map(|c| Envelope::parse(&c)) {
+            None => {
+                output::warning(&format!("{env_name}: {file_name}.enc not found"));
+                continue;
+            }
+            Some(None) => {
+                output::warning(&format!(
+                    "{env_name}: any recipient can still decrypt {file_name}.enc. \
+                     Split it into shares with 'vaultic encrypt --env {env_name}'."
+                ));
+                continue;
+            }
+            Some(Some(envelope)) => envelope?,
+        };
+
+        output::header(&format!(
+            "{env_name}: {} of {} shareholder(s) needed",
+            envelope.threshold,
+            envelope.shares.len()
+        ));
+        let mut table = Table::new(vec![
+            Column::new("Share"),
+            Column::new("Key").shrink(20).middle(),
+            Column::new("Member").shrink(10),
+        ]);
+        for share in &envelope.shares {
+            let holder = KeyIdentity {
+                public_key: share.recipient.clone(),
+                label: None,
+                added_at: None,
+            };
+            let member = members.describe(&holder).unwrap_or_else(|| "—".into());
+            let mine = own_keys.contains(&share.recipient);
+            table.row(vec![
+                Cell::new(share.index.to_string()),
+                Cell::new(share.recipient.as_str()),
+                if mine {
+                    Cell::new(format!("{member} (you)")).color(Color::Green)
+                } else {
+                    Cell::new(member)
+                },
+            ]);
+        }
+        table.print();
+        if envelope.threshold != threshold {
+            output::warning(&format!(
+                "config.toml asks for {threshold} shares, but {file_name}.enc was encrypted \
+                 with {}. Re-encrypt it to apply the change.",
+                envelope.threshold
+            ));
+        }
+        println!();
+    }
+    Ok(())
+}
+
+/// Encrypt this machine's share of `env` to the shareholder `to`, writing
+/// it to `output_path` or `<env>.share-<index>.age`.
+fn execute_share_release(env: Option<&str>, to: &str, output_path: Option<&str>) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+    let config = crate::cli::context::config()?;
+    let env_name = &crate::cli::context::select_env(env, Some(config))?;
+    config.require_env(env_name)?;
+    if config.threshold(env_name).is_none() {
+        return Err(VaulticError::InvalidConfig {
+            detail: format!(
+                "'{env_name}' has no threshold in config.toml, so any recipient \
+                 can decrypt it without shares"
+            ),
+        });
+    }
+    super::crypto_helpers::check_recipients_signature(vaultic_dir, Some(config))?;
+
+    let enc_path = super::mirror_helpers::enc_path(vaultic_dir, config, env_name)?;
+    if !enc_path.exists() {
+        return Err(VaulticError::FileNotFound { path: enc_path });
+    }
+    let content = std::fs::read(&enc_path)?;
+    let envelope = match Envelope::parse(&content) {
+        Some(envelope) => envelope?,
+        None => {
+            return Err(VaulticError::InvalidConfig {
+                detail: format!(
+                    "{} is not split into shares yet. Re-encrypt it with \
+                     'vaultic encrypt --env {env_name}'.",
+                    enc_path.display()
+                ),
+            });
+        }
+    };
+    // Anyone else collecting enough shares could read the environment
+    if !envelope.shares.iter().any(|s| s.recipient == to) {
+        return Err(VaulticError::InvalidConfig {
+            detail: format!(
+                "{to} holds no share of {env_name}. Shares can only be released to its \
+                 shareholders; see 'vaultic keys share status'."
+            ),
+        });
+    }
+
+    let backend = ThresholdBackend::new(super::crypto_helpers::age_backend()?);
+    let receiver = KeyIdentity {
+        public_key: to.to_string(),
+        label: None,
+        added_at: None,
+    };
+    let (index, released) = backend.release(&content, &receiver)?;
+    let dest = output_path
+        .map(String::from)
+        .unwrap_or_else(|| format!("{env_name}.share-{index}.age"));
+    std::fs::write(&dest, released)?;
+
+    super::audit_helpers::log_audit(
+        AuditAction::ShareRelease,
+        vec![format!("{}.enc", config.env_file_name(env_name))],
+        Some(AuditDetail::ShareRelease {
+            index,
+            to: to.to_string(),
+        }),
     );
+    output::success(&format!("Released share {index} of {env_name} to {to}"));
+    println!("\n  Send {dest} to its holder; only their key opens it.");
+    println!("  They decrypt with: vaultic decrypt --env {env_name} --share {dest}");
+    Ok(())
+}
+
+/// Write the recipients of `recipients.txt` and their members as a JSON
+/// bundle to `output`, or to stdout.
+fn execute_export(output_path: Option<&str>) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
 
+    let store = FileKeyStore::new(vaultic_dir.join("recipients.txt"));
+    let keys = KeyService { store }.list_keys()?;
+    let members = MembersFile::new(vaultic_dir).load()?;
+    let bundle = RecipientBundle::new(&keys, &members, crate::cli::context::clock().now());
+
+    let Some(path) = output_path else {
+        return output::json(&bundle);
+    };
+    std::fs::write(path, format!("{}\n", output::to_json(&bundle)?))?;
+    output::success(&format!(
+        "Exported {} recipient(s) to {path}",
+        bundle.recipients.len()
+    ));
+    println!("\n  Sign it so importing projects can trust it:");
+    println!("    minisign -Sm {path} -x {path}.sig");
+    Ok(())
+}
+
+/// Add the recipients of a bundle made by `vaultic keys export` that are
+/// not in `recipients.txt` yet, with their members.
+///
+/// The bundle's `.sig` is checked against `[recipients] admin_keys` and
+/// `signers`; with neither, the import goes ahead with a warning.
+fn execute_import(bundle_path: &str, signers: &[String], dry_run: bool) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+
+    let path = Path::new(bundle_path);
+    if !path.exists() {
+        return Err(VaulticError::FileNotFound {
+            path: path.to_path_buf(),
+        });
+    }
+    let content = std::fs::read(path)?;
+    check_bundle_signature(path, &content, signers)?;
+    let bundle =
+        RecipientBundle::parse(&String::from_utf8_lossy(&content)).map_err(|e| match e {
+            VaulticError::ParseError { detail, .. } => VaulticError::ParseError {
+                file: path.to_path_buf(),
+                detail,
+            },
+            other => other,
+        })?;
+    for recipient in &bundle.recipients {
+        validate_recipient_key(&recipient.member.key)?;
+    }
+
+    let service = KeyService {
+        store: FileKeyStore::new(vaultic_dir.join("recipients.txt")),
+    };
+    let existing = service.list_keys()?;
+    let missing = bundle.missing_from(&existing);
+    let skipped = bundle.recipients.len() - missing.len();
+
+    output::header(&format!(
+        "Importing {} recipient(s) from {bundle_path}",
+        bundle.recipients.len()
+    ));
+    if missing.is_empty() {
+        output::success("Every recipient in the bundle is already in recipients.txt");
+        return Ok(());
+    }
+    if dry_run {
+        for recipient in &missing {
+            let who = recipient.member.describe().or(recipient.label.clone());
+            match who {
+                Some(who) => println!("  + {} ({who})", recipient.member.key),
+                None => println!("  + {}", recipient.member.key),
+            }
+        }
+        println!(
+            "\n  {} would be added, {skipped} already present. Nothing was changed (dry run).",
+            missing.len()
+        );
+        return Ok(());
+    }
+
+    let file = MembersFile::new(vaultic_dir);
+    let mut members = if file.exists() {
+        file.load()?
+    } else {
+        Members::from_labels(&existing)
+    };
+    let mut members_changed = false;
+    let now = crate::cli::context::clock().now();
+    for recipient in &missing {
+        let member = &recipient.member;
+        let ki = KeyIdentity {
+            public_key: member.key.clone(),
+            label: member.name.clone().or(recipient.label.clone()),
+            added_at: Some(now),
+        };
+        service.add_key(&ki)?;
+        if member.describe().is_some() || member.expires.is_some() {
+            members.upsert(member.clone());
+            members_changed = true;
+        }
+        match members.describe(&ki) {
+            Some(who) => output::success(&format!("Added recipient: {} ({who})", ki.public_key)),
+            None => output::success(&format!("Added recipient: {}", ki.public_key)),
+        }
+        super::audit_helpers::log_audit(
+            AuditAction::KeyAdd,
+            vec![],
+            Some(AuditDetail::KeyAdd {
+                key: ki.public_key.clone(),
+                label: ki.label.clone(),
+                email: member.email.clone(),
+                role: member.role.clone(),
+            }),
+        );
+    }
+    if members_changed {
+        file.save(&members)?;
+    }
+    super::audit_helpers::record_manifest(vaultic_dir);
+
+    if skipped > 0 {
+        println!("  {skipped} recipient(s) already present were skipped.");
+    }
+    println!("\n  Re-encrypt with 'vaultic encrypt --all' so the new recipients can decrypt.");
+    print_resign_hint();
     Ok(())
 }
 
+/// Verify `<bundle>.sig` against the project's admin keys and `signers`.
+fn check_bundle_signature(path: &Path, content: &[u8], signers: &[String]) -> Result<()> {
+    let mut keys: Vec<String> = crate::cli::context::config()
+        .ok()
+        .and_then(|c| c.recipients.as_ref())
+        .map(|r| r.admin_keys.clone())
+        .unwrap_or_default();
+    keys.extend(signers.iter().cloned());
+
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    if keys.is_empty() {
+        output::warning(&format!(
+            "Signature of {name} not checked: no [recipients] admin_keys or --signer given"
+        ));
+        return Ok(());
+    }
+    let mut sig_path = path.as_os_str().to_owned();
+    sig_path.push(".sig");
+    let signature =
+        std::fs::read(&sig_path).map_err(|_| VaulticError::RecipientsSignatureInvalid {
+            reason: format!("{name}.sig not found"),
+        })?;
+    recipients_signature::verify(&name, content, &signature, &keys)?;
+    output::success(&format!("Signature of {name} verified"));
+    Ok(())
+}
+
+/// Parse a `--expires` date (`YYYY-MM-DD`).
+fn parse_expires(date: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| VaulticError::InvalidConfig {
+        detail: format!(
+            "Invalid expiry date: '{date}'. Expected ISO 8601 (YYYY-MM-DD), e.g. 2026-12-31"
+        ),
+    })
+}
+
+/// Report recipients that are not on an allow-list (offboarding audit).
+///
+/// Fails with a validation error when any recipient is missing from the
+/// roster, so the check can gate a CI job.
+fn execute_verify_roster(roster_path: &str) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+
+    let path = Path::new(roster_path);
+    if !path.exists() {
+        return Err(VaulticError::FileNotFound {
+            path: path.to_path_buf(),
+        });
+    }
+    let roster = key_service::parse_roster(&std::fs::read_to_string(path)?);
+
+    let mut keys: Vec<KeyIdentity> = Vec::new();
+    for store in all_recipient_lists(vaultic_dir) {
+        for key in (KeyService { store }).list_keys()? {
+            if !keys.iter().any(|k| k.public_key == key.public_key) {
+                keys.push(key);
+            }
+        }
+    }
+    let missing = key_service::off_roster(&keys, &roster);
+
+    output::header(&format!(
+        "Roster check: {} recipient(s) against {roster_path} ({} entries)",
+        keys.len(),
+        roster.len()
+    ));
+
+    super::audit_helpers::log_audit(
+        AuditAction::Check,
+        vec!["recipients.txt".to_string()],
+        Some(AuditDetail::CheckRoster {
+            roster: roster_path.to_string(),
+            missing: missing.len(),
+        }),
+    );
+
+    if missing.is_empty() {
+        output::success("Every recipient is on the roster");
+        return Ok(());
+    }
+
+    output::warning(&format!(
+        "{} recipient(s) not on the roster:",
+        missing.len()
+    ));
+    let mut table = Table::new(vec![
+        Column::new("Key").shrink(20).middle(),
+        Column::new("Label").shrink(10),
+    ]);
+    for ki in &missing {
+        table.row(vec![
+            Cell::new(ki.public_key.as_str()).color(Color::Red),
+            Cell::new(ki.label.as_deref().unwrap_or("—")),
+        ]);
+    }
+    table.print();
+    println!("\n  Remove them with 'vaultic keys remove <key>', then 'vaultic encrypt --all'.");
+
+    Err(VaulticError::ValidationFailed {
+        count: missing.len(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -331,12 +1502,76 @@ mod tests {
         assert!(validate_recipient_key(&public_key).is_ok());
     }
 
+    #[test]
+    fn recipient_problem_flags_malformed_and_truncated_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = AgeBackend::generate_identity(&dir.path().join("keys.txt")).unwrap();
+        assert_eq!(recipient_problem(&key, None), None);
+
+        let truncated = recipient_problem(&key[..key.len() - 4], None).unwrap();
+        assert!(truncated.starts_with("invalid age key"), "{truncated}");
+        let mut typo = key.clone();
+        typo.replace_range(10..11, if &key[10..11] == "q" { "p" } else { "q" });
+        assert!(recipient_problem(&typo, None).is_some());
+
+        assert!(recipient_problem("ssh-ed25519 AAAAC3Nz", None).is_some());
+        assert_eq!(recipient_problem("0123456789ABCDEF", None), None);
+        assert_eq!(
+            recipient_problem("0123456789ABCDEF01234", None).as_deref(),
+            Some("truncated GPG fingerprint (21 hex digits)")
+        );
+        assert_eq!(recipient_problem("alice@example.com", None), None);
+        assert_eq!(
+            recipient_problem("not-a-key", None).as_deref(),
+            Some("unrecognized key format")
+        );
+    }
+
+    #[test]
+    fn render_qr_produces_square_block() {
+        let qr =
+            render_qr("age1ql3z7hjy54pw3hyww5ayyfg7zqgvc7w3j2elw8zmrj2kg5sfn9aqmcac8p").unwrap();
+        let lines: Vec<&str> = qr.lines().collect();
+        assert!(lines.len() > 10);
+        let width = lines[0].chars().count();
+        assert!(lines.iter().all(|l| l.chars().count() == width));
+    }
+
     #[test]
     fn validate_invalid_age_key() {
         let result = validate_recipient_key("age1invalidkey");
         assert!(result.is_err());
     }
 
+    const SSH_ED25519: &str =
+        "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHsKLqeplhpW+uObz5dvMgjz1OxfM/XXUB+VHtZ6isGN";
+
+    #[test]
+    fn validate_ssh_ed25519_key() {
+        assert!(validate_recipient_key(SSH_ED25519).is_ok());
+        assert!(validate_recipient_key(&format!("{SSH_ED25519} alice@laptop")).is_ok());
+    }
+
+    #[test]
+    fn validate_invalid_ssh_key() {
+        assert!(validate_recipient_key("ssh-ed25519 AAAAnotbase64!").is_err());
+        // An SSH key with an email comment must not pass as a GPG email
+        assert!(validate_recipient_key("ssh-ed25519 AAAA alice@laptop").is_err());
+    }
+
+    #[test]
+    fn split_key_comment_moves_ssh_comment_to_label() {
+        let (key, label) = split_key_comment(&format!("{SSH_ED25519} alice@laptop work"));
+        assert_eq!(key, SSH_ED25519);
+        assert_eq!(label.as_deref(), Some("alice@laptop work"));
+
+        assert_eq!(
+            split_key_comment(SSH_ED25519),
+            (SSH_ED25519.to_string(), None)
+        );
+        assert_eq!(split_key_comment("age1abc"), ("age1abc".to_string(), None));
+    }
+
     #[test]
     fn validate_gpg_email() {
         assert!(validate_recipient_key("user@example.com").is_ok());
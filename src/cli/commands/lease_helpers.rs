@@ -0,0 +1,239 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::Path;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread::JoinHandle;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::adapters::credential_providers::aws_sts::AwsStsProvider;
+use crate::adapters::credential_providers::command::CommandProvider;
+use crate::adapters::parsers::dotenv_parser::DotenvParser;
+use crate::cli::output;
+use crate::cli::signals::SensitiveFile;
+use crate::config::app_config::{AppConfig, LeaseProvider};
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::lease::Lease;
+use crate::core::models::secret_file::{Line, SecretEntry, SecretFile};
+use crate::core::traits::credential_provider::CredentialProvider;
+use crate::core::traits::parser::ConfigParser;
+
+/// How long to wait before asking again after a failed refresh.
+const RETRY_SECONDS: i64 = 30;
+
+/// A `[leases]` entry requested for the environment being run.
+pub struct ActiveLease {
+    provider: Box<dyn CredentialProvider>,
+    refresh_signal: Option<String>,
+    lease: Lease,
+    next_refresh: Option<DateTime<Utc>>,
+}
+
+impl ActiveLease {
+    pub fn name(&self) -> &str {
+        self.provider.name()
+    }
+}
+
+/// Request every lease in `[leases]` that applies to `env_name`, in name
+/// order. Fails on the first provider that cannot hand out credentials.
+pub fn acquire(config: &AppConfig, env_name: &str) -> Result<Vec<ActiveLease>> {
+    let Some(entries) = &config.leases else {
+        return Ok(Vec::new());
+    };
+    let mut names: Vec<&String> = entries.keys().collect();
+    names.sort();
+
+    let mut active = Vec::new();
+    for name in names {
+        let entry = &entries[name];
+        if !entry.applies_to(env_name) {
+            continue;
+        }
+        let provider: Box<dyn CredentialProvider> = match &entry.provider {
+            LeaseProvider::AwsSts {
+                role_arn,
+                duration_seconds,
+                region,
+            } => Box::new(AwsStsProvider {
+                name: name.clone(),
+                role_arn: role_arn.clone(),
+                duration_seconds: *duration_seconds,
+                region: region.clone(),
+            }),
+            LeaseProvider::Command {
+                command,
+                ttl_seconds,
+            } => Box::new(CommandProvider {
+                name: name.clone(),
+                command: command.clone(),
+                ttl_seconds: *ttl_seconds,
+            }),
+        };
+        let lease = provider.lease(Utc::now()).map_err(|e| match e {
+            VaulticError::InvalidConfig { detail } => VaulticError::InvalidConfig {
+                detail: format!("Lease '{name}' failed: {detail}"),
+            },
+            other => other,
+        })?;
+        output::detail(&format!(
+            "Leased {} variable(s) from '{name}'",
+            lease.vars.len()
+        ));
+        active.push(ActiveLease {
+            provider,
+            refresh_signal: entry.refresh_signal.clone(),
+            next_refresh: lease.refresh_at(),
+            lease,
+        });
+    }
+    Ok(active)
+}
+
+/// Variables of all leases; a later lease wins on a shared key.
+pub fn vars(leases: &[ActiveLease]) -> BTreeMap<String, String> {
+    leases.iter().flat_map(|l| l.lease.vars.clone()).collect()
+}
+
+/// Whether any lease expires, so the child needs a way to see new ones.
+pub fn refreshable(leases: &[ActiveLease]) -> bool {
+    leases.iter().any(|l| l.next_refresh.is_some())
+}
+
+/// Write the leased variables to a private file the child can re-read
+/// (`VAULTIC_LEASE_FILE`). Kept in memory-backed `/dev/shm` when
+/// available and removed when the guard is dropped.
+pub fn lease_file(leases: &[ActiveLease]) -> Result<SensitiveFile> {
+    let shm = Path::new("/dev/shm");
+    let builder = {
+        let mut b = tempfile::Builder::new();
+        b.prefix("vaultic-lease-").suffix(".env");
+        b
+    };
+    let file = if shm.is_dir() {
+        builder.tempfile_in(shm)?
+    } else {
+        builder.tempfile()?
+    };
+    let path = file.into_temp_path().keep().map_err(|e| e.error)?;
+    let guard = SensitiveFile::new(path);
+    write_lease_file(guard.path(), leases)?;
+    Ok(guard)
+}
+
+/// Replace the lease file atomically, so the child never reads half of it.
+fn write_lease_file(path: &Path, leases: &[ActiveLease]) -> Result<()> {
+    let lines = vars(leases)
+        .into_iter()
+        .enumerate()
+        .map(|(i, (key, value))| {
+            Line::Entry(SecretEntry {
+                key,
+                value,
+                comment: None,
+                line_number: i + 1,
+            })
+        })
+        .collect();
+    let mut content = DotenvParser.serialize(&SecretFile {
+        lines,
+        source_path: None,
+    })?;
+    if !content.ends_with('\n') {
+        content.push('\n');
+    }
+
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let mut tmp = tempfile::Builder::new()
+        .prefix(".vaultic-lease-")
+        .tempfile_in(dir)?;
+    tmp.write_all(content.as_bytes())?;
+    tmp.persist(path).map_err(|e| e.error)?;
+    Ok(())
+}
+
+/// Background thread renewing leases while the child runs.
+pub struct Refresher {
+    stop: Sender<()>,
+    handle: JoinHandle<()>,
+}
+
+impl Refresher {
+    /// Renew each lease once it is due, rewrite `file` and send the
+    /// lease's `refresh_signal` to process `pid`.
+    pub fn start(mut leases: Vec<ActiveLease>, file: SensitiveFile, pid: u32) -> Self {
+        let (stop, rx) = mpsc::channel();
+        let handle = std::thread::spawn(move || {
+            let _file = &file;
+            loop {
+                let Some(due) = leases.iter().filter_map(|l| l.next_refresh).min() else {
+                    let _ = rx.recv();
+                    return;
+                };
+                let wait = (due - Utc::now()).max(Duration::seconds(1));
+                match rx.recv_timeout(wait.to_std().unwrap_or_default()) {
+                    Err(RecvTimeoutError::Timeout) => {}
+                    _ => return,
+                }
+
+                let now = Utc::now();
+                let mut refreshed = false;
+                let mut signals = Vec::new();
+                for active in leases.iter_mut() {
+                    if active.next_refresh.is_none_or(|at| at > now) {
+                        continue;
+                    }
+                    match active.provider.lease(now) {
+                        Ok(lease) => {
+                            active.next_refresh = lease.refresh_at();
+                            active.lease = lease;
+                            refreshed = true;
+                            signals.extend(active.refresh_signal.clone());
+                        }
+                        Err(e) => {
+                            active.next_refresh = Some(now + Duration::seconds(RETRY_SECONDS));
+                            output::warning(&format!(
+                                "Could not refresh lease '{}', retrying in {RETRY_SECONDS}s: {e}",
+                                active.name()
+                            ));
+                        }
+                    }
+                }
+                if !refreshed {
+                    continue;
+                }
+                if let Err(e) = write_lease_file(file.path(), &leases) {
+                    output::warning(&format!("Could not update the lease file: {e}"));
+                    continue;
+                }
+                signals.sort();
+                signals.dedup();
+                for signal in signals {
+                    send_signal(&signal, pid);
+                }
+            }
+        });
+        Self { stop, handle }
+    }
+
+    /// Stop refreshing and remove the lease file.
+    pub fn stop(self) {
+        let _ = self.stop.send(());
+        let _ = self.handle.join();
+    }
+}
+
+#[cfg(unix)]
+fn send_signal(signal: &str, pid: u32) {
+    let signal = signal.trim_start_matches("SIG");
+    let sent = std::process::Command::new("kill")
+        .args(["-s", signal, &pid.to_string()])
+        .status()
+        .is_ok_and(|s| s.success());
+    if !sent {
+        output::warning(&format!("Could not send SIG{signal} to the child"));
+    }
+}
+
+#[cfg(not(unix))]
+fn send_signal(_signal: &str, _pid: u32) {}
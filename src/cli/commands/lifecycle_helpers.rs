@@ -0,0 +1,199 @@
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::cli::output;
+use crate::config::app_config::HooksSection;
+use crate::core::errors::{Result, VaulticError};
+
+/// Variables a hook inherits. Everything else, including any secret
+/// exported in the shell, is dropped.
+const PASSED_VARS: &[&str] = &[
+    "PATH",
+    "HOME",
+    "USER",
+    "LOGNAME",
+    "LANG",
+    "LC_ALL",
+    "TERM",
+    "TMPDIR",
+    "SYSTEMROOT",
+    "USERPROFILE",
+    "TEMP",
+    "TMP",
+];
+
+/// A point in a command where a `[hooks]` entry runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hook {
+    PreEncrypt,
+    PostEncrypt,
+    PostDecrypt,
+    PostResolve,
+}
+
+impl Hook {
+    /// Key in `[hooks]`, also passed to the hook as `VAULTIC_HOOK`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Hook::PreEncrypt => "pre_encrypt",
+            Hook::PostEncrypt => "post_encrypt",
+            Hook::PostDecrypt => "post_decrypt",
+            Hook::PostResolve => "post_resolve",
+        }
+    }
+
+    fn command(self, hooks: &HooksSection) -> Option<&str> {
+        match self {
+            Hook::PreEncrypt => hooks.pre_encrypt.as_deref(),
+            Hook::PostEncrypt => hooks.post_encrypt.as_deref(),
+            Hook::PostDecrypt => hooks.post_decrypt.as_deref(),
+            Hook::PostResolve => hooks.post_resolve.as_deref(),
+        }
+    }
+
+    fn is_pre(self) -> bool {
+        self == Hook::PreEncrypt
+    }
+}
+
+/// Run the `[hooks]` entry for `hook`, if the project has one, for
+/// `env_name` and the file the command reads or wrote.
+///
+/// The hook runs from the project root with stdin closed, its output on
+/// stderr so `--stdout` payloads stay clean, and only `PASSED_VARS`
+/// plus `VAULTIC_HOOK`, `VAULTIC_ENV`, `VAULTIC_FILE` and `VAULTIC_DIR`
+/// in its environment. It is stopped after `timeout_seconds`. A failing
+/// pre hook aborts the command; a failing post hook only warns, since
+/// the command's work is already done.
+pub fn run(hook: Hook, env_name: &str, file: &Path) -> Result<()> {
+    let Some(hooks) = crate::cli::context::config()
+        .ok()
+        .and_then(|c| c.hooks.as_ref())
+    else {
+        return Ok(());
+    };
+    let Some(command) = hook.command(hooks) else {
+        return Ok(());
+    };
+
+    match execute(hook, command, hooks.timeout_seconds, env_name, file) {
+        Ok(()) => Ok(()),
+        Err(reason) if hook.is_pre() => Err(VaulticError::InvalidConfig {
+            detail: format!(
+                "{} hook '{command}' {reason}\n\n  \
+                 Solutions:\n    \
+                 → Fix what the hook reported above and run the command again\n    \
+                 → Change or remove {} in the [hooks] section of .vaultic/config.toml",
+                hook.name(),
+                hook.name()
+            ),
+        }),
+        Err(reason) => {
+            output::warning(&format!("{} hook '{command}' {reason}", hook.name()));
+            Ok(())
+        }
+    }
+}
+
+/// Start `command` and wait for it. Returns why it failed, if it did.
+fn execute(
+    hook: Hook,
+    command: &str,
+    timeout_seconds: u64,
+    env_name: &str,
+    file: &Path,
+) -> std::result::Result<(), String> {
+    let words = crate::cli::aliases::split_words(command)
+        .map_err(|e| format!("is not a valid command: {e}"))?;
+    let Some((program, args)) = words.split_first() else {
+        return Err("is empty".into());
+    };
+
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    let vaultic_dir = std::fs::canonicalize(vaultic_dir).unwrap_or(vaultic_dir.to_path_buf());
+    let root = vaultic_dir
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    let file = std::path::absolute(file).unwrap_or(file.to_path_buf());
+
+    let mut cmd = Command::new(program_path(program, &root));
+    cmd.args(args)
+        .current_dir(&root)
+        .env_clear()
+        .envs(
+            PASSED_VARS
+                .iter()
+                .filter_map(|name| std::env::var_os(name).map(|value| (name, value))),
+        )
+        .env("VAULTIC_HOOK", hook.name())
+        .env("VAULTIC_ENV", env_name)
+        .env("VAULTIC_FILE", &file)
+        .env("VAULTIC_DIR", &vaultic_dir)
+        .stdin(Stdio::null())
+        .stdout(std::io::stderr())
+        .stderr(Stdio::inherit());
+    output::detail(&format!("Running {} hook: {command}", hook.name()));
+
+    let mut child = cmd.spawn().map_err(|e| format!("could not start: {e}"))?;
+    let status = wait(&mut child, Duration::from_secs(timeout_seconds))
+        .map_err(|e| format!("could not be waited for: {e}"))?
+        .ok_or_else(|| format!("timed out after {timeout_seconds}s and was stopped"))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "failed with exit code {}",
+            super::run::exit_code(status)
+        ))
+    }
+}
+
+/// `program` as written for a bare name looked up on `PATH`, or joined
+/// onto the project root when it is a relative path such as
+/// `./scripts/lint-env.sh`.
+fn program_path(program: &str, root: &Path) -> PathBuf {
+    let path = Path::new(program);
+    if path.is_relative() && path.components().count() > 1 {
+        root.join(path)
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// Wait up to `timeout` for `child`, killing it after that. `None` means
+/// it timed out.
+fn wait(child: &mut Child, timeout: Duration) -> std::io::Result<Option<ExitStatus>> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if Instant::now() >= deadline {
+            child.kill()?;
+            child.wait()?;
+            return Ok(None);
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_programs_resolve_against_the_project_root() {
+        let root = Path::new("/project");
+        assert_eq!(
+            program_path("./scripts/lint.sh", root),
+            root.join("./scripts/lint.sh")
+        );
+        assert_eq!(
+            program_path("scripts/lint.sh", root),
+            root.join("scripts/lint.sh")
+        );
+        assert_eq!(program_path("make", root), PathBuf::from("make"));
+    }
+}
@@ -1,9 +1,9 @@
 use chrono::{NaiveDate, TimeZone, Utc};
-use colored::Colorize;
+use colored::Color;
 
 use crate::adapters::audit::json_audit_logger::JsonAuditLogger;
 use crate::cli::output;
-use crate::config::app_config::AppConfig;
+use crate::cli::table::{Cell, Column, Table};
 use crate::core::errors::{Result, VaulticError};
 use crate::core::models::audit_entry::{AuditAction, AuditEntry};
 use crate::core::traits::audit::AuditLogger;
@@ -11,16 +11,15 @@ use crate::core::traits::audit::AuditLogger;
 /// Execute the `vaultic log` command.
 ///
 /// Displays the audit log with optional filters for author, date,
-/// and entry count.
+/// and entry count. With `--json`, prints the matching entries as a
+/// JSON array in the same shape as the audit log lines.
 pub fn execute(author: Option<&str>, since: Option<&str>, last: Option<usize>) -> Result<()> {
     let vaultic_dir = crate::cli::context::vaultic_dir();
     if !vaultic_dir.exists() {
-        return Err(VaulticError::InvalidConfig {
-            detail: "Vaultic not initialized. Run 'vaultic init' first.".into(),
-        });
+        return Err(crate::cli::first_run::not_initialized());
     }
 
-    let config = AppConfig::load(vaultic_dir)?;
+    let config = crate::cli::context::config()?;
     let audit_section = config.audit.as_ref();
     let logger = JsonAuditLogger::from_config(vaultic_dir, audit_section);
 
@@ -29,7 +28,7 @@ pub fn execute(author: Option<&str>, since: Option<&str>, last: Option<usize>) -
 
     let entries = logger.query(author, since_dt)?;
 
-    if entries.is_empty() {
+    if entries.is_empty() && !output::is_json_mode() {
         output::header("vaultic log");
         output::warning("No audit entries found");
         if author.is_some() || since.is_some() {
@@ -51,12 +50,26 @@ pub fn execute(author: Option<&str>, since: Option<&str>, last: Option<usize>) -
         None => entries.iter().collect(),
     };
 
+    if output::is_json_mode() {
+        return output::json(&display);
+    }
+
     output::header(&format!("vaultic log ({} entries)", display.len()));
     println!();
 
+    let mut table = Table::new(vec![
+        Column::new("Date"),
+        Column::new("Author").shrink(6),
+        Column::new("Action"),
+        Column::new("Files").shrink(10),
+        Column::new("Detail").shrink(10),
+    ])
+    .separator(" │ ")
+    .headerless();
     for entry in &display {
-        print_entry(entry);
+        table.row(entry_row(entry));
     }
+    table.print();
 
     Ok(())
 }
@@ -72,58 +85,60 @@ fn parse_since(s: &str) -> Result<chrono::DateTime<Utc>> {
         .map(|d| Utc.from_utc_datetime(&d.and_hms_opt(0, 0, 0).expect("midnight is always valid")))
 }
 
-/// Print a single audit entry as a formatted row.
-fn print_entry(entry: &AuditEntry) {
-    let date = entry.timestamp.format("%Y-%m-%d %H:%M:%S");
-    let author = truncate_author(&entry.author, 10);
-    let action = format_action(&entry.action);
+/// Build the table row for a single audit entry.
+fn entry_row(entry: &AuditEntry) -> Vec<Cell> {
     let files = if entry.files.is_empty() {
-        "—".dimmed().to_string()
+        Cell::new("—").dimmed()
     } else {
-        entry.files.join(", ")
+        Cell::new(entry.files.join(", "))
     };
-    let detail = entry.detail.as_deref().unwrap_or("").dimmed().to_string();
-    let sep = "│".dimmed();
-
-    println!(
-        "  {} {sep} {:<10} {sep} {:<10} {sep} {} {}",
-        date.to_string().dimmed(),
-        author,
-        action,
+    vec![
+        Cell::new(entry.timestamp.format("%Y-%m-%d %H:%M:%S").to_string()).dimmed(),
+        Cell::new(entry.author.as_str()),
+        format_action(&entry.action),
         files,
-        detail,
-    );
-}
-
-/// Truncate an author name for display.
-fn truncate_author(author: &str, max_len: usize) -> String {
-    let char_count = author.chars().count();
-    if char_count <= max_len {
-        author.to_string()
-    } else {
-        author
-            .chars()
-            .take(max_len.saturating_sub(1))
-            .collect::<String>()
-            + "…"
-    }
+        Cell::new(
+            entry
+                .detail
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+        )
+        .dimmed(),
+    ]
 }
 
-/// Format an AuditAction as a colored string.
-fn format_action(action: &AuditAction) -> String {
+/// Format an AuditAction as a colored cell.
+fn format_action(action: &AuditAction) -> Cell {
     match action {
-        AuditAction::Init => "init".cyan().to_string(),
-        AuditAction::Encrypt => "encrypt".green().to_string(),
-        AuditAction::Decrypt => "decrypt".blue().to_string(),
-        AuditAction::KeyAdd => "key add".green().to_string(),
-        AuditAction::KeyRemove => "key rm".red().to_string(),
-        AuditAction::Check => "check".yellow().to_string(),
-        AuditAction::Diff => "diff".yellow().to_string(),
-        AuditAction::Resolve => "resolve".blue().to_string(),
-        AuditAction::HookInstall => "hook +".green().to_string(),
-        AuditAction::HookUninstall => "hook -".red().to_string(),
-        AuditAction::TemplateSync => "tmpl sync".cyan().to_string(),
-        AuditAction::Validate => "validate".yellow().to_string(),
-        AuditAction::CiExport => "ci export".blue().to_string(),
+        AuditAction::Init => Cell::new("init").color(Color::Cyan),
+        AuditAction::Encrypt => Cell::new("encrypt").color(Color::Green),
+        AuditAction::Decrypt => Cell::new("decrypt").color(Color::Blue),
+        AuditAction::DecryptFailed => Cell::new("decrypt ✗").color(Color::Red),
+        AuditAction::KeyAdd => Cell::new("key add").color(Color::Green),
+        AuditAction::KeyRemove => Cell::new("key rm").color(Color::Red),
+        AuditAction::KeyRotate => Cell::new("key rot").color(Color::Yellow),
+        AuditAction::Check => Cell::new("check").color(Color::Yellow),
+        AuditAction::Diff => Cell::new("diff").color(Color::Yellow),
+        AuditAction::Resolve => Cell::new("resolve").color(Color::Blue),
+        AuditAction::HookInstall => Cell::new("hook +").color(Color::Green),
+        AuditAction::HookUninstall => Cell::new("hook -").color(Color::Red),
+        AuditAction::HookBypass => Cell::new("hook skip").color(Color::Red),
+        AuditAction::TemplateSync => Cell::new("tmpl sync").color(Color::Cyan),
+        AuditAction::Validate => Cell::new("validate").color(Color::Yellow),
+        AuditAction::CiExport => Cell::new("ci export").color(Color::Blue),
+        AuditAction::ArchiveCreate => Cell::new("archive +").color(Color::Cyan),
+        AuditAction::ArchiveRestore => Cell::new("archive ⟲").color(Color::Cyan),
+        AuditAction::BundleCreate => Cell::new("bundle").color(Color::Yellow),
+        AuditAction::Run => Cell::new("run").color(Color::Blue),
+        AuditAction::Prune => Cell::new("prune").color(Color::Red),
+        AuditAction::Move => Cell::new("move").color(Color::Cyan),
+        AuditAction::Sync => Cell::new("sync").color(Color::Blue),
+        AuditAction::SnapshotCreate => Cell::new("snap +").color(Color::Cyan),
+        AuditAction::SnapshotRestore => Cell::new("snap ⟲").color(Color::Yellow),
+        AuditAction::Promote => Cell::new("promote").color(Color::Green),
+        AuditAction::ManifestAccept => Cell::new("manifest ✓").color(Color::Yellow),
+        AuditAction::ShareRelease => Cell::new("share ↗").color(Color::Yellow),
+        AuditAction::ArmorRepair => Cell::new("repair").color(Color::Yellow),
     }
 }
@@ -0,0 +1,74 @@
+use std::path::{Path, PathBuf};
+
+use crate::adapters::updater::github_updater;
+use crate::cli::output;
+use crate::config::app_config::AppConfig;
+use crate::config::path_expand::expand_path;
+use crate::core::errors::{Result, VaulticError};
+
+/// Directory under `.vaultic/` keeping the last copy fetched for each
+/// URL mirror, used when the upstream cannot be reached.
+const CACHE_DIR: &str = "mirrors";
+
+/// The encrypted file to read for `name`: `.vaultic/<name>.env.enc`, or
+/// the upstream copy when the environment is a mirror.
+///
+/// Path mirrors (usually a git submodule) are read in place. URL mirrors
+/// are downloaded on every call into `.vaultic/mirrors/`; when the
+/// download fails, the last fetched copy is used with a warning.
+pub fn enc_path(vaultic_dir: &Path, config: &AppConfig, name: &str) -> Result<PathBuf> {
+    let Some(origin) = config.mirror(name) else {
+        return Ok(vaultic_dir.join(format!("{name}.env.enc")));
+    };
+
+    if origin.starts_with("http://") {
+        return Err(VaulticError::InvalidConfig {
+            detail: format!("Mirror for '{name}' must use https: {origin}"),
+        });
+    }
+    let path = local_copy(vaultic_dir, name, origin)?;
+    if !origin.starts_with("https://") {
+        if !path.exists() {
+            return Err(VaulticError::InvalidConfig {
+                detail: format!(
+                    "Mirror for '{name}' not found at {}\n\n  \
+                     If it lives in a submodule, check it out: git submodule update --init",
+                    path.display()
+                ),
+            });
+        }
+        return Ok(path);
+    }
+
+    match github_updater::download_bytes(origin) {
+        Ok(bytes) => {
+            std::fs::create_dir_all(vaultic_dir.join(CACHE_DIR))?;
+            std::fs::write(&path, bytes)?;
+        }
+        Err(e) => {
+            let reason = match e {
+                VaulticError::UpdateFailed { reason } => reason,
+                other => other.to_string(),
+            };
+            if !path.exists() {
+                return Err(VaulticError::InvalidConfig {
+                    detail: format!("Failed to fetch mirror for '{name}' from {origin}: {reason}"),
+                });
+            }
+            output::warning(&format!(
+                "Could not refresh mirror for '{name}' ({reason}) — using the last fetched copy"
+            ));
+        }
+    }
+    Ok(path)
+}
+
+/// Where a mirror is read from on this machine, without fetching: the
+/// expanded path, or the cached download for a URL.
+pub fn local_copy(vaultic_dir: &Path, name: &str, origin: &str) -> Result<PathBuf> {
+    if origin.starts_with("https://") {
+        Ok(vaultic_dir.join(CACHE_DIR).join(format!("{name}.env.enc")))
+    } else {
+        expand_path(origin, Path::new("."))
+    }
+}
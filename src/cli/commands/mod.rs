@@ -1,16 +1,48 @@
+pub mod access_helpers;
+pub mod archive;
 pub mod audit_helpers;
+pub mod bundle;
 pub mod check;
 pub mod ci;
+pub mod clipboard_helpers;
+pub mod config;
 pub mod crypto_helpers;
 pub mod decrypt;
 pub mod diff;
+pub mod edit;
 pub mod encrypt;
+pub mod env;
+pub mod external;
+pub mod get;
+pub mod guide;
+pub mod history;
 pub mod hook;
+pub mod identity_helpers;
+pub mod import;
 pub mod init;
+pub mod junit_helpers;
 pub mod keys;
+pub mod lease_helpers;
+pub mod lifecycle_helpers;
 pub mod log;
+pub mod mirror_helpers;
+pub mod promote;
+pub mod prune;
+pub mod reference_helpers;
+pub mod relocate;
+pub mod repair_armor;
+pub mod report;
 pub mod resolve;
+pub mod rollback;
+pub mod run;
+pub mod self_cmd;
+pub mod set;
+pub mod snapshot;
 pub mod status;
+pub mod sync;
 pub mod template;
+pub mod test_env;
 pub mod update;
+pub mod upgrade_config;
 pub mod validate;
+pub mod verify;
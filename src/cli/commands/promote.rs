@@ -0,0 +1,159 @@
+use std::collections::HashSet;
+
+use crate::adapters::key_stores::file_key_store::FileKeyStore;
+use crate::cli::commands::crypto_helpers;
+use crate::cli::output;
+use crate::config::app_config::AppConfig;
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::audit_entry::{AuditAction, AuditDetail};
+use crate::core::models::secret_file::{Line, SecretFile};
+use crate::core::services::diff_service::DiffService;
+use crate::core::services::env_resolver::EnvResolver;
+use crate::core::services::key_filter::KeyFilter;
+
+/// Execute the `vaultic promote` command.
+///
+/// Resolves `from` (inheritance applied) and copies the chosen keys whose
+/// value differs in `to` into `to`'s own encrypted file. Keys are picked
+/// with `keys` globs or interactively. A preview of the change, showing
+/// value shapes only, is confirmed before `to` is re-encrypted.
+pub fn execute(
+    from: &str,
+    to: &str,
+    keys: Option<&str>,
+    cipher: &str,
+    note: Option<&str>,
+    force: bool,
+) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+
+    let config = crate::cli::context::config()?;
+    config.require_env(from)?;
+    config.require_env(to)?;
+    config.require_writable(to)?;
+    if from == to {
+        return Err(VaulticError::InvalidConfig {
+            detail: format!("Cannot promote '{from}' into itself"),
+        });
+    }
+    crypto_helpers::check_recipients_signature(vaultic_dir, Some(config))?;
+
+    let source = resolve(from, config, cipher)?;
+    let target = resolve(to, config, cipher)?;
+
+    let candidates: Vec<&str> = source
+        .entries()
+        .filter(|e| target.get(&e.key) != Some(e.value.as_str()))
+        .map(|e| e.key.as_str())
+        .collect();
+    if candidates.is_empty() {
+        output::success(&format!("{to} already has every value of {from}"));
+        return Ok(());
+    }
+
+    let selected: HashSet<&str> = match keys {
+        Some(patterns) => {
+            let filter = KeyFilter::new(Some(patterns), None);
+            candidates
+                .into_iter()
+                .filter(|key| filter.allows(key))
+                .collect()
+        }
+        None => {
+            let prompt = format!("Keys to promote from {from} into {to}");
+            let Some(chosen) = output::multi_select(&prompt, &candidates)? else {
+                return Err(VaulticError::InvalidConfig {
+                    detail: format!(
+                        "No keys selected.\n\n  \
+                         Choose them with --keys when not running in a terminal:\n    \
+                         → vaultic promote {from} {to} --keys \"DB_*,API_URL\""
+                    ),
+                });
+            };
+            chosen.into_iter().map(|i| candidates[i]).collect()
+        }
+    };
+    if selected.is_empty() {
+        output::warning("No keys to promote — nothing changed.");
+        return Ok(());
+    }
+
+    let only = |file: &SecretFile| SecretFile {
+        lines: file
+            .lines
+            .iter()
+            .filter(|line| matches!(line, Line::Entry(e) if selected.contains(e.key.as_str())))
+            .cloned()
+            .collect(),
+        source_path: None,
+    };
+    let promoted = only(&source);
+    let current = only(&target);
+
+    output::header(&format!("Promoting {from} → {to}"));
+    let result = DiffService.diff(&current, &promoted, to, from)?;
+    super::diff::report(
+        &current,
+        &promoted,
+        &result,
+        super::diff::DiffView::default(),
+        &format!("{to} already matches {from}"),
+    )?;
+    let count = promoted.keys().len();
+    if !output::confirm(
+        &format!("Promote {count} key(s) from {from} into {to}?"),
+        false,
+    )? {
+        output::warning("Nothing promoted.");
+        return Ok(());
+    }
+
+    // Write into the target's own layer, keeping its other values
+    let enc_path = vaultic_dir.join(format!("{}.enc", config.env_file_name(to)));
+    let overlay = if enc_path.exists() {
+        crypto_helpers::decrypt_in_memory(&enc_path, vaultic_dir, cipher)?
+    } else {
+        Vec::new()
+    };
+    let overlay = String::from_utf8(overlay).map_err(|_| VaulticError::ParseError {
+        file: enc_path.clone(),
+        detail: "Decrypted content is not valid UTF-8".into(),
+    })?;
+    let (updated, _) = super::import::merge(&overlay, &promoted, config.env_format(to), false)?;
+
+    let default_note = format!("promote {count} key(s) from {from}");
+    let key_store = FileKeyStore::new(vaultic_dir.join("recipients.txt")).for_env(to);
+    super::encrypt::encrypt_bytes_to(
+        updated.as_bytes(),
+        &enc_path,
+        to,
+        cipher,
+        &key_store,
+        Some(note.unwrap_or(&default_note)),
+        force,
+    )?;
+
+    let keys = promoted.keys().into_iter().map(String::from).collect();
+    super::audit_helpers::log_audit(
+        AuditAction::Promote,
+        vec![from.to_string(), to.to_string()],
+        Some(AuditDetail::Promote { keys }),
+    );
+
+    output::success(&format!("Promoted {count} key(s) from {from} into {to}"));
+    println!("\n  Commit {} to the repo.", enc_path.display());
+
+    Ok(())
+}
+
+/// Decrypt and resolve `env_name` in memory.
+fn resolve(env_name: &str, config: &AppConfig, cipher: &str) -> Result<SecretFile> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    let resolver = EnvResolver;
+    let chain = resolver.build_chain(env_name, config)?;
+    let files = crypto_helpers::load_env_files(&chain, vaultic_dir, cipher, config, false)?;
+    Ok(resolver.resolve(env_name, config, &files)?.resolved)
+}
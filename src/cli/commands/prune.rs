@@ -0,0 +1,130 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::cli::output;
+use crate::config::app_config::AppConfig;
+use crate::core::errors::Result;
+use crate::core::models::audit_entry::{AuditAction, AuditDetail};
+
+/// Execute the `vaultic prune` command.
+///
+/// Lists `.vaultic/*.enc` files that no configured environment refers to
+/// and deletes them after confirmation, logging one audit entry per file.
+pub fn execute() -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+
+    let config = crate::cli::context::config()?;
+    let orphans = orphaned_files(vaultic_dir, config)?;
+
+    if orphans.is_empty() {
+        output::success("No orphaned encrypted files");
+        return Ok(());
+    }
+
+    output::header(&format!("Orphaned encrypted files ({})", orphans.len()));
+    for path in &orphans {
+        println!("  • {}", file_name(path));
+    }
+    println!();
+
+    if !output::confirm(
+        &format!(
+            "Delete {} file(s) from {}?",
+            orphans.len(),
+            vaultic_dir.display()
+        ),
+        false,
+    )? {
+        output::warning("Nothing deleted.");
+        return Ok(());
+    }
+
+    for path in &orphans {
+        std::fs::remove_file(path)?;
+        let name = file_name(path);
+        output::success(&format!("Removed {name}"));
+        super::audit_helpers::log_audit(
+            AuditAction::Prune,
+            vec![name],
+            Some(AuditDetail::Prune {
+                reason: "environment not in config.toml".into(),
+            }),
+        );
+    }
+    println!("\n  Commit the removal to the repo.");
+
+    Ok(())
+}
+
+/// Encrypted files in `vaultic_dir` not referenced by any environment,
+/// sorted by name.
+///
+/// An environment refers to both `{name}.env.enc` and, when it sets a
+/// custom `file`, `{file}.enc`.
+fn orphaned_files(vaultic_dir: &Path, config: &AppConfig) -> Result<Vec<PathBuf>> {
+    let referenced: HashSet<String> = config
+        .environments
+        .keys()
+        .flat_map(|env| {
+            [
+                format!("{env}.env.enc"),
+                format!("{}.enc", config.env_file_name(env)),
+            ]
+        })
+        .collect();
+
+    let mut orphans: Vec<PathBuf> = std::fs::read_dir(vaultic_dir)?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "enc"))
+        .filter(|p| !referenced.contains(&file_name(p)))
+        .collect();
+    orphans.sort();
+    Ok(orphans)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orphaned_files_skips_configured_environments() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = AppConfig::parse(
+            r#"
+[vaultic]
+version = "1.0.0"
+default_cipher = "age"
+
+[environments]
+dev = {}
+prod = { file = "production.env" }
+"#,
+        )
+        .unwrap();
+        for name in [
+            "dev.env.enc",
+            "production.env.enc",
+            "qa.env.enc",
+            "legacy.env.enc",
+            "recipients.txt",
+        ] {
+            std::fs::write(dir.path().join(name), "x").unwrap();
+        }
+
+        let names: Vec<String> = orphaned_files(dir.path(), &config)
+            .unwrap()
+            .iter()
+            .map(|p| file_name(p))
+            .collect();
+        assert_eq!(names, vec!["legacy.env.enc", "qa.env.enc"]);
+    }
+}
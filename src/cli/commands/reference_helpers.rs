@@ -0,0 +1,56 @@
+use crate::adapters::secret_providers::onepassword::OnePasswordProvider;
+use crate::adapters::secret_providers::vault::VaultProvider;
+use crate::cli::output;
+use crate::config::app_config::AppConfig;
+use crate::core::errors::Result;
+use crate::core::models::secret_file::SecretFile;
+use crate::core::services::reference_service::ReferenceService;
+use crate::core::traits::secret_provider::SecretProvider;
+
+/// What `--materialize` / `--keep-refs` ask for; `None` when neither is
+/// given and `[references] materialize` decides.
+pub fn mode(materialize: bool, keep_refs: bool) -> Option<bool> {
+    if materialize {
+        Some(true)
+    } else if keep_refs {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// `file` with its `op://` and `vault://` values fetched from the
+/// providers in `[references]`, or unchanged when references are kept.
+pub fn apply(
+    file: SecretFile,
+    materialize: Option<bool>,
+    config: &AppConfig,
+) -> Result<SecretFile> {
+    let section = config.references.clone().unwrap_or_default();
+    let count = ReferenceService::references(&file).len();
+    if count == 0 {
+        return Ok(file);
+    }
+    if !materialize.unwrap_or(section.materialize) {
+        output::detail(&format!(
+            "Kept {count} reference value(s) as is (--materialize fetches them)"
+        ));
+        return Ok(file);
+    }
+
+    let mut providers: Vec<Box<dyn SecretProvider>> = Vec::new();
+    if let Some(vault) = section.vault {
+        providers.push(Box::new(VaultProvider {
+            address: vault.address,
+            namespace: vault.namespace,
+        }));
+    }
+    if let Some(op) = section.op {
+        providers.push(Box::new(OnePasswordProvider {
+            account: op.account,
+        }));
+    }
+    let (resolved, fetched) = ReferenceService { providers }.materialize(&file)?;
+    output::detail(&format!("Fetched {fetched} referenced value(s)"));
+    Ok(resolved)
+}
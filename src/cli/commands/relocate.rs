@@ -0,0 +1,236 @@
+use std::path::{Path, PathBuf};
+
+use colored::Color;
+
+use crate::cli::commands::crypto_helpers;
+use crate::cli::output;
+use crate::cli::table::{Cell, Column, Table};
+use crate::config::app_config::AppConfig;
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::audit_entry::{AuditAction, AuditDetail};
+
+/// Execute the `vaultic move` command.
+///
+/// Copies the project's `.vaultic/` into another directory, rewrites
+/// absolute config paths that pointed into the old project, checks that
+/// the local key can still decrypt every environment there, and records
+/// the move in both audit logs. The source project is left untouched.
+pub fn execute(to: &Path, cipher: &str) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+    let config = crate::cli::context::config()?;
+
+    if !to.is_dir() {
+        return Err(VaulticError::FileNotFound {
+            path: to.to_path_buf(),
+        });
+    }
+    let old_root = project_root(vaultic_dir)?;
+    let new_root = to.canonicalize()?;
+    if old_root == new_root {
+        return Err(VaulticError::InvalidConfig {
+            detail: "The destination is the current project.".into(),
+        });
+    }
+
+    let dir_name = vaultic_dir.file_name().unwrap_or(".vaultic".as_ref());
+    let dest_dir = new_root.join(dir_name);
+    if dest_dir.exists() {
+        return Err(VaulticError::InvalidConfig {
+            detail: format!(
+                "{} already exists\n\n  \
+                 Solutions:\n    \
+                 → Remove it first if it is left over from an earlier attempt\n    \
+                 → Choose a destination that is not a Vaultic project yet",
+                dest_dir.display()
+            ),
+        });
+    }
+
+    output::header(&format!("Moving Vaultic project to {}", new_root.display()));
+    let copied = copy_dir(vaultic_dir, &dest_dir)?;
+    output::success(&format!(
+        "Copied {copied} file(s) to {}",
+        dest_dir.display()
+    ));
+
+    let config_path = dest_dir.join("config.toml");
+    let content = std::fs::read_to_string(&config_path)?;
+    let (content, rewritten) = rewrite_paths(
+        &content,
+        &old_root.to_string_lossy(),
+        &new_root.to_string_lossy(),
+    );
+    if rewritten > 0 {
+        std::fs::write(&config_path, content)?;
+        super::audit_helpers::record_manifest(&dest_dir);
+        output::success(&format!("Rewrote {rewritten} path(s) in config.toml"));
+    }
+
+    super::init::add_to_gitignore(&new_root, ".env")?;
+
+    super::audit_helpers::log_audit(
+        AuditAction::Move,
+        vec![],
+        Some(AuditDetail::MovedTo {
+            path: new_root.display().to_string(),
+        }),
+    );
+    super::audit_helpers::log_audit_in(
+        &dest_dir,
+        AuditAction::Move,
+        vec![],
+        Some(AuditDetail::MovedFrom {
+            path: old_root.display().to_string(),
+        }),
+        None,
+    );
+
+    let denied = check_access(&dest_dir, config, cipher);
+
+    println!();
+    if denied > 0 {
+        output::warning(&format!(
+            "Your key cannot decrypt {denied} environment(s) in the new location. \
+             Ask a recipient to run 'vaultic encrypt --all' there."
+        ));
+    }
+    println!("  Next steps:");
+    println!("    cd {}", new_root.display());
+    println!("    vaultic status");
+    println!("    git add {} .gitignore", dir_name.to_string_lossy());
+    println!(
+        "\n  The original {} was kept; delete it once the new repo is committed.",
+        vaultic_dir.display()
+    );
+
+    Ok(())
+}
+
+/// Absolute path of the directory that contains `vaultic_dir`.
+fn project_root(vaultic_dir: &Path) -> Result<PathBuf> {
+    let parent = vaultic_dir
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    Ok(parent.canonicalize()?)
+}
+
+/// Recursively copy `src` into a new directory `dest`, returning the
+/// number of files copied.
+fn copy_dir(src: &Path, dest: &Path) -> Result<usize> {
+    std::fs::create_dir_all(dest)?;
+    let mut copied = 0;
+    for entry in std::fs::read_dir(src)? {
+        let entry = entry?;
+        let target = dest.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copied += copy_dir(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), &target)?;
+            copied += 1;
+        }
+    }
+    Ok(copied)
+}
+
+/// Replace `old_root` with `new_root` at the start of every quoted path in
+/// `content`, returning the new text and the number of paths changed.
+///
+/// Only whole path prefixes match: `/src/app` rewrites `"/src/app/x"` but
+/// not `"/src/app2/x"`. Relative and `${VAULTIC_PROJECT_ROOT}` paths move
+/// with the project and need no rewrite.
+fn rewrite_paths(content: &str, old_root: &str, new_root: &str) -> (String, usize) {
+    let needle = format!("\"{old_root}");
+    let mut out = String::with_capacity(content.len());
+    let mut count = 0;
+    let mut rest = content;
+
+    while let Some(pos) = rest.find(&needle) {
+        let after = &rest[pos + needle.len()..];
+        out.push_str(&rest[..pos]);
+        if after.starts_with(['/', '\\', '"']) {
+            out.push('"');
+            out.push_str(new_root);
+            count += 1;
+        } else {
+            out.push_str(&needle);
+        }
+        rest = after;
+    }
+    out.push_str(rest);
+    (out, count)
+}
+
+/// Try to decrypt every environment in `dest_dir` with the local key and
+/// print the result. Returns how many could not be decrypted.
+fn check_access(dest_dir: &Path, config: &AppConfig, cipher: &str) -> usize {
+    let mut env_names: Vec<&String> = config.environments.keys().collect();
+    env_names.sort();
+
+    output::header("Key access in the new location");
+    let mut table = Table::new(vec![Column::new("Environment"), Column::new("Access")]);
+    let mut denied = 0;
+    for env_name in env_names {
+        let enc_path = dest_dir.join(format!("{}.enc", config.env_file_name(env_name)));
+        let status = if !enc_path.exists() {
+            Cell::new("not encrypted").dimmed()
+        } else {
+            let decrypted = std::fs::read(&enc_path)
+                .map_err(VaulticError::from)
+                .and_then(|c| crypto_helpers::decrypt_bytes(&c, cipher));
+            match decrypted {
+                Ok(_) => Cell::new("✓ ok").color(Color::Green),
+                Err(_) => {
+                    denied += 1;
+                    Cell::new("✗ cannot decrypt").color(Color::Red)
+                }
+            }
+        };
+        table.row(vec![Cell::new(env_name.as_str()), status]);
+    }
+    table.print();
+    denied
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewrite_paths_replaces_whole_prefixes_only() {
+        let config = r#"
+[vaultic]
+template = "/work/app/.env.template"
+identity = "/work/app2/keys.txt"
+
+[environments]
+dev = { template = "/work/app" }
+prod = { template = "templates/prod.template" }
+"#;
+        let (rewritten, count) = rewrite_paths(config, "/work/app", "/repos/api");
+        assert_eq!(count, 2);
+        assert!(rewritten.contains(r#"template = "/repos/api/.env.template""#));
+        assert!(rewritten.contains(r#"identity = "/work/app2/keys.txt""#));
+        assert!(rewritten.contains(r#"dev = { template = "/repos/api" }"#));
+        assert!(rewritten.contains(r#""templates/prod.template""#));
+    }
+
+    #[test]
+    fn copy_dir_copies_nested_files() {
+        let src = tempfile::tempdir().unwrap();
+        let dest = tempfile::tempdir().unwrap();
+        std::fs::write(src.path().join("config.toml"), "x").unwrap();
+        std::fs::create_dir(src.path().join("nested")).unwrap();
+        std::fs::write(src.path().join("nested/a.txt"), "y").unwrap();
+
+        let target = dest.path().join(".vaultic");
+        assert_eq!(copy_dir(src.path(), &target).unwrap(), 2);
+        assert_eq!(
+            std::fs::read_to_string(target.join("nested/a.txt")).unwrap(),
+            "y"
+        );
+    }
+}
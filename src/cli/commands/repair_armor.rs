@@ -0,0 +1,113 @@
+use std::path::{Path, PathBuf};
+
+use crate::adapters::cipher::armor;
+use crate::cli::output;
+use crate::core::errors::Result;
+use crate::core::models::audit_entry::{AuditAction, AuditDetail};
+
+/// Execute the `vaultic repair-armor` command.
+///
+/// Undoes line-ending and whitespace damage that git hosts and editors
+/// do to age armor, in `files` or else in the encrypted file of every
+/// environment (or only `envs`). Files whose base64 itself is damaged
+/// are reported by line and left alone. Nothing is decrypted, so no key
+/// is needed.
+pub fn execute(files: &[String], envs: &[String], dry_run: bool) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    let paths = if files.is_empty() {
+        env_files(vaultic_dir, envs)?
+    } else {
+        files.iter().map(PathBuf::from).collect()
+    };
+
+    let config = crate::cli::context::config().ok();
+    output::header("vaultic repair-armor");
+    let mut repaired = 0;
+    let mut failure = None;
+    for path in &paths {
+        let name = file_name(vaultic_dir, path);
+        let content = std::fs::read(path)?;
+        if !armor::is_armored(&content) {
+            output::detail(&format!("{name}: not age armor, skipped"));
+            continue;
+        }
+        let result = match armor::repair(&content) {
+            Ok(result) => result,
+            Err(e) => {
+                output::warning(&format!("{name}: cannot be repaired"));
+                failure.get_or_insert(e);
+                continue;
+            }
+        };
+        if result.fixes.is_empty() {
+            output::success(&format!("{name}: intact"));
+            continue;
+        }
+
+        repaired += 1;
+        if dry_run {
+            output::warning(&format!("{name}: would fix {}", result.fixes.join(", ")));
+            continue;
+        }
+        let previous = super::audit_helpers::compute_file_hash(path);
+        std::fs::write(path, &result.content)?;
+        let env = config
+            .filter(|_| path.parent() == Some(vaultic_dir))
+            .and_then(|c| super::audit_helpers::env_of_file(c, &name));
+        if let Some(env) = env {
+            super::audit_helpers::record_state(vaultic_dir, &env, previous, path);
+        }
+        output::success(&format!("{name}: fixed {}", result.fixes.join(", ")));
+        super::audit_helpers::log_audit(
+            AuditAction::ArmorRepair,
+            vec![name],
+            Some(AuditDetail::ArmorRepair {
+                fixes: result.fixes,
+            }),
+        );
+    }
+
+    if let Some(e) = failure {
+        return Err(e);
+    }
+    if repaired > 0 {
+        if dry_run {
+            println!("\n  Run without --dry-run to write the {repaired} file(s).");
+        } else {
+            println!("\n  Commit the {repaired} repaired file(s) to the repo.");
+        }
+    }
+    Ok(())
+}
+
+/// Encrypted files of `envs`, or of every environment, that exist.
+fn env_files(vaultic_dir: &Path, envs: &[String]) -> Result<Vec<PathBuf>> {
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+    let config = crate::cli::context::config()?;
+    for env in envs {
+        config.require_env(env)?;
+    }
+    let mut names: Vec<&String> = if envs.is_empty() {
+        config.environments.keys().collect()
+    } else {
+        envs.iter().collect()
+    };
+    names.sort();
+    names.dedup();
+    Ok(names
+        .into_iter()
+        .map(|name| vaultic_dir.join(format!("{}.enc", config.env_file_name(name))))
+        .filter(|path| path.exists())
+        .collect())
+}
+
+/// `path` as the audit log names files: relative to `.vaultic/` when it
+/// is inside it.
+fn file_name(vaultic_dir: &Path, path: &Path) -> String {
+    path.strip_prefix(vaultic_dir)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
@@ -0,0 +1,198 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use colored::Colorize;
+
+use crate::adapters::git::git_history;
+use crate::adapters::parsers::dotenv_parser::DotenvParser;
+use crate::cli::ReportAction;
+use crate::cli::output;
+use crate::config::app_config::AppConfig;
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::audit_entry::AuditEntry;
+use crate::core::models::ownership::Ownership;
+use crate::core::models::secret_file::SecretFile;
+use crate::core::services::diff_service::DiffService;
+use crate::core::services::template_resolver::TemplateResolver;
+use crate::core::traits::parser::ConfigParser;
+
+/// Execute `vaultic report <action>`.
+pub fn execute(action: &ReportAction, cipher: &str) -> Result<()> {
+    match action {
+        ReportAction::Owners { check } => match check {
+            Some(base) => execute_owners_check(base, cipher),
+            None => execute_owners(),
+        },
+    }
+}
+
+/// Load config, ownership rules, and template keys for the project.
+fn load_ownership(vaultic_dir: &Path) -> Result<(&'static AppConfig, Ownership, Vec<String>)> {
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+    let config = crate::cli::context::config()?;
+
+    let mut ownership = Ownership::default();
+    if let Some(owners) = &config.owners {
+        let sorted: BTreeMap<_, _> = owners.iter().collect();
+        for (pattern, owner) in sorted {
+            ownership.add(pattern, owner);
+        }
+    }
+
+    // Template annotations are optional; a project without a template
+    // can still rely on [owners] in config.toml
+    let mut keys = Vec::new();
+    if let Ok(path) = TemplateResolver::resolve_global(Some(config), Path::new(".")) {
+        output::detail(&format!("Template: {}", path.display()));
+        let template = DotenvParser.parse(&std::fs::read_to_string(&path)?)?;
+        ownership.add_template_annotations(&template);
+        keys = template.keys().into_iter().map(String::from).collect();
+    }
+
+    Ok((config, ownership, keys))
+}
+
+/// Print template keys grouped by owning team.
+fn execute_owners() -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    let (_, ownership, keys) = load_ownership(vaultic_dir)?;
+
+    let mut by_owner: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    let mut unowned = Vec::new();
+    for key in &keys {
+        match ownership.owner_of(key) {
+            Some(owner) => by_owner.entry(owner).or_default().push(key),
+            None => unowned.push(key.as_str()),
+        }
+    }
+
+    output::header("Key owners");
+    if by_owner.is_empty() {
+        output::warning("No ownership rules match any template key.");
+        println!("  Annotate keys with '# vaultic:owner=<team>' or add an [owners] section.");
+    }
+    for (owner, owned) in &by_owner {
+        println!("\n  {} ({})", owner.bold(), owned.len());
+        for key in owned {
+            println!("    • {key}");
+        }
+    }
+
+    if !unowned.is_empty() {
+        println!("\n  {} ({})", "unowned".dimmed(), unowned.len());
+        for key in &unowned {
+            println!("    • {}", key.dimmed());
+        }
+    }
+
+    Ok(())
+}
+
+/// Fail unless every owned key changed since `base` is acknowledged by an
+/// audit entry (added since `base`) whose detail mentions the owning team.
+fn execute_owners_check(base: &str, cipher: &str) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    let (config, ownership, _) = load_ownership(vaultic_dir)?;
+
+    // owner → changed keys
+    let mut changed: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for env_name in config.environment_names() {
+        let enc_path = vaultic_dir.join(format!("{}.enc", config.env_file_name(env_name)));
+        let head = load_head(&enc_path, vaultic_dir, cipher)?;
+        let base_file = load_base(base, &enc_path, cipher)?;
+
+        let diff = DiffService.diff(&base_file, &head, base, "HEAD")?;
+        for entry in diff.entries {
+            if let Some(owner) = ownership.owner_of(&entry.key) {
+                changed
+                    .entry(owner.to_string())
+                    .or_default()
+                    .insert(entry.key);
+            }
+        }
+    }
+
+    if changed.is_empty() {
+        output::success(&format!("No owned keys changed since {base}"));
+        return Ok(());
+    }
+
+    let details = new_audit_details(base, vaultic_dir, config)?;
+    let mut violations = 0;
+    for (owner, keys) in &changed {
+        let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+        let needle = owner.to_lowercase();
+        if details.iter().any(|d| d.to_lowercase().contains(&needle)) {
+            output::success(&format!("{owner}: {} (acknowledged)", keys.join(", ")));
+        } else {
+            output::error(&format!(
+                "{owner}: {} changed without an audit note mentioning '{owner}'",
+                keys.join(", ")
+            ));
+            violations += 1;
+        }
+    }
+
+    if violations > 0 {
+        println!(
+            "\n  Re-encrypt with: vaultic encrypt --note \"approved by <team>\" so the \
+             audit log records the owning team's sign-off."
+        );
+        return Err(VaulticError::ValidationFailed { count: violations });
+    }
+
+    Ok(())
+}
+
+/// Decrypt the current version of an environment (empty if absent).
+fn load_head(enc_path: &Path, vaultic_dir: &Path, cipher: &str) -> Result<SecretFile> {
+    if !enc_path.exists() {
+        return DotenvParser.parse("");
+    }
+    let bytes = super::crypto_helpers::decrypt_in_memory(enc_path, vaultic_dir, cipher)?;
+    parse_plaintext(bytes, enc_path)
+}
+
+/// Decrypt an environment as it was at `base` (empty if absent there).
+fn load_base(base: &str, enc_path: &Path, cipher: &str) -> Result<SecretFile> {
+    match git_history::show_file(base, enc_path)? {
+        Some(ciphertext) => {
+            let bytes = super::crypto_helpers::decrypt_bytes(&ciphertext, cipher)?;
+            parse_plaintext(bytes, enc_path)
+        }
+        None => DotenvParser.parse(""),
+    }
+}
+
+fn parse_plaintext(bytes: Vec<u8>, enc_path: &Path) -> Result<SecretFile> {
+    let text = String::from_utf8(bytes).map_err(|_| VaulticError::ParseError {
+        file: enc_path.to_path_buf(),
+        detail: "Decrypted content is not valid UTF-8".into(),
+    })?;
+    DotenvParser.parse(&text)
+}
+
+/// Details of audit entries appended to the log since `base`.
+fn new_audit_details(base: &str, vaultic_dir: &Path, config: &AppConfig) -> Result<Vec<String>> {
+    let log_file = config
+        .audit
+        .as_ref()
+        .map(|a| a.log_file.as_str())
+        .unwrap_or("audit.log");
+    let log_path = vaultic_dir.join(log_file);
+
+    let current = std::fs::read_to_string(&log_path).unwrap_or_default();
+    let base_lines = git_history::show_file(base, &log_path)?
+        .map(|b| String::from_utf8_lossy(&b).lines().count())
+        .unwrap_or(0);
+
+    Ok(current
+        .lines()
+        .skip(base_lines)
+        .filter_map(|line| serde_json::from_str::<AuditEntry>(line).ok())
+        .filter_map(|entry| entry.detail)
+        .map(|detail| detail.to_string())
+        .collect())
+}
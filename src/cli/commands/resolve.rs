@@ -1,9 +1,10 @@
 use crate::adapters::parsers::dotenv_parser::DotenvParser;
-use crate::cli::commands::crypto_helpers;
+use crate::cli::commands::lifecycle_helpers::{self, Hook};
+use crate::cli::commands::{crypto_helpers, reference_helpers};
 use crate::cli::output;
-use crate::config::app_config::AppConfig;
-use crate::core::errors::{Result, VaulticError};
-use crate::core::services::env_resolver::EnvResolver;
+use crate::core::errors::Result;
+use crate::core::models::audit_entry::AuditDetail;
+use crate::core::services::key_filter::KeyFilter;
 use crate::core::traits::parser::ConfigParser;
 
 /// Execute the `vaultic resolve --env <name>` command.
@@ -11,71 +12,89 @@ use crate::core::traits::parser::ConfigParser;
 /// Resolves the full inheritance chain for the given environment,
 /// decrypting each layer in memory, merging from base to leaf,
 /// and writing the result to `.env` (or to `output_path` if provided).
+/// Keys rejected by `filter` are dropped before anything is written, and
+/// reference values of the rest are fetched when `materialize` says so.
+/// A `post_resolve` hook runs once the file is written.
 pub fn execute(
     env: Option<&str>,
     cipher: &str,
     output_path: Option<&str>,
     to_stdout: bool,
+    filter: &KeyFilter,
+    materialize: Option<bool>,
 ) -> Result<()> {
     let vaultic_dir = crate::cli::context::vaultic_dir();
     if !vaultic_dir.exists() {
-        return Err(VaulticError::InvalidConfig {
-            detail: "Vaultic not initialized. Run 'vaultic init' first.".into(),
-        });
+        return Err(crate::cli::first_run::not_initialized());
     }
 
-    let config = AppConfig::load(vaultic_dir)?;
-    let env_name = env.unwrap_or(&config.vaultic.default_env);
+    let config = crate::cli::context::config()?;
+    let env_name = &crate::cli::context::select_env(env, Some(config))?;
 
     if !to_stdout {
         output::header(&format!("Resolving environment: {env_name}"));
     }
 
-    let resolver = EnvResolver;
     let parser = DotenvParser;
 
-    // Build the chain first so we know what to decrypt
-    let chain = resolver.build_chain(env_name, &config)?;
+    // Decrypt and merge each layer, parent projects first (warnings are
+    // routed to stderr with --stdout)
+    let environment = crypto_helpers::resolve_env(env_name, vaultic_dir, cipher, config, true)?;
 
     if !to_stdout {
-        output::success(&format!("Inheritance chain: {}", chain.join(" -> ")));
+        output::success(&format!(
+            "Inheritance chain: {}",
+            environment.layers.join(" -> ")
+        ));
+    }
+    let total = environment.resolved.keys().len();
+    let resolved = filter.apply(&environment.resolved);
+    let resolved = reference_helpers::apply(resolved, materialize, config)?;
+    let var_count = resolved.keys().len();
+    if !filter.is_empty() && var_count == 0 {
+        output::warning(&format!(
+            "No variables match the key filter ({})",
+            filter.describe()
+        ));
     }
-
-    // Decrypt and parse each layer
-    let files = crypto_helpers::load_env_files(&chain, vaultic_dir, cipher, &parser, !to_stdout)?;
-
-    // Resolve the full inheritance
-    let environment = resolver.resolve(env_name, &config, &files)?;
 
     // Serialize
-    let content = parser.serialize(&environment.resolved)?;
+    let content = parser.serialize(&resolved)?;
 
     if to_stdout {
         print!("{content}");
         return Ok(());
     }
 
-    let var_count = environment.resolved.keys().len();
-
     let dest = output_path.unwrap_or(".env");
     std::fs::write(dest, &content)?;
 
-    output::success(&format!(
-        "Resolved {var_count} variables from {} layer(s)",
-        environment.layers.len()
-    ));
+    if filter.is_empty() {
+        output::success(&format!(
+            "Resolved {var_count} variables from {} layer(s)",
+            environment.layers.len()
+        ));
+    } else {
+        output::success(&format!(
+            "Resolved {var_count} of {total} variables from {} layer(s) ({})",
+            environment.layers.len(),
+            filter.describe()
+        ));
+    }
     output::success(&format!("Written to {dest}"));
     println!("\n  Run 'vaultic check' to verify against the template.");
 
     // Audit
+    let detail = AuditDetail::Resolve {
+        variables: var_count,
+        layers: environment.layers.len(),
+        filter: (!filter.is_empty()).then(|| filter.describe()),
+    };
     super::audit_helpers::log_audit(
         crate::core::models::audit_entry::AuditAction::Resolve,
         vec![format!("{env_name}")],
-        Some(format!(
-            "{var_count} variables from {} layer(s)",
-            environment.layers.len()
-        )),
+        Some(detail),
     );
 
-    Ok(())
+    lifecycle_helpers::run(Hook::PostResolve, env_name, std::path::Path::new(dest))
 }
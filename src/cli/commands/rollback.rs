@@ -0,0 +1,236 @@
+use std::path::{Path, PathBuf};
+
+use colored::Color;
+
+use crate::adapters::key_stores::file_key_store::FileKeyStore;
+use crate::cli::commands::crypto_helpers;
+use crate::cli::output;
+use crate::cli::table::{Cell, Column, Table};
+use crate::config::app_config::DEFAULT_KEEP_VERSIONS;
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::env_version::{EnvVersion, VersionManifest};
+
+use super::audit_helpers::compute_file_hash;
+
+const VERSIONS_DIR: &str = "history";
+const MANIFEST: &str = "manifest.toml";
+
+/// Execute the `vaultic rollback` command.
+///
+/// Without `to`, lists the kept versions of the environment. With `to`,
+/// decrypts that version in memory and, after confirmation, re-encrypts
+/// it as the environment's current content for today's recipients —
+/// so the rollback is itself a new version and shows up in the value
+/// history like any other change.
+pub fn execute(
+    env: Option<&str>,
+    to: Option<&str>,
+    cipher: &str,
+    note: Option<&str>,
+    force: bool,
+) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+    let config = crate::cli::context::config()?;
+    let env_name = &crate::cli::context::select_env(env, Some(config))?;
+    config.require_env(env_name)?;
+    config.require_writable(env_name)?;
+
+    let dir = versions_dir(vaultic_dir, env_name);
+    let manifest = read_manifest(&dir)?;
+    let enc_path = vaultic_dir.join(format!("{env_name}.env.enc"));
+
+    let Some(id) = to else {
+        list(env_name, &manifest, &enc_path);
+        return Ok(());
+    };
+
+    let version = manifest
+        .versions
+        .iter()
+        .find(|v| v.id == id)
+        .ok_or_else(|| VaulticError::InvalidConfig {
+            detail: format!(
+                "No version '{id}' of {env_name}.\n\n  \
+                 List the kept versions with: vaultic rollback --env {env_name}"
+            ),
+        })?;
+    let version_path = dir.join(format!("{id}.enc"));
+    if compute_file_hash(&version_path).as_deref() != Some(version.sha256.as_str()) {
+        return Err(VaulticError::InvalidConfig {
+            detail: format!(
+                "Version '{id}' of {env_name} is missing or does not match the SHA-256 in \
+                 its manifest ({}); refusing to restore it.",
+                version_path.display()
+            ),
+        });
+    }
+
+    crypto_helpers::check_recipients_signature(vaultic_dir, Some(config))?;
+    let restored = crypto_helpers::decrypt_in_memory(&version_path, vaultic_dir, cipher)?;
+    if enc_path.exists()
+        && crypto_helpers::decrypt_in_memory(&enc_path, vaultic_dir, cipher)? == restored
+    {
+        output::success(&format!(
+            "{env_name} already has the content of version {id}"
+        ));
+        return Ok(());
+    }
+
+    if !output::confirm(
+        &format!(
+            "Roll {env_name} back to version {id} ({} by {})?",
+            version.created_at.format("%Y-%m-%d %H:%M"),
+            version.author
+        ),
+        false,
+    )? {
+        output::warning("Nothing rolled back.");
+        return Ok(());
+    }
+
+    let default_note = format!("rollback to {id}");
+    let key_store = FileKeyStore::new(vaultic_dir.join("recipients.txt")).for_env(env_name);
+    super::encrypt::encrypt_bytes_to(
+        &restored,
+        &enc_path,
+        env_name,
+        cipher,
+        &key_store,
+        Some(note.unwrap_or(&default_note)),
+        force,
+    )?;
+
+    output::success(&format!("Rolled {env_name} back to version {id}"));
+    println!("\n  Commit {} to the repo.", enc_path.display());
+    Ok(())
+}
+
+/// The newest kept version of `env_name`, if any.
+pub(super) fn last_version(vaultic_dir: &Path, env_name: &str) -> Option<EnvVersion> {
+    read_manifest(&versions_dir(vaultic_dir, env_name))
+        .ok()?
+        .versions
+        .pop()
+}
+
+/// Keep a copy of the ciphertext just written to `enc_path`, holding
+/// `variables` variables, as a new version of `env_name`, deleting
+/// versions beyond `[history] keep_versions`.
+///
+/// Like the audit log, failures only warn.
+pub(super) fn record_version(
+    enc_path: &Path,
+    env_name: &str,
+    note: Option<&str>,
+    variables: Option<usize>,
+) {
+    let keep = crate::cli::context::config()
+        .map(|c| c.keep_versions())
+        .unwrap_or(DEFAULT_KEEP_VERSIONS);
+    let Some(vaultic_dir) = enc_path.parent() else {
+        return;
+    };
+    if keep == 0 {
+        return;
+    }
+    let dir = versions_dir(vaultic_dir, env_name);
+    if let Err(e) = try_record(enc_path, &dir, keep, note, variables) {
+        output::warning(&format!("Could not keep a version of {env_name}: {e}"));
+    }
+}
+
+fn try_record(
+    enc_path: &Path,
+    dir: &Path,
+    keep: usize,
+    note: Option<&str>,
+    variables: Option<usize>,
+) -> Result<()> {
+    let sha256 = compute_file_hash(enc_path).ok_or_else(|| VaulticError::FileNotFound {
+        path: enc_path.to_path_buf(),
+    })?;
+    let mut manifest = read_manifest(dir)?;
+    let created_at = crate::cli::context::clock().now();
+    let (author, email) = crate::cli::context::author_provider().author();
+    let id = manifest.next_id(created_at);
+
+    std::fs::create_dir_all(dir)?;
+    std::fs::copy(enc_path, dir.join(format!("{id}.enc")))?;
+    manifest.versions.push(EnvVersion {
+        id,
+        created_at,
+        author,
+        email,
+        sha256,
+        note: note.map(String::from),
+        variables,
+    });
+    for old in manifest.trim(keep) {
+        let _ = std::fs::remove_file(dir.join(format!("{}.enc", old.id)));
+    }
+    write_manifest(dir, &manifest)
+}
+
+fn list(env_name: &str, manifest: &VersionManifest, enc_path: &Path) {
+    if manifest.versions.is_empty() {
+        output::success(&format!("No kept versions of {env_name} yet"));
+        println!("\n  A version is kept each time {env_name} is encrypted.");
+        return;
+    }
+
+    let current = compute_file_hash(enc_path);
+    output::header(&format!(
+        "Versions of {env_name} ({})",
+        manifest.versions.len()
+    ));
+    let mut table = Table::new(vec![
+        Column::new("ID"),
+        Column::new("Created"),
+        Column::new("Author").shrink(10),
+        Column::new("Note").shrink(10),
+        Column::new(""),
+    ]);
+    for version in manifest.versions.iter().rev() {
+        let marker = if current.as_deref() == Some(version.sha256.as_str()) {
+            Cell::new("current").color(Color::Green)
+        } else {
+            Cell::new("")
+        };
+        table.row(vec![
+            Cell::new(version.id.as_str()).color(Color::Cyan),
+            Cell::new(version.created_at.format("%Y-%m-%d %H:%M").to_string()).dimmed(),
+            Cell::new(version.author.as_str()),
+            Cell::new(version.note.as_deref().unwrap_or_default()).dimmed(),
+            marker,
+        ]);
+    }
+    table.print();
+    println!("\n  Restore one with: vaultic rollback --env {env_name} --to <ID>");
+}
+
+/// `.vaultic/history/<env>/`
+fn versions_dir(vaultic_dir: &Path, env_name: &str) -> PathBuf {
+    vaultic_dir.join(VERSIONS_DIR).join(env_name)
+}
+
+fn read_manifest(dir: &Path) -> Result<VersionManifest> {
+    let path = dir.join(MANIFEST);
+    if !path.exists() {
+        return Ok(VersionManifest::default());
+    }
+    toml::from_str(&std::fs::read_to_string(&path)?).map_err(|e| VaulticError::ParseError {
+        file: path,
+        detail: e.to_string(),
+    })
+}
+
+fn write_manifest(dir: &Path, manifest: &VersionManifest) -> Result<()> {
+    let content = toml::to_string(manifest).map_err(|e| VaulticError::InvalidConfig {
+        detail: format!("Failed to serialize {MANIFEST}: {e}"),
+    })?;
+    std::fs::write(dir.join(MANIFEST), content)?;
+    Ok(())
+}
@@ -0,0 +1,124 @@
+use std::process::{Command, ExitStatus};
+
+use crate::cli::commands::{crypto_helpers, lease_helpers, reference_helpers};
+use crate::cli::{output, signals};
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::audit_entry::{AuditAction, AuditDetail};
+use crate::core::services::key_filter::KeyFilter;
+
+/// Execute the `vaultic run` command.
+///
+/// Decrypts and resolves the environment in memory, then runs `command`
+/// with the resolved variables added to its environment. Keys rejected by
+/// `filter` are never injected; reference values of the rest are fetched
+/// when `materialize` says so. Short-lived credentials from `[leases]`
+/// are added on top and renewed while the child runs. Nothing is written
+/// to disk. Returns the child's exit code.
+pub fn execute(
+    env: Option<&str>,
+    cipher: &str,
+    filter: &KeyFilter,
+    materialize: Option<bool>,
+    command: &[String],
+) -> Result<i32> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+
+    let Some((program, args)) = command.split_first() else {
+        return Err(VaulticError::InvalidConfig {
+            detail: "No command given. Usage: vaultic run --env <name> -- <command>".into(),
+        });
+    };
+
+    let config = crate::cli::context::config()?;
+    let env_name = &crate::cli::context::select_env(env, Some(config))?;
+
+    let environment = crypto_helpers::resolve_env(env_name, vaultic_dir, cipher, config, true)?;
+    let resolved = filter.apply(&environment.resolved);
+    let resolved = reference_helpers::apply(resolved, materialize, config)?;
+    let leases = lease_helpers::acquire(config, env_name)?;
+    let leased = lease_helpers::vars(&leases);
+
+    let vars: Vec<(&str, &str)> = resolved
+        .entries()
+        .filter(|e| !leased.contains_key(&e.key))
+        .map(|e| (e.key.as_str(), e.value.as_str()))
+        .collect();
+    if filter.is_empty() {
+        output::detail(&format!(
+            "Injecting {} variable(s) from '{env_name}' into '{program}'",
+            vars.len()
+        ));
+    } else {
+        output::detail(&format!(
+            "Injecting {} of {} variable(s) from '{env_name}' into '{program}' ({})",
+            vars.len(),
+            environment.resolved.keys().len(),
+            filter.describe()
+        ));
+        if vars.is_empty() {
+            output::warning("No variables match the key filter");
+        }
+    }
+
+    let detail = AuditDetail::Run {
+        program: program.to_string(),
+        variables: vars.len(),
+        filter: (!filter.is_empty()).then(|| filter.describe()),
+        leases: leases.iter().map(|l| l.name().to_string()).collect(),
+    };
+    super::audit_helpers::log_audit(AuditAction::Run, vec![env_name.to_string()], Some(detail));
+
+    let mut cmd = Command::new(program);
+    cmd.args(args).envs(vars).envs(&leased);
+    let lease_file = if lease_helpers::refreshable(&leases) {
+        let file = lease_helpers::lease_file(&leases)?;
+        cmd.env("VAULTIC_LEASE_FILE", file.path());
+        Some(file)
+    } else {
+        None
+    };
+
+    // The child shares our terminal, so Ctrl-C reaches it directly; Vaultic
+    // must wait for it to exit rather than quitting first.
+    signals::set_child_running(true);
+    let status = cmd.spawn().and_then(|mut child| {
+        let refresher =
+            lease_file.map(|file| lease_helpers::Refresher::start(leases, file, child.id()));
+        let status = child.wait();
+        if let Some(refresher) = refresher {
+            refresher.stop();
+        }
+        status
+    });
+    signals::set_child_running(false);
+
+    let status = status.map_err(|e| VaulticError::InvalidConfig {
+        detail: format!(
+            "Failed to start '{program}': {e}\n\n  \
+             Solutions:\n    \
+             → Check that '{program}' is installed and in your PATH\n    \
+             → Put the command after '--': vaultic run --env {env_name} -- {program}"
+        ),
+    })?;
+
+    Ok(exit_code(status))
+}
+
+/// Exit code to report for the child, using the shell's `128 + signal`
+/// convention when it was killed by a signal.
+pub fn exit_code(status: ExitStatus) -> i32 {
+    if let Some(code) = status.code() {
+        return code;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            return 128 + signal;
+        }
+    }
+    1
+}
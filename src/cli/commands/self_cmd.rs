@@ -0,0 +1,79 @@
+use crate::adapters::updater::github_updater;
+use crate::adapters::updater::verifier;
+use crate::cli::SelfAction;
+use crate::cli::output;
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::update_info::{current_platform_asset, current_version};
+
+/// Execute a `vaultic self` subcommand.
+pub fn execute(action: &SelfAction) -> Result<()> {
+    match action {
+        SelfAction::Verify => execute_verify(),
+    }
+}
+
+/// Hash the running binary and compare it with the SHA256SUMS.txt of the
+/// release it claims to be, after checking that file's minisign
+/// signature. Detects installs that were corrupted or tampered with.
+fn execute_verify() -> Result<()> {
+    output::header("🔍 Vaultic — Verify binary");
+
+    let version = current_version();
+    let asset_name = current_platform_asset().ok_or_else(|| VaulticError::UnsupportedPlatform {
+        platform: format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH),
+    })?;
+    let exe = std::env::current_exe().map_err(|e| VaulticError::BinaryIntegrityFailed {
+        reason: format!("Cannot locate the running binary: {e}"),
+    })?;
+    let binary = std::fs::read(&exe).map_err(|e| VaulticError::BinaryIntegrityFailed {
+        reason: format!("Cannot read {}: {e}", exe.display()),
+    })?;
+    output::success(&format!(
+        "Binary: {} (v{version}, {asset_name})",
+        exe.display()
+    ));
+
+    let sp = output::spinner(&format!("Downloading SHA256SUMS.txt for v{version}..."));
+    let fetch = |file: &str| {
+        github_updater::download_bytes(&github_updater::release_file_url(&version, file)).map_err(
+            |e| VaulticError::BinaryIntegrityFailed {
+                reason: format!("Could not fetch {file} for v{version}: {}", reason(e)),
+            },
+        )
+    };
+    let checksums = fetch("SHA256SUMS.txt")?;
+    let signature = fetch("SHA256SUMS.txt.minisig")?;
+    verifier::verify_signature(&checksums, &signature)
+        .map_err(|e| VaulticError::BinaryIntegrityFailed { reason: reason(e) })?;
+    output::finish_spinner(sp, "Checksums signature valid (minisign Ed25519)");
+
+    let computed = verifier::sha256_hex(&binary);
+    let expected = verifier::expected_sha256(asset_name, &String::from_utf8_lossy(&checksums))
+        .ok_or_else(|| VaulticError::BinaryIntegrityFailed {
+            reason: format!("Release v{version} lists no checksum for {asset_name}"),
+        })?;
+    if computed != expected {
+        return Err(VaulticError::BinaryIntegrityFailed {
+            reason: format!(
+                "SHA256 mismatch for {}\n\n  \
+                 Running binary hash:  {computed}\n  \
+                 v{version} release hash: {expected}\n\n  \
+                 The binary is corrupted, was modified, or was not built by the release pipeline.",
+                exe.display()
+            ),
+        });
+    }
+
+    output::success(&format!("SHA256 matches the official v{version} release"));
+    Ok(())
+}
+
+/// The bare reason of an updater error, without the update-specific
+/// advice its message carries.
+fn reason(error: VaulticError) -> String {
+    match error {
+        VaulticError::UpdateFailed { reason }
+        | VaulticError::UpdateVerificationFailed { reason } => reason,
+        other => other.to_string(),
+    }
+}
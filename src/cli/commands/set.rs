@@ -0,0 +1,126 @@
+use std::io::{IsTerminal, Read};
+
+use crate::adapters::key_stores::file_key_store::FileKeyStore;
+use crate::adapters::parsers::dotenv_parser::DotenvParser;
+use crate::cli::commands::crypto_helpers;
+use crate::cli::output;
+use crate::config::app_config::SecretFormat;
+use crate::core::errors::{Result, VaulticError};
+
+/// Execute the `vaultic set KEY=VALUE` command.
+///
+/// Decrypts the environment's own file in memory, updates or appends the
+/// variable while keeping every other line as it was, and re-encrypts.
+/// With a bare `KEY` the value is read from stdin, keeping it out of the
+/// shell history.
+pub fn execute(
+    assignment: &str,
+    env: Option<&str>,
+    cipher: &str,
+    note: Option<&str>,
+    force: bool,
+) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+
+    let (key, value) = match assignment.split_once('=') {
+        Some((key, value)) => (key.trim(), value.to_string()),
+        None => (assignment.trim(), read_value(assignment.trim())?),
+    };
+    validate_key(key)?;
+
+    let config = crate::cli::context::config()?;
+    let env_name = &crate::cli::context::select_env(env, Some(config))?;
+    config.require_env(env_name)?;
+    config.require_writable(env_name)?;
+    let format = config.env_format(env_name);
+    if format != SecretFormat::Dotenv {
+        return Err(VaulticError::InvalidConfig {
+            detail: format!(
+                "'vaultic set' only edits dotenv environments; {env_name} is stored as {}.\n\n  \
+                 Solutions:\n    \
+                 → Use 'vaultic edit --env {env_name}' to change it in your editor",
+                format.label()
+            ),
+        });
+    }
+    crypto_helpers::check_recipients_signature(vaultic_dir, Some(config))?;
+
+    let enc_path = vaultic_dir.join(format!("{env_name}.env.enc"));
+    let original = if enc_path.exists() {
+        crypto_helpers::decrypt_in_memory(&enc_path, vaultic_dir, cipher)?
+    } else {
+        output::warning(&format!(
+            "{} does not exist yet — creating it",
+            enc_path.display()
+        ));
+        Vec::new()
+    };
+    let content = String::from_utf8(original).map_err(|_| VaulticError::ParseError {
+        file: enc_path.clone(),
+        detail: "Decrypted content is not valid UTF-8".into(),
+    })?;
+
+    let (updated, existed) = DotenvParser.set_value(&content, key, &value)?;
+    if updated == content {
+        output::success(&format!("{key} already has that value in {env_name}"));
+        return Ok(());
+    }
+
+    let default_note = format!("set {key}");
+    let key_store = FileKeyStore::new(vaultic_dir.join("recipients.txt")).for_env(env_name);
+    super::encrypt::encrypt_bytes_to(
+        updated.as_bytes(),
+        &enc_path,
+        env_name,
+        cipher,
+        &key_store,
+        Some(note.unwrap_or(&default_note)),
+        force,
+    )?;
+
+    let verb = if existed { "Updated" } else { "Added" };
+    output::success(&format!("{verb} {key} in {env_name}"));
+    println!("\n  Commit {} to the repo.", enc_path.display());
+
+    Ok(())
+}
+
+/// Reject keys the dotenv parser would not read back as the same key.
+fn validate_key(key: &str) -> Result<()> {
+    if key.is_empty() || key.starts_with('#') || key.contains(char::is_whitespace) {
+        return Err(VaulticError::InvalidConfig {
+            detail: format!(
+                "Invalid variable name '{key}'\n\n  \
+                 Usage: vaultic set KEY=VALUE --env <name>"
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Read the value for `key` from a prompt, or from piped stdin.
+fn read_value(key: &str) -> Result<String> {
+    if std::io::stdin().is_terminal() {
+        return output::input_required(&format!("Value for {key}"));
+    }
+    let mut value = String::new();
+    std::io::stdin().read_to_string(&mut value)?;
+    let value = value.strip_suffix('\n').unwrap_or(&value);
+    Ok(value.strip_suffix('\r').unwrap_or(value).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_key_rejects_unparseable_names() {
+        assert!(validate_key("DATABASE_URL").is_ok());
+        assert!(validate_key("").is_err());
+        assert!(validate_key("MY KEY").is_err());
+        assert!(validate_key("#KEY").is_err());
+    }
+}
@@ -0,0 +1,542 @@
+use std::path::{Path, PathBuf};
+
+use colored::Color;
+
+use crate::adapters::parsers;
+use crate::cli::SnapshotAction;
+use crate::cli::commands::crypto_helpers;
+use crate::cli::output;
+use crate::cli::table::{Cell, Column, Table};
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::audit_entry::{AuditAction, AuditDetail};
+use crate::core::models::secret_file::SecretFile;
+use crate::core::models::snapshot::SnapshotMeta;
+use crate::core::services::diff_service::DiffService;
+
+use super::audit_helpers::compute_file_hash;
+use super::diff::DiffView;
+
+const SNAPSHOTS_DIR: &str = "snapshots";
+
+/// Execute `vaultic snapshot <action>`.
+pub fn execute(action: &SnapshotAction, cipher: &str) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+
+    match action {
+        SnapshotAction::Create {
+            environment,
+            name,
+            note,
+        } => execute_create(vaultic_dir, environment, name, note.as_deref()),
+        SnapshotAction::List { environment } => execute_list(vaultic_dir, environment.as_deref()),
+        SnapshotAction::Restore { environment, name } => {
+            execute_restore(vaultic_dir, environment, name)
+        }
+        SnapshotAction::Diff { environment, name } => {
+            execute_diff(vaultic_dir, environment, name, cipher)
+        }
+        SnapshotAction::Prune {
+            environment,
+            keep,
+            older_than,
+            dry_run,
+        } => execute_prune(
+            vaultic_dir,
+            environment.as_deref(),
+            *keep,
+            older_than.as_deref(),
+            *dry_run,
+        ),
+    }
+}
+
+/// Copy the environment's current ciphertext into
+/// `.vaultic/snapshots/<env>/<name>.env.enc` and record its metadata.
+///
+/// Nothing is decrypted: the snapshot is byte-for-byte the committed file.
+fn execute_create(
+    vaultic_dir: &Path,
+    env_name: &str,
+    name: &str,
+    note: Option<&str>,
+) -> Result<()> {
+    crate::config::names::validate_simple_filename(name, "snapshot name")?;
+    let config = crate::cli::context::config()?;
+    config.require_env(env_name)?;
+
+    let enc_path = vaultic_dir.join(format!("{env_name}.env.enc"));
+    if !enc_path.exists() {
+        return Err(VaulticError::FileNotFound { path: enc_path });
+    }
+
+    let (snap_path, meta_path) = snapshot_paths(vaultic_dir, env_name, name);
+    if snap_path.exists() {
+        return Err(VaulticError::InvalidConfig {
+            detail: format!(
+                "Snapshot '{name}' of {env_name} already exists\n\n  \
+                 Solutions:\n    \
+                 → Pick another name\n    \
+                 → See existing snapshots: vaultic snapshot list {env_name}"
+            ),
+        });
+    }
+
+    let sha256 = compute_file_hash(&enc_path).ok_or_else(|| VaulticError::FileNotFound {
+        path: enc_path.clone(),
+    })?;
+    let (author, email) = crate::cli::context::author_provider().author();
+    let meta = SnapshotMeta {
+        name: name.to_string(),
+        env: env_name.to_string(),
+        created_at: crate::cli::context::clock().now(),
+        author,
+        email,
+        sha256,
+        note: note.map(String::from),
+    };
+    let meta_toml = toml::to_string(&meta).map_err(|e| VaulticError::InvalidConfig {
+        detail: format!("Could not write snapshot metadata: {e}"),
+    })?;
+
+    if let Some(dir) = snap_path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::copy(&enc_path, &snap_path)?;
+    std::fs::write(&meta_path, meta_toml)?;
+
+    output::success(&format!(
+        "Saved snapshot '{name}' of {env_name} to {}",
+        snap_path.display()
+    ));
+    println!("\n  Commit it to the repo; restore with: vaultic snapshot restore {env_name} {name}");
+
+    super::audit_helpers::log_audit(
+        AuditAction::SnapshotCreate,
+        vec![env_name.to_string()],
+        Some(AuditDetail::SnapshotCreate {
+            name: name.to_string(),
+            note: note.map(String::from),
+        }),
+    );
+
+    Ok(())
+}
+
+/// List snapshots, newest first within each environment.
+fn execute_list(vaultic_dir: &Path, env_name: Option<&str>) -> Result<()> {
+    if let Some(env) = env_name {
+        crate::config::names::validate_env_name(env)?;
+    }
+    let snapshots = load_all(vaultic_dir, env_name)?;
+    if snapshots.is_empty() {
+        output::success("No snapshots yet");
+        println!("\n  Create one with: vaultic snapshot create <env> <name>");
+        return Ok(());
+    }
+
+    output::header(&format!("Snapshots ({})", snapshots.len()));
+    let mut table = Table::new(vec![
+        Column::new("Env"),
+        Column::new("Name").shrink(12),
+        Column::new("Created"),
+        Column::new("Author").shrink(10),
+        Column::new("Note").shrink(10),
+    ]);
+    for meta in &snapshots {
+        table.row(vec![
+            Cell::new(meta.env.as_str()).color(Color::Cyan),
+            Cell::new(meta.name.as_str()),
+            Cell::new(meta.created_at.format("%Y-%m-%d %H:%M").to_string()).dimmed(),
+            Cell::new(meta.author.as_str()),
+            Cell::new(meta.note.as_deref().unwrap_or_default()).dimmed(),
+        ]);
+    }
+    table.print();
+
+    Ok(())
+}
+
+/// Replace the environment's ciphertext with the snapshot's, after
+/// confirmation.
+fn execute_restore(vaultic_dir: &Path, env_name: &str, name: &str) -> Result<()> {
+    let config = crate::cli::context::config()?;
+    config.require_env(env_name)?;
+    config.require_writable(env_name)?;
+    let (meta, snap_path) = load_verified(vaultic_dir, env_name, name)?;
+
+    let enc_path = vaultic_dir.join(format!("{env_name}.env.enc"));
+    if compute_file_hash(&enc_path).as_deref() == Some(meta.sha256.as_str()) {
+        output::success(&format!("{env_name} already matches snapshot '{name}'"));
+        return Ok(());
+    }
+
+    if !output::confirm(
+        &format!(
+            "Replace {} with snapshot '{name}' from {}?",
+            enc_path.display(),
+            meta.created_at.format("%Y-%m-%d %H:%M")
+        ),
+        false,
+    )? {
+        output::warning("Nothing restored.");
+        return Ok(());
+    }
+
+    let previous = compute_file_hash(&enc_path);
+    std::fs::copy(&snap_path, &enc_path)?;
+    super::audit_helpers::record_state(vaultic_dir, env_name, previous, &enc_path);
+    output::success(&format!("Restored {env_name} from snapshot '{name}'"));
+    println!("\n  Commit {} to the repo.", enc_path.display());
+
+    super::audit_helpers::log_audit(
+        AuditAction::SnapshotRestore,
+        vec![env_name.to_string()],
+        Some(AuditDetail::SnapshotRestore {
+            name: name.to_string(),
+        }),
+    );
+
+    Ok(())
+}
+
+/// Compare a snapshot with the environment's current secrets, decrypting
+/// both in memory.
+fn execute_diff(vaultic_dir: &Path, env_name: &str, name: &str, cipher: &str) -> Result<()> {
+    let config = crate::cli::context::config()?;
+    config.require_env(env_name)?;
+    let (_, snap_path) = load_verified(vaultic_dir, env_name, name)?;
+
+    let parser = parsers::for_format(config.env_format(env_name));
+    let decrypt = |path: &Path| -> Result<SecretFile> {
+        let bytes = crypto_helpers::decrypt_in_memory(path, vaultic_dir, cipher)?;
+        let text = String::from_utf8(bytes).map_err(|_| VaulticError::ParseError {
+            file: path.to_path_buf(),
+            detail: "Decrypted content is not valid UTF-8".into(),
+        })?;
+        parser.parse(&text)
+    };
+
+    let snapshot = decrypt(&snap_path)?;
+    let enc_path = vaultic_dir.join(format!("{env_name}.env.enc"));
+    let current = if enc_path.exists() {
+        decrypt(&enc_path)?
+    } else {
+        output::warning(&format!("{} does not exist", enc_path.display()));
+        SecretFile {
+            lines: Vec::new(),
+            source_path: None,
+        }
+    };
+
+    output::header(&format!("Comparing snapshot '{name}' with {env_name}"));
+    let result = DiffService.diff(&snapshot, &current, name, env_name)?;
+    super::diff::report(
+        &snapshot,
+        &current,
+        &result,
+        DiffView::default(),
+        &format!("{env_name} matches snapshot '{name}'"),
+    )?;
+
+    super::audit_helpers::log_audit(
+        AuditAction::Diff,
+        vec![format!("snapshot:{name}"), env_name.to_string()],
+        Some(AuditDetail::Diff {
+            differences: result.entries.len(),
+        }),
+    );
+
+    Ok(())
+}
+
+/// Delete the snapshots selected by [`prune_candidates`] after
+/// confirmation, logging one audit entry per snapshot.
+fn execute_prune(
+    vaultic_dir: &Path,
+    env_name: Option<&str>,
+    keep: Option<usize>,
+    older_than: Option<&str>,
+    dry_run: bool,
+) -> Result<()> {
+    if let Some(env) = env_name {
+        crate::config::names::validate_env_name(env)?;
+    }
+    let max_age = older_than.map(parse_age).transpose()?;
+    let now = crate::cli::context::clock().now();
+    let snapshots = load_all(vaultic_dir, env_name)?;
+    let doomed = prune_candidates(&snapshots, keep.unwrap_or(0), max_age, now);
+
+    if doomed.is_empty() {
+        output::success(&format!(
+            "Nothing to prune ({} snapshot(s) kept)",
+            snapshots.len()
+        ));
+        return Ok(());
+    }
+
+    output::header(&format!("Snapshots to delete ({})", doomed.len()));
+    for meta in &doomed {
+        println!(
+            "  • {}/{}  {}",
+            meta.env,
+            meta.name,
+            meta.created_at.format("%Y-%m-%d")
+        );
+    }
+    println!();
+
+    if dry_run {
+        output::warning("Dry run — nothing deleted.");
+        return Ok(());
+    }
+    if !output::confirm(&format!("Delete {} snapshot(s)?", doomed.len()), false)? {
+        output::warning("Nothing deleted.");
+        return Ok(());
+    }
+
+    let mut reason = Vec::new();
+    if let Some(keep) = keep {
+        reason.push(format!("beyond newest {keep}"));
+    }
+    if let Some(age) = older_than {
+        reason.push(format!("older than {age}"));
+    }
+    let reason = format!("snapshot {}", reason.join(", "));
+
+    for meta in &doomed {
+        let (snap_path, meta_path) = snapshot_paths(vaultic_dir, &meta.env, &meta.name);
+        if snap_path.exists() {
+            std::fs::remove_file(&snap_path)?;
+        }
+        std::fs::remove_file(&meta_path)?;
+        super::audit_helpers::log_audit(
+            AuditAction::Prune,
+            vec![format!("{SNAPSHOTS_DIR}/{}/{}", meta.env, meta.name)],
+            Some(AuditDetail::Prune {
+                reason: reason.clone(),
+            }),
+        );
+    }
+    output::success(&format!(
+        "Deleted {} snapshot(s), kept {}",
+        doomed.len(),
+        snapshots.len() - doomed.len()
+    ));
+    println!("\n  Commit the removal to the repo.");
+
+    Ok(())
+}
+
+/// Snapshots to delete: everything but the newest `keep` of each
+/// environment, limited to those older than `max_age` when given.
+///
+/// `snapshots` must be sorted as returned by [`load_all`].
+fn prune_candidates(
+    snapshots: &[SnapshotMeta],
+    keep: usize,
+    max_age: Option<chrono::Duration>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Vec<&SnapshotMeta> {
+    let mut seen_in_env = 0;
+    let mut current_env = None;
+    snapshots
+        .iter()
+        .filter(|meta| {
+            if current_env != Some(meta.env.as_str()) {
+                current_env = Some(meta.env.as_str());
+                seen_in_env = 0;
+            }
+            seen_in_env += 1;
+            seen_in_env > keep && max_age.is_none_or(|age| now - meta.created_at > age)
+        })
+        .collect()
+}
+
+/// Parse an age such as `90d`, `12w` or `48h`.
+fn parse_age(s: &str) -> Result<chrono::Duration> {
+    let invalid = || VaulticError::InvalidConfig {
+        detail: format!("Invalid age '{s}'. Use a number of days, weeks or hours: 90d, 12w, 48h"),
+    };
+    let (number, unit) = s.split_at(s.len().saturating_sub(1));
+    let n: i64 = number.parse().map_err(|_| invalid())?;
+    match unit {
+        "d" => Ok(chrono::Duration::days(n)),
+        "w" => Ok(chrono::Duration::weeks(n)),
+        "h" => Ok(chrono::Duration::hours(n)),
+        _ => Err(invalid()),
+    }
+}
+
+/// Ciphertext and metadata paths of snapshot `name` of `env_name`.
+fn snapshot_paths(vaultic_dir: &Path, env_name: &str, name: &str) -> (PathBuf, PathBuf) {
+    let dir = vaultic_dir.join(SNAPSHOTS_DIR).join(env_name);
+    (
+        dir.join(format!("{name}.env.enc")),
+        dir.join(format!("{name}.toml")),
+    )
+}
+
+fn read_meta(path: &Path) -> Result<SnapshotMeta> {
+    let content = std::fs::read_to_string(path)?;
+    toml::from_str(&content).map_err(|e| VaulticError::ParseError {
+        file: path.to_path_buf(),
+        detail: e.to_string(),
+    })
+}
+
+/// Load snapshot `name` of `env_name`, checking that its ciphertext still
+/// matches the hash recorded when it was created.
+fn load_verified(
+    vaultic_dir: &Path,
+    env_name: &str,
+    name: &str,
+) -> Result<(SnapshotMeta, PathBuf)> {
+    crate::config::names::validate_simple_filename(name, "snapshot name")?;
+    let (snap_path, meta_path) = snapshot_paths(vaultic_dir, env_name, name);
+    if !snap_path.exists() || !meta_path.exists() {
+        return Err(VaulticError::InvalidConfig {
+            detail: format!(
+                "No snapshot '{name}' of {env_name}\n\n  \
+                 See existing snapshots: vaultic snapshot list {env_name}"
+            ),
+        });
+    }
+
+    let meta = read_meta(&meta_path)?;
+    if compute_file_hash(&snap_path).as_deref() != Some(meta.sha256.as_str()) {
+        return Err(VaulticError::InvalidConfig {
+            detail: format!(
+                "{} was modified after the snapshot was taken (SHA-256 mismatch)",
+                snap_path.display()
+            ),
+        });
+    }
+    Ok((meta, snap_path))
+}
+
+/// Metadata of every snapshot (of `env_name` only, when given), sorted by
+/// environment and then newest first.
+fn load_all(vaultic_dir: &Path, env_name: Option<&str>) -> Result<Vec<SnapshotMeta>> {
+    let root = vaultic_dir.join(SNAPSHOTS_DIR);
+    let dirs: Vec<PathBuf> = match env_name {
+        Some(env) => vec![root.join(env)],
+        None => std::fs::read_dir(&root)
+            .into_iter()
+            .flatten()
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.is_dir())
+            .collect(),
+    };
+
+    let mut snapshots = Vec::new();
+    for dir in dirs {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for path in entries.filter_map(|e| e.ok().map(|e| e.path())) {
+            if path.extension().is_some_and(|ext| ext == "toml") {
+                snapshots.push(read_meta(&path)?);
+            }
+        }
+    }
+    snapshots.sort_by(|a, b| a.env.cmp(&b.env).then(b.created_at.cmp(&a.created_at)));
+    Ok(snapshots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metadata_round_trips_through_toml() {
+        let meta = SnapshotMeta {
+            name: "pre-release-42".into(),
+            env: "prod".into(),
+            created_at: chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+            author: "Ana".into(),
+            email: None,
+            sha256: "abc".into(),
+            note: Some("before v42".into()),
+        };
+        let content = toml::to_string(&meta).unwrap();
+        assert!(!content.contains("email"));
+        assert_eq!(toml::from_str::<SnapshotMeta>(&content).unwrap(), meta);
+    }
+
+    fn meta(env: &str, name: &str, days_ago: i64) -> SnapshotMeta {
+        SnapshotMeta {
+            name: name.into(),
+            env: env.into(),
+            created_at: chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap()
+                - chrono::Duration::days(days_ago),
+            author: "Ana".into(),
+            email: None,
+            sha256: String::new(),
+            note: None,
+        }
+    }
+
+    #[test]
+    fn prune_keeps_newest_per_env_and_respects_age() {
+        let now = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let snapshots = vec![
+            meta("dev", "d1", 200),
+            meta("prod", "p1", 1),
+            meta("prod", "p2", 50),
+            meta("prod", "p3", 120),
+        ];
+        let names = |keep, age| -> Vec<String> {
+            prune_candidates(&snapshots, keep, age, now)
+                .into_iter()
+                .map(|m| m.name.clone())
+                .collect()
+        };
+
+        assert_eq!(names(1, None), vec!["p2", "p3"]);
+        assert_eq!(names(1, Some(chrono::Duration::days(90))), vec!["p3"]);
+        assert_eq!(names(0, Some(chrono::Duration::days(90))), vec!["d1", "p3"]);
+    }
+
+    #[test]
+    fn parse_age_accepts_days_weeks_hours() {
+        assert_eq!(parse_age("90d").unwrap(), chrono::Duration::days(90));
+        assert_eq!(parse_age("2w").unwrap(), chrono::Duration::days(14));
+        assert_eq!(parse_age("48h").unwrap(), chrono::Duration::hours(48));
+        assert!(parse_age("90").is_err());
+        assert!(parse_age("d").is_err());
+        assert!(parse_age("").is_err());
+    }
+
+    #[test]
+    fn load_all_sorts_by_env_then_newest() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |env: &str, name: &str, ts: i64| {
+            let (_, meta_path) = snapshot_paths(dir.path(), env, name);
+            std::fs::create_dir_all(meta_path.parent().unwrap()).unwrap();
+            let meta = SnapshotMeta {
+                name: name.into(),
+                env: env.into(),
+                created_at: chrono::DateTime::from_timestamp(ts, 0).unwrap(),
+                author: "Ana".into(),
+                email: None,
+                sha256: String::new(),
+                note: None,
+            };
+            std::fs::write(meta_path, toml::to_string(&meta).unwrap()).unwrap();
+        };
+        write("prod", "old", 1);
+        write("prod", "new", 2);
+        write("dev", "a", 3);
+
+        let names: Vec<String> = load_all(dir.path(), None)
+            .unwrap()
+            .into_iter()
+            .map(|m| format!("{}/{}", m.env, m.name))
+            .collect();
+        assert_eq!(names, vec!["dev/a", "prod/new", "prod/old"]);
+        assert_eq!(load_all(dir.path(), Some("prod")).unwrap().len(), 2);
+    }
+}
@@ -1,277 +1,286 @@
 use std::path::Path;
 
-use colored::Colorize;
+use colored::{Color, Colorize};
+use serde::Serialize;
 
 use crate::adapters::audit::json_audit_logger::JsonAuditLogger;
 use crate::adapters::cipher::age_backend::AgeBackend;
+use crate::adapters::cipher::plugin_backend::PluginBackend;
 use crate::adapters::key_stores::file_key_store::FileKeyStore;
+use crate::adapters::key_stores::manifest_file::ManifestFile;
+use crate::adapters::key_stores::members_file::MembersFile;
 use crate::cli::output;
+use crate::cli::table::{Cell, Column, Table, truncate_middle};
 use crate::config::app_config::AppConfig;
 use crate::core::errors::{Result, VaulticError};
+use crate::core::models::key_identity::KeyType;
+use crate::core::models::manifest::UnrecordedChange;
+use crate::core::models::member::{EXPIRY_NOTICE_DAYS, Member, Members};
+use crate::core::models::status_report::{
+    AuditAnomaly, AuditStatus, EnvStatus, ExpiringRecipient, KeyStatus, LocalState, RotationStatus,
+    StatusProblem, StatusReport,
+};
+use crate::core::services::audit_anomaly_service::AuditAnomalyService;
 use crate::core::services::key_service::KeyService;
-use crate::core::services::secret_age_service::SecretAgeService;
+use crate::core::services::secret_age_service::{SecretAgeResult, SecretAgeService};
 use crate::core::traits::audit::AuditLogger;
 
 /// Execute the `vaultic status` command.
 ///
 /// Displays a full overview of the project state: configuration,
-/// keys, encrypted environments, and local file status.
+/// keys, encrypted environments, and local file status. With `--json`,
+/// the same overview is printed as a JSON document. Fails when the
+/// report has problems (no key, not a recipient, `.env` not ignored), so
+/// CI can gate on it.
 pub fn execute() -> Result<()> {
     let vaultic_dir = crate::cli::context::vaultic_dir();
     if !vaultic_dir.exists() {
-        return Err(VaulticError::InvalidConfig {
-            detail: "Vaultic not initialized. Run 'vaultic init' first.".into(),
-        });
+        return Err(crate::cli::first_run::not_initialized());
     }
 
-    let config = AppConfig::load(vaultic_dir)?;
-
-    // Project info
-    output::header(&format!("Vaultic v{}", config.vaultic.version));
-    println!("  Cipher: {}", config.vaultic.default_cipher.cyan());
-    println!("  Default env: {}", config.vaultic.default_env.cyan());
-    println!("  Config: .vaultic/config.toml");
-
-    // Your key
-    print_your_key(vaultic_dir);
+    let config = crate::cli::context::config()?;
+    let report = collect_status(config, vaultic_dir);
+    let problems = report.problems();
 
-    // Recipients
-    print_recipients(vaultic_dir);
-
-    // Encrypted environments
-    print_environments(&config, vaultic_dir);
+    if output::is_json_mode() {
+        output::json(&StatusJson {
+            ok: problems.is_empty(),
+            problems: &problems,
+            report: &report,
+        })?;
+    } else {
+        print_report(config, &report);
+        if !problems.is_empty() {
+            println!("\n{}", "  Problems".bold());
+            for problem in &problems {
+                output::warning(&problem.describe());
+            }
+        }
+    }
 
-    // Local state
-    print_local_state();
+    if !problems.is_empty() {
+        return Err(VaulticError::StatusProblemsFound {
+            count: problems.len(),
+        });
+    }
+    Ok(())
+}
 
-    // Audit status
-    print_audit_status(&config, vaultic_dir);
+/// `vaultic status --json` output.
+#[derive(Serialize)]
+struct StatusJson<'a> {
+    ok: bool,
+    problems: &'a [StatusProblem],
+    #[serde(flatten)]
+    report: &'a StatusReport,
+}
 
-    // Rotation policy
-    print_rotation_policy(&config, vaultic_dir);
+/// Print the human-readable overview.
+fn print_report(config: &AppConfig, report: &StatusReport) {
+    // Project info
+    output::header(&format!("Vaultic v{}", report.version));
+    match &report.cipher_plugin {
+        Some(program) => println!("  Cipher: {} (plugin: {program})", report.cipher.cyan()),
+        None => println!("  Cipher: {}", report.cipher.cyan()),
+    }
+    let default_env = report.default_env.as_deref().unwrap_or("(ask)");
+    println!("  Default env: {}", default_env.cyan());
+    println!("  Config: .vaultic/config.toml");
 
-    Ok(())
+    print_your_key(&report.key);
+    print_recipients(&report.recipients, &report.members, &report.expiring);
+    print_environments(&report.environments);
+    print_local_state(&report.local);
+    print_audit_status(&report.audit);
+    if let Some(policy_days) = config.vaultic.rotation_days {
+        print_rotation_policy(&report.rotation, policy_days);
+    }
+    print_anomalies(&report.anomalies);
+    print_unrecorded_changes(&report.unrecorded_changes);
 }
 
 /// Print the "Your key" section showing the user's key status.
-fn print_your_key(vaultic_dir: &Path) {
+fn print_your_key(key: &KeyStatus) {
     println!("\n{}", "  Your key".bold());
 
-    let identity_path = match AgeBackend::default_identity_path() {
-        Ok(p) => p,
-        Err(_) => {
-            output::warning("Could not determine key location");
-            return;
+    let Some(identity) = &key.identity else {
+        match crate::cli::context::identity_path() {
+            Ok(path) => output::warning(&format!("No private key at {}", path.display())),
+            Err(_) => output::warning("Could not determine key location"),
         }
+        println!("  Run 'vaultic keys setup' to configure your key.");
+        return;
     };
 
-    if !identity_path.exists() {
-        output::warning(&format!("No private key at {}", identity_path.display()));
-        println!("  Run 'vaultic keys setup' to configure your key.");
+    output::success(&format!("Private key: {identity}"));
+
+    let Some(public_key) = &key.public_key else {
+        output::warning("Could not read public key from identity file");
         return;
+    };
+    output::success(&format!("Public key: {}", truncate_middle(public_key, 50)));
+    if key.in_recipients {
+        output::success("You are in the recipients list");
+    } else {
+        output::warning("You are NOT in the recipients list");
+        println!("  Ask an admin to run: vaultic keys add {public_key}");
     }
 
-    output::success(&format!("Private key: {}", identity_path.display()));
-
-    match AgeBackend::read_public_key(&identity_path) {
-        Ok(public_key) => {
-            output::success(&format!("Public key: {}", truncate_key(&public_key, 50)));
-
-            // Check if user is in the recipients list
-            let store = FileKeyStore::new(vaultic_dir.join("recipients.txt"));
-            let service = KeyService { store };
-            match service.list_keys() {
-                Ok(keys) => {
-                    let in_list = keys.iter().any(|ki| ki.public_key == public_key);
-                    if in_list {
-                        output::success("You are in the recipients list");
-                    } else {
-                        output::warning("You are NOT in the recipients list");
-                        println!("  Ask an admin to run: vaultic keys add {public_key}");
-                    }
-                }
-                Err(_) => {
-                    output::warning("Could not check recipients list");
-                }
-            }
-        }
-        Err(_) => {
-            output::warning("Could not read public key from identity file");
+    // Several age keys or hybrid teams: list every key with what it can open
+    if key.identities.len() > 1 || key.identities.iter().any(|i| i.key_type == KeyType::Gpg) {
+        println!("\n  Keys on this machine:");
+        super::identity_helpers::print_identities(&key.identities);
+        if key.identities.len() > 1 && !key.identities.iter().any(|i| i.preferred) {
+            println!("  Pick one for this project with: vaultic keys use <key>");
         }
     }
 }
 
-/// Print the recipients section.
-fn print_recipients(vaultic_dir: &Path) {
-    let store = FileKeyStore::new(vaultic_dir.join("recipients.txt"));
-    let service = KeyService { store };
+/// Print the recipients section, then the keys past or near their
+/// expiry date.
+fn print_recipients(recipients: &[String], members: &[Member], expiring: &[ExpiringRecipient]) {
+    if recipients.is_empty() {
+        println!();
+        output::warning("No recipients configured");
+        println!("  Run 'vaultic keys add <public-key>' to add one.");
+        return;
+    }
 
-    match service.list_keys() {
-        Ok(keys) if keys.is_empty() => {
-            println!();
-            output::warning("No recipients configured");
-            println!("  Run 'vaultic keys add <public-key>' to add one.");
-        }
-        Ok(keys) => {
-            println!("\n{}", format!("  Recipients ({})", keys.len()).bold());
-            for ki in &keys {
-                let display = truncate_key(&ki.public_key, 40);
-                println!("  {} {display}", "•".dimmed());
-            }
-        }
-        Err(_) => {
-            output::warning("Could not read recipients");
-        }
+    println!(
+        "\n{}",
+        format!("  Recipients ({})", recipients.len()).bold()
+    );
+    let mut table = Table::new(vec![
+        Column::new(""),
+        Column::new("Key").shrink(20).middle(),
+        Column::new("Member").shrink(10),
+    ])
+    .separator(" ")
+    .headerless();
+    for key in recipients {
+        let who = members
+            .iter()
+            .find(|m| &m.key == key)
+            .and_then(Member::describe)
+            .unwrap_or_default();
+        table.row(vec![
+            Cell::new("•").dimmed(),
+            Cell::new(key.as_str()),
+            Cell::new(who).dimmed(),
+        ]);
+    }
+    table.print();
+    for recipient in expiring {
+        output::warning(&recipient.describe());
     }
 }
 
 /// Print the encrypted environments section.
-fn print_environments(config: &AppConfig, vaultic_dir: &Path) {
+fn print_environments(environments: &[EnvStatus]) {
     println!("\n{}", "  Encrypted environments".bold());
 
-    let mut envs: Vec<_> = config.environments.keys().collect();
-    envs.sort();
-
-    for env_name in envs {
-        let file_name = config.env_file_name(env_name);
-        let enc_path = vaultic_dir.join(format!("{file_name}.enc"));
-
-        if enc_path.exists() {
-            let meta = std::fs::metadata(&enc_path).ok();
-            let size = meta
-                .as_ref()
-                .map(|m| format_bytes(m.len()))
-                .unwrap_or_default();
-            println!(
-                "  {} {:<12} {} {}",
-                "✓".green(),
-                env_name,
-                format!("{file_name}.enc").dimmed(),
-                size.dimmed(),
-            );
+    let mut table = Table::new(vec![
+        Column::new(""),
+        Column::new("Environment").shrink(8),
+        Column::new("File").shrink(10),
+        Column::new("Size"),
+        Column::new("Recipients").shrink(10),
+    ])
+    .separator(" ")
+    .headerless();
+    for env in environments {
+        let access = if env.recipients_file != SHARED_RECIPIENTS {
+            Cell::new(format!("{} ({})", env.recipients_file, env.recipients)).color(Color::Yellow)
         } else {
-            println!(
-                "  {} {:<12} {}",
-                "✗".red(),
-                env_name,
-                "(not encrypted)".dimmed(),
-            );
+            Cell::new("").dimmed()
+        };
+
+        let file = match &env.mirror {
+            Some(origin) => format!("mirror of {origin}"),
+            None => env.file.clone(),
+        };
+        if env.encrypted {
+            table.row(vec![
+                Cell::new("✓").color(Color::Green),
+                Cell::new(env.name.as_str()),
+                Cell::new(file).dimmed(),
+                Cell::new(env.size.map(format_bytes).unwrap_or_default()).dimmed(),
+                access,
+            ]);
+        } else {
+            table.row(vec![
+                Cell::new("✗").color(Color::Red),
+                Cell::new(env.name.as_str()),
+                Cell::new("(not encrypted)").dimmed(),
+                Cell::new(""),
+                access,
+            ]);
         }
     }
+    table.print();
 }
 
 /// Print local file status (.env, .env.template, .gitignore).
-fn print_local_state() {
+fn print_local_state(local: &LocalState) {
     println!("\n{}", "  Local state".bold());
 
-    // .env
-    let env_path = Path::new(".env");
-    if env_path.exists() {
-        let content = std::fs::read_to_string(env_path).unwrap_or_default();
-        let var_count = count_variables(&content);
-        output::success(&format!(".env present ({var_count} variables)"));
-    } else {
-        output::warning(".env not found");
+    match local.env_variables {
+        Some(count) => output::success(&format!(".env present ({count} variables)")),
+        None => output::warning(".env not found"),
     }
-
-    // .env.template
-    let template_path = Path::new(".env.template");
-    if template_path.exists() {
-        let content = std::fs::read_to_string(template_path).unwrap_or_default();
-        let var_count = count_variables(&content);
-        output::success(&format!(".env.template present ({var_count} variables)"));
-    } else {
-        output::warning(".env.template not found");
+    match local.template_variables {
+        Some(count) => output::success(&format!(".env.template present ({count} variables)")),
+        None => output::warning(".env.template not found"),
     }
-
-    // .gitignore
-    let gitignore = Path::new(".gitignore");
-    if gitignore.exists() {
-        let content = std::fs::read_to_string(gitignore).unwrap_or_default();
-        if content.lines().any(|l| l.trim() == ".env") {
-            output::success(".env in .gitignore");
-        } else {
-            output::warning(".env NOT in .gitignore — secrets may be committed!");
-        }
-    } else {
-        output::warning("No .gitignore found");
+    match local.env_in_gitignore {
+        Some(true) => output::success(".env in .gitignore"),
+        Some(false) => output::warning(".env NOT in .gitignore — secrets may be committed!"),
+        None => output::warning("No .gitignore found"),
     }
 
-    // .dockerignore (only relevant if Docker files exist)
-    let has_docker = Path::new("Dockerfile").exists()
-        || Path::new("docker-compose.yml").exists()
-        || Path::new("docker-compose.yaml").exists();
-
-    if has_docker {
-        let dockerignore = Path::new(".dockerignore");
-        if dockerignore.exists() {
-            let content = std::fs::read_to_string(dockerignore).unwrap_or_default();
-            if content.lines().any(|l| l.trim() == ".env") {
-                output::success(".env in .dockerignore");
-            } else {
-                output::warning(".env NOT in .dockerignore — secrets may leak into Docker images!");
+    // .dockerignore only matters if Docker files exist
+    if local.docker {
+        match local.env_in_dockerignore {
+            Some(true) => output::success(".env in .dockerignore"),
+            Some(false) => {
+                output::warning(".env NOT in .dockerignore — secrets may leak into Docker images!")
             }
-        } else {
-            output::warning(
+            None => output::warning(
                 "No .dockerignore found — .env may leak into Docker images! \
                  Add '.env' to .dockerignore.",
-            );
+            ),
         }
     }
 }
 
 /// Print audit log status.
-fn print_audit_status(config: &AppConfig, vaultic_dir: &Path) {
-    let audit = config.audit.as_ref();
-    let enabled = audit.map(|a| a.enabled).unwrap_or(true);
-
-    if !enabled {
+fn print_audit_status(audit: &AuditStatus) {
+    if !audit.enabled {
         println!("\n{}", "  Audit: disabled".dimmed());
-        return;
-    }
-
-    let log_file = audit.map(|a| a.log_file.as_str()).unwrap_or("audit.log");
-    let log_path = vaultic_dir.join(log_file);
-
-    if log_path.exists() {
-        let content = std::fs::read_to_string(&log_path).unwrap_or_default();
-        let entry_count = content.lines().filter(|l| !l.trim().is_empty()).count();
+    } else if audit.entries > 0 {
         println!(
             "\n  {} Audit: {} entries in {}",
             "✓".green(),
-            entry_count,
-            log_file,
+            audit.entries,
+            audit.log_file,
         );
     } else {
-        println!("\n  {} Audit: no entries yet ({})", "—".dimmed(), log_file);
+        println!(
+            "\n  {} Audit: no entries yet ({})",
+            "—".dimmed(),
+            audit.log_file
+        );
     }
 }
 
-/// Print rotation policy warnings if `rotation_days` is configured.
-fn print_rotation_policy(config: &AppConfig, vaultic_dir: &Path) {
-    let Some(policy_days) = config.vaultic.rotation_days else {
-        return;
-    };
-
-    let log_file = config
-        .audit
-        .as_ref()
-        .map(|a| a.log_file.as_str())
-        .unwrap_or("audit.log");
-    let logger = JsonAuditLogger::new(vaultic_dir, log_file);
-
-    let entries = match logger.query(None, None) {
-        Ok(e) => e,
-        Err(_) => return,
-    };
-
-    let results = SecretAgeService::check_rotation(&entries, policy_days, chrono::Utc::now());
-    if results.is_empty() {
+/// Print rotation policy warnings under `rotation_days`.
+fn print_rotation_policy(rotation: &[RotationStatus], policy_days: u32) {
+    if rotation.is_empty() {
         return;
     }
 
     println!("\n{}", "  Rotation policy".bold());
-    for r in &results {
+    for r in rotation {
         let days = r.days_since_rotation.unwrap_or(0);
         let date_str = r
             .last_rotated
@@ -280,17 +289,284 @@ fn print_rotation_policy(config: &AppConfig, vaultic_dir: &Path) {
         if r.exceeds_policy {
             output::warning(&format!(
                 "{} — last encrypted {} days ago ({}) (policy: {} days)",
-                r.key, days, date_str, policy_days
+                r.env, days, date_str, policy_days
             ));
         } else {
             output::success(&format!(
                 "{} — last encrypted {} days ago ({}) — ok",
-                r.key, days, date_str
+                r.env, days, date_str
             ));
         }
     }
 }
 
+/// Print audit log anomalies as warnings.
+fn print_anomalies(anomalies: &[AuditAnomaly]) {
+    if anomalies.is_empty() {
+        return;
+    }
+
+    println!("\n{}", "  Audit review".bold());
+    for anomaly in anomalies {
+        output::warning(&anomaly.describe());
+    }
+}
+
+/// Print protected files edited outside Vaultic as warnings.
+fn print_unrecorded_changes(changes: &[UnrecordedChange]) {
+    if changes.is_empty() {
+        return;
+    }
+
+    println!("\n{}", "  Manifest".bold());
+    for change in changes {
+        output::warning(&change.describe());
+    }
+    println!(
+        "  Review with 'git diff .vaultic/', then run 'vaultic verify --accept-changes' to keep it."
+    );
+}
+
+/// Name of the recipient list shared by all environments.
+const SHARED_RECIPIENTS: &str = "recipients.txt";
+
+/// Gather everything `vaultic status` shows into a [`StatusReport`].
+fn collect_status(config: &AppConfig, vaultic_dir: &Path) -> StatusReport {
+    let shared = FileKeyStore::new(vaultic_dir.join(SHARED_RECIPIENTS));
+    let recipients: Vec<String> = KeyService {
+        store: shared.clone(),
+    }
+    .list_keys()
+    .map(|keys| keys.into_iter().map(|ki| ki.public_key).collect())
+    .unwrap_or_default();
+
+    let identities = super::identity_helpers::discover(config, vaultic_dir);
+    let key = match super::identity_helpers::active(&identities) {
+        Some(gpg) if gpg.key_type == KeyType::Gpg => KeyStatus {
+            identity: Some(gpg.source.clone()),
+            public_key: Some(gpg.id.clone()),
+            // A GPG key is often only on the lists of the environments
+            // encrypted with GPG
+            in_recipients: !gpg.envs.is_empty() || recipients.iter().any(|r| gpg.matches(r)),
+            identities: identities.clone(),
+        },
+        _ => {
+            let identity = crate::cli::context::identity_path()
+                .ok()
+                .filter(|p| p.exists());
+            let public_key = identity
+                .as_ref()
+                .and_then(|p| AgeBackend::read_public_key(p).ok());
+            KeyStatus {
+                in_recipients: public_key
+                    .as_ref()
+                    .is_some_and(|pk| recipients.contains(pk)),
+                identity: identity.map(|p| p.display().to_string()),
+                public_key,
+                identities,
+            }
+        }
+    };
+
+    let mut env_names: Vec<&String> = config.environments.keys().collect();
+    env_names.sort();
+    let environments = env_names
+        .into_iter()
+        .map(|env_name| {
+            let file = format!("{}.enc", config.env_file_name(env_name));
+            let mirror = config.mirror(env_name).map(String::from);
+            let path = match &mirror {
+                Some(origin) => super::mirror_helpers::local_copy(vaultic_dir, env_name, origin)
+                    .unwrap_or_default(),
+                None => vaultic_dir.join(&file),
+            };
+            let size = std::fs::metadata(path).ok().map(|m| m.len());
+            let store = shared.for_env(env_name);
+            EnvStatus {
+                name: env_name.clone(),
+                file,
+                encrypted: size.is_some(),
+                size,
+                recipients_file: store
+                    .path()
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                recipients: KeyService { store }
+                    .list_keys()
+                    .map(|keys| keys.len())
+                    .unwrap_or(0),
+                mirror,
+            }
+        })
+        .collect();
+
+    let var_count = |path: &str| {
+        std::fs::read_to_string(path)
+            .ok()
+            .map(|c| count_variables(&c))
+    };
+    let lists_env = |path: &str| {
+        std::fs::read_to_string(path)
+            .ok()
+            .map(|c| c.lines().any(|l| l.trim() == ".env"))
+    };
+    let local = LocalState {
+        env_variables: var_count(".env"),
+        template_variables: var_count(".env.template"),
+        env_in_gitignore: lists_env(".gitignore"),
+        env_in_dockerignore: lists_env(".dockerignore"),
+        docker: ["Dockerfile", "docker-compose.yml", "docker-compose.yaml"]
+            .iter()
+            .any(|f| Path::new(f).exists()),
+    };
+
+    let audit_section = config.audit.as_ref();
+    let log_file = audit_section
+        .map(|a| a.log_file.clone())
+        .unwrap_or_else(|| "audit.log".to_string());
+    let audit = AuditStatus {
+        enabled: audit_section.map(|a| a.enabled).unwrap_or(true),
+        entries: audit_entry_count(&vaultic_dir.join(&log_file)).unwrap_or(0),
+        log_file,
+    };
+
+    let rotation = config
+        .vaultic
+        .rotation_days
+        .map(|days| rotation_results(config, vaultic_dir, days))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|r| RotationStatus {
+            env: r.key,
+            last_rotated: r.last_rotated,
+            days_since_rotation: r.days_since_rotation,
+            exceeds_policy: r.exceeds_policy,
+        })
+        .collect();
+
+    let anomalies = audit_anomalies(config, vaultic_dir, &shared);
+    let members = MembersFile::new(vaultic_dir).load().unwrap_or_default();
+
+    StatusReport {
+        version: config.vaultic.version.clone(),
+        cipher: config.vaultic.default_cipher.clone(),
+        cipher_plugin: Some(config.vaultic.default_cipher.as_str())
+            .filter(|c| !matches!(*c, "age" | "gpg"))
+            .and_then(PluginBackend::find)
+            .map(|p| p.program().display().to_string()),
+        default_env: config.vaultic.default_env.clone(),
+        key,
+        expiring: expiring_recipients(config, vaultic_dir, &members),
+        unrecorded_changes: ManifestFile::new(vaultic_dir)
+            .changes()
+            .ok()
+            .flatten()
+            .unwrap_or_default(),
+        members: members
+            .members
+            .into_iter()
+            .filter(|m| recipients.contains(&m.key))
+            .collect(),
+        recipients,
+        environments,
+        local,
+        audit,
+        rotation,
+        anomalies,
+    }
+}
+
+/// Recipients of `recipients.txt` or a per-environment list whose
+/// `expires` date has passed or is within [`EXPIRY_NOTICE_DAYS`].
+fn expiring_recipients(
+    config: &AppConfig,
+    vaultic_dir: &Path,
+    members: &Members,
+) -> Vec<ExpiringRecipient> {
+    let today = crate::cli::context::clock().now().date_naive();
+    let lists = std::iter::once(vaultic_dir.join(SHARED_RECIPIENTS)).chain(
+        super::crypto_helpers::env_recipient_lists(vaultic_dir, config)
+            .into_iter()
+            .map(|(_, path)| path),
+    );
+    let mut expiring: Vec<ExpiringRecipient> = Vec::new();
+    for path in lists {
+        let keys = KeyService {
+            store: FileKeyStore::new(path),
+        }
+        .list_keys()
+        .unwrap_or_default();
+        for ki in keys {
+            let Some(member) = members.get(&ki.public_key) else {
+                continue;
+            };
+            if let (Some(expires), Some(days_left)) = (member.expires, member.days_left(today))
+                && days_left <= EXPIRY_NOTICE_DAYS
+                && !expiring.iter().any(|r| r.key == ki.public_key)
+            {
+                expiring.push(ExpiringRecipient {
+                    key: ki.public_key,
+                    expires,
+                    days_left,
+                });
+            }
+        }
+    }
+    expiring
+}
+
+/// Review the audit log, matching authors against the recipient labels
+/// of each environment.
+fn audit_anomalies(
+    config: &AppConfig,
+    vaultic_dir: &Path,
+    shared: &FileKeyStore,
+) -> Vec<AuditAnomaly> {
+    let logger = JsonAuditLogger::from_config(vaultic_dir, config.audit.as_ref());
+    let Ok(entries) = logger.query(None, None) else {
+        return Vec::new();
+    };
+    let labels_for = |env: &str| {
+        KeyService {
+            store: shared.for_env(env),
+        }
+        .list_keys()
+        .map(|keys| keys.into_iter().filter_map(|ki| ki.label).collect())
+        .unwrap_or_default()
+    };
+    AuditAnomalyService::detect(&entries, labels_for, crate::cli::context::clock().now())
+}
+
+/// Number of entries in the audit log, or `None` if it does not exist.
+fn audit_entry_count(log_path: &Path) -> Option<usize> {
+    let content = std::fs::read_to_string(log_path).ok()?;
+    Some(content.lines().filter(|l| !l.trim().is_empty()).count())
+}
+
+/// Rotation check for every encrypted environment against `policy_days`.
+fn rotation_results(
+    config: &AppConfig,
+    vaultic_dir: &Path,
+    policy_days: u32,
+) -> Vec<SecretAgeResult> {
+    let log_file = config
+        .audit
+        .as_ref()
+        .map(|a| a.log_file.as_str())
+        .unwrap_or("audit.log");
+    let logger = JsonAuditLogger::new(vaultic_dir, log_file);
+
+    match logger.query(None, None) {
+        Ok(entries) => SecretAgeService::check_rotation(
+            &entries,
+            policy_days,
+            crate::cli::context::clock().now(),
+        ),
+        Err(_) => Vec::new(),
+    }
+}
+
 /// Count variable definitions in a dotenv-style string.
 fn count_variables(content: &str) -> usize {
     content
@@ -302,19 +578,6 @@ fn count_variables(content: &str) -> usize {
         .count()
 }
 
-/// Truncate a key string for display, showing start and end.
-fn truncate_key(key: &str, max_len: usize) -> String {
-    let char_count = key.chars().count();
-    if char_count <= max_len {
-        key.to_string()
-    } else {
-        let keep = max_len.saturating_sub(3) / 2;
-        let start: String = key.chars().take(keep).collect();
-        let end: String = key.chars().skip(char_count - keep).collect();
-        format!("{start}...{end}")
-    }
-}
-
 /// Format a byte count as a human-readable string.
 fn format_bytes(bytes: u64) -> String {
     if bytes < 1024 {
@@ -323,39 +586,3 @@ fn format_bytes(bytes: u64) -> String {
         format!("({:.1} KB)", bytes as f64 / 1024.0)
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn truncate_key_short_string_unchanged() {
-        assert_eq!(truncate_key("abc", 10), "abc");
-    }
-
-    #[test]
-    fn truncate_key_exact_length_unchanged() {
-        assert_eq!(truncate_key("abcdefghij", 10), "abcdefghij");
-    }
-
-    #[test]
-    fn truncate_key_long_ascii() {
-        let result = truncate_key("abcdefghijklmnopqrst", 10);
-        assert!(result.contains("..."));
-        assert!(result.chars().count() <= 10);
-    }
-
-    #[test]
-    fn truncate_key_non_ascii_no_panic() {
-        let key = "María García <maria@example.com>";
-        let result = truncate_key(key, 15);
-        assert!(result.contains("..."));
-    }
-
-    #[test]
-    fn truncate_key_emoji_no_panic() {
-        let key = "🔑🔒🔐🔓🗝️🔑🔒🔐🔓🗝️";
-        let result = truncate_key(key, 5);
-        assert!(result.contains("..."));
-    }
-}
@@ -0,0 +1,141 @@
+use std::collections::BTreeMap;
+
+use colored::Color;
+
+use crate::adapters::remote_stores::aws::{ParameterStore, SecretsManagerStore};
+use crate::cli::SyncTarget;
+use crate::cli::commands::crypto_helpers;
+use crate::cli::output;
+use crate::cli::table::{Cell, Column, Table};
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::audit_entry::{AuditAction, AuditDetail};
+use crate::core::services::sync_service::{SyncPlan, SyncService};
+use crate::core::traits::remote_store::RemoteStore;
+
+/// Execute the `vaultic sync` command.
+///
+/// Resolves the environment in memory, compares it with the remote
+/// store, prints the planned changes by key name, and pushes them after
+/// confirmation unless `dry_run` is set.
+pub fn execute(target: &SyncTarget, env: Option<&str>, cipher: &str) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+
+    let config = crate::cli::context::config()?;
+    let env_name = &crate::cli::context::select_env(env, Some(config))?;
+
+    let SyncTarget::Aws {
+        service,
+        name,
+        region,
+        dry_run,
+    } = target;
+    let store: Box<dyn RemoteStore> = match service.as_str() {
+        "secrets-manager" => Box::new(SecretsManagerStore {
+            secret_id: name
+                .clone()
+                .unwrap_or_else(|| format!("vaultic/{env_name}")),
+            region: region.clone(),
+        }),
+        "ssm" => Box::new(ParameterStore {
+            prefix: name
+                .clone()
+                .unwrap_or_else(|| format!("/vaultic/{env_name}")),
+            region: region.clone(),
+        }),
+        other => {
+            return Err(VaulticError::InvalidConfig {
+                detail: format!("Unknown AWS service '{other}'. Use secrets-manager or ssm."),
+            });
+        }
+    };
+
+    let environment = crypto_helpers::resolve_env(env_name, vaultic_dir, cipher, config, true)?;
+    let local: BTreeMap<String, String> = environment
+        .resolved
+        .entries()
+        .map(|e| (e.key.clone(), e.value.clone()))
+        .collect();
+
+    let location = store.location();
+    output::header(&format!("Syncing {env_name} to {location}"));
+    let remote = store.fetch()?;
+    let plan = SyncService.plan(&local, &remote);
+
+    if plan.is_empty() {
+        output::success(&format!(
+            "{location} is already in sync ({} key(s))",
+            plan.unchanged
+        ));
+        return Ok(());
+    }
+    print_plan(&plan);
+
+    if *dry_run {
+        println!("\n  Dry run — nothing was pushed.");
+        return Ok(());
+    }
+    if !output::confirm(
+        &format!("Push {} change(s) to {location}?", plan.change_count()),
+        false,
+    )? {
+        output::warning("Cancelled — nothing was pushed.");
+        return Ok(());
+    }
+
+    store.push(&local, &plan.removed)?;
+    output::success(&format!(
+        "Pushed {} change(s) to {location}",
+        plan.change_count()
+    ));
+
+    super::audit_helpers::log_audit(
+        AuditAction::Sync,
+        vec![env_name.to_string()],
+        Some(AuditDetail::Sync {
+            location: location.to_string(),
+            added: plan.added.len(),
+            changed: plan.changed.len(),
+            removed: plan.removed.len(),
+        }),
+    );
+
+    Ok(())
+}
+
+/// Print the planned changes, one key per row, without values.
+fn print_plan(plan: &SyncPlan) {
+    let mut table = Table::new(vec![
+        Column::new("Key").shrink(12).middle(),
+        Column::new("Change"),
+    ])
+    .headerless();
+    for key in &plan.added {
+        table.row(vec![
+            Cell::new(format!("+ {key}")),
+            Cell::new("added").color(Color::Green),
+        ]);
+    }
+    for key in &plan.changed {
+        table.row(vec![
+            Cell::new(format!("~ {key}")),
+            Cell::new("changed").color(Color::Yellow),
+        ]);
+    }
+    for key in &plan.removed {
+        table.row(vec![
+            Cell::new(format!("- {key}")),
+            Cell::new("removed").color(Color::Red),
+        ]);
+    }
+    table.print();
+    output::detail(&format!("{} key(s) unchanged", plan.unchanged));
+    println!(
+        "\n  {} added, {} changed, {} removed",
+        plan.added.len(),
+        plan.changed.len(),
+        plan.removed.len()
+    );
+}
@@ -1,60 +1,37 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::adapters::cipher::age_backend::AgeBackend;
-use crate::adapters::key_stores::file_key_store::FileKeyStore;
+use crate::adapters::parsers;
 use crate::adapters::parsers::dotenv_parser::DotenvParser;
 use crate::cli::TemplateAction;
+use crate::cli::commands::crypto_helpers;
 use crate::cli::output;
-use crate::config::app_config::AppConfig;
+use crate::config::path_expand::expand_path;
 use crate::core::errors::{Result, VaulticError};
-use crate::core::models::audit_entry::AuditAction;
-use crate::core::services::encryption_service::EncryptionService;
+use crate::core::models::audit_entry::{AuditAction, AuditDetail};
 use crate::core::services::template_sync_service::TemplateSyncService;
 use crate::core::traits::parser::ConfigParser;
 
 /// Execute `vaultic template` subcommands.
-pub fn execute(action: &TemplateAction) -> Result<()> {
+pub fn execute(action: &TemplateAction, cipher: &str) -> Result<()> {
     match action {
         TemplateAction::Sync {
             output: output_path,
-        } => sync(output_path.as_deref()),
+        } => sync(output_path.as_deref(), cipher),
     }
 }
 
-/// Implement `vaultic template sync`.
+/// Implement `vaultic template sync` (alias `generate`).
 ///
-/// Decrypts all environments in memory, collects all keys (union),
-/// strips values, and writes the result to `.env.template` (or a custom path).
-fn sync(output_path: Option<&str>) -> Result<()> {
+/// Decrypts all environments in memory, collects all keys (union) along
+/// with the comments above them, strips values, and writes the result to
+/// the configured template (`.env.template` by default) or a custom path.
+fn sync(output_path: Option<&str>, cipher: &str) -> Result<()> {
     let vaultic_dir = crate::cli::context::vaultic_dir();
     if !vaultic_dir.exists() {
-        return Err(VaulticError::InvalidConfig {
-            detail: "Vaultic not initialized. Run 'vaultic init' first.".into(),
-        });
-    }
-
-    let config = AppConfig::load(vaultic_dir)?;
-
-    // Resolve the identity path — only age is supported for in-memory decryption
-    let identity_path = AgeBackend::default_identity_path()?;
-    if !identity_path.exists() {
-        return Err(VaulticError::EncryptionFailed {
-            reason: format!(
-                "No private key found at {}\n\n  Solutions:\n    \
-                 → New here? Run 'vaultic keys setup' to generate a key\n    \
-                 → Have a key? Use --key <path> to specify the location\n    \
-                 → Lost your key? Ask an admin to re-add you as a recipient",
-                identity_path.display()
-            ),
-        });
+        return Err(crate::cli::first_run::not_initialized());
     }
 
-    let key_store = FileKeyStore::new(vaultic_dir.join("recipients.txt"));
-    let backend = AgeBackend::new(identity_path);
-    let service = EncryptionService {
-        cipher: backend,
-        key_store,
-    };
+    let config = crate::cli::context::config()?;
     let parser = DotenvParser;
 
     let sp = output::spinner("Decrypting environments for template sync...");
@@ -76,9 +53,9 @@ fn sync(output_path: Option<&str>) -> Result<()> {
             continue;
         }
 
-        let plaintext_bytes = service.decrypt_to_bytes(&enc_path)?;
+        let plaintext_bytes = crypto_helpers::decrypt_in_memory(&enc_path, vaultic_dir, cipher)?;
         let content = String::from_utf8_lossy(&plaintext_bytes);
-        let secret_file = parser.parse(&content)?;
+        let secret_file = parsers::for_format(config.env_format(env_name)).parse(&content)?;
 
         output::detail(&format!(
             "Decrypted {env_name}: {} keys",
@@ -119,7 +96,11 @@ fn sync(output_path: Option<&str>) -> Result<()> {
     let serialized = parser.serialize(&template)?;
 
     // Write to output path
-    let dest = PathBuf::from(output_path.unwrap_or(".env.template"));
+    let dest = match (output_path, &config.vaultic.template) {
+        (Some(path), _) => PathBuf::from(path),
+        (None, Some(template)) => expand_path(template, Path::new("."))?,
+        (None, None) => PathBuf::from(".env.template"),
+    };
     std::fs::write(&dest, &serialized)?;
 
     output::success(&format!("Written {} keys to {}", key_count, dest.display()));
@@ -127,10 +108,10 @@ fn sync(output_path: Option<&str>) -> Result<()> {
     println!("  Run 'vaultic check' to verify your local .env is in sync.");
 
     // Audit
-    let detail = format!(
-        "{key_count} keys written from environments: {}",
-        processed.join(", ")
-    );
+    let detail = AuditDetail::TemplateSync {
+        keys: key_count,
+        envs: processed.iter().map(|e| e.to_string()).collect(),
+    };
     super::audit_helpers::log_audit(
         AuditAction::TemplateSync,
         vec![dest.display().to_string()],
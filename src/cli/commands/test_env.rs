@@ -0,0 +1,69 @@
+use crate::adapters::parsers::dotenv_parser::DotenvParser;
+use crate::cli::commands::crypto_helpers;
+use crate::cli::output;
+use crate::core::errors::Result;
+use crate::core::models::audit_entry::AuditDetail;
+use crate::core::services::test_env_service::TestEnvService;
+use crate::core::traits::parser::ConfigParser;
+
+/// Execute the `vaultic test-env` command.
+///
+/// Resolves the environment like `vaultic resolve`, then keeps only the
+/// keys allowed by `[test_env]` in config.toml and prefixes them, so
+/// integration tests get managed secrets without prod-only variables.
+pub fn execute(
+    env: Option<&str>,
+    cipher: &str,
+    prefix: Option<&str>,
+    output_path: &str,
+) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+
+    let config = crate::cli::context::config()?;
+    let env_name = &crate::cli::context::select_env(env, Some(config))?;
+    output::header(&format!("Building test env from: {env_name}"));
+
+    let parser = DotenvParser;
+    let environment = crypto_helpers::resolve_env(env_name, vaultic_dir, cipher, config, true)?;
+
+    let rules = config.test_env.clone().unwrap_or_default();
+    let prefix = prefix.or(rules.prefix.as_deref()).unwrap_or("");
+    let reduced = TestEnvService.filter(
+        &environment.resolved,
+        &rules.include,
+        &rules.exclude,
+        prefix,
+    );
+
+    let total = environment.resolved.keys().len();
+    let kept = reduced.keys().len();
+    output::detail(&format!(
+        "Include: {:?}, exclude: {:?}, prefix: '{prefix}'",
+        rules.include, rules.exclude
+    ));
+
+    let content = parser.serialize(&reduced)?;
+    std::fs::write(output_path, &content)?;
+
+    output::success(&format!(
+        "Kept {kept} of {total} variable(s), dropped {}",
+        total - kept
+    ));
+    output::success(&format!("Written to {output_path}"));
+    println!("\n  Add {output_path} to .gitignore; it contains plaintext secrets.");
+
+    super::audit_helpers::log_audit(
+        crate::core::models::audit_entry::AuditAction::Resolve,
+        vec![env_name.to_string()],
+        Some(AuditDetail::TestEnv {
+            kept,
+            total,
+            dest: output_path.to_string(),
+        }),
+    );
+
+    Ok(())
+}
@@ -2,14 +2,18 @@ use crate::adapters::updater::github_updater;
 use crate::adapters::updater::verifier;
 use crate::cli::output;
 use crate::core::errors::Result;
-use crate::core::models::update_info::current_version;
+use crate::core::models::update_info::{current_version, summarize_notes};
 
 /// Execute the `vaultic update` command.
 ///
 /// Checks for a newer release on GitHub, downloads the binary for the
-/// current platform, verifies its SHA256 checksum and minisign signature,
-/// and replaces the running binary.
-pub fn execute() -> Result<()> {
+/// current platform (with a progress bar, resuming an interrupted
+/// download), verifies its SHA256 checksum and minisign signature, and
+/// replaces the running binary.
+///
+/// With `check_only`, stops after showing the new version and the top of
+/// its release notes.
+pub fn execute(check_only: bool) -> Result<()> {
     output::header("🔄 Vaultic — Update");
 
     // 1. Check for newer version
@@ -24,6 +28,13 @@ pub fn execute() -> Result<()> {
                     info.version
                 ),
             );
+            if let Some(notes) = info
+                .notes
+                .as_deref()
+                .and_then(|n| summarize_notes(n, github_updater::NOTES_SUMMARY_LINES))
+            {
+                output::quote(&notes);
+            }
             info
         }
         None => {
@@ -32,10 +43,31 @@ pub fn execute() -> Result<()> {
         }
     };
 
+    if check_only {
+        output::success(&format!("Full notes: {}", info.release_url));
+        println!("\n  Run 'vaultic update' to install.");
+        return Ok(());
+    }
+
     // 2. Download binary, checksums, and signature
-    let sp = output::spinner(&format!("Downloading {}...", info.asset_name));
-    let binary_data = github_updater::download_bytes(&info.asset_url)?;
-    output::finish_spinner(sp, &format!("Downloaded {} bytes", binary_data.len()));
+    let part_path = github_updater::partial_download_path(&info.asset_name, &info.version)?;
+    if let Ok(meta) = std::fs::metadata(&part_path) {
+        output::success(&format!(
+            "Resuming earlier download ({} bytes already on disk)",
+            meta.len()
+        ));
+    }
+    let bar = output::download_bar(&format!("Downloading {}", info.asset_name));
+    let binary_data =
+        github_updater::download_resumable(&info.asset_url, &part_path, |done, total| {
+            if let Some(pb) = &bar {
+                if let Some(total) = total {
+                    pb.set_length(total);
+                }
+                pb.set_position(done);
+            }
+        })?;
+    output::finish_spinner(bar, &format!("Downloaded {} bytes", binary_data.len()));
 
     let sp = output::spinner("Downloading verification files...");
     let checksums_data = github_updater::download_bytes(&info.checksums_url)?;
@@ -50,7 +82,11 @@ pub fn execute() -> Result<()> {
     // 4. Verify SHA256 checksum of the binary
     let sp = output::spinner("Verifying SHA256 checksum...");
     let checksums_str = String::from_utf8_lossy(&checksums_data);
-    verifier::verify_sha256(&binary_data, &info.asset_name, &checksums_str)?;
+    let verified = verifier::verify_sha256(&binary_data, &info.asset_name, &checksums_str);
+    // Whether it matched or not, the partial file has served its purpose;
+    // a corrupt one must not be resumed next time.
+    let _ = std::fs::remove_file(&part_path);
+    verified?;
     output::finish_spinner(sp, "Checksum verified");
 
     // 5. Write to unique temp file and replace the running binary
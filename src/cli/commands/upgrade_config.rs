@@ -0,0 +1,319 @@
+use crate::cli::output;
+use crate::core::errors::{Result, VaulticError};
+
+/// An optional setting `upgrade-config` can suggest.
+struct Setting {
+    /// Key inside `[vaultic]`, or the name of a top-level table.
+    name: &'static str,
+    /// Whether `name` is a table rather than a `[vaultic]` key.
+    table: bool,
+    /// Commented-out example, one `#` line per line.
+    example: &'static str,
+}
+
+/// Every optional setting, in the order it is added to config.toml.
+const SETTINGS: &[Setting] = &[
+    Setting {
+        name: "default_env",
+        table: false,
+        example: "# Environment used when --env is omitted\n\
+                  # default_env = \"dev\"",
+    },
+    Setting {
+        name: "template",
+        table: false,
+        example: "# Template checked by 'vaultic check' (default: auto-discovered)\n\
+                  # template = \".env.template\"",
+    },
+    Setting {
+        name: "identity",
+        table: false,
+        example: "# age identity file (default: ~/.config/age/keys.txt)\n\
+                  # identity = \"~/.config/age/project-keys.txt\"",
+    },
+    Setting {
+        name: "rotation_days",
+        table: false,
+        example: "# Warn in 'vaultic status' when an environment is older than this\n\
+                  # rotation_days = 90",
+    },
+    Setting {
+        name: "storage",
+        table: false,
+        example: "# \"per-value\" keeps keys readable in .enc files so diffs show what changed\n\
+                  # storage = \"file\"",
+    },
+    Setting {
+        name: "audit",
+        table: true,
+        example: "# Audit log of Vaultic operations ('vaultic log')\n\
+                  # [audit]\n\
+                  # enabled = true\n\
+                  # log_file = \"audit.log\"",
+    },
+    Setting {
+        name: "validation",
+        table: true,
+        example: "# Format rules checked by 'vaultic validate'\n\
+                  # [validation]\n\
+                  # DATABASE_URL = { type = \"url\", required = true }\n\
+                  # PORT = { type = \"integer\", min = 1024, max = 65535 }",
+    },
+    Setting {
+        name: "owners",
+        table: true,
+        example: "# Owning team per key, shown by 'vaultic report owners'\n\
+                  # [owners]\n\
+                  # \"STRIPE_*\" = \"payments\"\n\
+                  # \"*\" = \"infra\"",
+    },
+    Setting {
+        name: "test_env",
+        table: true,
+        example: "# Keys kept by 'vaultic test-env'\n\
+                  # [test_env]\n\
+                  # include = [\"DB_*\", \"API_URL\"]\n\
+                  # exclude = [\"STRIPE_*\"]\n\
+                  # prefix = \"TEST_\"",
+    },
+    Setting {
+        name: "recipients",
+        table: true,
+        example: "# Require recipients.txt to be signed by an admin (minisign)\n\
+                  # [recipients]\n\
+                  # admin_keys = [\"RWQ...\"]\n\
+                  # enforce = true",
+    },
+    Setting {
+        name: "history",
+        table: true,
+        example: "# Previous ciphertexts kept per environment for 'vaultic rollback'\n\
+                  # Set max_shrink_percent to how many variables 'vaultic encrypt' may lose\n\
+                  # [history]\n\
+                  # keep_versions = 20\n\
+                  # max_shrink_percent = 50",
+    },
+    Setting {
+        name: "ui",
+        table: true,
+        example: "# Language of Vaultic's messages: en or es (VAULTIC_LANG overrides it)\n\
+                  # Set redact_values = true to refuse 'diff --show-values'\n\
+                  # [ui]\n\
+                  # language = \"es\"\n\
+                  # redact_values = true",
+    },
+    Setting {
+        name: "references",
+        table: true,
+        example: "# Providers for op:// and vault:// values, fetched by 'resolve --materialize'\n\
+                  # [references]\n\
+                  # materialize = false\n\
+                  # vault = { address = \"https://vault.example.com:8200\" }\n\
+                  # op = { account = \"my-team.1password.com\" }",
+    },
+    Setting {
+        name: "leases",
+        table: true,
+        example: "# Short-lived credentials requested by 'vaultic run' and refreshed before they expire\n\
+                  # [leases]\n\
+                  # aws = { provider = \"aws-sts\", role_arn = \"arn:aws:iam::123456789012:role/deploy\" }",
+    },
+    Setting {
+        name: "hooks",
+        table: true,
+        example: "# Commands run around encrypt, decrypt and resolve, without any secrets in their environment\n\
+                  # [hooks]\n\
+                  # pre_encrypt = \"./scripts/lint-env.sh\"\n\
+                  # post_resolve = \"./scripts/restart-dev.sh\"\n\
+                  # timeout_seconds = 30",
+    },
+    Setting {
+        name: "aliases",
+        table: true,
+        example: "# Custom commands, run as 'vaultic deploy-prep'\n\
+                  # [aliases]\n\
+                  # deploy-prep = \"resolve --env prod -o deploy/.env\"",
+    },
+    Setting {
+        name: "outputs",
+        table: true,
+        example: "# Generated files checked by 'vaultic check --all-outputs'\n\
+                  # [outputs]\n\
+                  # backend = { path = \"backend/.env\" }\n\
+                  # web = { path = \"apps/web/.env.local\", template = \"apps/web/.env.example\" }",
+    },
+    Setting {
+        name: "policy",
+        table: true,
+        example: "# Rules on secret values enforced by 'vaultic check --policy'\n\
+                  # [policy]\n\
+                  # secret_keys = [\"*_SECRET\", \"*_KEY\"]\n\
+                  # min_length = 16\n\
+                  # strict_envs = [\"prod\"]\n\
+                  # max_age_days = 90",
+    },
+];
+
+/// Execute the `vaultic upgrade-config` command.
+///
+/// Adds a commented-out example for every optional setting that
+/// config.toml neither sets nor already mentions in a comment. Existing
+/// lines are never changed, so the result parses exactly as before.
+pub fn execute(dry_run: bool) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+    // Refuse to touch a config that does not load today
+    crate::cli::context::config()?;
+
+    let path = vaultic_dir.join("config.toml");
+    let content = std::fs::read_to_string(&path)?;
+    let (upgraded, added) = upgrade(&content)?;
+
+    if added.is_empty() {
+        output::success("config.toml already mentions every supported setting");
+        return Ok(());
+    }
+
+    let names = added.join(", ");
+    if dry_run {
+        output::header("Settings that would be added to config.toml (commented out)");
+        for setting in SETTINGS.iter().filter(|s| added.contains(&label(s))) {
+            println!("\n{}", setting.example);
+        }
+        println!("\n  Dry run — config.toml was not changed.");
+        return Ok(());
+    }
+
+    std::fs::write(&path, upgraded)?;
+    super::audit_helpers::record_manifest(vaultic_dir);
+    output::success(&format!("Added commented examples for: {names}"));
+    println!("\n  Uncomment the settings you need in {}.", path.display());
+
+    Ok(())
+}
+
+/// How a setting is named in messages: `rotation_days` or `[owners]`.
+fn label(setting: &Setting) -> String {
+    if setting.table {
+        format!("[{}]", setting.name)
+    } else {
+        setting.name.to_string()
+    }
+}
+
+/// Return `content` with examples for the missing settings, and their
+/// labels. `[vaultic]` keys go at the end of that table so they stay in
+/// it once uncommented; tables are appended to the file.
+fn upgrade(content: &str) -> Result<(String, Vec<String>)> {
+    let parsed: toml::Table = toml::from_str(content).map_err(|e| VaulticError::InvalidConfig {
+        detail: format!("Failed to parse config.toml: {e}"),
+    })?;
+    let vaultic = parsed.get("vaultic").and_then(|v| v.as_table());
+
+    let missing: Vec<&Setting> = SETTINGS
+        .iter()
+        .filter(|s| {
+            let set = if s.table {
+                parsed.contains_key(s.name)
+            } else {
+                vaultic.is_some_and(|t| t.contains_key(s.name))
+            };
+            !set && !mentioned(content, s)
+        })
+        .collect();
+
+    let mut lines: Vec<String> = content.lines().map(String::from).collect();
+
+    let keys: Vec<&str> = missing
+        .iter()
+        .filter(|s| !s.table)
+        .map(|s| s.example)
+        .collect();
+    if !keys.is_empty()
+        && let Some(at) = vaultic_section_end(&lines)
+    {
+        let block = format!("\n{}", keys.join("\n\n"));
+        lines.splice(at..at, block.lines().map(String::from));
+    }
+
+    for setting in missing.iter().filter(|s| s.table) {
+        lines.push(String::new());
+        lines.extend(setting.example.lines().map(String::from));
+    }
+
+    let mut upgraded = lines.join("\n");
+    upgraded.push('\n');
+    Ok((upgraded, missing.iter().map(|s| label(s)).collect()))
+}
+
+/// Whether config.toml already has a commented-out form of `setting`.
+fn mentioned(content: &str, setting: &Setting) -> bool {
+    content.lines().any(|line| {
+        let Some(rest) = line.trim_start().strip_prefix('#') else {
+            return false;
+        };
+        let rest = rest.trim_start();
+        if setting.table {
+            rest.starts_with(&format!("[{}]", setting.name))
+        } else {
+            rest.strip_prefix(setting.name)
+                .is_some_and(|r| r.trim_start().starts_with('='))
+        }
+    })
+}
+
+/// Index just past the last non-blank line of the `[vaultic]` table.
+fn vaultic_section_end(lines: &[String]) -> Option<usize> {
+    let start = lines.iter().position(|l| l.trim() == "[vaultic]")?;
+    let next = lines[start + 1..]
+        .iter()
+        .position(|l| l.trim_start().starts_with('['))
+        .map_or(lines.len(), |i| start + 1 + i);
+    let mut end = next;
+    while end > start + 1 && lines[end - 1].trim().is_empty() {
+        end -= 1;
+    }
+    Some(end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::app_config::AppConfig;
+
+    const CONFIG: &str = "[vaultic]\nversion = \"0.1.0\"\ndefault_cipher = \"age\"\n\
+                          default_env = \"dev\"\n\n[environments]\ndev = {}\n\n\
+                          [audit]\nenabled = true\nlog_file = \"audit.log\"\n";
+
+    #[test]
+    fn adds_missing_settings_without_changing_existing_ones() {
+        let (upgraded, added) = upgrade(CONFIG).unwrap();
+
+        assert!(!added.contains(&"default_env".to_string()));
+        assert!(!added.contains(&"[audit]".to_string()));
+        assert!(added.contains(&"rotation_days".to_string()));
+        assert!(added.contains(&"[outputs]".to_string()));
+        for line in CONFIG.lines() {
+            assert!(upgraded.contains(line));
+        }
+
+        // [vaultic] keys stay inside [vaultic]
+        let rotation = upgraded.find("# rotation_days").unwrap();
+        assert!(rotation < upgraded.find("[environments]").unwrap());
+
+        let before = AppConfig::parse(CONFIG).unwrap();
+        let after = AppConfig::parse(&upgraded).unwrap();
+        assert_eq!(before.vaultic.default_env, after.vaultic.default_env);
+        assert!(after.owners.is_none());
+    }
+
+    #[test]
+    fn upgrade_is_idempotent() {
+        let (once, _) = upgrade(CONFIG).unwrap();
+        let (twice, added) = upgrade(&once).unwrap();
+        assert!(added.is_empty());
+        assert_eq!(once, twice);
+    }
+}
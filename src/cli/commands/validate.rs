@@ -5,9 +5,8 @@ use colored::Colorize;
 
 use crate::adapters::parsers::dotenv_parser::DotenvParser;
 use crate::cli::output;
-use crate::config::app_config::AppConfig;
 use crate::core::errors::{Result, VaulticError};
-use crate::core::models::audit_entry::AuditAction;
+use crate::core::models::audit_entry::{AuditAction, AuditDetail};
 use crate::core::services::validation_service::ValidationService;
 use crate::core::traits::parser::ConfigParser;
 
@@ -30,12 +29,10 @@ pub fn execute(file: Option<&str>) -> Result<()> {
 
     let vaultic_dir = crate::cli::context::vaultic_dir();
     if !vaultic_dir.exists() {
-        return Err(VaulticError::InvalidConfig {
-            detail: "Vaultic not initialized. Run 'vaultic init' first.".into(),
-        });
+        return Err(crate::cli::first_run::not_initialized());
     }
 
-    let config = AppConfig::load(vaultic_dir)?;
+    let config = crate::cli::context::config()?;
 
     let rules = match config.validation.as_ref() {
         Some(r) if !r.is_empty() => r,
@@ -98,7 +95,10 @@ pub fn execute(file: Option<&str>) -> Result<()> {
     println!("  {passed_count}/{total} rules passed");
 
     // Audit
-    let detail = format!("{passed_count} passed, {failed_count} failed");
+    let detail = AuditDetail::Validate {
+        passed: passed_count,
+        failed: failed_count,
+    };
     super::audit_helpers::log_audit(
         AuditAction::Validate,
         vec![file_path_str.to_string()],
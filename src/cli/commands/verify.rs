@@ -0,0 +1,381 @@
+use std::path::Path;
+
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::adapters::audit::json_audit_logger::JsonAuditLogger;
+use crate::adapters::cipher::age_backend::AgeBackend;
+use crate::adapters::cipher::threshold_backend::Envelope;
+use crate::adapters::key_stores::file_key_store::FileKeyStore;
+use crate::adapters::key_stores::manifest_file::{MANIFEST_FILE, ManifestFile};
+use crate::cli::commands::junit_helpers::{self, TestCase, TestSuite};
+use crate::cli::output;
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::audit_entry::AuditAction;
+use crate::core::models::manifest::{ChainBreak, UnrecordedChange};
+use crate::core::models::verification::{Access, EnvVerification};
+use crate::core::services::encryption_service;
+use crate::core::services::key_service::KeyService;
+use crate::core::services::verify_service::{LocalKey, VerifyService};
+use crate::core::traits::audit::AuditLogger;
+
+/// Execute the `vaultic verify` command.
+///
+/// Checks the header of every encrypted environment (or those in `envs`)
+/// against its recipients list. Fails when a listed recipient cannot
+/// decrypt a file or a removed one still can, or when `config.toml` or a
+/// recipient list no longer matches `manifest.toml`. `report` (`json` or
+/// `junit`) is emitted on stdout or into `output_path`.
+pub fn execute(
+    envs: &[String],
+    cipher: &str,
+    report: Option<&str>,
+    output_path: Option<&str>,
+) -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+    if cipher != "age" {
+        return Err(VaulticError::InvalidConfig {
+            detail: format!(
+                "vaultic verify reads age file headers; cipher '{cipher}' is not supported"
+            ),
+        });
+    }
+
+    let config = crate::cli::context::config()?;
+    for env in envs {
+        config.require_env(env)?;
+    }
+    let mut names: Vec<&String> = if envs.is_empty() {
+        config.environments.keys().collect()
+    } else {
+        envs.iter().collect()
+    };
+    names.sort();
+    names.dedup();
+
+    let entries = JsonAuditLogger::from_config(vaultic_dir, config.audit.as_ref())
+        .query(None, None)
+        .unwrap_or_default();
+    // Your key is only identified from the identity file; an inline
+    // VAULTIC_AGE_KEY is still used to decrypt.
+    let local_public_key = crate::cli::context::identity_path()
+        .ok()
+        .and_then(|p| AgeBackend::read_public_key(&p).ok());
+    let backend = super::crypto_helpers::age_backend().ok();
+    let shared = FileKeyStore::new(vaultic_dir.join("recipients.txt"));
+
+    let mut reports = Vec::new();
+    for name in names {
+        if config.mirror(name).is_some() {
+            output::detail(&format!("Skipping mirror '{name}'"));
+            continue;
+        }
+        let file = super::audit_helpers::env_enc_file(vaultic_dir, config, name);
+        let Ok(ciphertext) = std::fs::read(vaultic_dir.join(&file)) else {
+            if !envs.is_empty() {
+                output::warning(&format!(
+                    "No encrypted file for '{name}' ({file}) — skipping"
+                ));
+            }
+            continue;
+        };
+        // A threshold envelope holds one age file per shareholder
+        let envelope = Envelope::parse(&ciphertext).transpose()?;
+        let parts: Vec<&[u8]> = match &envelope {
+            Some(envelope) => envelope.shares.iter().map(|s| s.share.as_bytes()).collect(),
+            None => vec![&ciphertext],
+        };
+        let mut stanzas = Vec::new();
+        for part in &parts {
+            stanzas.extend(AgeBackend::header_stanzas(part)?);
+        }
+
+        let mut recipients = KeyService {
+            store: shared.for_env(name),
+        }
+        .list_keys()?;
+        for ki in &mut recipients {
+            ki.added_at = super::audit_helpers::key_added_at(&entries, &ki.public_key);
+        }
+        let local =
+            local_public_key
+                .as_deref()
+                .zip(backend.as_ref())
+                .map(|(public_key, backend)| LocalKey {
+                    public_key,
+                    decrypts: parts
+                        .iter()
+                        .any(|part| encryption_service::decrypt_content(backend, part).is_ok()),
+                });
+
+        reports.push(VerifyService::verify(
+            name,
+            &file,
+            &stanzas,
+            &recipients,
+            super::audit_helpers::last_encrypted_at(&entries, &file),
+            local,
+            AgeBackend::ssh_tag,
+        ));
+    }
+
+    // Projects from before the manifest have nothing to compare with
+    let changes = ManifestFile::new(vaultic_dir)
+        .changes()?
+        .unwrap_or_default();
+
+    let failed = reports.iter().filter(|r| !r.is_ok()).count();
+    let json = VerifyJson {
+        ok: failed == 0 && changes.is_empty(),
+        environments: &reports,
+        unrecorded_changes: &changes,
+    };
+    if let Some(format) = report {
+        junit_helpers::emit(
+            format,
+            "vaultic verify",
+            || to_suites(&reports, &changes),
+            &json,
+            output_path,
+        )?;
+    }
+    if junit_helpers::stdout_free(report, output_path) {
+        if output::is_json_mode() {
+            output::json(&json)?;
+        } else {
+            print_reports(&reports);
+            print_changes(&changes);
+        }
+    }
+
+    if failed > 0 {
+        return Err(VaulticError::VerifyFailed { count: failed });
+    }
+    if !changes.is_empty() {
+        return Err(VaulticError::UnrecordedChanges {
+            count: changes.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Execute `vaultic verify --accept-changes`.
+///
+/// Records `config.toml` and the recipient lists as they are now in
+/// `manifest.toml`, after someone reviewed edits made outside Vaultic,
+/// and restarts the state chain of every environment whose encrypted
+/// file was replaced behind Vaultic's back. Logs which files were
+/// accepted. Also creates the manifest for projects that have none yet.
+pub fn execute_accept_changes() -> Result<()> {
+    let vaultic_dir = crate::cli::context::vaultic_dir();
+    if !vaultic_dir.exists() {
+        return Err(crate::cli::first_run::not_initialized());
+    }
+
+    let manifest = ManifestFile::new(vaultic_dir);
+    let changes = manifest.changes()?;
+    let broken = broken_chains(vaultic_dir, &manifest)?;
+    if changes.as_ref().is_some_and(Vec::is_empty) && broken.is_empty() {
+        output::success(
+            "config.toml, the recipient lists and the encrypted files match the manifest",
+        );
+        return Ok(());
+    }
+    let created = changes.is_none();
+    let changes = changes.unwrap_or_default();
+    for change in &changes {
+        output::warning(&change.describe());
+    }
+    for chain in &broken {
+        for chain_break in &chain.breaks {
+            output::warning(&chain_break.describe(&chain.env));
+        }
+    }
+    let now = crate::cli::context::clock().now();
+    manifest.record(now)?;
+    for chain in &broken {
+        manifest.restart_chain(&chain.env, chain.current.clone(), now)?;
+    }
+    let config = crate::cli::context::config()?;
+    super::audit_helpers::log_audit(
+        AuditAction::ManifestAccept,
+        changes
+            .iter()
+            .map(|c| c.file.clone())
+            .chain(
+                broken.iter().map(|chain| {
+                    super::audit_helpers::env_enc_file(vaultic_dir, config, &chain.env)
+                }),
+            )
+            .collect(),
+        None,
+    );
+
+    let accepted = changes.len() + broken.len();
+    if created {
+        output::success(&format!("Created .vaultic/{MANIFEST_FILE}"));
+    } else {
+        output::success(&format!(
+            "Recorded {accepted} change(s) in .vaultic/{MANIFEST_FILE}"
+        ));
+    }
+    println!("  Commit .vaultic/ to the repo.");
+    Ok(())
+}
+
+/// An environment whose state chain in the manifest breaks.
+struct BrokenChain {
+    env: String,
+    breaks: Vec<ChainBreak>,
+    /// Checksum of its encrypted file now.
+    current: Option<String>,
+}
+
+/// Environments whose state chain in the manifest breaks.
+fn broken_chains(vaultic_dir: &Path, manifest: &ManifestFile) -> Result<Vec<BrokenChain>> {
+    let envs: Vec<String> = manifest
+        .load()?
+        .map(|m| m.chains.into_keys().collect())
+        .unwrap_or_default();
+    let mut broken = Vec::new();
+    for env in envs {
+        let (current, breaks) = super::history::chain_breaks(vaultic_dir, &env)?;
+        let breaks = breaks.unwrap_or_default();
+        if !breaks.is_empty() {
+            broken.push(BrokenChain {
+                env,
+                breaks,
+                current,
+            });
+        }
+    }
+    Ok(broken)
+}
+
+/// `vaultic verify --json` output.
+#[derive(Serialize)]
+struct VerifyJson<'a> {
+    ok: bool,
+    environments: &'a [EnvVerification],
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    unrecorded_changes: &'a [UnrecordedChange],
+}
+
+/// One JUnit suite per environment, with a test case per recipient and
+/// a failed one for stanzas of keys no longer listed, plus a suite for
+/// the manifest when files changed outside Vaultic.
+fn to_suites(reports: &[EnvVerification], changes: &[UnrecordedChange]) -> Vec<TestSuite> {
+    let mut suites: Vec<TestSuite> = reports
+        .iter()
+        .map(|report| {
+            let mut cases: Vec<TestCase> = report
+                .recipients
+                .iter()
+                .map(|r| {
+                    let name = match &r.label {
+                        Some(label) => format!("{} ({label})", r.key),
+                        None => r.key.clone(),
+                    };
+                    match r.access {
+                        Access::Yes | Access::Likely => TestCase::passed(name),
+                        Access::No => TestCase::failed(name, "no-access", r.reason.as_str()),
+                        Access::Unknown => TestCase::failed(name, "unknown", r.reason.as_str()),
+                    }
+                })
+                .collect();
+            if report.unlisted_stanzas > 0 {
+                cases.push(TestCase::failed(
+                    "unlisted recipients",
+                    "unlisted",
+                    format!(
+                        "{} stanza(s) for keys no longer listed",
+                        report.unlisted_stanzas
+                    ),
+                ));
+            }
+            TestSuite {
+                name: report.env.clone(),
+                cases,
+            }
+        })
+        .collect();
+    if !changes.is_empty() {
+        suites.push(TestSuite {
+            name: MANIFEST_FILE.to_string(),
+            cases: changes
+                .iter()
+                .map(|c| TestCase::failed(c.file.as_str(), "unrecorded", c.describe()))
+                .collect(),
+        });
+    }
+    suites
+}
+
+/// Print files changed outside Vaultic, if any.
+fn print_changes(changes: &[UnrecordedChange]) {
+    if changes.is_empty() {
+        return;
+    }
+    output::header("  Manifest");
+    for change in changes {
+        println!("    {} {}", "✗".red(), change.describe().red());
+    }
+}
+
+fn print_reports(reports: &[EnvVerification]) {
+    if reports.is_empty() {
+        output::warning("No encrypted environments to verify");
+        return;
+    }
+    for report in reports {
+        output::header(&format!("  {} ({})", report.env, report.file));
+        for r in &report.recipients {
+            let mark = match r.access {
+                Access::Yes => "✓".green(),
+                Access::Likely => "✓".yellow(),
+                Access::No => "✗".red(),
+                Access::Unknown => "?".yellow(),
+            };
+            let label = r
+                .label
+                .as_ref()
+                .map(|l| format!(" # {l}"))
+                .unwrap_or_default();
+            println!(
+                "    {mark} {}{} {}",
+                r.key,
+                label.dimmed(),
+                format!("— {}", r.reason).dimmed()
+            );
+        }
+        if report.unlisted_stanzas > 0 {
+            println!(
+                "    {} {}",
+                "✗".red(),
+                format!(
+                    "{} stanza(s) for keys no longer listed: removed recipients can still decrypt",
+                    report.unlisted_stanzas
+                )
+                .red()
+            );
+        }
+    }
+
+    let failed: Vec<&str> = reports
+        .iter()
+        .filter(|r| !r.is_ok())
+        .map(|r| r.env.as_str())
+        .collect();
+    println!();
+    if failed.is_empty() {
+        output::success(&format!(
+            "{} environment(s) readable by exactly their recipients",
+            reports.len()
+        ));
+    } else {
+        output::warning(&format!("Re-encrypt: {}", failed.join(", ")));
+    }
+}
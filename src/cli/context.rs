@@ -1,9 +1,19 @@
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
+use crate::adapters::clock::system_clock::{FixedClock, SystemClock};
+use crate::adapters::git::git_author::{FixedAuthorProvider, GitAuthorProvider};
+use crate::config::app_config::AppConfig;
+use crate::config::user_config::UserConfig;
 use crate::core::errors::{Result, VaulticError};
+use crate::core::traits::author::AuthorProvider;
+use crate::core::traits::clock::Clock;
 
 static VAULTIC_DIR: OnceLock<PathBuf> = OnceLock::new();
+static CLOCK: OnceLock<Box<dyn Clock>> = OnceLock::new();
+static AUTHOR: OnceLock<Box<dyn AuthorProvider>> = OnceLock::new();
+static CONFIG: OnceLock<AppConfig> = OnceLock::new();
+static USER_CONFIG: OnceLock<UserConfig> = OnceLock::new();
 
 /// Initialize the global vaultic directory path.
 /// If `custom` is provided, uses that path; otherwise defaults to `.vaultic`.
@@ -22,115 +32,196 @@ pub fn vaultic_dir() -> &'static Path {
         .unwrap_or(Path::new(".vaultic"))
 }
 
-/// Validate that an environment name is safe for path construction.
+/// The project's config.toml, parsed once per invocation.
 ///
-/// Prevents path traversal attacks by restricting names to `[a-zA-Z0-9_-]`.
-/// For example, `--env ../../../etc` would construct `.vaultic/../../../etc.env.enc`
-/// and escape the project directory.
-pub fn validate_env_name(name: &str) -> Result<()> {
-    if name.is_empty() {
-        return Err(VaulticError::InvalidConfig {
-            detail: "Environment name cannot be empty.\n\n  \
-                     Use a name like 'dev', 'staging', or 'prod'."
-                .into(),
-        });
+/// Commands, key lookup, and audit logging all share this copy instead of
+/// re-reading the file. Only a successful load is cached, so `vaultic init`
+/// can still create the config and read it afterwards.
+pub fn config() -> Result<&'static AppConfig> {
+    if let Some(config) = CONFIG.get() {
+        return Ok(config);
     }
+    let config = AppConfig::load(vaultic_dir())?;
+    Ok(CONFIG.get_or_init(|| config))
+}
 
-    if !name
-        .chars()
-        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
-    {
-        return Err(VaulticError::InvalidConfig {
-            detail: format!(
-                "Invalid environment name: '{name}'\n\n  \
-                 Environment names can only contain letters, digits, hyphens, and underscores.\n  \
-                 Valid examples: 'dev', 'staging', 'prod-us', 'test_01'"
-            ),
-        });
+/// Path of the local age identity file.
+///
+/// Uses `VAULTIC_IDENTITY` when set, then `identity` from config.toml
+/// (after `~` / `${...}` expansion), then the first `identity` of the
+/// user config, otherwise the platform default.
+pub fn identity_path() -> Result<PathBuf> {
+    if let Some(path) = identity_override()? {
+        return Ok(path);
+    }
+    if let Some(raw) = config().ok().and_then(|c| c.vaultic.identity.as_deref()) {
+        return crate::config::path_expand::expand_path(raw, Path::new("."));
+    }
+    match user_config().identities().first() {
+        Some(raw) => crate::config::path_expand::expand_input(raw, Path::new(".")),
+        None => crate::adapters::cipher::age_backend::AgeBackend::default_identity_path(),
     }
-
-    Ok(())
 }
 
-/// Validate that a filename is safe and does not contain path separators.
+/// Every existing identity file to try when decrypting with age, in
+/// order: the project `identity`, the user config `identity` list and
+/// `identity_dir`, the platform default, then `~/.ssh/id_ed25519` and
+/// `~/.ssh/id_rsa`. Whichever matches the ciphertext is used.
 ///
-/// Prevents a compromised `config.toml` from writing files outside `.vaultic/`.
-pub fn validate_simple_filename(name: &str, context: &str) -> Result<()> {
-    if name.is_empty() {
-        return Err(VaulticError::InvalidConfig {
-            detail: format!("{context} cannot be empty."),
+/// `VAULTIC_IDENTITY` replaces the whole list with its one file.
+pub fn identity_paths() -> Result<Vec<PathBuf>> {
+    if let Some(path) = identity_override()? {
+        return Ok(if path.exists() {
+            vec![path]
+        } else {
+            Vec::new()
         });
     }
+    let user = user_config();
+    let base = Path::new(".");
+    let mut candidates = Vec::new();
+    if let Some(raw) = config().ok().and_then(|c| c.vaultic.identity.as_deref()) {
+        candidates.push(crate::config::path_expand::expand_path(raw, base)?);
+    }
+    for raw in user.identities() {
+        candidates.push(crate::config::path_expand::expand_input(raw, base)?);
+    }
+    candidates.extend(user.identity_dir_files(base)?);
+    candidates.push(crate::adapters::cipher::age_backend::AgeBackend::default_identity_path()?);
+    if let Some(home) = dirs::home_dir() {
+        candidates.push(home.join(".ssh").join("id_ed25519"));
+        candidates.push(home.join(".ssh").join("id_rsa"));
+    }
 
-    if name.contains('/') || name.contains('\\') || name.contains("..") || name.starts_with('.') {
-        return Err(VaulticError::InvalidConfig {
-            detail: format!(
-                "Unsafe {context}: '{name}'\n\n  \
-                 The value must be a simple filename without path separators.\n  \
-                 Valid examples: 'audit.log', 'vaultic-audit.log'"
-            ),
-        });
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for path in candidates {
+        if path.exists() && !paths.contains(&path) {
+            paths.push(path);
+        }
     }
+    Ok(paths)
+}
 
-    Ok(())
+/// The identity file named by `VAULTIC_IDENTITY`, if set and not empty.
+fn identity_override() -> Result<Option<PathBuf>> {
+    match std::env::var("VAULTIC_IDENTITY") {
+        Ok(raw) if !raw.trim().is_empty() => Ok(Some(crate::config::path_expand::expand_input(
+            raw.trim(),
+            Path::new("."),
+        )?)),
+        _ => Ok(None),
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// The user config, read once per invocation. A broken file is reported
+/// once and then ignored, so it never blocks a project command.
+pub fn user_config() -> &'static UserConfig {
+    USER_CONFIG.get_or_init(|| {
+        UserConfig::load().unwrap_or_else(|e| {
+            crate::cli::output::warning(&crate::cli::i18n::error_message(&e));
+            UserConfig::default()
+        })
+    })
+}
 
-    #[test]
-    fn valid_env_names() {
-        assert!(validate_env_name("dev").is_ok());
-        assert!(validate_env_name("staging").is_ok());
-        assert!(validate_env_name("prod").is_ok());
-        assert!(validate_env_name("prod-us").is_ok());
-        assert!(validate_env_name("test_01").is_ok());
-        assert!(validate_env_name("A").is_ok());
-    }
+/// Whether `VAULTIC_TEST_MODE` is set to a non-empty value other than `0`.
+///
+/// In test mode, timestamps and the git author are fixed so audit logs
+/// and generated key files are byte-for-byte reproducible.
+pub fn is_test_mode() -> bool {
+    std::env::var("VAULTIC_TEST_MODE").is_ok_and(|v| !v.is_empty() && v != "0")
+}
 
-    #[test]
-    fn rejects_path_traversal() {
-        assert!(validate_env_name("../../../etc").is_err());
-        assert!(validate_env_name("..").is_err());
-        assert!(validate_env_name("foo/bar").is_err());
-        assert!(validate_env_name("foo\\bar").is_err());
-    }
+/// Clock used for audit entries and generated files.
+pub fn clock() -> &'static dyn Clock {
+    CLOCK
+        .get_or_init(|| {
+            if is_test_mode() {
+                Box::new(FixedClock::test_default())
+            } else {
+                Box::new(SystemClock)
+            }
+        })
+        .as_ref()
+}
 
-    #[test]
-    fn rejects_empty_env_name() {
-        assert!(validate_env_name("").is_err());
+/// Author provider used for audit entries.
+pub fn author_provider() -> &'static dyn AuthorProvider {
+    AUTHOR
+        .get_or_init(|| {
+            if is_test_mode() {
+                Box::new(FixedAuthorProvider::test_default())
+            } else {
+                Box::new(GitAuthorProvider)
+            }
+        })
+        .as_ref()
+}
+
+/// Pick the target environment for commands that operate on one.
+///
+/// Priority: `--env`, then `default_env` from config.toml, then an
+/// interactive picker when attached to a terminal. Without config
+/// (legacy layouts) the historical `dev` default still applies.
+pub fn select_env(flag: Option<&str>, config: Option<&AppConfig>) -> Result<String> {
+    if let Some(name) = flag {
+        return Ok(name.to_string());
+    }
+    let Some(config) = config else {
+        return Ok("dev".to_string());
+    };
+    if let Some(default) = &config.vaultic.default_env {
+        return Ok(default.clone());
     }
 
-    #[test]
-    fn rejects_special_characters() {
-        assert!(validate_env_name("dev;rm -rf").is_err());
-        assert!(validate_env_name("prod env").is_err());
-        assert!(validate_env_name("dev.staging").is_err());
+    let names = config.environment_names();
+    if names.is_empty() {
+        return Err(VaulticError::InvalidConfig {
+            detail: "No environments defined in .vaultic/config.toml.".into(),
+        });
+    }
+    match crate::cli::output::select("Select environment", &names, 0)? {
+        Some(idx) => Ok(names[idx].to_string()),
+        None => Err(VaulticError::InvalidConfig {
+            detail: format!(
+                "No environment selected.\n\n  \
+                 Pass --env <name> or set default_env in .vaultic/config.toml.\n  \
+                 Available environments: {}",
+                names.join(", ")
+            ),
+        }),
     }
+}
 
-    #[test]
-    fn valid_simple_filenames() {
-        assert!(validate_simple_filename("audit.log", "log file").is_ok());
-        assert!(validate_simple_filename("vaultic-audit.log", "log file").is_ok());
-        assert!(validate_simple_filename("log", "log file").is_ok());
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_default(default_env: Option<&str>) -> AppConfig {
+        let default_line = default_env
+            .map(|d| format!("default_env = \"{d}\"\n"))
+            .unwrap_or_default();
+        toml::from_str(&format!(
+            "[vaultic]\nversion = \"0.1.0\"\ndefault_cipher = \"age\"\n{default_line}\n\
+             [environments]\ndev = {{}}\nprod = {{}}\n"
+        ))
+        .unwrap()
     }
 
     #[test]
-    fn rejects_path_in_filename() {
-        assert!(validate_simple_filename("../secret.log", "log file").is_err());
-        assert!(validate_simple_filename("/etc/passwd", "log file").is_err());
-        assert!(validate_simple_filename("foo\\bar.log", "log file").is_err());
-        assert!(validate_simple_filename("..\\..\\etc", "log file").is_err());
+    fn select_env_prefers_flag() {
+        let config = config_with_default(Some("dev"));
+        assert_eq!(select_env(Some("prod"), Some(&config)).unwrap(), "prod");
     }
 
     #[test]
-    fn rejects_hidden_files() {
-        assert!(validate_simple_filename(".hidden", "log file").is_err());
+    fn select_env_uses_configured_default() {
+        let config = config_with_default(Some("prod"));
+        assert_eq!(select_env(None, Some(&config)).unwrap(), "prod");
     }
 
     #[test]
-    fn rejects_empty_filename() {
-        assert!(validate_simple_filename("", "log file").is_err());
+    fn select_env_without_config_falls_back_to_dev() {
+        assert_eq!(select_env(None, None).unwrap(), "dev");
     }
 }
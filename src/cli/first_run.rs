@@ -0,0 +1,135 @@
+use std::path::{Path, PathBuf};
+
+use crate::adapters::parsers::dotenv_parser::DotenvParser;
+use crate::cli::i18n::{t, t_args};
+use crate::core::errors::VaulticError;
+use crate::core::traits::parser::ConfigParser;
+
+/// Error for a command run where there is no `.vaultic/` directory.
+///
+/// Instead of a bare "not initialized", points at the nearest parent
+/// directory that is a Vaultic project, lists the secret files found
+/// here, and tailors the `vaultic init` steps to whether a `.env` exists.
+pub fn not_initialized() -> VaulticError {
+    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    VaulticError::InvalidConfig {
+        detail: describe(crate::cli::context::vaultic_dir(), &cwd),
+    }
+}
+
+fn describe(vaultic_dir: &Path, cwd: &Path) -> String {
+    if vaultic_dir != Path::new(".vaultic") {
+        return t_args(
+            "not-initialized-config",
+            &[("path", &vaultic_dir.display().to_string())],
+        );
+    }
+
+    let mut detail = t("not-initialized");
+
+    if let Some(parent) = find_parent_project(cwd, vaultic_dir) {
+        detail.push_str("\n\n  ");
+        detail.push_str(&t_args(
+            "not-initialized-parent",
+            &[
+                ("parent", &parent.display().to_string()),
+                ("config", &parent.join(vaultic_dir).display().to_string()),
+            ],
+        ));
+        return detail;
+    }
+
+    let found = found_here(cwd);
+    if !found.is_empty() {
+        detail.push_str("\n\n  ");
+        detail.push_str(&t_args(
+            "not-initialized-found-here",
+            &[("files", &found.join(", "))],
+        ));
+    }
+    let next = if cwd.join(".env").is_file() {
+        t("not-initialized-next-existing")
+    } else {
+        t("not-initialized-next-new")
+    };
+    detail.push_str("\n\n  ");
+    detail.push_str(&t_args("not-initialized-solutions", &[("next", &next)]));
+    detail
+}
+
+/// Nearest ancestor of `cwd` (excluding `cwd`) that contains
+/// `vaultic_dir/config.toml`.
+fn find_parent_project(cwd: &Path, vaultic_dir: &Path) -> Option<PathBuf> {
+    cwd.ancestors()
+        .skip(1)
+        .find(|dir| dir.join(vaultic_dir).join("config.toml").is_file())
+        .map(Path::to_path_buf)
+}
+
+/// Short descriptions of the secret-related files in `dir`.
+fn found_here(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|e| e.path().is_file())
+        .filter_map(|e| e.file_name().into_string().ok())
+        .filter(|n| n == ".env" || n.starts_with(".env.") || n == "env.template")
+        .collect();
+    names.sort();
+
+    let mut found: Vec<String> = names
+        .into_iter()
+        .map(|name| {
+            if name != ".env" {
+                return name;
+            }
+            let count = std::fs::read_to_string(dir.join(&name))
+                .ok()
+                .and_then(|c| DotenvParser.parse(&c).ok())
+                .map(|f| f.keys().len());
+            match count {
+                Some(n) => format!(".env ({n} variable(s))"),
+                None => name,
+            }
+        })
+        .collect();
+    if dir.join(".git").exists() {
+        found.push(t("not-initialized-git-repository"));
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggests_nearest_parent_project() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(root.path().join(".vaultic")).unwrap();
+        std::fs::write(root.path().join(".vaultic/config.toml"), "").unwrap();
+        let nested = root.path().join("apps/web");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        let detail = describe(Path::new(".vaultic"), &nested);
+        assert!(detail.contains(&format!("cd {}", root.path().display())));
+        assert!(detail.contains("--config"));
+    }
+
+    #[test]
+    fn lists_existing_env_files_and_tailors_next_step() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".env"), "A=1\nB=2\n").unwrap();
+        std::fs::write(dir.path().join(".env.example"), "A=\n").unwrap();
+
+        let detail = describe(Path::new(".vaultic"), dir.path());
+        assert!(detail.contains("Found here: .env (2 variable(s)), .env.example"));
+        assert!(detail.contains("encrypt your existing .env"));
+
+        let empty = tempfile::tempdir().unwrap();
+        let detail = describe(Path::new(".vaultic"), empty.path());
+        assert!(!detail.contains("Found here"));
+        assert!(detail.contains("put your secrets in .env"));
+    }
+}
@@ -0,0 +1,305 @@
+//! Localized user-facing messages.
+//!
+//! Messages live in Fluent catalogs under `locales/` and are embedded in
+//! the binary. English is the reference: a message missing from another
+//! catalog falls back to it. Errors keep their English text on
+//! `VaulticError`; other catalogs translate them under
+//! `error-<variant>` ids (see [`error_message`]).
+
+use std::sync::OnceLock;
+
+use fluent::concurrent::FluentBundle;
+use fluent::{FluentArgs, FluentResource, FluentValue};
+
+use crate::config::app_config::Language;
+use crate::core::errors::VaulticError;
+
+static LANGUAGE: OnceLock<Language> = OnceLock::new();
+static ENGLISH: OnceLock<FluentBundle<FluentResource>> = OnceLock::new();
+static SPANISH: OnceLock<FluentBundle<FluentResource>> = OnceLock::new();
+
+const ENGLISH_FTL: &str = include_str!("../../locales/en.ftl");
+const SPANISH_FTL: &str = include_str!("../../locales/es.ftl");
+
+/// Select the message language. Must be called once at startup.
+///
+/// `VAULTIC_LANG` (e.g. `es`) wins over `[ui] language` in config.toml;
+/// without either, messages are in English.
+pub fn init(configured: Option<Language>) {
+    let language = std::env::var("VAULTIC_LANG")
+        .ok()
+        .and_then(|code| Language::from_code(&code))
+        .or(configured)
+        .unwrap_or_default();
+    let _ = LANGUAGE.set(language);
+}
+
+/// The selected message language.
+pub fn language() -> Language {
+    LANGUAGE.get().copied().unwrap_or_default()
+}
+
+/// The message `id` in the selected language.
+pub fn t(id: &str) -> String {
+    format(language(), id, None)
+        .or_else(|| format(Language::En, id, None))
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// The message `id` in the selected language, with `{ $name }`
+/// placeables filled from `args`.
+pub fn t_args(id: &str, args: &[(&str, &str)]) -> String {
+    let args = fluent_args(args);
+    format(language(), id, Some(&args))
+        .or_else(|| format(Language::En, id, Some(&args)))
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// `error`'s message in the selected language.
+///
+/// Uses the `error-<variant>` translation when the catalog has one and
+/// the English `Display` text otherwise.
+pub fn error_message(error: &VaulticError) -> String {
+    if language() == Language::En {
+        return error.to_string();
+    }
+    let Some((id, args)) = error_args(error) else {
+        return error.to_string();
+    };
+    let mut fluent = fluent_args(&args);
+    if let VaulticError::ValidationFailed { count }
+    | VaulticError::SecretLeaksFound { count }
+    | VaulticError::PolicyViolations { count }
+    | VaulticError::StatusProblemsFound { count }
+    | VaulticError::VerifyFailed { count }
+    | VaulticError::UnrecordedChanges { count }
+    | VaulticError::StateChainBroken { count, .. }
+    | VaulticError::TemplateCheckFailed { count } = error
+    {
+        fluent.set("count", FluentValue::from(*count));
+    }
+    format(language(), id, Some(&fluent)).unwrap_or_else(|| error.to_string())
+}
+
+/// Catalog id and arguments for an error's translation.
+fn error_args(error: &VaulticError) -> Option<(&'static str, Vec<(&'static str, String)>)> {
+    use VaulticError::*;
+    let path = |p: &std::path::Path| p.display().to_string();
+    Some(match error {
+        FileNotFound { path: p } => ("error-file-not-found", vec![("path", path(p))]),
+        EncryptionFailed { reason } => {
+            ("error-encryption-failed", vec![("reason", reason.clone())])
+        }
+        DecryptionNoKey => ("error-decryption-no-key", vec![]),
+        ArmorCorrupted { line, reason } => (
+            "error-armor-corrupted",
+            vec![("line", line.to_string()), ("reason", reason.clone())],
+        ),
+        SharesRequired { env, have, need } => (
+            "error-shares-required",
+            vec![
+                ("env", env.clone()),
+                ("have", have.to_string()),
+                ("need", need.to_string()),
+            ],
+        ),
+        ParseError { file, detail } => (
+            "error-parse-error",
+            vec![("file", path(file)), ("detail", detail.clone())],
+        ),
+        EnvironmentNotFound { name, available } => (
+            "error-environment-not-found",
+            vec![("name", name.clone()), ("available", available.clone())],
+        ),
+        CircularInheritance { chain } => {
+            ("error-circular-inheritance", vec![("chain", chain.clone())])
+        }
+        RecipientsSignatureInvalid { reason } => (
+            "error-recipients-signature-invalid",
+            vec![("reason", reason.clone())],
+        ),
+        KeyNotFound { identity } => ("error-key-not-found", vec![("identity", identity.clone())]),
+        KeyAlreadyExists { identity } => (
+            "error-key-already-exists",
+            vec![("identity", identity.clone())],
+        ),
+        InvalidConfig { detail } => ("error-invalid-config", vec![("detail", detail.clone())]),
+        AuditError { detail } => ("error-audit-error", vec![("detail", detail.clone())]),
+        HookError { detail } => ("error-hook-error", vec![("detail", detail.clone())]),
+        UpdateCheckFailed { reason } => (
+            "error-update-check-failed",
+            vec![("reason", reason.clone())],
+        ),
+        UpdateVerificationFailed { reason } => (
+            "error-update-verification-failed",
+            vec![("reason", reason.clone())],
+        ),
+        UpdateFailed { reason } => ("error-update-failed", vec![("reason", reason.clone())]),
+        BinaryIntegrityFailed { reason } => (
+            "error-binary-integrity-failed",
+            vec![("reason", reason.clone())],
+        ),
+        UnsupportedPlatform { platform } => (
+            "error-unsupported-platform",
+            vec![("platform", platform.clone())],
+        ),
+        TemplateNotFound { searched } => (
+            "error-template-not-found",
+            vec![("searched", searched.clone())],
+        ),
+        FormatVersionTooNew {
+            project_version,
+            supported_version,
+        } => (
+            "error-format-version-too-new",
+            vec![
+                ("project_version", project_version.to_string()),
+                ("supported_version", supported_version.to_string()),
+            ],
+        ),
+        ValidationFailed { .. } => ("error-validation-failed", vec![]),
+        SecretLeaksFound { .. } => ("error-secret-leaks-found", vec![]),
+        PolicyViolations { .. } => ("error-policy-violations", vec![]),
+        StatusProblemsFound { .. } => ("error-status-problems-found", vec![]),
+        VerifyFailed { .. } => ("error-verify-failed", vec![]),
+        UnrecordedChanges { .. } => ("error-unrecorded-changes", vec![]),
+        StateChainBroken { env, .. } => ("error-state-chain-broken", vec![("env", env.clone())]),
+        TemplateCheckFailed { .. } => ("error-template-check-failed", vec![]),
+        ReferenceResolutionFailed {
+            key,
+            reference,
+            reason,
+        } => (
+            "error-reference-resolution-failed",
+            vec![
+                ("key", key.clone()),
+                ("reference", reference.clone()),
+                ("reason", reason.clone()),
+            ],
+        ),
+        ReadOnlyMirror { name, origin } => (
+            "error-read-only-mirror",
+            vec![("name", name.clone()), ("origin", origin.clone())],
+        ),
+        InvalidPattern {
+            key,
+            pattern,
+            reason,
+        } => (
+            "error-invalid-pattern",
+            vec![
+                ("key", key.clone()),
+                ("pattern", pattern.clone()),
+                ("reason", reason.clone()),
+            ],
+        ),
+        CiExportFailed { format } => ("error-ci-export-failed", vec![("format", format.clone())]),
+        GpgAgentUnavailable { detail } => (
+            "error-gpg-agent-unavailable",
+            vec![("detail", detail.clone())],
+        ),
+        ClipboardUnavailable { detail } => (
+            "error-clipboard-unavailable",
+            vec![("detail", detail.clone())],
+        ),
+        Io(_) => return None,
+    })
+}
+
+fn fluent_args<'a, S: AsRef<str>>(args: &'a [(&'a str, S)]) -> FluentArgs<'a> {
+    let mut fluent = FluentArgs::new();
+    for (name, value) in args {
+        fluent.set(*name, FluentValue::from(value.as_ref()));
+    }
+    fluent
+}
+
+/// Format `id` from `language`'s catalog, or `None` when it has no such
+/// message.
+fn format(language: Language, id: &str, args: Option<&FluentArgs>) -> Option<String> {
+    let bundle = bundle(language);
+    let pattern = bundle.get_message(id)?.value()?;
+    let mut errors = Vec::new();
+    let text = bundle.format_pattern(pattern, args, &mut errors);
+    errors.is_empty().then(|| text.into_owned())
+}
+
+fn bundle(language: Language) -> &'static FluentBundle<FluentResource> {
+    let (cell, source) = match language {
+        Language::En => (&ENGLISH, ENGLISH_FTL),
+        Language::Es => (&SPANISH, SPANISH_FTL),
+    };
+    cell.get_or_init(|| {
+        let resource = FluentResource::try_new(source.to_string())
+            .expect("embedded Fluent catalogs are valid");
+        let id = language
+            .code()
+            .parse()
+            .expect("language codes are valid identifiers");
+        let mut bundle = FluentBundle::new_concurrent(vec![id]);
+        // Unicode isolation marks around placeables show up as garbage in
+        // many terminals.
+        bundle.set_use_isolating(false);
+        bundle
+            .add_resource(resource)
+            .expect("embedded Fluent catalogs have unique ids");
+        bundle
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(source: &str) -> Vec<String> {
+        source
+            .lines()
+            .filter(|l| l.starts_with(|c: char| c.is_ascii_lowercase()))
+            .filter_map(|l| l.split_once(" =").map(|(id, _)| id.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn catalogs_parse() {
+        for language in [Language::En, Language::Es] {
+            bundle(language);
+        }
+    }
+
+    #[test]
+    fn spanish_translates_every_english_message() {
+        let spanish = ids(SPANISH_FTL);
+        for id in ids(ENGLISH_FTL) {
+            assert!(spanish.contains(&id), "es.ftl is missing {id}");
+        }
+    }
+
+    #[test]
+    fn multiline_messages_keep_relative_indentation() {
+        let text = format(
+            Language::Es,
+            "error-decryption-no-key",
+            Some(&FluentArgs::new()),
+        )
+        .unwrap();
+        assert!(text.starts_with("Error al descifrar"));
+        assert!(text.contains("\n\n  Soluciones:\n    → Pide"));
+    }
+
+    #[test]
+    fn errors_fill_their_arguments() {
+        let error = VaulticError::EnvironmentNotFound {
+            name: "qa".into(),
+            available: "dev, prod".into(),
+        };
+        let (id, args) = error_args(&error).unwrap();
+        let text = format(Language::Es, id, Some(&fluent_args(&args))).unwrap();
+        assert!(text.contains("No se encontró el entorno 'qa'"));
+        assert!(text.contains("Entornos disponibles: dev, prod"));
+    }
+
+    #[test]
+    fn unknown_message_falls_back_to_its_id() {
+        assert_eq!(t("no-such-message"), "no-such-message");
+    }
+}
@@ -1,7 +1,13 @@
+pub mod aliases;
 pub mod commands;
 pub mod context;
+pub mod first_run;
+pub mod i18n;
 pub mod output;
+pub mod signals;
+pub mod table;
 
+use clap::builder::FalseyValueParser;
 use clap::{Parser, Subcommand};
 
 /// Secure your secrets. Sync your team. Trust your configs.
@@ -19,31 +25,103 @@ use clap::{Parser, Subcommand};
                   New project:     vaultic init\n  \
                   Join a project:  vaultic keys setup → send your public key to admin\n  \
                   Check status:    vaultic status\n\n\
+                  Plugins: an executable named vaultic-<name> on PATH runs as 'vaultic <name>'.\n\n\
                   More info: https://github.com/SoftDryzz/vaultic"
 )]
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
 
-    /// Encryption backend to use
-    #[arg(long, global = true, default_value = "age")]
-    pub cipher: String,
+    /// Encryption backend to use [default: the project's default_cipher,
+    /// else the user config's, else age; -v shows which]
+    #[arg(long, global = true, env = "VAULTIC_CIPHER")]
+    pub cipher: Option<String>,
 
     /// Target environment(s). Repeat for diff: --env dev --env prod
-    #[arg(long, global = true)]
+    #[arg(long, global = true, env = "VAULTIC_ENV")]
     pub env: Vec<String>,
 
     /// Verbose output
-    #[arg(short, long, global = true)]
+    #[arg(short, long, global = true, env = "VAULTIC_VERBOSE", value_parser = FalseyValueParser::new())]
     pub verbose: bool,
 
     /// Quiet mode: only show errors
-    #[arg(short, long, global = true)]
+    #[arg(short, long, global = true, env = "VAULTIC_QUIET", value_parser = FalseyValueParser::new())]
     pub quiet: bool,
 
     /// Path to alternative config file
-    #[arg(long, global = true)]
+    #[arg(long, global = true, env = "VAULTIC_CONFIG")]
     pub config: Option<String>,
+
+    /// Answer yes to every prompt and accept default choices
+    #[arg(short, long, global = true, env = "VAULTIC_YES", value_parser = FalseyValueParser::new())]
+    pub yes: bool,
+
+    /// Never prompt; use defaults or fail when input is required
+    #[arg(
+        long,
+        global = true,
+        env = "VAULTIC_NO_INPUT",
+        value_parser = FalseyValueParser::new(),
+        visible_alias = "non-interactive"
+    )]
+    pub no_input: bool,
+
+    /// Print JSON instead of formatted output (status, verify, check, diff, log, keys list)
+    #[arg(long, global = true, env = "VAULTIC_JSON", value_parser = FalseyValueParser::new())]
+    pub json: bool,
+}
+
+impl Cli {
+    /// Let explicit flags win over `VAULTIC_*` variables that contradict
+    /// them, and drop `VAULTIC_JSON` for commands without JSON output, so
+    /// a variable set for a whole CI job never breaks a single command.
+    ///
+    /// Fails only when `--yes` and `--no-input` are both given as flags.
+    pub fn apply_env_precedence(&mut self, matches: &clap::ArgMatches) {
+        let from_env =
+            |id: &str| matches.value_source(id) == Some(clap::parser::ValueSource::EnvVariable);
+        if self.yes && self.no_input {
+            if from_env("yes") {
+                self.yes = false;
+            } else if from_env("no_input") {
+                self.no_input = false;
+            } else {
+                <Self as clap::CommandFactory>::command()
+                    .error(
+                        clap::error::ErrorKind::ArgumentConflict,
+                        "the argument '--no-input' cannot be used with '--yes'",
+                    )
+                    .exit();
+            }
+        }
+        if self.json && from_env("json") && !self.command.supports_json() {
+            self.json = false;
+        }
+    }
+
+    /// The cipher to use and where it was set: `--cipher`, then
+    /// `VAULTIC_CIPHER`, then the project's `default_cipher`, then the
+    /// user config's, then age.
+    pub fn resolve_cipher(&self, matches: &clap::ArgMatches) -> (String, &'static str) {
+        if let Some(cipher) = &self.cipher {
+            let source = match matches.value_source("cipher") {
+                Some(clap::parser::ValueSource::EnvVariable) => "VAULTIC_CIPHER",
+                _ => "--cipher",
+            };
+            return (cipher.clone(), source);
+        }
+        if let Ok(config) = context::config() {
+            return (
+                config.vaultic.default_cipher.clone(),
+                "default_cipher in config.toml",
+            );
+        }
+        match &context::user_config().default_cipher {
+            Some(cipher) => (cipher.clone(), "default_cipher in the user config"),
+            None => ("age".to_string(), "built-in default"),
+        }
+    }
 }
 
 #[derive(Subcommand, Debug)]
@@ -54,26 +132,67 @@ pub enum Commands {
                       Creates the .vaultic/ directory, generates config.toml with defaults, \
                       creates an empty .env.template, and adds .env to .gitignore.\n\n\
                       During setup, Vaultic detects existing age and GPG keys and offers \
-                      to generate a new key if none is found.",
+                      to generate a new key if none is found.\n\n\
+                      Every prompt can be answered with flags, so init can run from \
+                      provisioning scripts and devcontainers: --yes generates a key when \
+                      none exists, --no-keygen never does. The environments are base plus \
+                      dev, staging and prod unless --environments lists others; each \
+                      inherits base.\n\n\
+                      --template picks another environment layout: minimal (dev and prod, \
+                      no shared base), monorepo (base, dev and prod, plus api-* and web-* \
+                      on top of each stage) or trunk-based (base, dev, preview on top of \
+                      dev, and prod). It also accepts the path of a starter config.toml, \
+                      which is checked and copied as is.",
         after_help = "Examples:\n  \
                       vaultic init              # Interactive setup with key detection\n  \
-                      vaultic init --cipher gpg # Initialize with GPG as default backend"
+                      vaultic init --cipher gpg # Initialize with GPG as default backend\n  \
+                      vaultic init --yes        # Generate a key without asking\n  \
+                      vaultic init --no-keygen --environments dev,prod --default-env dev\n  \
+                      vaultic init --template monorepo\n  \
+                      vaultic init --template ../platform/starter.toml"
     )]
-    Init,
+    Init {
+        /// Never generate a key; use an existing one or set it up later
+        #[arg(long)]
+        no_keygen: bool,
+        /// Default environment in config.toml (default: dev, or the first one)
+        #[arg(long)]
+        default_env: Option<String>,
+        /// Comma-separated environments to create, each inheriting base
+        #[arg(long, value_delimiter = ',', conflicts_with = "template")]
+        environments: Vec<String>,
+        /// Layout preset (minimal, monorepo, trunk-based) or a starter config.toml
+        #[arg(long)]
+        template: Option<String>,
+    },
 
     /// Encrypt secret files
     #[command(
         long_about = "Encrypt secret files for all authorized recipients.\n\n\
                       Reads the source file, encrypts it with the public keys of all \
                       recipients listed in .vaultic/recipients.txt, and saves the \
-                      ciphertext as .vaultic/<env>.env.enc.\n\n\
+                      ciphertext as .vaultic/<env>.env.enc. An environment with its own \
+                      .vaultic/recipients.<env>.txt is encrypted only for the keys in that \
+                      list.\n\n\
                       The original file is NOT modified or deleted. Use --all to \
-                      re-encrypt all environments (useful after adding/removing recipients).",
+                      re-encrypt all environments (useful after adding/removing recipients), \
+                      or --reencrypt with --env to re-encrypt one environment's current \
+                      ciphertext in memory, without its plaintext file.\n\n\
+                      A source file with no variables, or with more than [history] \
+                      max_shrink_percent (default 50) fewer variables than the last kept \
+                      version, is refused unless --force is given. So is encrypting for a \
+                      recipient whose expires date in .vaultic/members.toml has passed.\n\n\
+                      With --review, or review = true on the environment in config.toml, \
+                      the current ciphertext is decrypted in memory and the key-level \
+                      changes are shown, values masked, before asking to overwrite it.",
         after_help = "Examples:\n  \
                       vaultic encrypt                       # Encrypt .env as dev\n  \
                       vaultic encrypt .env --env prod       # Encrypt as prod environment\n  \
                       vaultic encrypt --all                 # Re-encrypt all environments\n  \
-                      vaultic encrypt --cipher gpg          # Encrypt with GPG backend"
+                      vaultic encrypt --env prod --reencrypt  # Only prod, without .env\n  \
+                      vaultic encrypt --cipher gpg          # Encrypt with GPG backend\n  \
+                      vaultic encrypt --env prod --force    # Variables were removed on purpose\n  \
+                      vaultic encrypt --env prod --review   # Confirm the changes first"
     )]
     Encrypt {
         /// File to encrypt (default: .env)
@@ -81,6 +200,20 @@ pub enum Commands {
         /// Re-encrypt all environments for current recipients
         #[arg(long)]
         all: bool,
+        /// Reason for the change, recorded in the audit log
+        #[arg(long)]
+        note: Option<String>,
+        /// Encrypt even when the file is empty or lost many variables, or a
+        /// recipient has expired
+        #[arg(long)]
+        force: bool,
+        /// Show the changes against the current ciphertext and ask first
+        #[arg(long, conflicts_with = "all")]
+        review: bool,
+        /// Re-encrypt the environment's current ciphertext for its
+        /// recipients, without a plaintext file
+        #[arg(long, conflicts_with_all = ["all", "file", "review"])]
+        reencrypt: bool,
     },
 
     /// Decrypt secret files
@@ -92,13 +225,17 @@ pub enum Commands {
                       This is useful when running Vaultic from a parent directory \
                       but the application expects .env in a subdirectory.\n\n\
                       By default, uses the age key at ~/.config/age/keys.txt. \
-                      Use --key to specify a different private key location.",
+                      Use --key to specify a different private key location.\n\n\
+                      An environment with a threshold in config.toml needs that many \
+                      shares: pass the files other shareholders released to you with \
+                      'vaultic keys share release' using --share.",
         after_help = "Examples:\n  \
                       vaultic decrypt                       # Decrypt dev → ./.env\n  \
                       vaultic decrypt --env prod            # Decrypt prod → ./.env\n  \
                       vaultic decrypt -o backend/.env       # Decrypt dev → backend/.env\n  \
                       vaultic decrypt --key /path/to/key    # Use custom private key\n  \
-                      vaultic decrypt --cipher gpg          # Decrypt with GPG backend"
+                      vaultic decrypt --cipher gpg          # Decrypt with GPG backend\n  \
+                      vaultic decrypt --env prod --share prod.share-2.age"
     )]
     Decrypt {
         /// File to decrypt
@@ -106,6 +243,9 @@ pub enum Commands {
         /// Path to private key file
         #[arg(long)]
         key: Option<String>,
+        /// Share released by another shareholder (repeatable)
+        #[arg(long, value_name = "FILE")]
+        share: Vec<String>,
         /// Output path for the decrypted file (default: .env)
         #[arg(short, long, conflicts_with = "stdout")]
         output: Option<String>,
@@ -119,28 +259,104 @@ pub enum Commands {
         long_about = "Verify your local .env against .env.template.\n\n\
                       Reports missing variables (in template but not in .env), \
                       extra variables (in .env but not in template), and \
-                      variables with empty values.",
+                      variables with empty values.\n\n\
+                      With --env, checks an environment instead of .env: it is decrypted \
+                      and resolved through its inheritance chain in memory, compared with \
+                      its own template, and any issue fails the command.\n\n\
+                      With --all-outputs, checks every file listed in [outputs] in \
+                      config.toml against its own template and prints a summary table, \
+                      which suits monorepos with several generated .env files.\n\n\
+                      With --scan, decrypts the environments in memory and searches every \
+                      file tracked by git for their current values, failing if any secret \
+                      has leaked into plaintext. --deep also searches every commit in the \
+                      git history. Values shorter than 8 characters, booleans and numbers \
+                      are not searched for.\n\n\
+                      With --policy, decrypts the environments in memory and holds their \
+                      values to the [policy] rules in config.toml: a minimum length for \
+                      *_SECRET and *_KEY variables, no placeholder values such as \
+                      'password' in prod, and a maximum age since the last encrypt \
+                      according to the audit log. Any violation fails the command.\n\n\
+                      With --schema, resolves every environment that no other one inherits \
+                      from (or those given with --env) in memory and validates its values \
+                      against .vaultic/schema.toml: required keys, types (url, int, bool, \
+                      enum), regex patterns and lengths. Values are never printed.",
         after_help = "Examples:\n  \
-                      vaultic check                         # Check .env vs .env.template"
+                      vaultic check                         # Check .env vs .env.template\n  \
+                      vaultic check --json                  # Report as JSON for CI\n  \
+                      vaultic check --env prod              # Resolved prod vs its template\n  \
+                      vaultic check --all-outputs           # Check every [outputs] entry\n  \
+                      vaultic check --scan                  # Look for leaked secret values\n  \
+                      vaultic check --scan --env prod       # Only values of prod\n  \
+                      vaultic check --scan --deep           # Include the git history\n  \
+                      vaultic check --policy                # Enforce [policy] on secret values\n  \
+                      vaultic check --policy --report junit -o policy.xml\n  \
+                      vaultic check --schema                # Validate against schema.toml\n  \
+                      vaultic check --schema --env prod     # Only resolved prod\n  \
+                      vaultic check --report junit -o check.xml"
     )]
-    Check,
+    Check {
+        /// Check every file in [outputs] instead of ./.env
+        #[arg(long, conflicts_with = "scan")]
+        all_outputs: bool,
+        /// Search tracked files for secret values of the environments
+        #[arg(long)]
+        scan: bool,
+        /// With --scan, also search every commit in the git history
+        #[arg(long, requires = "scan")]
+        deep: bool,
+        /// Enforce the [policy] rules on the environments' secret values
+        #[arg(long, conflicts_with_all = ["scan", "all_outputs"])]
+        policy: bool,
+        /// Validate the resolved environments against .vaultic/schema.toml
+        #[arg(long, conflicts_with_all = ["scan", "all_outputs", "policy"])]
+        schema: bool,
+        /// Report format: json or junit (one test case per variable)
+        #[arg(long, value_parser = ["json", "junit"])]
+        report: Option<String>,
+        /// Write the report to this file instead of stdout
+        #[arg(short, long, requires = "report")]
+        output: Option<String>,
+    },
 
     /// Compare secret files or environments
     #[command(
         long_about = "Compare two secret files or two resolved environments side by side.\n\n\
                       In file mode, compares two .env files directly.\n\
                       In environment mode (--env dev --env prod), resolves the full \
-                      inheritance chain for each environment before comparing.",
+                      inheritance chain for each environment before comparing.\n\
+                      With one file and one --env, compares the local file against what \
+                      is encrypted for that environment.\n\
+                      With --rev, compares one environment's encrypted file at a git \
+                      revision with the current one, decrypting both in memory.",
         after_help = "Examples:\n  \
                       vaultic diff .env .env.prod           # Compare two files\n  \
                       vaultic diff --env dev --env prod     # Compare resolved environments\n  \
-                      vaultic diff --env dev --env prod --cipher gpg"
+                      vaultic diff .env --env prod          # Compare a local file with an environment\n  \
+                      vaultic diff --env prod --rev HEAD~3  # Compare prod with its history\n  \
+                      vaultic diff --env dev --env prod --cipher gpg\n  \
+                      vaultic diff .env .env.prod --side-by-side\n  \
+                      vaultic diff --env dev --env prod --show-values     # Print raw values\n  \
+                      vaultic diff --env dev --env prod --side-by-side --show-values\n  \
+                      vaultic diff --env dev --env prod --only-keys\n  \
+                      vaultic diff --env dev --env prod --json"
     )]
     Diff {
         /// First file to compare
         file1: Option<String>,
         /// Second file to compare
         file2: Option<String>,
+        /// Show both sides in two columns sized to the terminal
+        #[arg(long)]
+        side_by_side: bool,
+        /// Print raw values instead of masking them (not with --json)
+        #[arg(long, conflicts_with = "only_keys")]
+        show_values: bool,
+        /// Only list changed key names with counts, for a PR description or chat
+        #[arg(long, conflicts_with = "side_by_side")]
+        only_keys: bool,
+        /// Compare --env with its encrypted file at this git revision (e.g. HEAD~3)
+        #[arg(long, value_name = "REV")]
+        rev: Option<String>,
     },
 
     /// Generate resolved file with inheritance applied
@@ -150,12 +366,20 @@ pub enum Commands {
                       each layer in memory, and merges them from base to leaf. \
                       The overlay always wins when keys conflict.\n\n\
                       Use --output to write the resolved file to a custom path instead \
-                      of the default .env in the working directory.",
+                      of the default .env in the working directory.\n\n\
+                      Use --only and --except to hand a consumer just the keys it needs \
+                      (comma-separated globs such as DB_* or *_URL).\n\n\
+                      Values can be references to an external secret manager, such as \
+                      vault://secret/data/app#api_key or op://vault/item/field. \
+                      --materialize fetches them through the providers configured under \
+                      [references] in config.toml; --keep-refs leaves them for the app.",
         after_help = "Examples:\n  \
                       vaultic resolve --env dev             # Resolve dev → ./.env\n  \
                       vaultic resolve --env staging         # Resolve staging chain\n  \
                       vaultic resolve --env prod -o prod.env  # Resolve prod → prod.env\n  \
-                      vaultic resolve --env prod --cipher gpg"
+                      vaultic resolve --env prod --cipher gpg\n  \
+                      vaultic resolve --env prod --only \"NEXT_PUBLIC_*,API_URL\" -o web/.env\n  \
+                      vaultic resolve --env prod --materialize --stdout"
     )]
     Resolve {
         /// Output path for the resolved file (default: .env)
@@ -164,6 +388,229 @@ pub enum Commands {
         /// Write resolved content to stdout instead of a file
         #[arg(long)]
         stdout: bool,
+        /// Only include keys matching these comma-separated globs
+        #[arg(long, value_name = "PATTERNS")]
+        only: Option<String>,
+        /// Leave out keys matching these comma-separated globs
+        #[arg(long, value_name = "PATTERNS")]
+        except: Option<String>,
+        /// Fetch op:// and vault:// values from their [references] providers
+        #[arg(long, conflicts_with = "keep_refs")]
+        materialize: bool,
+        /// Leave op:// and vault:// values as they are for the app to resolve
+        #[arg(long)]
+        keep_refs: bool,
+    },
+
+    /// Generate a reduced env file for integration tests
+    #[command(
+        long_about = "Resolve an environment and write only the keys tests should see.\n\n\
+                      Keys are filtered by the [test_env] section of .vaultic/config.toml \
+                      (include/exclude patterns, exact keys or globs like DB_*) and optionally renamed \
+                      with a prefix, so test suites reuse managed secrets without picking up \
+                      prod-only variables.",
+        after_help = "Example config.toml:\n  \
+                      [test_env]\n  \
+                      include = [\"DB_*\", \"API_URL\"]\n  \
+                      exclude = [\"DB_ADMIN_*\"]\n\n\
+                      Examples:\n  \
+                      vaultic test-env --env dev                          # → .env.test\n  \
+                      vaultic test-env --env dev --prefix TEST_ -o .env.ci"
+    )]
+    TestEnv {
+        /// Prefix prepended to every key (overrides [test_env].prefix)
+        #[arg(long)]
+        prefix: Option<String>,
+        /// Output path for the test env file
+        #[arg(short, long, default_value = ".env.test")]
+        output: String,
+    },
+
+    /// Run a command with the environment's secrets injected
+    #[command(
+        long_about = "Decrypt and resolve an environment in memory, then run a command \
+                      with the resolved variables set in its environment.\n\n\
+                      No plaintext is written to disk. Resolved values override variables \
+                      already set in your shell. Vaultic exits with the command's exit code.\n\n\
+                      Use --only and --except to inject just the keys the command needs.\n\n\
+                      With --materialize, op:// and vault:// values are fetched from their \
+                      [references] providers before the command starts.\n\n\
+                      Credentials from [leases] (AWS STS, or any command) are injected too \
+                      and renewed while the command runs; the current values are kept in \
+                      the file named by VAULTIC_LEASE_FILE.",
+        after_help = "Examples:\n  \
+                      vaultic run --env prod -- npm start\n  \
+                      vaultic run --env dev -- cargo test\n  \
+                      vaultic run -- ./scripts/migrate.sh --dry-run   # default_env\n  \
+                      vaultic run --env prod --only \"NEXT_PUBLIC_*\" -- npm run build\n  \
+                      vaultic run --env prod --materialize -- ./server"
+    )]
+    Run {
+        /// Only inject keys matching these comma-separated globs
+        #[arg(long, value_name = "PATTERNS")]
+        only: Option<String>,
+        /// Leave out keys matching these comma-separated globs
+        #[arg(long, value_name = "PATTERNS")]
+        except: Option<String>,
+        /// Fetch op:// and vault:// values from their [references] providers
+        #[arg(long, conflicts_with = "keep_refs")]
+        materialize: bool,
+        /// Leave op:// and vault:// values as they are for the app to resolve
+        #[arg(long)]
+        keep_refs: bool,
+        /// Command and arguments to run, after `--`
+        #[arg(last = true, required = true, value_name = "COMMAND")]
+        command: Vec<String>,
+    },
+
+    /// Print one variable from an encrypted environment
+    #[command(
+        long_about = "Decrypt and resolve an environment in memory (with inheritance, like \
+                      'resolve') and print the value of a single variable.\n\n\
+                      Only the value goes to stdout, so it can be used in scripts. \
+                      With --copy it goes to the clipboard instead, which is cleared \
+                      after 45 seconds or on Ctrl-C.",
+        after_help = "Examples:\n  \
+                      vaultic get DATABASE_URL --env prod\n  \
+                      psql \"$(vaultic get DATABASE_URL --env dev)\"\n  \
+                      vaultic get STRIPE_KEY --env prod --copy"
+    )]
+    Get {
+        /// Variable name
+        key: String,
+        /// Copy the value to the clipboard instead of printing it
+        #[arg(long)]
+        copy: bool,
+    },
+
+    /// Add or update one variable in an encrypted environment
+    #[command(
+        long_about = "Decrypt an environment in memory, set one variable, and re-encrypt.\n\n\
+                      An existing entry is updated in place; a new one is appended. \
+                      Comments and all other lines are kept as they are. Nothing is \
+                      written to disk in plaintext. With a bare KEY, the value is read \
+                      from stdin so it stays out of your shell history.",
+        after_help = "Examples:\n  \
+                      vaultic set LOG_LEVEL=debug --env dev\n  \
+                      vaultic set API_KEY --env prod < api_key.txt\n  \
+                      vaultic set DATABASE_URL=postgres://db/app --env prod --note \"new host\""
+    )]
+    Set {
+        /// KEY=VALUE, or KEY to read the value from stdin
+        #[arg(value_name = "KEY[=VALUE]")]
+        assignment: String,
+        /// Reason for the change, recorded in the audit log
+        #[arg(long)]
+        note: Option<String>,
+        /// Encrypt even when a recipient has expired
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Import secrets from a dotenv, JSON or YAML file
+    #[command(
+        long_about = "Merge secrets from an existing file into an encrypted environment.\n\n\
+                      The source may be a dotenv, JSON or YAML file, or stdin (detected as \
+                      JSON when it starts with '{', dotenv otherwise). The environment is \
+                      decrypted in memory, imported variables are added or overwrite \
+                      existing ones, and the result is re-encrypted. Variables missing \
+                      from the source are kept. Only key names are printed.",
+        after_help = "Examples:\n  \
+                      vaultic import .env.production --env prod\n  \
+                      vaultic import secrets.json --env staging --keep-existing\n  \
+                      aws secretsmanager get-secret-value --secret-id app --query SecretString \\\n    \
+                      --output text | vaultic import --env prod"
+    )]
+    Import {
+        /// File to import (default: stdin)
+        source: Option<String>,
+        /// Source format: dotenv, json or yaml (default: from the extension)
+        #[arg(long)]
+        format: Option<String>,
+        /// Only add new variables; never change existing values
+        #[arg(long)]
+        keep_existing: bool,
+        /// Reason for the change, recorded in the audit log
+        #[arg(long)]
+        note: Option<String>,
+        /// Encrypt even when a recipient has expired
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Copy selected keys from one environment into another
+    #[command(
+        long_about = "Promote configuration from one environment to the next, e.g. dev to \
+                      staging to prod.\n\n\
+                      The source environment is resolved with inheritance, and each chosen \
+                      key whose value differs in the target is written into the target's \
+                      own encrypted file, which is then re-encrypted. Choose keys with \
+                      --keys (comma-separated globs) or from an interactive list. A preview \
+                      showing value shapes only is confirmed first.",
+        after_help = "Examples:\n  \
+                      vaultic promote dev staging                      # Pick keys interactively\n  \
+                      vaultic promote staging prod --keys \"FEATURE_*,API_URL\"\n  \
+                      vaultic promote dev staging --keys \"*\" --yes --note \"release 2.4\""
+    )]
+    Promote {
+        /// Environment to copy values from
+        from: String,
+        /// Environment to copy values into
+        to: String,
+        /// Keys to promote (comma-separated globs, e.g. DB_*,API_URL)
+        #[arg(long)]
+        keys: Option<String>,
+        /// Reason for the change, recorded in the audit log
+        #[arg(long)]
+        note: Option<String>,
+        /// Encrypt even when a recipient has expired
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Edit an encrypted environment in your editor
+    #[command(
+        long_about = "Decrypt an environment to a private temp file, open it in the user \
+                      config editor, $VISUAL or $EDITOR, validate the result, and re-encrypt it.\n\n\
+                      The temp file lives on memory-backed /dev/shm when available, is \
+                      readable only by you, and is deleted afterwards, even on Ctrl-C. \
+                      Invalid env syntax re-opens the editor instead of saving.",
+        after_help = "Examples:\n  \
+                      vaultic edit --env prod\n  \
+                      EDITOR=\"code --wait\" vaultic edit --env dev\n  \
+                      vaultic edit --env prod --note \"rotate Stripe key\""
+    )]
+    Edit {
+        /// Reason for the change, recorded in the audit log
+        #[arg(long)]
+        note: Option<String>,
+        /// Encrypt even when a recipient has expired
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Restore a previous version of an environment
+    #[command(
+        long_about = "Restore a previous encrypted state of an environment.\n\n\
+                      Every encrypt keeps a copy of the new ciphertext in \
+                      .vaultic/history/<env>/<id>.enc, listed in manifest.toml with its \
+                      author and date ([history] keep_versions, default 20). Without --to, \
+                      lists the kept versions. With --to, decrypts that version in memory \
+                      and re-encrypts it for the current recipients after confirmation.",
+        after_help = "Examples:\n  \
+                      vaultic rollback --env prod                        # List versions\n  \
+                      vaultic rollback --env prod --to 20260301-101500   # Restore one"
+    )]
+    Rollback {
+        /// Version to restore (omit to list versions)
+        #[arg(long, value_name = "ID")]
+        to: Option<String>,
+        /// Reason for the change, recorded in the audit log
+        #[arg(long)]
+        note: Option<String>,
+        /// Encrypt even when a recipient has expired
+        #[arg(long)]
+        force: bool,
     },
 
     /// Manage keys and recipients
@@ -175,7 +622,11 @@ pub enum Commands {
                       vaultic keys setup                    # Generate or import a key\n  \
                       vaultic keys add age1abc...xyz        # Add a recipient\n  \
                       vaultic keys list                     # List all recipients\n  \
-                      vaultic keys remove age1abc...xyz     # Remove a recipient"
+                      vaultic keys list --json              # Recipients as JSON for automation\n  \
+                      vaultic keys remove age1abc...xyz     # Remove a recipient\n  \
+                      vaultic keys rotate                   # Replace your key, re-encrypt all\n  \
+                      vaultic keys verify-roster roster.txt # Recipients missing from an allow-list\n  \
+                      vaultic keys use alice@example.com    # Prefer a GPG key in this project"
     )]
     Keys {
         #[command(subcommand)]
@@ -191,7 +642,8 @@ pub enum Commands {
                       vaultic log                           # Show full history\n  \
                       vaultic log --last 10                 # Show last 10 entries\n  \
                       vaultic log --author \"Alice\"          # Filter by author\n  \
-                      vaultic log --since 2026-01-01        # Filter by date"
+                      vaultic log --since 2026-01-01        # Filter by date\n  \
+                      vaultic log --json                    # Entries as a JSON array"
     )]
     Log {
         /// Filter by author
@@ -205,22 +657,213 @@ pub enum Commands {
         last: Option<usize>,
     },
 
+    /// Show when a variable changed and by whom
+    #[command(
+        long_about = "Show the recorded changes to one variable in an environment.\n\n\
+                      Every time an environment is encrypted (encrypt, edit, set, import, \
+                      encrypt --all), the variables that were added, modified or removed \
+                      are appended to .vaultic/history/<env>.history.enc, encrypted for \
+                      the same recipients. Values are never stored, only their SHA-256.\n\n\
+                      With --verify, checks the environment's encrypted file instead: each \
+                      write by Vaultic appends the checksums before and after it to \
+                      .vaultic/manifest.toml, so a file replaced outside Vaultic breaks the \
+                      chain, even when it was re-encrypted since. Exits with code 1 when the \
+                      chain is broken; review the change, then keep it with \
+                      vaultic verify --accept-changes.",
+        after_help = "Examples:\n  \
+                      vaultic history DATABASE_URL --env prod\n  \
+                      vaultic history API_KEY --json        # Changes as a JSON array\n  \
+                      vaultic history prod --verify         # Was prod.env.enc replaced?"
+    )]
+    History {
+        /// Variable to show the history of (with --verify, the environment)
+        #[arg(required_unless_present = "verify")]
+        key: Option<String>,
+        /// Check that the encrypted file is the one Vaultic last wrote
+        #[arg(long)]
+        verify: bool,
+    },
+
     /// Show full project status
-    #[command(long_about = "Show a full project dashboard.\n\n\
+    #[command(
+        long_about = "Show a full project dashboard.\n\n\
                       Displays configuration, authorized recipients, encrypted \
                       environments with file sizes, local state (.env, template, \
-                      gitignore), your key info, and audit log entry count.")]
+                      gitignore), your key info, and audit log entry count.\n\n\
+                      Exits with code 1 when there are problems: no private key, your key \
+                      not in the recipients list, or .env not in .gitignore.",
+        after_help = "Examples:\n  \
+                      vaultic status                        # Project dashboard\n  \
+                      vaultic status --json                 # Same data as JSON for scripts"
+    )]
     Status,
 
+    /// Check which recipients can decrypt each environment
+    #[command(
+        long_about = "Read the age header of each encrypted environment and report, for \
+                      every recipient in its recipients list, whether that key can open \
+                      the file. Needs no private key, but uses yours when present.\n\n\
+                      SSH recipients are matched exactly. age keys are not named in the \
+                      header: your own key is proven by decrypting, keys added after the \
+                      file was last encrypted (per the audit log) cannot open it, and the \
+                      rest are inferred from the number of stanzas. Stanzas left for \
+                      removed keys are reported too.\n\n\
+                      Checks every encrypted environment, or only those given with --env. \
+                      Mirrors are skipped. Exits with code 1 when any environment needs \
+                      re-encrypting.\n\n\
+                      Also compares config.toml and the recipient lists with the checksums \
+                      Vaultic recorded in .vaultic/manifest.toml when it last changed them, \
+                      and fails when one was edited outside Vaultic. Review such an edit, \
+                      then record it with --accept-changes.",
+        after_help = "Examples:\n  \
+                      vaultic verify                        # Every encrypted environment\n  \
+                      vaultic verify --env prod             # Only prod\n  \
+                      vaultic verify --json                 # Per-recipient results for scripts\n  \
+                      vaultic verify --report junit -o verify.xml\n  \
+                      vaultic verify --accept-changes       # Keep a reviewed manual edit"
+    )]
+    Verify {
+        /// Record the current config.toml and recipient lists in the
+        /// manifest after reviewing edits made outside Vaultic
+        #[arg(long, conflicts_with_all = ["report", "output"])]
+        accept_changes: bool,
+        /// Report format: json or junit (one test case per recipient)
+        #[arg(long, value_parser = ["json", "junit"])]
+        report: Option<String>,
+        /// Write the report to this file instead of stdout
+        #[arg(short, long, requires = "report")]
+        output: Option<String>,
+    },
+
+    /// Delete encrypted files of environments no longer in config
+    #[command(
+        long_about = "Find .vaultic/*.enc files that no environment in config.toml refers \
+                      to, list them, and delete them after confirmation.\n\n\
+                      Each deleted file gets its own audit log entry. Use --yes to skip \
+                      the confirmation.",
+        after_help = "Examples:\n  \
+                      vaultic prune              # List orphaned files and confirm\n  \
+                      vaultic prune --yes        # Delete without asking"
+    )]
+    Prune,
+
+    /// Undo line-ending and whitespace damage to encrypted files
+    #[command(
+        long_about = "Repair age armor that a git host, editor or copy-paste mangled.\n\n\
+                      Strips CRLF line endings, stray carriage returns, leading and \
+                      trailing spaces, blank lines and a byte order mark, and wraps the \
+                      lines at 64 characters again. Without files, repairs the encrypted \
+                      file of every environment, or of each --env. Nothing is decrypted, \
+                      so no key is needed.\n\n\
+                      Armor only reflowed to another line width is read as is; a file \
+                      whose base64 itself is damaged or cut short cannot be repaired and \
+                      is reported with the line at fault.",
+        after_help = "Examples:\n  \
+                      vaultic repair-armor                  # Every environment\n  \
+                      vaultic repair-armor --env prod --dry-run\n  \
+                      vaultic repair-armor ./downloads/prod.env.enc"
+    )]
+    RepairArmor {
+        /// Encrypted files to repair (default: those of the environments)
+        files: Vec<String>,
+        /// List what would be fixed without writing the files
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Push a resolved environment to a cloud secret store
+    #[command(
+        long_about = "Push the resolved environment to a cloud secret store so runtime \
+                      config matches what is encrypted in git.\n\n\
+                      The environment is resolved in memory, compared with what the store \
+                      holds, and the added, changed and removed keys are listed (names \
+                      only) before anything is written.",
+        after_help = "Examples:\n  \
+                      vaultic sync aws --env prod --dry-run\n  \
+                      vaultic sync aws --env prod --name myapp/prod\n  \
+                      vaultic sync aws --env prod --service ssm --name /myapp/prod"
+    )]
+    Sync {
+        #[command(subcommand)]
+        target: SyncTarget,
+    },
+
+    /// Show a step-by-step guide for a common task
+    #[command(
+        long_about = "Print a task-oriented guide in the terminal, with this project's \
+                      paths and environment names filled in.\n\n\
+                      Topics:\n  \
+                      • onboarding — get access to a project's secrets as a new teammate\n  \
+                      • rotation — offboard someone and rotate keys and secrets\n  \
+                      • ci — give a pipeline its own key and export secrets in CI\n\n\
+                      The guides are built into the binary and work offline.",
+        after_help = "Examples:\n  \
+                      vaultic guide                 # List the topics\n  \
+                      vaultic guide onboarding\n  \
+                      vaultic guide ci"
+    )]
+    Guide {
+        /// onboarding, rotation or ci
+        topic: Option<String>,
+    },
+
+    /// Add commented examples of newer settings to config.toml
+    #[command(
+        long_about = "Help long-lived projects discover settings added since config.toml \
+                      was created.\n\n\
+                      For every optional setting the config neither sets nor mentions in a \
+                      comment, a commented-out example is added: [vaultic] keys at the end \
+                      of that table, other sections at the end of the file. Existing lines \
+                      are never changed, and running it again adds nothing new.",
+        after_help = "Examples:\n  \
+                      vaultic upgrade-config --dry-run      # Show what would be added\n  \
+                      vaultic upgrade-config"
+    )]
+    UpgradeConfig {
+        /// Print the examples instead of writing config.toml
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Copy the project's .vaultic/ into another repository
+    #[command(
+        long_about = "Move a Vaultic project to another directory, e.g. when a repository \
+                      is split or restructured.\n\n\
+                      Copies .vaultic/ into the destination, rewrites absolute paths in \
+                      config.toml that pointed into this project, adds .env to the \
+                      destination's .gitignore, checks that your key can decrypt every \
+                      environment there, and records the move in both audit logs. The \
+                      original .vaultic/ is left in place.",
+        after_help = "Examples:\n  \
+                      vaultic move --to ../new-repo\n  \
+                      vaultic move --to ~/src/api-service"
+    )]
+    Move {
+        /// Destination project directory (must exist)
+        #[arg(long, value_name = "DIR")]
+        to: String,
+    },
+
     /// Install or uninstall git hooks
     #[command(
         long_about = "Manage git hooks for secret safety.\n\n\
                       The pre-commit hook blocks plaintext .env files from being \
                       committed accidentally. It detects Vaultic-managed hooks via \
-                      marker comments and refuses to overwrite foreign hooks.",
+                      marker comments and refuses to overwrite foreign hooks.\n\n\
+                      With --post-commit, a post-commit hook also checks every new commit. \
+                      When one contains files the pre-commit hook would block (because it \
+                      was skipped with --no-verify), the bypass is written to the audit log \
+                      and the next vaultic command warns about it.\n\n\
+                      --hook installs other hooks instead: pre-push blocks pushing an \
+                      environment that was not re-encrypted after its recipients list \
+                      changed; post-merge and post-checkout warn when a pull or branch \
+                      switch changed encrypted environments and suggest 'vaultic decrypt'.",
         after_help = "Examples:\n  \
                       vaultic hook install                  # Install pre-commit hook\n  \
-                      vaultic hook uninstall                # Remove pre-commit hook"
+                      vaultic hook install --post-commit    # Also record --no-verify bypasses\n  \
+                      vaultic hook install --hook pre-push  # Block pushing stale environments\n  \
+                      vaultic hook install --hook post-merge --hook post-checkout\n  \
+                      vaultic hook uninstall                # Remove every Vaultic hook"
     )]
     Hook {
         #[command(subcommand)]
@@ -241,6 +884,20 @@ pub enum Commands {
         action: TemplateAction,
     },
 
+    /// Check environments as a whole
+    #[command(
+        long_about = "Commands that work on every configured environment at once.\n\n\
+                      Use 'vaultic env template-check' in CI to make sure each encrypted \
+                      environment still provides what its template asks for.",
+        after_help = "Examples:\n  \
+                      vaultic env template-check\n  \
+                      vaultic env template-check --report junit -o template-check.xml"
+    )]
+    Env {
+        #[command(subcommand)]
+        action: EnvAction,
+    },
+
     /// Validate secrets against format rules in config.toml
     #[command(
         long_about = "Validate your local .env against format rules in .vaultic/config.toml.\n\n\
@@ -281,6 +938,117 @@ pub enum Commands {
         action: CiAction,
     },
 
+    /// Inspect the config.toml format
+    #[command(
+        long_about = "Commands for working with .vaultic/config.toml.\n\n\
+                      Use 'vaultic config schema' to emit a JSON Schema of the config \
+                      file so editors can validate and autocomplete it.",
+        after_help = "Examples:\n  \
+                      vaultic config schema                         # Print schema to stdout\n  \
+                      vaultic config schema -o vaultic.schema.json  # Write schema to a file"
+    )]
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
+    /// Reports about the project's secrets
+    #[command(
+        long_about = "Reports about the project's secrets.\n\n\
+                      'vaultic report owners' lists template keys by owning team. Owners come \
+                      from '# vaultic:owner=<team>' annotations in the template (the next key, \
+                      or every key with 'prefix=<PREFIX>') and from [owners] in config.toml.\n\n\
+                      With --check <base>, fails if owned keys changed since <base> without an \
+                      audit entry (added since <base>) whose detail mentions the owning team.",
+        after_help = "Examples:\n  \
+                      vaultic report owners\n  \
+                      vaultic report owners --check origin/main   # In a PR pipeline"
+    )]
+    Report {
+        #[command(subcommand)]
+        action: ReportAction,
+    },
+
+    /// Bundle or restore the whole project in one encrypted archive
+    #[command(
+        long_about = "Bundle every encrypted environment, config.toml, and recipients.txt \
+                      into a single encrypted archive, or restore a project from one.\n\n\
+                      The archive is encrypted for the current recipients, so it is safe \
+                      to store as a backup or move to a new repository.",
+        after_help = "Examples:\n  \
+                      vaultic archive create -o project-secrets.vaultic\n  \
+                      vaultic archive restore project-secrets.vaultic\n  \
+                      vaultic archive restore backup.vaultic --force  # Overwrite existing .vaultic/"
+    )]
+    Archive {
+        #[command(subcommand)]
+        action: ArchiveAction,
+    },
+
+    /// Pack one resolved environment for a host without the repository
+    #[command(
+        args_conflicts_with_subcommands = true,
+        long_about = "Resolve an environment (--env) and encrypt it, with its metadata and \
+                      checksums, into one file for a deployment host that cannot clone the \
+                      repository or run the rest of Vaultic against it.\n\n\
+                      The bundle is encrypted only to the --recipient keys, usually the \
+                      host's own age or SSH key, not to the project's recipients. On the \
+                      host, 'vaultic bundle extract' decrypts it with the host key, checks \
+                      the checksums and writes the secrets as a .env file. Creating a bundle \
+                      is recorded in the audit log.",
+        after_help = "Examples:\n  \
+                      vaultic bundle --env prod --recipient age1...             # Writes prod.vaultic-bundle\n  \
+                      vaultic bundle --env prod --recipient \"$(cat deploy.pub)\" -o release.bundle\n  \
+                      vaultic bundle extract prod.vaultic-bundle -o /srv/app/.env   # On the host"
+    )]
+    Bundle {
+        #[command(subcommand)]
+        action: Option<BundleAction>,
+        /// Public key of the deployment host (repeatable)
+        #[arg(long, value_name = "KEY")]
+        recipient: Vec<String>,
+        /// File to write [default: <env>.vaultic-bundle]
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Save, list, compare, and restore named copies of an environment
+    #[command(
+        long_about = "Keep named copies of an environment's encrypted file so secrets can be \
+                      rolled back alongside a bad release.\n\n\
+                      'create' copies the current ciphertext into \
+                      .vaultic/snapshots/<env>/<name>.env.enc with its author, date and \
+                      SHA-256. Nothing is decrypted. 'diff' compares a snapshot with the \
+                      current secrets in memory; 'restore' puts the snapshot back after \
+                      confirmation; 'prune' deletes old snapshots.",
+        after_help = "Examples:\n  \
+                      vaultic snapshot create prod pre-release-42 --note \"before v42\"\n  \
+                      vaultic snapshot list prod\n  \
+                      vaultic snapshot diff prod pre-release-42\n  \
+                      vaultic snapshot restore prod pre-release-42\n  \
+                      vaultic snapshot prune --keep 10 --older-than 90d --dry-run"
+    )]
+    Snapshot {
+        #[command(subcommand)]
+        action: SnapshotAction,
+    },
+
+    /// Manage the vaultic binary itself
+    #[command(
+        name = "self",
+        long_about = "Commands about the installed vaultic binary.\n\n\
+                      'verify' hashes the running binary and compares it with the \
+                      SHA256SUMS.txt published for its version on GitHub Releases, after \
+                      checking that file's minisign signature. A mismatch means the install \
+                      is corrupted, was tampered with, or was built from source.",
+        after_help = "Examples:\n  \
+                      vaultic self verify                   # Check the install against its release"
+    )]
+    SelfCmd {
+        #[command(subcommand)]
+        action: SelfAction,
+    },
+
     /// Update Vaultic to the latest version
     #[command(
         long_about = "Check for and install the latest Vaultic release.\n\n\
@@ -290,67 +1058,489 @@ pub enum Commands {
                       The update is safe: your encrypted files and configuration are \
                       never modified. Only the vaultic binary itself is replaced.",
         after_help = "Examples:\n  \
-                      vaultic update                        # Check and install latest version"
+                      vaultic update                        # Check and install latest version\n  \
+                      vaultic update --check                # Show what's new without installing"
     )]
-    Update,
+    Update {
+        /// Only check for a newer version and show its release notes
+        #[arg(long)]
+        check: bool,
+    },
+
+    /// A command provided by a `vaultic-<name>` executable on PATH
+    #[command(external_subcommand)]
+    External(Vec<String>),
+}
+
+impl Commands {
+    /// Whether this command's stdout carries a payload (secrets or a
+    /// wrapped process) that Vaultic's own output must not pollute.
+    pub fn is_exec(&self) -> bool {
+        match self {
+            Commands::Decrypt { stdout, .. } | Commands::Resolve { stdout, .. } => *stdout,
+            Commands::Ci { .. } | Commands::Run { .. } | Commands::External(_) => true,
+            Commands::Get { copy, .. } => !copy,
+            Commands::Config {
+                action: ConfigAction::Schema { output },
+            } => output.is_none(),
+            _ => false,
+        }
+    }
+
+    /// Whether this command can print `--json` output.
+    pub fn supports_json(&self) -> bool {
+        matches!(
+            self,
+            Commands::Status
+                | Commands::Verify { .. }
+                | Commands::Env { .. }
+                | Commands::Check { .. }
+                | Commands::Diff { .. }
+                | Commands::Log { .. }
+                | Commands::History { .. }
+                | Commands::Keys {
+                    action: KeysAction::List { .. }
+                }
+                | Commands::Snapshot {
+                    action: SnapshotAction::Diff { .. }
+                }
+        )
+    }
 }
 
 #[derive(Subcommand, Debug)]
 pub enum KeysAction {
     /// Generate or import a key for this project
-    #[command(long_about = "Interactive key setup for new users.\n\n\
+    #[command(
+        long_about = "Interactive key setup for new users.\n\n\
                       Options:\n  \
                       1. Generate a new age key (recommended)\n  \
                       2. Import an existing age key from file\n  \
-                      3. Use an existing GPG key from the system keyring")]
-    Setup,
+                      3. Use an existing GPG key from the system keyring\n  \
+                      4. Use your SSH key, when ~/.ssh/id_ed25519 exists\n\n\
+                      With --from-ssh, the SSH key becomes your identity without a menu: \
+                      age decrypts with it directly, so no new key is created and the \
+                      public key to share is the one in its .pub file.",
+        after_help = "Examples:\n  \
+                      vaultic keys setup                          # Interactive menu\n  \
+                      vaultic keys setup --from-ssh               # Use ~/.ssh/id_ed25519\n  \
+                      vaultic keys setup --from-ssh ~/.ssh/id_rsa # Use another SSH key"
+    )]
+    Setup {
+        /// Use an unencrypted ssh-ed25519 or ssh-rsa private key as your
+        /// identity [default: ~/.ssh/id_ed25519]
+        #[arg(
+            long,
+            value_name = "PATH",
+            num_args = 0..=1,
+            default_missing_value = "~/.ssh/id_ed25519"
+        )]
+        from_ssh: Option<String>,
+        /// Copy the public key to share to the clipboard
+        #[arg(long)]
+        copy: bool,
+    },
     /// Add a recipient (public key)
     #[command(after_help = "Accepted formats:\n  \
                             age key:          age1ql3z7hjy54pw...ac8p\n  \
+                            SSH key:          \"ssh-ed25519 AAAAC3Nz... alice@laptop\"\n  \
                             GPG fingerprint:  A1B2C3D4E5F6...\n  \
-                            GPG email:        user@example.com")]
+                            GPG email:        user@example.com\n\n\
+                            With --name, --email, --role or --expires, the member behind the \
+                            key is recorded in .vaultic/members.toml and shown by 'keys list', \
+                            'status' and the audit log. After --expires, 'status', \
+                            'keys audit' and 'encrypt' flag the key.\n\n\
+                            Examples:\n  \
+                            vaultic keys add age1ql3z7hjy54pw... --name \"Alice\" \
+                            --email alice@example.com --role admin\n  \
+                            vaultic keys add age1x9ynm5k... --name \"Contractor\" --expires 2026-12-31")]
     Add {
         /// Public key or identity to add
         identity: String,
+        /// Name of the person or system holding the key
+        #[arg(long)]
+        name: Option<String>,
+        /// Their email address
+        #[arg(long)]
+        email: Option<String>,
+        /// Their role, e.g. admin, developer or ci
+        #[arg(long)]
+        role: Option<String>,
+        /// Last day the key should be a recipient (YYYY-MM-DD)
+        #[arg(long, value_name = "DATE")]
+        expires: Option<String>,
     },
     /// List authorized recipients
-    List,
+    #[command(
+        long_about = "List authorized recipients.\n\n\
+                      With --check-valid, every entry is validated as well: age keys are \
+                      decoded, which verifies their bech32 checksum, SSH keys are parsed, and \
+                      GPG fingerprints and emails must name a public key in the keyring. \
+                      Malformed or truncated lines are flagged, and the command fails when \
+                      any entry cannot be encrypted to.",
+        after_help = "With --json, prints an array of {key, label, added_at, type, envs}, \
+                      plus {valid, problem} with --check-valid.\n\n\
+                      Examples:\n  \
+                      vaultic keys list\n  \
+                      vaultic keys list --check-valid   # Fails on a malformed recipient"
+    )]
+    List {
+        /// Validate every recipient and fail when one is invalid
+        #[arg(long)]
+        check_valid: bool,
+    },
+    /// Show your own public key
+    Show {
+        /// Render the key as a QR code in the terminal
+        #[arg(long)]
+        qr: bool,
+        /// Also copy the key to the clipboard
+        #[arg(long)]
+        copy: bool,
+    },
+    /// Pick which of your keys this project uses
+    #[command(
+        long_about = "Pick the age or GPG key Vaultic reports as yours in this project.\n\n\
+                      For teams mixing age and GPG recipients: 'vaultic status' and \
+                      'vaultic keys setup' list every key on this machine with the \
+                      environments it can decrypt. The choice is stored per user in \
+                      ~/.config/vaultic/identities.json, not in the repository.",
+        after_help = "Examples:\n  \
+                      vaultic keys use age                  # Your age identity file\n  \
+                      vaultic keys use alice@example.com    # A GPG key by email\n  \
+                      vaultic keys use 89ABCDEF01234567     # A GPG key by key ID or fingerprint"
+    )]
+    Use {
+        /// `age`, an age public key, or a GPG fingerprint, key ID or email
+        identity: String,
+    },
     /// Remove a recipient
     Remove {
         /// Public key or identity to remove
         identity: String,
     },
+    /// Replace your age key with a new one and re-encrypt everything
+    #[command(long_about = "Rotate your age identity in one step.\n\n\
+                      Decrypts every environment with the current key, generates a new \
+                      identity, swaps the old public key for the new one in recipients.txt, \
+                      and re-encrypts all environments. The old identity is kept next to \
+                      the new one with a .old suffix until you delete it.")]
+    Rotate {
+        /// Reason for the rotation, recorded in the audit log
+        #[arg(long)]
+        note: Option<String>,
+    },
+    /// Review recipients for expired or soon-to-expire access
+    #[command(
+        long_about = "Review every recipient, in recipients.txt and the per-environment \
+                      lists, against the expires dates in .vaultic/members.toml.\n\n\
+                      Keys that expired are listed with the command to remove them, keys \
+                      expiring within 30 days are flagged, and keys without a member entry \
+                      or date are shown as having no expiry. Exits with code 2 when any \
+                      recipient has expired.",
+        after_help = "Examples:\n  \
+                      vaultic keys audit                    # Review access before a release\n  \
+                      vaultic keys add age1... --expires 2026-12-31   # Give a key an end date"
+    )]
+    Audit,
+    /// Export recipients with their member details as a JSON bundle
+    #[command(
+        long_about = "Export the recipients of recipients.txt, with the name, email, role and \
+                      expiry date recorded in members.toml, as a JSON bundle another project \
+                      can import with 'vaultic keys import'.\n\n\
+                      The bundle is signed like recipients.txt, with a detached minisign \
+                      signature next to it. Importing projects check it against their \
+                      [recipients] admin_keys, or the key given with --signer.",
+        after_help = "Examples:\n  \
+                      vaultic keys export -o team.json                     # Write the bundle\n  \
+                      minisign -Sm team.json -x team.json.sig              # Sign it\n  \
+                      vaultic keys export                                  # Print it to stdout"
+    )]
+    Export {
+        /// File to write the bundle to [default: stdout]
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Add the recipients of a bundle made by `keys export`
+    #[command(
+        long_about = "Add the recipients of a bundle written by 'vaultic keys export' to \
+                      recipients.txt, and their member details to members.toml.\n\n\
+                      Keys already in recipients.txt are skipped, so a team's bundle can be \
+                      imported again whenever it changes. The bundle's minisign signature \
+                      (<bundle>.sig) is checked against [recipients] admin_keys and any \
+                      --signer key; the import fails when it is missing or does not match. \
+                      With neither, the signature is not checked.",
+        after_help = "Examples:\n  \
+                      vaultic keys import team.json                        # Add the team's keys\n  \
+                      vaultic keys import team.json --dry-run              # Preview what would be added\n  \
+                      vaultic keys import team.json --signer RWQ...        # Require the platform team's signature"
+    )]
+    Import {
+        /// Bundle file written by `vaultic keys export`
+        bundle: String,
+        /// Minisign public key the bundle must be signed with (repeatable)
+        #[arg(long, value_name = "KEY")]
+        signer: Vec<String>,
+        /// Show the keys that would be added without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Threshold shares of environments that need several recipients
+    #[command(
+        long_about = "Work with the Shamir shares of environments that set a threshold in \
+                      config.toml, e.g. threshold = 2 under [environments.prod].\n\n\
+                      Such an environment's key is split into one share per recipient, and \
+                      decrypting needs as many shares as the threshold, so no single person \
+                      can read it. One shareholder coordinates: the others release their \
+                      share to the coordinator's key, and the coordinator decrypts with \
+                      'vaultic decrypt --share'.",
+        after_help = "Examples:\n  \
+                      vaultic keys share status                             # Who holds shares\n  \
+                      vaultic keys share release --env prod --to age1...    # Hand your share to age1...\n  \
+                      vaultic decrypt --env prod --share prod.share-2.age   # Decrypt as the coordinator"
+    )]
+    Share {
+        #[command(subcommand)]
+        action: ShareAction,
+    },
+    /// Report recipients that are not on an allow-list
+    #[command(
+        long_about = "Compare recipients against an allow-list (e.g. an HR export).\n\n\
+                            The roster has one email or label per line; '#' starts a comment.\n\
+                            A recipient is on the roster when its key, its label, or an email in \
+                            its label matches an entry (case-insensitive). Exits with code 2 \
+                            when any recipient is not on the roster."
+    )]
+    VerifyRoster {
+        /// Allow-list file with one email or label per line
+        roster: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ShareAction {
+    /// List threshold environments and who holds their shares
+    Status,
+    /// Encrypt your share of an environment to another shareholder
+    #[command(
+        long_about = "Decrypt your share of a threshold environment (--env) and encrypt it \
+                      to another shareholder's key, who passes the file to \
+                      'vaultic decrypt --share'.\n\n\
+                      Only recipients of the environment can receive a share, and the file \
+                      only works for the ciphertext it was released from: once the \
+                      environment is re-encrypted, shares must be released again. Every \
+                      release is recorded in the audit log."
+    )]
+    Release {
+        /// Public key of the shareholder who collects the shares
+        #[arg(long, value_name = "KEY")]
+        to: String,
+        /// File to write [default: <env>.share-<index>.age]
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ReportAction {
+    /// Show which team owns each key
+    Owners {
+        /// Git revision to compare against; require owner sign-off for changes
+        #[arg(long, value_name = "BASE_REF")]
+        check: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ArchiveAction {
+    /// Write all encrypted files, config, and recipients to one archive
+    Create {
+        /// Archive path to write
+        #[arg(short, long, default_value = "project-secrets.vaultic")]
+        output: String,
+    },
+    /// Unpack and validate an archive into .vaultic/
+    Restore {
+        /// Archive file to restore from
+        input: String,
+        /// Overwrite an existing initialized .vaultic/ directory
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BundleAction {
+    /// Decrypt a bundle with this host's key and write its secrets
+    #[command(
+        long_about = "Decrypt a bundle made by 'vaultic bundle', check every file against its \
+                      checksums, and write the secrets as dotenv. Needs no Vaultic project: \
+                      only a private key the bundle was encrypted to, from --key, \
+                      VAULTIC_AGE_KEY or the default key location."
+    )]
+    Extract {
+        /// Bundle file written by `vaultic bundle`
+        bundle: String,
+        /// Where to write the secrets
+        #[arg(short, long, default_value = ".env")]
+        output: String,
+        /// Path to the host's private key file
+        #[arg(long)]
+        key: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SelfAction {
+    /// Compare the running binary with the SHA-256 published for its release
+    Verify,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SnapshotAction {
+    /// Copy an environment's current ciphertext into a named snapshot
+    Create {
+        /// Environment to snapshot
+        #[arg(value_name = "ENV")]
+        environment: String,
+        /// Snapshot name, e.g. pre-release-42
+        name: String,
+        /// Note stored with the snapshot
+        #[arg(long)]
+        note: Option<String>,
+    },
+    /// List snapshots, newest first
+    List {
+        /// Only list snapshots of this environment
+        #[arg(value_name = "ENV")]
+        environment: Option<String>,
+    },
+    /// Replace an environment's ciphertext with a snapshot
+    Restore {
+        /// Environment to restore
+        #[arg(value_name = "ENV")]
+        environment: String,
+        /// Snapshot name
+        name: String,
+    },
+    /// Compare a snapshot with the environment's current secrets
+    Diff {
+        /// Environment to compare
+        #[arg(value_name = "ENV")]
+        environment: String,
+        /// Snapshot name
+        name: String,
+    },
+    /// Delete old snapshots, keeping the newest per environment
+    #[command(after_help = "Examples:\n  \
+                            vaultic snapshot prune --keep 10 --older-than 90d --dry-run\n  \
+                            vaultic snapshot prune prod --keep 5")]
+    Prune {
+        /// Only prune snapshots of this environment
+        #[arg(value_name = "ENV")]
+        environment: Option<String>,
+        /// Always keep this many of the newest snapshots per environment
+        #[arg(long, value_name = "N", required_unless_present = "older_than")]
+        keep: Option<usize>,
+        /// Only delete snapshots older than this age (e.g. 90d, 12w, 48h)
+        #[arg(long, value_name = "AGE")]
+        older_than: Option<String>,
+        /// List what would be deleted without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 #[derive(Subcommand, Debug)]
 pub enum HookAction {
-    /// Install git pre-commit hook
-    Install,
-    /// Uninstall git pre-commit hook
+    /// Install git hooks (pre-commit by default)
+    Install {
+        /// Hook to install instead of pre-commit; repeat for several
+        #[arg(
+            long = "hook",
+            value_name = "HOOK",
+            value_parser = ["pre-commit", "pre-push", "post-merge", "post-checkout", "post-commit"]
+        )]
+        hooks: Vec<String>,
+        /// Also install a post-commit hook that records commits made with --no-verify
+        #[arg(long)]
+        post_commit: bool,
+    },
+    /// Uninstall every hook Vaultic installed
     Uninstall,
+    /// Check the last commit for files the pre-commit hook blocks (run by the post-commit hook)
+    #[command(hide = true)]
+    PostCommit,
+    /// Check that environments are encrypted for their current recipients (run by the pre-push hook)
+    #[command(hide = true)]
+    PrePush,
+    /// Report encrypted environments changed by a merge (run by the post-merge hook)
+    #[command(hide = true)]
+    PostMerge,
+    /// Report encrypted environments changed by a checkout (run by the post-checkout hook)
+    #[command(hide = true)]
+    PostCheckout {
+        /// Previous HEAD
+        previous: Option<String>,
+        /// New HEAD
+        new: Option<String>,
+        /// 1 for a branch checkout, 0 for a file checkout
+        branch_flag: Option<String>,
+    },
 }
 
 #[derive(Subcommand, Debug)]
 pub enum TemplateAction {
-    /// Sync .env.template from encrypted environments
+    /// Regenerate .env.template from encrypted environments
     #[command(
+        visible_alias = "generate",
         long_about = "Auto-generate .env.template from all encrypted environments.\n\n\
-                      Decrypts each environment in memory, collects all keys (union of all envs), \
-                      strips all values, and writes the result to .env.template.\n\n\
+                      Decrypts each environment in memory, collects all keys (union of all envs) \
+                      with the comments above them, strips all values, and writes the result \
+                      to the [vaultic] template path (default .env.template).\n\n\
                       This keeps your template always in sync with the actual secrets \
                       without ever exposing values.\n\n\
                       Requires your private key to decrypt the environments.",
         after_help = "Examples:\n  \
-                      vaultic template sync              # Sync .env.template\n  \
+                      vaultic template generate          # Regenerate .env.template\n  \
                       vaultic template sync -o my.template  # Write to custom path"
     )]
     Sync {
-        /// Output path (default: .env.template)
+        /// Output path (default: [vaultic] template, or .env.template)
         #[arg(short, long)]
         output: Option<String>,
     },
 }
 
+#[derive(Subcommand, Debug)]
+pub enum EnvAction {
+    /// Check every encrypted environment against its template
+    #[command(
+        long_about = "Resolve every environment that has an encrypted file, in memory, and \
+                      compare it with its template: the environment's `template` in \
+                      config.toml, .vaultic/<env>.env.template, or the project template.\n\n\
+                      Reports missing, extra and empty variables per environment. \
+                      Environments that cannot be decrypted or have no template are \
+                      reported as errors. Exits with code 1 when any environment does not \
+                      match, so pipelines can stop before a deploy.\n\n\
+                      --report json or --report junit prints a machine-readable report \
+                      instead of the table, or writes it to --output.",
+        after_help = "Examples:\n  \
+                      vaultic env template-check                    # Table for every environment\n  \
+                      vaultic env template-check --env prod         # Only prod\n  \
+                      vaultic env template-check --report json      # JSON on stdout\n  \
+                      vaultic env template-check --report junit -o template-check.xml"
+    )]
+    TemplateCheck {
+        /// Report format: json or junit (default: table)
+        #[arg(long, value_parser = ["json", "junit"])]
+        report: Option<String>,
+        /// Write the report to this file instead of stdout
+        #[arg(short, long, requires = "report")]
+        output: Option<String>,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 pub enum CiAction {
     /// Export secrets for CI/CD pipelines
@@ -377,3 +1567,58 @@ pub enum CiAction {
         mask: bool,
     },
 }
+
+#[derive(Subcommand, Debug)]
+pub enum SyncTarget {
+    /// Push to AWS Secrets Manager or SSM Parameter Store
+    #[command(
+        long_about = "Push the resolved environment to AWS using the aws CLI and its \
+                      configured credentials.\n\n\
+                      Services:\n  \
+                      • secrets-manager — one JSON secret holding every key (default name: \
+                      vaultic/<env>)\n  \
+                      • ssm — one SecureString parameter per key under a path prefix \
+                      (default: /vaultic/<env>)\n\n\
+                      The store is made to match the environment exactly: keys no longer \
+                      in the environment are removed. Secret values are handed to the aws \
+                      CLI through a private temp file, never on its command line.",
+        after_help = "Examples:\n  \
+                      vaultic sync aws --env prod --dry-run\n  \
+                      vaultic sync aws --env prod --name myapp/prod --region eu-west-1\n  \
+                      vaultic sync aws --env prod --service ssm --name /myapp/prod --yes"
+    )]
+    Aws {
+        /// AWS service: secrets-manager or ssm
+        #[arg(long, default_value = "secrets-manager")]
+        service: String,
+        /// Secret name (secrets-manager) or parameter path prefix (ssm)
+        #[arg(long)]
+        name: Option<String>,
+        /// AWS region (default: from the aws CLI configuration)
+        #[arg(long)]
+        region: Option<String>,
+        /// Show what would change without pushing
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    /// Print a JSON Schema for config.toml
+    #[command(
+        long_about = "Emit a JSON Schema describing .vaultic/config.toml.\n\n\
+                      The schema is generated from the same definitions Vaultic uses to \
+                      read the config, so it always matches this version.\n\n\
+                      Editors with TOML schema support (e.g. Taplo / Even Better TOML) \
+                      can use it for validation and autocompletion.",
+        after_help = "Examples:\n  \
+                      vaultic config schema > vaultic.schema.json\n  \
+                      vaultic config schema -o vaultic.schema.json"
+    )]
+    Schema {
+        /// Output path (default: stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+}
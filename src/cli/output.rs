@@ -1,8 +1,14 @@
+use std::io::{BufRead, IsTerminal, Write};
 use std::sync::OnceLock;
 use std::time::Duration;
 
 use colored::Colorize;
 use indicatif::{ProgressBar, ProgressStyle};
+use serde::Serialize;
+
+use crate::cli::i18n;
+use crate::config::user_config::ColorChoice;
+use crate::core::errors::{Result, VaulticError};
 
 /// Verbosity level for CLI output.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -14,9 +20,30 @@ pub enum Verbosity {
 
 static VERBOSITY: OnceLock<Verbosity> = OnceLock::new();
 
+static EXEC_MODE: OnceLock<bool> = OnceLock::new();
+
+static JSON_MODE: OnceLock<bool> = OnceLock::new();
+
+/// How interactive prompts are answered.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PromptMode {
+    /// Ask the user on the terminal (EOF picks the default).
+    Interactive,
+    /// `--yes`: accept every confirmation and the default choice.
+    AssumeYes,
+    /// `--no-input`: never read stdin; use defaults or fail.
+    NoInput,
+}
+
+static PROMPT_MODE: OnceLock<PromptMode> = OnceLock::new();
+
 /// Initialize the global verbosity level. Must be called once at startup.
-pub fn init(verbose: bool, quiet: bool) {
-    let level = if quiet {
+///
+/// In exec mode (commands whose stdout belongs to secrets or a child
+/// process), Vaultic's own messages go to stderr and everything except
+/// errors is suppressed unless `--verbose` is given.
+pub fn init(verbose: bool, quiet: bool, exec: bool) {
+    let level = if quiet || (exec && !verbose) {
         Verbosity::Quiet
     } else if verbose {
         Verbosity::Verbose
@@ -24,6 +51,70 @@ pub fn init(verbose: bool, quiet: bool) {
         Verbosity::Normal
     };
     let _ = VERBOSITY.set(level);
+    let _ = EXEC_MODE.set(exec);
+}
+
+/// Apply the user config `color` preference. `NO_COLOR` still wins over
+/// `always`.
+pub fn init_color(choice: ColorChoice) {
+    match choice {
+        ColorChoice::Auto => {}
+        ColorChoice::Always if std::env::var_os("NO_COLOR").is_some() => {}
+        ColorChoice::Always => colored::control::set_override(true),
+        ColorChoice::Never => colored::control::set_override(false),
+    }
+}
+
+/// Enable `--json` output. Must be called once at startup.
+///
+/// Commands that support it print a single JSON document on stdout
+/// instead of their formatted report; run them in exec mode so nothing
+/// else reaches stdout.
+pub fn init_json(json: bool) {
+    let _ = JSON_MODE.set(json);
+}
+
+/// Whether `--json` was given.
+pub fn is_json_mode() -> bool {
+    JSON_MODE.get().copied().unwrap_or(false)
+}
+
+/// Print `value` as pretty-printed JSON on stdout.
+pub fn json<T: Serialize + ?Sized>(value: &T) -> Result<()> {
+    println!("{}", to_json(value)?);
+    Ok(())
+}
+
+/// `value` as pretty-printed JSON, as [`json`] prints it.
+pub fn to_json<T: Serialize + ?Sized>(value: &T) -> Result<String> {
+    serde_json::to_string_pretty(value).map_err(|e| VaulticError::InvalidConfig {
+        detail: format!("Failed to serialize JSON output: {e}"),
+    })
+}
+
+/// Initialize how prompts are answered. Must be called once at startup.
+pub fn init_prompts(assume_yes: bool, no_input: bool) {
+    let mode = if assume_yes {
+        PromptMode::AssumeYes
+    } else if no_input {
+        PromptMode::NoInput
+    } else {
+        PromptMode::Interactive
+    };
+    let _ = PROMPT_MODE.set(mode);
+}
+
+/// Whether prompts are answered by the user rather than by `--yes` or
+/// `--no-input`.
+pub fn is_interactive() -> bool {
+    prompt_mode() == PromptMode::Interactive
+}
+
+fn prompt_mode() -> PromptMode {
+    PROMPT_MODE
+        .get()
+        .copied()
+        .unwrap_or(PromptMode::Interactive)
 }
 
 /// Get the current verbosity level.
@@ -31,17 +122,31 @@ fn verbosity() -> Verbosity {
     VERBOSITY.get().copied().unwrap_or(Verbosity::Normal)
 }
 
+/// Whether output is running in exec mode (stdout reserved for payload).
+pub fn is_exec_mode() -> bool {
+    EXEC_MODE.get().copied().unwrap_or(false)
+}
+
+/// Print a line to stdout, or to stderr in exec mode.
+fn emit(line: String) {
+    if is_exec_mode() {
+        eprintln!("{line}");
+    } else {
+        println!("{line}");
+    }
+}
+
 /// Print a success message (suppressed in quiet mode).
 pub fn success(msg: &str) {
     if verbosity() != Verbosity::Quiet {
-        println!("  {} {}", "✓".green(), msg);
+        emit(format!("  {} {}", "✓".green(), msg));
     }
 }
 
 /// Print a warning message (suppressed in quiet mode).
 pub fn warning(msg: &str) {
     if verbosity() != Verbosity::Quiet {
-        println!("  {} {}", "⚠".yellow(), msg);
+        emit(format!("  {} {}", "⚠".yellow(), msg));
     }
 }
 
@@ -53,17 +158,158 @@ pub fn error(msg: &str) {
 /// Print a header line (suppressed in quiet mode).
 pub fn header(msg: &str) {
     if verbosity() != Verbosity::Quiet {
-        println!("\n{}", msg.bold());
+        emit(format!("\n{}", msg.bold()));
     }
 }
 
 /// Print a detail message (only shown in verbose mode).
 pub fn detail(msg: &str) {
     if verbosity() == Verbosity::Verbose {
-        println!("  {} {}", "·".dimmed(), msg);
+        emit(format!("  {} {}", "·".dimmed(), msg));
+    }
+}
+
+/// Print an indented, dimmed block such as release notes (suppressed in quiet mode).
+pub fn quote(text: &str) {
+    if verbosity() != Verbosity::Quiet {
+        for line in text.lines() {
+            emit(format!("    {}", line.dimmed()));
+        }
     }
 }
 
+/// Width of the terminal in columns.
+///
+/// Falls back to `$COLUMNS`, then 100, when stdout is not a terminal.
+pub fn terminal_width() -> usize {
+    if let Some((terminal_size::Width(w), _)) = terminal_size::terminal_size() {
+        return w as usize;
+    }
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.parse().ok())
+        .filter(|&c: &usize| c > 0)
+        .unwrap_or(100)
+}
+
+/// Print `prompt` and read one trimmed line from the terminal.
+///
+/// Fails when stdin is not a terminal: a pipe or `< /dev/null` in CI may
+/// never deliver a line, so scripts must say how to answer with `--yes`
+/// or `--no-input` instead. Returns `None` on EOF (Ctrl-D), and callers
+/// fall back to their default.
+fn read_answer(prompt: &str) -> Result<Option<String>> {
+    if !std::io::stdin().is_terminal() {
+        return Err(VaulticError::InvalidConfig {
+            detail: i18n::t_args("input-no-terminal", &[("prompt", prompt.trim_end())]),
+        });
+    }
+    if is_exec_mode() {
+        eprint!("  {prompt}");
+        std::io::stderr().flush()?;
+    } else {
+        print!("  {prompt}");
+        std::io::stdout().flush()?;
+    }
+
+    let mut input = String::new();
+    let read = std::io::stdin().lock().read_line(&mut input)?;
+    if read == 0 {
+        emit(String::new());
+        return Ok(None);
+    }
+    Ok(Some(input.trim().to_string()))
+}
+
+/// Ask a yes/no question. `--yes` answers yes, `--no-input` and EOF
+/// take `default`. Fails without a terminal unless one of them is given.
+pub fn confirm(prompt: &str, default: bool) -> Result<bool> {
+    match prompt_mode() {
+        PromptMode::AssumeYes => return Ok(true),
+        PromptMode::NoInput => return Ok(default),
+        PromptMode::Interactive => {}
+    }
+    let hint = i18n::t(if default {
+        "confirm-hint-default-yes"
+    } else {
+        "confirm-hint-default-no"
+    });
+    let answer = read_answer(&format!("{prompt} {hint}: "))?;
+    Ok(match answer.as_deref().map(str::to_lowercase).as_deref() {
+        None | Some("") => default,
+        Some(answer) => i18n::t("confirm-yes-answers")
+            .split(',')
+            .any(|yes| yes.trim() == answer),
+    })
+}
+
+/// Ask for a value with a default. `--yes`, `--no-input`, an empty
+/// answer, and EOF all return `default`. Fails without a terminal
+/// unless `--yes` or `--no-input` is given.
+pub fn input(prompt: &str, default: &str) -> Result<String> {
+    if prompt_mode() != PromptMode::Interactive {
+        return Ok(default.to_string());
+    }
+    let answer = read_answer(&format!("{prompt} [{default}]: "))?;
+    Ok(match answer {
+        Some(a) if !a.is_empty() => a,
+        _ => default.to_string(),
+    })
+}
+
+/// Ask for a value that has no sensible default.
+///
+/// Fails under `--yes` / `--no-input` and without a terminal; returns an
+/// empty string on EOF.
+pub fn input_required(prompt: &str) -> Result<String> {
+    if prompt_mode() != PromptMode::Interactive {
+        return Err(VaulticError::InvalidConfig {
+            detail: i18n::t_args("input-required", &[("prompt", prompt)]),
+        });
+    }
+    Ok(read_answer(&format!("{prompt}: "))?.unwrap_or_default())
+}
+
+/// Ask the user to pick one of `items` with a fuzzy-searchable list.
+///
+/// Returns `None` when no terminal is attached (or in exec mode, or
+/// under `--yes` / `--no-input`), so
+/// callers can fall back to a non-interactive default or an error.
+pub fn select(prompt: &str, items: &[&str], default: usize) -> Result<Option<usize>> {
+    if is_exec_mode()
+        || prompt_mode() != PromptMode::Interactive
+        || !std::io::stdin().is_terminal()
+        || !std::io::stderr().is_terminal()
+    {
+        return Ok(None);
+    }
+    dialoguer::FuzzySelect::new()
+        .with_prompt(prompt)
+        .items(items)
+        .default(default)
+        .interact_opt()
+        .map_err(|e| VaulticError::Io(std::io::Error::other(e)))
+}
+
+/// Ask the user to tick any number of `items`.
+///
+/// Returns the chosen indices, or `None` when no terminal is attached
+/// (or in exec mode, or under `--yes` / `--no-input`).
+pub fn multi_select(prompt: &str, items: &[&str]) -> Result<Option<Vec<usize>>> {
+    if is_exec_mode()
+        || prompt_mode() != PromptMode::Interactive
+        || !std::io::stdin().is_terminal()
+        || !std::io::stderr().is_terminal()
+    {
+        return Ok(None);
+    }
+    dialoguer::MultiSelect::new()
+        .with_prompt(prompt)
+        .items(items)
+        .interact_opt()
+        .map_err(|e| VaulticError::Io(std::io::Error::other(e)))
+}
+
 /// Start a spinner with the given message. Returns `None` in quiet mode.
 pub fn spinner(msg: &str) -> Option<ProgressBar> {
     if verbosity() == Verbosity::Quiet {
@@ -81,6 +327,26 @@ pub fn spinner(msg: &str) -> Option<ProgressBar> {
     Some(pb)
 }
 
+/// Create a download progress bar, or `None` in quiet mode.
+///
+/// Starts without a length; set it with `set_length` once the size is
+/// known. Finish it with `finish_spinner`.
+pub fn download_bar(msg: &str) -> Option<ProgressBar> {
+    if verbosity() == Verbosity::Quiet {
+        return None;
+    }
+    let pb = ProgressBar::no_length();
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("  {spinner:.green} {msg} [{bar:30.green/dim}] {bytes}/{total_bytes} ({eta})")
+            .expect("valid progress template")
+            .progress_chars("=> "),
+    );
+    pb.set_message(msg.to_string());
+    pb.enable_steady_tick(Duration::from_millis(80));
+    Some(pb)
+}
+
 /// Finish a spinner with a success message.
 pub fn finish_spinner(spinner: Option<ProgressBar>, msg: &str) {
     if let Some(pb) = spinner {
@@ -0,0 +1,146 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::core::errors::Result;
+
+/// Sensitive files that must be removed if the process is interrupted.
+static SENSITIVE_PATHS: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+/// Set while `vaultic run` waits for its child process.
+static CHILD_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Set while a secret is on the clipboard waiting to be cleared.
+static CLIPBOARD_SECRET: AtomicBool = AtomicBool::new(false);
+
+/// Install the Ctrl-C handler. Must be called once at startup.
+///
+/// On interrupt (Ctrl-C, or SIGTERM / SIGHUP when the process is killed
+/// or its terminal closes), removes every registered sensitive file,
+/// clears a secret left on the clipboard, restores the terminal cursor
+/// (spinners hide it), and exits with code 130.
+/// While a child process runs, the interrupt is left to the child.
+pub fn init() {
+    let _ = ctrlc::set_handler(|| {
+        if CHILD_RUNNING.load(Ordering::SeqCst) {
+            return;
+        }
+        remove_all();
+        if CLIPBOARD_SECRET.load(Ordering::SeqCst) {
+            crate::adapters::clipboard::system_clipboard::clear();
+        }
+        let mut stderr = std::io::stderr();
+        let _ = writeln!(stderr, "\x1b[?25h");
+        let _ = stderr.flush();
+        std::process::exit(130);
+    });
+}
+
+/// Mark whether a child process owns the terminal. The child receives
+/// Ctrl-C itself; Vaultic keeps running until it exits.
+pub fn set_child_running(running: bool) {
+    CHILD_RUNNING.store(running, Ordering::SeqCst);
+}
+
+/// Mark whether the clipboard holds a secret that Ctrl-C must clear.
+pub fn set_clipboard_secret(held: bool) {
+    CLIPBOARD_SECRET.store(held, Ordering::SeqCst);
+}
+
+/// Remove all registered sensitive files. Errors are ignored.
+fn remove_all() {
+    if let Ok(mut paths) = SENSITIVE_PATHS.lock() {
+        for path in paths.drain(..) {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+fn register(path: &Path) {
+    if let Ok(mut paths) = SENSITIVE_PATHS.lock() {
+        paths.push(path.to_path_buf());
+    }
+}
+
+/// Whether `path` would be removed on Ctrl-C.
+#[cfg(test)]
+pub(crate) fn is_registered(path: &Path) -> bool {
+    SENSITIVE_PATHS
+        .lock()
+        .is_ok_and(|paths| paths.iter().any(|p| p == path))
+}
+
+fn unregister(path: &Path) {
+    if let Ok(mut paths) = SENSITIVE_PATHS.lock() {
+        paths.retain(|p| p != path);
+    }
+}
+
+/// Guard for a file holding sensitive data (plaintext, private keys).
+///
+/// The file is deleted when the guard is dropped or when the user hits
+/// Ctrl-C, so an aborted operation never leaves secrets behind.
+/// Call [`SensitiveFile::persist`] to keep the file at its final location.
+pub struct SensitiveFile {
+    path: PathBuf,
+}
+
+impl SensitiveFile {
+    /// Start tracking `path`. The file itself is created by the caller.
+    pub fn new(path: PathBuf) -> Self {
+        register(&path);
+        Self { path }
+    }
+
+    /// Path of the guarded file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Atomically move the file to `dest` and stop tracking it.
+    pub fn persist(self, dest: &Path) -> Result<()> {
+        std::fs::rename(&self.path, dest)?;
+        unregister(&self.path);
+        std::mem::forget(self);
+        Ok(())
+    }
+}
+
+impl Drop for SensitiveFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+        unregister(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drop_removes_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plain.tmp");
+        {
+            let guard = SensitiveFile::new(path.clone());
+            std::fs::write(guard.path(), "SECRET=1").unwrap();
+        }
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn persist_moves_file_and_keeps_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let tmp = dir.path().join("keys.txt.tmp");
+        let dest = dir.path().join("keys.txt");
+
+        let guard = SensitiveFile::new(tmp.clone());
+        std::fs::write(guard.path(), "AGE-SECRET-KEY-1").unwrap();
+        guard.persist(&dest).unwrap();
+
+        assert!(!tmp.exists());
+        assert_eq!(std::fs::read_to_string(&dest).unwrap(), "AGE-SECRET-KEY-1");
+        assert!(!SENSITIVE_PATHS.lock().unwrap().contains(&tmp));
+    }
+}
@@ -0,0 +1,413 @@
+use colored::{Color, Colorize};
+
+use crate::cli::output;
+
+/// Left margin printed before every row.
+const INDENT: &str = "  ";
+
+/// How a column shortens values that do not fit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Truncate {
+    /// Keep the start: `hello...`.
+    End,
+    /// Keep both ends, useful for keys: `age1qy...x7f2`.
+    Middle,
+}
+
+/// A table column and how much it may shrink.
+#[derive(Debug, Clone)]
+pub struct Column {
+    header: String,
+    min_width: Option<usize>,
+    truncate: Truncate,
+}
+
+impl Column {
+    /// A column that always gets its full width.
+    pub fn new(header: &str) -> Self {
+        Self {
+            header: header.to_string(),
+            min_width: None,
+            truncate: Truncate::End,
+        }
+    }
+
+    /// Allow the column to shrink down to `min_width` on narrow terminals.
+    pub fn shrink(mut self, min_width: usize) -> Self {
+        self.min_width = Some(min_width);
+        self
+    }
+
+    /// Truncate in the middle instead of at the end.
+    pub fn middle(mut self) -> Self {
+        self.truncate = Truncate::Middle;
+        self
+    }
+}
+
+/// A single value with optional styling. Styling is applied after
+/// padding so colors never throw off the alignment.
+#[derive(Debug, Clone, Default)]
+pub struct Cell {
+    text: String,
+    color: Option<Color>,
+    dimmed: bool,
+}
+
+impl Cell {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn dimmed(mut self) -> Self {
+        self.dimmed = true;
+        self
+    }
+
+    fn style(&self, padded: String) -> String {
+        match (self.color, self.dimmed) {
+            (Some(c), true) => padded.color(c).dimmed().to_string(),
+            (Some(c), false) => padded.color(c).to_string(),
+            (None, true) => padded.dimmed().to_string(),
+            (None, false) => padded,
+        }
+    }
+}
+
+impl From<&str> for Cell {
+    fn from(text: &str) -> Self {
+        Cell::new(text)
+    }
+}
+
+impl From<String> for Cell {
+    fn from(text: String) -> Self {
+        Cell::new(text)
+    }
+}
+
+/// Rows of cells laid out to fit the terminal width.
+///
+/// Columns start at their natural width (widest cell or header). When the
+/// row is wider than the terminal, shrinkable columns give up space, widest
+/// first, until it fits or every one is at its minimum.
+pub struct Table {
+    columns: Vec<Column>,
+    rows: Vec<Vec<Cell>>,
+    separator: String,
+    show_header: bool,
+    width: Option<usize>,
+}
+
+impl Table {
+    pub fn new(columns: Vec<Column>) -> Self {
+        Self {
+            columns,
+            rows: Vec::new(),
+            separator: "   ".to_string(),
+            show_header: true,
+            width: None,
+        }
+    }
+
+    /// Text placed between columns (default: three spaces).
+    pub fn separator(mut self, separator: &str) -> Self {
+        self.separator = separator.to_string();
+        self
+    }
+
+    /// Do not print the header and rule lines.
+    pub fn headerless(mut self) -> Self {
+        self.show_header = false;
+        self
+    }
+
+    /// Lay out for `width` columns instead of the terminal width.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Append a row. Missing trailing cells are left blank.
+    pub fn row(&mut self, cells: Vec<Cell>) {
+        self.rows.push(cells);
+    }
+
+    /// Width assigned to each column.
+    pub fn layout(&self) -> Vec<usize> {
+        let mut widths: Vec<usize> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, col)| {
+                let header = if self.show_header {
+                    col.header.chars().count()
+                } else {
+                    0
+                };
+                self.rows
+                    .iter()
+                    .filter_map(|r| r.get(i))
+                    .map(|c| c.text.chars().count())
+                    .max()
+                    .unwrap_or(0)
+                    .max(header)
+            })
+            .collect();
+
+        let total = self.width.unwrap_or_else(output::terminal_width);
+        let gaps = self.separator.chars().count() * self.columns.len().saturating_sub(1);
+        let available = total.saturating_sub(INDENT.len() + gaps);
+
+        while widths.iter().sum::<usize>() > available {
+            let widest = self
+                .columns
+                .iter()
+                .zip(&widths)
+                .enumerate()
+                .filter(|(_, (col, w))| col.min_width.is_some_and(|min| **w > min))
+                .max_by_key(|(_, (_, w))| **w)
+                .map(|(i, _)| i);
+            match widest {
+                Some(i) => widths[i] -= 1,
+                None => break,
+            }
+        }
+        widths
+    }
+
+    /// Render the header (if any) and all rows as printable lines.
+    pub fn render(&self) -> Vec<String> {
+        let widths = self.layout();
+        let mut lines = Vec::new();
+
+        if self.show_header {
+            let cells: Vec<Cell> = self
+                .columns
+                .iter()
+                .map(|c| Cell::new(c.header.clone()))
+                .collect();
+            lines.push(self.render_row(&cells, &widths).bold().to_string());
+            let rule = widths.iter().sum::<usize>()
+                + self.separator.chars().count() * widths.len().saturating_sub(1);
+            lines.push(format!("{INDENT}{}", "─".repeat(rule)));
+        }
+
+        for row in &self.rows {
+            lines.push(self.render_row(row, &widths));
+        }
+        lines
+    }
+
+    /// Print the rendered table to stdout.
+    pub fn print(&self) {
+        for line in self.render() {
+            println!("{line}");
+        }
+    }
+
+    fn render_row(&self, cells: &[Cell], widths: &[usize]) -> String {
+        let blank = Cell::default();
+        let mut parts: Vec<(&Cell, String)> = self
+            .columns
+            .iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(i, (col, &width))| {
+                let cell = cells.get(i).unwrap_or(&blank);
+                let text = match col.truncate {
+                    Truncate::End => truncate(&cell.text, width),
+                    Truncate::Middle => truncate_middle(&cell.text, width),
+                };
+                (cell, text)
+            })
+            .collect();
+        // Trailing blank cells are dropped and the last cell is not padded,
+        // so lines carry no trailing whitespace.
+        while parts.last().is_some_and(|(_, text)| text.is_empty()) {
+            parts.pop();
+        }
+
+        let last = parts.len().saturating_sub(1);
+        let styled: Vec<String> = parts
+            .into_iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(i, ((cell, text), &width))| {
+                let pad = if i == last {
+                    0
+                } else {
+                    width.saturating_sub(text.chars().count())
+                };
+                cell.style(format!("{text}{}", " ".repeat(pad)))
+            })
+            .collect();
+        format!(
+            "{INDENT}{}",
+            styled.join(&self.separator.dimmed().to_string())
+        )
+    }
+}
+
+/// Truncate a string to `max_len` characters, appending "..." if needed.
+/// Uses char boundaries to avoid panic on multibyte UTF-8 sequences.
+pub fn truncate(s: &str, max_len: usize) -> String {
+    let char_count = s.chars().count();
+    if char_count <= max_len {
+        s.to_string()
+    } else {
+        let limit = max_len.saturating_sub(3);
+        let truncated: String = s.chars().take(limit).collect();
+        format!("{truncated}...")
+    }
+}
+
+/// Truncate a string for display, showing start and end.
+pub fn truncate_middle(key: &str, max_len: usize) -> String {
+    let char_count = key.chars().count();
+    if char_count <= max_len {
+        key.to_string()
+    } else {
+        let keep = max_len.saturating_sub(3) / 2;
+        let start: String = key.chars().take(keep).collect();
+        let end: String = key.chars().skip(char_count - keep).collect();
+        format!("{start}...{end}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(width: usize) -> Table {
+        let mut table = Table::new(vec![
+            Column::new("Variable").shrink(8),
+            Column::new("Value").shrink(6),
+            Column::new("Change"),
+        ])
+        .width(width);
+        table.row(vec![
+            "DATABASE_CONNECTION_URL".into(),
+            "postgres://db.internal:5432/app".into(),
+            "url host".into(),
+        ]);
+        table.row(vec!["PORT".into(), "5432".into(), "length".into()]);
+        table
+    }
+
+    #[test]
+    fn layout_uses_natural_widths_when_they_fit() {
+        assert_eq!(sample(200).layout(), vec![23, 31, 8]);
+    }
+
+    #[test]
+    fn layout_shrinks_widest_column_first() {
+        // 2 indent + 2 × 3 separator leaves 52 for the columns.
+        let widths = sample(60).layout();
+        assert_eq!(widths.iter().sum::<usize>(), 52);
+        assert_eq!(widths[2], 8);
+        assert!(widths[1] >= widths[0]);
+    }
+
+    #[test]
+    fn layout_stops_at_minimum_widths() {
+        assert_eq!(sample(10).layout(), vec![8, 6, 8]);
+    }
+
+    #[test]
+    fn render_truncates_and_aligns() {
+        colored::control::set_override(false);
+        let table = sample(40);
+        let widths = table.layout();
+        let lines = table.render();
+        let rows = &lines[2..];
+        assert!(rows[0].starts_with("  DATA") && rows[0].contains("..."));
+        let value_col = 2 + widths[0] + 3;
+        assert!(rows[0][value_col..].starts_with("post"));
+        assert!(rows[1][value_col..].starts_with("5432"));
+        assert!(lines.iter().all(|l| !l.ends_with(' ')));
+    }
+
+    #[test]
+    fn missing_cells_render_blank() {
+        colored::control::set_override(false);
+        let mut table = Table::new(vec![Column::new("A"), Column::new("B")])
+            .headerless()
+            .width(80);
+        table.row(vec!["x".into()]);
+        assert_eq!(table.render(), vec!["  x".to_string()]);
+    }
+
+    #[test]
+    fn truncate_short_string_unchanged() {
+        assert_eq!(truncate("hello", 10), "hello");
+    }
+
+    #[test]
+    fn truncate_exact_length_unchanged() {
+        assert_eq!(truncate("hello", 5), "hello");
+    }
+
+    #[test]
+    fn truncate_long_string() {
+        assert_eq!(truncate("hello world!", 8), "hello...");
+    }
+
+    #[test]
+    fn truncate_unicode_safe() {
+        // "contraseña" has 10 chars but 11 bytes (ñ = 2 bytes)
+        let result = truncate("contraseña", 8);
+        assert_eq!(result, "contr...");
+        // Should not panic
+        let _ = truncate("日本語テスト", 5);
+    }
+
+    #[test]
+    fn truncate_empty_string() {
+        assert_eq!(truncate("", 5), "");
+    }
+
+    #[test]
+    fn truncate_max_len_zero() {
+        assert_eq!(truncate("hello", 0), "...");
+    }
+
+    #[test]
+    fn truncate_middle_short_string_unchanged() {
+        assert_eq!(truncate_middle("abc", 10), "abc");
+    }
+
+    #[test]
+    fn truncate_middle_exact_length_unchanged() {
+        assert_eq!(truncate_middle("abcdefghij", 10), "abcdefghij");
+    }
+
+    #[test]
+    fn truncate_middle_long_ascii() {
+        let result = truncate_middle("abcdefghijklmnopqrst", 10);
+        assert!(result.contains("..."));
+        assert!(result.chars().count() <= 10);
+    }
+
+    #[test]
+    fn truncate_middle_non_ascii_no_panic() {
+        let key = "María García <maria@example.com>";
+        let result = truncate_middle(key, 15);
+        assert!(result.contains("..."));
+    }
+
+    #[test]
+    fn truncate_middle_emoji_no_panic() {
+        let key = "🔑🔒🔐🔓🗝️🔑🔒🔐🔓🗝️";
+        let result = truncate_middle(key, 5);
+        assert!(result.contains("..."));
+    }
+}
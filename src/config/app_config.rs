@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::path::Path;
@@ -5,13 +6,44 @@ use std::path::Path;
 use crate::core::errors::{Result, VaulticError};
 
 /// Top-level Vaultic configuration read from `.vaultic/config.toml`.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 pub struct AppConfig {
+    /// Project-wide settings.
     pub vaultic: VaulticSection,
+    /// Environment definitions keyed by name (e.g. `dev`, `prod`).
     pub environments: HashMap<String, EnvEntry>,
+    /// Audit log settings.
     pub audit: Option<AuditSection>,
+    /// Per-key validation rules used by `vaultic validate`.
     #[allow(dead_code)]
     pub validation: Option<ValidationConfig>,
+    /// Key ownership: exact key or `PREFIX_*` pattern → owning team.
+    pub owners: Option<HashMap<String, String>>,
+    /// Filtering rules used by `vaultic test-env`.
+    pub test_env: Option<TestEnvSection>,
+    /// Signature requirements for `recipients.txt`.
+    pub recipients: Option<RecipientsSection>,
+    /// Custom commands: name → Vaultic arguments, e.g.
+    /// `deploy-prep = "resolve --env prod -o deploy/.env"`.
+    pub aliases: Option<HashMap<String, String>>,
+    /// Generated files checked by `vaultic check --all-outputs`, keyed by
+    /// name (e.g. `backend`, `web`).
+    pub outputs: Option<HashMap<String, OutputEntry>>,
+    /// Previous ciphertexts kept for `vaultic rollback`.
+    pub history: Option<HistorySection>,
+    /// Interface settings.
+    pub ui: Option<UiSection>,
+    /// Providers for `op://` and `vault://` values.
+    pub references: Option<ReferencesSection>,
+    /// Short-lived credentials `vaultic run` requests and injects, keyed
+    /// by name (e.g. `aws`, `db`).
+    pub leases: Option<HashMap<String, LeaseEntry>>,
+    /// How the `gpg` binary is invoked.
+    pub gpg: Option<GpgSection>,
+    /// Commands run before and after encrypt, decrypt and resolve.
+    pub hooks: Option<HooksSection>,
+    /// Rules on secret values enforced by `vaultic check --policy`.
+    pub policy: Option<PolicySection>,
 }
 
 impl AppConfig {
@@ -27,7 +59,12 @@ impl AppConfig {
             });
         }
         let content = std::fs::read_to_string(&config_path)?;
-        let config: Self = toml::from_str(&content).map_err(|e| VaulticError::InvalidConfig {
+        Self::parse(&content)
+    }
+
+    /// Parse and validate `config.toml` content (see [`AppConfig::load`]).
+    pub fn parse(content: &str) -> Result<Self> {
+        let config: Self = toml::from_str(content).map_err(|e| VaulticError::InvalidConfig {
             detail: format!("Failed to parse config.toml: {e}"),
         })?;
 
@@ -41,17 +78,82 @@ impl AppConfig {
 
         // Validate environment names from config
         for env_name in config.environments.keys() {
-            crate::cli::context::validate_env_name(env_name)?;
+            crate::config::names::validate_env_name(env_name)?;
+        }
+
+        for (env_name, entry) in &config.environments {
+            if entry.threshold.is_some_and(|t| t < 2) {
+                return Err(VaulticError::InvalidConfig {
+                    detail: format!(
+                        "environments.{env_name}.threshold must be at least 2; \
+                         remove it to let any recipient decrypt"
+                    ),
+                });
+            }
         }
 
         // Validate audit log filename
         if let Some(audit) = &config.audit {
-            crate::cli::context::validate_simple_filename(&audit.log_file, "audit log file")?;
+            crate::config::names::validate_simple_filename(&audit.log_file, "audit log file")?;
         }
 
         Ok(config)
     }
 
+    /// JSON Schema describing `config.toml`, derived from these structs.
+    pub fn json_schema() -> schemars::Schema {
+        let mut schema = schemars::schema_for!(AppConfig);
+        schema.insert("title".into(), "Vaultic config.toml".into());
+        schema
+    }
+
+    /// Names of all configured environments, sorted alphabetically.
+    pub fn environment_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.environments.keys().map(|s| s.as_str()).collect();
+        names.sort();
+        names
+    }
+
+    /// Build an `EnvironmentNotFound` error listing the configured environments.
+    pub fn environment_not_found(&self, name: &str) -> VaulticError {
+        let names = self.environment_names();
+        VaulticError::EnvironmentNotFound {
+            name: name.to_string(),
+            available: if names.is_empty() {
+                "(none defined)".to_string()
+            } else {
+                names.join(", ")
+            },
+        }
+    }
+
+    /// Return `EnvironmentNotFound` unless `name` is defined in `[environments]`.
+    pub fn require_env(&self, name: &str) -> Result<()> {
+        if self.environments.contains_key(name) {
+            Ok(())
+        } else {
+            Err(self.environment_not_found(name))
+        }
+    }
+
+    /// Where a mirrored environment's encrypted file comes from, or `None`
+    /// when the environment is kept in this repository.
+    pub fn mirror(&self, name: &str) -> Option<&str> {
+        self.environments.get(name)?.mirror.as_deref()
+    }
+
+    /// Fail when `name` is a mirror, whose secrets belong to another
+    /// repository.
+    pub fn require_writable(&self, name: &str) -> Result<()> {
+        match self.mirror(name) {
+            Some(origin) => Err(VaulticError::ReadOnlyMirror {
+                name: name.to_string(),
+                origin: origin.to_string(),
+            }),
+            None => Ok(()),
+        }
+    }
+
     /// Get the file name for a given environment, defaulting to `{name}.env`.
     pub fn env_file_name(&self, name: &str) -> String {
         self.environments
@@ -59,25 +161,93 @@ impl AppConfig {
             .and_then(|e| e.file.clone())
             .unwrap_or_else(|| format!("{name}.env"))
     }
+
+    /// How many previous ciphertexts to keep per environment for
+    /// `vaultic rollback` (0 disables versioning).
+    pub fn keep_versions(&self) -> usize {
+        self.history
+            .as_ref()
+            .map_or(DEFAULT_KEEP_VERSIONS, |h| h.keep_versions)
+    }
+
+    /// How much, in percent, `vaultic encrypt` lets an environment's
+    /// variable count drop below its last version without `--force`.
+    pub fn max_shrink_percent(&self) -> u32 {
+        self.history
+            .as_ref()
+            .map_or(DEFAULT_MAX_SHRINK_PERCENT, |h| h.max_shrink_percent)
+    }
+
+    /// How many recipients must release a share to decrypt `name`, or
+    /// `None` when any one of them can.
+    pub fn threshold(&self, name: &str) -> Option<u8> {
+        self.environments.get(name)?.threshold
+    }
+
+    /// The `[policy]` rules, or their defaults when the section is absent.
+    /// Without `max_age_days`, rotation age follows `vaultic.rotation_days`.
+    pub fn policy(&self) -> PolicySection {
+        let mut policy = self.policy.clone().unwrap_or_default();
+        policy.max_age_days = policy.max_age_days.or(self.vaultic.rotation_days);
+        policy
+    }
+
+    /// Format of an environment's secrets: the explicit `format`, or the
+    /// one implied by its file name.
+    pub fn env_format(&self, name: &str) -> SecretFormat {
+        self.environments
+            .get(name)
+            .and_then(|e| e.format)
+            .unwrap_or_else(|| SecretFormat::from_path(Path::new(&self.env_file_name(name))))
+    }
 }
 
 /// Current format version supported by this build of Vaultic.
 pub const CURRENT_FORMAT_VERSION: u32 = 1;
 
 /// The `[vaultic]` section.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 pub struct VaulticSection {
+    /// Version of Vaultic that created the project.
     pub version: String,
     /// Format version for backward compatibility. Defaults to 1 if missing.
     #[serde(default = "default_format_version")]
     pub format_version: u32,
+    /// Cipher backend: `age` or `gpg`.
     pub default_cipher: String,
-    pub default_env: String,
-    /// Global template file path (optional).
+    /// Environment used when `--env` is omitted. When unset, Vaultic
+    /// asks interactively (or fails outside a terminal).
+    pub default_env: Option<String>,
+    /// Global template file path (optional). Supports `~`, `${HOME}`
+    /// and `${VAULTIC_PROJECT_ROOT}`.
     pub template: Option<String>,
+    /// age identity file used instead of `~/.config/age/keys.txt`.
+    /// Supports `~`, `${HOME}` and `${VAULTIC_PROJECT_ROOT}`.
+    pub identity: Option<String>,
     /// Rotation policy: warn if an environment hasn't been encrypted
     /// in this many days. Default: no warning (None).
     pub rotation_days: Option<u32>,
+    /// How encrypted environments are stored. Default: `file`.
+    #[serde(default)]
+    pub storage: Storage,
+    /// Resolve environments over the same-named environment of the
+    /// nearest `.vaultic/` in a parent directory, e.g. a monorepo root
+    /// holding base secrets for every package. Default: `false`.
+    #[serde(default)]
+    pub extends_parent: bool,
+}
+
+/// How an environment is stored in `.vaultic/<env>.env.enc`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum Storage {
+    /// The whole file is one ciphertext.
+    #[default]
+    File,
+    /// Keys stay readable and each value is encrypted on its own
+    /// (`KEY=vaultic:enc:<base64>`), so git diffs show which keys changed.
+    /// Dotenv environments only.
+    PerValue,
 }
 
 fn default_format_version() -> u32 {
@@ -85,31 +255,406 @@ fn default_format_version() -> u32 {
 }
 
 /// An environment entry in `[environments]`.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 pub struct EnvEntry {
+    /// Source file name (default: `{name}.env`).
     pub file: Option<String>,
+    /// Parent environment whose values this one overrides.
     pub inherits: Option<String>,
-    /// Per-environment template file (optional).
+    /// Per-environment template file (optional), relative to `.vaultic/`.
+    /// Supports `~`, `${HOME}` and `${VAULTIC_PROJECT_ROOT}`.
     /// Used by `TemplateResolver::resolve_for_env` for per-env template checks.
     #[allow(dead_code)]
     pub template: Option<String>,
+    /// Format of the secrets file. Default: detected from the `file`
+    /// extension (`.yaml`/`.yml`, `.json`), otherwise dotenv.
+    pub format: Option<SecretFormat>,
+    /// Read the encrypted file from another repository instead of
+    /// `.vaultic/`: a path relative to the project root (such as a git
+    /// submodule checkout) or an `https://` URL. Mirrored environments
+    /// are read-only.
+    pub mirror: Option<String>,
+    /// Show the changes and ask before `vaultic encrypt` overwrites the
+    /// environment, as `--review` does.
+    #[serde(default)]
+    pub review: bool,
+    /// Encrypt with Shamir shares so that this many recipients must each
+    /// release their share to decrypt (e.g. `2` for 2-of-3). Age only.
+    pub threshold: Option<u8>,
+}
+
+/// A generated secrets file in `[outputs]`, e.g.
+/// `web = { path = "apps/web/.env.local" }`.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct OutputEntry {
+    /// Location of the file, relative to the project root.
+    /// Supports `~`, `${HOME}` and `${VAULTIC_PROJECT_ROOT}`.
+    pub path: String,
+    /// Template the file is checked against, relative to the project root.
+    /// Default: auto-discovered next to `path` (`.env.template`,
+    /// `.env.example`, ...).
+    pub template: Option<String>,
+}
+
+/// Seconds an `aws-sts` lease lasts when `duration_seconds` is not set.
+pub const DEFAULT_LEASE_SECONDS: u64 = 3600;
+
+/// A source of short-lived credentials in `[leases]`, e.g.
+/// `aws = { provider = "aws-sts", role_arn = "arn:aws:iam::123:role/deploy" }`.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct LeaseEntry {
+    /// Where the credentials come from.
+    #[serde(flatten)]
+    pub provider: LeaseProvider,
+    /// Environments the lease is requested for. Default: all.
+    pub environments: Option<Vec<String>>,
+    /// Signal sent to the child after the credentials are refreshed
+    /// (e.g. `HUP`), for programs that reload on a signal. Unix only.
+    pub refresh_signal: Option<String>,
+}
+
+/// Provider of a `[leases]` entry, chosen by its `provider` key.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(tag = "provider", rename_all = "kebab-case")]
+pub enum LeaseProvider {
+    /// Temporary AWS credentials from `aws sts assume-role`, injected as
+    /// `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and `AWS_SESSION_TOKEN`.
+    AwsSts {
+        /// Role to assume.
+        role_arn: String,
+        /// Lifetime requested from STS.
+        #[serde(default = "default_lease_seconds")]
+        duration_seconds: u64,
+        /// AWS region for the STS call. Default: the CLI's.
+        region: Option<String>,
+    },
+    /// Any command printing `KEY=value` lines or a JSON object of
+    /// variables. A JSON `expires_at` (RFC 3339) sets the expiry.
+    Command {
+        /// Program and arguments, e.g. `["vault", "read", "-format=json", "db/creds/app"]`.
+        command: Vec<String>,
+        /// Lifetime of the credentials when the output has no `expires_at`.
+        /// Without either, they are never refreshed.
+        ttl_seconds: Option<u64>,
+    },
+}
+
+fn default_lease_seconds() -> u64 {
+    DEFAULT_LEASE_SECONDS
+}
+
+impl LeaseEntry {
+    /// Whether `vaultic run --env <env_name>` requests this lease.
+    pub fn applies_to(&self, env_name: &str) -> bool {
+        self.environments
+            .as_ref()
+            .is_none_or(|envs| envs.iter().any(|e| e == env_name))
+    }
+}
+
+/// File format of a secrets file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SecretFormat {
+    /// `KEY=value` lines.
+    #[default]
+    Dotenv,
+    /// YAML mapping; nested keys are flattened (`db.host` → `DB_HOST`).
+    Yaml,
+    /// JSON object; nested keys are flattened (`db.host` → `DB_HOST`).
+    Json,
+}
+
+impl SecretFormat {
+    /// Format implied by a file name's extension. Anything that is not
+    /// `.yaml`, `.yml` or `.json` is treated as dotenv.
+    pub fn from_path(path: &Path) -> Self {
+        match path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase)
+            .as_deref()
+        {
+            Some("yaml" | "yml") => SecretFormat::Yaml,
+            Some("json") => SecretFormat::Json,
+            _ => SecretFormat::Dotenv,
+        }
+    }
+
+    /// Display name, e.g. `YAML`.
+    pub fn label(&self) -> &'static str {
+        match self {
+            SecretFormat::Dotenv => "dotenv",
+            SecretFormat::Yaml => "YAML",
+            SecretFormat::Json => "JSON",
+        }
+    }
+}
+
+/// The `[test_env]` section.
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+pub struct TestEnvSection {
+    /// Keys to keep (exact or `PREFIX_*`). Empty keeps every key.
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Keys to drop (exact or `PREFIX_*`), applied after `include`.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Prefix prepended to every key, unless `--prefix` is given.
+    pub prefix: Option<String>,
+}
+
+/// The `[recipients]` section.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct RecipientsSection {
+    /// Minisign public keys allowed to sign `recipients.txt`. When set,
+    /// encrypt and decrypt verify `recipients.txt.sig` first.
+    #[serde(default)]
+    pub admin_keys: Vec<String>,
+    /// Refuse to run on a missing or invalid signature (`false` only warns).
+    #[serde(default = "default_true")]
+    pub enforce: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Versions kept per environment when `[history]` does not say.
+pub const DEFAULT_KEEP_VERSIONS: usize = 20;
+
+/// Shrink `vaultic encrypt` allows when `[history]` does not say.
+pub const DEFAULT_MAX_SHRINK_PERCENT: u32 = 50;
+
+/// The `[history]` section.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct HistorySection {
+    /// Ciphertexts kept per environment under `.vaultic/history/<env>/`;
+    /// older ones are deleted on the next encrypt. 0 disables versioning.
+    #[serde(default = "default_keep_versions")]
+    pub keep_versions: usize,
+    /// `vaultic encrypt` refuses, without `--force`, a file with this
+    /// percentage fewer variables than the last kept version. 100 turns
+    /// the check off.
+    #[serde(default = "default_max_shrink_percent")]
+    pub max_shrink_percent: u32,
+}
+
+fn default_keep_versions() -> usize {
+    DEFAULT_KEEP_VERSIONS
+}
+
+fn default_max_shrink_percent() -> u32 {
+    DEFAULT_MAX_SHRINK_PERCENT
+}
+
+/// Seconds a `[hooks]` command may run when `timeout_seconds` is not set.
+pub const DEFAULT_HOOK_TIMEOUT_SECONDS: u64 = 30;
+
+/// The `[hooks]` section: commands run around Vaultic commands, e.g.
+/// `post_resolve = "./scripts/restart-dev.sh"`.
+///
+/// Each value is a program and its arguments, split like a shell would
+/// without running one. Hooks get no secrets: only a few variables such
+/// as `PATH` and `HOME` pass through, plus `VAULTIC_HOOK`, `VAULTIC_ENV`
+/// and `VAULTIC_FILE`.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct HooksSection {
+    /// Run before `vaultic encrypt`; a non-zero exit aborts it.
+    pub pre_encrypt: Option<String>,
+    /// Run after `vaultic encrypt` wrote an environment.
+    pub post_encrypt: Option<String>,
+    /// Run after `vaultic decrypt` wrote a plaintext file.
+    pub post_decrypt: Option<String>,
+    /// Run after `vaultic resolve` wrote its output file.
+    pub post_resolve: Option<String>,
+    /// Seconds a hook may run before it is stopped.
+    #[serde(default = "default_hook_timeout")]
+    pub timeout_seconds: u64,
+}
+
+fn default_hook_timeout() -> u64 {
+    DEFAULT_HOOK_TIMEOUT_SECONDS
+}
+
+/// The `[policy]` section: rules `vaultic check --policy` enforces on
+/// the secret values themselves.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct PolicySection {
+    /// Key patterns holding secrets, e.g. `*_SECRET`. Only these are
+    /// held to `min_length`.
+    #[serde(default = "default_policy_secret_keys")]
+    pub secret_keys: Vec<String>,
+    /// Shortest value accepted for a `secret_keys` variable.
+    #[serde(default = "default_policy_min_length")]
+    pub min_length: usize,
+    /// Environments where placeholder values such as `password` or
+    /// `changeme` are refused.
+    #[serde(default = "default_policy_strict_envs")]
+    pub strict_envs: Vec<String>,
+    /// Values refused in `strict_envs`, on top of the built-in list.
+    #[serde(default)]
+    pub weak_values: Vec<String>,
+    /// Days an environment may go without being re-encrypted, according
+    /// to the audit log.
+    pub max_age_days: Option<u32>,
+}
+
+impl Default for PolicySection {
+    fn default() -> Self {
+        Self {
+            secret_keys: default_policy_secret_keys(),
+            min_length: default_policy_min_length(),
+            strict_envs: default_policy_strict_envs(),
+            weak_values: Vec::new(),
+            max_age_days: None,
+        }
+    }
+}
+
+fn default_policy_secret_keys() -> Vec<String> {
+    vec!["*_SECRET".into(), "*_KEY".into()]
+}
+
+fn default_policy_min_length() -> usize {
+    16
+}
+
+fn default_policy_strict_envs() -> Vec<String> {
+    vec!["prod".into()]
+}
+
+/// The `[ui]` section.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct UiSection {
+    /// Language of Vaultic's messages. `VAULTIC_LANG` overrides it.
+    #[serde(default)]
+    pub language: Language,
+    /// Refuse `--show-values`, so secret values never reach terminal
+    /// scrollback or CI logs.
+    #[serde(default)]
+    pub redact_values: bool,
+}
+
+/// Languages Vaultic's messages are available in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    En,
+    Es,
+}
+
+impl Language {
+    /// Parse a language code such as `es`, `es-ES` or a locale like
+    /// `es_ES.UTF-8`.
+    pub fn from_code(code: &str) -> Option<Self> {
+        let primary = code
+            .split(['-', '_', '.'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match primary.as_str() {
+            "en" => Some(Language::En),
+            "es" => Some(Language::Es),
+            _ => None,
+        }
+    }
+
+    /// BCP 47 code, e.g. `es`.
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::En => "en",
+            Language::Es => "es",
+        }
+    }
+}
+
+/// The `[gpg]` section.
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+pub struct GpgSection {
+    /// Keyring directory passed as `--homedir`, instead of `~/.gnupg`.
+    /// Supports `~`, `${HOME}` and `${VAULTIC_PROJECT_ROOT}`.
+    /// `VAULTIC_GPG_HOMEDIR` overrides it.
+    pub homedir: Option<String>,
+    /// Invocation profile. `VAULTIC_GPG_PROFILE` overrides it.
+    #[serde(default)]
+    pub profile: GpgProfile,
+}
+
+/// How much `gpg` may interact with the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum GpgProfile {
+    /// Batch mode; gpg-agent may still ask for a passphrase through
+    /// pinentry.
+    #[default]
+    Interactive,
+    /// Never prompt: no tty and loopback pinentry, so a key that needs a
+    /// passphrase fails at once instead of hanging a CI job.
+    Ci,
+}
+
+impl GpgProfile {
+    /// Parse `interactive` or `ci`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "interactive" => Some(Self::Interactive),
+            "ci" => Some(Self::Ci),
+            _ => None,
+        }
+    }
+}
+
+/// The `[references]` section.
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+pub struct ReferencesSection {
+    /// Fetch referenced values in `resolve` and `run` without
+    /// `--materialize` (`--keep-refs` overrides it).
+    #[serde(default)]
+    pub materialize: bool,
+    /// HashiCorp Vault, for `vault://<path>#<field>` values.
+    pub vault: Option<VaultProviderConfig>,
+    /// 1Password, for `op://<vault>/<item>/<field>` values.
+    pub op: Option<OnePasswordProviderConfig>,
+}
+
+/// `[references.vault]`: read through the `vault` CLI.
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+pub struct VaultProviderConfig {
+    /// Server address; defaults to the CLI's `VAULT_ADDR`.
+    pub address: Option<String>,
+    /// Enterprise namespace; defaults to the CLI's `VAULT_NAMESPACE`.
+    pub namespace: Option<String>,
+}
+
+/// `[references.op]`: read through the 1Password `op` CLI.
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+pub struct OnePasswordProviderConfig {
+    /// Account to use when the CLI is signed in to several.
+    pub account: Option<String>,
 }
 
 /// The `[audit]` section.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 pub struct AuditSection {
+    /// Whether operations are recorded.
     pub enabled: bool,
+    /// Log file name inside `.vaultic/`.
     pub log_file: String,
 }
 
 /// Validation rules for a single secret key.
 /// All fields are optional — only specified constraints are checked.
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Deserialize, Default, JsonSchema)]
 #[allow(dead_code)]
 pub struct ValidationRule {
-    /// Expected type: "url", "integer", "boolean", "string"
+    /// Expected type: "url", "integer" (or "int"), "boolean" (or "bool"),
+    /// "enum" or "string"
     #[serde(rename = "type")]
     pub value_type: Option<String>,
+    /// Allowed values (for type = "enum")
+    pub values: Option<Vec<String>>,
     /// Minimum numeric value (for type = "integer")
     pub min: Option<i64>,
     /// Maximum numeric value (for type = "integer")
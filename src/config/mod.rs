@@ -1 +1,5 @@
 pub mod app_config;
+pub mod names;
+pub mod path_expand;
+pub mod user_config;
+pub mod value_schema;
@@ -0,0 +1,228 @@
+use std::path::{Component, Path, PathBuf};
+
+use crate::core::errors::{Result, VaulticError};
+
+/// Variables that may appear as `${NAME}` in config paths.
+const SUPPORTED_VARS: &[&str] = &["HOME", "VAULTIC_PROJECT_ROOT"];
+
+/// Expand `~`, `${HOME}` and `${VAULTIC_PROJECT_ROOT}` in a path from
+/// `config.toml`, join relative results onto `base`, and check that the
+/// result stays inside the project root, `base` or the home directory.
+///
+/// `VAULTIC_PROJECT_ROOT` comes from the environment when set, otherwise
+/// it is the current directory. The check applies to absolute and
+/// relative paths alike, after `..` and symlinks are resolved, so a
+/// shared config cannot point at `/etc` or at a sibling checkout while
+/// `../templates` next to `.vaultic/` stays valid.
+pub fn expand_path(raw: &str, base: &Path) -> Result<PathBuf> {
+    let path = expand(raw, base, "config path")?;
+    let resolved = resolve(&path);
+    let allowed = [project_root(), Some(base.to_path_buf()), dirs::home_dir()]
+        .into_iter()
+        .flatten()
+        .any(|root| resolved.starts_with(resolve(&root)));
+    if !allowed {
+        return Err(VaulticError::InvalidConfig {
+            detail: format!(
+                "Path '{raw}' in config.toml resolves to {}, outside the project and your \
+                 home directory\n\n  \
+                 Keep shared files under the project root (${{VAULTIC_PROJECT_ROOT}}), or \
+                 per-user files under ${{HOME}}.",
+                resolved.display()
+            ),
+        });
+    }
+    Ok(path)
+}
+
+/// Expand `~`, `${HOME}` and `${VAULTIC_PROJECT_ROOT}` in a path typed on
+/// the command line, set in the environment or read from the user config,
+/// and join relative results onto `base`. These come from the user
+/// themselves, so they may point anywhere.
+pub fn expand_input(raw: &str, base: &Path) -> Result<PathBuf> {
+    expand(raw, base, "path")
+}
+
+fn expand(raw: &str, base: &Path, what: &str) -> Result<PathBuf> {
+    let expanded = expand_vars(raw, what, |name| match name {
+        "HOME" => dirs::home_dir(),
+        "VAULTIC_PROJECT_ROOT" => project_root(),
+        _ => None,
+    })?;
+    let path = PathBuf::from(expanded);
+    Ok(if path.is_absolute() {
+        path
+    } else {
+        base.join(path)
+    })
+}
+
+/// `path` made absolute with `..` and `.` removed, and symlinks resolved
+/// in the part of it that exists.
+fn resolve(path: &Path) -> PathBuf {
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().unwrap_or_default().join(path)
+    };
+    let mut normal = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            Component::ParentDir => {
+                normal.pop();
+            }
+            Component::CurDir => {}
+            other => normal.push(other),
+        }
+    }
+
+    let mut existing = normal.as_path();
+    let mut missing = Vec::new();
+    while !existing.exists() {
+        let Some(parent) = existing.parent() else {
+            return normal;
+        };
+        missing.extend(existing.file_name());
+        existing = parent;
+    }
+    let mut resolved = existing
+        .canonicalize()
+        .unwrap_or_else(|_| existing.to_path_buf());
+    resolved.extend(missing.iter().rev());
+    resolved
+}
+
+/// The project root used for `${VAULTIC_PROJECT_ROOT}`.
+fn project_root() -> Option<PathBuf> {
+    std::env::var_os("VAULTIC_PROJECT_ROOT")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::current_dir().ok())
+}
+
+/// Substitute a leading `~` and every `${NAME}` using `lookup`.
+fn expand_vars(raw: &str, what: &str, lookup: impl Fn(&str) -> Option<PathBuf>) -> Result<String> {
+    let resolve = |name: &str| -> Result<String> {
+        if !SUPPORTED_VARS.contains(&name) {
+            return Err(VaulticError::InvalidConfig {
+                detail: format!(
+                    "Unsupported variable '${{{name}}}' in {what} '{raw}'\n\n  \
+                     Supported: ~, {}",
+                    SUPPORTED_VARS
+                        .iter()
+                        .map(|v| format!("${{{v}}}"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            });
+        }
+        lookup(name)
+            .map(|p| p.to_string_lossy().into_owned())
+            .ok_or_else(|| VaulticError::InvalidConfig {
+                detail: format!("Cannot expand '${{{name}}}' in {what} '{raw}'"),
+            })
+    };
+
+    let mut rest = raw;
+    let mut out = String::new();
+
+    if rest == "~" || rest.starts_with("~/") || rest.starts_with("~\\") {
+        out.push_str(&resolve("HOME")?);
+        rest = &rest[1..];
+    }
+
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after.find('}').ok_or_else(|| VaulticError::InvalidConfig {
+            detail: format!("Unclosed '${{' in {what} '{raw}'"),
+        })?;
+        out.push_str(&resolve(&after[..end])?);
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(name: &str) -> Option<PathBuf> {
+        match name {
+            "HOME" => Some(PathBuf::from("/home/dev")),
+            "VAULTIC_PROJECT_ROOT" => Some(PathBuf::from("/work/app")),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn expands_tilde_and_variables() {
+        assert_eq!(
+            expand_vars("~/.config/age/keys.txt", "config path", lookup).unwrap(),
+            "/home/dev/.config/age/keys.txt"
+        );
+        assert_eq!(
+            expand_vars(
+                "${VAULTIC_PROJECT_ROOT}/templates/.env.template",
+                "config path",
+                lookup
+            )
+            .unwrap(),
+            "/work/app/templates/.env.template"
+        );
+        assert_eq!(
+            expand_vars("${HOME}", "config path", lookup).unwrap(),
+            "/home/dev"
+        );
+    }
+
+    #[test]
+    fn leaves_plain_paths_alone() {
+        assert_eq!(
+            expand_vars(".env.template", "config path", lookup).unwrap(),
+            ".env.template"
+        );
+        assert_eq!(expand_vars("a~b", "config path", lookup).unwrap(), "a~b");
+    }
+
+    #[test]
+    fn rejects_unknown_and_unclosed_variables() {
+        let err = expand_vars("${AWS_SECRET}/x", "config path", lookup)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Unsupported variable '${AWS_SECRET}'"));
+        assert!(expand_vars("${HOME/x", "config path", lookup).is_err());
+    }
+
+    #[test]
+    fn relative_paths_join_base() {
+        let path = expand_path("templates/.env.template", Path::new("/repo")).unwrap();
+        assert_eq!(path, PathBuf::from("/repo/templates/.env.template"));
+    }
+
+    #[test]
+    fn confines_config_paths_to_project_and_home() {
+        let err = expand_path("/etc/vaultic/.env.template", Path::new("."))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("outside the project and your home directory"));
+        assert!(expand_path("../../../../../../../etc/passwd", Path::new(".")).is_err());
+
+        // `..` that stays inside the project is fine, e.g. next to .vaultic/
+        let path = expand_path("../templates/prod.env.template", Path::new(".vaultic")).unwrap();
+        assert_eq!(path, Path::new(".vaultic/../templates/prod.env.template"));
+        assert!(expand_path("~/shared/app.template", Path::new(".")).is_ok());
+    }
+
+    #[test]
+    fn user_input_may_point_anywhere() {
+        let path = expand_input("/run/secrets/age.key", Path::new(".")).unwrap();
+        assert_eq!(path, PathBuf::from("/run/secrets/age.key"));
+        let err = expand_input("${NOPE}/x", Path::new("."))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("in path '${NOPE}/x'"));
+    }
+}
@@ -0,0 +1,192 @@
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+use crate::core::errors::{Result, VaulticError};
+
+/// Per-user settings read from `~/.config/vaultic/config.toml`, shared
+/// by every project on the machine.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UserConfig {
+    /// age identity files tried when decrypting, in order. A single path
+    /// or a list; supports `~` and `${HOME}`.
+    #[serde(default)]
+    identity: Identities,
+    /// Directory whose files are all tried as identities, e.g. one key
+    /// per project in `~/.config/age/keys.d`.
+    pub identity_dir: Option<String>,
+    /// Cipher for commands run without `--cipher` outside a project, and
+    /// written by `vaultic init`. A project's `default_cipher` wins.
+    pub default_cipher: Option<String>,
+    /// When to color output.
+    #[serde(default)]
+    pub color: ColorChoice,
+    /// Set to `false` to skip the passive new-version notice.
+    #[serde(default = "default_true")]
+    pub update_check: bool,
+    /// Editor for `vaultic edit`, e.g. `code --wait`. Takes precedence
+    /// over `VISUAL` and `EDITOR`.
+    pub editor: Option<String>,
+}
+
+impl Default for UserConfig {
+    fn default() -> Self {
+        Self {
+            identity: Identities::default(),
+            identity_dir: None,
+            default_cipher: None,
+            color: ColorChoice::default(),
+            update_check: true,
+            editor: None,
+        }
+    }
+}
+
+/// `color = "auto" | "always" | "never"`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice {
+    /// Color on a terminal, unless `NO_COLOR` is set.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// `identity = "..."` or `identity = ["...", "..."]`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum Identities {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Default for Identities {
+    fn default() -> Self {
+        Self::Many(Vec::new())
+    }
+}
+
+impl UserConfig {
+    /// Load the user config, or the defaults when there is none.
+    pub fn load() -> Result<Self> {
+        match Self::path() {
+            Some(path) if path.exists() => {
+                Self::parse(&std::fs::read_to_string(&path)?).map_err(|e| {
+                    VaulticError::InvalidConfig {
+                        detail: format!("{}: {e}", path.display()),
+                    }
+                })
+            }
+            _ => Ok(Self::default()),
+        }
+    }
+
+    /// Parse user config content.
+    pub fn parse(content: &str) -> std::result::Result<Self, toml::de::Error> {
+        toml::from_str(content)
+    }
+
+    /// `~/.config/vaultic/config.toml`, or `%APPDATA%\vaultic\config.toml`.
+    pub fn path() -> Option<PathBuf> {
+        Some(dirs::config_dir()?.join("vaultic").join("config.toml"))
+    }
+
+    /// Write `identity = "<raw>"` at the top of the user config file,
+    /// creating it when needed. Returns `false`, leaving the file alone,
+    /// when it already sets `identity`.
+    pub fn write_identity(raw: &str) -> Result<bool> {
+        let path = Self::path().ok_or_else(|| VaulticError::InvalidConfig {
+            detail: "Could not determine the config directory".into(),
+        })?;
+        let content = std::fs::read_to_string(&path).unwrap_or_default();
+        let current: toml::Table =
+            toml::from_str(&content).map_err(|e| VaulticError::InvalidConfig {
+                detail: format!("{}: {e}", path.display()),
+            })?;
+        if current.contains_key("identity") {
+            return Ok(false);
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let line = format!("identity = {}\n", toml::Value::String(raw.to_string()));
+        std::fs::write(&path, line + &content)?;
+        Ok(true)
+    }
+
+    /// The `identity` entries, as written.
+    pub fn identities(&self) -> &[String] {
+        match &self.identity {
+            Identities::One(path) => std::slice::from_ref(path),
+            Identities::Many(paths) => paths,
+        }
+    }
+
+    /// Files in `identity_dir`, sorted by name. Hidden files and `.pub`
+    /// public keys are skipped.
+    pub fn identity_dir_files(&self, base: &Path) -> Result<Vec<PathBuf>> {
+        let Some(raw) = &self.identity_dir else {
+            return Ok(Vec::new());
+        };
+        let dir = crate::config::path_expand::expand_input(raw, base)?;
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            return Ok(Vec::new());
+        };
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_file())
+            .filter(|p| {
+                let name = p.file_name().unwrap_or_default().to_string_lossy();
+                !name.starts_with('.') && !name.ends_with(".pub")
+            })
+            .collect();
+        files.sort();
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identity_accepts_one_path_or_a_list() {
+        let one = UserConfig::parse("identity = \"~/keys/a.txt\"").unwrap();
+        assert_eq!(one.identities(), ["~/keys/a.txt"]);
+
+        let many = UserConfig::parse(
+            "identity = [\"~/keys/a.txt\", \"~/keys/b.txt\"]\nidentity_dir = \"~/keys.d\"",
+        )
+        .unwrap();
+        assert_eq!(many.identities(), ["~/keys/a.txt", "~/keys/b.txt"]);
+        assert_eq!(many.identity_dir.as_deref(), Some("~/keys.d"));
+
+        assert!(UserConfig::parse("").unwrap().identities().is_empty());
+        assert!(UserConfig::parse("identiy = \"typo\"").is_err());
+    }
+
+    #[test]
+    fn preferences_default_when_missing() {
+        let empty = UserConfig::parse("").unwrap();
+        assert_eq!(empty.default_cipher, None);
+        assert_eq!(empty.color, ColorChoice::Auto);
+        assert!(empty.update_check);
+        assert_eq!(empty.editor, None);
+
+        let set = UserConfig::parse(
+            "default_cipher = \"gpg\"\ncolor = \"never\"\nupdate_check = false\neditor = \"code --wait\"",
+        )
+        .unwrap();
+        assert_eq!(set.default_cipher.as_deref(), Some("gpg"));
+        assert_eq!(set.color, ColorChoice::Never);
+        assert!(!set.update_check);
+        assert_eq!(set.editor.as_deref(), Some("code --wait"));
+        assert!(UserConfig::parse("color = \"sometimes\"").is_err());
+    }
+}
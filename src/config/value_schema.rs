@@ -0,0 +1,78 @@
+use std::path::Path;
+
+use crate::config::app_config::ValidationConfig;
+use crate::core::errors::{Result, VaulticError};
+
+/// Name of the schema inside `.vaultic/`.
+pub const SCHEMA_FILE: &str = "schema.toml";
+
+/// Types a schema rule may declare.
+const TYPES: &[&str] = &["url", "integer", "int", "boolean", "bool", "enum", "string"];
+
+/// Load the per-key constraints of `.vaultic/schema.toml`, or `None` for
+/// projects that have none.
+///
+/// The file uses the rule format of `[validation]` in `config.toml`, one
+/// table per key:
+///
+/// ```toml
+/// DATABASE_URL = { type = "url", required = true }
+/// PORT = { type = "int", min = 1024, max = 65535 }
+/// LOG_LEVEL = { type = "enum", values = ["info", "debug"] }
+/// ```
+pub fn load(vaultic_dir: &Path) -> Result<Option<ValidationConfig>> {
+    let path = vaultic_dir.join(SCHEMA_FILE);
+    if !path.exists() {
+        return Ok(None);
+    }
+    parse(&std::fs::read_to_string(&path)?).map(Some)
+}
+
+/// Parse schema content. Unlike `[validation]`, unknown types and enums
+/// without values are rejected, so a typo cannot silently pass every value.
+pub fn parse(content: &str) -> Result<ValidationConfig> {
+    let invalid = |detail: String| VaulticError::InvalidConfig {
+        detail: format!("{SCHEMA_FILE}: {detail}"),
+    };
+    let schema: ValidationConfig = toml::from_str(content).map_err(|e| invalid(e.to_string()))?;
+    for (key, rule) in &schema {
+        match rule.value_type.as_deref() {
+            Some(t) if !TYPES.contains(&t) => {
+                return Err(invalid(format!(
+                    "{key} has unknown type '{t}' (expected url, int, bool, enum or string)"
+                )));
+            }
+            Some("enum") if rule.values.as_ref().is_none_or(Vec::is_empty) => {
+                return Err(invalid(format!(
+                    "{key} is an enum but lists no values, e.g. values = [\"a\", \"b\"]"
+                )));
+            }
+            _ => {}
+        }
+    }
+    Ok(schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_inline_and_table_rules() {
+        let schema = parse(
+            "PORT = { type = \"int\", min = 1 }\n\n\
+             [DATABASE_URL]\ntype = \"url\"\nrequired = true\n",
+        )
+        .unwrap();
+        assert_eq!(schema["PORT"].min, Some(1));
+        assert!(schema["DATABASE_URL"].required);
+    }
+
+    #[test]
+    fn rejects_unknown_types_and_empty_enums() {
+        let err = parse("PORT = { type = \"interger\" }").unwrap_err();
+        assert!(err.to_string().contains("unknown type 'interger'"));
+        let err = parse("MODE = { type = \"enum\" }").unwrap_err();
+        assert!(err.to_string().contains("lists no values"));
+    }
+}
@@ -26,6 +26,28 @@ pub enum VaulticError {
     )]
     DecryptionNoKey,
 
+    #[error(
+        "Encrypted file is corrupted at line {line}: {reason}\n\n  \
+         Solutions:\n    \
+         → Undo line-ending and whitespace changes: vaultic repair-armor\n    \
+         → Or restore the file from git: git checkout -- .vaultic/"
+    )]
+    ArmorCorrupted { line: usize, reason: String },
+
+    #[error(
+        "Decryption needs {need} shares of {env}, but only {have} are available\n\n  \
+         {env} is encrypted with a threshold: no single recipient can decrypt it.\n\n  \
+         Solutions:\n    \
+         → Ask other shareholders to run: vaultic keys share release --env {env} --to <your-key>\n    \
+         → Then decrypt with their files: vaultic decrypt --env {env} --share <file>\n    \
+         → See who holds shares: vaultic keys share status"
+    )]
+    SharesRequired {
+        env: String,
+        have: usize,
+        need: usize,
+    },
+
     #[error(
         "Parse error in {file}: {detail}\n\n  \
          Expected format: KEY=value (one per line).\n  \
@@ -49,6 +71,17 @@ pub enum VaulticError {
     )]
     CircularInheritance { chain: String },
 
+    #[error(
+        "Recipients signature check failed: {reason}\n\n  \
+         [recipients] admin_keys in .vaultic/config.toml requires recipients.txt (and any \
+         recipients.<env>.txt) to be signed by an admin, so unreviewed recipient changes \
+         cannot take effect.\n\n  \
+         Solutions:\n    \
+         → Review recent changes: git log -p .vaultic/recipients.txt\n    \
+         → Ask an admin to sign it: minisign -Sm .vaultic/recipients.txt -x .vaultic/recipients.txt.sig"
+    )]
+    RecipientsSignatureInvalid { reason: String },
+
     #[error("Key '{identity}' not found in recipients")]
     KeyNotFound { identity: String },
 
@@ -91,6 +124,15 @@ pub enum VaulticError {
     )]
     UpdateFailed { reason: String },
 
+    #[error(
+        "Binary verification failed: {reason}\n\n  \
+         Solutions:\n    \
+         → Reinstall the release binary: vaultic update, or download it from\n      \
+         https://github.com/SoftDryzz/vaultic/releases\n    \
+         → Builds from source (cargo install) never match the release hash"
+    )]
+    BinaryIntegrityFailed { reason: String },
+
     #[error(
         "Unsupported platform for auto-update: {platform}\n\n  \
          Pre-built binaries are not available for your platform.\n\n  \
@@ -127,6 +169,76 @@ pub enum VaulticError {
     #[error("Validation failed: {count} rule(s) violated")]
     ValidationFailed { count: usize },
 
+    #[error(
+        "Found {count} secret value(s) in plaintext files\n\n  \
+         Solutions:\n    \
+         → Remove the values from the files listed above\n    \
+         → Rotate every leaked secret: once committed, it stays in git history"
+    )]
+    SecretLeaksFound { count: usize },
+
+    #[error(
+        "Found {count} secret value(s) breaking the [policy] rules\n\n  \
+         Solutions:\n    \
+         → Replace short or placeholder values and re-encrypt\n    \
+         → Rotate environments past max_age_days: vaultic encrypt --env <env>"
+    )]
+    PolicyViolations { count: usize },
+
+    #[error("Found {count} problem(s) in the project status")]
+    StatusProblemsFound { count: usize },
+
+    #[error(
+        "{count} environment(s) are not readable by exactly their recipients\n\n  \
+         Solutions:\n    \
+         → Re-encrypt for the current recipients: vaultic encrypt --all\n    \
+         → Check the recipient list: vaultic keys list"
+    )]
+    VerifyFailed { count: usize },
+
+    #[error(
+        "{count} file(s) in .vaultic/ were changed outside Vaultic\n\n  \
+         Solutions:\n    \
+         → Review the change: git diff .vaultic/\n    \
+         → Undo it: git checkout -- .vaultic/\n    \
+         → Keep it once reviewed: vaultic verify --accept-changes"
+    )]
+    UnrecordedChanges { count: usize },
+
+    #[error(
+        "{env} was changed outside Vaultic {count} time(s)\n\n  \
+         Its encrypted file does not follow the states Vaultic recorded in \
+         .vaultic/manifest.toml.\n\n  \
+         Solutions:\n    \
+         → Review the change: git log -p .vaultic/\n    \
+         → Undo it: git checkout -- .vaultic/\n    \
+         → Keep it once reviewed: vaultic verify --accept-changes"
+    )]
+    StateChainBroken { env: String, count: usize },
+
+    #[error("{count} environment(s) do not match their template")]
+    TemplateCheckFailed { count: usize },
+
+    #[error(
+        "Could not resolve {key} = {reference}: {reason}\n\n  \
+         Solutions:\n    \
+         → Check the provider under [references] in .vaultic/config.toml\n    \
+         → Make sure its CLI is installed and signed in\n    \
+         → Or leave references unresolved: --keep-refs"
+    )]
+    ReferenceResolutionFailed {
+        key: String,
+        reference: String,
+        reason: String,
+    },
+
+    #[error(
+        "Environment '{name}' is a read-only mirror of {origin}\n\n  \
+         Change its secrets in the repository that owns them, then update the mirror\n  \
+         (e.g. git submodule update --remote)."
+    )]
+    ReadOnlyMirror { name: String, origin: String },
+
     #[error(
         "Invalid regex pattern '{pattern}' for key '{key}': {reason}\n\n  \
          Fix the pattern in .vaultic/config.toml under [validation]."
@@ -147,6 +259,24 @@ pub enum VaulticError {
     )]
     CiExportFailed { format: String },
 
+    #[error(
+        "GPG agent unavailable: {detail}\n\n  \
+         gpg needs gpg-agent to use your secret keys and could not reach it.\n\n  \
+         Solutions:\n    \
+         → Start it: gpgconf --launch gpg-agent\n    \
+         → With a custom homedir, keep its path short: the agent socket lives in it\n    \
+         → In containers, install gpg-agent (e.g. the gnupg2 package)"
+    )]
+    GpgAgentUnavailable { detail: String },
+
+    #[error(
+        "Clipboard unavailable: {detail}\n\n  \
+         Solutions:\n    \
+         → Run Vaultic inside a desktop session (X11, or Wayland with XWayland)\n    \
+         → Over SSH or in CI, drop --copy and use the printed output"
+    )]
+    ClipboardUnavailable { detail: String },
+
     #[error(transparent)]
     Io(#[from] std::io::Error),
 }
@@ -7,16 +7,40 @@ pub enum AuditAction {
     Init,
     Encrypt,
     Decrypt,
+    /// Repeated decrypts with a key that is not a recipient.
+    DecryptFailed,
     KeyAdd,
     KeyRemove,
+    KeyRotate,
     Check,
     Diff,
     Resolve,
     HookInstall,
     HookUninstall,
+    HookBypass,
     TemplateSync,
     Validate,
     CiExport,
+    ArchiveCreate,
+    ArchiveRestore,
+    /// `vaultic bundle` encrypted an environment to keys outside the
+    /// recipients list.
+    BundleCreate,
+    Run,
+    Prune,
+    Move,
+    Sync,
+    SnapshotCreate,
+    SnapshotRestore,
+    Promote,
+    /// `vaultic verify --accept-changes` recorded edits made outside
+    /// Vaultic in the manifest.
+    ManifestAccept,
+    /// `vaultic keys share release` handed a threshold share to another
+    /// shareholder.
+    ShareRelease,
+    /// `vaultic repair-armor` rewrote a damaged encrypted file.
+    ArmorRepair,
 }
 
 /// A single entry in the audit log (JSON lines format).
@@ -27,6 +51,367 @@ pub struct AuditEntry {
     pub email: Option<String>,
     pub action: AuditAction,
     pub files: Vec<String>,
-    pub detail: Option<String>,
+    pub detail: Option<AuditDetail>,
     pub state_hash: Option<String>,
 }
+
+/// What an audit entry recorded, with fields per action so tools reading
+/// `audit.log` need not parse prose. Displays as the one-line summary
+/// `vaultic log` shows.
+///
+/// Entries written before details were structured hold a plain string,
+/// read back as [`AuditDetail::Text`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AuditDetail {
+    Init,
+    Encrypt {
+        cipher: String,
+        recipients: usize,
+        /// `--note`, so reviewers can see why the change was made.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        note: Option<String>,
+    },
+    Decrypt {
+        variables: usize,
+        dest: String,
+    },
+    DecryptFailed {
+        attempts: u32,
+        /// Public key of the identity that failed, when it was the default.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        key: Option<String>,
+        since: chrono::DateTime<chrono::Utc>,
+    },
+    /// `vaultic keys add`. The label is the member's name when one was
+    /// given.
+    KeyAdd {
+        key: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        label: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        email: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        role: Option<String>,
+    },
+    KeyRemove {
+        key: String,
+        /// Name of the member who held the key, from `members.toml`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+    KeyRotate {
+        from: String,
+        to: String,
+    },
+    /// `vaultic resolve`.
+    Resolve {
+        variables: usize,
+        layers: usize,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        filter: Option<String>,
+    },
+    /// `vaultic get`.
+    Get {
+        key: String,
+    },
+    /// `vaultic test-env`.
+    TestEnv {
+        kept: usize,
+        total: usize,
+        dest: String,
+    },
+    CiExport {
+        variables: usize,
+        format: String,
+    },
+    Run {
+        program: String,
+        variables: usize,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        filter: Option<String>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        leases: Vec<String>,
+    },
+    Diff {
+        differences: usize,
+    },
+    Promote {
+        keys: Vec<String>,
+    },
+    Prune {
+        reason: String,
+    },
+    Sync {
+        location: String,
+        added: usize,
+        changed: usize,
+        removed: usize,
+    },
+    SnapshotCreate {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        note: Option<String>,
+    },
+    SnapshotRestore {
+        name: String,
+    },
+    ArchiveCreate {
+        dest: String,
+    },
+    ArchiveRestore {
+        source: String,
+    },
+    BundleCreate {
+        recipients: Vec<String>,
+        dest: String,
+    },
+    MovedTo {
+        path: String,
+    },
+    MovedFrom {
+        path: String,
+    },
+    HookInstall {
+        hooks: Vec<String>,
+    },
+    HookBypass {
+        commit: String,
+    },
+    TemplateSync {
+        keys: usize,
+        envs: Vec<String>,
+    },
+    Validate {
+        passed: usize,
+        failed: usize,
+    },
+    /// `vaultic check` against `.env.template`.
+    CheckTemplate {
+        present: usize,
+        total: usize,
+        missing: usize,
+    },
+    /// `vaultic env check`.
+    CheckEnvTemplates {
+        passed: usize,
+        total: usize,
+    },
+    /// `vaultic check --scan`.
+    CheckScan {
+        leaks: usize,
+    },
+    /// `vaultic check --policy`.
+    CheckPolicy {
+        violations: usize,
+    },
+    /// `vaultic check --schema`.
+    CheckSchema {
+        failed: usize,
+    },
+    /// `vaultic check --all-outputs`.
+    CheckOutputs {
+        passed: usize,
+        total: usize,
+    },
+    /// `vaultic keys verify-roster`.
+    CheckRoster {
+        roster: String,
+        missing: usize,
+    },
+    /// `vaultic keys share release`.
+    ShareRelease {
+        index: u8,
+        to: String,
+    },
+    /// `vaultic repair-armor`.
+    ArmorRepair {
+        fixes: Vec<String>,
+    },
+    /// A detail written as free text by an older version.
+    #[serde(untagged)]
+    Text(String),
+}
+
+impl std::fmt::Display for AuditDetail {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Init => write!(f, "project initialized"),
+            Self::Encrypt {
+                cipher,
+                recipients,
+                note,
+            } => {
+                write!(f, "encrypted with {cipher} for {recipients} recipient(s)")?;
+                match note {
+                    Some(note) => write!(f, ": {note}"),
+                    None => Ok(()),
+                }
+            }
+            Self::Decrypt { variables, dest } => {
+                write!(f, "{variables} variables decrypted to {dest}")
+            }
+            Self::DecryptFailed {
+                attempts,
+                key,
+                since,
+            } => {
+                write!(f, "{attempts} consecutive failed decrypts")?;
+                if let Some(key) = key {
+                    write!(f, " with key {key}")?;
+                }
+                write!(f, " since {}", since.format("%Y-%m-%d %H:%M"))
+            }
+            Self::KeyAdd {
+                key, label: None, ..
+            } => write!(f, "added {key}"),
+            Self::KeyAdd {
+                key,
+                label: Some(label),
+                ..
+            } => write!(f, "added {key} ({label})"),
+            Self::KeyRemove { key, name: None } => write!(f, "removed {key}"),
+            Self::KeyRemove {
+                key,
+                name: Some(name),
+            } => write!(f, "removed {key} ({name})"),
+            Self::KeyRotate { from, to } => write!(f, "rotated {from} to {to}"),
+            Self::Resolve {
+                variables,
+                layers,
+                filter,
+            } => {
+                write!(f, "{variables} variables from {layers} layer(s)")?;
+                match filter {
+                    Some(filter) => write!(f, " ({filter})"),
+                    None => Ok(()),
+                }
+            }
+            Self::Get { key } => write!(f, "get {key}"),
+            Self::TestEnv { kept, total, dest } => {
+                write!(f, "test env: {kept} of {total} variables to {dest}")
+            }
+            Self::CiExport { variables, format } => {
+                write!(f, "{variables} variables exported as {format}")
+            }
+            Self::Run {
+                program,
+                variables,
+                filter,
+                leases,
+            } => {
+                write!(f, "{variables} variable(s) into {program}")?;
+                if let Some(filter) = filter {
+                    write!(f, " ({filter})")?;
+                }
+                if !leases.is_empty() {
+                    write!(f, ", leases: {}", leases.join(", "))?;
+                }
+                Ok(())
+            }
+            Self::Diff { differences } => write!(f, "{differences} difference(s)"),
+            Self::Promote { keys } => write!(f, "{} key(s): {}", keys.len(), keys.join(", ")),
+            Self::Prune { reason } => write!(f, "{reason}"),
+            Self::Sync {
+                location,
+                added,
+                changed,
+                removed,
+            } => write!(
+                f,
+                "{location}: {added} added, {changed} changed, {removed} removed"
+            ),
+            Self::SnapshotCreate { name, note } => match note {
+                Some(note) => write!(f, "{name}: {note}"),
+                None => write!(f, "{name}"),
+            },
+            Self::SnapshotRestore { name } => write!(f, "{name}"),
+            Self::ArchiveCreate { dest } => write!(f, "created {dest}"),
+            Self::ArchiveRestore { source } => write!(f, "restored from {source}"),
+            Self::BundleCreate { recipients, dest } => {
+                write!(f, "bundled to {dest} for {}", recipients.join(", "))
+            }
+            Self::MovedTo { path } => write!(f, "moved to {path}"),
+            Self::MovedFrom { path } => write!(f, "moved from {path}"),
+            Self::HookInstall { hooks } => write!(f, "{}", hooks.join(", ")),
+            Self::HookBypass { commit } => {
+                write!(f, "commit {commit} skipped the pre-commit hook")
+            }
+            Self::TemplateSync { keys, envs } => write!(
+                f,
+                "{keys} keys written from environments: {}",
+                envs.join(", ")
+            ),
+            Self::Validate { passed, failed } => write!(f, "{passed} passed, {failed} failed"),
+            Self::CheckTemplate {
+                present,
+                total,
+                missing,
+            } => {
+                write!(f, "{present}/{total} present")?;
+                if *missing > 0 {
+                    write!(f, ", {missing} missing")?;
+                }
+                Ok(())
+            }
+            Self::CheckEnvTemplates { passed, total } => {
+                write!(f, "{passed}/{total} environments match their template")
+            }
+            Self::CheckScan { leaks } => write!(f, "scan: {leaks} leak(s)"),
+            Self::CheckPolicy { violations } => write!(f, "policy: {violations} violation(s)"),
+            Self::CheckSchema { failed } => write!(f, "schema: {failed} key(s) failed"),
+            Self::CheckOutputs { passed, total } => write!(f, "{passed}/{total} outputs ok"),
+            Self::CheckRoster { roster, missing } => {
+                write!(f, "roster {roster}: {missing} not on roster")
+            }
+            Self::ShareRelease { index, to } => write!(f, "share {index} released to {to}"),
+            Self::ArmorRepair { fixes } => write!(f, "repaired: {}", fixes.join(", ")),
+            Self::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn structured_detail_round_trips_with_a_kind_tag() {
+        let detail = AuditDetail::KeyAdd {
+            key: "age1alice".into(),
+            label: Some("alice@laptop".into()),
+            email: None,
+            role: None,
+        };
+        let json = serde_json::to_value(&detail).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"kind": "key_add", "key": "age1alice", "label": "alice@laptop"})
+        );
+        assert_eq!(serde_json::from_value::<AuditDetail>(json).unwrap(), detail);
+        assert_eq!(detail.to_string(), "added age1alice (alice@laptop)");
+    }
+
+    #[test]
+    fn string_details_from_older_logs_still_parse() {
+        let line = r#"{"timestamp":"2026-02-22T09:00:00Z","author":"Cristo","email":null,"action":"encrypt","files":["dev.env.enc"],"detail":"encrypted with age for 2 recipient(s)","state_hash":null}"#;
+        let entry: AuditEntry = serde_json::from_str(line).unwrap();
+        assert_eq!(
+            entry.detail,
+            Some(AuditDetail::Text(
+                "encrypted with age for 2 recipient(s)".into()
+            ))
+        );
+
+        let structured = AuditDetail::Encrypt {
+            cipher: "age".into(),
+            recipients: 2,
+            note: None,
+        };
+        assert_eq!(
+            structured.to_string(),
+            entry.detail.unwrap().to_string(),
+            "the summary reads the same as the old text"
+        );
+    }
+}
@@ -6,9 +6,75 @@ pub enum DiffKind {
     Modified {
         old_value: String,
         new_value: String,
+        /// How the value's format changed, for display without raw values.
+        /// Empty when only the content differs.
+        changes: Vec<ValueChange>,
     },
 }
 
+/// A format-level difference between two values of the same key.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValueChange {
+    /// Character count changed.
+    Length { old: usize, new: usize },
+    /// The set of characters used changed (e.g. hex → base64).
+    Charset { old: Charset, new: Charset },
+    /// Both values are URLs pointing at different hosts.
+    UrlHost,
+}
+
+/// Narrowest character class that describes a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Charset {
+    Empty,
+    Numeric,
+    Hex,
+    Alphanumeric,
+    Base64,
+    Text,
+}
+
+impl Charset {
+    /// Classify `value` into the narrowest matching class.
+    pub fn of(value: &str) -> Self {
+        if value.is_empty() {
+            Charset::Empty
+        } else if value.chars().all(|c| c.is_ascii_digit()) {
+            Charset::Numeric
+        } else if value.chars().all(|c| c.is_ascii_hexdigit()) {
+            Charset::Hex
+        } else if value.chars().all(|c| c.is_ascii_alphanumeric()) {
+            Charset::Alphanumeric
+        } else if is_base64(value) {
+            Charset::Base64
+        } else {
+            Charset::Text
+        }
+    }
+
+    /// Short lowercase label, e.g. `hex`.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Charset::Empty => "empty",
+            Charset::Numeric => "numeric",
+            Charset::Hex => "hex",
+            Charset::Alphanumeric => "alnum",
+            Charset::Base64 => "base64",
+            Charset::Text => "text",
+        }
+    }
+}
+
+/// Standard or URL-safe base64 alphabet with optional trailing `=` padding.
+fn is_base64(value: &str) -> bool {
+    let body = value.trim_end_matches('=');
+    value.len() - body.len() <= 2
+        && !body.is_empty()
+        && body
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '-' | '_'))
+}
+
 /// One entry in a diff comparison.
 #[derive(Debug, Clone, PartialEq)]
 pub struct DiffEntry {
@@ -24,9 +90,58 @@ pub struct DiffResult {
     pub entries: Vec<DiffEntry>,
 }
 
+impl DiffKind {
+    /// Human-readable summary of `Modified` changes, e.g. `length, charset`.
+    pub fn change_summary(&self) -> Option<String> {
+        let DiffKind::Modified { changes, .. } = self else {
+            return None;
+        };
+        if changes.is_empty() {
+            return Some("content".into());
+        }
+        let parts: Vec<&str> = changes
+            .iter()
+            .map(|c| match c {
+                ValueChange::Length { .. } => "length",
+                ValueChange::Charset { .. } => "charset",
+                ValueChange::UrlHost => "url host",
+            })
+            .collect();
+        Some(parts.join(", "))
+    }
+}
+
 impl DiffResult {
     /// Returns true if there are no differences.
     pub fn is_empty(&self) -> bool {
         self.entries.is_empty()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn charset_classification() {
+        assert_eq!(Charset::of(""), Charset::Empty);
+        assert_eq!(Charset::of("5432"), Charset::Numeric);
+        assert_eq!(Charset::of("deadBEEF01"), Charset::Hex);
+        assert_eq!(Charset::of("localhost"), Charset::Alphanumeric);
+        assert_eq!(Charset::of("c2VjcmV0Cg=="), Charset::Base64);
+        assert_eq!(Charset::of("sk_live_abc-123"), Charset::Base64);
+        assert_eq!(Charset::of("postgres://db:5432/app"), Charset::Text);
+        assert_eq!(Charset::of("a===="), Charset::Text);
+    }
+
+    #[test]
+    fn change_summary_lists_changes() {
+        let kind = DiffKind::Modified {
+            old_value: "a".into(),
+            new_value: "bb".into(),
+            changes: vec![ValueChange::Length { old: 1, new: 2 }, ValueChange::UrlHost],
+        };
+        assert_eq!(kind.change_summary().unwrap(), "length, url host");
+        assert_eq!(DiffKind::Added.change_summary(), None);
+    }
+}
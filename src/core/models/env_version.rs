@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+
+/// A previous ciphertext of an environment, kept for `vaultic rollback`
+/// at `.vaultic/history/<env>/<id>.enc`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnvVersion {
+    /// Creation time as `YYYYMMDD-HHMMSS`, with a `-N` suffix when two
+    /// versions share a second.
+    pub id: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub author: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// SHA-256 of the stored ciphertext.
+    pub sha256: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Number of variables in the version, when they could be counted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variables: Option<usize>,
+}
+
+/// Every kept version of one environment, oldest first
+/// (`.vaultic/history/<env>/manifest.toml`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VersionManifest {
+    #[serde(default)]
+    pub versions: Vec<EnvVersion>,
+}
+
+impl VersionManifest {
+    /// An id for a version created at `created_at` that no kept version
+    /// uses yet.
+    pub fn next_id(&self, created_at: chrono::DateTime<chrono::Utc>) -> String {
+        let base = created_at.format("%Y%m%d-%H%M%S").to_string();
+        let taken = |id: &str| self.versions.iter().any(|v| v.id == id);
+        if !taken(&base) {
+            return base;
+        }
+        (2..)
+            .map(|n| format!("{base}-{n}"))
+            .find(|id| !taken(id))
+            .expect("unbounded range always yields a free id")
+    }
+
+    /// Drop the oldest versions so at most `keep` remain, returning the
+    /// removed ones.
+    pub fn trim(&mut self, keep: usize) -> Vec<EnvVersion> {
+        let excess = self.versions.len().saturating_sub(keep);
+        self.versions.drain(..excess).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(id: &str) -> EnvVersion {
+        EnvVersion {
+            id: id.into(),
+            created_at: chrono::Utc::now(),
+            author: "alice".into(),
+            email: None,
+            sha256: "abc".into(),
+            note: None,
+            variables: None,
+        }
+    }
+
+    #[test]
+    fn next_id_suffixes_collisions() {
+        let at = "2026-03-01T10:15:00Z".parse().unwrap();
+        let mut manifest = VersionManifest::default();
+        assert_eq!(manifest.next_id(at), "20260301-101500");
+
+        manifest.versions.push(version("20260301-101500"));
+        manifest.versions.push(version("20260301-101500-2"));
+        assert_eq!(manifest.next_id(at), "20260301-101500-3");
+    }
+
+    #[test]
+    fn trim_removes_oldest() {
+        let mut manifest = VersionManifest {
+            versions: vec![version("1"), version("2"), version("3")],
+        };
+        let removed = manifest.trim(2);
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].id, "1");
+        assert_eq!(manifest.versions.len(), 2);
+        assert_eq!(manifest.versions[0].id, "2");
+    }
+}
@@ -7,6 +7,29 @@ pub struct KeyIdentity {
     pub added_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+/// Kind of public key a recipient uses, inferred from its format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyType {
+    Age,
+    Gpg,
+    Ssh,
+}
+
+impl KeyIdentity {
+    /// `age1…` keys are age, `ssh-…` keys are SSH, anything else
+    /// (fingerprint or email) is a GPG identifier.
+    pub fn key_type(&self) -> KeyType {
+        if self.public_key.starts_with("age1") {
+            KeyType::Age
+        } else if self.public_key.starts_with("ssh-") {
+            KeyType::Ssh
+        } else {
+            KeyType::Gpg
+        }
+    }
+}
+
 impl std::fmt::Display for KeyIdentity {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match &self.label {
@@ -15,3 +38,24 @@ impl std::fmt::Display for KeyIdentity {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity(key: &str) -> KeyIdentity {
+        KeyIdentity {
+            public_key: key.to_string(),
+            label: None,
+            added_at: None,
+        }
+    }
+
+    #[test]
+    fn key_type_from_format() {
+        assert_eq!(identity("age1ql3z7hjy54pw").key_type(), KeyType::Age);
+        assert_eq!(identity("ssh-ed25519 AAAAC3Nz").key_type(), KeyType::Ssh);
+        assert_eq!(identity("alice@example.com").key_type(), KeyType::Gpg);
+        assert_eq!(identity("ABCDEF0123456789").key_type(), KeyType::Gpg);
+    }
+}
@@ -0,0 +1,67 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Duration, Utc};
+
+/// Short-lived credentials handed out by a [`CredentialProvider`].
+///
+/// [`CredentialProvider`]: crate::core::traits::credential_provider::CredentialProvider
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lease {
+    /// Variables to inject, e.g. `AWS_SESSION_TOKEN`.
+    pub vars: BTreeMap<String, String>,
+    /// When the credentials were obtained.
+    pub issued_at: DateTime<Utc>,
+    /// When they stop working; `None` if they never expire.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl Lease {
+    /// When to ask for new credentials: once four fifths of the lifetime
+    /// have passed, leaving time to retry before the old ones expire.
+    pub fn refresh_at(&self) -> Option<DateTime<Utc>> {
+        let lifetime = self.expires_at? - self.issued_at;
+        Some(self.issued_at + lifetime * 4 / 5)
+    }
+
+    /// Whether the credentials still work at `now`.
+    pub fn is_valid_at(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_none_or(|expires| now < expires)
+    }
+
+    /// Credentials valid for `ttl` from `issued_at`.
+    pub fn for_ttl(
+        vars: BTreeMap<String, String>,
+        issued_at: DateTime<Utc>,
+        ttl: Option<Duration>,
+    ) -> Self {
+        Self {
+            vars,
+            issued_at,
+            expires_at: ttl.map(|ttl| issued_at + ttl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refresh_at_four_fifths_of_lifetime() {
+        let issued = DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let lease = Lease::for_ttl(BTreeMap::new(), issued, Some(Duration::seconds(3600)));
+
+        assert_eq!(lease.refresh_at(), Some(issued + Duration::seconds(2880)));
+        assert!(lease.is_valid_at(issued + Duration::seconds(3599)));
+        assert!(!lease.is_valid_at(issued + Duration::seconds(3600)));
+    }
+
+    #[test]
+    fn lease_without_expiry_is_never_refreshed() {
+        let lease = Lease::for_ttl(BTreeMap::new(), Utc::now(), None);
+        assert_eq!(lease.refresh_at(), None);
+        assert!(lease.is_valid_at(Utc::now() + Duration::days(365)));
+    }
+}
@@ -0,0 +1,110 @@
+use serde::Serialize;
+
+use crate::core::models::key_identity::KeyType;
+
+/// A private key on this machine: the age identity file or a secret key
+/// in the GPG keyring.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LocalIdentity {
+    #[serde(rename = "type")]
+    pub key_type: KeyType,
+    /// age public key or GPG fingerprint.
+    pub id: String,
+    /// Identity file path, or `GPG keyring`.
+    pub source: String,
+    /// GPG user IDs, e.g. `Alice <alice@example.com>`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub user_ids: Vec<String>,
+    /// Chosen with `vaultic keys use` for this project.
+    pub preferred: bool,
+    /// Encrypted environments whose recipients include this key.
+    pub envs: Vec<String>,
+}
+
+impl LocalIdentity {
+    /// Whether `recipient`, an entry of a recipients list, names this key.
+    ///
+    /// An age key matches its public key. A GPG key matches its
+    /// fingerprint, a long or short key ID (its trailing 16 or 8 hex
+    /// digits), or an email in one of its user IDs, case-insensitively.
+    pub fn matches(&self, recipient: &str) -> bool {
+        match self.key_type {
+            KeyType::Gpg => {
+                let recipient = recipient.trim_start_matches("0x");
+                let is_key_id = [8, 16, 40].contains(&recipient.len())
+                    && recipient.chars().all(|c| c.is_ascii_hexdigit());
+                if is_key_id {
+                    return self
+                        .id
+                        .to_ascii_uppercase()
+                        .ends_with(&recipient.to_ascii_uppercase());
+                }
+                recipient.contains('@')
+                    && self
+                        .user_ids
+                        .iter()
+                        .filter_map(|uid| email(uid))
+                        .any(|e| e.eq_ignore_ascii_case(recipient.trim_matches(['<', '>'])))
+            }
+            KeyType::Age | KeyType::Ssh => self.id == recipient,
+        }
+    }
+
+    /// `id`, followed by the first user ID for a GPG key.
+    pub fn describe(&self) -> String {
+        match self.user_ids.first() {
+            Some(uid) => format!("{} ({uid})", self.id),
+            None => self.id.clone(),
+        }
+    }
+}
+
+/// The email of a `Name <email>` user ID, or the whole ID when it is a
+/// bare address.
+fn email(user_id: &str) -> Option<&str> {
+    match (user_id.rfind('<'), user_id.rfind('>')) {
+        (Some(start), Some(end)) if start < end => Some(&user_id[start + 1..end]),
+        _ => user_id.contains('@').then_some(user_id.trim()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gpg_key() -> LocalIdentity {
+        LocalIdentity {
+            key_type: KeyType::Gpg,
+            id: "0123456789ABCDEF0123456789ABCDEF01234567".into(),
+            source: "GPG keyring".into(),
+            user_ids: vec!["Alice Ops <Alice@Example.com>".into()],
+            preferred: false,
+            envs: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn gpg_key_matches_fingerprint_key_ids_and_email() {
+        let key = gpg_key();
+        assert!(key.matches("0123456789ABCDEF0123456789ABCDEF01234567"));
+        assert!(key.matches("89abcdef01234567"));
+        assert!(key.matches("0x01234567"));
+        assert!(key.matches("alice@example.com"));
+        assert!(!key.matches("bob@example.com"));
+        assert!(!key.matches("FEDCBA9876543210"));
+    }
+
+    #[test]
+    fn age_key_matches_only_itself() {
+        let key = LocalIdentity {
+            key_type: KeyType::Age,
+            id: "age1alice".into(),
+            source: "keys.txt".into(),
+            user_ids: Vec::new(),
+            preferred: false,
+            envs: Vec::new(),
+        };
+        assert!(key.matches("age1alice"));
+        assert!(!key.matches("age1bob"));
+    }
+}
@@ -0,0 +1,277 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// SHA-256 checksums of the files that decide who can decrypt, as Vaultic
+/// last wrote them (`.vaultic/manifest.toml`).
+///
+/// Commands that change `config.toml` or a recipient list record it
+/// again, so a file that no longer matches was edited outside Vaultic and
+/// never reached the audit log.
+///
+/// It also keeps, per environment, the chain of ciphertext states Vaultic
+/// wrote: each link names the checksum the file had before the write and
+/// the one it had after, so a ciphertext swapped in between two writes
+/// shows up as a link that does not follow on from the one before.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub updated_at: DateTime<Utc>,
+    /// File name inside `.vaultic/` to its hex SHA-256.
+    #[serde(default)]
+    pub files: BTreeMap<String, String>,
+    /// Environment name to its recorded ciphertext states, oldest first.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub chains: BTreeMap<String, Vec<StateLink>>,
+}
+
+/// How many states are kept per environment; older links are dropped.
+pub const MAX_STATES: usize = 100;
+
+/// One write of an environment's encrypted file by Vaultic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateLink {
+    pub at: DateTime<Utc>,
+    /// SHA-256 of the file before the write; `None` when there was none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev_hash: Option<String>,
+    /// SHA-256 of the file Vaultic wrote.
+    pub new_hash: String,
+}
+
+/// An encrypted file that changed without Vaultic recording it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChainBreak {
+    pub change: FileChange,
+    /// The last state Vaultic recorded before the change.
+    pub after: DateTime<Utc>,
+    /// The next state Vaultic recorded, or `None` when the change is
+    /// what is on disk now.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<DateTime<Utc>>,
+}
+
+/// How a protected file differs from the manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileChange {
+    Modified,
+    /// A recipient list the manifest does not know.
+    Added,
+    Removed,
+}
+
+/// A protected file changed since the manifest was recorded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UnrecordedChange {
+    pub file: String,
+    pub change: FileChange,
+}
+
+impl Manifest {
+    /// Files in `current` (name to checksum) that differ from the
+    /// manifest, sorted by name.
+    pub fn changes(&self, current: &BTreeMap<String, String>) -> Vec<UnrecordedChange> {
+        let mut changes: Vec<UnrecordedChange> = current
+            .iter()
+            .filter_map(|(file, hash)| {
+                let change = match self.files.get(file) {
+                    Some(recorded) if recorded == hash => return None,
+                    Some(_) => FileChange::Modified,
+                    None => FileChange::Added,
+                };
+                Some(UnrecordedChange {
+                    file: file.clone(),
+                    change,
+                })
+            })
+            .chain(
+                self.files
+                    .keys()
+                    .filter(|file| !current.contains_key(*file))
+                    .map(|file| UnrecordedChange {
+                        file: file.clone(),
+                        change: FileChange::Removed,
+                    }),
+            )
+            .collect();
+        changes.sort_by(|a, b| a.file.cmp(&b.file));
+        changes
+    }
+
+    /// Append a state to `env`'s chain, dropping the oldest links past
+    /// [`MAX_STATES`].
+    pub fn push_state(&mut self, env: &str, link: StateLink) {
+        let chain = self.chains.entry(env.to_string()).or_default();
+        chain.push(link);
+        if chain.len() > MAX_STATES {
+            chain.drain(..chain.len() - MAX_STATES);
+        }
+    }
+
+    /// Places where `env`'s chain does not follow on, oldest first, and
+    /// whether `current` (the checksum on disk, `None` when the file is
+    /// gone) differs from the last recorded state. Empty when the chain
+    /// is intact or nothing was recorded.
+    pub fn chain_breaks(&self, env: &str, current: Option<&str>) -> Vec<ChainBreak> {
+        let Some(chain) = self.chains.get(env) else {
+            return Vec::new();
+        };
+        let mut breaks: Vec<ChainBreak> = chain
+            .windows(2)
+            .filter(|pair| pair[1].prev_hash.as_deref() != Some(pair[0].new_hash.as_str()))
+            .map(|pair| ChainBreak {
+                change: if pair[1].prev_hash.is_some() {
+                    FileChange::Modified
+                } else {
+                    FileChange::Removed
+                },
+                after: pair[0].at,
+                before: Some(pair[1].at),
+            })
+            .collect();
+        if let Some(last) = chain.last() {
+            let change = match current {
+                Some(hash) if hash == last.new_hash => None,
+                Some(_) => Some(FileChange::Modified),
+                None => Some(FileChange::Removed),
+            };
+            breaks.extend(change.map(|change| ChainBreak {
+                change,
+                after: last.at,
+                before: None,
+            }));
+        }
+        breaks
+    }
+}
+
+impl ChainBreak {
+    /// E.g. `prod was modified outside Vaultic after 2026-10-16 09:30`.
+    pub fn describe(&self, env: &str) -> String {
+        let how = match self.change {
+            FileChange::Modified | FileChange::Added => "modified",
+            FileChange::Removed => "removed",
+        };
+        let after = self.after.format("%Y-%m-%d %H:%M");
+        match self.before {
+            Some(before) => format!(
+                "{env} was {how} outside Vaultic between {after} and {}",
+                before.format("%Y-%m-%d %H:%M")
+            ),
+            None => format!("{env} was {how} outside Vaultic after {after}"),
+        }
+    }
+}
+
+impl UnrecordedChange {
+    /// E.g. `recipients.txt was modified outside Vaultic`.
+    pub fn describe(&self) -> String {
+        let how = match self.change {
+            FileChange::Modified => "modified",
+            FileChange::Added => "added",
+            FileChange::Removed => "removed",
+        };
+        format!("{} was {how} outside Vaultic", self.file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries
+            .iter()
+            .map(|(file, hash)| (file.to_string(), hash.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn changes_report_modified_added_and_removed_files() {
+        let manifest = Manifest {
+            updated_at: Utc::now(),
+            files: files(&[
+                ("config.toml", "aaa"),
+                ("recipients.txt", "bbb"),
+                ("recipients.prod.txt", "ccc"),
+            ]),
+            chains: BTreeMap::new(),
+        };
+        assert!(manifest.changes(&manifest.files).is_empty());
+
+        let current = files(&[
+            ("config.toml", "aaa"),
+            ("recipients.txt", "changed"),
+            ("recipients.dev.txt", "ddd"),
+        ]);
+        let changes = manifest.changes(&current);
+        assert_eq!(
+            changes
+                .iter()
+                .map(|c| (c.file.as_str(), c.change))
+                .collect::<Vec<_>>(),
+            vec![
+                ("recipients.dev.txt", FileChange::Added),
+                ("recipients.prod.txt", FileChange::Removed),
+                ("recipients.txt", FileChange::Modified),
+            ]
+        );
+        assert_eq!(
+            changes[2].describe(),
+            "recipients.txt was modified outside Vaultic"
+        );
+    }
+
+    #[test]
+    fn chain_breaks_find_states_vaultic_did_not_write() {
+        let at = |hour| {
+            DateTime::parse_from_rfc3339(&format!("2026-10-16T{hour:02}:00:00Z"))
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+        let link = |hour, prev: Option<&str>, new: &str| StateLink {
+            at: at(hour),
+            prev_hash: prev.map(String::from),
+            new_hash: new.to_string(),
+        };
+        let mut manifest = Manifest {
+            updated_at: at(8),
+            files: BTreeMap::new(),
+            chains: BTreeMap::new(),
+        };
+        assert!(manifest.chain_breaks("prod", None).is_empty());
+
+        manifest.push_state("prod", link(9, None, "aaa"));
+        manifest.push_state("prod", link(10, Some("aaa"), "bbb"));
+        assert!(manifest.chain_breaks("prod", Some("bbb")).is_empty());
+
+        // Replaced on disk since the last write
+        let breaks = manifest.chain_breaks("prod", Some("evil"));
+        assert_eq!(breaks.len(), 1);
+        assert_eq!(breaks[0].change, FileChange::Modified);
+        assert_eq!(
+            breaks[0].describe("prod"),
+            "prod was modified outside Vaultic after 2026-10-16 10:00"
+        );
+        assert_eq!(
+            manifest.chain_breaks("prod", None)[0].change,
+            FileChange::Removed
+        );
+
+        // Re-encrypting over the replaced file keeps the gap visible
+        manifest.push_state("prod", link(11, Some("evil"), "ccc"));
+        let breaks = manifest.chain_breaks("prod", Some("ccc"));
+        assert_eq!(breaks.len(), 1);
+        assert_eq!(
+            breaks[0].describe("prod"),
+            "prod was modified outside Vaultic between 2026-10-16 10:00 and 2026-10-16 11:00"
+        );
+
+        for hour in 0..MAX_STATES {
+            manifest.push_state("dev", link(hour % 24, None, "x"));
+        }
+        manifest.push_state("dev", link(0, None, "x"));
+        assert_eq!(manifest.chains["dev"].len(), MAX_STATES);
+    }
+}
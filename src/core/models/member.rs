@@ -0,0 +1,184 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::core::models::key_identity::KeyIdentity;
+
+/// Days before its `expires` date from which a member is reported as
+/// expiring.
+pub const EXPIRY_NOTICE_DAYS: i64 = 30;
+
+/// The teammate behind a recipient key, so an `age1…` string can be told
+/// apart from the others.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Member {
+    /// Public key, GPG fingerprint or email, as in the recipient lists.
+    pub key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// Free-form, e.g. `admin`, `developer` or `ci`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    /// Last day the key should be a recipient, e.g. the end of a
+    /// contract. Only reported; the key is not removed automatically.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<NaiveDate>,
+}
+
+impl Member {
+    /// `Name <email> (role)`, leaving out what is unknown, or `None` when
+    /// nothing is.
+    pub fn describe(&self) -> Option<String> {
+        let mut parts: Vec<String> = Vec::new();
+        if let Some(name) = &self.name {
+            parts.push(name.clone());
+        }
+        if let Some(email) = &self.email {
+            parts.push(format!("<{email}>"));
+        }
+        if let Some(role) = &self.role {
+            parts.push(format!("({role})"));
+        }
+        (!parts.is_empty()).then(|| parts.join(" "))
+    }
+
+    /// Days from `today` until `expires`: 0 on the last day, negative
+    /// once it has passed, `None` without a date.
+    pub fn days_left(&self, today: NaiveDate) -> Option<i64> {
+        self.expires.map(|expires| (expires - today).num_days())
+    }
+
+    /// Whether `expires` has passed on `today`.
+    pub fn is_expired(&self, today: NaiveDate) -> bool {
+        self.days_left(today).is_some_and(|days| days < 0)
+    }
+
+    /// How the expiry date reads on `today` (see [`describe_expiry`]), or
+    /// `None` without a date.
+    pub fn expiry_note(&self, today: NaiveDate) -> Option<String> {
+        Some(describe_expiry(self.expires?, self.days_left(today)?))
+    }
+}
+
+/// `expired on …`, `expires today`, `expires in N day(s) on …` within
+/// [`EXPIRY_NOTICE_DAYS`], else `expires on …`.
+pub fn describe_expiry(expires: NaiveDate, days_left: i64) -> String {
+    match days_left {
+        days if days < 0 => format!("expired on {expires}"),
+        0 => "expires today".to_string(),
+        days if days <= EXPIRY_NOTICE_DAYS => format!("expires in {days} day(s) on {expires}"),
+        _ => format!("expires on {expires}"),
+    }
+}
+
+/// Every named member of a project (`.vaultic/members.toml`).
+///
+/// The recipient lists stay the source of truth for who can decrypt; a
+/// key without a member entry is still a recipient.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Members {
+    #[serde(default, rename = "member", skip_serializing_if = "Vec::is_empty")]
+    pub members: Vec<Member>,
+}
+
+impl Members {
+    /// Members named by the `# label` of each recipient, for projects
+    /// that only had labels so far.
+    pub fn from_labels(recipients: &[KeyIdentity]) -> Self {
+        Self {
+            members: recipients
+                .iter()
+                .filter_map(|ki| {
+                    ki.label.as_ref().map(|label| Member {
+                        key: ki.public_key.clone(),
+                        name: Some(label.clone()),
+                        email: None,
+                        role: None,
+                        expires: None,
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    /// The member holding `key`.
+    pub fn get(&self, key: &str) -> Option<&Member> {
+        self.members.iter().find(|m| m.key == key)
+    }
+
+    /// Add `member`, replacing the entry for the same key.
+    pub fn upsert(&mut self, member: Member) {
+        match self.members.iter_mut().find(|m| m.key == member.key) {
+            Some(existing) => *existing = member,
+            None => self.members.push(member),
+        }
+    }
+
+    /// Remove the member holding `key`, returning it.
+    pub fn remove(&mut self, key: &str) -> Option<Member> {
+        let index = self.members.iter().position(|m| m.key == key)?;
+        Some(self.members.remove(index))
+    }
+
+    /// Who holds `recipient`: its member description, else its label.
+    pub fn describe(&self, recipient: &KeyIdentity) -> Option<String> {
+        self.get(&recipient.public_key)
+            .and_then(Member::describe)
+            .or_else(|| recipient.label.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recipient(key: &str, label: Option<&str>) -> KeyIdentity {
+        KeyIdentity {
+            public_key: key.into(),
+            label: label.map(String::from),
+            added_at: None,
+        }
+    }
+
+    #[test]
+    fn labels_seed_members_and_entries_describe_recipients() {
+        let recipients = [
+            recipient("age1alice", Some("alice")),
+            recipient("age1ci", None),
+        ];
+        let mut members = Members::from_labels(&recipients);
+        assert_eq!(members.members.len(), 1);
+        assert_eq!(members.describe(&recipients[0]).as_deref(), Some("alice"));
+        assert_eq!(members.describe(&recipients[1]), None);
+
+        members.upsert(Member {
+            key: "age1alice".into(),
+            name: Some("Alice".into()),
+            email: Some("alice@example.com".into()),
+            role: Some("admin".into()),
+            expires: NaiveDate::from_ymd_opt(2026, 6, 30),
+        });
+        assert_eq!(members.members.len(), 1);
+        assert_eq!(
+            members.describe(&recipients[0]).as_deref(),
+            Some("Alice <alice@example.com> (admin)")
+        );
+        let alice = members.get("age1alice").unwrap();
+        let day = |d| NaiveDate::from_ymd_opt(2026, 6, d).unwrap();
+        assert_eq!(alice.days_left(day(20)), Some(10));
+        assert_eq!(alice.days_left(day(30)), Some(0));
+        assert_eq!(
+            alice.days_left(NaiveDate::from_ymd_opt(2026, 7, 2).unwrap()),
+            Some(-2)
+        );
+        assert!(alice.is_expired(NaiveDate::from_ymd_opt(2026, 7, 1).unwrap()));
+        assert!(!alice.is_expired(day(30)));
+        assert_eq!(
+            alice.expiry_note(day(20)).as_deref(),
+            Some("expires in 10 day(s) on 2026-06-30")
+        );
+        assert!(members.remove("age1alice").is_some());
+        assert_eq!(members.describe(&recipients[0]).as_deref(), Some("alice"));
+    }
+}
@@ -1,6 +1,18 @@
 pub mod audit_entry;
 pub mod diff_result;
+pub mod env_version;
 pub mod environment;
 pub mod key_identity;
+pub mod lease;
+pub mod local_identity;
+pub mod manifest;
+pub mod member;
+pub mod ownership;
+pub mod recipient_bundle;
 pub mod secret_file;
+pub mod secret_ref;
+pub mod snapshot;
+pub mod status_report;
 pub mod update_info;
+pub mod value_history;
+pub mod verification;
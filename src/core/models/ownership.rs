@@ -0,0 +1,177 @@
+use crate::core::models::secret_file::{Line, SecretFile};
+
+/// Comment marker that assigns an owner in a template file.
+const OWNER_MARKER: &str = "vaultic:owner=";
+
+/// One ownership rule: a key or key prefix owned by a team.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OwnerRule {
+    /// Exact key (`STRIPE_KEY`) or prefix ending in `*` (`STRIPE_*`).
+    pub pattern: String,
+    pub owner: String,
+}
+
+impl OwnerRule {
+    /// Specificity of a match against `key`, or `None` if it does not match.
+    ///
+    /// Exact matches beat any prefix; longer prefixes beat shorter ones.
+    fn specificity(&self, key: &str) -> Option<usize> {
+        match self.pattern.strip_suffix('*') {
+            Some(prefix) => key.starts_with(prefix).then_some(prefix.len()),
+            None => (self.pattern == key).then_some(usize::MAX),
+        }
+    }
+}
+
+/// CODEOWNERS-style key ownership.
+///
+/// Rules come from `[owners]` in config.toml and from
+/// `# vaultic:owner=<team>` annotations in the template. The most
+/// specific matching rule wins; on a tie, the rule added last wins.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Ownership {
+    pub rules: Vec<OwnerRule>,
+}
+
+impl Ownership {
+    /// Add a rule.
+    pub fn add(&mut self, pattern: &str, owner: &str) {
+        self.rules.push(OwnerRule {
+            pattern: pattern.to_string(),
+            owner: owner.to_string(),
+        });
+    }
+
+    /// Collect annotations from a template file.
+    ///
+    /// - `# vaultic:owner=payments` owns the next key in the file
+    /// - `# vaultic:owner=payments prefix=STRIPE_` owns every key with that prefix
+    pub fn add_template_annotations(&mut self, template: &SecretFile) {
+        let mut pending: Option<String> = None;
+
+        for line in &template.lines {
+            match line {
+                Line::Comment(text) => {
+                    let Some((owner, prefix)) = parse_annotation(text) else {
+                        continue;
+                    };
+                    match prefix {
+                        Some(prefix) => self.add(&format!("{prefix}*"), &owner),
+                        None => pending = Some(owner),
+                    }
+                }
+                Line::Entry(entry) => {
+                    if let Some(owner) = pending.take() {
+                        self.add(&entry.key, &owner);
+                    }
+                }
+                Line::Blank => {}
+            }
+        }
+    }
+
+    /// Owner of `key`, if any rule matches.
+    pub fn owner_of(&self, key: &str) -> Option<&str> {
+        self.rules
+            .iter()
+            .filter_map(|r| r.specificity(key).map(|s| (s, r)))
+            .max_by_key(|(s, _)| *s)
+            .map(|(_, r)| r.owner.as_str())
+    }
+}
+
+/// Parse `# vaultic:owner=<team> [prefix=<PREFIX>]` into `(team, prefix)`.
+fn parse_annotation(comment: &str) -> Option<(String, Option<String>)> {
+    let body = comment.trim().trim_start_matches('#').trim();
+    let rest = body.strip_prefix(OWNER_MARKER)?;
+
+    let mut parts = rest.split_whitespace();
+    let owner = parts.next()?.to_string();
+    let prefix = parts
+        .find_map(|p| p.strip_prefix("prefix="))
+        .map(|p| p.trim_end_matches('*').to_string())
+        .filter(|p| !p.is_empty());
+
+    Some((owner, prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::secret_file::SecretEntry;
+
+    fn template(lines: &[&str]) -> SecretFile {
+        SecretFile {
+            lines: lines
+                .iter()
+                .enumerate()
+                .map(|(i, l)| match l.split_once('=') {
+                    Some((k, v)) if !l.starts_with('#') => Line::Entry(SecretEntry {
+                        key: k.into(),
+                        value: v.into(),
+                        comment: None,
+                        line_number: i + 1,
+                    }),
+                    _ if l.is_empty() => Line::Blank,
+                    _ => Line::Comment(l.to_string()),
+                })
+                .collect(),
+            source_path: None,
+        }
+    }
+
+    #[test]
+    fn annotation_owns_next_key() {
+        let mut own = Ownership::default();
+        own.add_template_annotations(&template(&[
+            "# vaultic:owner=payments",
+            "STRIPE_KEY=",
+            "DB_URL=",
+        ]));
+
+        assert_eq!(own.owner_of("STRIPE_KEY"), Some("payments"));
+        assert_eq!(own.owner_of("DB_URL"), None);
+    }
+
+    #[test]
+    fn prefix_annotation_owns_matching_keys() {
+        let mut own = Ownership::default();
+        own.add_template_annotations(&template(&["# vaultic:owner=payments prefix=STRIPE_"]));
+
+        assert_eq!(own.owner_of("STRIPE_KEY"), Some("payments"));
+        assert_eq!(own.owner_of("STRIPE_WEBHOOK"), Some("payments"));
+        assert_eq!(own.owner_of("DB_URL"), None);
+    }
+
+    #[test]
+    fn most_specific_rule_wins() {
+        let mut own = Ownership::default();
+        own.add("*", "platform");
+        own.add("STRIPE_*", "payments");
+        own.add("STRIPE_WEBHOOK_*", "webhooks");
+        own.add("STRIPE_WEBHOOK_SECRET", "security");
+
+        assert_eq!(own.owner_of("DB_URL"), Some("platform"));
+        assert_eq!(own.owner_of("STRIPE_KEY"), Some("payments"));
+        assert_eq!(own.owner_of("STRIPE_WEBHOOK_URL"), Some("webhooks"));
+        assert_eq!(own.owner_of("STRIPE_WEBHOOK_SECRET"), Some("security"));
+    }
+
+    #[test]
+    fn later_rule_wins_on_tie() {
+        let mut own = Ownership::default();
+        own.add("API_KEY", "config-team");
+        own.add("API_KEY", "template-team");
+
+        assert_eq!(own.owner_of("API_KEY"), Some("template-team"));
+    }
+
+    #[test]
+    fn ordinary_comments_are_ignored() {
+        assert_eq!(parse_annotation("# Database settings"), None);
+        assert_eq!(
+            parse_annotation("#vaultic:owner=infra"),
+            Some(("infra".into(), None))
+        );
+    }
+}
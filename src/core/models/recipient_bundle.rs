@@ -0,0 +1,150 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::key_identity::KeyIdentity;
+use crate::core::models::member::{Member, Members};
+
+/// Version of the bundle format written by `vaultic keys export`.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// A project's recipients with the member behind each key, as exchanged
+/// between projects by `vaultic keys export` and `vaultic keys import`.
+///
+/// The bundle carries no signature of its own: it is signed like
+/// `recipients.txt`, with a detached minisign `.sig` next to it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecipientBundle {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub recipients: Vec<BundleRecipient>,
+}
+
+/// One recipient of a [`RecipientBundle`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleRecipient {
+    /// The `key`, `name`, `email`, `role` and `expires` of `members.toml`.
+    #[serde(flatten)]
+    pub member: Member,
+    /// The `# label` of the key in `recipients.txt`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+impl RecipientBundle {
+    /// Bundle `recipients` with their entries in `members`.
+    pub fn new(recipients: &[KeyIdentity], members: &Members, now: DateTime<Utc>) -> Self {
+        Self {
+            version: BUNDLE_VERSION,
+            exported_at: now,
+            recipients: recipients
+                .iter()
+                .map(|ki| BundleRecipient {
+                    member: members.get(&ki.public_key).cloned().unwrap_or(Member {
+                        key: ki.public_key.clone(),
+                        name: None,
+                        email: None,
+                        role: None,
+                        expires: None,
+                    }),
+                    label: ki.label.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Parse a bundle, rejecting formats newer than this build reads.
+    pub fn parse(json: &str) -> Result<Self> {
+        let bundle: Self = serde_json::from_str(json).map_err(|e| VaulticError::ParseError {
+            file: "recipient bundle".into(),
+            detail: e.to_string(),
+        })?;
+        if bundle.version > BUNDLE_VERSION {
+            return Err(VaulticError::InvalidConfig {
+                detail: format!(
+                    "Recipient bundle version {} is newer than this Vaultic supports ({BUNDLE_VERSION}). \
+                     Update Vaultic to import it.",
+                    bundle.version
+                ),
+            });
+        }
+        Ok(bundle)
+    }
+
+    /// Recipients whose key is not among `existing`, each once.
+    pub fn missing_from(&self, existing: &[KeyIdentity]) -> Vec<&BundleRecipient> {
+        let mut missing: Vec<&BundleRecipient> = Vec::new();
+        for recipient in &self.recipients {
+            let key = &recipient.member.key;
+            if !existing.iter().any(|k| &k.public_key == key)
+                && !missing.iter().any(|r| &r.member.key == key)
+            {
+                missing.push(recipient);
+            }
+        }
+        missing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recipient(key: &str, label: Option<&str>) -> KeyIdentity {
+        KeyIdentity {
+            public_key: key.into(),
+            label: label.map(String::from),
+            added_at: None,
+        }
+    }
+
+    #[test]
+    fn bundle_round_trips_with_members() {
+        let mut members = Members::default();
+        members.upsert(Member {
+            key: "age1alice".into(),
+            name: Some("Alice".into()),
+            email: Some("alice@example.com".into()),
+            role: Some("admin".into()),
+            expires: None,
+        });
+        let keys = [
+            recipient("age1alice", Some("Alice")),
+            recipient("age1ci", Some("ci")),
+        ];
+        let bundle = RecipientBundle::new(&keys, &members, Utc::now());
+
+        let json = serde_json::to_string(&bundle).unwrap();
+        assert!(
+            json.contains(r#""key":"age1alice","name":"Alice""#),
+            "{json}"
+        );
+        let parsed = RecipientBundle::parse(&json).unwrap();
+        assert_eq!(parsed, bundle);
+        assert_eq!(parsed.recipients[1].member.name, None);
+        assert_eq!(parsed.recipients[1].label.as_deref(), Some("ci"));
+    }
+
+    #[test]
+    fn missing_from_skips_existing_and_duplicate_keys() {
+        let keys = [
+            recipient("age1alice", None),
+            recipient("age1bob", None),
+            recipient("age1bob", Some("again")),
+        ];
+        let bundle = RecipientBundle::new(&keys, &Members::default(), Utc::now());
+
+        let missing = bundle.missing_from(&[recipient("age1alice", None)]);
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].member.key, "age1bob");
+    }
+
+    #[test]
+    fn newer_versions_are_rejected() {
+        let json = r#"{"version": 99, "exported_at": "2026-01-01T00:00:00Z", "recipients": []}"#;
+        assert!(matches!(
+            RecipientBundle::parse(json),
+            Err(VaulticError::InvalidConfig { .. })
+        ));
+    }
+}
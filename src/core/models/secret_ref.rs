@@ -0,0 +1,79 @@
+use std::fmt;
+
+/// Schemes recognized as references to a secret held elsewhere.
+pub const REFERENCE_SCHEMES: &[&str] = &["op", "vault"];
+
+/// A value that points at a secret in an external manager instead of
+/// holding it, e.g. `vault://secret/data/app#api_key` or
+/// `op://vault/item/field`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretRef {
+    /// `op` or `vault`.
+    pub scheme: String,
+    /// Everything after `<scheme>://` up to the `#`.
+    pub path: String,
+    /// The part after `#`, if any.
+    pub field: Option<String>,
+}
+
+impl SecretRef {
+    /// Parse `value` as a reference. Returns `None` for ordinary values,
+    /// including URLs with other schemes, and for paths starting with
+    /// `-`, which the provider CLIs would read as a flag.
+    pub fn parse(value: &str) -> Option<Self> {
+        let (scheme, rest) = value.trim().split_once("://")?;
+        if !REFERENCE_SCHEMES.contains(&scheme) || rest.is_empty() || rest.starts_with('-') {
+            return None;
+        }
+        let (path, field) = match rest.split_once('#') {
+            Some((path, field)) => (path, Some(field.to_string())),
+            None => (rest, None),
+        };
+        Some(Self {
+            scheme: scheme.to_string(),
+            path: path.to_string(),
+            field,
+        })
+    }
+}
+
+impl fmt::Display for SecretRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}://{}", self.scheme, self.path)?;
+        if let Some(field) = &self.field {
+            write!(f, "#{field}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_vault_and_op_references() {
+        let vault = SecretRef::parse("vault://secret/data/app#api_key").unwrap();
+        assert_eq!(vault.scheme, "vault");
+        assert_eq!(vault.path, "secret/data/app");
+        assert_eq!(vault.field.as_deref(), Some("api_key"));
+        assert_eq!(vault.to_string(), "vault://secret/data/app#api_key");
+
+        let op = SecretRef::parse("op://Prod/Stripe/secret key").unwrap();
+        assert_eq!(op.path, "Prod/Stripe/secret key");
+        assert_eq!(op.field, None);
+    }
+
+    #[test]
+    fn ordinary_values_are_not_references() {
+        for value in [
+            "https://example.com",
+            "postgres://u:p@db/app",
+            "op://",
+            "vault://-address=https://evil.example#x",
+            "plain",
+        ] {
+            assert_eq!(SecretRef::parse(value), None, "{value}");
+        }
+    }
+}
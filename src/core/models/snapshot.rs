@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// Metadata stored next to a snapshot's ciphertext
+/// (`.vaultic/snapshots/<env>/<name>.toml`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotMeta {
+    /// Snapshot name, e.g. `pre-release-42`.
+    pub name: String,
+    /// Environment the ciphertext was copied from.
+    pub env: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub author: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// SHA-256 of the copied ciphertext.
+    pub sha256: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
@@ -0,0 +1,320 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+
+use crate::core::models::local_identity::LocalIdentity;
+use crate::core::models::manifest::UnrecordedChange;
+use crate::core::models::member::{self, Member};
+
+/// Everything `vaultic status` reports about a project.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatusReport {
+    pub version: String,
+    pub cipher: String,
+    /// Executable of the `vaultic-cipher-<name>` plugin providing
+    /// `cipher`, when it is not built in and the plugin is on PATH.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cipher_plugin: Option<String>,
+    pub default_env: Option<String>,
+    pub key: KeyStatus,
+    pub recipients: Vec<String>,
+    /// Named members behind the recipients, from `members.toml`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub members: Vec<Member>,
+    /// Recipients of any list past or near their `expires` date.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub expiring: Vec<ExpiringRecipient>,
+    /// `config.toml` and recipient lists that no longer match
+    /// `manifest.toml`. Warnings only; `vaultic verify` fails on them.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unrecorded_changes: Vec<UnrecordedChange>,
+    pub environments: Vec<EnvStatus>,
+    pub local: LocalState,
+    pub audit: AuditStatus,
+    pub rotation: Vec<RotationStatus>,
+    /// Audit log activity worth a second look. Warnings only.
+    pub anomalies: Vec<AuditAnomaly>,
+}
+
+/// The current user's private key.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KeyStatus {
+    /// Path of the private key, or `GPG keyring`; `None` if there is none.
+    pub identity: Option<String>,
+    /// age public key or GPG fingerprint; `None` if there is no key or
+    /// it cannot be read.
+    pub public_key: Option<String>,
+    pub in_recipients: bool,
+    /// Every age and GPG key found on this machine.
+    pub identities: Vec<LocalIdentity>,
+}
+
+/// A recipient whose `expires` date in `members.toml` has passed or is
+/// within [`EXPIRY_NOTICE_DAYS`](member::EXPIRY_NOTICE_DAYS).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExpiringRecipient {
+    pub key: String,
+    pub expires: NaiveDate,
+    /// Negative once the date has passed.
+    pub days_left: i64,
+}
+
+/// One configured environment.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EnvStatus {
+    pub name: String,
+    pub file: String,
+    pub encrypted: bool,
+    pub size: Option<u64>,
+    /// Recipient list the environment is encrypted for.
+    pub recipients_file: String,
+    pub recipients: usize,
+    /// Upstream of a read-only mirror environment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mirror: Option<String>,
+}
+
+/// Files in the working directory. Variable counts are `None` when the
+/// file does not exist; the ignore flags are `None` when the ignore file
+/// does not exist.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LocalState {
+    pub env_variables: Option<usize>,
+    pub template_variables: Option<usize>,
+    pub env_in_gitignore: Option<bool>,
+    pub env_in_dockerignore: Option<bool>,
+    /// Whether the project has a Dockerfile or Compose file.
+    pub docker: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuditStatus {
+    pub enabled: bool,
+    pub log_file: String,
+    pub entries: usize,
+}
+
+/// Age of one environment under `rotation_days`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RotationStatus {
+    pub env: String,
+    pub last_rotated: Option<DateTime<Utc>>,
+    pub days_since_rotation: Option<i64>,
+    pub exceeds_policy: bool,
+}
+
+/// Unusual activity found in the audit log.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AuditAnomaly {
+    /// A production environment was decrypted by an author who matches
+    /// none of its recipient labels.
+    UnlabeledDecrypt {
+        env: String,
+        author: String,
+        timestamp: DateTime<Utc>,
+    },
+    /// Many decrypts by one author in a short window.
+    DecryptBurst {
+        author: String,
+        count: usize,
+        minutes: i64,
+        timestamp: DateTime<Utc>,
+    },
+    /// A key was removed and nothing was re-encrypted since, so it can
+    /// still open the current files.
+    KeyRemovedNotReencrypted {
+        detail: String,
+        timestamp: DateTime<Utc>,
+    },
+}
+
+impl AuditAnomaly {
+    /// When the activity happened (start of the window for a burst).
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        match self {
+            Self::UnlabeledDecrypt { timestamp, .. }
+            | Self::DecryptBurst { timestamp, .. }
+            | Self::KeyRemovedNotReencrypted { timestamp, .. } => *timestamp,
+        }
+    }
+
+    /// One-line description with what to check.
+    pub fn describe(&self) -> String {
+        let date = self.timestamp().format("%Y-%m-%d %H:%M");
+        match self {
+            Self::UnlabeledDecrypt { env, author, .. } => {
+                format!("{env} decrypted by '{author}' on {date}, who matches no recipient label")
+            }
+            Self::DecryptBurst {
+                author,
+                count,
+                minutes,
+                ..
+            } => format!("'{author}' decrypted {count} times within {minutes} minutes on {date}"),
+            Self::KeyRemovedNotReencrypted { detail, .. } => format!(
+                "Key removed on {date} ({detail}) but nothing re-encrypted since — \
+                 run 'vaultic encrypt --all'"
+            ),
+        }
+    }
+}
+
+/// Something in a [`StatusReport`] that needs fixing.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StatusProblem {
+    /// No usable private key, so nothing can be decrypted.
+    MissingKey,
+    /// The key exists but environments are not encrypted for it.
+    NotInRecipients { public_key: String },
+    /// `.env` is not listed in `.gitignore`.
+    EnvNotIgnored,
+    /// A recipient is past its `expires` date but still on a list.
+    ExpiredRecipient { key: String, expires: NaiveDate },
+}
+
+impl StatusProblem {
+    /// One-line description with the fix.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::MissingKey => "No private key — run 'vaultic keys setup'".to_string(),
+            Self::NotInRecipients { public_key } => {
+                format!(
+                    "Not in the recipients list — ask an admin to run: vaultic keys add {public_key}"
+                )
+            }
+            Self::EnvNotIgnored => {
+                ".env is not in .gitignore — secrets may be committed".to_string()
+            }
+            Self::ExpiredRecipient { key, expires } => format!(
+                "Recipient {key} expired on {expires} — run 'vaultic keys remove {key}', \
+                 then 'vaultic encrypt --all'"
+            ),
+        }
+    }
+}
+
+impl ExpiringRecipient {
+    /// E.g. `age1… expires in 5 day(s) on 2026-06-30`.
+    pub fn describe(&self) -> String {
+        format!(
+            "{} {}",
+            self.key,
+            member::describe_expiry(self.expires, self.days_left)
+        )
+    }
+}
+
+impl StatusReport {
+    /// Problems that make `vaultic status` fail, in report order.
+    pub fn problems(&self) -> Vec<StatusProblem> {
+        let mut problems = Vec::new();
+        match &self.key.public_key {
+            None => problems.push(StatusProblem::MissingKey),
+            Some(public_key) if !self.key.in_recipients => {
+                problems.push(StatusProblem::NotInRecipients {
+                    public_key: public_key.clone(),
+                })
+            }
+            Some(_) => {}
+        }
+        if self.local.env_in_gitignore != Some(true) {
+            problems.push(StatusProblem::EnvNotIgnored);
+        }
+        problems.extend(self.expiring.iter().filter(|r| r.days_left < 0).map(|r| {
+            StatusProblem::ExpiredRecipient {
+                key: r.key.clone(),
+                expires: r.expires,
+            }
+        }));
+        problems
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> StatusReport {
+        StatusReport {
+            version: "1".into(),
+            cipher: "age".into(),
+            cipher_plugin: None,
+            default_env: None,
+            key: KeyStatus {
+                identity: Some("keys.txt".into()),
+                public_key: Some("age1me".into()),
+                in_recipients: true,
+                identities: Vec::new(),
+            },
+            recipients: vec!["age1me".into()],
+            members: Vec::new(),
+            expiring: Vec::new(),
+            unrecorded_changes: Vec::new(),
+            environments: Vec::new(),
+            local: LocalState {
+                env_variables: Some(2),
+                template_variables: None,
+                env_in_gitignore: Some(true),
+                env_in_dockerignore: None,
+                docker: false,
+            },
+            audit: AuditStatus {
+                enabled: true,
+                log_file: "audit.log".into(),
+                entries: 0,
+            },
+            rotation: Vec::new(),
+            anomalies: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn healthy_project_has_no_problems() {
+        assert!(report().problems().is_empty());
+    }
+
+    #[test]
+    fn reports_key_and_gitignore_problems() {
+        let mut status = report();
+        status.key.in_recipients = false;
+        status.local.env_in_gitignore = None;
+        assert_eq!(
+            status.problems(),
+            vec![
+                StatusProblem::NotInRecipients {
+                    public_key: "age1me".into()
+                },
+                StatusProblem::EnvNotIgnored,
+            ]
+        );
+
+        status.key.public_key = None;
+        assert_eq!(status.problems()[0], StatusProblem::MissingKey);
+    }
+
+    #[test]
+    fn expired_recipients_are_problems_and_expiring_ones_are_not() {
+        let mut status = report();
+        let expires = NaiveDate::from_ymd_opt(2026, 6, 30).unwrap();
+        status.expiring = vec![
+            ExpiringRecipient {
+                key: "age1old".into(),
+                expires,
+                days_left: -3,
+            },
+            ExpiringRecipient {
+                key: "age1soon".into(),
+                expires,
+                days_left: 5,
+            },
+        ];
+        assert_eq!(
+            status.problems(),
+            vec![StatusProblem::ExpiredRecipient {
+                key: "age1old".into(),
+                expires
+            }]
+        );
+    }
+}
@@ -15,6 +15,17 @@ pub struct UpdateInfo {
     pub signature_url: String,
     /// URL to the release page (for changelog link).
     pub release_url: String,
+    /// Release notes (markdown body of the release), if any.
+    pub notes: Option<String>,
+}
+
+/// A newer release found by the passive startup check.
+#[derive(Debug, Clone)]
+pub struct AvailableUpdate {
+    /// Version string without the `v` prefix (e.g., "1.2.0").
+    pub version: String,
+    /// Top of the release notes, see [`summarize_notes`].
+    pub notes: Option<String>,
 }
 
 /// Partial structure for deserializing the GitHub Releases API response.
@@ -24,6 +35,9 @@ pub struct GitHubRelease {
     pub tag_name: String,
     /// URL to the release page on GitHub.
     pub html_url: String,
+    /// Release notes in markdown.
+    #[serde(default)]
+    pub body: Option<String>,
     /// List of downloadable assets attached to the release.
     pub assets: Vec<GitHubAsset>,
 }
@@ -44,6 +58,37 @@ pub struct UpdateCheckCache {
     pub checked_at: String,
     /// Latest version found (None if check failed).
     pub latest_version: Option<String>,
+    /// Summary of that version's release notes.
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+/// Last successful GitHub releases response, replayed on `304 Not Modified`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ReleaseCache {
+    /// `ETag` header returned with the response.
+    pub etag: String,
+    /// Raw JSON body of the response.
+    pub body: String,
+}
+
+/// First `max_lines` non-blank lines of release notes, with `…` appended
+/// when the notes are longer. Returns `None` for empty notes.
+pub fn summarize_notes(body: &str, max_lines: usize) -> Option<String> {
+    let lines: Vec<&str> = body
+        .lines()
+        .map(str::trim_end)
+        .filter(|l| !l.trim().is_empty())
+        .collect();
+    if lines.is_empty() || max_lines == 0 {
+        return None;
+    }
+
+    let mut summary = lines[..lines.len().min(max_lines)].join("\n");
+    if lines.len() > max_lines {
+        summary.push_str("\n…");
+    }
+    Some(summary)
 }
 
 /// Returns the expected asset name for the current platform.
@@ -71,6 +116,22 @@ pub fn current_version() -> semver::Version {
 mod tests {
     use super::*;
 
+    #[test]
+    fn summarize_notes_keeps_first_lines() {
+        let body = "## Security\r\n\r\n- Fix key leak\n- Bump age\n\n## Added\n- Thing";
+        assert_eq!(
+            summarize_notes(body, 3).unwrap(),
+            "## Security\n- Fix key leak\n- Bump age\n…"
+        );
+        assert_eq!(summarize_notes("one\ntwo", 5).unwrap(), "one\ntwo");
+    }
+
+    #[test]
+    fn summarize_notes_empty_body() {
+        assert_eq!(summarize_notes("\n  \n", 5), None);
+        assert_eq!(summarize_notes("text", 0), None);
+    }
+
     #[test]
     fn current_version_is_valid_semver() {
         let v = current_version();
@@ -110,9 +171,18 @@ mod tests {
         let cache = UpdateCheckCache {
             checked_at: "2026-02-28T12:00:00Z".to_string(),
             latest_version: Some("1.2.0".to_string()),
+            notes: Some("- Fix key leak".to_string()),
         };
         let json = serde_json::to_string(&cache).unwrap();
         let parsed: UpdateCheckCache = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed.latest_version, Some("1.2.0".to_string()));
+        assert_eq!(parsed.notes, Some("- Fix key leak".to_string()));
+    }
+
+    #[test]
+    fn update_check_cache_without_notes_still_parses() {
+        let json = r#"{"checked_at":"2026-02-28T12:00:00Z","latest_version":"1.2.0"}"#;
+        let parsed: UpdateCheckCache = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed.notes, None);
     }
 }
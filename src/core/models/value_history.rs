@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// How a variable changed between two encryptions of an environment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueChangeKind {
+    Added,
+    Modified,
+    Removed,
+}
+
+/// One change to one variable, stored in the encrypted history of an
+/// environment (`.vaultic/history/<env>.history.enc`, JSON lines).
+///
+/// Values are never stored, only their SHA-256, so a known value can be
+/// matched against the history without the history revealing it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValueHistoryEntry {
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub author: String,
+    pub email: Option<String>,
+    pub key: String,
+    pub change: ValueChangeKind,
+    /// Hash of the value before the change (`None` when added).
+    pub old_hash: Option<String>,
+    /// Hash of the value after the change (`None` when removed).
+    pub new_hash: Option<String>,
+}
+
+/// SHA-256 of a variable's value, hex encoded.
+pub fn value_hash(value: &str) -> String {
+    format!("{:x}", Sha256::digest(value.as_bytes()))
+}
@@ -0,0 +1,59 @@
+use serde::Serialize;
+
+/// A recipient stanza found in an age file header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeaderStanza {
+    /// `-> X25519 …`: the stanza does not reveal which key it is for.
+    X25519,
+    /// `-> ssh-ed25519 <tag> …` or `-> ssh-rsa <tag> …`: the tag is the
+    /// first four bytes of the SHA-256 of the SSH public key, base64.
+    Ssh { tag: String },
+}
+
+/// Whether a recipient can open an encrypted file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Access {
+    /// Proven: the SSH tag matches, or the local key decrypted the file.
+    Yes,
+    /// Proven or certain: no stanza for the key, or it was added after
+    /// the file was last encrypted.
+    No,
+    /// The header holds enough X25519 stanzas to include this key.
+    Likely,
+    /// The header holds fewer X25519 stanzas than listed age keys, so
+    /// some of them cannot decrypt but which ones is unknown.
+    Unknown,
+}
+
+/// One listed recipient of an environment and its access to the file.
+#[derive(Debug, Clone, Serialize)]
+pub struct RecipientAccess {
+    pub key: String,
+    pub label: Option<String>,
+    pub access: Access,
+    /// Why the access was decided that way.
+    pub reason: String,
+}
+
+/// Verification of one encrypted environment.
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvVerification {
+    pub env: String,
+    pub file: String,
+    pub recipients: Vec<RecipientAccess>,
+    /// Stanzas that match no listed recipient: keys that were removed
+    /// but can still open the file until it is re-encrypted.
+    pub unlisted_stanzas: usize,
+}
+
+impl EnvVerification {
+    /// Whether every listed recipient can decrypt and nobody else can.
+    pub fn is_ok(&self) -> bool {
+        self.unlisted_stanzas == 0
+            && self
+                .recipients
+                .iter()
+                .all(|r| matches!(r.access, Access::Yes | Access::Likely))
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::core::models::audit_entry::{AuditAction, AuditEntry};
+use crate::core::models::status_report::AuditAnomaly;
+
+/// How far back decrypt activity is reviewed.
+const RECENT_DAYS: i64 = 30;
+/// Decrypts by one author within [`BURST_MINUTES`] that count as a burst.
+const BURST_COUNT: usize = 10;
+const BURST_MINUTES: i64 = 10;
+
+/// Service that reviews the audit log for activity worth a second look.
+///
+/// A lightweight local review, not an intrusion detector: authors come
+/// from git config and the log lives in the repository, so anyone can
+/// forge or trim it.
+pub struct AuditAnomalyService;
+
+impl AuditAnomalyService {
+    /// Anomalies in `entries`, oldest first.
+    ///
+    /// `labels_for` returns the recipient labels of an environment; a
+    /// production decrypt is flagged when its author matches none of
+    /// them. Environments without any labels are not checked.
+    pub fn detect(
+        entries: &[AuditEntry],
+        labels_for: impl Fn(&str) -> Vec<String>,
+        now: DateTime<Utc>,
+    ) -> Vec<AuditAnomaly> {
+        let recent: Vec<&AuditEntry> = entries
+            .iter()
+            .filter(|e| now - e.timestamp <= Duration::days(RECENT_DAYS))
+            .collect();
+
+        let mut anomalies = Vec::new();
+        let mut labels: HashMap<String, Vec<String>> = HashMap::new();
+        for entry in &recent {
+            let Some(env) = decrypted_env(entry).filter(|env| is_production(env)) else {
+                continue;
+            };
+            let labels = labels
+                .entry(env.clone())
+                .or_insert_with(|| labels_for(&env).iter().map(|l| l.to_lowercase()).collect());
+            if !labels.is_empty() && !is_labeled(entry, labels) {
+                anomalies.push(AuditAnomaly::UnlabeledDecrypt {
+                    env,
+                    author: entry.author.clone(),
+                    timestamp: entry.timestamp,
+                });
+            }
+        }
+
+        anomalies.extend(bursts(&recent));
+
+        for (i, entry) in entries.iter().enumerate() {
+            if entry.action != AuditAction::KeyRemove {
+                continue;
+            }
+            let reencrypted = entries[i + 1..]
+                .iter()
+                .any(|e| matches!(e.action, AuditAction::Encrypt | AuditAction::KeyRotate));
+            if !reencrypted {
+                anomalies.push(AuditAnomaly::KeyRemovedNotReencrypted {
+                    detail: entry
+                        .detail
+                        .as_ref()
+                        .map(ToString::to_string)
+                        .unwrap_or_default(),
+                    timestamp: entry.timestamp,
+                });
+            }
+        }
+
+        anomalies.sort_by_key(|a| a.timestamp());
+        anomalies
+    }
+}
+
+/// The environment whose plaintext `entry` produced, if it is a decrypt,
+/// resolve or run.
+fn decrypted_env(entry: &AuditEntry) -> Option<String> {
+    if !matches!(
+        entry.action,
+        AuditAction::Decrypt | AuditAction::Resolve | AuditAction::Run
+    ) {
+        return None;
+    }
+    let file = entry.files.first()?;
+    Some(
+        file.trim_end_matches(".enc")
+            .trim_end_matches(".env")
+            .to_string(),
+    )
+}
+
+/// Whether `env` names a production environment (`prod`, `production`,
+/// `prod-eu`, ...).
+fn is_production(env: &str) -> bool {
+    env.starts_with("prod")
+}
+
+/// Whether the author or email of `entry` appears in one of the
+/// (lowercased) recipient labels.
+fn is_labeled(entry: &AuditEntry, labels: &[String]) -> bool {
+    let author = entry.author.to_lowercase();
+    let email = entry.email.as_deref().map(str::to_lowercase);
+    labels
+        .iter()
+        .any(|label| label.contains(&author) || email.as_deref().is_some_and(|e| label.contains(e)))
+}
+
+/// One anomaly per author for the first window of [`BURST_MINUTES`] that
+/// holds at least [`BURST_COUNT`] decrypts.
+fn bursts(entries: &[&AuditEntry]) -> Vec<AuditAnomaly> {
+    let mut by_author: HashMap<&str, Vec<DateTime<Utc>>> = HashMap::new();
+    for entry in entries {
+        if decrypted_env(entry).is_some() {
+            by_author
+                .entry(entry.author.as_str())
+                .or_default()
+                .push(entry.timestamp);
+        }
+    }
+
+    let mut anomalies = Vec::new();
+    for (author, mut times) in by_author {
+        times.sort();
+        let mut start = 0;
+        for end in 0..times.len() {
+            while times[end] - times[start] > Duration::minutes(BURST_MINUTES) {
+                start += 1;
+            }
+            if end + 1 - start >= BURST_COUNT {
+                let count = times[start..]
+                    .iter()
+                    .take_while(|t| **t - times[start] <= Duration::minutes(BURST_MINUTES))
+                    .count();
+                anomalies.push(AuditAnomaly::DecryptBurst {
+                    author: author.to_string(),
+                    count,
+                    minutes: BURST_MINUTES,
+                    timestamp: times[start],
+                });
+                break;
+            }
+        }
+    }
+    anomalies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(action: AuditAction, author: &str, file: &str, minutes_ago: i64) -> AuditEntry {
+        AuditEntry {
+            timestamp: now() - Duration::minutes(minutes_ago),
+            author: author.to_string(),
+            email: None,
+            action,
+            files: vec![file.to_string()],
+            detail: None,
+            state_hash: None,
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        "2026-03-01T12:00:00Z".parse().unwrap()
+    }
+
+    fn labels(_env: &str) -> Vec<String> {
+        vec!["Alice (laptop)".to_string()]
+    }
+
+    #[test]
+    fn flags_prod_decrypt_by_unlabeled_author() {
+        let entries = vec![
+            entry(AuditAction::Decrypt, "alice", "prod.env.enc", 30),
+            entry(AuditAction::Decrypt, "mallory", "prod.env.enc", 20),
+            entry(AuditAction::Decrypt, "mallory", "dev.env.enc", 10),
+            entry(
+                AuditAction::Decrypt,
+                "mallory",
+                "prod.env.enc",
+                60 * 24 * 40,
+            ),
+        ];
+        let anomalies = AuditAnomalyService::detect(&entries, labels, now());
+        assert_eq!(anomalies.len(), 1);
+        assert!(matches!(
+            &anomalies[0],
+            AuditAnomaly::UnlabeledDecrypt { env, author, .. } if env == "prod" && author == "mallory"
+        ));
+
+        let none = AuditAnomalyService::detect(&entries, |_| Vec::new(), now());
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn flags_decrypt_burst_once() {
+        let entries: Vec<AuditEntry> = (0..12)
+            .map(|i| entry(AuditAction::Run, "alice", "dev", 12 - i))
+            .collect();
+        let anomalies = AuditAnomalyService::detect(&entries, labels, now());
+        assert_eq!(anomalies.len(), 1);
+        assert!(matches!(
+            &anomalies[0],
+            AuditAnomaly::DecryptBurst { author, count: 11, .. } if author == "alice"
+        ));
+    }
+
+    #[test]
+    fn flags_key_removal_until_reencrypted() {
+        let mut entries = vec![entry(AuditAction::KeyRemove, "alice", "", 30)];
+        let anomalies = AuditAnomalyService::detect(&entries, labels, now());
+        assert!(matches!(
+            anomalies[..],
+            [AuditAnomaly::KeyRemovedNotReencrypted { .. }]
+        ));
+
+        entries.push(entry(AuditAction::Encrypt, "alice", "prod.env.enc", 10));
+        assert!(AuditAnomalyService::detect(&entries, labels, now()).is_empty());
+    }
+}
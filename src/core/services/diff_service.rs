@@ -1,7 +1,7 @@
 use std::collections::BTreeSet;
 
 use crate::core::errors::Result;
-use crate::core::models::diff_result::{DiffEntry, DiffKind, DiffResult};
+use crate::core::models::diff_result::{Charset, DiffEntry, DiffKind, DiffResult, ValueChange};
 use crate::core::models::secret_file::SecretFile;
 
 /// Compares two secret files and produces a structured diff.
@@ -12,7 +12,8 @@ impl DiffService {
     ///
     /// - Keys only in `left` are `Removed`
     /// - Keys only in `right` are `Added`
-    /// - Keys in both with different values are `Modified`
+    /// - Keys in both with different values are `Modified`, classified
+    ///   by length, charset, and URL host changes
     /// - Keys in both with the same value are omitted (no diff)
     ///
     /// Results are sorted alphabetically by key.
@@ -54,6 +55,7 @@ impl DiffService {
                         kind: DiffKind::Modified {
                             old_value: old.to_string(),
                             new_value: new.to_string(),
+                            changes: classify_change(old, new),
                         },
                     });
                 }
@@ -69,6 +71,48 @@ impl DiffService {
     }
 }
 
+/// Describe how `new` differs from `old` in shape, without exposing values.
+fn classify_change(old: &str, new: &str) -> Vec<ValueChange> {
+    let mut changes = Vec::new();
+
+    let (old_len, new_len) = (old.chars().count(), new.chars().count());
+    if old_len != new_len {
+        changes.push(ValueChange::Length {
+            old: old_len,
+            new: new_len,
+        });
+    }
+
+    let (old_set, new_set) = (Charset::of(old), Charset::of(new));
+    if old_set != new_set {
+        changes.push(ValueChange::Charset {
+            old: old_set,
+            new: new_set,
+        });
+    }
+
+    if let (Some(old_host), Some(new_host)) = (url_host(old), url_host(new))
+        && !old_host.eq_ignore_ascii_case(new_host)
+    {
+        changes.push(ValueChange::UrlHost);
+    }
+
+    changes
+}
+
+/// Extract the host of a `scheme://[user[:pass]@]host[:port][/...]` URL.
+fn url_host(value: &str) -> Option<&str> {
+    let (_, rest) = value.split_once("://")?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host_port = authority.rsplit_once('@').map_or(authority, |(_, h)| h);
+    let host = match host_port.strip_prefix('[') {
+        // IPv6 literal: [::1]:5432
+        Some(v6) => v6.split(']').next()?,
+        None => host_port.split(':').next()?,
+    };
+    (!host.is_empty()).then_some(host)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -141,10 +185,57 @@ mod tests {
             DiffKind::Modified {
                 old_value: "localhost".to_string(),
                 new_value: "rds.aws.com".to_string(),
+                changes: vec![
+                    ValueChange::Length { old: 9, new: 11 },
+                    ValueChange::Charset {
+                        old: Charset::Alphanumeric,
+                        new: Charset::Text,
+                    },
+                ],
             }
         );
     }
 
+    #[test]
+    fn classifies_hex_to_base64_rotation() {
+        let changes = classify_change("deadbeefdeadbeef", "3q2+7w==");
+        assert_eq!(
+            changes,
+            vec![
+                ValueChange::Length { old: 16, new: 8 },
+                ValueChange::Charset {
+                    old: Charset::Hex,
+                    new: Charset::Base64,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn classifies_url_host_change() {
+        let changes = classify_change(
+            "postgres://user:pw@db-a.internal:5432/app",
+            "postgres://user:pw@db-b.internal:5432/app",
+        );
+        assert_eq!(changes, vec![ValueChange::UrlHost]);
+    }
+
+    #[test]
+    fn same_shape_has_no_changes() {
+        assert!(classify_change("s3cr3tA", "p4ssw0B").is_empty());
+    }
+
+    #[test]
+    fn url_host_parsing() {
+        assert_eq!(
+            url_host("https://api.example.com/v1"),
+            Some("api.example.com")
+        );
+        assert_eq!(url_host("redis://:secret@cache:6379"), Some("cache"));
+        assert_eq!(url_host("postgres://[::1]:5432/db"), Some("::1"));
+        assert_eq!(url_host("localhost:5432"), None);
+    }
+
     #[test]
     fn mixed_changes() {
         let svc = DiffService;
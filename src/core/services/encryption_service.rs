@@ -1,9 +1,22 @@
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use sha2::{Digest, Sha256};
 
 use crate::core::errors::{Result, VaulticError};
+use crate::core::models::key_identity::KeyIdentity;
 use crate::core::traits::cipher::CipherBackend;
 use crate::core::traits::key_store::KeyStore;
 
+/// First line of a file stored with `storage = "per-value"`, followed by
+/// a fingerprint of the recipients the values are encrypted for.
+pub const PER_VALUE_HEADER: &str = "# vaultic:per-value";
+
+/// Marks an encrypted value (or comment) in a per-value file.
+const SEALED_PREFIX: &str = "vaultic:enc:";
+
 /// Orchestrates encrypt/decrypt operations by combining a
 /// `CipherBackend` with a `KeyStore`.
 pub struct EncryptionService<C: CipherBackend, K: KeyStore> {
@@ -78,12 +91,315 @@ impl<C: CipherBackend, K: KeyStore> EncryptionService<C, K> {
     /// Decrypt a file in memory and return the plaintext bytes.
     ///
     /// Useful for operations that need decrypted content without
-    /// writing it to disk (e.g. environment resolution).
+    /// writing it to disk (e.g. environment resolution). Handles both
+    /// whole-file and per-value storage.
     pub fn decrypt_to_bytes(&self, source: &Path) -> Result<Vec<u8>> {
         let ciphertext = std::fs::read(source).map_err(|_| VaulticError::FileNotFound {
             path: source.to_path_buf(),
         })?;
 
-        self.cipher.decrypt(&ciphertext)
+        decrypt_content(&self.cipher, &ciphertext)
+    }
+
+    /// Encrypt each line of dotenv `plaintext` separately and write the
+    /// result to `dest`, keeping keys, blank lines and ordering readable.
+    ///
+    /// The whole `KEY=value` line is sealed, not only the value, so a
+    /// sealed value moved under another key fails to decrypt.
+    ///
+    /// Values whose plaintext is unchanged since the file at `dest` was
+    /// written keep their old ciphertext (when the recipients are the
+    /// same), so a git diff shows exactly which keys changed.
+    pub fn encrypt_values_to(&self, plaintext: &[u8], dest: &Path) -> Result<()> {
+        let recipients = self.key_store.list()?;
+        if recipients.is_empty() {
+            return Err(VaulticError::EncryptionFailed {
+                reason: "No recipients configured. Run 'vaultic keys add' first.".into(),
+            });
+        }
+        let plaintext = std::str::from_utf8(plaintext).map_err(|_| not_dotenv(dest))?;
+
+        let header = format!("{PER_VALUE_HEADER} {}", recipients_fingerprint(&recipients));
+        let previous = std::fs::read_to_string(dest)
+            .ok()
+            .filter(|old| old.lines().next() == Some(header.as_str()))
+            .map(|old| self.previous_values(&old))
+            .unwrap_or_default();
+
+        let mut sealed = vec![header];
+        for line in plaintext.split('\n') {
+            let prefix = match split_line(line) {
+                _ if line.trim().is_empty() => {
+                    sealed.push(line.to_string());
+                    continue;
+                }
+                Some((prefix, _)) => prefix,
+                None => return Err(not_dotenv(dest)),
+            };
+            let value = match previous.get(line) {
+                Some(value) => value.clone(),
+                None => {
+                    let ciphertext = self.cipher.encrypt(line.as_bytes(), &recipients)?;
+                    format!("{SEALED_PREFIX}{}", STANDARD.encode(ciphertext))
+                }
+            };
+            sealed.push(format!("{prefix}{value}"));
+        }
+
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(dest, sealed.join("\n"))?;
+
+        Ok(())
+    }
+
+    /// Sealed values of an existing per-value file, by plaintext line.
+    /// Lines that cannot be decrypted are left out and get encrypted
+    /// afresh.
+    fn previous_values(&self, content: &str) -> HashMap<String, String> {
+        content
+            .lines()
+            .skip(1)
+            .filter_map(|line| {
+                let (prefix, sealed) = split_sealed(line)?;
+                let plaintext = open_line(&self.cipher, prefix, sealed).ok()?;
+                Some((plaintext, format!("{SEALED_PREFIX}{sealed}")))
+            })
+            .collect()
+    }
+}
+
+/// Whether `content` was written with `storage = "per-value"`.
+pub fn is_per_value(content: &[u8]) -> bool {
+    content.starts_with(PER_VALUE_HEADER.as_bytes())
+}
+
+/// Decrypt ciphertext from an encrypted file, whichever way it is stored.
+///
+/// In a per-value file, every line after the header must be blank or
+/// sealed: a plaintext line added by hand is refused rather than passed
+/// through as if it had been encrypted.
+pub fn decrypt_content<C: CipherBackend>(cipher: &C, content: &[u8]) -> Result<Vec<u8>> {
+    if !is_per_value(content) {
+        return cipher.decrypt(content);
+    }
+
+    let content = String::from_utf8_lossy(content);
+    let mut plaintext = Vec::new();
+    for (n, line) in content.split('\n').enumerate().skip(1) {
+        if line.trim().is_empty() {
+            plaintext.push(line.to_string());
+            continue;
+        }
+        let (prefix, sealed) =
+            split_sealed(line).ok_or_else(|| VaulticError::EncryptionFailed {
+                reason: format!(
+                    "Line {} of a per-value file is not encrypted; \
+                     it was added outside Vaultic",
+                    n + 1
+                ),
+            })?;
+        plaintext.push(open_line(cipher, prefix, sealed)?);
+    }
+    Ok(plaintext.join("\n").into_bytes())
+}
+
+/// Split a plaintext dotenv line into the part kept readable and the
+/// rest: `KEY=` and the raw value, or `#` and the comment.
+fn split_line(line: &str) -> Option<(&str, &str)> {
+    if line.trim_start().starts_with('#') {
+        return Some(("#", line));
+    }
+    let eq = line.find('=')?;
+    Some(line.split_at(eq + 1))
+}
+
+/// The readable prefix and base64 ciphertext of a sealed line.
+fn split_sealed(line: &str) -> Option<(&str, &str)> {
+    let start = line.find(SEALED_PREFIX)?;
+    let prefix = &line[..start];
+    (prefix == "#" || prefix.ends_with('=')).then(|| (prefix, &line[start + SEALED_PREFIX.len()..]))
+}
+
+/// Decrypt a sealed line and check that it is the line of its readable
+/// prefix, so a value moved under another key is refused.
+fn open_line<C: CipherBackend>(cipher: &C, prefix: &str, sealed: &str) -> Result<String> {
+    let line = open_value(cipher, sealed)?;
+    if split_line(&line).map(|(p, _)| p) != Some(prefix) {
+        return Err(VaulticError::EncryptionFailed {
+            reason: format!(
+                "The sealed value under '{}' belongs to another line; \
+                 it was moved outside Vaultic",
+                prefix.trim_end_matches('=')
+            ),
+        });
+    }
+    Ok(line)
+}
+
+fn open_value<C: CipherBackend>(cipher: &C, sealed: &str) -> Result<String> {
+    let ciphertext =
+        STANDARD
+            .decode(sealed.trim())
+            .map_err(|e| VaulticError::EncryptionFailed {
+                reason: format!("Corrupt per-value ciphertext: {e}"),
+            })?;
+    String::from_utf8(cipher.decrypt(&ciphertext)?).map_err(|_| VaulticError::EncryptionFailed {
+        reason: "Decrypted value is not valid UTF-8".into(),
+    })
+}
+
+/// Short hash of the recipient list, so values are only reused while
+/// they are encrypted for the same keys.
+fn recipients_fingerprint(recipients: &[KeyIdentity]) -> String {
+    let mut keys: Vec<&str> = recipients.iter().map(|r| r.public_key.as_str()).collect();
+    keys.sort_unstable();
+    let digest = Sha256::digest(keys.join("\n").as_bytes());
+    format!("recipients={}", &format!("{digest:x}")[..16])
+}
+
+fn not_dotenv(path: &Path) -> VaulticError {
+    VaulticError::ParseError {
+        file: PathBuf::from(path),
+        detail: "storage = \"per-value\" needs KEY=value lines (dotenv format)".into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Reversible "cipher" whose output differs on every call, like age.
+    #[derive(Default)]
+    struct CountingCipher(AtomicUsize);
+
+    impl CipherBackend for CountingCipher {
+        fn encrypt(&self, plaintext: &[u8], _: &[KeyIdentity]) -> Result<Vec<u8>> {
+            let n = self.0.fetch_add(1, Ordering::SeqCst);
+            Ok([format!("{n}:").as_bytes(), plaintext].concat())
+        }
+
+        fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>> {
+            let colon = ciphertext.iter().position(|&b| b == b':').unwrap();
+            Ok(ciphertext[colon + 1..].to_vec())
+        }
+
+        fn name(&self) -> &str {
+            "counting"
+        }
+    }
+
+    struct OneRecipient;
+
+    impl KeyStore for OneRecipient {
+        fn add(&self, _: &KeyIdentity) -> Result<()> {
+            Ok(())
+        }
+
+        fn list(&self) -> Result<Vec<KeyIdentity>> {
+            Ok(vec![KeyIdentity {
+                public_key: "age1me".into(),
+                label: None,
+                added_at: None,
+            }])
+        }
+
+        fn remove(&self, _: &str) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn service() -> EncryptionService<CountingCipher, OneRecipient> {
+        EncryptionService {
+            cipher: CountingCipher::default(),
+            key_store: OneRecipient,
+        }
+    }
+
+    #[test]
+    fn per_value_round_trip_keeps_keys_readable() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("dev.env.enc");
+        let plaintext = "# db\nexport DB_PASS=\"s3cret\"\n\nAPI_KEY=abc\n";
+
+        service()
+            .encrypt_values_to(plaintext.as_bytes(), &dest)
+            .unwrap();
+        let sealed = std::fs::read_to_string(&dest).unwrap();
+        assert!(sealed.starts_with(PER_VALUE_HEADER));
+        assert!(sealed.contains("\nexport DB_PASS=vaultic:enc:"));
+        assert!(sealed.contains("\nAPI_KEY=vaultic:enc:"));
+        assert!(!sealed.contains("s3cret") && !sealed.contains("# db"));
+
+        assert!(is_per_value(sealed.as_bytes()));
+        let opened = service().decrypt_to_bytes(&dest).unwrap();
+        assert_eq!(String::from_utf8(opened).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn per_value_reuses_ciphertext_of_unchanged_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("dev.env.enc");
+        let service = service();
+
+        service.encrypt_values_to(b"A=1\nB=2", &dest).unwrap();
+        let before = std::fs::read_to_string(&dest).unwrap();
+        service.encrypt_values_to(b"A=1\nB=3", &dest).unwrap();
+        let after = std::fs::read_to_string(&dest).unwrap();
+
+        let line = |s: &str, key: &str| s.lines().find(|l| l.starts_with(key)).unwrap().to_string();
+        assert_eq!(line(&before, "A="), line(&after, "A="));
+        assert_ne!(line(&before, "B="), line(&after, "B="));
+    }
+
+    #[test]
+    fn per_value_rejects_non_dotenv_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = service()
+            .encrypt_values_to(b"db:\n  host: x\n", &dir.path().join("dev.enc"))
+            .unwrap_err();
+        assert!(err.to_string().contains("dotenv"));
+    }
+
+    #[test]
+    fn per_value_refuses_values_moved_to_another_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("prod.env.enc");
+        service()
+            .encrypt_values_to(b"API_URL=https://api\nDB_PASS=s3cret\n", &dest)
+            .unwrap();
+
+        let sealed = std::fs::read_to_string(&dest).unwrap();
+        let value = |key: &str| {
+            let line = sealed.lines().find(|l| l.starts_with(key)).unwrap();
+            line[key.len()..].to_string()
+        };
+        let swapped = sealed.replace(&value("API_URL="), &value("DB_PASS="));
+        std::fs::write(&dest, swapped).unwrap();
+
+        let err = service().decrypt_to_bytes(&dest).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("under 'API_URL' belongs to another line")
+        );
+    }
+
+    #[test]
+    fn per_value_refuses_plaintext_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("prod.env.enc");
+        service().encrypt_values_to(b"A=1\n", &dest).unwrap();
+
+        let mut sealed = std::fs::read_to_string(&dest).unwrap();
+        sealed.push_str("DB_HOST=evil\n");
+        std::fs::write(&dest, sealed).unwrap();
+
+        let err = service().decrypt_to_bytes(&dest).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Line 3 of a per-value file is not encrypted")
+        );
     }
 }
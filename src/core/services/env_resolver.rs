@@ -69,22 +69,10 @@ impl EnvResolver {
                 });
             }
 
-            let entry = config.environments.get(&current).ok_or_else(|| {
-                let mut available: Vec<_> = config.environments.keys().collect();
-                available.sort();
-                VaulticError::EnvironmentNotFound {
-                    name: current.clone(),
-                    available: if available.is_empty() {
-                        "(none defined)".to_string()
-                    } else {
-                        available
-                            .iter()
-                            .map(|s| s.as_str())
-                            .collect::<Vec<_>>()
-                            .join(", ")
-                    },
-                }
-            })?;
+            let entry = config
+                .environments
+                .get(&current)
+                .ok_or_else(|| config.environment_not_found(&current))?;
 
             visited.insert(current.clone());
             chain.push(current.clone());
@@ -100,6 +88,17 @@ impl EnvResolver {
         Ok(chain)
     }
 
+    /// Layer `child` over `parent`, the same environment resolved in an
+    /// enclosing project: the child's values win, and the parent's layers
+    /// come first.
+    pub fn extend(&self, parent: Environment, child: Environment) -> Environment {
+        Environment {
+            name: child.name,
+            resolved: Self::merge(&parent.resolved, &child.resolved),
+            layers: parent.layers.into_iter().chain(child.layers).collect(),
+        }
+    }
+
     /// Merge two secret files: base + overlay.
     ///
     /// 1. Start with all entries from base.
@@ -180,6 +179,10 @@ mod tests {
                     file: file.map(|f| f.to_string()),
                     inherits: inherits.map(|i| i.to_string()),
                     template: None,
+                    format: None,
+                    mirror: None,
+                    review: false,
+                    threshold: None,
                 },
             );
         }
@@ -188,9 +191,12 @@ mod tests {
                 version: "0.1.0".to_string(),
                 format_version: 1,
                 default_cipher: "age".to_string(),
-                default_env: "dev".to_string(),
+                default_env: Some("dev".to_string()),
                 template: None,
+                identity: None,
                 rotation_days: None,
+                storage: Default::default(),
+                extends_parent: false,
             },
             environments,
             audit: Some(AuditSection {
@@ -198,6 +204,18 @@ mod tests {
                 log_file: "audit.log".to_string(),
             }),
             validation: None,
+            owners: None,
+            test_env: None,
+            recipients: None,
+            aliases: None,
+            outputs: None,
+            history: None,
+            ui: None,
+            references: None,
+            leases: None,
+            gpg: None,
+            hooks: None,
+            policy: None,
         }
     }
 
@@ -339,6 +357,23 @@ mod tests {
         assert!(err.contains("nonexistent"));
     }
 
+    #[test]
+    fn resolve_missing_environment_lists_available_sorted() {
+        let resolver = EnvResolver;
+        let config = make_config(&[
+            ("prod", None, Some("base")),
+            ("base", None, None),
+            ("dev", None, Some("base")),
+        ]);
+
+        let err = resolver
+            .resolve("dve", &config, &HashMap::new())
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("Available environments: base, dev, prod"));
+    }
+
     #[test]
     fn resolve_missing_parent_fails() {
         let resolver = EnvResolver;
@@ -396,4 +431,27 @@ mod tests {
         assert!(matches!(result.lines[0], Line::Comment(_)));
         assert_eq!(result.get("DB"), Some("rds.aws.com"));
     }
+
+    #[test]
+    fn extend_layers_child_over_parent() {
+        let parent = Environment {
+            name: "prod".into(),
+            resolved: make_file(&[("LOG_LEVEL", "warn"), ("DB", "shared-db")]),
+            layers: vec!["../../.vaultic:base".into(), "../../.vaultic:prod".into()],
+        };
+        let child = Environment {
+            name: "prod".into(),
+            resolved: make_file(&[("DB", "web-db")]),
+            layers: vec!["prod".into()],
+        };
+
+        let env = EnvResolver.extend(parent, child);
+
+        assert_eq!(env.resolved.get("DB"), Some("web-db"));
+        assert_eq!(env.resolved.get("LOG_LEVEL"), Some("warn"));
+        assert_eq!(
+            env.layers,
+            vec!["../../.vaultic:base", "../../.vaultic:prod", "prod"]
+        );
+    }
 }
@@ -0,0 +1,108 @@
+use crate::core::errors::Result;
+use crate::core::models::diff_result::DiffKind;
+use crate::core::models::secret_file::SecretFile;
+use crate::core::models::value_history::{ValueChangeKind, ValueHistoryEntry, value_hash};
+use crate::core::services::diff_service::DiffService;
+
+/// Builds value history entries from two versions of an environment.
+pub struct HistoryService;
+
+impl HistoryService {
+    /// One entry per variable that was added, modified or removed
+    /// between `old` and `new`, sorted by key and stamped with the given
+    /// time and author.
+    pub fn changes(
+        &self,
+        old: &SecretFile,
+        new: &SecretFile,
+        timestamp: chrono::DateTime<chrono::Utc>,
+        author: &str,
+        email: Option<&str>,
+    ) -> Result<Vec<ValueHistoryEntry>> {
+        let diff = DiffService.diff(old, new, "old", "new")?;
+        Ok(diff
+            .entries
+            .into_iter()
+            .map(|entry| {
+                let (change, old_hash, new_hash) = match entry.kind {
+                    DiffKind::Added => (
+                        ValueChangeKind::Added,
+                        None,
+                        new.get(&entry.key).map(value_hash),
+                    ),
+                    DiffKind::Removed => (
+                        ValueChangeKind::Removed,
+                        old.get(&entry.key).map(value_hash),
+                        None,
+                    ),
+                    DiffKind::Modified {
+                        old_value,
+                        new_value,
+                        ..
+                    } => (
+                        ValueChangeKind::Modified,
+                        Some(value_hash(&old_value)),
+                        Some(value_hash(&new_value)),
+                    ),
+                };
+                ValueHistoryEntry {
+                    timestamp,
+                    author: author.to_string(),
+                    email: email.map(String::from),
+                    key: entry.key,
+                    change,
+                    old_hash,
+                    new_hash,
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::secret_file::{Line, SecretEntry};
+
+    fn file(pairs: &[(&str, &str)]) -> SecretFile {
+        SecretFile {
+            lines: pairs
+                .iter()
+                .enumerate()
+                .map(|(i, (key, value))| {
+                    Line::Entry(SecretEntry {
+                        key: key.to_string(),
+                        value: value.to_string(),
+                        comment: None,
+                        line_number: i + 1,
+                    })
+                })
+                .collect(),
+            source_path: None,
+        }
+    }
+
+    #[test]
+    fn records_added_modified_and_removed_keys() {
+        let old = file(&[("A", "1"), ("B", "2"), ("SAME", "x")]);
+        let new = file(&[("A", "3"), ("C", "4"), ("SAME", "x")]);
+        let now = chrono::Utc::now();
+
+        let changes = HistoryService
+            .changes(&old, &new, now, "alice", None)
+            .unwrap();
+        let summary: Vec<_> = changes.iter().map(|c| (c.key.as_str(), c.change)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("A", ValueChangeKind::Modified),
+                ("B", ValueChangeKind::Removed),
+                ("C", ValueChangeKind::Added),
+            ]
+        );
+        assert_eq!(changes[0].old_hash, Some(value_hash("1")));
+        assert_eq!(changes[0].new_hash, Some(value_hash("3")));
+        assert_eq!(changes[1].new_hash, None);
+        assert_eq!(changes[2].old_hash, None);
+    }
+}
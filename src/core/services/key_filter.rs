@@ -0,0 +1,140 @@
+use crate::core::models::secret_file::{Line, SecretFile};
+
+/// Selects the keys a consumer may receive (`--only` / `--except`).
+///
+/// Patterns are globs where `*` matches any run of characters
+/// (`DB_*`, `*_URL`, `NEXT_PUBLIC_*`); anything else must match exactly.
+#[derive(Debug, Clone, Default)]
+pub struct KeyFilter {
+    only: Vec<String>,
+    except: Vec<String>,
+}
+
+impl KeyFilter {
+    /// Build a filter from comma-separated pattern lists, e.g.
+    /// `"DB_*,REDIS_URL"`. `None` or empty lists do not filter.
+    pub fn new(only: Option<&str>, except: Option<&str>) -> Self {
+        Self {
+            only: split(only),
+            except: split(except),
+        }
+    }
+
+    /// Whether the filter lets every key through.
+    pub fn is_empty(&self) -> bool {
+        self.only.is_empty() && self.except.is_empty()
+    }
+
+    /// Whether `key` matches `only` (when given) and no `except` pattern.
+    pub fn allows(&self, key: &str) -> bool {
+        (self.only.is_empty() || self.only.iter().any(|p| glob_match(p, key)))
+            && !self.except.iter().any(|p| glob_match(p, key))
+    }
+
+    /// Copy of `file` without the entries this filter rejects. Comments
+    /// and blank lines are kept.
+    pub fn apply(&self, file: &SecretFile) -> SecretFile {
+        SecretFile {
+            lines: file
+                .lines
+                .iter()
+                .filter(|line| match line {
+                    Line::Entry(entry) => self.allows(&entry.key),
+                    _ => true,
+                })
+                .cloned()
+                .collect(),
+            source_path: file.source_path.clone(),
+        }
+    }
+
+    /// Short description for audit entries, e.g. `only DB_*; except DB_ADMIN_*`.
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.only.is_empty() {
+            parts.push(format!("only {}", self.only.join(",")));
+        }
+        if !self.except.is_empty() {
+            parts.push(format!("except {}", self.except.join(",")));
+        }
+        parts.join("; ")
+    }
+}
+
+fn split(patterns: Option<&str>) -> Vec<String> {
+    patterns
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Match `key` against `pattern`, where `*` matches any run of characters.
+pub fn glob_match(pattern: &str, key: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = key.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No `*`: exact match
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::secret_file::SecretEntry;
+
+    #[test]
+    fn glob_match_supports_wildcards_anywhere() {
+        assert!(glob_match("DB_*", "DB_URL"));
+        assert!(glob_match("*_URL", "REDIS_URL"));
+        assert!(glob_match("NEXT_*_KEY", "NEXT_PUBLIC_KEY"));
+        assert!(glob_match("*", "ANYTHING"));
+        assert!(glob_match("REDIS_URL", "REDIS_URL"));
+        assert!(!glob_match("REDIS_URL", "REDIS_URL2"));
+        assert!(!glob_match("DB_*", "ADMIN_DB_URL"));
+        assert!(!glob_match("A*A", "A"));
+    }
+
+    #[test]
+    fn only_and_except_combine() {
+        let filter = KeyFilter::new(Some("DB_*, REDIS_URL"), Some("DB_ADMIN_*"));
+        assert!(filter.allows("DB_URL"));
+        assert!(filter.allows("REDIS_URL"));
+        assert!(!filter.allows("DB_ADMIN_PASSWORD"));
+        assert!(!filter.allows("STRIPE_KEY"));
+        assert_eq!(filter.describe(), "only DB_*,REDIS_URL; except DB_ADMIN_*");
+        assert!(KeyFilter::new(None, Some("")).is_empty());
+    }
+
+    #[test]
+    fn apply_drops_rejected_entries() {
+        let entry = |key: &str, n| {
+            Line::Entry(SecretEntry {
+                key: key.into(),
+                value: "v".into(),
+                comment: None,
+                line_number: n,
+            })
+        };
+        let file = SecretFile {
+            lines: vec![entry("API_URL", 1), entry("STRIPE_KEY", 2)],
+            source_path: None,
+        };
+        let out = KeyFilter::new(None, Some("STRIPE_*")).apply(&file);
+        assert_eq!(out.keys(), vec!["API_URL"]);
+    }
+}
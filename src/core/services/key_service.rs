@@ -23,3 +23,73 @@ impl<K: KeyStore> KeyService<K> {
         self.store.remove(public_key)
     }
 }
+
+/// Parse an allow-list of people who should hold keys.
+///
+/// One email or label per line; blank lines and `#` comments are skipped.
+/// Entries are lowercased for case-insensitive matching.
+pub fn parse_roster(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(|line| line.split_once('#').map_or(line, |(entry, _)| entry))
+        .map(|entry| entry.trim().to_lowercase())
+        .filter(|entry| !entry.is_empty())
+        .collect()
+}
+
+/// Recipients that match no roster entry.
+///
+/// A recipient matches when its public key (e.g. a GPG email), its label,
+/// or an email inside its label (`Alice <alice@corp.com>`) is on the roster.
+pub fn off_roster<'a>(keys: &'a [KeyIdentity], roster: &[String]) -> Vec<&'a KeyIdentity> {
+    let listed = |candidate: &str| roster.iter().any(|r| *r == candidate.to_lowercase());
+    keys.iter()
+        .filter(|ki| {
+            let label = ki.label.as_deref().unwrap_or("");
+            let on_roster = listed(&ki.public_key)
+                || (!label.is_empty() && listed(label))
+                || label
+                    .split(|c: char| c.is_whitespace() || "<>,;()".contains(c))
+                    .filter(|token| token.contains('@'))
+                    .any(listed);
+            !on_roster
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(public_key: &str, label: Option<&str>) -> KeyIdentity {
+        KeyIdentity {
+            public_key: public_key.to_string(),
+            label: label.map(String::from),
+            added_at: None,
+        }
+    }
+
+    #[test]
+    fn parse_roster_skips_comments_and_lowercases() {
+        let roster = parse_roster("# HR export\nAlice@Corp.com\n\n  bob  # contractor\n");
+        assert_eq!(roster, vec!["alice@corp.com", "bob"]);
+    }
+
+    #[test]
+    fn off_roster_matches_key_label_and_label_email() {
+        let keys = vec![
+            key("alice@corp.com", None),
+            key("age1bob", Some("Bob")),
+            key("age1carol", Some("Carol <carol@corp.com>")),
+            key("age1mallory", Some("mallory@corp.com")),
+            key("age1nolabel", None),
+        ];
+        let roster = parse_roster("ALICE@corp.com\nbob\ncarol@corp.com");
+
+        let missing: Vec<&str> = off_roster(&keys, &roster)
+            .iter()
+            .map(|ki| ki.public_key.as_str())
+            .collect();
+        assert_eq!(missing, vec!["age1mallory", "age1nolabel"]);
+    }
+}
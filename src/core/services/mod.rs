@@ -1,9 +1,19 @@
+pub mod audit_anomaly_service;
 pub mod check_service;
 pub mod diff_service;
 pub mod encryption_service;
 pub mod env_resolver;
+pub mod history_service;
+pub mod key_filter;
 pub mod key_service;
+pub mod policy_service;
+pub mod reference_service;
+pub mod scan_service;
 pub mod secret_age_service;
+pub mod shamir;
+pub mod sync_service;
 pub mod template_resolver;
 pub mod template_sync_service;
+pub mod test_env_service;
 pub mod validation_service;
+pub mod verify_service;
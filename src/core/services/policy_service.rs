@@ -0,0 +1,216 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::config::app_config::PolicySection;
+use crate::core::models::secret_file::SecretFile;
+use crate::core::models::secret_ref::SecretRef;
+use crate::core::services::key_filter::glob_match;
+
+/// Values refused in strict environments whatever `[policy]` adds.
+pub const WEAK_VALUES: &[&str] = &[
+    "password",
+    "passwd",
+    "changeme",
+    "secret",
+    "test",
+    "example",
+    "admin",
+    "default",
+    "dummy",
+    "placeholder",
+    "todo",
+    "letmein",
+    "123456",
+    "xxx",
+];
+
+/// Which `[policy]` rule a value broke.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyRule {
+    /// A `secret_keys` value shorter than `min_length`.
+    MinLength,
+    /// A placeholder value in a strict environment.
+    WeakValue,
+    /// An environment not re-encrypted within `max_age_days`.
+    RotationAge,
+}
+
+impl PolicyRule {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::MinLength => "min-length",
+            Self::WeakValue => "weak-value",
+            Self::RotationAge => "rotation-age",
+        }
+    }
+}
+
+/// One broken rule. Never carries the value itself.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PolicyViolation {
+    pub env: String,
+    /// Offending variable; `None` for environment-wide rules.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    pub rule: PolicyRule,
+    pub detail: String,
+}
+
+/// Service that holds secret values to the `[policy]` rules.
+///
+/// References (`op://`, `vault://`) are skipped: their values live in
+/// the provider. So are empty values, which `vaultic check` reports.
+pub struct PolicyService;
+
+impl PolicyService {
+    /// Length and weak-value violations in `file`, which holds `env`.
+    pub fn check_values(
+        env: &str,
+        file: &SecretFile,
+        policy: &PolicySection,
+    ) -> Vec<PolicyViolation> {
+        let strict = policy.strict_envs.iter().any(|e| e == env);
+        let mut violations = Vec::new();
+        for entry in file.entries() {
+            let value = entry.value.trim();
+            if value.is_empty() || SecretRef::parse(value).is_some() {
+                continue;
+            }
+            let violation = |rule, detail| PolicyViolation {
+                env: env.to_string(),
+                key: Some(entry.key.clone()),
+                rule,
+                detail,
+            };
+            if strict && Self::is_weak(value, &policy.weak_values) {
+                violations.push(violation(
+                    PolicyRule::WeakValue,
+                    format!("placeholder value not allowed in {env}"),
+                ));
+            }
+            let length = value.chars().count();
+            if length < policy.min_length
+                && policy
+                    .secret_keys
+                    .iter()
+                    .any(|pattern| glob_match(pattern, &entry.key))
+            {
+                violations.push(violation(
+                    PolicyRule::MinLength,
+                    format!(
+                        "{length} characters, at least {} required",
+                        policy.min_length
+                    ),
+                ));
+            }
+        }
+        violations
+    }
+
+    /// A rotation violation when `env` was last encrypted more than
+    /// `max_age_days` before `now`. Environments the audit log has no
+    /// encryption for are not judged.
+    pub fn check_age(
+        env: &str,
+        last_encrypted: Option<DateTime<Utc>>,
+        policy: &PolicySection,
+        now: DateTime<Utc>,
+    ) -> Option<PolicyViolation> {
+        let max_days = policy.max_age_days?;
+        let days = (now - last_encrypted?).num_days();
+        (days > i64::from(max_days)).then(|| PolicyViolation {
+            env: env.to_string(),
+            key: None,
+            rule: PolicyRule::RotationAge,
+            detail: format!("last encrypted {days} days ago, at most {max_days} allowed"),
+        })
+    }
+
+    /// Whether `value` is a known placeholder, ignoring case and trailing
+    /// digits (`Password123` counts as `password`).
+    fn is_weak(value: &str, extra: &[String]) -> bool {
+        let lower = value.to_lowercase();
+        let stem = lower.trim_end_matches(|c: char| c.is_ascii_digit());
+        WEAK_VALUES
+            .iter()
+            .copied()
+            .chain(extra.iter().map(String::as_str))
+            .any(|weak| {
+                let weak = weak.to_lowercase();
+                lower == weak || (!stem.is_empty() && stem == weak)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::parsers::dotenv_parser::DotenvParser;
+    use crate::core::traits::parser::ConfigParser;
+
+    fn file(content: &str) -> SecretFile {
+        DotenvParser.parse(content).unwrap()
+    }
+
+    fn rules(violations: &[PolicyViolation]) -> Vec<(&str, PolicyRule)> {
+        violations
+            .iter()
+            .map(|v| (v.key.as_deref().unwrap_or(""), v.rule))
+            .collect()
+    }
+
+    #[test]
+    fn short_secrets_break_min_length() {
+        let file =
+            file("JWT_SECRET=short\nSTRIPE_KEY=sk_live_0123456789abcdef\nPORT=80\nEMPTY_KEY=\n");
+        let violations = PolicyService::check_values("dev", &file, &PolicySection::default());
+        assert_eq!(rules(&violations), [("JWT_SECRET", PolicyRule::MinLength)]);
+        assert_eq!(violations[0].detail, "5 characters, at least 16 required");
+    }
+
+    #[test]
+    fn placeholders_are_refused_in_strict_envs_only() {
+        let file = file("DB_PASSWORD=Password123\nAPI_TOKEN=changeme\nNAME=acme\n");
+        let policy = PolicySection::default();
+        assert!(PolicyService::check_values("dev", &file, &policy).is_empty());
+        assert_eq!(
+            rules(&PolicyService::check_values("prod", &file, &policy)),
+            [
+                ("DB_PASSWORD", PolicyRule::WeakValue),
+                ("API_TOKEN", PolicyRule::WeakValue)
+            ]
+        );
+
+        let policy = PolicySection {
+            weak_values: vec!["ACME".into()],
+            ..PolicySection::default()
+        };
+        assert_eq!(PolicyService::check_values("prod", &file, &policy).len(), 3);
+    }
+
+    #[test]
+    fn references_are_skipped() {
+        let file = file("API_KEY=op://Prod/Stripe/key\n");
+        assert!(PolicyService::check_values("prod", &file, &PolicySection::default()).is_empty());
+    }
+
+    #[test]
+    fn rotation_age_uses_last_encryption() {
+        let now = Utc::now();
+        let policy = PolicySection {
+            max_age_days: Some(90),
+            ..PolicySection::default()
+        };
+        let old = now - chrono::Duration::days(120);
+        let recent = now - chrono::Duration::days(10);
+
+        let violation = PolicyService::check_age("prod", Some(old), &policy, now).unwrap();
+        assert_eq!(violation.rule, PolicyRule::RotationAge);
+        assert!(PolicyService::check_age("prod", Some(recent), &policy, now).is_none());
+        assert!(PolicyService::check_age("prod", None, &policy, now).is_none());
+        assert!(
+            PolicyService::check_age("prod", Some(old), &PolicySection::default(), now).is_none()
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use crate::core::errors::{Result, VaulticError};
+use crate::core::models::secret_file::{Line, SecretFile};
+use crate::core::models::secret_ref::SecretRef;
+use crate::core::traits::secret_provider::SecretProvider;
+
+/// Service that replaces reference values (`op://…`, `vault://…`) with
+/// the secrets they point at.
+pub struct ReferenceService {
+    /// Configured providers; a reference whose scheme has none fails.
+    pub providers: Vec<Box<dyn SecretProvider>>,
+}
+
+impl ReferenceService {
+    /// Keys of `file` whose value is a reference, in file order.
+    pub fn references(file: &SecretFile) -> Vec<(String, SecretRef)> {
+        file.entries()
+            .filter_map(|e| Some((e.key.clone(), SecretRef::parse(&e.value)?)))
+            .collect()
+    }
+
+    /// A copy of `file` with every reference replaced by its value, and
+    /// the number of values fetched. Stops at the first failure so a
+    /// half-resolved environment is never handed to a consumer.
+    pub fn materialize(&self, file: &SecretFile) -> Result<(SecretFile, usize)> {
+        let mut resolved = file.clone();
+        let mut fetched = 0;
+        for line in &mut resolved.lines {
+            let Line::Entry(entry) = line else {
+                continue;
+            };
+            let Some(reference) = SecretRef::parse(&entry.value) else {
+                continue;
+            };
+            let fail = |reason: String| VaulticError::ReferenceResolutionFailed {
+                key: entry.key.clone(),
+                reference: reference.to_string(),
+                reason,
+            };
+            let provider = self
+                .providers
+                .iter()
+                .find(|p| p.scheme() == reference.scheme)
+                .ok_or_else(|| {
+                    fail(format!(
+                        "no [references.{}] provider in config.toml",
+                        reference.scheme
+                    ))
+                })?;
+            entry.value = provider.fetch(&reference).map_err(|e| {
+                fail(match e {
+                    VaulticError::InvalidConfig { detail } => detail,
+                    other => other.to_string(),
+                })
+            })?;
+            fetched += 1;
+        }
+        Ok((resolved, fetched))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::parsers::dotenv_parser::DotenvParser;
+    use crate::core::traits::parser::ConfigParser;
+
+    struct FakeVault;
+
+    impl SecretProvider for FakeVault {
+        fn scheme(&self) -> &'static str {
+            "vault"
+        }
+
+        fn fetch(&self, reference: &SecretRef) -> Result<String> {
+            match (reference.path.as_str(), reference.field.as_deref()) {
+                ("secret/data/app", Some("api_key")) => Ok("sk_live_123".into()),
+                _ => Err(VaulticError::InvalidConfig {
+                    detail: "not found".into(),
+                }),
+            }
+        }
+    }
+
+    fn service() -> ReferenceService {
+        ReferenceService {
+            providers: vec![Box::new(FakeVault)],
+        }
+    }
+
+    #[test]
+    fn materialize_replaces_only_references() {
+        let file = DotenvParser
+            .parse("API_KEY=vault://secret/data/app#api_key\nURL=https://example.com\n")
+            .unwrap();
+        assert_eq!(ReferenceService::references(&file).len(), 1);
+
+        let (resolved, fetched) = service().materialize(&file).unwrap();
+        assert_eq!(fetched, 1);
+        assert_eq!(resolved.get("API_KEY"), Some("sk_live_123"));
+        assert_eq!(resolved.get("URL"), Some("https://example.com"));
+    }
+
+    #[test]
+    fn materialize_fails_without_provider_or_value() {
+        let file = DotenvParser.parse("TOKEN=op://Prod/Stripe/key\n").unwrap();
+        let err = service().materialize(&file).unwrap_err().to_string();
+        assert!(err.contains("TOKEN = op://Prod/Stripe/key"));
+        assert!(err.contains("no [references.op] provider"));
+
+        let file = DotenvParser
+            .parse("API_KEY=vault://secret/data/other#api_key\n")
+            .unwrap();
+        let err = service().materialize(&file).unwrap_err().to_string();
+        assert!(err.contains("API_KEY = vault://secret/data/other#api_key: not found"));
+    }
+}
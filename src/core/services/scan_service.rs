@@ -0,0 +1,133 @@
+use serde::Serialize;
+
+use crate::core::models::secret_file::SecretFile;
+
+/// Values shorter than this are not scanned for: ports, flags and
+/// small numbers would match all over any code base.
+pub const MIN_SECRET_LEN: usize = 8;
+
+/// A value stored in an encrypted environment, to look for in plaintext.
+#[derive(Debug, Clone)]
+pub struct KnownSecret {
+    pub env: String,
+    pub key: String,
+    pub value: String,
+}
+
+/// An occurrence of a known secret value in a plaintext file.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SecretLeak {
+    /// File containing the value, as given to [`ScanService::scan`].
+    pub path: String,
+    /// 1-based line of the first occurrence.
+    pub line: usize,
+    /// Commit that introduced the value, for history scans.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    pub env: String,
+    pub key: String,
+}
+
+/// Service that finds secret values leaked into plaintext files.
+///
+/// Matching is exact: only values that are in an environment today are
+/// found, never "secret-looking" strings. Values that are too short or
+/// too common to be meaningful are skipped (see [`ScanService::secrets`]).
+pub struct ScanService;
+
+impl ScanService {
+    /// The scannable values of `file`, which holds environment `env`.
+    ///
+    /// Skips values shorter than [`MIN_SECRET_LEN`], booleans and plain
+    /// numbers.
+    pub fn secrets(env: &str, file: &SecretFile) -> Vec<KnownSecret> {
+        file.entries()
+            .filter(|e| Self::is_scannable(&e.value))
+            .map(|e| KnownSecret {
+                env: env.to_string(),
+                key: e.key.clone(),
+                value: e.value.clone(),
+            })
+            .collect()
+    }
+
+    /// Every secret in `secrets` that occurs in `content`, reported once
+    /// per secret at its first occurrence.
+    pub fn scan(
+        &self,
+        secrets: &[KnownSecret],
+        path: &str,
+        content: &str,
+        commit: Option<&str>,
+    ) -> Vec<SecretLeak> {
+        secrets
+            .iter()
+            .filter_map(|secret| {
+                let offset = content.find(&secret.value)?;
+                Some(SecretLeak {
+                    path: path.to_string(),
+                    line: content[..offset].matches('\n').count() + 1,
+                    commit: commit.map(String::from),
+                    env: secret.env.clone(),
+                    key: secret.key.clone(),
+                })
+            })
+            .collect()
+    }
+
+    fn is_scannable(value: &str) -> bool {
+        let value = value.trim();
+        value.chars().count() >= MIN_SECRET_LEN
+            && !matches!(value.to_ascii_lowercase().as_str(), "true" | "false")
+            && value.parse::<f64>().is_err()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::parsers::dotenv_parser::DotenvParser;
+    use crate::core::traits::parser::ConfigParser;
+
+    fn secrets(content: &str) -> Vec<KnownSecret> {
+        ScanService::secrets("prod", &DotenvParser.parse(content).unwrap())
+    }
+
+    #[test]
+    fn skips_short_and_trivial_values() {
+        let found =
+            secrets("PORT=5432\nDEBUG=false\nTIMEOUT=300000000\nNAME=app\nTOKEN=sk_live_abc123\n");
+        let keys: Vec<&str> = found.iter().map(|s| s.key.as_str()).collect();
+        assert_eq!(keys, vec!["TOKEN"]);
+    }
+
+    #[test]
+    fn reports_first_occurrence_line() {
+        let known = secrets("TOKEN=sk_live_abc123\nDB_URL=postgres://u:p@db/app\n");
+        let content =
+            "fn main() {\n    let t = \"sk_live_abc123\";\n    let u = \"sk_live_abc123\";\n}\n";
+
+        let leaks = ScanService.scan(&known, "src/main.rs", content, None);
+        assert_eq!(
+            leaks,
+            vec![SecretLeak {
+                path: "src/main.rs".into(),
+                line: 2,
+                commit: None,
+                env: "prod".into(),
+                key: "TOKEN".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn clean_content_has_no_leaks() {
+        let known = secrets("TOKEN=sk_live_abc123\n");
+        let content = "let t = std::env::var(\"TOKEN\");\n";
+        assert!(
+            ScanService
+                .scan(&known, "a.rs", content, Some("abc1234"))
+                .is_empty()
+        );
+    }
+}
@@ -80,7 +80,7 @@ impl SecretAgeService {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::models::audit_entry::{AuditAction, AuditEntry};
+    use crate::core::models::audit_entry::{AuditAction, AuditDetail, AuditEntry};
 
     fn encrypt_entry(days_ago: i64) -> AuditEntry {
         AuditEntry {
@@ -89,7 +89,11 @@ mod tests {
             email: None,
             action: AuditAction::Encrypt,
             files: vec!["dev.env.enc".to_string()],
-            detail: Some("3 variables encrypted".to_string()),
+            detail: Some(AuditDetail::Encrypt {
+                cipher: "age".into(),
+                recipients: 3,
+                note: None,
+            }),
             state_hash: None,
         }
     }
@@ -0,0 +1,223 @@
+use crate::core::errors::{Result, VaulticError};
+
+/// One share of a secret split by [`split`]: the point `(x, y)` of the
+/// secret's polynomials, with one `y` byte per secret byte.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Share {
+    /// Share index, 1 to 255. Index 0 would be the secret itself.
+    pub x: u8,
+    pub y: Vec<u8>,
+}
+
+impl Share {
+    /// `x` followed by `y`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.y.len() + 1);
+        bytes.push(self.x);
+        bytes.extend_from_slice(&self.y);
+        bytes
+    }
+
+    /// Read a share written by [`Share::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        match bytes.split_first() {
+            Some((&x, y)) if x != 0 && !y.is_empty() => Ok(Self { x, y: y.to_vec() }),
+            _ => Err(VaulticError::EncryptionFailed {
+                reason: "Malformed secret share".into(),
+            }),
+        }
+    }
+}
+
+/// Split `secret` into `count` shares so that any `threshold` of them
+/// rebuild it and fewer reveal nothing (Shamir's secret sharing over
+/// GF(2^8)).
+///
+/// `random` fills a buffer with cryptographically secure bytes; it is
+/// passed in so the core stays free of I/O.
+pub fn split(
+    secret: &[u8],
+    threshold: u8,
+    count: u8,
+    mut random: impl FnMut(&mut [u8]),
+) -> Result<Vec<Share>> {
+    if threshold < 2 || threshold > count {
+        return Err(VaulticError::InvalidConfig {
+            detail: format!(
+                "threshold must be between 2 and {count} (the number of recipients), \
+                 got {threshold}"
+            ),
+        });
+    }
+
+    let mut shares: Vec<Share> = (1..=count)
+        .map(|x| Share {
+            x,
+            y: Vec::with_capacity(secret.len()),
+        })
+        .collect();
+    // One polynomial of degree threshold - 1 per byte, with the byte as
+    // its constant term
+    let mut coefficients = vec![0u8; threshold as usize];
+    for &byte in secret {
+        coefficients[0] = byte;
+        random(&mut coefficients[1..]);
+        for share in &mut shares {
+            share.y.push(evaluate(&coefficients, share.x));
+        }
+    }
+    coefficients.fill(0);
+    Ok(shares)
+}
+
+/// Rebuild the secret from shares produced by one [`split`].
+///
+/// Needs at least as many distinct shares as the threshold; fewer
+/// yield unrelated bytes, which callers detect by checking the result.
+pub fn combine(shares: &[Share]) -> Result<Vec<u8>> {
+    let malformed = |reason: &str| VaulticError::EncryptionFailed {
+        reason: reason.to_string(),
+    };
+    let Some(first) = shares.first() else {
+        return Err(malformed("No secret shares to combine"));
+    };
+    if shares.iter().any(|s| s.y.len() != first.y.len()) {
+        return Err(malformed("Secret shares have different lengths"));
+    }
+    for (i, share) in shares.iter().enumerate() {
+        if share.x == 0 || shares[..i].iter().any(|s| s.x == share.x) {
+            return Err(malformed(
+                "Secret shares must have distinct, non-zero indexes",
+            ));
+        }
+    }
+
+    // Lagrange interpolation at x = 0; subtraction is XOR in GF(2^8)
+    let basis: Vec<u8> = shares
+        .iter()
+        .map(|share| {
+            shares
+                .iter()
+                .filter(|other| other.x != share.x)
+                .fold(1, |acc, other| mul(acc, div(other.x, other.x ^ share.x)))
+        })
+        .collect();
+    Ok((0..first.y.len())
+        .map(|i| {
+            shares
+                .iter()
+                .zip(&basis)
+                .fold(0, |acc, (share, &l)| acc ^ mul(share.y[i], l))
+        })
+        .collect())
+}
+
+/// The polynomial with `coefficients` (constant term first) at `x`.
+fn evaluate(coefficients: &[u8], x: u8) -> u8 {
+    coefficients
+        .iter()
+        .rev()
+        .fold(0, |acc, &coefficient| mul(acc, x) ^ coefficient)
+}
+
+/// Multiplication in GF(2^8) with the AES polynomial x^8 + x^4 + x^3 + x + 1.
+///
+/// Runs the same eight steps without branching on either operand, so its
+/// timing does not depend on the secret bytes it multiplies.
+fn mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    for _ in 0..8 {
+        product ^= a & 0u8.wrapping_sub(b & 1);
+        let carry = 0u8.wrapping_sub(a >> 7);
+        a = (a << 1) ^ (carry & 0x1b);
+        b >>= 1;
+    }
+    product
+}
+
+/// Division in GF(2^8): `a` times the inverse of `b`, which is `b^254`.
+fn div(a: u8, b: u8) -> u8 {
+    let mut inverse = 1;
+    let mut power = b;
+    let mut exponent = 254u8;
+    while exponent != 0 {
+        if exponent & 1 != 0 {
+            inverse = mul(inverse, power);
+        }
+        power = mul(power, power);
+        exponent >>= 1;
+    }
+    mul(a, inverse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic stand-in for a CSPRNG.
+    fn counter() -> impl FnMut(&mut [u8]) {
+        let mut next = 7u8;
+        move |buf: &mut [u8]| {
+            for byte in buf {
+                next = next.wrapping_mul(31).wrapping_add(17);
+                *byte = next;
+            }
+        }
+    }
+
+    #[test]
+    fn any_threshold_shares_rebuild_the_secret() {
+        let secret = b"AGE-SECRET-KEY-1EXAMPLE";
+        let shares = split(secret, 2, 3, counter()).unwrap();
+        assert_eq!(shares.len(), 3);
+
+        for pair in [[0, 1], [0, 2], [1, 2], [2, 0]] {
+            let picked = [shares[pair[0]].clone(), shares[pair[1]].clone()];
+            assert_eq!(combine(&picked).unwrap(), secret);
+        }
+        assert_eq!(combine(&shares).unwrap(), secret);
+        assert_ne!(combine(&shares[..1]).unwrap(), secret);
+    }
+
+    #[test]
+    fn shares_round_trip_through_bytes() {
+        let share = Share {
+            x: 3,
+            y: vec![1, 2, 3],
+        };
+        assert_eq!(Share::from_bytes(&share.to_bytes()).unwrap(), share);
+        assert!(Share::from_bytes(&[0, 1]).is_err());
+        assert!(Share::from_bytes(&[1]).is_err());
+    }
+
+    #[test]
+    fn rejects_impossible_thresholds_and_duplicate_shares() {
+        assert!(split(b"secret", 1, 3, counter()).is_err());
+        let err = split(b"secret", 4, 3, counter()).unwrap_err().to_string();
+        assert!(
+            err.contains("threshold must be between 2 and 3 (the number of recipients), got 4"),
+            "{err}"
+        );
+
+        let shares = split(b"secret", 2, 2, counter()).unwrap();
+        assert!(combine(&[shares[0].clone(), shares[0].clone()]).is_err());
+    }
+
+    #[test]
+    fn field_multiplication_matches_known_products() {
+        // FIPS-197 section 4.2
+        assert_eq!(mul(0x57, 0x83), 0xc1);
+        assert_eq!(mul(0x57, 0x13), 0xfe);
+        assert_eq!(mul(0x80, 0x02), 0x1b);
+        assert_eq!(mul(0xff, 0x00), 0x00);
+    }
+
+    #[test]
+    fn field_division_inverts_multiplication() {
+        for a in 1..=255u8 {
+            for b in [1u8, 2, 0x53, 0xca, 255] {
+                assert_eq!(div(mul(a, b), b), a);
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use std::collections::BTreeMap;
+
+/// What pushing an environment to a remote store would change.
+/// Holds key names only, never values.
+#[derive(Debug, Default, PartialEq)]
+pub struct SyncPlan {
+    pub added: Vec<String>,
+    pub changed: Vec<String>,
+    /// Keys in the store that the environment no longer has.
+    pub removed: Vec<String>,
+    pub unchanged: usize,
+}
+
+impl SyncPlan {
+    /// Whether the store already matches the environment.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+
+    /// Number of keys to add, change or remove.
+    pub fn change_count(&self) -> usize {
+        self.added.len() + self.changed.len() + self.removed.len()
+    }
+}
+
+/// Compares a resolved environment with the contents of a remote store.
+pub struct SyncService;
+
+impl SyncService {
+    /// Plan the changes that make `remote` equal to `local`.
+    pub fn plan(
+        &self,
+        local: &BTreeMap<String, String>,
+        remote: &BTreeMap<String, String>,
+    ) -> SyncPlan {
+        let mut plan = SyncPlan::default();
+        for (key, value) in local {
+            match remote.get(key) {
+                None => plan.added.push(key.clone()),
+                Some(current) if current != value => plan.changed.push(key.clone()),
+                Some(_) => plan.unchanged += 1,
+            }
+        }
+        plan.removed = remote
+            .keys()
+            .filter(|k| !local.contains_key(*k))
+            .cloned()
+            .collect();
+        plan
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn plan_classifies_every_key() {
+        let local = map(&[("A", "1"), ("B", "2"), ("C", "3")]);
+        let remote = map(&[("B", "2"), ("C", "old"), ("D", "4")]);
+
+        let plan = SyncService.plan(&local, &remote);
+        assert_eq!(plan.added, vec!["A"]);
+        assert_eq!(plan.changed, vec!["C"]);
+        assert_eq!(plan.removed, vec!["D"]);
+        assert_eq!(plan.unchanged, 1);
+        assert_eq!(plan.change_count(), 3);
+    }
+
+    #[test]
+    fn identical_maps_need_no_changes() {
+        let local = map(&[("A", "1")]);
+        assert!(SyncService.plan(&local, &local.clone()).is_empty());
+    }
+}
@@ -1,6 +1,7 @@
 use std::path::{Path, PathBuf};
 
-use crate::config::app_config::AppConfig;
+use crate::config::app_config::{AppConfig, OutputEntry};
+use crate::config::path_expand::expand_path;
 use crate::core::errors::{Result, VaulticError};
 
 /// Priority list of template file names for auto-discovery.
@@ -28,7 +29,7 @@ impl TemplateResolver {
         if let Some(cfg) = config
             && let Some(ref tpl) = cfg.vaultic.template
         {
-            let path = project_root.join(tpl);
+            let path = expand_path(tpl, project_root)?;
             if path.exists() {
                 return Ok(path);
             }
@@ -45,7 +46,6 @@ impl TemplateResolver {
     /// 2. `{env}.env.template` convention in `.vaultic/`
     /// 3. Global `template` field in config
     /// 4. Auto-discovery in project root
-    #[allow(dead_code)]
     pub fn resolve_for_env(
         env_name: &str,
         config: &AppConfig,
@@ -56,7 +56,7 @@ impl TemplateResolver {
         if let Some(env_entry) = config.environments.get(env_name)
             && let Some(ref tpl) = env_entry.template
         {
-            let path = vaultic_dir.join(tpl);
+            let path = expand_path(tpl, vaultic_dir)?;
             if path.exists() {
                 return Ok(path);
             }
@@ -70,7 +70,7 @@ impl TemplateResolver {
 
         // 3. Global template from config
         if let Some(ref tpl) = config.vaultic.template {
-            let path = project_root.join(tpl);
+            let path = expand_path(tpl, project_root)?;
             if path.exists() {
                 return Ok(path);
             }
@@ -100,6 +100,30 @@ impl TemplateResolver {
         })
     }
 
+    /// Resolve the template path for an `[outputs]` entry.
+    ///
+    /// Resolution order:
+    /// 1. `template` field of the entry (must exist when set)
+    /// 2. Auto-discovery in the directory containing the output file
+    pub fn resolve_for_output(entry: &OutputEntry, project_root: &Path) -> Result<PathBuf> {
+        if let Some(ref tpl) = entry.template {
+            let path = expand_path(tpl, project_root)?;
+            if path.exists() {
+                return Ok(path);
+            }
+            return Err(VaulticError::TemplateNotFound {
+                searched: format!("✗ {} (from config)", path.display()),
+            });
+        }
+
+        let output = expand_path(&entry.path, project_root)?;
+        let dir = output
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or(project_root);
+        Self::auto_discover(dir)
+    }
+
     /// Auto-discover a template file in the given directory.
     fn auto_discover(base: &Path) -> Result<PathBuf> {
         for candidate in TEMPLATE_CANDIDATES {
@@ -156,4 +180,25 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), dir.path().join(".env.example"));
     }
+
+    #[test]
+    fn resolve_for_output_discovers_next_to_the_output() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("apps/web")).unwrap();
+        std::fs::write(dir.path().join("apps/web/.env.example"), "KEY=\n").unwrap();
+        let entry = OutputEntry {
+            path: "apps/web/.env.local".into(),
+            template: None,
+        };
+        assert_eq!(
+            TemplateResolver::resolve_for_output(&entry, dir.path()).unwrap(),
+            dir.path().join("apps/web/.env.example")
+        );
+
+        let entry = OutputEntry {
+            template: Some("apps/web/missing.template".into()),
+            ..entry
+        };
+        assert!(TemplateResolver::resolve_for_output(&entry, dir.path()).is_err());
+    }
 }
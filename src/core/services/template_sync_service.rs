@@ -15,7 +15,10 @@ impl TemplateSyncService {
     ///
     /// - Keys are deduplicated; the first occurrence determines position.
     /// - All values in the result are set to `""`.
-    /// - Comments and blank lines from the source files are **not** copied.
+    /// - Comment lines and blank lines directly above a key are copied with
+    ///   it, so section headers and descriptions survive. Comments above a
+    ///   key that is already in the template, or at the end of a file, are
+    ///   dropped.
     /// - An empty slice returns an empty `SecretFile`.
     pub fn merge_to_template(&self, files: &[SecretFile]) -> SecretFile {
         let mut seen: HashSet<String> = HashSet::new();
@@ -23,16 +26,35 @@ impl TemplateSyncService {
         let mut line_number: usize = 1;
 
         for file in files {
-            for entry in file.entries() {
-                if seen.insert(entry.key.clone()) {
-                    lines.push(Line::Entry(SecretEntry {
-                        key: entry.key.clone(),
-                        value: String::new(),
-                        comment: None,
-                        line_number,
-                    }));
+            let mut pending: Vec<Line> = Vec::new();
+            for line in &file.lines {
+                let entry = match line {
+                    Line::Entry(entry) => entry,
+                    other => {
+                        pending.push(other.clone());
+                        continue;
+                    }
+                };
+                if !seen.insert(entry.key.clone()) {
+                    pending.clear();
+                    continue;
+                }
+
+                for line in pending.drain(..) {
+                    let after_blank = matches!(lines.last(), None | Some(Line::Blank));
+                    if matches!(line, Line::Blank) && after_blank {
+                        continue;
+                    }
+                    lines.push(line);
                     line_number += 1;
                 }
+                lines.push(Line::Entry(SecretEntry {
+                    key: entry.key.clone(),
+                    value: String::new(),
+                    comment: None,
+                    line_number,
+                }));
+                line_number += 1;
             }
         }
 
@@ -114,6 +136,54 @@ mod tests {
         assert_eq!(keys, vec!["A", "B", "C"]);
     }
 
+    #[test]
+    fn merge_keeps_comments_above_new_keys() {
+        let file = |lines: Vec<Line>| SecretFile {
+            lines,
+            source_path: None,
+        };
+        let comment = |text: &str| Line::Comment(text.into());
+        let dev = file(vec![
+            comment("# Database"),
+            entry("DB_URL", 1),
+            Line::Blank,
+            comment("# Cache"),
+            entry("REDIS_URL", 4),
+        ]);
+        let prod = file(vec![
+            comment("# Database"),
+            entry("DB_URL", 1),
+            Line::Blank,
+            comment("# Payments"),
+            entry("STRIPE_KEY", 4),
+            comment("# trailing"),
+        ]);
+        let result = TemplateSyncService.merge_to_template(&[dev, prod]);
+
+        assert_eq!(
+            result.lines,
+            vec![
+                Line::Comment("# Database".into()),
+                entry("DB_URL", 2),
+                Line::Blank,
+                Line::Comment("# Cache".into()),
+                entry("REDIS_URL", 5),
+                Line::Blank,
+                Line::Comment("# Payments".into()),
+                entry("STRIPE_KEY", 8),
+            ]
+        );
+    }
+
+    fn entry(key: &str, line_number: usize) -> Line {
+        Line::Entry(SecretEntry {
+            key: key.into(),
+            value: String::new(),
+            comment: None,
+            line_number,
+        })
+    }
+
     #[test]
     fn merge_duplicate_keys_not_doubled() {
         let svc = TemplateSyncService;
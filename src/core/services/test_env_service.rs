@@ -0,0 +1,99 @@
+use crate::core::models::secret_file::{Line, SecretEntry, SecretFile};
+use crate::core::services::key_filter::glob_match;
+
+/// Reduces a resolved environment to the subset used by tests.
+pub struct TestEnvService;
+
+impl TestEnvService {
+    /// Keep keys matching `include` (all keys when empty) and not matching
+    /// `exclude`, then prepend `prefix` to each kept key.
+    ///
+    /// Patterns are exact keys or globs such as `DB_*` or `*_URL`.
+    /// Comments and blank lines are dropped; key order is preserved.
+    pub fn filter(
+        &self,
+        resolved: &SecretFile,
+        include: &[String],
+        exclude: &[String],
+        prefix: &str,
+    ) -> SecretFile {
+        let lines = resolved
+            .entries()
+            .filter(|e| include.is_empty() || include.iter().any(|p| glob_match(p, &e.key)))
+            .filter(|e| !exclude.iter().any(|p| glob_match(p, &e.key)))
+            .enumerate()
+            .map(|(i, e)| {
+                Line::Entry(SecretEntry {
+                    key: format!("{prefix}{}", e.key),
+                    value: e.value.clone(),
+                    comment: None,
+                    line_number: i + 1,
+                })
+            })
+            .collect();
+
+        SecretFile {
+            lines,
+            source_path: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_file(pairs: &[(&str, &str)]) -> SecretFile {
+        SecretFile {
+            lines: pairs
+                .iter()
+                .enumerate()
+                .map(|(i, (k, v))| {
+                    Line::Entry(SecretEntry {
+                        key: k.to_string(),
+                        value: v.to_string(),
+                        comment: None,
+                        line_number: i + 1,
+                    })
+                })
+                .collect(),
+            source_path: None,
+        }
+    }
+
+    fn patterns(p: &[&str]) -> Vec<String> {
+        p.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn empty_include_keeps_everything() {
+        let file = make_file(&[("A", "1"), ("B", "2")]);
+        let out = TestEnvService.filter(&file, &[], &[], "");
+        assert_eq!(out.keys(), vec!["A", "B"]);
+    }
+
+    #[test]
+    fn include_and_exclude_patterns() {
+        let file = make_file(&[
+            ("DB_URL", "pg"),
+            ("DB_ADMIN_PASSWORD", "x"),
+            ("STRIPE_LIVE_KEY", "sk"),
+            ("API_URL", "http"),
+        ]);
+        let out = TestEnvService.filter(
+            &file,
+            &patterns(&["DB_*", "API_URL"]),
+            &patterns(&["DB_ADMIN_*"]),
+            "",
+        );
+        assert_eq!(out.keys(), vec!["DB_URL", "API_URL"]);
+    }
+
+    #[test]
+    fn prefix_is_prepended() {
+        let file = make_file(&[("DB_URL", "pg")]);
+        let out = TestEnvService.filter(&file, &[], &[], "TEST_");
+        assert_eq!(out.get("TEST_DB_URL"), Some("pg"));
+        assert_eq!(out.get("DB_URL"), None);
+    }
+}
@@ -45,6 +45,7 @@ impl ValidationService {
     /// Returns `Err` only for invalid regex patterns in rules (config error).
     /// Missing non-required keys are silently skipped.
     /// All failures for a key are collected before moving to the next.
+    /// Failure reasons never include the value, which may be a secret.
     pub fn validate(
         values: &HashMap<String, String>,
         rules: &ValidationConfig,
@@ -88,29 +89,33 @@ impl ValidationService {
                         failures.push("expected a valid URL (must contain '://')".to_string());
                     }
                     "url" => {}
-                    "integer" => match val.parse::<i64>() {
+                    "integer" | "int" => match val.parse::<i64>() {
                         Ok(n) => {
                             if let Some(min) = rule.min
                                 && n < min
                             {
-                                failures.push(format!("value {n} is below minimum {min}"));
+                                failures.push(format!("value is below minimum {min}"));
                             }
                             if let Some(max) = rule.max
                                 && n > max
                             {
-                                failures.push(format!("value {n} is above maximum {max}"));
+                                failures.push(format!("value is above maximum {max}"));
                             }
                         }
                         Err(_) => {
-                            failures.push(format!("expected integer, got '{val}'"));
+                            failures.push("expected integer".to_string());
                         }
                     },
-                    "boolean" => {
+                    "boolean" | "bool" => {
                         let lower = val.to_lowercase();
                         if !matches!(lower.as_str(), "true" | "false" | "1" | "0" | "yes" | "no") {
-                            failures.push(format!(
-                                "expected boolean (true/false/1/0/yes/no), got '{val}'"
-                            ));
+                            failures.push("expected boolean (true/false/1/0/yes/no)".to_string());
+                        }
+                    }
+                    "enum" => {
+                        let allowed = rule.values.as_deref().unwrap_or_default();
+                        if !allowed.iter().any(|v| v == val) {
+                            failures.push(format!("expected one of: {}", allowed.join(", ")));
                         }
                     }
                     _ => {
@@ -318,6 +323,31 @@ mod tests {
         assert!(report.results[0].failures[0].contains("expected boolean"));
     }
 
+    #[test]
+    fn enum_accepts_listed_values_only() {
+        let rules = make_rules(vec![(
+            "LOG_LEVEL",
+            ValidationRule {
+                value_type: Some("enum".to_string()),
+                values: Some(vec!["info".to_string(), "debug".to_string()]),
+                ..Default::default()
+            },
+        )]);
+        let values = make_values(&[("LOG_LEVEL", "debug")]);
+        assert!(
+            ValidationService::validate(&values, &rules)
+                .unwrap()
+                .is_ok()
+        );
+
+        let values = make_values(&[("LOG_LEVEL", "verbose")]);
+        let report = ValidationService::validate(&values, &rules).unwrap();
+        assert_eq!(
+            report.results[0].failures,
+            vec!["expected one of: info, debug".to_string()]
+        );
+    }
+
     // ----- Length tests -----
 
     #[test]
@@ -0,0 +1,231 @@
+use chrono::{DateTime, Utc};
+
+use crate::core::models::key_identity::{KeyIdentity, KeyType};
+use crate::core::models::verification::{Access, EnvVerification, HeaderStanza, RecipientAccess};
+
+/// What is known about the local key for the file being verified.
+pub struct LocalKey<'a> {
+    pub public_key: &'a str,
+    /// Whether it decrypted the file.
+    pub decrypts: bool,
+}
+
+/// Service that checks which recipients can open an age-encrypted file.
+///
+/// SSH stanzas carry a tag derived from the key, so they are matched
+/// exactly. X25519 stanzas are anonymous: the local key is proven by
+/// decrypting, keys added after the last encrypt are known to be missing,
+/// and the rest is inferred from how many stanzas the header holds.
+pub struct VerifyService;
+
+impl VerifyService {
+    /// Verify `recipients` against the `stanzas` of the file for `env`.
+    ///
+    /// `ssh_tag` computes the header tag of an SSH public key. Each
+    /// recipient's `added_at` is compared with `last_encrypt`, the time
+    /// the file was last written, when both are known.
+    pub fn verify(
+        env: &str,
+        file: &str,
+        stanzas: &[HeaderStanza],
+        recipients: &[KeyIdentity],
+        last_encrypt: Option<DateTime<Utc>>,
+        local: Option<LocalKey<'_>>,
+        ssh_tag: impl Fn(&str) -> Option<String>,
+    ) -> EnvVerification {
+        let x25519_stanzas = stanzas
+            .iter()
+            .filter(|s| matches!(s, HeaderStanza::X25519))
+            .count();
+        let ssh_tags: Vec<&str> = stanzas
+            .iter()
+            .filter_map(|s| match s {
+                HeaderStanza::Ssh { tag } => Some(tag.as_str()),
+                HeaderStanza::X25519 => None,
+            })
+            .collect();
+
+        let mut matched_tags = Vec::new();
+        let mut pending = Vec::new();
+        let mut results: Vec<Option<(Access, String)>> = recipients
+            .iter()
+            .enumerate()
+            .map(|(i, ki)| match ki.key_type() {
+                KeyType::Ssh => Some(match ssh_tag(&ki.public_key) {
+                    Some(tag) if ssh_tags.contains(&tag.as_str()) => {
+                        matched_tags.push(tag);
+                        (Access::Yes, "header has a stanza for this SSH key".into())
+                    }
+                    _ => (Access::No, "no stanza for this SSH key".into()),
+                }),
+                KeyType::Gpg => Some((Access::No, "not an age or SSH key".into())),
+                KeyType::Age => {
+                    if let Some(local) = local.as_ref().filter(|l| l.public_key == ki.public_key) {
+                        return Some(if local.decrypts {
+                            (Access::Yes, "decrypted with your key".into())
+                        } else {
+                            (Access::No, "your key cannot decrypt it".into())
+                        });
+                    }
+                    if let (Some(added), Some(written)) = (ki.added_at, last_encrypt)
+                        && added > written
+                    {
+                        return Some((
+                            Access::No,
+                            format!(
+                                "added {} after the last encrypt ({})",
+                                added.format("%Y-%m-%d"),
+                                written.format("%Y-%m-%d")
+                            ),
+                        ));
+                    }
+                    pending.push(i);
+                    None
+                }
+            })
+            .collect();
+
+        // X25519 stanzas expected for the age keys that were not ruled out
+        let expected = recipients
+            .iter()
+            .zip(&results)
+            .filter(|(ki, result)| {
+                ki.key_type() == KeyType::Age && !matches!(result, Some((Access::No, _)))
+            })
+            .count();
+        let inferred = if x25519_stanzas >= expected {
+            (
+                Access::Likely,
+                format!("{x25519_stanzas} X25519 stanza(s) for {expected} age key(s)"),
+            )
+        } else {
+            (
+                Access::Unknown,
+                format!("only {x25519_stanzas} X25519 stanza(s) for {expected} age key(s)"),
+            )
+        };
+        for i in pending {
+            results[i] = Some(inferred.clone());
+        }
+
+        let unlisted_ssh = ssh_tags
+            .iter()
+            .filter(|tag| !matched_tags.iter().any(|m| m == *tag))
+            .count();
+
+        EnvVerification {
+            env: env.to_string(),
+            file: file.to_string(),
+            recipients: recipients
+                .iter()
+                .zip(results)
+                .map(|(ki, result)| {
+                    let (access, reason) = result.unwrap_or((Access::Unknown, String::new()));
+                    RecipientAccess {
+                        key: ki.public_key.clone(),
+                        label: ki.label.clone(),
+                        access,
+                        reason,
+                    }
+                })
+                .collect(),
+            unlisted_stanzas: x25519_stanzas.saturating_sub(expected) + unlisted_ssh,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(public_key: &str, added_at: Option<&str>) -> KeyIdentity {
+        KeyIdentity {
+            public_key: public_key.to_string(),
+            label: None,
+            added_at: added_at.map(|t| t.parse().unwrap()),
+        }
+    }
+
+    fn tag(public_key: &str) -> Option<String> {
+        public_key
+            .strip_prefix("ssh-ed25519 ")
+            .map(|k| format!("tag-{k}"))
+    }
+
+    fn access(report: &EnvVerification) -> Vec<Access> {
+        report.recipients.iter().map(|r| r.access).collect()
+    }
+
+    #[test]
+    fn key_added_after_last_encrypt_cannot_decrypt() {
+        let recipients = vec![
+            key("age1alice", Some("2026-01-01T00:00:00Z")),
+            key("age1bob", Some("2026-03-01T00:00:00Z")),
+        ];
+        let report = VerifyService::verify(
+            "dev",
+            "dev.env.enc",
+            &[HeaderStanza::X25519],
+            &recipients,
+            Some("2026-02-01T00:00:00Z".parse().unwrap()),
+            None,
+            tag,
+        );
+        assert_eq!(access(&report), vec![Access::Likely, Access::No]);
+        assert!(!report.is_ok());
+    }
+
+    #[test]
+    fn ssh_keys_are_matched_by_tag() {
+        let recipients = vec![key("ssh-ed25519 AAAA", None), key("ssh-ed25519 BBBB", None)];
+        let stanzas = vec![
+            HeaderStanza::Ssh {
+                tag: "tag-AAAA".into(),
+            },
+            HeaderStanza::Ssh {
+                tag: "tag-CCCC".into(),
+            },
+        ];
+        let report =
+            VerifyService::verify("dev", "dev.env.enc", &stanzas, &recipients, None, None, tag);
+        assert_eq!(access(&report), vec![Access::Yes, Access::No]);
+        assert_eq!(report.unlisted_stanzas, 1);
+    }
+
+    #[test]
+    fn local_key_is_proven_and_extra_stanzas_are_reported() {
+        let recipients = vec![key("age1alice", None), key("age1bob", None)];
+        let stanzas = vec![HeaderStanza::X25519; 3];
+        let local = LocalKey {
+            public_key: "age1alice",
+            decrypts: true,
+        };
+        let report = VerifyService::verify(
+            "prod",
+            "prod.env.enc",
+            &stanzas,
+            &recipients,
+            None,
+            Some(local),
+            tag,
+        );
+        assert_eq!(access(&report), vec![Access::Yes, Access::Likely]);
+        assert_eq!(report.unlisted_stanzas, 1);
+    }
+
+    #[test]
+    fn too_few_stanzas_leave_age_keys_unknown() {
+        let recipients = vec![key("age1alice", None), key("age1bob", None)];
+        let report = VerifyService::verify(
+            "dev",
+            "dev.env.enc",
+            &[HeaderStanza::X25519],
+            &recipients,
+            None,
+            None,
+            tag,
+        );
+        assert_eq!(access(&report), vec![Access::Unknown, Access::Unknown]);
+        assert!(!report.is_ok());
+    }
+}
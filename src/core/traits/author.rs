@@ -0,0 +1,8 @@
+/// Port for identifying who performs an operation.
+///
+/// The default implementation reads `git config`; test mode swaps in a
+/// fixed identity so audit logs are reproducible.
+pub trait AuthorProvider: Send + Sync {
+    /// Return the author name and optional email.
+    fn author(&self) -> (String, Option<String>);
+}
@@ -3,7 +3,8 @@ use crate::core::models::key_identity::KeyIdentity;
 
 /// Port for encryption/decryption backends.
 ///
-/// Implementations live in `adapters::cipher` (e.g. AgeBackend, GpgBackend).
+/// Implementations live in `adapters::cipher` (e.g. AgeBackend, GpgBackend,
+/// PluginBackend).
 /// The core layer only depends on this trait, never on a concrete backend.
 pub trait CipherBackend: Send + Sync {
     /// Encrypt plaintext for the given recipients.
@@ -15,3 +16,18 @@ pub trait CipherBackend: Send + Sync {
     /// Human-readable name of this backend (e.g. "age", "gpg").
     fn name(&self) -> &str;
 }
+
+/// Lets commands pick a backend at runtime, e.g. a cipher plugin.
+impl<C: CipherBackend + ?Sized> CipherBackend for Box<C> {
+    fn encrypt(&self, plaintext: &[u8], recipients: &[KeyIdentity]) -> Result<Vec<u8>> {
+        (**self).encrypt(plaintext, recipients)
+    }
+
+    fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>> {
+        (**self).decrypt(ciphertext)
+    }
+
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+}
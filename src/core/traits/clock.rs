@@ -0,0 +1,10 @@
+use chrono::{DateTime, Utc};
+
+/// Port for reading the current time.
+///
+/// Lets audit entries and generated files carry deterministic
+/// timestamps under `VAULTIC_TEST_MODE`.
+pub trait Clock: Send + Sync {
+    /// Current instant in UTC.
+    fn now(&self) -> DateTime<Utc>;
+}
@@ -0,0 +1,16 @@
+use chrono::{DateTime, Utc};
+
+use crate::core::errors::Result;
+use crate::core::models::lease::Lease;
+
+/// Port for a source of short-lived credentials, such as a cloud STS.
+///
+/// `Send` so `vaultic run` can refresh leases from a background thread
+/// while the child process runs.
+pub trait CredentialProvider: Send {
+    /// Name of the lease in `[leases]`, for messages.
+    fn name(&self) -> &str;
+
+    /// Request fresh credentials. `now` is when the request is made.
+    fn lease(&self, now: DateTime<Utc>) -> Result<Lease>;
+}
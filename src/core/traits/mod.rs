@@ -1,4 +1,9 @@
 pub mod audit;
+pub mod author;
 pub mod cipher;
+pub mod clock;
+pub mod credential_provider;
 pub mod key_store;
 pub mod parser;
+pub mod remote_store;
+pub mod secret_provider;
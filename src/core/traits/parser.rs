@@ -3,8 +3,8 @@ use crate::core::models::secret_file::SecretFile;
 
 /// Port for parsing and serializing configuration files.
 ///
-/// v1.0 only ships with `DotenvParser`; the trait enables future
-/// support for TOML, YAML, JSON, etc.
+/// Implemented by `DotenvParser`, `YamlParser` and `JsonParser`; pick
+/// one with `adapters::parsers::for_format`.
 pub trait ConfigParser: Send + Sync {
     /// Parse raw file content into a structured `SecretFile`.
     fn parse(&self, content: &str) -> Result<SecretFile>;
@@ -0,0 +1,17 @@
+use std::collections::BTreeMap;
+
+use crate::core::errors::Result;
+
+/// Port for a cloud secret store that resolved environments are pushed to.
+pub trait RemoteStore {
+    /// Where the secrets live, e.g. `secretsmanager:myapp/prod`.
+    fn location(&self) -> String;
+
+    /// Key/value pairs currently in the store. Empty when nothing has
+    /// been pushed yet.
+    fn fetch(&self) -> Result<BTreeMap<String, String>>;
+
+    /// Make the store hold exactly `desired`. `removed` lists the keys in
+    /// the store that are not in `desired`.
+    fn push(&self, desired: &BTreeMap<String, String>, removed: &[String]) -> Result<()>;
+}
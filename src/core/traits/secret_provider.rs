@@ -0,0 +1,11 @@
+use crate::core::errors::Result;
+use crate::core::models::secret_ref::SecretRef;
+
+/// Port for an external secret manager that reference values point at.
+pub trait SecretProvider {
+    /// The reference scheme this provider serves, e.g. `vault`.
+    fn scheme(&self) -> &'static str;
+
+    /// The current value behind `reference`.
+    fn fetch(&self, reference: &SecretRef) -> Result<String>;
+}
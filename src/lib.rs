@@ -0,0 +1,73 @@
+//! Vaultic — secure your secrets, sync your team, trust your configs.
+//!
+//! The `vaultic` binary is a thin wrapper over this library. Other tools,
+//! such as a dashboard or a build plugin, can embed it to read Vaultic
+//! projects without shelling out:
+//!
+//! - [`config`]: `.vaultic/config.toml` and the per-user config.
+//! - [`core`]: models, services such as
+//!   [`EnvResolver`](core::services::env_resolver::EnvResolver), the traits
+//!   adapters implement, and [`VaulticError`](core::errors::VaulticError).
+//! - [`adapters`]: age and GPG ciphers, dotenv/JSON/YAML parsers, key
+//!   stores, git and the audit log.
+//! - `cli`: the commands, behind the default `cli` feature.
+//!
+//! To depend on the library alone, without the CLI's terminal, clipboard
+//! and self-update dependencies:
+//!
+//! ```toml
+//! [dependencies]
+//! vaultic = { version = "1", default-features = false }
+//! ```
+//!
+//! Resolve an environment through its inheritance chain, then encrypt it:
+//!
+//! ```
+//! use std::collections::HashMap;
+//!
+//! use vaultic::adapters::cipher::age_backend::AgeBackend;
+//! use vaultic::adapters::parsers::dotenv_parser::DotenvParser;
+//! use vaultic::config::app_config::AppConfig;
+//! use vaultic::core::models::key_identity::KeyIdentity;
+//! use vaultic::core::services::encryption_service;
+//! use vaultic::core::services::env_resolver::EnvResolver;
+//! use vaultic::core::traits::cipher::CipherBackend;
+//! use vaultic::core::traits::parser::ConfigParser;
+//!
+//! # fn main() -> vaultic::core::errors::Result<()> {
+//! let config = AppConfig::parse(
+//!     "[vaultic]\nversion = \"0.1.0\"\ndefault_cipher = \"age\"\n\n\
+//!      [environments]\nbase = {}\nprod = { inherits = \"base\" }\n",
+//! )?;
+//! let parser = DotenvParser;
+//! let files = HashMap::from([
+//!     ("base".to_string(), parser.parse("LOG_LEVEL=info\nDB_HOST=localhost\n")?),
+//!     ("prod".to_string(), parser.parse("DB_HOST=db.internal\n")?),
+//! ]);
+//! let prod = EnvResolver.resolve("prod", &config, &files)?;
+//! assert_eq!(prod.resolved.get("DB_HOST"), Some("db.internal"));
+//! assert_eq!(prod.resolved.get("LOG_LEVEL"), Some("info"));
+//!
+//! let dir = tempfile::tempdir()?;
+//! let identity = dir.path().join("keys.txt");
+//! let recipients = [KeyIdentity {
+//!     public_key: AgeBackend::generate_identity(&identity)?,
+//!     label: None,
+//!     added_at: None,
+//! }];
+//! let backend = AgeBackend::new(identity);
+//! let plaintext = parser.serialize(&prod.resolved)?;
+//! let ciphertext = backend.encrypt(plaintext.as_bytes(), &recipients)?;
+//! assert_eq!(
+//!     encryption_service::decrypt_content(&backend, &ciphertext)?,
+//!     plaintext.as_bytes()
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod adapters;
+#[cfg(feature = "cli")]
+pub mod cli;
+pub mod config;
+pub mod core;
@@ -1,33 +1,86 @@
-mod adapters;
-mod cli;
-mod config;
-mod core;
+use clap::{CommandFactory, FromArgMatches};
 
-use clap::Parser;
-
-use cli::{Cli, Commands};
+use vaultic::cli::commands::reference_helpers;
+use vaultic::cli::{Cli, Commands};
+use vaultic::core::services::key_filter::KeyFilter;
+use vaultic::{adapters, cli, config, core};
 
 fn main() {
-    let args = Cli::parse();
+    let raw_args = match cli::aliases::expand(std::env::args_os().collect()) {
+        Ok(raw_args) => raw_args,
+        Err(e) => {
+            cli::output::error(&format!("Error: {e}"));
+            std::process::exit(1);
+        }
+    };
+    let matches = Cli::command().get_matches_from(raw_args);
+    let mut args = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    args.apply_env_precedence(&matches);
 
     // Initialize global CLI state before any command runs
-    cli::output::init(args.verbose, args.quiet);
     cli::context::init(args.config.as_deref());
+    cli::i18n::init(
+        cli::context::config()
+            .ok()
+            .and_then(|c| c.ui.as_ref())
+            .map(|ui| ui.language),
+    );
+
+    if args.json && !args.command.supports_json() {
+        cli::output::error(&format!(
+            "{}: {}",
+            cli::i18n::t("error-label"),
+            cli::i18n::t("json-unsupported")
+        ));
+        std::process::exit(1);
+    }
+    let exec_mode = args.json || args.command.is_exec();
+    cli::output::init(args.verbose, args.quiet, exec_mode);
+    cli::output::init_json(args.json);
+    cli::output::init_prompts(args.yes, args.no_input);
+    cli::signals::init();
+
+    let user_config = cli::context::user_config();
+    cli::output::init_color(user_config.color);
 
-    // Passive version check (suppressed in quiet mode and during update)
+    let (cipher, cipher_source) = args.resolve_cipher(&matches);
+    cli::output::detail(&format!("Cipher: {cipher} (from {cipher_source})"));
+
+    // Passive version check (suppressed in quiet/exec mode, during update
+    // and with update_check = false in the user config)
     if !args.quiet
-        && !matches!(args.command, Commands::Update)
-        && let Some(latest) = adapters::updater::github_updater::check_latest_version()
+        && !exec_mode
+        && user_config.update_check
+        && !matches!(args.command, Commands::Update { .. })
+        && let Some(update) = adapters::updater::github_updater::check_latest_version()
     {
-        cli::output::warning(&format!(
-            "New version available: v{latest}. Run 'vaultic update' to upgrade."
+        cli::output::warning(&cli::i18n::t_args(
+            "update-available",
+            &[("version", &update.version.to_string())],
         ));
+        if let Some(notes) = &update.notes {
+            cli::output::quote(notes);
+        }
+    }
+
+    // Surface commits recorded by the post-commit hook as skipping pre-commit
+    if !matches!(
+        args.command,
+        Commands::Hook {
+            action: cli::HookAction::PostCommit
+        }
+    ) {
+        cli::commands::hook::report_bypasses();
     }
 
     // Validate all --env values before dispatching any command
     for env_name in &args.env {
-        if let Err(e) = cli::context::validate_env_name(env_name) {
-            cli::output::error(&format!("Error: {e}"));
+        if let Err(e) = config::names::validate_env_name(env_name) {
+            cli::output::error(&format!(
+                "{}: {}",
+                cli::i18n::t("error-label"),
+                cli::i18n::error_message(&e)
+            ));
             std::process::exit(1);
         }
     }
@@ -36,56 +89,293 @@ fn main() {
     let single_env = args.env.first().map(|s| s.as_str());
 
     let result = match &args.command {
-        Commands::Init => cli::commands::init::execute(),
-        Commands::Encrypt { file, all } => {
-            cli::commands::encrypt::execute(file.as_deref(), single_env, &args.cipher, *all)
+        Commands::Init {
+            no_keygen,
+            default_env,
+            environments,
+            template,
+        } => cli::commands::init::execute(&cli::commands::init::InitOptions {
+            cipher: &cipher,
+            no_keygen: *no_keygen,
+            default_env: default_env.as_deref(),
+            environments,
+            template: template.as_deref(),
+        }),
+        Commands::Encrypt {
+            file,
+            all,
+            note,
+            force,
+            review,
+            reencrypt,
+        } => {
+            if *reencrypt {
+                cli::commands::encrypt::execute_reencrypt(
+                    single_env,
+                    &cipher,
+                    note.as_deref(),
+                    *force,
+                )
+            } else {
+                cli::commands::encrypt::execute(
+                    file.as_deref(),
+                    single_env,
+                    &cipher,
+                    *all,
+                    note.as_deref(),
+                    *force,
+                    *review,
+                )
+            }
         }
         Commands::Decrypt {
             file,
             key,
+            share,
             output,
             stdout,
         } => cli::commands::decrypt::execute(
             file.as_deref(),
             single_env,
-            &args.cipher,
+            &cipher,
             key.as_deref(),
+            share,
             output.as_deref(),
             *stdout,
         ),
-        Commands::Check => cli::commands::check::execute(),
-        Commands::Diff { file1, file2 } => cli::commands::diff::execute(
+        Commands::Check {
+            all_outputs,
+            scan,
+            deep,
+            policy,
+            schema,
+            report,
+            output,
+        } => {
+            if *schema {
+                cli::commands::check::schema(
+                    &args.env,
+                    &cipher,
+                    report.as_deref(),
+                    output.as_deref(),
+                )
+            } else if *policy {
+                cli::commands::check::policy(
+                    &args.env,
+                    &cipher,
+                    report.as_deref(),
+                    output.as_deref(),
+                )
+            } else if *scan {
+                cli::commands::check::scan(
+                    &args.env,
+                    *deep,
+                    &cipher,
+                    report.as_deref(),
+                    output.as_deref(),
+                )
+            } else if let Some(env) = single_env.filter(|_| !*all_outputs) {
+                cli::commands::check::resolved(env, &cipher, report.as_deref(), output.as_deref())
+            } else {
+                cli::commands::check::execute(*all_outputs, report.as_deref(), output.as_deref())
+            }
+        }
+        Commands::Diff {
+            file1,
+            file2,
+            side_by_side,
+            show_values,
+            only_keys,
+            rev,
+        } => cli::commands::diff::execute(
             file1.as_deref(),
             file2.as_deref(),
             &args.env,
-            &args.cipher,
+            rev.as_deref(),
+            &cipher,
+            cli::commands::diff::DiffView {
+                side_by_side: *side_by_side,
+                show_values: *show_values,
+                only_keys: *only_keys,
+            },
         ),
-        Commands::Resolve { output, stdout } => {
-            cli::commands::resolve::execute(single_env, &args.cipher, output.as_deref(), *stdout)
+        Commands::Resolve {
+            output,
+            stdout,
+            only,
+            except,
+            materialize,
+            keep_refs,
+        } => cli::commands::resolve::execute(
+            single_env,
+            &cipher,
+            output.as_deref(),
+            *stdout,
+            &KeyFilter::new(only.as_deref(), except.as_deref()),
+            reference_helpers::mode(*materialize, *keep_refs),
+        ),
+        Commands::Get { key, copy } => cli::commands::get::execute(key, single_env, &cipher, *copy),
+        Commands::Set {
+            assignment,
+            note,
+            force,
+        } => cli::commands::set::execute(assignment, single_env, &cipher, note.as_deref(), *force),
+        Commands::Promote {
+            from,
+            to,
+            keys,
+            note,
+            force,
+        } => cli::commands::promote::execute(
+            from,
+            to,
+            keys.as_deref(),
+            &cipher,
+            note.as_deref(),
+            *force,
+        ),
+        Commands::Import {
+            source,
+            format,
+            keep_existing,
+            note,
+            force,
+        } => cli::commands::import::execute(
+            source.as_deref(),
+            format.as_deref(),
+            *keep_existing,
+            single_env,
+            &cipher,
+            note.as_deref(),
+            *force,
+        ),
+        Commands::Edit { note, force } => {
+            cli::commands::edit::execute(single_env, &cipher, note.as_deref(), *force)
+        }
+        Commands::Run {
+            only,
+            except,
+            materialize,
+            keep_refs,
+            command,
+        } => cli::commands::run::execute(
+            single_env,
+            &cipher,
+            &KeyFilter::new(only.as_deref(), except.as_deref()),
+            reference_helpers::mode(*materialize, *keep_refs),
+            command,
+        )
+        .map(|code| {
+            if code != 0 {
+                std::process::exit(code);
+            }
+        }),
+        Commands::TestEnv { prefix, output } => {
+            cli::commands::test_env::execute(single_env, &cipher, prefix.as_deref(), output)
         }
-        Commands::Keys { action } => cli::commands::keys::execute(action),
+        Commands::Keys { action } => cli::commands::keys::execute(action, single_env),
         Commands::Log {
             author,
             since,
             last,
         } => cli::commands::log::execute(author.as_deref(), since.as_deref(), *last),
         Commands::Status => cli::commands::status::execute(),
+        Commands::Verify {
+            accept_changes: true,
+            ..
+        } => cli::commands::verify::execute_accept_changes(),
+        Commands::Verify { report, output, .. } => {
+            cli::commands::verify::execute(&args.env, &cipher, report.as_deref(), output.as_deref())
+        }
+        Commands::Prune => cli::commands::prune::execute(),
+        Commands::RepairArmor { files, dry_run } => {
+            cli::commands::repair_armor::execute(files, &args.env, *dry_run)
+        }
+        Commands::Sync { target } => cli::commands::sync::execute(target, single_env, &cipher),
+        Commands::Guide { topic } => cli::commands::guide::execute(topic.as_deref()),
+        Commands::UpgradeConfig { dry_run } => cli::commands::upgrade_config::execute(*dry_run),
+        Commands::Move { to } => {
+            cli::commands::relocate::execute(std::path::Path::new(to), &cipher)
+        }
         Commands::Hook { action } => cli::commands::hook::execute(action),
-        Commands::Template { action } => cli::commands::template::execute(action),
+        Commands::Template { action } => cli::commands::template::execute(action, &cipher),
+        Commands::Env { action } => match action {
+            cli::EnvAction::TemplateCheck { report, output } => {
+                cli::commands::env::execute_template_check(
+                    &args.env,
+                    &cipher,
+                    report.as_deref(),
+                    output.as_deref(),
+                )
+            }
+        },
         Commands::Validate { file } => cli::commands::validate::execute(file.as_deref()),
         Commands::Ci { action } => {
             use cli::CiAction;
             match action {
                 CiAction::Export { format, mask } => {
-                    cli::commands::ci::execute_export(single_env, &args.cipher, format, *mask)
+                    cli::commands::ci::execute_export(single_env, &cipher, format, *mask)
                 }
             }
         }
-        Commands::Update => cli::commands::update::execute(),
+        Commands::Config { action } => cli::commands::config::execute(action),
+        Commands::Report { action } => cli::commands::report::execute(action, &cipher),
+        Commands::Archive { action } => cli::commands::archive::execute(action, &cipher),
+        Commands::Bundle {
+            action,
+            recipient,
+            output,
+        } => cli::commands::bundle::execute(
+            action.as_ref(),
+            single_env,
+            recipient,
+            output.as_deref(),
+            &cipher,
+        ),
+        Commands::History { key, verify: true } => {
+            cli::commands::history::verify(key.as_deref().or(single_env))
+        }
+        Commands::History { key, .. } => {
+            cli::commands::history::execute(key.as_deref().unwrap_or_default(), single_env, &cipher)
+        }
+        Commands::Rollback { to, note, force } => cli::commands::rollback::execute(
+            single_env,
+            to.as_deref(),
+            &cipher,
+            note.as_deref(),
+            *force,
+        ),
+        Commands::SelfCmd { action } => cli::commands::self_cmd::execute(action),
+        Commands::Snapshot { action } => cli::commands::snapshot::execute(action, &cipher),
+        Commands::Update { check } => cli::commands::update::execute(*check),
+        Commands::External(words) => {
+            let (name, rest) = words.split_first().expect("clap passes the command name");
+            let Some(program) = cli::commands::external::find(name) else {
+                Cli::command()
+                    .error(
+                        clap::error::ErrorKind::InvalidSubcommand,
+                        format!(
+                            "unrecognized subcommand '{name}'\n\n  \
+                             tip: no {}{name} plugin was found on PATH",
+                            cli::commands::external::COMMAND_PREFIX
+                        ),
+                    )
+                    .exit()
+            };
+            cli::commands::external::execute(&program, rest, single_env).map(|code| {
+                if code != 0 {
+                    std::process::exit(code);
+                }
+            })
+        }
     };
 
     if let Err(e) = result {
-        cli::output::error(&format!("Error: {e}"));
+        cli::output::error(&format!(
+            "{}: {}",
+            cli::i18n::t("error-label"),
+            cli::i18n::error_message(&e)
+        ));
         let code = match e {
             core::errors::VaulticError::ValidationFailed { .. } => 2,
             _ => 1,
@@ -0,0 +1,115 @@
+use assert_cmd::Command;
+use assert_cmd::cargo::cargo_bin_cmd;
+use predicates::prelude::*;
+
+fn vaultic() -> Command {
+    cargo_bin_cmd!("vaultic")
+}
+
+fn init_with_aliases(dir: &assert_fs::TempDir, aliases: &str) {
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+
+    let config_path = dir.path().join(".vaultic/config.toml");
+    let mut config = std::fs::read_to_string(&config_path).unwrap();
+    config.push_str(&format!("\n[aliases]\n{aliases}\n"));
+    std::fs::write(&config_path, config).unwrap();
+}
+
+#[test]
+fn alias_expands_and_keeps_extra_args() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    init_with_aliases(&dir, "recipients = \"keys list\"");
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["recipients", "--json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("["))
+        .stdout(predicate::str::contains("\"type\": \"age\""));
+}
+
+#[test]
+fn alias_is_read_from_the_vaultic_config_project() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    init_with_aliases(&dir, "recipients = \"keys list\"");
+    let elsewhere = assert_fs::TempDir::new().unwrap();
+
+    vaultic()
+        .current_dir(elsewhere.path())
+        .env("VAULTIC_CONFIG", dir.path().join(".vaultic"))
+        .args(["recipients", "--json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"type\": \"age\""));
+}
+
+#[test]
+fn builtin_commands_take_precedence_over_aliases() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    init_with_aliases(&dir, "status = \"keys list --json\"");
+
+    vaultic()
+        .current_dir(dir.path())
+        .arg("status")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("[").not());
+}
+
+#[test]
+fn unknown_command_still_fails() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    init_with_aliases(&dir, "recipients = \"keys list\"");
+
+    vaultic()
+        .current_dir(dir.path())
+        .arg("deploy-prep")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unrecognized subcommand"));
+}
+
+/// Unknown commands run a `vaultic-<name>` executable on PATH with the
+/// project in its environment.
+#[cfg(unix)]
+#[test]
+fn unknown_command_runs_plugin_on_path() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = assert_fs::TempDir::new().unwrap();
+    init_with_aliases(&dir, "deploy = \"status\"");
+    let bin = assert_fs::TempDir::new().unwrap();
+    let plugin = bin.path().join("vaultic-hello");
+    std::fs::write(
+        &plugin,
+        "#!/bin/sh\necho \"args=$*\"\necho \"dir=$VAULTIC_DIR\"\necho \"env=$VAULTIC_ENV\"\n\
+         [ -x \"$VAULTIC_BIN\" ] && echo bin-ok\nexit 3\n",
+    )
+    .unwrap();
+    std::fs::set_permissions(&plugin, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let path = std::env::join_paths(
+        std::iter::once(bin.path().to_path_buf())
+            .chain(std::env::split_paths(&std::env::var_os("PATH").unwrap())),
+    )
+    .unwrap();
+    let vaultic_dir = std::fs::canonicalize(dir.path().join(".vaultic")).unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .env("PATH", &path)
+        .args(["--env", "prod", "hello", "one", "--two"])
+        .assert()
+        .code(3)
+        .stdout(predicate::str::contains("args=one --two"))
+        .stdout(predicate::str::contains(format!(
+            "dir={}",
+            vaultic_dir.display()
+        )))
+        .stdout(predicate::str::contains("env=prod"))
+        .stdout(predicate::str::contains("bin-ok"));
+}
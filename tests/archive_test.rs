@@ -0,0 +1,157 @@
+use assert_cmd::Command;
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+
+fn vaultic() -> Command {
+    cargo_bin_cmd!("vaultic")
+}
+
+fn setup_project(dir: &assert_fs::TempDir) {
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+
+    dir.child(".env").write_str("DB_HOST=localhost").unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn archive_create_and_restore_round_trip() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    setup_project(&dir);
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["archive", "create", "-o", "backup.vaultic"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Archived 3 file(s)"));
+
+    dir.child("backup.vaultic")
+        .assert(predicate::str::contains("BEGIN AGE ENCRYPTED FILE"));
+
+    // Restore into a fresh project directory
+    let fresh = assert_fs::TempDir::new().unwrap();
+    std::fs::copy(
+        dir.path().join("backup.vaultic"),
+        fresh.path().join("backup.vaultic"),
+    )
+    .unwrap();
+
+    vaultic()
+        .current_dir(fresh.path())
+        .args(["archive", "restore", "backup.vaultic"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Restored 3 file(s)"));
+
+    vaultic()
+        .current_dir(fresh.path())
+        .args(["decrypt", "--env", "dev"])
+        .assert()
+        .success();
+    let content = std::fs::read_to_string(fresh.path().join(".env")).unwrap();
+    assert!(content.contains("DB_HOST=localhost"));
+}
+
+#[test]
+fn archive_restore_refuses_initialized_project_without_force() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    setup_project(&dir);
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["archive", "create", "-o", "backup.vaultic"])
+        .assert()
+        .success();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["archive", "restore", "backup.vaultic"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already initialized"));
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["archive", "restore", "backup.vaultic", "--force"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn archive_restore_missing_file_fails() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["archive", "restore", "nope.vaultic"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not found"));
+}
+
+#[test]
+fn bundle_extracts_on_a_host_without_the_project() {
+    use age::secrecy::ExposeSecret;
+
+    let dir = assert_fs::TempDir::new().unwrap();
+    setup_project(&dir);
+    let host = assert_fs::TempDir::new().unwrap();
+    let host_key = age::x25519::Identity::generate();
+    host.child("host.txt")
+        .write_str(host_key.to_string().expose_secret())
+        .unwrap();
+    let host_public = host_key.to_public().to_string();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["bundle", "--env", "dev"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--recipient"));
+    vaultic()
+        .current_dir(dir.path())
+        .args(["bundle", "--env", "dev", "--recipient", &host_public])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("into dev.vaultic-bundle"));
+    let log = std::fs::read_to_string(dir.path().join(".vaultic/audit.log")).unwrap();
+    assert!(log.contains(r#""action":"bundle_create""#), "{log}");
+
+    std::fs::copy(
+        dir.path().join("dev.vaultic-bundle"),
+        host.path().join("dev.vaultic-bundle"),
+    )
+    .unwrap();
+    // Encrypted to the host only, not to the project's recipients
+    vaultic()
+        .current_dir(host.path())
+        .args(["bundle", "extract", "dev.vaultic-bundle"])
+        .env_remove("VAULTIC_AGE_KEY")
+        .assert()
+        .failure();
+    vaultic()
+        .current_dir(host.path())
+        .args([
+            "bundle",
+            "extract",
+            "dev.vaultic-bundle",
+            "--key",
+            "host.txt",
+            "-o",
+            "app.env",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Extracted 1 variables of dev"));
+    let content = std::fs::read_to_string(host.path().join("app.env")).unwrap();
+    assert!(content.contains("DB_HOST=localhost"), "{content}");
+}
@@ -17,8 +17,7 @@ fn init_creates_audit_entry() {
 
     vaultic()
         .current_dir(dir.path())
-        .arg("init")
-        .write_stdin("y\n")
+        .args(["init", "--yes"])
         .assert()
         .success();
 
@@ -35,8 +34,7 @@ fn encrypt_creates_audit_entry() {
 
     vaultic()
         .current_dir(dir.path())
-        .arg("init")
-        .write_stdin("y\n")
+        .args(["init", "--yes"])
         .assert()
         .success();
 
@@ -70,8 +68,7 @@ fn decrypt_audit_includes_destination_path() {
     // Init without global key generation
     vaultic()
         .current_dir(dir.path())
-        .arg("init")
-        .write_stdin("n\n")
+        .args(["init", "--no-keygen"])
         .assert()
         .success();
 
@@ -121,8 +118,7 @@ fn log_shows_entries() {
 
     vaultic()
         .current_dir(dir.path())
-        .arg("init")
-        .write_stdin("y\n")
+        .args(["init", "--yes"])
         .assert()
         .success();
 
@@ -140,8 +136,7 @@ fn log_empty_no_entries() {
 
     vaultic()
         .current_dir(dir.path())
-        .arg("init")
-        .write_stdin("y\n")
+        .args(["init", "--yes"])
         .assert()
         .success();
 
@@ -162,8 +157,7 @@ fn log_filter_author_no_match() {
 
     vaultic()
         .current_dir(dir.path())
-        .arg("init")
-        .write_stdin("y\n")
+        .args(["init", "--yes"])
         .assert()
         .success();
 
@@ -181,8 +175,7 @@ fn log_last_limits_entries() {
 
     vaultic()
         .current_dir(dir.path())
-        .arg("init")
-        .write_stdin("y\n")
+        .args(["init", "--yes"])
         .assert()
         .success();
 
@@ -220,8 +213,7 @@ fn log_invalid_since_date_fails() {
 
     vaultic()
         .current_dir(dir.path())
-        .arg("init")
-        .write_stdin("y\n")
+        .args(["init", "--yes"])
         .assert()
         .success();
 
@@ -233,16 +225,146 @@ fn log_invalid_since_date_fails() {
         .stderr(predicate::str::contains("Invalid date format"));
 }
 
+#[test]
+fn log_json_prints_entries_as_array() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+    dir.child(".env").write_str("SECRET=1").unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .success();
+
+    let out = vaultic()
+        .current_dir(dir.path())
+        .args(["log", "--last", "1", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let entries: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    let entries = entries.as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["action"], "encrypt");
+    assert_eq!(entries[0]["files"], serde_json::json!(["dev.env.enc"]));
+}
+
+#[test]
+fn json_is_rejected_by_unsupported_commands() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["prune", "--json"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--json is only supported"));
+}
+
 // ─── Status tests ────────────────────────────────────────────────
 
+#[test]
+fn status_json_reports_environments() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+    dir.child(".env").write_str("A=1\nB=2").unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .success();
+
+    let out = vaultic()
+        .current_dir(dir.path())
+        .args(["--json", "status"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let status: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(status["ok"], true);
+    assert_eq!(status["problems"].as_array().unwrap().len(), 0);
+    assert_eq!(status["cipher"], "age");
+    assert_eq!(status["recipients"].as_array().unwrap().len(), 1);
+    assert_eq!(status["local"]["env_variables"], 2);
+    assert_eq!(status["local"]["env_in_gitignore"], true);
+
+    let envs = status["environments"].as_array().unwrap();
+    let dev = envs.iter().find(|e| e["name"] == "dev").unwrap();
+    assert_eq!(dev["encrypted"], true);
+    assert_eq!(dev["file"], "dev.env.enc");
+    let prod = envs.iter().find(|e| e["name"] == "prod").unwrap();
+    assert_eq!(prod["encrypted"], false);
+    assert!(prod["size"].is_null());
+}
+
+#[test]
+fn status_warns_about_decrypt_burst() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+    dir.child(".env").write_str("A=1").unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .success();
+    for _ in 0..10 {
+        vaultic()
+            .current_dir(dir.path())
+            .args(["resolve", "--env", "dev"])
+            .assert()
+            .success();
+    }
+
+    vaultic()
+        .current_dir(dir.path())
+        .arg("status")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Audit review"))
+        .stdout(predicate::str::contains(
+            "decrypted 10 times within 10 minutes",
+        ));
+
+    let out = vaultic()
+        .current_dir(dir.path())
+        .args(["--json", "status"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let status: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(status["ok"], true);
+    assert_eq!(status["anomalies"][0]["kind"], "decrypt_burst");
+    assert_eq!(status["anomalies"][0]["count"], 10);
+}
+
 #[test]
 fn status_shows_project_info() {
     let dir = assert_fs::TempDir::new().unwrap();
 
     vaultic()
         .current_dir(dir.path())
-        .arg("init")
-        .write_stdin("y\n")
+        .args(["init", "--yes"])
         .assert()
         .success();
 
@@ -262,8 +384,7 @@ fn status_shows_env_files() {
 
     vaultic()
         .current_dir(dir.path())
-        .arg("init")
-        .write_stdin("y\n")
+        .args(["init", "--yes"])
         .assert()
         .success();
 
@@ -276,6 +397,39 @@ fn status_shows_env_files() {
         .stdout(predicate::str::contains("not encrypted"));
 }
 
+#[test]
+fn status_fails_when_env_not_ignored() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+    dir.child(".gitignore").write_str("target/\n").unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .arg("status")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Problems"))
+        .stderr(predicate::str::contains("Found 1 problem(s)"));
+
+    let out = vaultic()
+        .current_dir(dir.path())
+        .args(["--json", "status"])
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+    let status: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(status["ok"], false);
+    assert_eq!(status["problems"][0]["kind"], "env_not_ignored");
+    assert_eq!(status["local"]["env_in_gitignore"], false);
+}
+
 #[test]
 fn status_without_init_fails() {
     let dir = assert_fs::TempDir::new().unwrap();
@@ -302,8 +456,7 @@ fn hook_install_and_uninstall() {
 
     vaultic()
         .current_dir(dir.path())
-        .arg("init")
-        .write_stdin("y\n")
+        .args(["init", "--yes"])
         .assert()
         .success();
 
@@ -334,8 +487,7 @@ fn hook_install_without_git_fails() {
 
     vaultic()
         .current_dir(dir.path())
-        .arg("init")
-        .write_stdin("y\n")
+        .args(["init", "--yes"])
         .assert()
         .success();
 
@@ -359,8 +511,7 @@ fn hook_install_refuses_foreign_hook() {
 
     vaultic()
         .current_dir(dir.path())
-        .arg("init")
-        .write_stdin("y\n")
+        .args(["init", "--yes"])
         .assert()
         .success();
 
@@ -379,3 +530,220 @@ fn hook_install_refuses_foreign_hook() {
         .failure()
         .stderr(predicate::str::contains("not installed by Vaultic"));
 }
+
+#[cfg(unix)]
+#[test]
+fn post_commit_hook_records_no_verify_bypass() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let git = |args: &[&str]| {
+        let bin_dir = std::path::Path::new(env!("CARGO_BIN_EXE_vaultic"))
+            .parent()
+            .unwrap();
+        let path = format!("{}:{}", bin_dir.display(), std::env::var("PATH").unwrap());
+        std::process::Command::new("git")
+            .args(["-c", "user.name=Dev", "-c", "user.email=dev@example.com"])
+            .args(args)
+            .current_dir(dir.path())
+            .env("PATH", path)
+            .output()
+            .unwrap()
+    };
+
+    git(&["init"]);
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["hook", "install", "--post-commit"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Post-commit hook installed"));
+
+    dir.child(".env.local").write_str("API_KEY=leaked").unwrap();
+    git(&["add", ".env.local"]);
+    let commit = git(&["commit", "--no-verify", "-m", "oops"]);
+    assert!(commit.status.success());
+
+    vaultic()
+        .current_dir(dir.path())
+        .arg("status")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "1 commit(s) skipped the pre-commit hook",
+        ))
+        .stdout(predicate::str::contains(".env.local"));
+    // Reported once
+    vaultic()
+        .current_dir(dir.path())
+        .arg("status")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("skipped the pre-commit hook").not());
+
+    let log = std::fs::read_to_string(dir.path().join(".vaultic/audit.log")).unwrap();
+    assert!(log.contains("\"hook_bypass\""));
+    assert!(log.contains(".env.local"));
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["hook", "uninstall"])
+        .assert()
+        .success();
+    assert!(!dir.path().join(".git/hooks/post-commit").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn pre_push_hook_blocks_environments_encrypted_for_old_recipients() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let remote = assert_fs::TempDir::new().unwrap();
+    let git = |args: &[&str]| {
+        let bin_dir = std::path::Path::new(env!("CARGO_BIN_EXE_vaultic"))
+            .parent()
+            .unwrap();
+        let path = format!("{}:{}", bin_dir.display(), std::env::var("PATH").unwrap());
+        std::process::Command::new("git")
+            .args(["-c", "user.name=Dev", "-c", "user.email=dev@example.com"])
+            .args(args)
+            .current_dir(dir.path())
+            .env("PATH", path)
+            .output()
+            .unwrap()
+    };
+
+    std::process::Command::new("git")
+        .args(["init", "--bare", "-q"])
+        .current_dir(remote.path())
+        .output()
+        .unwrap();
+    git(&["init", "-q"]);
+    git(&[
+        "remote",
+        "add",
+        "origin",
+        &remote.path().display().to_string(),
+    ]);
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+    dir.child(".gitignore").write_str(".env\n").unwrap();
+    dir.child(".env").write_str("API_KEY=secret").unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .success();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["hook", "install", "--hook", "pre-push"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pre-push hook installed"));
+    assert!(!dir.path().join(".git/hooks/pre-commit").exists());
+
+    git(&["add", "-A"]);
+    git(&["commit", "-q", "-m", "base"]);
+    assert!(git(&["push", "-q", "origin", "HEAD"]).status.success());
+
+    // A new recipient is committed, but dev is not re-encrypted for it
+    let pubkey = age::x25519::Identity::generate().to_public().to_string();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["keys", "add", &pubkey])
+        .assert()
+        .success();
+    git(&["commit", "-q", "-am", "add recipient"]);
+    let push = git(&["push", "-q", "origin", "HEAD"]);
+    assert!(!push.status.success());
+    let stderr = String::from_utf8_lossy(&push.stderr);
+    assert!(stderr.contains("Push blocked"), "{stderr}");
+    assert!(stderr.contains("dev.env.enc"), "{stderr}");
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--all"])
+        .assert()
+        .success();
+    git(&["commit", "-q", "-am", "re-encrypt"]);
+    assert!(git(&["push", "-q", "origin", "HEAD"]).status.success());
+}
+
+#[cfg(unix)]
+#[test]
+fn post_merge_hook_reports_changed_environments() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(["-c", "user.name=Dev", "-c", "user.email=dev@example.com"])
+            .args(args)
+            .current_dir(dir.path())
+            .output()
+            .unwrap()
+    };
+
+    git(&["init", "-q"]);
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+    dir.child(".env").write_str("API_KEY=one").unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .success();
+    git(&["add", ".vaultic"]);
+    git(&["commit", "-q", "-m", "base"]);
+    let base = String::from_utf8(git(&["rev-parse", "HEAD"]).stdout).unwrap();
+
+    dir.child(".env").write_str("API_KEY=two").unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .success();
+    git(&["commit", "-q", "-am", "rotate"]);
+    git(&["update-ref", "ORIG_HEAD", base.trim()]);
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["hook", "post-merge"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "The merge changed encrypted environment(s): dev",
+        ))
+        .stdout(predicate::str::contains("vaultic decrypt --env dev"));
+
+    // A file checkout (flag 0) is not a branch switch
+    vaultic()
+        .current_dir(dir.path())
+        .args(["hook", "post-checkout", base.trim(), "HEAD", "0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("changed encrypted").not());
+}
+
+#[test]
+fn test_mode_produces_deterministic_audit_entries() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .env("VAULTIC_TEST_MODE", "1")
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+
+    let content = std::fs::read_to_string(dir.path().join(".vaultic/audit.log")).unwrap();
+    assert!(content.contains("\"timestamp\":\"2026-01-01T00:00:00Z\""));
+    assert!(content.contains("\"author\":\"vaultic-test\""));
+    assert!(content.contains("\"email\":\"test@vaultic.invalid\""));
+}
@@ -85,6 +85,44 @@ fn check_empty_values() {
         .stdout(predicate::str::contains("API_KEY"));
 }
 
+#[test]
+fn check_junit_report_has_a_case_per_variable() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    dir.child(".env")
+        .write_str("DB_HOST=localhost\nDEBUG=\nLEGACY=1")
+        .unwrap();
+    dir.child(".env.template")
+        .write_str("DB_HOST=\nDEBUG=\nAPI_KEY=")
+        .unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["check", "--report", "junit"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "<testsuite name=\".env\" tests=\"4\" failures=\"3\" errors=\"0\">",
+        ))
+        .stdout(predicate::str::contains(
+            "<testcase classname=\".env\" name=\"DB_HOST\"/>",
+        ))
+        .stdout(predicate::str::contains("<failure type=\"missing\""))
+        .stdout(predicate::str::contains("<failure type=\"empty\""))
+        .stdout(predicate::str::contains("<failure type=\"extra\""))
+        .stdout(predicate::str::contains("variables present").not());
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["check", "--report", "json", "-o", "check.json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Report written to check.json"))
+        .stdout(predicate::str::contains("variables present"));
+    let json = std::fs::read_to_string(dir.path().join("check.json")).unwrap();
+    assert!(json.contains("\"missing\": [\n    \"API_KEY\"\n  ]"));
+}
+
 #[test]
 fn check_missing_env_file_fails() {
     let dir = assert_fs::TempDir::new().unwrap();
@@ -151,6 +189,140 @@ fn diff_shows_changes() {
         .stdout(predicate::str::contains("REDIS"));
 }
 
+#[test]
+fn diff_only_keys_lists_names_grouped_by_change() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    dir.child("dev.env")
+        .write_str("DB=localhost\nDEBUG=true\nPORT=5432")
+        .unwrap();
+    dir.child("prod.env")
+        .write_str("DB=rds.aws.com\nREDIS=redis.prod\nPORT=5432")
+        .unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["diff", "dev.env", "prod.env", "--only-keys"])
+        .assert()
+        .success()
+        .stdout(
+            "dev.env → prod.env: 1 added, 1 removed, 1 modified\n\
+             Added (1): REDIS\n\
+             Removed (1): DEBUG\n\
+             Modified (1): DB\n",
+        );
+}
+
+#[test]
+fn diff_classifies_modifications_without_raw_values() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    dir.child("a.env")
+        .write_str("TOKEN=deadbeefdeadbeef\nDB_URL=postgres://db-a.internal/app")
+        .unwrap();
+    dir.child("b.env")
+        .write_str("TOKEN=3q2+7w==\nDB_URL=postgres://db-b.internal/app")
+        .unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["diff", "a.env", "b.env"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("16 hex"))
+        .stdout(predicate::str::contains("8 base64"))
+        .stdout(predicate::str::contains("length, charset"))
+        .stdout(predicate::str::contains("url host"))
+        .stdout(predicate::str::contains("deadbeef").not())
+        .stdout(predicate::str::contains("db-b.internal").not());
+}
+
+#[test]
+fn diff_side_by_side_masks_values_by_default() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    dir.child("a.env")
+        .write_str(
+            "APP=vaultic
+DB_PASS=hunter2
+DEBUG=true",
+        )
+        .unwrap();
+    dir.child("b.env")
+        .write_str(
+            "APP=vaultic
+DB_PASS=correct-horse
+REDIS=redis.prod",
+        )
+        .unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .env("COLUMNS", "120")
+        .args(["diff", "a.env", "b.env", "--side-by-side"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("APP=[7 alnum]"))
+        .stdout(predicate::str::contains("DB_PASS=[13 base64]"))
+        .stdout(predicate::str::contains("REDIS="))
+        .stdout(predicate::str::contains("hunter2").not())
+        .stdout(predicate::str::contains("redis.prod").not());
+
+    vaultic()
+        .current_dir(dir.path())
+        .env("COLUMNS", "120")
+        .args(["diff", "a.env", "b.env", "--side-by-side", "--show-values"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("DB_PASS=hunter2"))
+        .stdout(predicate::str::contains("DB_PASS=correct-horse"));
+}
+
+#[test]
+fn diff_table_masks_values_unless_asked_and_allowed() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--no-keygen"])
+        .assert()
+        .success();
+    dir.child("a.env").write_str("DB_PASS=hunter2").unwrap();
+    dir.child("b.env")
+        .write_str("DB_PASS=correct-horse")
+        .unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .env("COLUMNS", "120")
+        .args(["diff", "a.env", "b.env"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("7 alnum"))
+        .stdout(predicate::str::contains("hunter2").not());
+
+    vaultic()
+        .current_dir(dir.path())
+        .env("COLUMNS", "120")
+        .args(["diff", "a.env", "b.env", "--show-values"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("hunter2"))
+        .stdout(predicate::str::contains("correct-horse"));
+
+    let config_path = dir.path().join(".vaultic/config.toml");
+    let mut config = std::fs::read_to_string(&config_path).unwrap();
+    config.push_str("\n[ui]\nredact_values = true\n");
+    std::fs::write(&config_path, config).unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["diff", "a.env", "b.env", "--show-values"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("hunter2").not())
+        .stderr(predicate::str::contains("redact_values = true"));
+}
+
 #[test]
 fn diff_summary_counts() {
     let dir = assert_fs::TempDir::new().unwrap();
@@ -253,3 +425,153 @@ fn check_all_good_message() {
         .success()
         .stdout(predicate::str::contains("all good"));
 }
+
+#[test]
+fn check_json_reports_issues() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    dir.child(".env")
+        .write_str("DB_HOST=localhost\nEMPTY=\nOLD_VAR=legacy")
+        .unwrap();
+    dir.child(".env.template")
+        .write_str("DB_HOST=\nEMPTY=\nAPI_KEY=")
+        .unwrap();
+
+    let out = vaultic()
+        .current_dir(dir.path())
+        .args(["check", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let report: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(report["total"], 3);
+    assert_eq!(report["present"], 2);
+    assert_eq!(report["missing"], serde_json::json!(["API_KEY"]));
+    assert_eq!(report["extra"], serde_json::json!(["OLD_VAR"]));
+    assert_eq!(report["empty_values"], serde_json::json!(["EMPTY"]));
+    assert_eq!(report["ok"], false);
+}
+
+#[test]
+fn diff_json_describes_values_without_revealing_them() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    dir.child("a.env")
+        .write_str("PORT=5432\nOLD=x\nSAME=1")
+        .unwrap();
+    dir.child("b.env")
+        .write_str("PORT=54321\nNEW=y\nSAME=1")
+        .unwrap();
+
+    let out = vaultic()
+        .current_dir(dir.path())
+        .args(["diff", "a.env", "b.env", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let text = String::from_utf8(out).unwrap();
+    assert!(!text.contains("54321"));
+
+    let diff: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(diff["left"], "a.env");
+    assert_eq!(diff["right"], "b.env");
+    let entries = diff["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 3);
+
+    let port = entries.iter().find(|e| e["key"] == "PORT").unwrap();
+    assert_eq!(port["change"], "modified");
+    assert_eq!(port["left_shape"], "4 numeric");
+    assert_eq!(port["right_shape"], "5 numeric");
+
+    let new = entries.iter().find(|e| e["key"] == "NEW").unwrap();
+    assert_eq!(new["change"], "added");
+    assert!(new["left_shape"].is_null());
+}
+
+#[test]
+fn diff_compares_dotenv_with_yaml_file() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    dir.child(".env")
+        .write_str("DB_HOST=localhost\nDB_PORT=5432")
+        .unwrap();
+    dir.child("secrets.yaml")
+        .write_str("db:\n  host: localhost\n  port: 6543\n")
+        .unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["diff", ".env", "secrets.yaml"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("DB_PORT"))
+        .stdout(predicate::str::contains("DB_HOST").not())
+        .stdout(predicate::str::contains("1 modified"));
+}
+
+#[test]
+fn check_all_outputs_summarizes_every_output() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    dir.child(".vaultic/config.toml")
+        .write_str(
+            "[vaultic]\nversion = \"0.1.0\"\ndefault_cipher = \"age\"\n\n\
+             [environments]\ndev = {}\n\n\
+             [outputs]\n\
+             backend = { path = \"backend/.env\" }\n\
+             web = { path = \"apps/web/.env.local\", template = \"apps/web/.env.example\" }\n\
+             worker = { path = \"worker/.env\" }\n",
+        )
+        .unwrap();
+    dir.child("backend/.env")
+        .write_str("DB_URL=x\nPORT=1")
+        .unwrap();
+    dir.child("backend/.env.template")
+        .write_str("DB_URL=\nPORT=")
+        .unwrap();
+    dir.child("apps/web/.env.local").write_str("API=x").unwrap();
+    dir.child("apps/web/.env.example")
+        .write_str("API=\nSENTRY_DSN=")
+        .unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["check", "--all-outputs"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("backend/.env"))
+        .stdout(predicate::str::contains("2/2"))
+        .stdout(predicate::str::contains("1/2"))
+        .stdout(predicate::str::contains("file not found"))
+        .stdout(predicate::str::contains("2 of 3 output(s) need attention"));
+
+    let out = vaultic()
+        .current_dir(dir.path())
+        .args(["--json", "check", "--all-outputs"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(json["ok"], false);
+    assert_eq!(json["outputs"][0]["name"], "backend");
+    assert_eq!(json["outputs"][0]["ok"], true);
+    assert_eq!(json["outputs"][1]["missing"][0], "SENTRY_DSN");
+    assert_eq!(json["outputs"][2]["error"], "file not found");
+}
+
+#[test]
+fn check_all_outputs_requires_outputs_section() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    dir.child(".vaultic/config.toml")
+        .write_str("[vaultic]\nversion = \"0.1.0\"\ndefault_cipher = \"age\"\n\n[environments]\ndev = {}\n")
+        .unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["check", "--all-outputs"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No outputs defined"));
+}
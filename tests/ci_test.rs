@@ -11,8 +11,7 @@ fn setup_env(dir: &assert_fs::TempDir, env_name: &str, content: &str) {
     if !dir.path().join(".vaultic").exists() {
         vaultic()
             .current_dir(dir.path())
-            .arg("init")
-            .write_stdin("y\n")
+            .args(["init", "--yes"])
             .assert()
             .success();
     }
@@ -0,0 +1,120 @@
+use assert_cmd::Command;
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+
+fn vaultic() -> Command {
+    cargo_bin_cmd!("vaultic")
+}
+
+fn init_with_config_line(dir: &assert_fs::TempDir, line: &str) {
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--no-keygen"])
+        .assert()
+        .success();
+
+    let config_path = dir.path().join(".vaultic/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    let config = config.replacen("[vaultic]\n", &format!("[vaultic]\n{line}\n"), 1);
+    std::fs::write(&config_path, config).unwrap();
+}
+
+#[test]
+fn identity_path_expands_project_root() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    init_with_config_line(&dir, "identity = \"${VAULTIC_PROJECT_ROOT}/keys/id.txt\"");
+
+    let identity = age::x25519::Identity::generate();
+    let public_key = identity.to_public().to_string();
+    dir.child("keys/id.txt")
+        .write_str(&format!(
+            "{}\n",
+            age::secrecy::ExposeSecret::expose_secret(&identity.to_string())
+        ))
+        .unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .env("VAULTIC_PROJECT_ROOT", dir.path())
+        .args(["keys", "show"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(public_key));
+}
+
+#[test]
+fn template_path_expands_home() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let home = assert_fs::TempDir::new().unwrap();
+    init_with_config_line(&dir, "template = \"~/shared/app.template\"");
+
+    home.child("shared/app.template")
+        .write_str("ONLY_IN_SHARED=\n")
+        .unwrap();
+    dir.child(".env").write_str("ONLY_IN_SHARED=1\n").unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .env("HOME", home.path())
+        .arg("check")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1/1 variables present"));
+}
+
+#[test]
+fn env_template_may_sit_beside_the_vaultic_dir() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes", "--template", "minimal"])
+        .assert()
+        .success();
+
+    let config_path = dir.path().join(".vaultic/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    let config = config.replacen(
+        "prod = { file = \"prod.env\" }",
+        "prod = { file = \"prod.env\", template = \"../templates/prod.env.template\" }",
+        1,
+    );
+    std::fs::write(&config_path, config).unwrap();
+
+    dir.child("templates/prod.env.template")
+        .write_str("API_KEY=\nDB_URL=\n")
+        .unwrap();
+    dir.child(".env")
+        .write_str("API_KEY=prod-key\nDB_URL=postgres://db\n")
+        .unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "prod"])
+        .assert()
+        .success();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["check", "--env", "prod"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2/2 variables present"));
+}
+
+#[test]
+fn template_path_outside_project_and_home_is_rejected() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let home = assert_fs::TempDir::new().unwrap();
+    init_with_config_line(&dir, "template = \"../elsewhere/.env.template\"");
+    dir.child(".env").write_str("A=1\n").unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .env("HOME", home.path())
+        .arg("check")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "outside the project and your home directory",
+        ));
+}
@@ -0,0 +1,85 @@
+use assert_cmd::Command;
+use assert_cmd::cargo::cargo_bin_cmd;
+use predicates::prelude::*;
+
+fn vaultic() -> Command {
+    cargo_bin_cmd!("vaultic")
+}
+
+#[test]
+fn config_schema_prints_valid_json_schema() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    let output = vaultic()
+        .current_dir(dir.path())
+        .args(["config", "schema"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(schema["title"], "Vaultic config.toml");
+    assert!(schema["properties"]["environments"].is_object());
+    assert!(schema["properties"]["audit"].is_object());
+    assert!(schema["$defs"]["ValidationRule"]["properties"]["min_length"].is_object());
+
+    let required = schema["required"].as_array().unwrap();
+    assert!(required.contains(&"vaultic".into()));
+    assert!(required.contains(&"environments".into()));
+}
+
+#[test]
+fn config_schema_writes_to_output_file() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["config", "schema", "-o", "vaultic.schema.json"])
+        .assert()
+        .success();
+
+    let content = std::fs::read_to_string(dir.path().join("vaultic.schema.json")).unwrap();
+    let schema: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert!(schema["$defs"]["VaulticSection"].is_object());
+}
+
+#[test]
+fn upgrade_config_adds_commented_examples_once() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--no-input"])
+        .assert()
+        .success();
+    let config = dir.path().join(".vaultic/config.toml");
+    let original = std::fs::read_to_string(&config).unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["upgrade-config", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("# [outputs]"));
+    assert_eq!(std::fs::read_to_string(&config).unwrap(), original);
+
+    vaultic()
+        .current_dir(dir.path())
+        .arg("upgrade-config")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("rotation_days"))
+        .stdout(predicate::str::contains("[audit]").not());
+    let upgraded = std::fs::read_to_string(&config).unwrap();
+    assert!(upgraded.starts_with(original.lines().next().unwrap()));
+    assert!(upgraded.contains("# rotation_days = 90"));
+
+    // Still a valid config, and a second run adds nothing
+    vaultic()
+        .current_dir(dir.path())
+        .arg("upgrade-config")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "already mentions every supported setting",
+        ));
+}
@@ -0,0 +1,88 @@
+use assert_cmd::Command;
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+
+fn vaultic() -> Command {
+    cargo_bin_cmd!("vaultic")
+}
+
+fn git(dir: &assert_fs::TempDir, args: &[&str]) {
+    let status = std::process::Command::new("git")
+        .current_dir(dir.path())
+        .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .output()
+        .unwrap()
+        .status;
+    assert!(status.success(), "git {args:?} failed");
+}
+
+fn encrypt(dir: &assert_fs::TempDir, content: &str) {
+    dir.child(".env").write_str(content).unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "prod"])
+        .assert()
+        .success();
+}
+
+fn setup() -> assert_fs::TempDir {
+    let dir = assert_fs::TempDir::new().unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+    git(&dir, &["init", "-q"]);
+    encrypt(&dir, "DB_HOST=db.internal\nPORT=5432\nLEGACY=1\n");
+    git(&dir, &["add", ".vaultic"]);
+    git(&dir, &["commit", "-q", "-m", "prod"]);
+    dir
+}
+
+#[test]
+fn diff_rev_shows_changes_since_revision() {
+    let dir = setup();
+    encrypt(
+        &dir,
+        "DB_HOST=db.replica\nPORT=5432\nSENTRY_DSN=https://x\n",
+    );
+    git(&dir, &["commit", "-q", "-am", "rotate"]);
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["diff", "--env", "prod", "--rev", "HEAD~1", "--only-keys"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("prod@HEAD~1 → prod"))
+        .stdout(predicate::str::contains("Added (1): SENTRY_DSN"))
+        .stdout(predicate::str::contains("Removed (1): LEGACY"))
+        .stdout(predicate::str::contains("Modified (1): DB_HOST"));
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["diff", "--env", "prod", "--rev", "HEAD"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("prod has not changed since HEAD"));
+}
+
+#[test]
+fn diff_rev_rejects_unknown_revision_and_files() {
+    let dir = setup();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["diff", "--env", "prod", "--rev", "nope"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown git revision: 'nope'"));
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["diff", ".env", "--env", "prod", "--rev", "HEAD"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--rev compares one environment"));
+}
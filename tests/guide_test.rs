@@ -0,0 +1,64 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use predicates::prelude::*;
+
+/// Run vaultic with given args.
+fn vaultic() -> assert_cmd::Command {
+    cargo_bin_cmd!("vaultic")
+}
+
+#[test]
+fn guide_lists_topics_and_fills_in_project_names() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .arg("guide")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("onboarding"))
+        .stdout(predicate::str::contains("rotation"))
+        .stdout(predicate::str::contains("ci"));
+
+    // Outside a project the guides use generic names
+    vaultic()
+        .current_dir(dir.path())
+        .args(["guide", "ci"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("vaultic ci export --env prod"));
+
+    vaultic()
+        .current_dir(dir.path())
+        .args([
+            "init",
+            "--no-keygen",
+            "--environments",
+            "local,production",
+            "--default-env",
+            "local",
+        ])
+        .assert()
+        .success();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["guide", "onboarding"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("base, local, production"))
+        .stdout(predicate::str::contains("vaultic decrypt --env local"))
+        .stdout(predicate::str::contains("{{").not());
+    vaultic()
+        .current_dir(dir.path())
+        .args(["guide", "rotation"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("vaultic edit --env production"))
+        .stdout(predicate::str::contains(".vaultic/recipients.txt"));
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["guide", "backups"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("onboarding, rotation, ci"));
+}
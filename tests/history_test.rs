@@ -0,0 +1,207 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+
+/// Run vaultic with given args.
+fn vaultic() -> assert_cmd::Command {
+    cargo_bin_cmd!("vaultic")
+}
+
+fn sha256(value: &str) -> String {
+    use sha2::{Digest, Sha256};
+    format!("{:x}", Sha256::digest(value.as_bytes()))
+}
+
+#[test]
+fn history_records_changes_from_encrypt_and_set() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+
+    dir.child(".env")
+        .write_str("DATABASE_URL=postgres://one\nOTHER=1\n")
+        .unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .success();
+    dir.child(".vaultic/history/dev.history.enc")
+        .assert(predicate::path::exists());
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["set", "DATABASE_URL=postgres://two", "--env", "dev"])
+        .assert()
+        .success();
+    // Re-encrypting unchanged content records nothing
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--all"])
+        .assert()
+        .success();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["history", "DATABASE_URL", "--env", "dev"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("added"))
+        .stdout(predicate::str::contains("modified"))
+        .stdout(predicate::str::contains(&sha256("postgres://two")[..8]))
+        .stdout(predicate::str::contains("postgres://").not());
+
+    let out = vaultic()
+        .current_dir(dir.path())
+        .args(["history", "DATABASE_URL", "--env", "dev", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let entries: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    let entries = entries.as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0]["change"], "added");
+    assert!(entries[0]["old_hash"].is_null());
+    assert_eq!(entries[1]["change"], "modified");
+    assert_eq!(entries[1]["old_hash"], sha256("postgres://one"));
+    assert_eq!(entries[1]["new_hash"], sha256("postgres://two"));
+    assert!(entries[1]["author"].is_string());
+}
+
+#[test]
+fn history_without_records_explains_itself() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--no-keygen"])
+        .assert()
+        .success();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["history", "API_KEY", "--env", "prod"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "No recorded changes to API_KEY in prod",
+        ));
+}
+
+#[test]
+fn history_verify_detects_ciphertext_replaced_outside_vaultic() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+    dir.child(".env").write_str("API_KEY=one\n").unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "prod"])
+        .assert()
+        .success();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["set", "API_KEY=two", "--env", "prod"])
+        .assert()
+        .success();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["history", "prod", "--verify"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "matches the last of 2 recorded state(s)",
+        ));
+
+    // Swap in another environment's ciphertext, as a bad merge would
+    dir.child(".env").write_str("API_KEY=evil\n").unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .success();
+    std::fs::copy(
+        dir.path().join(".vaultic/dev.env.enc"),
+        dir.path().join(".vaultic/prod.env.enc"),
+    )
+    .unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["history", "prod", "--verify"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "prod was modified outside Vaultic",
+        ))
+        .stderr(predicate::str::contains(
+            "changed outside Vaultic 1 time(s)",
+        ));
+
+    // Re-encrypting does not hide the replacement
+    vaultic()
+        .current_dir(dir.path())
+        .args(["set", "API_KEY=three", "--env", "prod"])
+        .assert()
+        .success();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["history", "prod", "--verify"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("outside Vaultic between"));
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["verify", "--accept-changes"])
+        .assert()
+        .success();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["history", "prod", "--verify"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn history_verify_follows_the_file_encrypt_writes_for_custom_files() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes", "--template", "minimal"])
+        .assert()
+        .success();
+    let config_path = dir.path().join(".vaultic/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(
+        &config_path,
+        config.replacen(
+            "prod = { file = \"prod.env\" }",
+            "prod = { file = \"production.env\" }",
+            1,
+        ),
+    )
+    .unwrap();
+
+    dir.child(".env").write_str("API_KEY=one\n").unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "prod"])
+        .assert()
+        .success();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["history", "prod", "--verify"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "matches the last of 1 recorded state(s)",
+        ));
+}
@@ -0,0 +1,58 @@
+use assert_cmd::Command;
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+
+fn vaultic() -> Command {
+    let mut cmd = cargo_bin_cmd!("vaultic");
+    cmd.env_remove("VAULTIC_LANG");
+    cmd
+}
+
+#[test]
+fn vaultic_lang_selects_spanish_messages() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    dir.child(".env").write_str("KEY=value").unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .env("VAULTIC_LANG", "es_ES.UTF-8")
+        .arg("encrypt")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Vaultic no está inicializado en este directorio",
+        ))
+        .stderr(predicate::str::contains("not initialized").not());
+}
+
+#[test]
+fn ui_language_in_config_translates_errors() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--no-keygen"])
+        .assert()
+        .success();
+    let config_path = dir.path().join(".vaultic/config.toml");
+    let mut config = std::fs::read_to_string(&config_path).unwrap();
+    config.push_str("\n[ui]\nlanguage = \"es\"\n");
+    std::fs::write(&config_path, config).unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["decrypt", "--env", "qa"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No se encontró el entorno 'qa'"));
+
+    // VAULTIC_LANG wins over the config.
+    vaultic()
+        .current_dir(dir.path())
+        .env("VAULTIC_LANG", "en")
+        .args(["decrypt", "--env", "qa"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("'qa'"))
+        .stderr(predicate::str::contains("No se encontró").not());
+}
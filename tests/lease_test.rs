@@ -0,0 +1,87 @@
+#![cfg(unix)]
+
+use assert_cmd::Command;
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+
+fn vaultic() -> Command {
+    cargo_bin_cmd!("vaultic")
+}
+
+/// Init a project whose `dev` holds `TOKEN=static`, with `leases`
+/// appended to its config.
+fn setup(leases: &str) -> assert_fs::TempDir {
+    let dir = assert_fs::TempDir::new().unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+    dir.child(".env")
+        .write_str("TOKEN=static\nDB_HOST=localhost\n")
+        .unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .success();
+
+    let config_path = dir.path().join(".vaultic/config.toml");
+    let mut config = std::fs::read_to_string(&config_path).unwrap();
+    config.push_str(leases);
+    std::fs::write(&config_path, config).unwrap();
+    dir
+}
+
+#[test]
+fn run_injects_leased_variables_over_static_ones() {
+    let dir =
+        setup("\n[leases.token]\nprovider = \"command\"\ncommand = [\"echo\", \"TOKEN=leased\"]\n");
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["run", "--env", "dev", "--", "sh", "-c"])
+        .arg("echo \"$TOKEN $DB_HOST ${VAULTIC_LEASE_FILE:-none}\"")
+        .assert()
+        .success()
+        .stdout("leased localhost none\n");
+}
+
+#[test]
+fn run_refreshes_expiring_lease_into_lease_file() {
+    let dir = setup(
+        "\n[leases.token]\nprovider = \"command\"\n\
+         command = [\"sh\", \"-c\", \"echo TOKEN=t-$$\"]\nttl_seconds = 1\n",
+    );
+
+    let output = vaultic()
+        .current_dir(dir.path())
+        .args(["run", "--env", "dev", "--", "sh", "-c"])
+        .arg("echo \"$TOKEN\"; sleep 3; cat \"$VAULTIC_LEASE_FILE\"; echo \"$VAULTIC_LEASE_FILE\"")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let stdout = String::from_utf8(output).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 3, "{stdout}");
+    assert!(lines[0].starts_with("t-"));
+    assert!(lines[1].starts_with("TOKEN=t-"));
+    assert_ne!(lines[1], format!("TOKEN={}", lines[0]));
+    assert!(!std::path::Path::new(lines[2]).exists());
+}
+
+#[test]
+fn failed_lease_stops_run() {
+    let dir = setup("\n[leases.token]\nprovider = \"command\"\ncommand = [\"false\"]\n");
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["run", "--env", "dev", "--", "echo", "started"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("started").not())
+        .stderr(predicate::str::contains("Lease 'token' failed"));
+}
@@ -0,0 +1,110 @@
+#![cfg(unix)]
+
+use assert_cmd::Command;
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+
+fn vaultic() -> Command {
+    cargo_bin_cmd!("vaultic")
+}
+
+fn init_with_hooks(dir: &assert_fs::TempDir, hooks: &str) {
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+
+    let config_path = dir.path().join(".vaultic/config.toml");
+    let mut config = std::fs::read_to_string(&config_path).unwrap();
+    config.push_str(&format!("\n[hooks]\n{hooks}\n"));
+    std::fs::write(&config_path, config).unwrap();
+}
+
+#[test]
+fn pre_encrypt_hook_can_reject_a_file() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = assert_fs::TempDir::new().unwrap();
+    init_with_hooks(
+        &dir,
+        "pre_encrypt = \"./scripts/lint-env.sh\"\n\
+         post_encrypt = \"sh -c 'echo after $VAULTIC_HOOK $VAULTIC_ENV ${SECRET_TOKEN:-unset}'\"",
+    );
+    dir.child("scripts/lint-env.sh")
+        .write_str("#!/bin/sh\nif grep -q TODO \"$VAULTIC_FILE\"; then echo 'TODO left in file'; exit 1; fi\n")
+        .unwrap();
+    std::fs::set_permissions(
+        dir.path().join("scripts/lint-env.sh"),
+        std::fs::Permissions::from_mode(0o755),
+    )
+    .unwrap();
+
+    dir.child(".env").write_str("API_KEY=TODO\n").unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("TODO left in file"))
+        .stderr(predicate::str::contains(
+            "pre_encrypt hook './scripts/lint-env.sh' failed with exit code 1",
+        ));
+    assert!(!dir.path().join(".vaultic/dev.env.enc").exists());
+
+    // Secrets exported in the shell never reach a hook
+    dir.child(".env").write_str("API_KEY=real\n").unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .env("SECRET_TOKEN", "leaked")
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("after post_encrypt dev unset"));
+}
+
+#[test]
+fn post_hooks_run_after_resolve_and_decrypt_and_time_out() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    init_with_hooks(
+        &dir,
+        "post_resolve = \"sh -c 'cp \\\"$VAULTIC_FILE\\\" resolved-copy.env'\"\n\
+         post_decrypt = \"sleep 5\"\n\
+         timeout_seconds = 1",
+    );
+    dir.child(".env").write_str("DB_HOST=localhost\n").unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .success();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["resolve", "--env", "dev", "-o", "out.env"])
+        .assert()
+        .success();
+    dir.child("resolved-copy.env")
+        .assert(predicate::str::contains("DB_HOST=localhost"));
+
+    // A post hook failing only warns: the plaintext is already written
+    vaultic()
+        .current_dir(dir.path())
+        .args(["decrypt", "--env", "dev", "-o", "decrypted.env"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "post_decrypt hook 'sleep 5' timed out after 1s",
+        ));
+    dir.child("decrypted.env")
+        .assert(predicate::str::contains("DB_HOST=localhost"));
+
+    // No hook runs when the plaintext only goes to stdout
+    vaultic()
+        .current_dir(dir.path())
+        .args(["decrypt", "--env", "dev", "--stdout"])
+        .assert()
+        .success()
+        .stdout("DB_HOST=localhost\n");
+}
@@ -0,0 +1,114 @@
+use assert_cmd::Command;
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+
+fn vaultic() -> Command {
+    cargo_bin_cmd!("vaultic")
+}
+
+/// A service project whose `base` mirrors `shared` from a platform
+/// repository checked out at `platform/` (as a submodule would be).
+fn setup() -> assert_fs::TempDir {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let platform = dir.child("platform");
+    platform.create_dir_all().unwrap();
+
+    vaultic()
+        .current_dir(platform.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+    platform
+        .child(".env")
+        .write_str("SENTRY_DSN=https://sentry.internal\nLOG_LEVEL=info\n")
+        .unwrap();
+    vaultic()
+        .current_dir(platform.path())
+        .args(["encrypt", "--env", "base"])
+        .assert()
+        .success();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+    let config_path = dir.path().join(".vaultic/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(
+        &config_path,
+        config.replace(
+            "base = { file = \"base.env\" }",
+            "base = { file = \"base.env\", mirror = \"platform/.vaultic/base.env.enc\" }",
+        ),
+    )
+    .unwrap();
+
+    dir.child(".env").write_str("LOG_LEVEL=debug\n").unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .success();
+    dir
+}
+
+#[test]
+fn mirror_is_read_through_resolve_chain() {
+    let dir = setup();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["resolve", "--env", "dev", "--stdout"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "SENTRY_DSN=https://sentry.internal",
+        ))
+        .stdout(predicate::str::contains("LOG_LEVEL=debug"));
+
+    vaultic()
+        .current_dir(dir.path())
+        .arg("status")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "mirror of platform/.vaultic/base.env.enc",
+        ));
+}
+
+#[test]
+fn mirror_is_read_only() {
+    let dir = setup();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "base"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Environment 'base' is a read-only mirror of platform/.vaultic/base.env.enc",
+        ));
+    assert!(!dir.path().join(".vaultic/base.env.enc").exists());
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["set", "LOG_LEVEL=warn", "--env", "base"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("read-only mirror"));
+}
+
+#[test]
+fn missing_mirror_checkout_fails() {
+    let dir = setup();
+    std::fs::remove_dir_all(dir.path().join("platform")).unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["resolve", "--env", "dev", "--stdout"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("git submodule update --init"));
+}
@@ -0,0 +1,101 @@
+use assert_cmd::Command;
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+
+fn vaultic() -> Command {
+    cargo_bin_cmd!("vaultic")
+}
+
+/// Project in `old/` whose identity lives at an absolute path inside it.
+fn setup_project(root: &std::path::Path) -> std::path::PathBuf {
+    let old = root.join("old");
+    std::fs::create_dir_all(old.join("keys")).unwrap();
+    std::fs::create_dir_all(root.join("new")).unwrap();
+
+    vaultic()
+        .current_dir(&old)
+        .args(["init", "--no-keygen"])
+        .assert()
+        .success();
+
+    let identity = age::x25519::Identity::generate();
+    std::fs::write(
+        old.join("keys/id.txt"),
+        format!(
+            "{}\n",
+            age::secrecy::ExposeSecret::expose_secret(&identity.to_string())
+        ),
+    )
+    .unwrap();
+    let config_path = old.join(".vaultic/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap().replacen(
+        "[vaultic]\n",
+        &format!("[vaultic]\nidentity = \"{}/keys/id.txt\"\n", old.display()),
+        1,
+    );
+    std::fs::write(&config_path, config).unwrap();
+
+    vaultic()
+        .current_dir(&old)
+        .args(["keys", "add", &identity.to_public().to_string()])
+        .assert()
+        .success();
+    std::fs::write(old.join(".env"), "MOVED=1\n").unwrap();
+    vaultic()
+        .current_dir(&old)
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .success();
+    old
+}
+
+#[test]
+fn move_copies_project_and_rewrites_paths() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let root = dir.path().canonicalize().unwrap();
+    let old = setup_project(&root);
+    let new = root.join("new");
+
+    vaultic()
+        .current_dir(&old)
+        .args(["move", "--to", "../new"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Rewrote 1 path(s)"))
+        .stdout(predicate::str::contains("✓ ok"));
+
+    let config = std::fs::read_to_string(new.join(".vaultic/config.toml")).unwrap();
+    assert!(config.contains(&format!("identity = \"{}/keys/id.txt\"", new.display())));
+    assert!(new.join(".vaultic/dev.env.enc").exists());
+    assert!(old.join(".vaultic/dev.env.enc").exists());
+
+    let gitignore = std::fs::read_to_string(new.join(".gitignore")).unwrap();
+    assert!(gitignore.lines().any(|l| l == ".env"));
+
+    let old_audit = std::fs::read_to_string(old.join(".vaultic/audit.log")).unwrap();
+    assert!(old_audit.contains("\"kind\":\"moved_to\""));
+    let new_audit = std::fs::read_to_string(new.join(".vaultic/audit.log")).unwrap();
+    assert!(new_audit.contains("\"kind\":\"moved_from\""));
+}
+
+#[test]
+fn move_refuses_existing_project() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let root = dir.path().canonicalize().unwrap();
+    let old = setup_project(&root);
+    dir.child("new/.vaultic/config.toml")
+        .write_str("# other project")
+        .unwrap();
+
+    vaultic()
+        .current_dir(&old)
+        .args(["move", "--to", "../new"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+    assert_eq!(
+        std::fs::read_to_string(root.join("new/.vaultic/config.toml")).unwrap(),
+        "# other project"
+    );
+}
@@ -0,0 +1,75 @@
+use assert_cmd::Command;
+use assert_cmd::cargo::cargo_bin_cmd;
+use predicates::prelude::*;
+
+fn vaultic() -> Command {
+    cargo_bin_cmd!("vaultic")
+}
+
+/// Init a project and drop one configured and one orphaned `.enc` file.
+fn setup_project(dir: &assert_fs::TempDir) {
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--no-keygen"])
+        .assert()
+        .success();
+    std::fs::write(dir.path().join(".vaultic/dev.env.enc"), "ciphertext").unwrap();
+    std::fs::write(dir.path().join(".vaultic/qa.env.enc"), "ciphertext").unwrap();
+}
+
+#[test]
+fn prune_deletes_orphans_after_confirmation() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    setup_project(&dir);
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["prune", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("qa.env.enc"))
+        .stdout(predicate::str::contains("dev.env.enc").not());
+
+    assert!(!dir.path().join(".vaultic/qa.env.enc").exists());
+    assert!(dir.path().join(".vaultic/dev.env.enc").exists());
+
+    let audit = std::fs::read_to_string(dir.path().join(".vaultic/audit.log")).unwrap();
+    assert!(
+        audit
+            .lines()
+            .any(|l| l.contains("\"prune\"") && l.contains("qa.env.enc"))
+    );
+}
+
+#[test]
+fn prune_keeps_files_when_declined() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    setup_project(&dir);
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["prune", "--no-input"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Nothing deleted"));
+
+    assert!(dir.path().join(".vaultic/qa.env.enc").exists());
+}
+
+#[test]
+fn prune_reports_clean_project() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--no-keygen"])
+        .assert()
+        .success();
+
+    vaultic()
+        .current_dir(dir.path())
+        .arg("prune")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No orphaned encrypted files"));
+}
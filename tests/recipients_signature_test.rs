@@ -0,0 +1,137 @@
+use assert_cmd::Command;
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_fs::prelude::*;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use blake2::Digest;
+use ed25519_dalek::{Signer, SigningKey};
+use predicates::prelude::*;
+
+fn vaultic() -> Command {
+    cargo_bin_cmd!("vaultic")
+}
+
+const KEYNUM: [u8; 8] = [8, 7, 6, 5, 4, 3, 2, 1];
+
+/// Minisign public key line for `sk`.
+fn minisign_public_key(sk: &SigningKey) -> String {
+    let mut bytes = b"Ed".to_vec();
+    bytes.extend_from_slice(&KEYNUM);
+    bytes.extend_from_slice(sk.verifying_key().as_bytes());
+    STANDARD.encode(bytes)
+}
+
+/// Minisign signature file for `message`, as `minisign -S` writes it.
+fn minisign_sign(sk: &SigningKey, message: &[u8]) -> String {
+    let sig = sk.sign(&blake2::Blake2b512::digest(message));
+    let mut bytes = b"ED".to_vec();
+    bytes.extend_from_slice(&KEYNUM);
+    bytes.extend_from_slice(&sig.to_bytes());
+
+    let trusted = "timestamp:1700000000\tfile:recipients.txt";
+    let mut global = sig.to_bytes().to_vec();
+    global.extend_from_slice(trusted.as_bytes());
+
+    format!(
+        "untrusted comment: signature from minisign secret key\n{}\ntrusted comment: {trusted}\n{}\n",
+        STANDARD.encode(bytes),
+        STANDARD.encode(sk.sign(&global).to_bytes())
+    )
+}
+
+/// Init with a generated key and pin `admin` in `[recipients]`.
+fn setup_signed_project(dir: &assert_fs::TempDir, admin: &SigningKey, enforce: bool) {
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+
+    let config_path = dir.path().join(".vaultic/config.toml");
+    let mut config = std::fs::read_to_string(&config_path).unwrap();
+    config.push_str(&format!(
+        "\n[recipients]\nadmin_keys = [\"{}\"]\nenforce = {enforce}\n",
+        minisign_public_key(admin)
+    ));
+    std::fs::write(&config_path, config).unwrap();
+    dir.child(".env").write_str("SECRET=1").unwrap();
+}
+
+fn sign_recipients(dir: &assert_fs::TempDir, sk: &SigningKey) {
+    let recipients = std::fs::read(dir.path().join(".vaultic/recipients.txt")).unwrap();
+    std::fs::write(
+        dir.path().join(".vaultic/recipients.txt.sig"),
+        minisign_sign(sk, &recipients),
+    )
+    .unwrap();
+}
+
+#[test]
+fn encrypt_accepts_recipients_signed_by_admin() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let admin = SigningKey::from_bytes(&[3; 32]);
+    setup_signed_project(&dir, &admin, true);
+    sign_recipients(&dir, &admin);
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn encrypt_refuses_unsigned_recipient_addition() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let admin = SigningKey::from_bytes(&[3; 32]);
+    setup_signed_project(&dir, &admin, true);
+    sign_recipients(&dir, &admin);
+
+    // A recipient slipped in after the admin signed the list
+    let path = dir.path().join(".vaultic/recipients.txt");
+    let mut recipients = std::fs::read_to_string(&path).unwrap();
+    recipients.push_str("age1mallory # mallory\n");
+    std::fs::write(&path, recipients).unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Recipients signature check failed",
+        ));
+    assert!(!dir.path().join(".vaultic/dev.env.enc").exists());
+}
+
+#[test]
+fn encrypt_requires_signature_file_when_admin_keys_are_pinned() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let admin = SigningKey::from_bytes(&[3; 32]);
+    setup_signed_project(&dir, &admin, true);
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("recipients.txt.sig not found"));
+}
+
+#[test]
+fn unenforced_signature_check_only_warns() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let admin = SigningKey::from_bytes(&[3; 32]);
+    let intruder = SigningKey::from_bytes(&[4; 32]);
+    setup_signed_project(&dir, &admin, false);
+    sign_recipients(&dir, &intruder);
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Recipients signature check failed",
+        ));
+}
@@ -0,0 +1,119 @@
+#![cfg(unix)]
+
+use assert_cmd::Command;
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+
+fn vaultic() -> Command {
+    cargo_bin_cmd!("vaultic")
+}
+
+/// A stand-in for the vault CLI serving one KV v2 secret at
+/// `secret/data/app`.
+const FAKE_VAULT: &str = r#"#!/bin/sh
+if [ "$3" = "--" ] && [ "$4" = "secret/data/app" ]; then
+    echo '{"data": {"data": {"api_key": "sk_from_vault"}, "metadata": {"version": 1}}}'
+else
+    echo "No value found at $4" >&2
+    exit 2
+fi
+"#;
+
+/// Init a project whose `dev` holds a vault:// reference, with a fake
+/// vault CLI in `bin/`. Returns the PATH to run with.
+fn setup(dir: &assert_fs::TempDir) -> String {
+    use std::os::unix::fs::PermissionsExt;
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+    dir.child(".env")
+        .write_str("API_KEY=vault://secret/data/app#api_key\nDB_HOST=localhost\n")
+        .unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .success();
+
+    dir.child("bin/vault").write_str(FAKE_VAULT).unwrap();
+    std::fs::set_permissions(
+        dir.path().join("bin/vault"),
+        std::fs::Permissions::from_mode(0o755),
+    )
+    .unwrap();
+    format!(
+        "{}:{}",
+        dir.path().join("bin").display(),
+        std::env::var("PATH").unwrap()
+    )
+}
+
+fn configure_vault(dir: &assert_fs::TempDir) {
+    let config_path = dir.path().join(".vaultic/config.toml");
+    let mut config = std::fs::read_to_string(&config_path).unwrap();
+    config.push_str("\n[references]\nvault = {}\n");
+    std::fs::write(&config_path, config).unwrap();
+}
+
+#[test]
+fn resolve_keeps_references_by_default() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let path = setup(&dir);
+    configure_vault(&dir);
+
+    vaultic()
+        .current_dir(dir.path())
+        .env("PATH", &path)
+        .args(["resolve", "--env", "dev", "--stdout"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "API_KEY=vault://secret/data/app#api_key",
+        ));
+}
+
+#[test]
+fn resolve_materialize_fetches_from_provider() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let path = setup(&dir);
+    configure_vault(&dir);
+
+    vaultic()
+        .current_dir(dir.path())
+        .env("PATH", &path)
+        .args(["resolve", "--env", "dev", "--stdout", "--materialize"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("API_KEY=sk_from_vault"))
+        .stdout(predicate::str::contains("DB_HOST=localhost"));
+
+    vaultic()
+        .current_dir(dir.path())
+        .env("PATH", &path)
+        .args(["run", "--env", "dev", "--materialize", "--", "sh", "-c"])
+        .arg("test \"$API_KEY\" = sk_from_vault")
+        .assert()
+        .success();
+}
+
+#[test]
+fn materialize_without_provider_fails() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let path = setup(&dir);
+
+    vaultic()
+        .current_dir(dir.path())
+        .env("PATH", &path)
+        .args(["resolve", "--env", "dev", "--stdout", "--materialize"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains(
+            "Could not resolve API_KEY = vault://secret/data/app#api_key",
+        ))
+        .stderr(predicate::str::contains("no [references.vault] provider"));
+}
@@ -0,0 +1,113 @@
+use assert_cmd::Command;
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+
+fn vaultic() -> Command {
+    cargo_bin_cmd!("vaultic")
+}
+
+const TEMPLATE: &str = "# vaultic:owner=payments\nSTRIPE_KEY=\n\
+                        # vaultic:owner=platform prefix=DB_\nDB_URL=\nDB_POOL=\nLOG_LEVEL=\n";
+
+fn git(dir: &assert_fs::TempDir, args: &[&str]) {
+    let status = std::process::Command::new("git")
+        .current_dir(dir.path())
+        .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .output()
+        .unwrap()
+        .status;
+    assert!(status.success(), "git {args:?} failed");
+}
+
+fn encrypt(dir: &assert_fs::TempDir, content: &str, note: Option<&str>) {
+    dir.child(".env").write_str(content).unwrap();
+    let mut cmd = vaultic();
+    cmd.current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"]);
+    if let Some(note) = note {
+        cmd.args(["--note", note]);
+    }
+    cmd.assert().success();
+}
+
+#[test]
+fn report_owners_groups_template_keys() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--no-keygen"])
+        .assert()
+        .success();
+    dir.child(".env.template").write_str(TEMPLATE).unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["report", "owners"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("payments (1)"))
+        .stdout(predicate::str::contains("platform (2)"))
+        .stdout(predicate::str::contains("unowned (1)"))
+        .stdout(predicate::str::contains("LOG_LEVEL"));
+}
+
+#[test]
+fn report_owners_check_requires_owner_note() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+    dir.child(".env.template").write_str(TEMPLATE).unwrap();
+    encrypt(&dir, "STRIPE_KEY=old\nLOG_LEVEL=info", None);
+
+    git(&dir, &["init", "-q"]);
+    git(&dir, &["add", "-A"]);
+    git(&dir, &["commit", "-q", "-m", "base"]);
+
+    // Owned key changed, unowned key changed: only the owned one matters
+    encrypt(&dir, "STRIPE_KEY=new\nLOG_LEVEL=debug", None);
+    vaultic()
+        .current_dir(dir.path())
+        .args(["report", "owners", "--check", "HEAD"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "STRIPE_KEY changed without an audit note mentioning 'payments'",
+        ));
+
+    encrypt(
+        &dir,
+        "STRIPE_KEY=new\nLOG_LEVEL=debug",
+        Some("rotated, approved by Payments"),
+    );
+    vaultic()
+        .current_dir(dir.path())
+        .args(["report", "owners", "--check", "HEAD"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "payments: STRIPE_KEY (acknowledged)",
+        ));
+}
+
+#[test]
+fn report_owners_check_unknown_revision_fails() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--no-keygen"])
+        .assert()
+        .success();
+    git(&dir, &["init", "-q"]);
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["report", "owners", "--check", "no-such-branch"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown git revision"));
+}
@@ -21,8 +21,7 @@ fn setup_multi_env(
     // Init with auto key generation
     vaultic()
         .current_dir(dir.path())
-        .arg("init")
-        .write_stdin("y\n")
+        .args(["init", "--yes"])
         .assert()
         .success();
 
@@ -91,8 +90,7 @@ fn resolve_unknown_env_fails() {
 
     vaultic()
         .current_dir(dir.path())
-        .arg("init")
-        .write_stdin("y\n")
+        .args(["init", "--yes"])
         .assert()
         .success();
 
@@ -111,8 +109,7 @@ fn resolve_base_only_works() {
     // Init with key
     vaultic()
         .current_dir(dir.path())
-        .arg("init")
-        .write_stdin("y\n")
+        .args(["init", "--yes"])
         .assert()
         .success();
 
@@ -176,8 +173,7 @@ fn resolve_with_short_output_flag() {
     // Init with key
     vaultic()
         .current_dir(dir.path())
-        .arg("init")
-        .write_stdin("y\n")
+        .args(["init", "--yes"])
         .assert()
         .success();
 
@@ -210,8 +206,7 @@ fn diff_env_shows_differences() {
     // Init
     vaultic()
         .current_dir(dir.path())
-        .arg("init")
-        .write_stdin("y\n")
+        .args(["init", "--yes"])
         .assert()
         .success();
 
@@ -253,8 +248,7 @@ fn diff_env_identical_shows_no_differences() {
 
     vaultic()
         .current_dir(dir.path())
-        .arg("init")
-        .write_stdin("y\n")
+        .args(["init", "--yes"])
         .assert()
         .success();
 
@@ -279,3 +273,358 @@ fn diff_env_identical_shows_no_differences() {
         .success()
         .stdout(predicate::str::contains("No differences"));
 }
+
+#[test]
+fn diff_local_file_against_env() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+
+    dir.child(".env")
+        .write_str("DB_HOST=localhost\nPORT=5432\n")
+        .unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .success();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["diff", ".env", "--env", "dev"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            ".env matches what is encrypted for dev",
+        ));
+
+    dir.child(".env")
+        .write_str("DB_HOST=db.internal\nPORT=5432\nDEBUG=true\n")
+        .unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["diff", ".env", "--env", "dev"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("DB_HOST"))
+        .stdout(predicate::str::contains("DEBUG"))
+        .stdout(predicate::str::contains("PORT").not());
+}
+
+#[test]
+fn resolve_without_env_or_default_fails_outside_terminal() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--no-keygen"])
+        .assert()
+        .success();
+
+    let config_path = dir.path().join(".vaultic/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(&config_path, config.replace("default_env = \"dev\"\n", "")).unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .arg("resolve")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No environment selected"))
+        .stderr(predicate::str::contains("base, dev, prod, staging"));
+}
+
+#[test]
+fn test_env_filters_and_prefixes_keys() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    setup_multi_env(
+        &dir,
+        "DB_HOST=localhost\nDB_ADMIN_PASSWORD=root",
+        "dev",
+        "API_URL=http://dev\nSTRIPE_LIVE_KEY=sk_live",
+    );
+
+    let config_path = dir.path().join(".vaultic/config.toml");
+    let mut config = std::fs::read_to_string(&config_path).unwrap();
+    config
+        .push_str("\n[test_env]\ninclude = [\"DB_*\", \"API_URL\"]\nexclude = [\"DB_ADMIN_*\"]\n");
+    std::fs::write(&config_path, config).unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["test-env", "--env", "dev", "--prefix", "TEST_"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Kept 2 of 4 variable(s)"));
+
+    let content = std::fs::read_to_string(dir.path().join(".env.test")).unwrap();
+    assert!(content.contains("TEST_DB_HOST=localhost"));
+    assert!(content.contains("TEST_API_URL=http://dev"));
+    assert!(!content.contains("DB_ADMIN_PASSWORD"));
+    assert!(!content.contains("STRIPE_LIVE_KEY"));
+}
+
+#[cfg(unix)]
+#[test]
+fn run_injects_resolved_env_into_child() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    setup_multi_env(
+        &dir,
+        "DB_HOST=localhost\nDB_PORT=5432",
+        "dev",
+        "DB_HOST=dev-db",
+    );
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["run", "--env", "dev", "--", "sh", "-c"])
+        .arg("echo \"$DB_HOST:$DB_PORT\"")
+        .assert()
+        .success()
+        .stdout("dev-db:5432\n");
+
+    // Nothing is written to disk
+    assert!(!dir.path().join(".env").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn run_propagates_child_exit_code() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    setup_multi_env(&dir, "A=1", "dev", "B=2");
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["run", "--env", "dev", "--", "sh", "-c", "exit 7"])
+        .assert()
+        .code(7);
+}
+
+#[test]
+fn run_missing_program_fails() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    setup_multi_env(&dir, "A=1", "dev", "B=2");
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["run", "--env", "dev", "--", "vaultic-no-such-program"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Failed to start 'vaultic-no-such-program'",
+        ));
+}
+
+#[test]
+fn resolve_only_and_except_filter_keys() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    setup_multi_env(
+        &dir,
+        "DB_HOST=localhost\nDB_ADMIN_PASSWORD=root\nREDIS_URL=redis://x",
+        "dev",
+        "STRIPE_KEY=sk_test",
+    );
+
+    let output = vaultic()
+        .current_dir(dir.path())
+        .args(["resolve", "--env", "dev", "--stdout"])
+        .args(["--only", "DB_*,REDIS_URL", "--except", "DB_ADMIN_*"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("DB_HOST=localhost"));
+    assert!(stdout.contains("REDIS_URL=redis://x"));
+    assert!(!stdout.contains("DB_ADMIN_PASSWORD"));
+    assert!(!stdout.contains("STRIPE_KEY"));
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["resolve", "--env", "dev", "--except", "STRIPE_*"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Resolved 3 of 4 variables"));
+    let content = std::fs::read_to_string(dir.path().join(".env")).unwrap();
+    assert!(!content.contains("STRIPE_KEY"));
+}
+
+#[cfg(unix)]
+#[test]
+fn run_only_injects_matching_keys() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    setup_multi_env(&dir, "PUBLIC_URL=https://x", "dev", "SECRET_KEY=s3cret");
+
+    vaultic()
+        .current_dir(dir.path())
+        .args([
+            "run", "--env", "dev", "--only", "PUBLIC_*", "--", "sh", "-c",
+        ])
+        .arg("echo \"$PUBLIC_URL|${SECRET_KEY:-unset}\"")
+        .assert()
+        .success()
+        .stdout("https://x|unset\n");
+}
+
+#[test]
+fn yaml_and_json_environments_resolve_with_flattened_keys() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+
+    // dev's format comes from its file extension; prod sets it explicitly
+    let config_path = dir.path().join(".vaultic/config.toml");
+    let config = std::fs::read_to_string(&config_path)
+        .unwrap()
+        .replace(
+            r#"dev = { file = "dev.env", inherits = "base" }"#,
+            r#"dev = { file = "secrets.yaml", inherits = "base" }"#,
+        )
+        .replace(
+            r#"prod = { file = "prod.env", inherits = "base" }"#,
+            r#"prod = { file = "prod.env", inherits = "base", format = "json" }"#,
+        );
+    std::fs::write(&config_path, config).unwrap();
+
+    dir.child(".env").write_str("LOG_LEVEL=info").unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "base"])
+        .assert()
+        .success();
+
+    dir.child("secrets.yaml")
+        .write_str("db:\n  host: dev-db\n  port: 5432\n")
+        .unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "secrets.yaml", "--env", "dev"])
+        .assert()
+        .success();
+
+    dir.child("prod.json")
+        .write_str(r#"{"db": {"host": "prod-db", "port": 5432}}"#)
+        .unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "prod.json", "--env", "prod"])
+        .assert()
+        .success();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["resolve", "--env", "dev", "--stdout"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("LOG_LEVEL=info"))
+        .stdout(predicate::str::contains("DB_HOST=dev-db"))
+        .stdout(predicate::str::contains("DB_PORT=5432"));
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["diff", "--env", "dev", "--env", "prod"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("DB_HOST"))
+        .stdout(predicate::str::contains("DB_PORT").not());
+}
+
+#[test]
+fn encrypt_warns_when_extension_and_format_disagree() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+
+    dir.child("secrets.yaml").write_str("a: 1\n").unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "secrets.yaml", "--env", "dev"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "secrets.yaml looks like YAML but dev is read as dotenv",
+        ));
+}
+
+#[test]
+fn package_project_extends_parent_project() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    setup_multi_env(
+        &dir,
+        "LOG_LEVEL=warn\nDB_HOST=shared-db",
+        "dev",
+        "LOG_LEVEL=debug",
+    );
+
+    let package = dir.child("apps/web");
+    package.create_dir_all().unwrap();
+    vaultic()
+        .current_dir(package.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+    let config_path = package.path().join(".vaultic/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(
+        &config_path,
+        config.replacen("[vaultic]\n", "[vaultic]\nextends_parent = true\n", 1),
+    )
+    .unwrap();
+    package.child(".env").write_str("DB_HOST=web-db\n").unwrap();
+    vaultic()
+        .current_dir(package.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .success();
+
+    vaultic()
+        .current_dir(package.path())
+        .args(["resolve", "--env", "dev", "-o", "resolved.env"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "../../.vaultic:base -> ../../.vaultic:dev -> base -> dev",
+        ));
+    let resolved = std::fs::read_to_string(package.path().join("resolved.env")).unwrap();
+    assert!(resolved.contains("DB_HOST=web-db"), "package value wins");
+    assert!(
+        resolved.contains("LOG_LEVEL=debug"),
+        "parent dev over parent base"
+    );
+
+    // Environments the parent does not define resolve on their own
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(
+        &config_path,
+        config.replacen("[environments]\n", "[environments]\nweb-only = {}\n", 1),
+    )
+    .unwrap();
+    vaultic()
+        .current_dir(package.path())
+        .args(["encrypt", "--env", "web-only"])
+        .assert()
+        .success();
+    vaultic()
+        .current_dir(package.path())
+        .args(["resolve", "--env", "web-only", "--stdout"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("LOG_LEVEL").not());
+}
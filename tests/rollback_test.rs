@@ -0,0 +1,167 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+
+/// Run vaultic with given args.
+fn vaultic() -> assert_cmd::Command {
+    cargo_bin_cmd!("vaultic")
+}
+
+fn init(dir: &assert_fs::TempDir) {
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+}
+
+fn version_ids(dir: &assert_fs::TempDir, env: &str) -> Vec<String> {
+    let manifest = std::fs::read_to_string(
+        dir.path()
+            .join(format!(".vaultic/history/{env}/manifest.toml")),
+    )
+    .unwrap();
+    let manifest: toml::Value = toml::from_str(&manifest).unwrap();
+    manifest["versions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn rollback_restores_a_previous_version() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    init(&dir);
+    dir.child(".env").write_str("API_KEY=first\n").unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .success();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["set", "API_KEY=second", "--env", "dev"])
+        .assert()
+        .success();
+
+    let ids = version_ids(&dir, "dev");
+    assert_eq!(ids.len(), 2);
+    vaultic()
+        .current_dir(dir.path())
+        .args(["rollback", "--env", "dev"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Versions of dev (2)"))
+        .stdout(predicate::str::contains(ids[0].as_str()))
+        .stdout(predicate::str::contains("current"));
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["rollback", "--env", "dev", "--to", &ids[0], "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "Rolled dev back to version {}",
+            ids[0]
+        )));
+    vaultic()
+        .current_dir(dir.path())
+        .args(["get", "API_KEY", "--env", "dev"])
+        .assert()
+        .success()
+        .stdout("first\n");
+    // The rollback is itself a new version
+    assert_eq!(version_ids(&dir, "dev").len(), 3);
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["rollback", "--env", "dev", "--to", "nope"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No version 'nope' of dev"));
+}
+
+#[test]
+fn keep_versions_limits_kept_ciphertexts() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    init(&dir);
+    let config = dir.path().join(".vaultic/config.toml");
+    let mut content = std::fs::read_to_string(&config).unwrap();
+    content.push_str("\n[history]\nkeep_versions = 2\n");
+    std::fs::write(&config, content).unwrap();
+
+    for value in ["1", "2", "3"] {
+        dir.child(".env")
+            .write_str(&format!("N={value}\n"))
+            .unwrap();
+        vaultic()
+            .current_dir(dir.path())
+            .args(["encrypt", "--env", "dev"])
+            .assert()
+            .success();
+    }
+
+    let ids = version_ids(&dir, "dev");
+    assert_eq!(ids.len(), 2);
+    let kept = std::fs::read_dir(dir.path().join(".vaultic/history/dev"))
+        .unwrap()
+        .filter(|e| {
+            e.as_ref()
+                .unwrap()
+                .path()
+                .extension()
+                .is_some_and(|x| x == "enc")
+        })
+        .count();
+    assert_eq!(kept, 2);
+}
+
+#[test]
+fn encrypt_refuses_empty_or_shrunken_files_without_force() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    init(&dir);
+    dir.child(".env").write_str("A=1\nB=2\nC=3\nD=4\n").unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .success();
+
+    // A script truncated .env before it was encrypted
+    dir.child(".env").write_str("").unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("has no variables"))
+        .stderr(predicate::str::contains("--force"));
+
+    dir.child(".env").write_str("A=1\n").unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("down from 4"));
+    assert_eq!(version_ids(&dir, "dev").len(), 1);
+
+    // Removing half is within the default limit
+    dir.child(".env").write_str("A=1\nB=2\n").unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .success();
+
+    dir.child(".env").write_str("").unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("encrypting anyway"));
+    assert_eq!(version_ids(&dir, "dev").len(), 3);
+}
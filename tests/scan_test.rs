@@ -0,0 +1,167 @@
+use assert_cmd::Command;
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+
+fn vaultic() -> Command {
+    cargo_bin_cmd!("vaultic")
+}
+
+fn git(dir: &assert_fs::TempDir, args: &[&str]) {
+    let status = std::process::Command::new("git")
+        .current_dir(dir.path())
+        .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .output()
+        .unwrap()
+        .status;
+    assert!(status.success(), "git {args:?} failed");
+}
+
+const TOKEN: &str = "sk_live_4f8a9c2e71";
+
+fn setup() -> assert_fs::TempDir {
+    let dir = assert_fs::TempDir::new().unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+    dir.child(".env")
+        .write_str(&format!("API_TOKEN={TOKEN}\nPORT=5432\n"))
+        .unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .success();
+
+    dir.child(".gitignore").write_str(".env\n").unwrap();
+    dir.child("server.js")
+        .write_str("const port = 5432;\n")
+        .unwrap();
+    git(&dir, &["init", "-q"]);
+    git(&dir, &["add", "-A"]);
+    git(&dir, &["commit", "-q", "-m", "base"]);
+    dir
+}
+
+#[test]
+fn scan_passes_without_leaks() {
+    let dir = setup();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["check", "--scan"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No secret values of dev found"));
+}
+
+#[test]
+fn scan_flags_leaked_value_without_printing_it() {
+    let dir = setup();
+    dir.child("server.js")
+        .write_str(&format!("const port = 5432;\nconst token = \"{TOKEN}\";\n"))
+        .unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["check", "--scan"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("server.js"))
+        .stdout(predicate::str::contains("API_TOKEN"))
+        .stdout(predicate::str::contains(TOKEN).not())
+        .stderr(predicate::str::contains("Found 1 secret value(s)"));
+}
+
+#[test]
+fn deep_scan_finds_leak_removed_from_history() {
+    let dir = setup();
+    dir.child("server.js")
+        .write_str(&format!("const token = \"{TOKEN}\";\n"))
+        .unwrap();
+    git(&dir, &["commit", "-q", "-am", "leak"]);
+    dir.child("server.js")
+        .write_str("const token = process.env.API_TOKEN;\n")
+        .unwrap();
+    git(&dir, &["commit", "-q", "-am", "fix"]);
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["check", "--scan"])
+        .assert()
+        .success();
+
+    let output = vaultic()
+        .current_dir(dir.path())
+        .args(["--json", "check", "--scan", "--deep"])
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+    let report: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(report["ok"], false);
+    assert_eq!(report["leaks"].as_array().unwrap().len(), 0);
+    let history = report["history_leaks"].as_array().unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0]["path"], "server.js");
+    assert_eq!(history[0]["key"], "API_TOKEN");
+    assert_eq!(history[0]["line"], 1);
+}
+
+#[test]
+fn policy_fails_on_short_and_placeholder_values() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+    dir.child(".env")
+        .write_str("JWT_SECRET=tooshort\nDB_PASSWORD=changeme\n")
+        .unwrap();
+    for env in ["dev", "prod"] {
+        vaultic()
+            .current_dir(dir.path())
+            .args(["encrypt", "--env", env])
+            .assert()
+            .success();
+    }
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["check", "--policy", "--env", "dev"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("JWT_SECRET"))
+        .stdout(predicate::str::contains("DB_PASSWORD").not())
+        .stderr(predicate::str::contains("Found 1 secret value(s)"));
+
+    let output = vaultic()
+        .current_dir(dir.path())
+        .args(["--json", "check", "--policy"])
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let rules: Vec<(&str, &str)> = json["violations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| (v["env"].as_str().unwrap(), v["rule"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        rules,
+        [
+            ("dev", "min_length"),
+            ("prod", "min_length"),
+            ("prod", "weak_value")
+        ]
+    );
+    assert!(!String::from_utf8_lossy(&output).contains("changeme"));
+}
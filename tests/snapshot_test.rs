@@ -0,0 +1,180 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+
+/// Run vaultic with given args.
+fn vaultic() -> assert_cmd::Command {
+    cargo_bin_cmd!("vaultic")
+}
+
+/// Encrypt `content` as the dev environment.
+fn encrypt_dev(dir: &assert_fs::TempDir, content: &str) {
+    dir.child(".env").write_str(content).unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn snapshot_create_diff_and_restore() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+    encrypt_dev(&dir, "DB_URL=postgres://old\nAPI_KEY=abc\n");
+    let original = std::fs::read(dir.path().join(".vaultic/dev.env.enc")).unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args([
+            "snapshot",
+            "create",
+            "dev",
+            "pre-release-42",
+            "--note",
+            "v42",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Saved snapshot 'pre-release-42'"));
+    assert!(
+        dir.path()
+            .join(".vaultic/snapshots/dev/pre-release-42.toml")
+            .exists()
+    );
+
+    // Names are unique per environment
+    vaultic()
+        .current_dir(dir.path())
+        .args(["snapshot", "create", "dev", "pre-release-42"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["snapshot", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("pre-release-42"))
+        .stdout(predicate::str::contains("v42"));
+
+    encrypt_dev(&dir, "DB_URL=postgres://new\nAPI_KEY=abc\nNEW_FLAG=1\n");
+
+    let output = vaultic()
+        .current_dir(dir.path())
+        .args(["snapshot", "diff", "dev", "pre-release-42", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let changes: Vec<(&str, &str)> = json["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| (e["key"].as_str().unwrap(), e["change"].as_str().unwrap()))
+        .collect();
+    assert!(changes.contains(&("DB_URL", "modified")));
+    assert!(changes.contains(&("NEW_FLAG", "added")));
+    assert_eq!(changes.len(), 2);
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["--yes", "snapshot", "restore", "dev", "pre-release-42"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Restored dev"));
+    assert_eq!(
+        std::fs::read(dir.path().join(".vaultic/dev.env.enc")).unwrap(),
+        original
+    );
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["snapshot", "restore", "dev", "pre-release-42"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("already matches"));
+}
+
+#[test]
+fn snapshot_restore_rejects_tampered_ciphertext() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+    encrypt_dev(&dir, "A=1\n");
+    vaultic()
+        .current_dir(dir.path())
+        .args(["snapshot", "create", "dev", "v1"])
+        .assert()
+        .success();
+
+    std::fs::write(dir.path().join(".vaultic/snapshots/dev/v1.env.enc"), "x").unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["--yes", "snapshot", "restore", "dev", "v1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("SHA-256 mismatch"));
+}
+
+#[test]
+fn snapshot_prune_keeps_newest() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+    encrypt_dev(&dir, "A=1\n");
+    for name in ["v1", "v2", "v3"] {
+        vaultic()
+            .current_dir(dir.path())
+            .args(["snapshot", "create", "dev", name])
+            .assert()
+            .success();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+    let snapshots = dir.path().join(".vaultic/snapshots/dev");
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["snapshot", "prune", "--keep", "1", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("dev/v1"))
+        .stdout(predicate::str::contains("dev/v2"))
+        .stdout(predicate::str::contains("dev/v3").not());
+    assert!(snapshots.join("v1.env.enc").exists());
+
+    // Nothing is old enough
+    vaultic()
+        .current_dir(dir.path())
+        .args(["snapshot", "prune", "--keep", "1", "--older-than", "90d"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Nothing to prune"));
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["--yes", "snapshot", "prune", "--keep", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Deleted 2 snapshot(s), kept 1"));
+    assert!(!snapshots.join("v1.env.enc").exists());
+    assert!(!snapshots.join("v2.toml").exists());
+    assert!(snapshots.join("v3.env.enc").exists());
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["snapshot", "prune"])
+        .assert()
+        .failure();
+}
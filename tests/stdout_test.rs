@@ -12,8 +12,7 @@ fn vaultic() -> Command {
 fn setup_encrypted_env(dir: &assert_fs::TempDir, env_name: &str, content: &str) {
     vaultic()
         .current_dir(dir.path())
-        .arg("init")
-        .write_stdin("y\n")
+        .args(["init", "--yes"])
         .assert()
         .success();
 
@@ -36,8 +35,7 @@ fn setup_multi_env(
 ) {
     vaultic()
         .current_dir(dir.path())
-        .arg("init")
-        .write_stdin("y\n")
+        .args(["init", "--yes"])
         .assert()
         .success();
 
@@ -163,3 +161,38 @@ fn resolve_stdout_and_output_are_mutually_exclusive() {
         .assert()
         .failure();
 }
+
+#[test]
+fn resolve_stdout_routes_verbose_messages_to_stderr() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    // Only dev is encrypted, so the base layer is reported as missing
+    setup_encrypted_env(&dir, "dev", "DEV_KEY=2");
+
+    let output = vaultic()
+        .current_dir(dir.path())
+        .args(["resolve", "--env", "dev", "--stdout", "--verbose"])
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(stdout.trim(), "DEV_KEY=2");
+    assert!(stderr.contains("No encrypted file for 'base'"));
+}
+
+#[test]
+fn resolve_stdout_is_silent_by_default() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    setup_encrypted_env(&dir, "dev", "DEV_KEY=2");
+
+    let output = vaultic()
+        .current_dir(dir.path())
+        .args(["resolve", "--env", "dev", "--stdout"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stderr).unwrap().is_empty());
+}
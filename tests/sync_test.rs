@@ -0,0 +1,124 @@
+#![cfg(unix)]
+
+use assert_cmd::Command;
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+
+fn vaultic() -> Command {
+    cargo_bin_cmd!("vaultic")
+}
+
+/// A stand-in for the aws CLI that keeps one Secrets Manager secret in
+/// `state/secret.json` and logs every invocation to `state/calls`.
+const FAKE_AWS: &str = r#"#!/bin/sh
+state="$FAKE_AWS_STATE"
+echo "$@" >> "$state/calls"
+input=""
+prev=""
+for arg in "$@"; do
+    [ "$prev" = "--cli-input-json" ] && input="${arg#file://}"
+    prev="$arg"
+done
+case "$2" in
+    get-secret-value|put-secret-value)
+        if [ ! -f "$state/secret.json" ]; then
+            echo "An error occurred (ResourceNotFoundException)" >&2
+            exit 254
+        fi
+        if [ "$2" = "get-secret-value" ]; then cat "$state/secret.json"; else cp "$input" "$state/secret.json"; fi
+        ;;
+    create-secret) cp "$input" "$state/secret.json" ;;
+esac
+"#;
+
+/// Init a project with `dev` encrypted and a fake aws CLI in `bin/`.
+fn setup(dir: &assert_fs::TempDir) -> String {
+    use std::os::unix::fs::PermissionsExt;
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+    dir.child(".env")
+        .write_str("DB_HOST=localhost\nAPI_KEY=secret123\n")
+        .unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .success();
+
+    dir.child("state").create_dir_all().unwrap();
+    dir.child("bin/aws").write_str(FAKE_AWS).unwrap();
+    std::fs::set_permissions(
+        dir.path().join("bin/aws"),
+        std::fs::Permissions::from_mode(0o755),
+    )
+    .unwrap();
+    format!(
+        "{}:{}",
+        dir.path().join("bin").display(),
+        std::env::var("PATH").unwrap()
+    )
+}
+
+#[test]
+fn sync_aws_dry_run_then_push() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let path = setup(&dir);
+    let sync = |extra: &[&str]| {
+        let mut cmd = vaultic();
+        cmd.current_dir(dir.path())
+            .env("PATH", &path)
+            .env("FAKE_AWS_STATE", dir.path().join("state"))
+            .args(["sync", "aws", "--env", "dev", "--name", "myapp/dev"])
+            .args(extra);
+        cmd
+    };
+
+    sync(&["--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("+ API_KEY"))
+        .stdout(predicate::str::contains("2 added, 0 changed, 0 removed"))
+        .stdout(predicate::str::contains("Dry run"))
+        .stdout(predicate::str::contains("secret123").not());
+    dir.child("state/secret.json")
+        .assert(predicate::path::missing());
+
+    sync(&["--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Pushed 2 change(s) to secretsmanager:myapp/dev",
+        ));
+    let pushed = std::fs::read_to_string(dir.path().join("state/secret.json")).unwrap();
+    assert!(pushed.contains("secret123"));
+    let calls = std::fs::read_to_string(dir.path().join("state/calls")).unwrap();
+    assert!(calls.contains("create-secret"));
+    assert!(!calls.contains("secret123"), "values must not reach argv");
+
+    sync(&[])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("already in sync (2 key(s))"));
+
+    let log = std::fs::read_to_string(dir.path().join(".vaultic/audit.log")).unwrap();
+    assert!(log.contains("\"sync\""));
+}
+
+#[test]
+fn sync_aws_rejects_unknown_service() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let path = setup(&dir);
+
+    vaultic()
+        .current_dir(dir.path())
+        .env("PATH", &path)
+        .args(["sync", "aws", "--env", "dev", "--service", "dynamo"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Use secrets-manager or ssm"));
+}
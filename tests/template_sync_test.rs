@@ -85,8 +85,7 @@ fn template_sync_full_flow() {
     // Init with auto key generation so a real age identity is created
     vaultic()
         .current_dir(dir.path())
-        .arg("init")
-        .write_stdin("y\n")
+        .args(["init", "--yes"])
         .assert()
         .success()
         .stdout(predicate::str::contains("Public key:"));
@@ -140,8 +139,7 @@ fn template_sync_output_flag_writes_to_custom_path() {
     // Init with auto key generation
     vaultic()
         .current_dir(dir.path())
-        .arg("init")
-        .write_stdin("y\n")
+        .args(["init", "--yes"])
         .assert()
         .success();
 
@@ -179,3 +177,51 @@ fn template_sync_output_flag_writes_to_custom_path() {
         "custom output should not contain plaintext value"
     );
 }
+
+#[test]
+fn template_generate_keeps_comments_and_unions_envs() {
+    let dir = assert_fs::TempDir::new().unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+
+    dir.child(".env")
+        .write_str("# Database\nDB_URL=postgres://dev\n")
+        .unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "dev"])
+        .assert()
+        .success();
+    dir.child(".env")
+        .write_str("# Database\nDB_URL=postgres://prod\n\n# Payments\nSTRIPE_KEY=sk_live_x\n")
+        .unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["encrypt", "--env", "prod"])
+        .assert()
+        .success();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["template", "generate"])
+        .assert()
+        .success();
+
+    let content = std::fs::read_to_string(dir.path().join(".env.template")).unwrap();
+    assert_eq!(content, "# Database\nDB_URL=\n\n# Payments\nSTRIPE_KEY=");
+
+    // The regenerated template is what `check` compares against
+    dir.child(".env")
+        .write_str("DB_URL=x\nSTRIPE_KEY=y\n")
+        .unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .arg("check")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2/2 variables present"));
+}
@@ -132,3 +132,116 @@ fn init_includes_format_version() {
         "config.toml should contain format_version"
     );
 }
+
+// ─── env template-check ──────────────────────────────────────────
+
+#[test]
+fn env_template_check_reports_each_environment() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+    dir.child(".env.template")
+        .write_str("DB_HOST=\nAPI_KEY=\n")
+        .unwrap();
+    for (env, content) in [
+        ("dev", "DB_HOST=localhost\nAPI_KEY=dev-key\n"),
+        ("prod", "DB_HOST=db.internal\n"),
+    ] {
+        dir.child(".env").write_str(content).unwrap();
+        vaultic()
+            .current_dir(dir.path())
+            .args(["encrypt", "--env", env])
+            .assert()
+            .success();
+    }
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["env", "template-check"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("prod is missing: API_KEY"))
+        .stdout(predicate::str::contains("1/2 environments match"))
+        .stderr(predicate::str::contains(
+            "1 environment(s) do not match their template",
+        ));
+
+    vaultic()
+        .current_dir(dir.path())
+        .args([
+            "env",
+            "template-check",
+            "--report",
+            "junit",
+            "-o",
+            "report.xml",
+        ])
+        .assert()
+        .failure();
+    let xml = std::fs::read_to_string(dir.path().join("report.xml")).unwrap();
+    assert!(xml.contains("<testsuite name=\"dev\" tests=\"2\" failures=\"0\" errors=\"0\">"));
+    assert!(xml.contains(
+        "<testcase classname=\"prod\" name=\"API_KEY\">\n      <failure type=\"missing\""
+    ));
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["env", "template-check", "--env", "dev", "--report", "json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"ok\": true"));
+}
+
+#[test]
+fn check_env_resolves_inheritance_before_comparing() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+    dir.child(".env.template")
+        .write_str("DB_HOST=\nAPI_KEY=\n")
+        .unwrap();
+    for (env, content) in [
+        ("base", "DB_HOST=db.internal\n"),
+        ("prod", "API_KEY=prod-key\n"),
+        ("staging", "LOG_LEVEL=debug\n"),
+    ] {
+        dir.child(".env").write_str(content).unwrap();
+        vaultic()
+            .current_dir(dir.path())
+            .args(["encrypt", "--env", env])
+            .assert()
+            .success();
+    }
+    std::fs::remove_file(dir.path().join(".env")).unwrap();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["check", "--env", "prod", "-v"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Environment: prod (base → prod)"))
+        .stdout(predicate::str::contains("2/2 variables present"));
+    dir.child(".env").assert(predicate::path::missing());
+
+    let output = vaultic()
+        .current_dir(dir.path())
+        .args(["--json", "check", "--env", "staging"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "1 environment(s) do not match their template",
+        ))
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(json["env"], "staging");
+    assert_eq!(json["missing"], serde_json::json!(["API_KEY"]));
+    assert_eq!(json["extra"], serde_json::json!(["LOG_LEVEL"]));
+}
@@ -181,3 +181,62 @@ fn validate_fails_without_env_file() {
         .failure()
         .stderr(predicate::str::contains(".env"));
 }
+
+// ─── check --schema ─────────────────────────────────────────────────────────
+
+#[test]
+fn check_schema_validates_resolved_environments() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+    dir.child(".vaultic/schema.toml")
+        .write_str(
+            "DATABASE_URL = { type = \"url\", required = true }\n\
+             PORT = { type = \"int\" }\n\
+             LOG_LEVEL = { type = \"enum\", values = [\"info\", \"debug\"] }\n",
+        )
+        .unwrap();
+    // The URL is inherited from base; prod breaks the port
+    for (env, content) in [
+        ("base", "DATABASE_URL=postgres://db/app\nLOG_LEVEL=info\n"),
+        ("dev", "PORT=8080\n"),
+        ("prod", "PORT=abc\n"),
+    ] {
+        dir.child(".env").write_str(content).unwrap();
+        vaultic()
+            .current_dir(dir.path())
+            .args(["encrypt", "--env", env])
+            .assert()
+            .success();
+    }
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["check", "--schema", "--env", "dev"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("dev match schema.toml"));
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["check", "--schema"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("prod"))
+        .stdout(predicate::str::contains("expected integer"))
+        .stdout(predicate::str::contains("abc").not())
+        .stderr(predicate::str::contains("1 rule(s) violated"));
+
+    dir.child(".vaultic/schema.toml")
+        .write_str("PORT = { type = \"interger\" }\n")
+        .unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["check", "--schema"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown type 'interger'"));
+}
@@ -0,0 +1,121 @@
+use assert_cmd::Command;
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+
+fn vaultic() -> Command {
+    cargo_bin_cmd!("vaultic")
+}
+
+/// Recipient changes show up until the file is re-encrypted. The project
+/// key lives in a private XDG_CONFIG_HOME so verify can prove it by
+/// decrypting; `dirs::config_dir` follows it on Linux only.
+#[cfg(target_os = "linux")]
+#[test]
+fn verify_reports_recipients_until_reencrypted() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let config_home = assert_fs::TempDir::new().unwrap();
+    let run = |args: &[&str]| {
+        vaultic()
+            .current_dir(dir.path())
+            .env("XDG_CONFIG_HOME", config_home.path())
+            .args(args)
+            .assert()
+    };
+
+    run(&["init", "--yes"]).success();
+    dir.child(".env").write_str("SECRET=1\n").unwrap();
+    run(&["encrypt", "--env", "dev"]).success();
+    run(&["verify"])
+        .success()
+        .stdout(predicate::str::contains("decrypted with your key"))
+        .stdout(predicate::str::contains("1 environment(s) readable"));
+
+    let bob = age::x25519::Identity::generate().to_public().to_string();
+    run(&["keys", "add", &bob]).success();
+    run(&["verify", "--env", "dev"])
+        .failure()
+        .stdout(predicate::str::contains(&bob))
+        .stdout(predicate::str::contains("Re-encrypt: dev"))
+        .stderr(predicate::str::contains(
+            "1 environment(s) are not readable",
+        ));
+
+    run(&["encrypt", "--env", "dev"]).success();
+    let output = run(&["verify", "--json"])
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(json["ok"], true);
+    let recipients = json["environments"][0]["recipients"].as_array().unwrap();
+    assert_eq!(recipients.len(), 2);
+    assert_eq!(recipients[1]["access"], "likely");
+
+    // Removing bob leaves his stanza in the file until the next encrypt
+    run(&["keys", "remove", &bob, "--yes"]).success();
+    run(&["verify", "--report", "junit", "-o", "verify.xml"]).failure();
+    let xml = std::fs::read_to_string(dir.path().join("verify.xml")).unwrap();
+    assert!(xml.contains("<testsuite name=\"dev\" tests=\"2\" failures=\"1\" errors=\"0\">"));
+    assert!(xml.contains("<failure type=\"unlisted\""));
+    run(&["verify"]).failure().stdout(predicate::str::contains(
+        "1 stanza(s) for keys no longer listed",
+    ));
+}
+
+#[test]
+fn verify_rejects_gpg_projects() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    vaultic()
+        .current_dir(dir.path())
+        .args(["init", "--yes"])
+        .assert()
+        .success();
+
+    vaultic()
+        .current_dir(dir.path())
+        .args(["--cipher", "gpg", "verify"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("reads age file headers"));
+}
+
+/// Edits to config.toml or a recipient list that bypass Vaultic fail
+/// verify until someone accepts them.
+#[test]
+fn verify_flags_recipient_edits_made_outside_vaultic() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let run = |args: &[&str]| vaultic().current_dir(dir.path()).args(args).assert();
+
+    run(&["init", "--no-keygen"]).success();
+    let alice = age::x25519::Identity::generate().to_public().to_string();
+    run(&["keys", "add", &alice]).success();
+    run(&["verify"]).success();
+
+    let mallory = age::x25519::Identity::generate().to_public().to_string();
+    let recipients = dir.path().join(".vaultic/recipients.txt");
+    let mut list = std::fs::read_to_string(&recipients).unwrap();
+    list.push_str(&format!("{mallory}\n"));
+    std::fs::write(&recipients, list).unwrap();
+
+    run(&["verify"])
+        .failure()
+        .stdout(predicate::str::contains(
+            "recipients.txt was modified outside Vaultic",
+        ))
+        .stderr(predicate::str::contains(
+            "1 file(s) in .vaultic/ were changed outside Vaultic",
+        ));
+    let output = run(&["status", "--json"]).get_output().stdout.clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(json["unrecorded_changes"][0]["file"], "recipients.txt");
+    assert_eq!(json["unrecorded_changes"][0]["change"], "modified");
+
+    run(&["verify", "--accept-changes"])
+        .success()
+        .stdout(predicate::str::contains("Recorded 1 change(s)"));
+    run(&["verify"]).success();
+    let log = std::fs::read_to_string(dir.path().join(".vaultic/audit.log")).unwrap();
+    assert!(log.contains("\"action\":\"manifest_accept\""), "{log}");
+}